@@ -84,6 +84,36 @@ db.links.createIndex(
     { name: "idx_links_kurum_id" }
 );
 
+// ============================================
+// api_keys koleksiyonu için index'ler
+// ============================================
+
+// key_hash için unique index (anahtar doğrulama)
+db.api_keys.createIndex(
+    { "key_hash": 1 },
+    { unique: true, name: "idx_api_keys_hash_unique" }
+);
+
+// ============================================
+// api_key_usage koleksiyonu için index'ler
+// ============================================
+
+// Anahtar başına günlük sayaç
+db.api_key_usage.createIndex(
+    { "key_id": 1, "day": 1 },
+    { unique: true, name: "idx_api_key_usage_key_day_unique" }
+);
+
+// ============================================
+// rate_limits koleksiyonu için index'ler (RATE_LIMIT_STORE=mongodb)
+// ============================================
+
+// Boşta kalan bucket'lar expires_at geçince silinir
+db.rate_limits.createIndex(
+    { "expires_at": 1 },
+    { expireAfterSeconds: 0, name: "idx_rate_limits_expires_at_ttl" }
+);
+
 // ============================================
 // Index Kullanımını Kontrol Etme
 // ============================================
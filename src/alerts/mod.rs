@@ -0,0 +1,471 @@
+pub mod smtp;
+pub mod target;
+
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use crate::config::PublicUrls;
+use crate::corpus::CorpusRegistry;
+use crate::dates;
+use crate::handlers::search::search_newest;
+use crate::repository::{
+    AlertHit, AlertWrite, DocumentRecord, Page, Repositories, RepositoryError, SavedSearchRecord, SavedSearchRun,
+    ScanCursor,
+};
+use crate::utils::format_timestamp;
+use crate::webhooks::{self, start_cursor, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER};
+use self::smtp::{Email, SmtpConfig};
+use self::target::PublicResolver;
+
+// Kayıtlı aramalar: her aktif arama belirli aralıklarla, son çalışmadan sonra eklenen belgeler
+// üzerinde arama ile aynı eşleştirme kurallarıyla yeniden çalıştırılır. Yeni eşleşmeler gelen
+// kutusuna bildirim olarak yazılır; kanal webhook ya da email ise ayrıca bir kez gönderilir
+// (gönderim sonucu bildirimde saklanır, yeniden deneme yapılmaz).
+
+pub const CHANNEL_INBOX: &str = "inbox";
+pub const CHANNEL_WEBHOOK: &str = "webhook";
+pub const CHANNEL_EMAIL: &str = "email";
+pub const CHANNELS: [&str; 3] = [CHANNEL_INBOX, CHANNEL_WEBHOOK, CHANNEL_EMAIL];
+
+pub const EVENT_SAVED_SEARCH_MATCHED: &str = "saved_search.matched";
+
+pub const NOTIFICATION_DELIVERED: &str = "delivered";
+pub const NOTIFICATION_FAILED: &str = "failed";
+
+const DEFAULT_INTERVAL_SECS: u64 = 900;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+// Yeni eşleşmeler en yeni önce bu boyutta sayfalarla okunur
+const PAGE_SIZE: u64 = 100;
+// Bildirimdeki en fazla eşleşme; fazlası sonraki çalışmada bildirilir
+const MAX_HITS: usize = 50;
+// E-posta gövdesindeki önizleme uzunluğu (karakter)
+const EMAIL_PREVIEW_CHARS: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlertConfig {
+    // None ise kayıtlı aramalar çalıştırılmaz
+    pub interval: Option<Duration>,
+    // Webhook gönderiminin zaman aşımı
+    pub timeout: Duration,
+    // None ise email kanalı kullanılamaz
+    pub smtp: Option<SmtpConfig>,
+    // İç ağ denetiminden muaf webhook sunucuları (küçük harf)
+    pub allowed_hosts: Vec<String>,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        AlertConfig {
+            interval: Some(Duration::from_secs(DEFAULT_INTERVAL_SECS)),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            smtp: None,
+            allowed_hosts: Vec::new(),
+        }
+    }
+}
+
+impl AlertConfig {
+    // SAVED_SEARCH_INTERVAL: saniye cinsinden çalışma aralığı; 0 kayıtlı arama işçisini kapatır.
+    // SAVED_SEARCH_WEBHOOK_ALLOWED_HOSTS: virgülle ayrılmış, iç ağda olsa da webhook gönderilebilecek
+    // sunucular. SMTP_* değişkenleri için smtp::SmtpConfig::from_env
+    pub fn from_env() -> Self {
+        let default = AlertConfig::default();
+        let interval = match env::var("SAVED_SEARCH_INTERVAL")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+        {
+            Some(0) => None,
+            Some(seconds) => Some(Duration::from_secs(seconds)),
+            None => default.interval,
+        };
+        let allowed_hosts = env::var("SAVED_SEARCH_WEBHOOK_ALLOWED_HOSTS")
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|host| host.trim().to_ascii_lowercase())
+                    .filter(|host| !host.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        AlertConfig {
+            interval,
+            smtp: SmtpConfig::from_env(),
+            allowed_hosts,
+            ..default
+        }
+    }
+}
+
+// Bildirim kanalına gönderilen içerik
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlertMessage {
+    pub alert_id: String,
+    pub search: SavedSearchRecord,
+    pub hits: Vec<AlertHit>,
+    pub created_at: String,
+}
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    // Hata metni bildirime yazılır
+    async fn notify(&self, message: &AlertMessage) -> Result<(), String>;
+}
+
+// Kayıtlı aramanın adresine, aramanın anahtarıyla imzalanmış JSON POST eder. İç ağ adreslerine
+// gönderim yapılmaz (bkz. target)
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    allowed_hosts: Vec<String>,
+}
+
+impl WebhookNotifier {
+    pub fn new(timeout: Duration, allowed_hosts: Vec<String>) -> Self {
+        WebhookNotifier {
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .redirect(reqwest::redirect::Policy::none())
+                .dns_resolver(Arc::new(PublicResolver::new(allowed_hosts.clone())))
+                .build()
+                .expect("HTTP client oluşturulamadı"),
+            allowed_hosts,
+        }
+    }
+}
+
+pub fn payload(message: &AlertMessage) -> String {
+    json!({
+        "id": message.alert_id,
+        "type": EVENT_SAVED_SEARCH_MATCHED,
+        "created_at": message.created_at,
+        "data": {
+            "saved_search_id": message.search.id,
+            "ad": message.search.ad,
+            "q": message.search.q,
+            "corpus": message.search.corpus,
+            "hits": message.hits.iter().map(|hit| json!({
+                "id": hit.id,
+                "pdf_adi": hit.pdf_adi,
+                "url_slug": hit.url_slug,
+                "url": hit.url,
+                "kurum_adi": hit.kurum_adi,
+                "belge_turu": hit.belge_turu,
+                "content_preview": hit.content_preview,
+                "olusturulma_tarihi": hit.olusturulma_tarihi,
+            })).collect::<Vec<_>>(),
+        },
+    })
+    .to_string()
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, message: &AlertMessage) -> Result<(), String> {
+        let url = message.search.hedef.as_deref().ok_or("Webhook adresi tanımlı değil")?;
+        target::check_url(url, &self.allowed_hosts)?;
+        let body = payload(message);
+        let secret = message.search.secret.as_deref().unwrap_or_default();
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(reqwest::header::USER_AGENT, "MevzuatGPT-Webhooks/1.0")
+            .header(SIGNATURE_HEADER, webhooks::signature_header(secret, Utc::now().timestamp(), &body))
+            .header(EVENT_HEADER, EVENT_SAVED_SEARCH_MATCHED)
+            .header(DELIVERY_HEADER, &message.alert_id)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("HTTP {}", response.status().as_u16()))
+        }
+    }
+}
+
+// Kayıtlı aramanın adresine düz metin e-posta gönderir
+pub struct EmailNotifier {
+    config: Option<SmtpConfig>,
+}
+
+impl EmailNotifier {
+    pub fn new(config: Option<SmtpConfig>) -> Self {
+        EmailNotifier { config }
+    }
+}
+
+fn preview(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.char_indices().nth(EMAIL_PREVIEW_CHARS) {
+        Some((index, _)) => format!("{}...", &text[..index]),
+        None => text,
+    }
+}
+
+pub fn email(message: &AlertMessage, to: &str) -> Email {
+    let mut body = format!(
+        "\"{}\" kayıtlı aramanız için {} yeni belge bulundu.\nSorgu: {}\n",
+        message.search.ad,
+        message.hits.len(),
+        message.search.q
+    );
+    for hit in &message.hits {
+        body.push_str(&format!("\n{}\n{}\n", hit.pdf_adi, hit.url));
+        if !hit.kurum_adi.is_empty() {
+            body.push_str(&format!("{}\n", hit.kurum_adi));
+        }
+        let content = preview(&hit.content_preview);
+        if !content.is_empty() {
+            body.push_str(&format!("{}\n", content));
+        }
+    }
+    Email {
+        to: to.to_string(),
+        subject: format!("MevzuatGPT: \"{}\" için {} yeni belge", message.search.ad, message.hits.len()),
+        body,
+    }
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    async fn notify(&self, message: &AlertMessage) -> Result<(), String> {
+        let config = self.config.as_ref().ok_or("SMTP sunucusu yapılandırılmamış (SMTP_HOST)")?;
+        let to = message.search.hedef.as_deref().ok_or("E-posta adresi tanımlı değil")?;
+        smtp::send(config, &email(message, to)).await
+    }
+}
+
+// Kanal başına bildirim gönderici; inbox için gönderim yoktur
+#[derive(Clone)]
+pub struct Notifiers {
+    pub webhook: Arc<dyn Notifier>,
+    pub email: Arc<dyn Notifier>,
+}
+
+impl Notifiers {
+    pub fn new(config: &AlertConfig) -> Self {
+        Notifiers {
+            webhook: Arc::new(WebhookNotifier::new(config.timeout, config.allowed_hosts.clone())),
+            email: Arc::new(EmailNotifier::new(config.smtp.clone())),
+        }
+    }
+
+    fn for_channel(&self, kanal: &str) -> Option<&dyn Notifier> {
+        match kanal {
+            CHANNEL_WEBHOOK => Some(self.webhook.as_ref()),
+            CHANNEL_EMAIL => Some(self.email.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+// Çalışmanın özeti
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunReport {
+    // Çalıştırılan aktif arama sayısı
+    pub searches: usize,
+    // Yazılan bildirim sayısı
+    pub alerts: usize,
+    // Webhook/e-posta ile gönderilemeyen bildirimler
+    pub failed: usize,
+    // Sorgusu çalıştırılamayan aramalar
+    pub errors: usize,
+}
+
+// Aramanın imleçten sonraki eşleşmeleri ve ilerletilmiş imleç
+async fn new_hits(
+    repos: &Repositories,
+    corpora: &CorpusRegistry,
+    urls: &PublicUrls,
+    search: &SavedSearchRecord,
+) -> Result<(Vec<AlertHit>, Option<ScanCursor>), String> {
+    let corpus = corpora
+        .get(&search.corpus)
+        .ok_or_else(|| format!("Corpus bulunamadı: {}", search.corpus))?;
+    let since = search
+        .cursor
+        .as_ref()
+        .and_then(|cursor| dates::parse_timestamp(&cursor.watermark).ok());
+
+    // İmleç yoksa ilk sayfa imleci kurmaya yeter; varsa imlecin gerisine inene dek okunur
+    let mut records = Vec::new();
+    for page in 0.. {
+        let batch = search_newest(
+            repos,
+            corpus,
+            &search.q,
+            search.kurum_id.as_deref(),
+            Page { offset: page * PAGE_SIZE, limit: PAGE_SIZE },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        let exhausted = (batch.len() as u64) < PAGE_SIZE;
+        let reached = |since: DateTime<Utc>| {
+            batch.last().is_some_and(|(record, _)| {
+                dates::parse_timestamp(&record.olusturulma_tarihi).is_ok_and(|time| time < since)
+            })
+        };
+        let done = exhausted || since.is_none_or(reached);
+        records.extend(batch);
+        if done {
+            break;
+        }
+    }
+
+    // Zaman format_timestamp ile normalize edilir; imleç diğer taramalar gibi (zaman, _id) sırasıyla ilerler
+    let mut keyed: Vec<_> = records
+        .into_iter()
+        .filter_map(|hit| {
+            let time = dates::parse_timestamp(&hit.0.olusturulma_tarihi).ok()?;
+            Some((format_timestamp(time), hit))
+        })
+        .collect();
+    keyed.sort_by(|(a_time, (a, _)), (b_time, (b, _))| a_time.cmp(b_time).then_with(|| a.id.cmp(&b.id)));
+    let Some(cursor) = &search.cursor else {
+        let start = start_cursor(keyed.iter().map(|(time, (record, _))| (time.as_str(), record.id.as_str())));
+        return Ok((Vec::new(), Some(start)));
+    };
+    let mut fresh: Vec<_> = keyed
+        .into_iter()
+        .filter(|(time, (record, _))| !cursor.covers(time, &record.id))
+        .collect();
+    let wanted = |record: &DocumentRecord| {
+        search
+            .belge_turu
+            .as_deref()
+            .is_none_or(|belge_turu| record.belge_turu.eq_ignore_ascii_case(belge_turu))
+    };
+    // Bildirime sığmayan eşleşmeler sonraki çalışmaya kalır: imleç son gönderilen eşleşmede durur
+    let overflow = fresh
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, (record, _)))| wanted(record))
+        .nth(MAX_HITS)
+        .map(|(index, _)| index);
+    if let Some(index) = overflow {
+        fresh.truncate(index);
+    }
+    let next = cursor.advance(fresh.iter().map(|(time, (record, _))| (time.as_str(), record.id.as_str())));
+    let next = (next != *cursor).then_some(next);
+    let hits = fresh
+        .into_iter()
+        .rev()
+        .map(|(_, hit)| hit)
+        .filter(|(record, _)| wanted(record))
+        .map(|(record, result)| AlertHit {
+            url: urls.document(corpus, &record.url_slug, &record.id),
+            id: result.id,
+            pdf_adi: result.pdf_adi,
+            url_slug: result.url_slug,
+            kurum_adi: result.kurum_adi,
+            belge_turu: record.belge_turu,
+            content_preview: result.content_preview,
+            olusturulma_tarihi: record.olusturulma_tarihi,
+        })
+        .collect();
+    Ok((hits, next))
+}
+
+// Aktif kayıtlı aramaları bir kez çalıştırır. İlk çalışmada yalnızca imleç kurulur; mevcut
+// belgeler bildirim olarak gönderilmez.
+pub async fn run(
+    repos: &Repositories,
+    corpora: &CorpusRegistry,
+    urls: &PublicUrls,
+    notifiers: &Notifiers,
+    now: DateTime<Utc>,
+) -> Result<RunReport, RepositoryError> {
+    let mut report = RunReport::default();
+    let run_at = format_timestamp(now);
+    for search in repos.saved_searches.list(None).await? {
+        if !search.aktif {
+            continue;
+        }
+        report.searches += 1;
+        let (hits, next) = match new_hits(repos, corpora, urls, &search).await {
+            Ok(found) => found,
+            Err(error) => {
+                report.errors += 1;
+                log::warn!("Kayıtlı arama çalıştırılamadı: {} ({}): {}", search.id, search.owner, error);
+                let run = SavedSearchRun {
+                    cursor: search.cursor.clone(),
+                    run_at: run_at.clone(),
+                    error: Some(error),
+                };
+                repos.saved_searches.record_run(&search.id, &run).await?;
+                continue;
+            }
+        };
+
+        if !hits.is_empty() {
+            let alert = AlertWrite {
+                saved_search_id: search.id.clone(),
+                owner: search.owner.clone(),
+                ad: search.ad.clone(),
+                q: search.q.clone(),
+                hits: hits.clone(),
+                kanal: search.kanal.clone(),
+                created_at: run_at.clone(),
+            };
+            let alert_id = repos.saved_searches.insert_alert(&alert).await?;
+            report.alerts += 1;
+            if let Some(notifier) = notifiers.for_channel(&search.kanal) {
+                let message = AlertMessage {
+                    alert_id: alert_id.clone(),
+                    search: search.clone(),
+                    hits,
+                    created_at: run_at.clone(),
+                };
+                let result = notifier.notify(&message).await;
+                if let Err(error) = &result {
+                    report.failed += 1;
+                    log::warn!("Kayıtlı arama bildirimi gönderilemedi: {} ({}): {}", alert_id, search.kanal, error);
+                }
+                let status = if result.is_ok() { NOTIFICATION_DELIVERED } else { NOTIFICATION_FAILED };
+                repos
+                    .saved_searches
+                    .record_notification(&alert_id, status, result.err().as_deref())
+                    .await?;
+            }
+        }
+
+        let run = SavedSearchRun {
+            cursor: next.or(search.cursor),
+            run_at: run_at.clone(),
+            error: None,
+        };
+        repos.saved_searches.record_run(&search.id, &run).await?;
+    }
+    Ok(report)
+}
+
+pub fn spawn(
+    repos: Repositories,
+    corpora: CorpusRegistry,
+    urls: PublicUrls,
+    notifiers: Notifiers,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match run(&repos, &corpora, &urls, &notifiers, Utc::now()).await {
+                Ok(report) if report.alerts > 0 || report.errors > 0 => log::info!(
+                    "Kayıtlı aramalar: {} arama, {} bildirim ({} gönderilemedi), {} hata",
+                    report.searches,
+                    report.alerts,
+                    report.failed,
+                    report.errors
+                ),
+                Ok(_) => {}
+                Err(e) => log::error!("Kayıtlı aramalar çalıştırılamadı: {}", e),
+            }
+        }
+    })
+}
@@ -0,0 +1,178 @@
+use std::env;
+use std::time::Duration;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::Utc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+// Yalın SMTP istemcisi (EHLO, MAIL FROM, RCPT TO, DATA, QUIT). TLS ve kimlik doğrulama yapılmaz;
+// bildirimler aynı makinedeki ya da iç ağdaki bir relay'e (Postfix, MailHog vb.) bırakılır,
+// dış dünyaya gönderim relay'in işidir.
+
+const DEFAULT_PORT: u16 = 25;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_FROM: &str = "bildirim@mevzuatgpt.org";
+const SENDER_NAME: &str = "MevzuatGPT";
+const HELO_NAME: &str = "mevzuatgpt.org";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    // Zarf ve From başlığındaki adres
+    pub from: String,
+    // Bağlantı ve her yanıt için zaman aşımı
+    pub timeout: Duration,
+}
+
+impl SmtpConfig {
+    // SMTP_HOST verilmezse None (e-posta kanalındaki bildirimler hata ile kaydedilir).
+    // SMTP_PORT (varsayılan 25), SMTP_FROM, SMTP_TIMEOUT (saniye)
+    pub fn from_env() -> Option<Self> {
+        let host = env::var("SMTP_HOST").ok().map(|host| host.trim().to_string()).filter(|host| !host.is_empty())?;
+        Some(SmtpConfig {
+            host,
+            port: env::var("SMTP_PORT")
+                .ok()
+                .and_then(|port| port.trim().parse().ok())
+                .unwrap_or(DEFAULT_PORT),
+            from: env::var("SMTP_FROM")
+                .ok()
+                .map(|from| from.trim().to_string())
+                .filter(|from| is_email(from))
+                .unwrap_or_else(|| DEFAULT_FROM.to_string()),
+            timeout: env::var("SMTP_TIMEOUT")
+                .ok()
+                .and_then(|seconds| seconds.trim().parse::<u64>().ok())
+                .filter(|seconds| *seconds > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    // Düz metin gövde
+    pub body: String,
+}
+
+// Zarf adresi olarak kullanılabilecek yalın adres (ad <adres> biçimi kabul edilmez)
+pub fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !domain.contains('@')
+        && value.chars().all(|c| c.is_ascii_graphic() && !matches!(c, '<' | '>' | ',' | ';' | '"'))
+}
+
+// RFC 2047 B kodlaması; başlıkta ASCII dışı karakterler için
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", STANDARD.encode(value))
+    }
+}
+
+// Başlıklar ve base64 kodlanmış UTF-8 gövde (satırlar CRLF ile, en fazla 76 karakter)
+pub fn message(from: &str, email: &Email) -> String {
+    let body = STANDARD.encode(email.body.replace("\r\n", "\n").replace('\n', "\r\n"));
+    let mut lines = vec![
+        format!("From: {} <{}>", SENDER_NAME, from),
+        format!("To: <{}>", email.to),
+        format!("Subject: {}", encode_header(&email.subject.replace(['\r', '\n'], " "))),
+        format!("Date: {}", Utc::now().to_rfc2822()),
+        format!("Message-ID: <{}@{}>", uuid::Uuid::new_v4(), HELO_NAME),
+        "MIME-Version: 1.0".to_string(),
+        "Content-Type: text/plain; charset=utf-8".to_string(),
+        "Content-Transfer-Encoding: base64".to_string(),
+        String::new(),
+    ];
+    lines.extend(
+        body.as_bytes()
+            .chunks(76)
+            .map(|chunk| String::from_utf8_lossy(chunk).into_owned()),
+    );
+    lines.join("\r\n")
+}
+
+struct Connection {
+    reader: BufReader<TcpStream>,
+    timeout: Duration,
+}
+
+impl Connection {
+    // Çok satırlı yanıtın ("250-...", "250 ...") kodunu ve son satırını okur
+    async fn reply(&mut self) -> Result<(u16, String), String> {
+        loop {
+            let mut line = String::new();
+            let read = tokio::time::timeout(self.timeout, self.reader.read_line(&mut line))
+                .await
+                .map_err(|_| "SMTP yanıtı zaman aşımına uğradı".to_string())?
+                .map_err(|e| format!("SMTP yanıtı okunamadı: {}", e))?;
+            if read == 0 {
+                return Err("SMTP bağlantısı kapandı".to_string());
+            }
+            let line = line.trim_end().to_string();
+            let code = line
+                .get(..3)
+                .and_then(|code| code.parse::<u16>().ok())
+                .ok_or_else(|| format!("Geçersiz SMTP yanıtı: {}", line))?;
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok((code, line));
+            }
+        }
+    }
+
+    async fn expect(&mut self, accepted: &[u16]) -> Result<(), String> {
+        let (code, line) = self.reply().await?;
+        if accepted.contains(&code) {
+            Ok(())
+        } else {
+            Err(format!("SMTP sunucusu reddetti: {}", line))
+        }
+    }
+
+    async fn command(&mut self, command: &str, accepted: &[u16]) -> Result<(), String> {
+        let stream = self.reader.get_mut();
+        tokio::time::timeout(self.timeout, stream.write_all(format!("{}\r\n", command).as_bytes()))
+            .await
+            .map_err(|_| "SMTP komutu zaman aşımına uğradı".to_string())?
+            .map_err(|e| format!("SMTP komutu gönderilemedi: {}", e))?;
+        self.expect(accepted).await
+    }
+}
+
+pub async fn send(config: &SmtpConfig, email: &Email) -> Result<(), String> {
+    if !is_email(&email.to) {
+        return Err(format!("Geçersiz alıcı adresi: {}", email.to));
+    }
+    let stream = tokio::time::timeout(config.timeout, TcpStream::connect((config.host.as_str(), config.port)))
+        .await
+        .map_err(|_| format!("SMTP sunucusuna bağlanılamadı: {}:{} zaman aşımı", config.host, config.port))?
+        .map_err(|e| format!("SMTP sunucusuna bağlanılamadı: {}:{}: {}", config.host, config.port, e))?;
+    let mut connection = Connection {
+        reader: BufReader::new(stream),
+        timeout: config.timeout,
+    };
+
+    connection.expect(&[220]).await?;
+    connection.command(&format!("EHLO {}", HELO_NAME), &[250]).await?;
+    connection.command(&format!("MAIL FROM:<{}>", config.from), &[250]).await?;
+    connection.command(&format!("RCPT TO:<{}>", email.to), &[250, 251]).await?;
+    connection.command("DATA", &[354]).await?;
+    // Gövde base64 olduğundan nokta ile başlayan satır oluşmaz
+    let data = format!("{}\r\n.", message(&config.from, email));
+    connection.command(&data, &[250]).await?;
+    // Mesaj kabul edildi; QUIT yanıtı beklenmese de olur
+    let _ = connection.command("QUIT", &[221]).await;
+    Ok(())
+}
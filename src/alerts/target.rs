@@ -0,0 +1,123 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use url::{Host, Url};
+
+// Kayıtlı arama webhook'larının hedef denetimi: kullanıcının verdiği adres sunucunun iç ağına
+// (loopback, özel, link-local, unique-local; ör. MongoDB ya da bulut metadata servisi) istek
+// attırmak için kullanılamaz. Adres kayıtta biçimiyle, gönderimde ise çözümlenen IP'siyle yeniden
+// denetlenir; böylece sonradan iç adrese çözülen alan adları da reddedilir. İzin listesindeki
+// sunucular (SAVED_SEARCH_WEBHOOK_ALLOWED_HOSTS) bu denetimden muaftır.
+
+// İç ağ adı olarak kullanılan son ekler
+const INTERNAL_SUFFIXES: [&str; 4] = [".localhost", ".local", ".internal", ".localdomain"];
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // 0.0.0.0/8, 100.64.0.0/10 (CGNAT), 192.0.0.0/24, 198.18.0.0/15, 240.0.0.0/4
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (b == 18 || b == 19))
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return is_public_ipv4(v4);
+    }
+    let segments = ip.segments();
+    // 64:ff9b::/96 (NAT64) gömülü IPv4 adresine göre denetlenir
+    if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        let [a, b] = segments[6].to_be_bytes();
+        let [c, d] = segments[7].to_be_bytes();
+        return is_public_ipv4(Ipv4Addr::new(a, b, c, d));
+    }
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // fc00::/7 (unique-local), fe80::/10 (link-local), 2001:db8::/32 (dokümantasyon)
+        || (segments[0] & 0xfe00) == 0xfc00
+        || (segments[0] & 0xffc0) == 0xfe80
+        || (segments[0] == 0x2001 && segments[1] == 0xdb8))
+}
+
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+fn is_allowed(host: &str, allowed_hosts: &[String]) -> bool {
+    allowed_hosts.iter().any(|allowed| allowed.eq_ignore_ascii_case(host))
+}
+
+// Adresin biçimsel denetimi: IP adresi ise herkese açık olmalı, alan adı ise localhost ya da
+// iç ağ adı olmamalı. Hata metni gönderimde bildirime yazılır
+pub fn check_url(value: &str, allowed_hosts: &[String]) -> Result<(), String> {
+    let url = Url::parse(value).map_err(|e| e.to_string())?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Desteklenmeyen şema: {}", url.scheme()));
+    }
+    let host = url.host_str().unwrap_or_default().trim_start_matches('[').trim_end_matches(']');
+    if is_allowed(host, allowed_hosts) {
+        return Ok(());
+    }
+    let internal = match url.host() {
+        None => true,
+        Some(Host::Ipv4(ip)) => !is_public_ipv4(ip),
+        Some(Host::Ipv6(ip)) => !is_public_ipv6(ip),
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost"
+                || !domain.contains('.')
+                || INTERNAL_SUFFIXES.iter().any(|suffix| domain.ends_with(suffix))
+        }
+    };
+    if internal {
+        Err(format!("İç ağ adresine gönderim yapılamaz: {}", host))
+    } else {
+        Ok(())
+    }
+}
+
+// Çözümlenen adreslerden herkese açık olmayanları reddeden DNS çözümleyici; gönderim
+// istemcisine takılır ve yönlendirmeler kapalı olduğundan tek bağlantı noktası budur
+pub struct PublicResolver {
+    allowed_hosts: Arc<Vec<String>>,
+}
+
+impl PublicResolver {
+    pub fn new(allowed_hosts: Vec<String>) -> Self {
+        PublicResolver {
+            allowed_hosts: Arc::new(allowed_hosts),
+        }
+    }
+}
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let allowed_hosts = self.allowed_hosts.clone();
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0)).await?.collect();
+            if is_allowed(host, &allowed_hosts) {
+                return Ok(Box::new(addrs.into_iter()) as Addrs);
+            }
+            let public: Vec<SocketAddr> = addrs.into_iter().filter(|addr| is_public_ip(addr.ip())).collect();
+            if public.is_empty() {
+                return Err(format!("{} iç ağ adresine çözümlendi", host).into());
+            }
+            Ok(Box::new(public.into_iter()) as Addrs)
+        })
+    }
+}
@@ -0,0 +1,187 @@
+pub mod stream;
+
+use std::collections::HashMap;
+use actix_web::{HttpMessage, HttpRequest};
+use chrono::Utc;
+use mongodb::bson::{oid::ObjectId, Bson, Document as MongoDocument};
+use crate::auth::ApiKeyIdentity;
+use crate::repository::records::id_string;
+use crate::repository::{AuditChange, AuditRecord};
+pub use crate::utils::format_timestamp;
+
+// Denetim kaydı (audit_log): yönetim API'si, CLI ve MongoDB change stream'i üzerinden
+// metadata, content, kurumlar, kurum_duyuru ve links koleksiyonlarındaki değişiklikler.
+// API ve CLI yazmaları kaydı repository katmanında, veriyle aynı işlemde yazar; change
+// stream yalnızca bu yollardan geçmeyen (script, elle yapılan) değişiklikleri ekler.
+
+pub const AUDIT_COLLECTION: &str = "audit_log";
+// Change stream olaylarıyla eşleştirilmiş API/CLI kayıtları; _id denetim kaydının _id'sidir
+pub const AUDIT_CLAIMS_COLLECTION: &str = "audit_claims";
+
+// Change stream'in izlediği koleksiyonlar
+pub const AUDITED_COLLECTIONS: [&str; 5] = ["metadata", "content", "kurumlar", "kurum_duyuru", "links"];
+
+// Bu uzunluğu aşan string değerler (örn. belge metni) kısaltılarak saklanır
+const MAX_VALUE_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+impl AuditAction {
+    pub const ALL: [AuditAction; 3] = [AuditAction::Create, AuditAction::Update, AuditAction::Delete];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.as_str() == value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditSource {
+    Api,
+    Cli,
+    ChangeStream,
+}
+
+impl AuditSource {
+    pub const ALL: [AuditSource; 3] = [AuditSource::Api, AuditSource::Cli, AuditSource::ChangeStream];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditSource::Api => "api",
+            AuditSource::Cli => "cli",
+            AuditSource::ChangeStream => "change_stream",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|source| source.as_str() == value)
+    }
+}
+
+// Yazma işlemini yapan taraf; repository'nin yazma metotlarına verilir
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Actor {
+    pub source: AuditSource,
+    pub name: Option<String>,
+}
+
+impl Actor {
+    // İsteği yapan API anahtarının prefix'i
+    pub fn from_request(req: &HttpRequest) -> Self {
+        Actor {
+            source: AuditSource::Api,
+            name: req.extensions().get::<ApiKeyIdentity>().map(|identity| identity.prefix.clone()),
+        }
+    }
+
+    // Komut satırı: işletim sistemi kullanıcısı biliniyorsa o
+    pub fn cli() -> Self {
+        Actor {
+            source: AuditSource::Cli,
+            name: std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok(),
+        }
+    }
+}
+
+fn truncated(value: &Bson) -> Bson {
+    match value {
+        Bson::String(text) if text.chars().count() > MAX_VALUE_CHARS => {
+            Bson::String(format!("{}…", text.chars().take(MAX_VALUE_CHARS).collect::<String>()))
+        }
+        other => other.clone(),
+    }
+}
+
+// İki doküman arasındaki üst düzey alan farkları (_id hariç)
+pub fn diff(before: Option<&MongoDocument>, after: Option<&MongoDocument>) -> Vec<AuditChange> {
+    let mut fields: Vec<&str> = Vec::new();
+    for doc in [before, after].into_iter().flatten() {
+        for field in doc.keys() {
+            if field != "_id" && !fields.contains(&field.as_str()) {
+                fields.push(field);
+            }
+        }
+    }
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let old = before.and_then(|doc| doc.get(field));
+            let new = after.and_then(|doc| doc.get(field));
+            (old != new).then(|| AuditChange {
+                field: field.to_string(),
+                before: old.map(truncated),
+                after: new.map(truncated),
+            })
+        })
+        .collect()
+}
+
+pub fn entry(
+    actor: &Actor,
+    action: AuditAction,
+    collection: &str,
+    target_id: &str,
+    changes: Vec<AuditChange>,
+) -> AuditRecord {
+    AuditRecord {
+        id: ObjectId::new().to_hex(),
+        timestamp: format_timestamp(Utc::now()),
+        actor: actor.name.clone(),
+        source: actor.source.as_str().to_string(),
+        action: action.as_str().to_string(),
+        collection: collection.to_string(),
+        target_id: target_id.to_string(),
+        changes,
+    }
+}
+
+// Yazma öncesi ve sonrası dokümanlardan denetim kayıtları. Dokümanlar koleksiyon ve
+// _id ile eşleştirilir; değişmeyenler için kayıt oluşturulmaz.
+pub fn entries(
+    actor: &Actor,
+    before: &[(String, MongoDocument)],
+    after: &[(String, MongoDocument)],
+) -> Vec<AuditRecord> {
+    let index = |docs: &[(String, MongoDocument)]| -> HashMap<(String, String), usize> {
+        docs.iter()
+            .enumerate()
+            .map(|(position, (collection, doc))| ((collection.clone(), id_string(doc, "_id")), position))
+            .collect()
+    };
+    let before_index = index(before);
+    let after_index = index(after);
+
+    let mut records = Vec::new();
+    for (collection, old) in before {
+        let id = id_string(old, "_id");
+        match after_index.get(&(collection.clone(), id.clone())) {
+            Some(&position) => {
+                let changes = diff(Some(old), Some(&after[position].1));
+                if !changes.is_empty() {
+                    records.push(entry(actor, AuditAction::Update, collection, &id, changes));
+                }
+            }
+            None => records.push(entry(actor, AuditAction::Delete, collection, &id, diff(Some(old), None))),
+        }
+    }
+    for (collection, new) in after {
+        let id = id_string(new, "_id");
+        if !before_index.contains_key(&(collection.clone(), id.clone())) {
+            records.push(entry(actor, AuditAction::Create, collection, &id, diff(None, Some(new))));
+        }
+    }
+    records
+}
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use futures::StreamExt;
+use mongodb::bson::{doc, Bson, Document as MongoDocument};
+use mongodb::change_stream::event::{ChangeStreamEvent, OperationType, ResumeToken};
+use mongodb::options::{ChangeStreamOptions, FullDocumentBeforeChangeType, FullDocumentType};
+use mongodb::Database;
+use sha2::{Digest, Sha256};
+use crate::repository::records::id_string;
+use crate::repository::{AuditChange, AuditFilter, AuditRecord, AuditRepository, Page};
+use super::{diff, format_timestamp, truncated, AuditAction, AuditSource, AUDITED_COLLECTIONS};
+
+// MongoDB change stream'inden denetim kaydı. API ve CLI yazmaları kaydı zaten veriyle aynı
+// transaction'da yazdığından, olayla eşleşen bir kayıt varsa olay atlanır; kalanlar
+// (script'ler, elle yapılan değişiklikler) actor'sız olarak eklenir. Change stream yalnızca
+// replica set'lerde çalışır. Pre-image'ları açık koleksiyonlarda (changeStreamPreAndPostImages)
+// önceki değerler de kaydedilir.
+
+// Son işlenen olayın resume token'ı; yeniden başlatmada kalınan yerden devam edilir
+const STATE_COLLECTION: &str = "audit_state";
+const STATE_ID: &str = "change_stream";
+
+// Olay zamanından bu kadar önceye kadar yazılmış API kayıtları olayla eşleştirilir
+const MATCH_WINDOW: chrono::Duration = chrono::Duration::seconds(30);
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+
+pub fn spawn(db: Database, audit: Arc<dyn AuditRepository>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let watcher = Watcher { db, audit };
+        loop {
+            match watcher.run().await {
+                Ok(()) => log::warn!("Denetim change stream'i kapandı, yeniden açılıyor"),
+                Err(e) if change_streams_unsupported(&e) => {
+                    log::warn!("MongoDB change stream desteklemiyor (replica set değil); yalnızca API ve CLI yazmaları denetlenecek");
+                    return;
+                }
+                Err(e) => log::error!("Denetim change stream hatası: {}", e),
+            }
+            tokio::time::sleep(RETRY_DELAY).await;
+        }
+    })
+}
+
+struct Watcher {
+    db: Database,
+    audit: Arc<dyn AuditRepository>,
+}
+
+impl Watcher {
+    async fn run(&self) -> mongodb::error::Result<()> {
+        let options = ChangeStreamOptions::builder()
+            .full_document(Some(FullDocumentType::UpdateLookup))
+            .full_document_before_change(Some(FullDocumentBeforeChangeType::WhenAvailable))
+            .resume_after(self.resume_token().await)
+            .build();
+        let pipeline = [doc! { "$match": {
+            "ns.coll": { "$in": AUDITED_COLLECTIONS.to_vec() },
+            "operationType": { "$in": ["insert", "update", "replace", "delete"] },
+        } }];
+
+        let mut stream = self.db.watch(pipeline, options).await?;
+        log::info!("Denetim change stream'i dinleniyor: {}", AUDITED_COLLECTIONS.join(", "));
+        while let Some(event) = stream.next().await {
+            let event = event?;
+            let token = event.id.clone();
+            if let Err(e) = self.handle(event).await {
+                log::error!("Change stream olayı denetim kaydına yazılamadı: {}", e);
+            }
+            self.save_resume_token(&token).await;
+        }
+        Ok(())
+    }
+
+    async fn resume_token(&self) -> Option<ResumeToken> {
+        let state = self
+            .db
+            .collection::<MongoDocument>(STATE_COLLECTION)
+            .find_one(doc! { "_id": STATE_ID }, None)
+            .await
+            .ok()??;
+        mongodb::bson::from_bson(state.get("resume_token")?.clone()).ok()
+    }
+
+    async fn save_resume_token(&self, token: &ResumeToken) {
+        let Ok(token) = mongodb::bson::to_bson(token) else {
+            return;
+        };
+        let result = self
+            .db
+            .collection::<MongoDocument>(STATE_COLLECTION)
+            .update_one(
+                doc! { "_id": STATE_ID },
+                doc! { "$set": { "resume_token": token } },
+                mongodb::options::UpdateOptions::builder().upsert(true).build(),
+            )
+            .await;
+        if let Err(e) = result {
+            log::warn!("Change stream resume token'ı kaydedilemedi: {}", e);
+        }
+    }
+
+    async fn handle(&self, event: ChangeStreamEvent<MongoDocument>) -> crate::repository::RepositoryResult<()> {
+        let Some(record) = record_for(&event) else {
+            return Ok(());
+        };
+        if self.claim_api_record(&record).await? {
+            return Ok(());
+        }
+        // Birden çok sunucu aynı olayı işlerse _id (resume token'dan türetilir) çakışır
+        self.audit.record(&record).await?;
+        Ok(())
+    }
+
+    // Olayı aynı hedefe yazılmış ve henüz eşleştirilmemiş bir API/CLI kaydıyla eşleştirir.
+    // Eşleştirme audit_claims'e yazılır; yeniden başlatmada ya da birden çok sunucuda her kayıt
+    // yine tek olayı karşılar
+    async fn claim_api_record(&self, record: &AuditRecord) -> crate::repository::RepositoryResult<bool> {
+        let from = chrono::DateTime::parse_from_rfc3339(&record.timestamp)
+            .map(|time| time.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now())
+            - MATCH_WINDOW;
+        let filter = AuditFilter {
+            action: Some(record.action.clone()),
+            collection: Some(record.collection.clone()),
+            target_id: Some(record.target_id.clone()),
+            from: Some(format_timestamp(from)),
+            ..AuditFilter::default()
+        };
+        let candidates = self.audit.list(&filter, Page { offset: 0, limit: 50 }).await?;
+
+        // En eski eşleşmeyen kayıt; olaylar yazma sırasıyla gelir
+        for candidate in candidates
+            .iter()
+            .rev()
+            .filter(|candidate| candidate.source != AuditSource::ChangeStream.as_str())
+        {
+            if self.audit.claim(&candidate.id, &record.id).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+// Change stream olayından actor'sız denetim kaydı
+fn record_for(event: &ChangeStreamEvent<MongoDocument>) -> Option<AuditRecord> {
+    let action = match event.operation_type {
+        OperationType::Insert => AuditAction::Create,
+        OperationType::Update | OperationType::Replace => AuditAction::Update,
+        OperationType::Delete => AuditAction::Delete,
+        _ => return None,
+    };
+    let collection = event.ns.as_ref()?.coll.clone()?;
+    let target_id = id_string(event.document_key.as_ref()?, "_id");
+
+    let before = event.full_document_before_change.as_ref();
+    let after = event.full_document.as_ref().filter(|_| action != AuditAction::Delete);
+    let changes = match (&event.update_description, before) {
+        // Pre-image yoksa güncellenen alanlar önceki değerleri olmadan yazılır
+        (Some(update), None) => update
+            .updated_fields
+            .iter()
+            .map(|(field, value)| AuditChange { field: field.clone(), before: None, after: Some(truncated(value)) })
+            .chain(update.removed_fields.iter().map(|field| AuditChange {
+                field: field.clone(),
+                before: None,
+                after: None,
+            }))
+            .collect(),
+        _ => diff(before, after),
+    };
+
+    let timestamp = event
+        .wall_time
+        .and_then(|time| chrono::DateTime::from_timestamp_millis(time.timestamp_millis()))
+        .unwrap_or_else(Utc::now);
+    let token = mongodb::bson::to_bson(&event.id).unwrap_or(Bson::Null).to_string();
+
+    Some(AuditRecord {
+        id: hex::encode(&Sha256::digest(token.as_bytes())[..12]),
+        timestamp: format_timestamp(timestamp),
+        actor: None,
+        source: AuditSource::ChangeStream.as_str().to_string(),
+        action: action.as_str().to_string(),
+        collection,
+        target_id,
+        changes,
+    })
+}
+
+// Tekil sunucu: "The $changeStream stage is only supported on replica sets" (kod 40573)
+pub(crate) fn change_streams_unsupported(e: &mongodb::error::Error) -> bool {
+    matches!(e.kind.as_ref(), mongodb::error::ErrorKind::Command(command) if command.code == 40573)
+}
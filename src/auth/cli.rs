@@ -0,0 +1,140 @@
+use crate::repository::ApiKeyRepository;
+use crate::utils::format_timestamp;
+use super::{create_key, today, Scope};
+
+// Anahtar yönetimi komutları:
+//   --create-api-key <ad> [--scopes read,search] [--daily-quota 1000]
+//   --revoke-api-key <id|prefix>
+//   --list-api-keys
+#[derive(Debug, PartialEq, Eq)]
+pub enum KeyCommand {
+    Create {
+        name: String,
+        scopes: Vec<Scope>,
+        daily_quota: Option<u64>,
+    },
+    Revoke(String),
+    List,
+}
+
+const DEFAULT_SCOPES: &[Scope] = &[Scope::Read, Scope::Search];
+
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .map(|value| value.as_str())
+}
+
+impl KeyCommand {
+    // Anahtar komutu verilmemişse None döner
+    pub fn from_args(args: &[String]) -> Option<Result<Self, String>> {
+        if args.iter().any(|arg| arg == "--list-api-keys") {
+            return Some(Ok(KeyCommand::List));
+        }
+
+        if args.iter().any(|arg| arg == "--revoke-api-key") {
+            return Some(
+                arg_value(args, "--revoke-api-key")
+                    .map(|id| KeyCommand::Revoke(id.to_string()))
+                    .ok_or_else(|| "--revoke-api-key için anahtar id'si ya da prefix'i gerekli".to_string()),
+            );
+        }
+
+        if args.iter().any(|arg| arg == "--create-api-key") {
+            return Some(Self::parse_create(args));
+        }
+
+        None
+    }
+
+    fn parse_create(args: &[String]) -> Result<Self, String> {
+        let name = arg_value(args, "--create-api-key")
+            .filter(|name| !name.starts_with("--"))
+            .ok_or("--create-api-key için anahtar adı gerekli")?;
+
+        let scopes = match arg_value(args, "--scopes") {
+            Some(value) => value
+                .split(',')
+                .filter(|scope| !scope.trim().is_empty())
+                .map(|scope| Scope::parse(scope).ok_or(format!("Bilinmeyen scope: {}", scope.trim())))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => DEFAULT_SCOPES.to_vec(),
+        };
+        if scopes.is_empty() {
+            return Err("En az bir scope belirtilmeli".to_string());
+        }
+
+        let daily_quota = arg_value(args, "--daily-quota")
+            .map(|value| {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| format!("Geçersiz --daily-quota değeri: {}", value))
+            })
+            .transpose()?;
+
+        Ok(KeyCommand::Create {
+            name: name.to_string(),
+            scopes,
+            daily_quota,
+        })
+    }
+}
+
+pub async fn run(command: KeyCommand, repo: &dyn ApiKeyRepository) -> Result<(), String> {
+    match command {
+        KeyCommand::Create { name, scopes, daily_quota } => {
+            let (key, record) = create_key(repo, &name, &scopes, daily_quota)
+                .await
+                .map_err(|e| format!("API anahtarı oluşturulamadı: {}", e))?;
+
+            println!("API anahtarı oluşturuldu: {} ({})", record.name, record.id);
+            println!("Scope'lar: {}", record.scopes.join(","));
+            match record.daily_quota {
+                Some(quota) => println!("Günlük kota: {}", quota),
+                None => println!("Günlük kota: sınırsız"),
+            }
+            println!();
+            println!("{}", key);
+            println!();
+            println!("Bu anahtar bir daha gösterilmeyecek; güvenli bir yerde saklayın.");
+        }
+        KeyCommand::Revoke(id_or_prefix) => {
+            let revoked = repo
+                .revoke(&id_or_prefix, &format_timestamp(chrono::Utc::now()))
+                .await
+                .map_err(|e| format!("API anahtarı iptal edilemedi: {}", e))?;
+            if !revoked {
+                return Err(format!("Aktif API anahtarı bulunamadı: {}", id_or_prefix));
+            }
+            println!("API anahtarı iptal edildi: {}", id_or_prefix);
+        }
+        KeyCommand::List => {
+            let keys = repo
+                .list()
+                .await
+                .map_err(|e| format!("API anahtarları alınamadı: {}", e))?;
+            let day = today();
+
+            for key in keys {
+                let used = repo.usage(&key.id, &day).await.unwrap_or(0);
+                let quota = key
+                    .daily_quota
+                    .map(|quota| quota.to_string())
+                    .unwrap_or_else(|| "sınırsız".to_string());
+                let status = if key.is_revoked() { "iptal" } else { "aktif" };
+                println!(
+                    "{}  {}  {}  [{}]  bugün {}/{}  {}",
+                    key.prefix,
+                    key.id,
+                    key.name,
+                    key.scopes.join(","),
+                    used,
+                    quota,
+                    status
+                );
+            }
+        }
+    }
+    Ok(())
+}
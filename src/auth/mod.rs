@@ -0,0 +1,239 @@
+pub mod cli;
+
+use std::env;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use crate::config::AppState;
+use crate::error::{ApiError, Resource};
+use crate::repository::{ApiKeyRecord, ApiKeyRepository, RepositoryResult};
+use crate::utils::format_timestamp;
+
+// API anahtarları "mgpt_" + 40 hex karakterden oluşur; veritabanında yalnızca
+// SHA-256 özeti ve listeleme için kısa bir prefix saklanır
+pub const KEY_PREFIX: &str = "mgpt_";
+const DISPLAY_PREFIX_LEN: usize = KEY_PREFIX.len() + 8;
+
+// Anahtar olmadan da erişilebilen varsayılan route'lar (API_PUBLIC_ROUTES ile değiştirilebilir)
+const DEFAULT_PUBLIC_ROUTES: &[&str] = &[
+    "/api/health",
+    "/api/openapi.json",
+    "/api/docs",
+    "/api/v1/sitemap",
+    "/sitemap.xml",
+    "/sitemaps",
+    "/robots.txt",
+    "/indexnow-key.txt",
+    "/feeds",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    // Belge, kurum, istatistik gibi okuma endpoint'leri
+    Read,
+    // Tam metin arama (regex taraması yapan pahalı sorgular)
+    Search,
+    // Canlı duyuru çekme (dış sitelere istek atar)
+    Scrape,
+    // Yönetim API'si (/api/admin/...); diğer tüm scope'ları da kapsar
+    Admin,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 4] = [Scope::Read, Scope::Search, Scope::Scrape, Scope::Admin];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scope::Read => "read",
+            Scope::Search => "search",
+            Scope::Scrape => "scrape",
+            Scope::Admin => "admin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Scope::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == value.trim().to_ascii_lowercase())
+    }
+
+    // İstek yolunun gerektirdiği scope; route önekine göre belirlenir, böylece "admin" ya da
+    // "search" adlı bir slug yönetim/arama scope'u istemez
+    pub fn for_path(path: &str) -> Self {
+        let under = |prefix: &str| {
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        };
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        if under("/api/admin") {
+            Scope::Admin
+        } else if under("/api/v1/search")
+            || under("/api/v2/search")
+            || matches!(segments.as_slice(), ["api", "v1", "corpora", _, "search"])
+        {
+            Scope::Search
+        } else if under("/api/v1/kurum-duyuru") {
+            Scope::Scrape
+        } else {
+            Scope::Read
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    // false ise anahtarsız istekler her route'a erişebilir; gönderilen anahtarlar
+    // yine de doğrulanır ve kotaya sayılır
+    pub required: bool,
+    pub public_routes: Vec<String>,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        AuthConfig {
+            required: false,
+            public_routes: DEFAULT_PUBLIC_ROUTES.iter().map(|route| route.to_string()).collect(),
+        }
+    }
+}
+
+impl AuthConfig {
+    pub fn from_env() -> Self {
+        let mut config = AuthConfig {
+            required: env::var("API_AUTH_REQUIRED")
+                .map(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(false),
+            ..AuthConfig::default()
+        };
+        if let Ok(routes) = env::var("API_PUBLIC_ROUTES") {
+            config.public_routes = routes
+                .split(',')
+                .map(|route| route.trim().trim_end_matches('/').to_string())
+                .filter(|route| !route.is_empty())
+                .collect();
+        }
+        config
+    }
+
+    // Route kendisi ya da alt yolu listede ise herkese açıktır
+    pub fn is_public(&self, path: &str) -> bool {
+        self.public_routes.iter().any(|route| {
+            path == route
+                || path
+                    .strip_prefix(route.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+// Doğrulanmış anahtarın handler'lara request extension olarak verilen özeti
+#[derive(Debug, Clone)]
+pub struct ApiKeyIdentity {
+    pub id: String,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<Scope>,
+    pub daily_quota: Option<u64>,
+}
+
+impl ApiKeyIdentity {
+    pub fn grants(&self, scope: Scope) -> bool {
+        self.scopes.contains(&Scope::Admin) || self.scopes.contains(&scope)
+    }
+}
+
+impl From<ApiKeyRecord> for ApiKeyIdentity {
+    fn from(record: ApiKeyRecord) -> Self {
+        ApiKeyIdentity {
+            id: record.id,
+            name: record.name,
+            prefix: record.prefix,
+            scopes: record.scopes.iter().filter_map(|scope| Scope::parse(scope)).collect(),
+            daily_quota: record.daily_quota,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Authenticated {
+    pub identity: ApiKeyIdentity,
+    // Bu istek dahil bugünkü istek sayısı
+    pub used_today: u64,
+}
+
+pub fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+pub fn today() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+// Yeni anahtar üretip kaydeder; düz anahtar yalnızca burada döner ve bir daha gösterilemez
+pub async fn create_key(
+    repo: &dyn ApiKeyRepository,
+    name: &str,
+    scopes: &[Scope],
+    daily_quota: Option<u64>,
+) -> RepositoryResult<(String, ApiKeyRecord)> {
+    let mut secret = [0u8; 20];
+    rand::thread_rng().fill_bytes(&mut secret);
+    let key = format!("{}{}", KEY_PREFIX, hex::encode(secret));
+
+    let mut record = ApiKeyRecord {
+        id: String::new(),
+        name: name.to_string(),
+        prefix: key[..DISPLAY_PREFIX_LEN].to_string(),
+        key_hash: hash_key(&key),
+        scopes: scopes.iter().map(|scope| scope.as_str().to_string()).collect(),
+        daily_quota,
+        created_at: format_timestamp(chrono::Utc::now()),
+        revoked_at: None,
+    };
+    record.id = repo.insert(&record).await?;
+    Ok((key, record))
+}
+
+// İsteği doğrular: anahtar yoksa yalnızca auth zorunlu değilse ya da route herkese
+// açıksa geçer; anahtar varsa geçerlilik, scope ve günlük kota kontrol edilir.
+// Yönetim route'ları API_AUTH_REQUIRED kapalı olsa da her zaman anahtar ister.
+pub async fn authenticate(
+    state: &AppState,
+    presented_key: Option<&str>,
+    path: &str,
+) -> Result<Option<Authenticated>, ApiError> {
+    let public = state.auth.is_public(path);
+    let scope = Scope::for_path(path);
+    let Some(key) = presented_key else {
+        return if (state.auth.required || scope == Scope::Admin) && !public {
+            Err(ApiError::MissingApiKey)
+        } else {
+            Ok(None)
+        };
+    };
+
+    let identity: ApiKeyIdentity = state
+        .repos
+        .api_keys
+        .find_by_hash(&hash_key(key))
+        .await
+        .map_err(ApiError::storage(Resource::ApiKeys))?
+        .filter(|record| !record.is_revoked())
+        .ok_or(ApiError::InvalidApiKey)?
+        .into();
+
+    if !public && !identity.grants(scope) {
+        return Err(ApiError::InsufficientScope(scope.as_str()));
+    }
+
+    let used_today = state
+        .repos
+        .api_keys
+        .record_usage(&identity.id, &today(), scope.as_str())
+        .await
+        .map_err(ApiError::storage(Resource::ApiKeys))?;
+    if identity.daily_quota.is_some_and(|quota| used_today > quota) {
+        return Err(ApiError::QuotaExceeded);
+    }
+
+    Ok(Some(Authenticated { identity, used_today }))
+}
@@ -1,13 +1,46 @@
 use mongodb::{Client, Database, options::{ClientOptions, IndexOptions}, IndexModel};
 use mongodb::bson::doc;
 use std::env;
+use std::sync::Arc;
 use std::time::Duration;
+use crate::alerts::AlertConfig;
+use crate::auth::AuthConfig;
+use crate::corpus::CorpusRegistry;
+use crate::error::ErrorFormat;
+use crate::harvester::HarvestConfig;
+use crate::indexnow::IndexNowConfig;
+use crate::ratelimit::{LocalStore, MongoStore, RateLimitConfig, RateLimitStore, RateLimiter, StoreKind};
+use crate::repository::Repositories;
+use crate::scraping::{FetchConfig, Fetcher};
+use crate::sitemap::SitemapConfig;
+use crate::statistics::{StatisticsCache, StatisticsConfig};
+use crate::stream::{DocumentStream, StreamConfig};
+use crate::webhooks::WebhookConfig;
+
+mod urls;
+pub use urls::PublicUrls;
 
 pub struct AppConfig {
     pub mongodb_uri: String,
     pub mongodb_db_name: String,
     pub port: u16,
     pub host: String,
+    pub corpora: CorpusRegistry,
+    // Sitenin herkese açık adresi ve belge/kurum sayfası şablonları
+    pub urls: PublicUrls,
+    pub error_format: ErrorFormat,
+    pub auth: AuthConfig,
+    pub rate_limit: RateLimitConfig,
+    // Koleksiyonlardaki API dışı değişiklikler change stream ile denetim kaydına yazılsın mı
+    pub audit_change_stream: bool,
+    pub announcement_harvest: HarvestConfig,
+    pub scrape_cache: FetchConfig,
+    pub webhooks: WebhookConfig,
+    pub saved_searches: AlertConfig,
+    pub stream: StreamConfig,
+    pub sitemap: SitemapConfig,
+    pub indexnow: IndexNowConfig,
+    pub statistics: StatisticsConfig,
 }
 
 impl AppConfig {
@@ -24,6 +57,9 @@ impl AppConfig {
             .or_else(|_| env::var("MONGODB_DB_NAME"))
             .unwrap_or_else(|_| "mevzuatgpt".to_string());
 
+        let corpora = CorpusRegistry::from_env()?;
+        let urls = PublicUrls::from_env(&corpora)?;
+
         Ok(AppConfig {
             mongodb_uri,
             mongodb_db_name,
@@ -33,16 +69,113 @@ impl AppConfig {
                 .unwrap_or(8080),
             host: env::var("HOST")
                 .unwrap_or_else(|_| "0.0.0.0".to_string()),
+            corpora,
+            urls,
+            // Hata gövdesi formatı: legacy (varsayılan, eski istemciler için) ya da v2
+            error_format: env::var("API_ERROR_FORMAT")
+                .ok()
+                .and_then(|value| ErrorFormat::parse(&value))
+                .unwrap_or(ErrorFormat::Legacy),
+            // API anahtarı zorunluluğu ve anahtarsız erişilebilen route'lar
+            auth: AuthConfig::from_env(),
+            // IP/anahtar başına token bucket limitleri
+            rate_limit: RateLimitConfig::from_env(),
+            // Change stream replica set gerektirir; tekil sunucuda kendiliğinden kapanır
+            audit_change_stream: env::var("AUDIT_CHANGE_STREAM")
+                .map(|value| !matches!(value.trim().to_ascii_lowercase().as_str(), "false" | "0" | "off"))
+                .unwrap_or(true),
+            // Kurum duyuru sayfalarının periyodik hasadı
+            announcement_harvest: HarvestConfig::from_env(),
+            // Duyuru sayfası önbelleğinin tazelik ve stale-while-revalidate süreleri
+            scrape_cache: FetchConfig::from_env(),
+            // Webhook taraması, teslimat aralığı ve yeniden deneme sayısı
+            webhooks: WebhookConfig::from_env(),
+            // Kayıtlı aramaların çalışma aralığı ve e-posta bildirimleri için SMTP relay'i
+            saved_searches: AlertConfig::from_env(),
+            // Yeni belge akışının (SSE) kaynağı ve keep-alive aralığı
+            stream: StreamConfig::from_env(),
+            // Alt sitemap başına URL sayısı ve gzip'li alt sitemap adresleri
+            sitemap: SitemapConfig::from_env(),
+            // Eklenen/değişen belgelerin IndexNow ile arama motorlarına bildirilmesi
+            indexnow: IndexNowConfig::from_env()?,
+            // İstatistik özetinin yenilenme aralığı
+            statistics: StatisticsConfig::from_env(),
         })
     }
 }
 
 pub struct AppState {
-    pub db: Database,
+    pub corpora: CorpusRegistry,
+    pub urls: PublicUrls,
+    pub repos: Repositories,
+    pub error_format: ErrorFormat,
+    pub auth: AuthConfig,
+    pub rate_limiter: RateLimiter,
+    // Duyuru sayfaları için paylaşılan HTTP client ve sayfa önbelleği
+    pub fetcher: Fetcher,
+    // Yeni belge olaylarının SSE istemcilerine dağıtıldığı kanal
+    pub document_stream: DocumentStream,
+    pub sitemap: SitemapConfig,
+    // Anahtar dosyası (KEY_FILE_PATH) bu yapılandırmadan sunulur
+    pub indexnow: IndexNowConfig,
+    // Kayıtlı arama webhook hedefleri bu yapılandırmadaki izin listesiyle denetlenir
+    pub saved_searches: AlertConfig,
+    // Önceden hesaplanmış istatistik özeti
+    pub statistics: StatisticsCache,
 }
 
 impl AppState {
     pub async fn new(config: &AppConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let client = Self::connect_client(config).await?;
+        let db = client.database(&config.mongodb_db_name);
+        log::info!("MongoDB bağlantısı başarıyla kuruldu: {}", config.mongodb_db_name);
+
+        let rate_limit_store: Arc<dyn RateLimitStore> = match config.rate_limit.store {
+            StoreKind::Memory => Arc::new(LocalStore::new()),
+            StoreKind::MongoDb => Arc::new(MongoStore::new(&db)),
+        };
+
+        let mut state = AppState::with_repositories(config.corpora.clone(), Repositories::mongo(client, &config.mongodb_db_name));
+        state.rate_limiter = RateLimiter::new(config.rate_limit.clone(), rate_limit_store);
+        state.error_format = config.error_format;
+        state.auth = config.auth.clone();
+        state.fetcher = Fetcher::new(config.scrape_cache.clone());
+        state.document_stream = DocumentStream::new(&config.stream);
+        state.sitemap = config.sitemap.clone();
+        state.urls = config.urls.clone();
+        state.indexnow = config.indexnow.clone();
+        state.saved_searches = config.saved_searches.clone();
+        Ok(state)
+    }
+
+    // Verilen repository'lerle state oluştur (testlerde bellek içi repository kullanılır)
+    pub fn with_repositories(corpora: CorpusRegistry, repos: Repositories) -> Self {
+        AppState {
+            corpora,
+            urls: PublicUrls::default(),
+            repos,
+            error_format: ErrorFormat::Legacy,
+            auth: AuthConfig::default(),
+            rate_limiter: RateLimiter::disabled(),
+            fetcher: Fetcher::new(FetchConfig::default()),
+            document_stream: DocumentStream::default(),
+            sitemap: SitemapConfig::default(),
+            indexnow: IndexNowConfig::default(),
+            saved_searches: AlertConfig::default(),
+            statistics: StatisticsCache::default(),
+        }
+    }
+
+    pub async fn connect(config: &AppConfig) -> Result<Database, Box<dyn std::error::Error>> {
+        let db = Self::connect_client(config).await?.database(&config.mongodb_db_name);
+
+        log::info!("MongoDB bağlantısı başarıyla kuruldu: {}", config.mongodb_db_name);
+
+        Ok(db)
+    }
+
+    // Transaction için session açılabilmesi gereken yerlerde (belge yazma) client da gerekir
+    pub async fn connect_client(config: &AppConfig) -> Result<Client, Box<dyn std::error::Error>> {
         // MongoDB client options ile timeout ayarları
         let mut client_options = ClientOptions::parse(&config.mongodb_uri).await?;
         
@@ -51,12 +184,7 @@ impl AppState {
         client_options.connect_timeout = Some(Duration::from_secs(30)); // 30 saniye
         // socket_timeout private field, MongoDB driver'ı otomatik yönetiyor
         
-        let client = Client::with_options(client_options)?;
-        let db = client.database(&config.mongodb_db_name);
-
-        log::info!("MongoDB bağlantısı başarıyla kuruldu: {}", config.mongodb_db_name);
-
-        Ok(AppState { db })
+        Ok(Client::with_options(client_options)?)
     }
 
     // MongoDB index'lerini güvenli bir şekilde oluştur
@@ -254,6 +382,241 @@ impl AppState {
             log::info!("✓ links kurum_id index oluşturuldu");
         }
 
+        // api_keys koleksiyonu index'leri
+        let api_keys_collection = db.collection::<mongodb::bson::Document>("api_keys");
+
+        // key_hash unique index (anahtar doğrulama her istekte bu alandan yapılır)
+        if let Err(e) = api_keys_collection.create_index(
+            IndexModel::builder()
+                .keys(doc! { "key_hash": 1 })
+                .options(IndexOptions::builder()
+                    .unique(true)
+                    .name("idx_api_keys_hash_unique".to_string())
+                    .build())
+                .build(),
+            None,
+        ).await {
+            if e.to_string().contains("already exists") || e.to_string().contains("duplicate") {
+                log::info!("✓ api_keys key_hash unique index zaten mevcut");
+            } else {
+                log::warn!("api_keys key_hash index oluşturulamadı: {}", e);
+            }
+        } else {
+            log::info!("✓ api_keys key_hash unique index oluşturuldu");
+        }
+
+        // api_key_usage koleksiyonu index'leri
+        let api_key_usage_collection = db.collection::<mongodb::bson::Document>("api_key_usage");
+
+        // key_id + day unique compound index (günlük sayaç upsert'i için)
+        if let Err(e) = api_key_usage_collection.create_index(
+            IndexModel::builder()
+                .keys(doc! { "key_id": 1, "day": 1 })
+                .options(IndexOptions::builder()
+                    .unique(true)
+                    .name("idx_api_key_usage_key_day_unique".to_string())
+                    .build())
+                .build(),
+            None,
+        ).await {
+            if e.to_string().contains("already exists") || e.to_string().contains("duplicate") {
+                log::info!("✓ api_key_usage key_id+day index zaten mevcut");
+            } else {
+                log::warn!("api_key_usage key_id+day index oluşturulamadı: {}", e);
+            }
+        } else {
+            log::info!("✓ api_key_usage key_id+day index oluşturuldu");
+        }
+
+        // rate_limits koleksiyonu index'leri (RATE_LIMIT_STORE=mongodb)
+        let rate_limits_collection = db.collection::<mongodb::bson::Document>("rate_limits");
+
+        // expires_at TTL index (boşta kalan bucket'lar otomatik silinir)
+        if let Err(e) = rate_limits_collection.create_index(
+            IndexModel::builder()
+                .keys(doc! { "expires_at": 1 })
+                .options(IndexOptions::builder()
+                    .expire_after(Duration::from_secs(0))
+                    .name("idx_rate_limits_expires_at_ttl".to_string())
+                    .build())
+                .build(),
+            None,
+        ).await {
+            if e.to_string().contains("already exists") || e.to_string().contains("duplicate") {
+                log::info!("✓ rate_limits expires_at TTL index zaten mevcut");
+            } else {
+                log::warn!("rate_limits expires_at TTL index oluşturulamadı: {}", e);
+            }
+        } else {
+            log::info!("✓ rate_limits expires_at TTL index oluşturuldu");
+        }
+
+        // audit_log koleksiyonu index'leri (denetim kaydı; en yeni önce listelenir)
+        let audit_log_collection = db.collection::<mongodb::bson::Document>("audit_log");
+
+        // timestamp index (tarih aralığı ve sıralama)
+        if let Err(e) = audit_log_collection.create_index(
+            IndexModel::builder()
+                .keys(doc! { "timestamp": -1 })
+                .options(IndexOptions::builder()
+                    .name("idx_audit_log_timestamp".to_string())
+                    .build())
+                .build(),
+            None,
+        ).await {
+            if e.to_string().contains("already exists") || e.to_string().contains("duplicate") {
+                log::info!("✓ audit_log timestamp index zaten mevcut");
+            } else {
+                log::warn!("audit_log timestamp index oluşturulamadı: {}", e);
+            }
+        } else {
+            log::info!("✓ audit_log timestamp index oluşturuldu");
+        }
+
+        // collection + target_id + timestamp index (bir kaydın geçmişi, change stream eşleştirmesi)
+        if let Err(e) = audit_log_collection.create_index(
+            IndexModel::builder()
+                .keys(doc! { "collection": 1, "target_id": 1, "timestamp": -1 })
+                .options(IndexOptions::builder()
+                    .name("idx_audit_log_target".to_string())
+                    .build())
+                .build(),
+            None,
+        ).await {
+            if e.to_string().contains("already exists") || e.to_string().contains("duplicate") {
+                log::info!("✓ audit_log collection+target_id index zaten mevcut");
+            } else {
+                log::warn!("audit_log collection+target_id index oluşturulamadı: {}", e);
+            }
+        } else {
+            log::info!("✓ audit_log collection+target_id index oluşturuldu");
+        }
+
+        // audit_claims claimed_at TTL index (eşleştirmeler change stream'in geri saramayacağı kadar eskiyince silinir)
+        let audit_claims_collection = db.collection::<mongodb::bson::Document>("audit_claims");
+        if let Err(e) = audit_claims_collection.create_index(
+            IndexModel::builder()
+                .keys(doc! { "claimed_at": 1 })
+                .options(IndexOptions::builder()
+                    .expire_after(Duration::from_secs(7 * 24 * 60 * 60))
+                    .name("idx_audit_claims_claimed_at_ttl".to_string())
+                    .build())
+                .build(),
+            None,
+        ).await {
+            if e.to_string().contains("already exists") || e.to_string().contains("duplicate") {
+                log::info!("✓ audit_claims claimed_at TTL index zaten mevcut");
+            } else {
+                log::warn!("audit_claims claimed_at TTL index oluşturulamadı: {}", e);
+            }
+        } else {
+            log::info!("✓ audit_claims claimed_at TTL index oluşturuldu");
+        }
+
+        // kurum_duyuru_items koleksiyonu index'leri (duyuru hasadı)
+        let kurum_duyuru_items_collection = db.collection::<mongodb::bson::Document>("kurum_duyuru_items");
+
+        // link unique index (duyurular link'e göre tekilleştirilir)
+        if let Err(e) = kurum_duyuru_items_collection.create_index(
+            IndexModel::builder()
+                .keys(doc! { "link": 1 })
+                .options(IndexOptions::builder()
+                    .unique(true)
+                    .name("idx_kurum_duyuru_items_link_unique".to_string())
+                    .build())
+                .build(),
+            None,
+        ).await {
+            if e.to_string().contains("already exists") || e.to_string().contains("duplicate") {
+                log::info!("✓ kurum_duyuru_items link unique index zaten mevcut");
+            } else {
+                log::warn!("kurum_duyuru_items link index oluşturulamadı: {}", e);
+            }
+        } else {
+            log::info!("✓ kurum_duyuru_items link unique index oluşturuldu");
+        }
+
+        // kurum_id + first_seen_at index (kurumun duyuruları, en yeni önce)
+        if let Err(e) = kurum_duyuru_items_collection.create_index(
+            IndexModel::builder()
+                .keys(doc! { "kurum_id": 1, "first_seen_at": -1 })
+                .options(IndexOptions::builder()
+                    .name("idx_kurum_duyuru_items_kurum_first_seen".to_string())
+                    .build())
+                .build(),
+            None,
+        ).await {
+            if e.to_string().contains("already exists") || e.to_string().contains("duplicate") {
+                log::info!("✓ kurum_duyuru_items kurum_id+first_seen_at index zaten mevcut");
+            } else {
+                log::warn!("kurum_duyuru_items kurum_id+first_seen_at index oluşturulamadı: {}", e);
+            }
+        } else {
+            log::info!("✓ kurum_duyuru_items kurum_id+first_seen_at index oluşturuldu");
+        }
+
+        // webhook_deliveries koleksiyonu index'leri (webhook teslimat kuyruğu ve kaydı)
+        let webhook_deliveries_collection = db.collection::<mongodb::bson::Document>("webhook_deliveries");
+
+        // status + next_attempt_at index (zamanı gelmiş teslimatlar)
+        if let Err(e) = webhook_deliveries_collection.create_index(
+            IndexModel::builder()
+                .keys(doc! { "status": 1, "next_attempt_at": 1 })
+                .options(IndexOptions::builder()
+                    .name("idx_webhook_deliveries_status_next_attempt".to_string())
+                    .build())
+                .build(),
+            None,
+        ).await {
+            if e.to_string().contains("already exists") || e.to_string().contains("duplicate") {
+                log::info!("✓ webhook_deliveries status+next_attempt_at index zaten mevcut");
+            } else {
+                log::warn!("webhook_deliveries status+next_attempt_at index oluşturulamadı: {}", e);
+            }
+        } else {
+            log::info!("✓ webhook_deliveries status+next_attempt_at index oluşturuldu");
+        }
+
+        // webhook_id + created_at index (aboneliğin teslimat kaydı, en yeni önce)
+        if let Err(e) = webhook_deliveries_collection.create_index(
+            IndexModel::builder()
+                .keys(doc! { "webhook_id": 1, "created_at": -1 })
+                .options(IndexOptions::builder()
+                    .name("idx_webhook_deliveries_webhook_created".to_string())
+                    .build())
+                .build(),
+            None,
+        ).await {
+            if e.to_string().contains("already exists") || e.to_string().contains("duplicate") {
+                log::info!("✓ webhook_deliveries webhook_id+created_at index zaten mevcut");
+            } else {
+                log::warn!("webhook_deliveries webhook_id+created_at index oluşturulamadı: {}", e);
+            }
+        } else {
+            log::info!("✓ webhook_deliveries webhook_id+created_at index oluşturuldu");
+        }
+
+        // saved_search_alerts koleksiyonu index'i (sahibin gelen kutusu, en yeni önce)
+        let saved_search_alerts_collection = db.collection::<mongodb::bson::Document>("saved_search_alerts");
+
+        if let Err(e) = saved_search_alerts_collection.create_index(
+            IndexModel::builder()
+                .keys(doc! { "owner": 1, "created_at": -1 })
+                .options(IndexOptions::builder()
+                    .name("idx_saved_search_alerts_owner_created".to_string())
+                    .build())
+                .build(),
+            None,
+        ).await {
+            if e.to_string().contains("already exists") || e.to_string().contains("duplicate") {
+                log::info!("✓ saved_search_alerts owner+created_at index zaten mevcut");
+            } else {
+                log::warn!("saved_search_alerts owner+created_at index oluşturulamadı: {}", e);
+            }
+        } else {
+            log::info!("✓ saved_search_alerts owner+created_at index oluşturuldu");
+        }
+
         log::info!("MongoDB index kontrolü tamamlandı");
     }
 }
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::env;
+use crate::corpus::{Corpus, CorpusRegistry};
+
+// Sitenin herkese açık adresleri: sitemap, besleme, webhook/bildirim ve belge cevaplarındaki
+// linkler buradan üretilir. Staging gibi ortamlarda PUBLIC_BASE_URL ile değiştirilir.
+
+const DEFAULT_BASE_URL: &str = "https://mevzuatgpt.org";
+const DEFAULT_INSTITUTION_TEMPLATE: &str = "/kurum/{slug}";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicUrls {
+    // Sonunda "/" olmadan, örn. https://mevzuatgpt.org
+    base_url: String,
+    // Corpus anahtarı -> belge sayfası şablonu; tanımsız corpus'ta "{url_prefix}/{slug}"
+    document_templates: HashMap<String, String>,
+    institution_template: String,
+}
+
+impl Default for PublicUrls {
+    fn default() -> Self {
+        PublicUrls {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            document_templates: HashMap::new(),
+            institution_template: DEFAULT_INSTITUTION_TEMPLATE.to_string(),
+        }
+    }
+}
+
+impl PublicUrls {
+    pub fn new(base_url: &str) -> Result<Self, String> {
+        let trimmed = base_url.trim().trim_end_matches('/');
+        let parsed = url::Url::parse(trimmed).map_err(|_| format!("PUBLIC_BASE_URL geçersiz: {}", base_url))?;
+        if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+            return Err(format!("PUBLIC_BASE_URL http(s) adresi olmalı: {}", base_url));
+        }
+        Ok(PublicUrls {
+            base_url: trimmed.to_string(),
+            ..PublicUrls::default()
+        })
+    }
+
+    // Şablonlar {slug} ve/veya {id} içermelidir; "/" ile başlayan şablon base URL'e eklenir,
+    // http(s) ile başlayan şablon olduğu gibi kullanılır
+    pub fn with_document_template(mut self, corpus: &str, template: &str) -> Result<Self, String> {
+        self.document_templates.insert(corpus.to_string(), validate_template(template)?);
+        Ok(self)
+    }
+
+    pub fn with_institution_template(mut self, template: &str) -> Result<Self, String> {
+        self.institution_template = validate_template(template)?;
+        Ok(self)
+    }
+
+    // PUBLIC_BASE_URL, DOCUMENT_URL_TEMPLATES ("mevzuat=/belge/{slug},yargitay=/karar/{slug}")
+    // ve INSTITUTION_URL_TEMPLATE. Bilinmeyen corpus anahtarı ya da geçersiz şablon başlangıçta hata verir.
+    pub fn from_env(corpora: &CorpusRegistry) -> Result<Self, String> {
+        let mut urls = match env::var("PUBLIC_BASE_URL") {
+            Ok(base_url) if !base_url.trim().is_empty() => PublicUrls::new(&base_url)?,
+            _ => PublicUrls::default(),
+        };
+        if let Ok(templates) = env::var("DOCUMENT_URL_TEMPLATES") {
+            for entry in templates.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+                let (key, template) = entry
+                    .split_once('=')
+                    .ok_or_else(|| format!("DOCUMENT_URL_TEMPLATES girdisi geçersiz: {}", entry))?;
+                let key = key.trim();
+                if corpora.get(key).is_none() {
+                    return Err(format!("DOCUMENT_URL_TEMPLATES bilinmeyen corpus: {}", key));
+                }
+                urls = urls.with_document_template(key, template)?;
+            }
+        }
+        if let Ok(template) = env::var("INSTITUTION_URL_TEMPLATE") {
+            if !template.trim().is_empty() {
+                urls = urls.with_institution_template(&template)?;
+            }
+        }
+        Ok(urls)
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    // Base URL'in host'u (port varsa onunla birlikte)
+    pub fn host(&self) -> String {
+        let parsed = url::Url::parse(&self.base_url).expect("base URL PublicUrls::new ile doğrulanır");
+        match (parsed.host_str(), parsed.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    // Site içi yol ("/feeds/...") için tam adres
+    pub fn absolute(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    // Belgenin sitedeki kanonik adresi
+    pub fn document(&self, corpus: &Corpus, slug: &str, id: &str) -> String {
+        match self.document_templates.get(&corpus.key) {
+            Some(template) => self.expand(template, slug, id),
+            None => self.absolute(&format!("{}/{}", corpus.url_prefix, slug)),
+        }
+    }
+
+    pub fn institution(&self, slug: &str) -> String {
+        self.expand(&self.institution_template, slug, "")
+    }
+
+    fn expand(&self, template: &str, slug: &str, id: &str) -> String {
+        let path = template.replace("{slug}", slug).replace("{id}", id);
+        if is_absolute(&path) {
+            path
+        } else {
+            self.absolute(&path)
+        }
+    }
+}
+
+fn is_absolute(template: &str) -> bool {
+    template.starts_with("http://") || template.starts_with("https://")
+}
+
+fn validate_template(template: &str) -> Result<String, String> {
+    let template = template.trim();
+    if !template.contains("{slug}") && !template.contains("{id}") {
+        return Err(format!("URL şablonu {{slug}} ya da {{id}} içermeli: {}", template));
+    }
+    if !template.starts_with('/') && !is_absolute(template) {
+        return Err(format!("URL şablonu \"/\" ya da http(s):// ile başlamalı: {}", template));
+    }
+    Ok(template.to_string())
+}
@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+
+// Corpus registry
+// Her belge koleksiyonu (mevzuat, Yargıtay kararları, ileride Danıştay, AYM...) burada
+// yapılandırma olarak tanımlanır. Liste, detay, arama, son eklenenler ve sitemap
+// route'ları bu tanımlardan üretilir; yeni bir kaynak için handler kopyalamaya gerek yoktur.
+
+pub const YARGITAY_KURUM_ID: &str = "68bf0cd13907e0d3ac876705";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Corpus {
+    // URL'de kullanılan anahtar: /api/v1/corpora/{key}/...
+    pub key: String,
+    pub title: String,
+    // MongoDB koleksiyon adı
+    pub collection: String,
+    // Frontend'deki belge sayfası öneki (örn. /belge)
+    #[serde(default = "default_url_prefix")]
+    pub url_prefix: String,
+    #[serde(default)]
+    pub fields: FieldMapping,
+    #[serde(default)]
+    pub content: ContentSource,
+    // Koleksiyondaki tüm kayıtlar tek bir kuruma aitse arama bu kuruma sabitlenir
+    #[serde(default)]
+    pub kurum_id: Option<String>,
+    // kurumlar koleksiyonu ile $lookup yapılsın mı
+    #[serde(default = "default_true")]
+    pub lookup_kurum: bool,
+    #[serde(default)]
+    pub search: SearchSettings,
+}
+
+// Koleksiyonlar arasında farklı adlandırılan alanlar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldMapping {
+    // Belgenin yayın/karar tarihi (belge_yayin_tarihi veya kararTarihi)
+    #[serde(default = "default_publication_date_field")]
+    pub publication_date: String,
+    #[serde(default = "default_created_at_field")]
+    pub created_at: String,
+    #[serde(default = "default_title_field")]
+    pub title: String,
+    // Regex aramasının yapıldığı düz metin alanı
+    #[serde(default = "default_search_text_field")]
+    pub search_text: String,
+    // Sitemap'e yalnızca bu status değerine sahip belgeler girer
+    #[serde(default = "default_active_status")]
+    pub active_status: String,
+}
+
+impl Default for FieldMapping {
+    fn default() -> Self {
+        FieldMapping {
+            publication_date: default_publication_date_field(),
+            created_at: default_created_at_field(),
+            title: default_title_field(),
+            search_text: default_search_text_field(),
+            active_status: default_active_status(),
+        }
+    }
+}
+
+// Belge içeriğinin nereden okunacağı
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentSource {
+    // İçerik belgenin kendisinde (icerik / content / text)
+    #[default]
+    Embedded,
+    // İçerik ayrı bir koleksiyonda, foreign_key ile metadata _id'sine bağlı
+    Join {
+        collection: String,
+        foreign_key: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchSettings {
+    // content_preview için kullanılan alan
+    #[serde(default = "default_preview_field")]
+    pub preview_field: String,
+    // true ise eşleşme çevresi yerine alanın tamamı döner
+    #[serde(default)]
+    pub full_preview: bool,
+}
+
+impl Default for SearchSettings {
+    fn default() -> Self {
+        SearchSettings {
+            preview_field: default_preview_field(),
+            full_preview: false,
+        }
+    }
+}
+
+fn default_url_prefix() -> String {
+    "/belge".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_publication_date_field() -> String {
+    "belge_yayin_tarihi".to_string()
+}
+
+fn default_created_at_field() -> String {
+    "olusturulma_tarihi".to_string()
+}
+
+fn default_title_field() -> String {
+    "pdf_adi".to_string()
+}
+
+fn default_search_text_field() -> String {
+    "icerik_text".to_string()
+}
+
+fn default_active_status() -> String {
+    "aktif".to_string()
+}
+
+fn default_preview_field() -> String {
+    "aciklama".to_string()
+}
+
+#[derive(Debug, Clone)]
+pub struct CorpusRegistry {
+    corpora: Vec<Corpus>,
+}
+
+impl CorpusRegistry {
+    // Mevcut iki koleksiyonun yerleşik tanımları
+    pub fn builtin() -> Self {
+        CorpusRegistry {
+            corpora: vec![
+                Corpus {
+                    key: "mevzuat".to_string(),
+                    title: "Mevzuat".to_string(),
+                    collection: "metadata".to_string(),
+                    url_prefix: default_url_prefix(),
+                    fields: FieldMapping::default(),
+                    content: ContentSource::Join {
+                        collection: "content".to_string(),
+                        foreign_key: "metadata_id".to_string(),
+                    },
+                    kurum_id: None,
+                    lookup_kurum: true,
+                    search: SearchSettings::default(),
+                },
+                Corpus {
+                    key: "yargitay".to_string(),
+                    title: "Yargıtay Kararları".to_string(),
+                    collection: "yargitay".to_string(),
+                    url_prefix: "/yargitay".to_string(),
+                    fields: FieldMapping {
+                        publication_date: "kararTarihi".to_string(),
+                        ..FieldMapping::default()
+                    },
+                    content: ContentSource::Embedded,
+                    kurum_id: Some(YARGITAY_KURUM_ID.to_string()),
+                    lookup_kurum: true,
+                    search: SearchSettings {
+                        preview_field: "icerik_text".to_string(),
+                        full_preview: true,
+                    },
+                },
+            ],
+        }
+    }
+
+    // CORPORA_CONFIG ile verilen JSON dosyasındaki tanımlar yerleşik tanımları
+    // aynı key ile ezer, yeni key'ler listeye eklenir
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let mut registry = Self::builtin();
+
+        if let Ok(path) = env::var("CORPORA_CONFIG") {
+            let raw = fs::read_to_string(&path)
+                .map_err(|e| format!("CORPORA_CONFIG okunamadı ({}): {}", path, e))?;
+            registry = Self::from_json(&raw)
+                .map_err(|e| format!("CORPORA_CONFIG geçersiz ({}): {}", path, e))?;
+        }
+
+        Ok(registry)
+    }
+
+    // Yerleşik tanımlar ve üzerine JSON dizisindeki tanımlar (CORPORA_CONFIG dosyasının içeriği)
+    pub fn from_json(raw: &str) -> Result<Self, serde_json::Error> {
+        let mut registry = Self::builtin();
+        let extra: Vec<Corpus> = serde_json::from_str(raw)?;
+        for corpus in extra {
+            registry.upsert(corpus);
+        }
+        Ok(registry)
+    }
+
+    pub fn upsert(&mut self, corpus: Corpus) {
+        match self.corpora.iter_mut().find(|c| c.key == corpus.key) {
+            Some(existing) => *existing = corpus,
+            None => self.corpora.push(corpus),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Corpus> {
+        self.corpora.iter().find(|c| c.key == key)
+    }
+
+    pub fn all(&self) -> &[Corpus] {
+        &self.corpora
+    }
+
+    // Yerleşik corpus'lar her zaman tanımlıdır; config ile silinemezler
+    pub fn mevzuat(&self) -> &Corpus {
+        self.get("mevzuat").expect("mevzuat corpus tanımlı olmalı")
+    }
+
+    pub fn yargitay(&self) -> &Corpus {
+        self.get("yargitay").expect("yargitay corpus tanımlı olmalı")
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+use std::ops::Range;
+use std::sync::OnceLock;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use regex::{Captures, Regex};
+
+// Türkçe tarih okuma: sayısal (12.03.2024, 12/03/24, 12-03-2024), ISO (2024-03-12,
+// 2024-03-12T10:00:00Z) ve Türkçe ay adlı ("12 Mart 2024", "12 Mar 2024", "12 MART 2024 Salı")
+// biçimler chrono::NaiveDate'e çevrilir. Ay adları büyük/küçük harf ve Türkçe karakter
+// farkı gözetmeden ("Subat", "AĞUSTOS", "Kasim") tanınır. Okunamayan değer için tarih
+// uydurulmaz; hata döner ya da alan boş bırakılır.
+
+// Ay adları ASCII'ye indirgenmiş halde: (tam ad, kısaltma)
+const MONTHS: [(&str, &str); 12] = [
+    ("ocak", "oca"),
+    ("subat", "sub"),
+    ("mart", "mar"),
+    ("nisan", "nis"),
+    ("mayis", "may"),
+    ("haziran", "haz"),
+    ("temmuz", "tem"),
+    ("agustos", "agu"),
+    ("eylul", "eyl"),
+    ("ekim", "eki"),
+    ("kasim", "kas"),
+    ("aralik", "ara"),
+];
+
+const ENGLISH_MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November",
+    "December",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    Empty,
+    // Metinde tarih biçimi bulunamadı
+    Unrecognized(String),
+    // Biçim tanındı ama takvimde böyle bir gün yok (örn. 31.02.2025)
+    Invalid(String),
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateError::Empty => write!(f, "Tarih boş"),
+            DateError::Unrecognized(text) => write!(f, "Tarih biçimi tanınmadı: {}", text),
+            DateError::Invalid(text) => write!(f, "Geçersiz tarih: {}", text),
+        }
+    }
+}
+
+impl std::error::Error for DateError {}
+
+// Türkçe harfleri ASCII karşılığına indirir ("Ş" -> "s", "İ" -> "i", "I" -> "i")
+fn fold(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'ç' | 'Ç' => 'c',
+            'ğ' | 'Ğ' => 'g',
+            'ı' | 'I' | 'İ' => 'i',
+            'ö' | 'Ö' => 'o',
+            'ş' | 'Ş' => 's',
+            'ü' | 'Ü' => 'u',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+// ASCII ay adından, Türkçe yazımı ve büyük harfleri de kapsayan desen ("subat" -> "[sş]ubat")
+fn month_pattern(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'c' => "[cç]".to_string(),
+            'g' => "[gğ]".to_string(),
+            'i' => "[iıİ]".to_string(),
+            'o' => "[oö]".to_string(),
+            's' => "[sş]".to_string(),
+            'u' => "[uü]".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn month_names_pattern() -> String {
+    let full = MONTHS.iter().map(|(name, _)| month_pattern(name));
+    let short = MONTHS.iter().map(|(_, short)| month_pattern(short));
+    full.chain(short).collect::<Vec<_>>().join("|")
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    let name = fold(name.trim_end_matches('.'));
+    MONTHS
+        .iter()
+        .position(|(full, short)| *full == name || *short == name)
+        .map(|index| index as u32 + 1)
+}
+
+// Üç biçim tek desende; metinde en solda başlayan eşleşme kazanır. Desenler bir kez derlenir
+fn date_regex() -> &'static Regex {
+    static DATE: OnceLock<Regex> = OnceLock::new();
+    DATE.get_or_init(|| {
+        Regex::new(&format!(
+            r"(?i)\b(?P<nd>\d{{1,2}})\.?\s+(?P<nm>{})\.?,?\s+(?P<ny>\d{{4}})\b|\b(?P<iy>\d{{4}})[-./](?P<im>\d{{1,2}})[-./](?P<id>\d{{1,2}})(?:T|\b)|\b(?P<dd>\d{{1,2}})[./-](?P<dm>\d{{1,2}})[./-](?P<dy>\d{{4}}|\d{{2}})\b",
+            month_names_pattern()
+        ))
+        .unwrap()
+    })
+}
+
+// Tarihten sonra gelebilecek saat ("10:30")
+fn time_regex() -> &'static Regex {
+    static TIME: OnceLock<Regex> = OnceLock::new();
+    TIME.get_or_init(|| Regex::new(r"^\d{1,2}:\d{2}").unwrap())
+}
+
+// Türkçe ay adları (tam ya da kısa, ardından isteğe bağlı nokta)
+fn month_names_regex() -> &'static Regex {
+    static NAMES: OnceLock<Regex> = OnceLock::new();
+    NAMES.get_or_init(|| Regex::new(&format!(r"(?i)\b({})\b\.?", month_names_pattern())).unwrap())
+}
+
+fn number<T: std::str::FromStr>(caps: &Captures, name: &str) -> Option<T> {
+    caps.name(name)?.as_str().parse().ok()
+}
+
+// Eşleşmenin (yıl, ay, gün) değerleri; iki haneli yıl 2000'li yıllara sayılır
+fn components(caps: &Captures) -> Option<(i32, u32, u32)> {
+    if let Some(month) = caps.name("nm") {
+        return Some((number(caps, "ny")?, month_number(month.as_str())?, number(caps, "nd")?));
+    }
+    if caps.name("iy").is_some() {
+        return Some((number(caps, "iy")?, number(caps, "im")?, number(caps, "id")?));
+    }
+    let year: i32 = number(caps, "dy")?;
+    let year = if caps["dy"].len() == 2 { 2000 + year } else { year };
+    Some((year, number(caps, "dm")?, number(caps, "dd")?))
+}
+
+fn to_date(caps: &Captures) -> Option<NaiveDate> {
+    let (year, month, day) = components(caps)?;
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+// Metindeki tüm geçerli tarihler ve metin içindeki konumları (byte aralığı)
+pub fn find_all(text: &str) -> Vec<(Range<usize>, NaiveDate)> {
+    date_regex()
+        .captures_iter(text)
+        .filter_map(|caps| Some((caps.get(0)?.range(), to_date(&caps)?)))
+        .collect()
+}
+
+// Serbest metindeki ilk geçerli tarih
+pub fn find(text: &str) -> Option<NaiveDate> {
+    find_all(text).into_iter().next().map(|(_, date)| date)
+}
+
+// Değerin tamamı bir tarih olmalıdır; ardından yalnızca saat ("10:30", ISO'da "T10:30:00Z")
+// ya da gün adı ("Salı") gelebilir
+pub fn parse(text: &str) -> Result<NaiveDate, DateError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(DateError::Empty);
+    }
+    let unrecognized = || DateError::Unrecognized(text.to_string());
+    let caps = date_regex().captures(text).ok_or_else(unrecognized)?;
+    let matched = caps.get(0).ok_or_else(unrecognized)?;
+    let rest = text[matched.end()..].trim();
+    let trailing_ok = rest.is_empty()
+        || time_regex().is_match(rest)
+        || (!rest.contains(char::is_whitespace) && rest.chars().all(char::is_alphabetic));
+    if matched.start() != 0 || !trailing_ok {
+        return Err(unrecognized());
+    }
+    to_date(&caps).ok_or_else(|| DateError::Invalid(text.to_string()))
+}
+
+// chrono formatıyla okur (örn. "%d %B %Y"); Türkçe ay adları %B/%b ile eşleşsin diye
+// önce İngilizceye çevrilir
+pub fn parse_with_format(text: &str, format: &str) -> Result<NaiveDate, DateError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(DateError::Empty);
+    }
+    let english = month_names_regex().replace_all(text, |caps: &Captures| {
+        let index = month_number(&caps[1]).map(|month| month as usize - 1).unwrap_or_default();
+        let name = ENGLISH_MONTHS[index];
+        if fold(&caps[1]) == MONTHS[index].0 {
+            name.to_string()
+        } else {
+            name[..3].to_string()
+        }
+    });
+    NaiveDate::parse_from_str(&english, format).map_err(|_| DateError::Unrecognized(text.to_string()))
+}
+
+// RFC 3339 zaman damgası ya da parse ile okunan tarih (UTC gece yarısı)
+pub fn parse_timestamp(text: &str) -> Result<DateTime<Utc>, DateError> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(text.trim()) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let date = parse(text)?;
+    Ok(date.and_hms_opt(0, 0, 0).ok_or_else(|| DateError::Invalid(text.to_string()))?.and_utc())
+}
+
+// Belge ve sitemap çıktısındaki biçim: "2024-03-12T00:00:00Z"
+pub fn to_rfc3339(text: &str) -> Result<String, DateError> {
+    parse_timestamp(text).map(|time| time.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+}
+
+// Duyurularda kullanılan gün.ay.yıl biçimi
+pub fn day_month_year(date: NaiveDate) -> String {
+    date.format("%d.%m.%Y").to_string()
+}
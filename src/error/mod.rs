@@ -0,0 +1,470 @@
+use std::fmt;
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use serde_json::Value;
+use crate::models::error::{ErrorDetail, ErrorEnvelope, FieldErrorDetail, LegacyErrorEnvelope};
+use crate::repository::RepositoryError;
+
+// Tüm endpoint'lerin ortak hata tipi. Kod (code) alanları istemciler için
+// sabittir; mesajlar Accept-Language'a göre Türkçe ya da İngilizce döner.
+#[derive(Debug)]
+pub enum ApiError {
+    EmptySearchQuery,
+    InvalidSearchQuery,
+    MissingParameter(&'static str),
+    InvalidParameter(&'static str),
+    InvalidQueryString(String),
+    InvalidBody(String),
+    // Alan bazlı doğrulama hataları (yönetim API'si)
+    Validation(Vec<FieldError>),
+    RouteNotFound,
+    CorpusNotFound(String),
+    DocumentNotFound,
+    InstitutionNotFound,
+    AnnouncementSourceNotFound,
+    AnnouncementLinkMissing,
+    LinkNotFound,
+    WebhookNotFound,
+    DeliveryNotFound,
+    SavedSearchNotFound,
+    AlertNotFound,
+    // Kuruma bağlı belge sayısı; belgeleri olan kurum silinemez
+    InstitutionInUse(u64),
+    MissingApiKey,
+    InvalidApiKey,
+    InsufficientScope(&'static str),
+    QuotaExceeded,
+    // Bir sonraki isteğe kadar beklenecek saniye
+    RateLimited(u64),
+    Storage(Resource, RepositoryError),
+    Upstream(String),
+    // PDF alım hattı: indirilemedi / okunamadı, geçersiz, metinsiz, boyut sınırı (bayt)
+    PdfUnavailable(String),
+    InvalidPdf(String),
+    PdfNoText,
+    PdfTooLarge(usize),
+}
+
+// Depolama hatalarında hangi verinin alınamadığı
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Documents,
+    Document,
+    DocumentFilters,
+    Institutions,
+    Institution,
+    Links,
+    Announcements,
+    AnnouncementSource,
+    Regulations,
+    Search,
+    Statistics,
+    Sitemap,
+    ApiKeys,
+    DocumentWrite,
+    InstitutionWrite,
+    AnnouncementSourceWrite,
+    LinkWrite,
+    Audit,
+    ScrapedAnnouncements,
+    Webhooks,
+    WebhookWrite,
+    WebhookDeliveries,
+    SavedSearches,
+    SavedSearchWrite,
+    SearchAlerts,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub issue: FieldIssue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldIssue {
+    Required,
+    InvalidFormat,
+    // İzin verilen en fazla karakter sayısı
+    TooLong(usize),
+    Negative,
+    // Başvurulan kayıt (örn. kurum) bulunamadı
+    NotFound,
+    AlreadyExists,
+    NotAllowed,
+}
+
+impl FieldError {
+    pub fn new(field: &'static str, issue: FieldIssue) -> Self {
+        FieldError { field, issue }
+    }
+}
+
+impl FieldIssue {
+    pub fn code(self) -> &'static str {
+        match self {
+            FieldIssue::Required => "REQUIRED",
+            FieldIssue::InvalidFormat => "INVALID_FORMAT",
+            FieldIssue::TooLong(_) => "TOO_LONG",
+            FieldIssue::Negative => "NEGATIVE",
+            FieldIssue::NotFound => "NOT_FOUND",
+            FieldIssue::AlreadyExists => "ALREADY_EXISTS",
+            FieldIssue::NotAllowed => "NOT_ALLOWED",
+        }
+    }
+
+    pub fn message(self, lang: Language) -> String {
+        let en = lang == Language::En;
+        match self {
+            FieldIssue::Required if en => "Field is required".to_string(),
+            FieldIssue::Required => "Alan zorunludur".to_string(),
+            FieldIssue::InvalidFormat if en => "Invalid format".to_string(),
+            FieldIssue::InvalidFormat => "Format geçersiz".to_string(),
+            FieldIssue::TooLong(max) if en => format!("Must be at most {} characters", max),
+            FieldIssue::TooLong(max) => format!("En fazla {} karakter olabilir", max),
+            FieldIssue::Negative if en => "Must not be negative".to_string(),
+            FieldIssue::Negative => "Negatif olamaz".to_string(),
+            FieldIssue::NotFound if en => "Referenced record not found".to_string(),
+            FieldIssue::NotFound => "Başvurulan kayıt bulunamadı".to_string(),
+            FieldIssue::AlreadyExists if en => "Value is already in use".to_string(),
+            FieldIssue::AlreadyExists => "Bu değer zaten kullanılıyor".to_string(),
+            FieldIssue::NotAllowed if en => "Value is not allowed".to_string(),
+            FieldIssue::NotAllowed => "Bu değere izin verilmiyor".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Tr,
+    En,
+}
+
+impl Language {
+    // Accept-Language başlığından dil seç; varsayılan Türkçe
+    pub fn from_accept_language(header: Option<&str>) -> Self {
+        match header {
+            Some(value) if value.trim_start().to_ascii_lowercase().starts_with("en") => Language::En,
+            _ => Language::Tr,
+        }
+    }
+}
+
+// Hata gövdesi formatı: v2 zarfı ya da eski istemciler için düz format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    V2,
+    Legacy,
+}
+
+impl ErrorFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "v2" => Some(ErrorFormat::V2),
+            "legacy" | "v1" => Some(ErrorFormat::Legacy),
+            _ => None,
+        }
+    }
+}
+
+impl Resource {
+    fn failure_message(self, lang: Language) -> &'static str {
+        match (self, lang) {
+            (Resource::Documents, Language::Tr) => "Belgeler alınamadı",
+            (Resource::Documents, Language::En) => "Failed to fetch documents",
+            (Resource::Document, Language::Tr) => "Belge alınamadı",
+            (Resource::Document, Language::En) => "Failed to fetch document",
+            (Resource::DocumentFilters, Language::Tr) => "Filtre listeleri alınamadı",
+            (Resource::DocumentFilters, Language::En) => "Failed to fetch document filters",
+            (Resource::Institutions, Language::Tr) => "Kurumlar listesi alınamadı",
+            (Resource::Institutions, Language::En) => "Failed to fetch institutions",
+            (Resource::Institution, Language::Tr) => "Kurum sorgulanamadı",
+            (Resource::Institution, Language::En) => "Failed to fetch institution",
+            (Resource::Links, Language::Tr) => "Kurum linkleri alınamadı",
+            (Resource::Links, Language::En) => "Failed to fetch links",
+            (Resource::Announcements, Language::Tr) => "Duyurular alınamadı",
+            (Resource::Announcements, Language::En) => "Failed to fetch announcements",
+            (Resource::AnnouncementSource, Language::Tr) => "Kurum duyuru linki alınamadı",
+            (Resource::AnnouncementSource, Language::En) => "Failed to fetch announcement source",
+            (Resource::Regulations, Language::Tr) => "Mevzuatlar alınamadı",
+            (Resource::Regulations, Language::En) => "Failed to fetch regulations",
+            (Resource::Search, Language::Tr) => "Arama yapılamadı",
+            (Resource::Search, Language::En) => "Search failed",
+            (Resource::Statistics, Language::Tr) => "İstatistikler alınamadı",
+            (Resource::Statistics, Language::En) => "Failed to fetch statistics",
+            (Resource::Sitemap, Language::Tr) => "Sitemap belgeleri alınamadı",
+            (Resource::Sitemap, Language::En) => "Failed to fetch sitemap documents",
+            (Resource::ApiKeys, Language::Tr) => "API anahtarı doğrulanamadı",
+            (Resource::ApiKeys, Language::En) => "Failed to verify API key",
+            (Resource::DocumentWrite, Language::Tr) => "Belge kaydedilemedi",
+            (Resource::DocumentWrite, Language::En) => "Failed to save document",
+            (Resource::InstitutionWrite, Language::Tr) => "Kurum kaydedilemedi",
+            (Resource::InstitutionWrite, Language::En) => "Failed to save institution",
+            (Resource::AnnouncementSourceWrite, Language::Tr) => "Kurum duyuru linki kaydedilemedi",
+            (Resource::AnnouncementSourceWrite, Language::En) => "Failed to save announcement source",
+            (Resource::LinkWrite, Language::Tr) => "Kurum linki kaydedilemedi",
+            (Resource::LinkWrite, Language::En) => "Failed to save link",
+            (Resource::Audit, Language::Tr) => "Denetim kayıtları alınamadı",
+            (Resource::Audit, Language::En) => "Failed to fetch audit log",
+            (Resource::ScrapedAnnouncements, Language::Tr) => "Kurum duyuruları alınamadı",
+            (Resource::ScrapedAnnouncements, Language::En) => "Failed to fetch institution announcements",
+            (Resource::Webhooks, Language::Tr) => "Webhook abonelikleri alınamadı",
+            (Resource::Webhooks, Language::En) => "Failed to fetch webhooks",
+            (Resource::WebhookWrite, Language::Tr) => "Webhook aboneliği kaydedilemedi",
+            (Resource::WebhookWrite, Language::En) => "Failed to save webhook",
+            (Resource::WebhookDeliveries, Language::Tr) => "Webhook teslimatları alınamadı",
+            (Resource::WebhookDeliveries, Language::En) => "Failed to fetch webhook deliveries",
+            (Resource::SavedSearches, Language::Tr) => "Kayıtlı aramalar alınamadı",
+            (Resource::SavedSearches, Language::En) => "Failed to fetch saved searches",
+            (Resource::SavedSearchWrite, Language::Tr) => "Kayıtlı arama kaydedilemedi",
+            (Resource::SavedSearchWrite, Language::En) => "Failed to save saved search",
+            (Resource::SearchAlerts, Language::Tr) => "Arama bildirimleri alınamadı",
+            (Resource::SearchAlerts, Language::En) => "Failed to fetch search alerts",
+        }
+    }
+
+    // Eski formatta bu kaynağın data alanı liste değil tekil nesneydi
+    fn is_single(self) -> bool {
+        matches!(
+            self,
+            Resource::Document
+                | Resource::DocumentFilters
+                | Resource::Institution
+                | Resource::Statistics
+                | Resource::DocumentWrite
+                | Resource::InstitutionWrite
+                | Resource::AnnouncementSourceWrite
+                | Resource::LinkWrite
+                | Resource::WebhookWrite
+                | Resource::SavedSearchWrite
+        )
+    }
+}
+
+impl ApiError {
+    // map_err ile kullanım için: .map_err(ApiError::storage(Resource::Documents))
+    pub fn storage(resource: Resource) -> impl FnOnce(RepositoryError) -> ApiError {
+        move |e| ApiError::Storage(resource, e)
+    }
+
+    // Sabit, makine tarafından okunabilir hata kodu
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::EmptySearchQuery => "EMPTY_SEARCH_QUERY",
+            ApiError::InvalidSearchQuery => "INVALID_SEARCH_QUERY",
+            ApiError::MissingParameter(_) => "MISSING_PARAMETER",
+            ApiError::InvalidParameter(_) => "INVALID_PARAMETER",
+            ApiError::InvalidQueryString(_) => "INVALID_QUERY_STRING",
+            ApiError::InvalidBody(_) => "INVALID_BODY",
+            ApiError::Validation(_) => "VALIDATION_FAILED",
+            ApiError::RouteNotFound => "ROUTE_NOT_FOUND",
+            ApiError::CorpusNotFound(_) => "CORPUS_NOT_FOUND",
+            ApiError::DocumentNotFound => "DOCUMENT_NOT_FOUND",
+            ApiError::InstitutionNotFound => "INSTITUTION_NOT_FOUND",
+            ApiError::AnnouncementSourceNotFound => "ANNOUNCEMENT_SOURCE_NOT_FOUND",
+            ApiError::AnnouncementLinkMissing => "ANNOUNCEMENT_LINK_MISSING",
+            ApiError::LinkNotFound => "LINK_NOT_FOUND",
+            ApiError::WebhookNotFound => "WEBHOOK_NOT_FOUND",
+            ApiError::DeliveryNotFound => "WEBHOOK_DELIVERY_NOT_FOUND",
+            ApiError::SavedSearchNotFound => "SAVED_SEARCH_NOT_FOUND",
+            ApiError::AlertNotFound => "SEARCH_ALERT_NOT_FOUND",
+            ApiError::InstitutionInUse(_) => "INSTITUTION_IN_USE",
+            ApiError::MissingApiKey => "API_KEY_REQUIRED",
+            ApiError::InvalidApiKey => "INVALID_API_KEY",
+            ApiError::InsufficientScope(_) => "INSUFFICIENT_SCOPE",
+            ApiError::QuotaExceeded => "QUOTA_EXCEEDED",
+            ApiError::RateLimited(_) => "RATE_LIMITED",
+            ApiError::Storage(..) => "STORAGE_ERROR",
+            ApiError::Upstream(_) => "UPSTREAM_ERROR",
+            ApiError::PdfUnavailable(_) => "PDF_UNAVAILABLE",
+            ApiError::InvalidPdf(_) => "INVALID_PDF",
+            ApiError::PdfNoText => "PDF_NO_TEXT",
+            ApiError::PdfTooLarge(_) => "PDF_TOO_LARGE",
+        }
+    }
+
+    pub fn message(&self, lang: Language) -> String {
+        let en = lang == Language::En;
+        match self {
+            ApiError::EmptySearchQuery if en => "Search query must not be empty".to_string(),
+            ApiError::EmptySearchQuery => "Arama sorgusu boş olamaz".to_string(),
+            ApiError::InvalidSearchQuery if en => "Invalid search query".to_string(),
+            ApiError::InvalidSearchQuery => "Geçersiz arama sorgusu".to_string(),
+            ApiError::MissingParameter(name) if en => format!("{} parameter is required", name),
+            ApiError::MissingParameter(name) => format!("{} parametresi zorunludur", name),
+            ApiError::InvalidParameter(name) if en => format!("Invalid {} format", name),
+            ApiError::InvalidParameter(name) => format!("{} formatı geçersiz", name),
+            ApiError::InvalidQueryString(detail) if en => format!("Invalid query string: {}", detail),
+            ApiError::InvalidQueryString(detail) => format!("Geçersiz sorgu parametresi: {}", detail),
+            ApiError::InvalidBody(detail) if en => format!("Invalid request body: {}", detail),
+            ApiError::InvalidBody(detail) => format!("Geçersiz istek gövdesi: {}", detail),
+            ApiError::Validation(fields) if en => format!("Validation failed for {} field(s)", fields.len()),
+            ApiError::Validation(fields) => format!("{} alan doğrulanamadı", fields.len()),
+            ApiError::RouteNotFound if en => "Route not found".to_string(),
+            ApiError::RouteNotFound => "Endpoint bulunamadı".to_string(),
+            ApiError::CorpusNotFound(key) if en => format!("Corpus not found: {}", key),
+            ApiError::CorpusNotFound(key) => format!("Corpus bulunamadı: {}", key),
+            ApiError::DocumentNotFound if en => "Document not found".to_string(),
+            ApiError::DocumentNotFound => "Belge bulunamadı".to_string(),
+            ApiError::InstitutionNotFound if en => "Institution not found".to_string(),
+            ApiError::InstitutionNotFound => "Kurum bulunamadı".to_string(),
+            ApiError::AnnouncementSourceNotFound if en => "Announcement link not found for institution".to_string(),
+            ApiError::AnnouncementSourceNotFound => "Kurum duyuru linki bulunamadı".to_string(),
+            ApiError::AnnouncementLinkMissing if en => "No announcement link defined for institution".to_string(),
+            ApiError::AnnouncementLinkMissing => "Kurum için duyuru linki tanımlanmamış".to_string(),
+            ApiError::LinkNotFound if en => "Link not found".to_string(),
+            ApiError::LinkNotFound => "Kurum linki bulunamadı".to_string(),
+            ApiError::WebhookNotFound if en => "Webhook not found".to_string(),
+            ApiError::WebhookNotFound => "Webhook aboneliği bulunamadı".to_string(),
+            ApiError::DeliveryNotFound if en => "Webhook delivery not found".to_string(),
+            ApiError::DeliveryNotFound => "Webhook teslimatı bulunamadı".to_string(),
+            ApiError::SavedSearchNotFound if en => "Saved search not found".to_string(),
+            ApiError::SavedSearchNotFound => "Kayıtlı arama bulunamadı".to_string(),
+            ApiError::AlertNotFound if en => "Search alert not found".to_string(),
+            ApiError::AlertNotFound => "Arama bildirimi bulunamadı".to_string(),
+            ApiError::InstitutionInUse(count) if en => {
+                format!("Institution is referenced by {} document(s); move or delete them first", count)
+            }
+            ApiError::InstitutionInUse(count) => {
+                format!("Kuruma bağlı {} belge var; önce belgeleri taşıyın ya da silin", count)
+            }
+            ApiError::MissingApiKey if en => "API key is required".to_string(),
+            ApiError::MissingApiKey => "API anahtarı gerekli".to_string(),
+            ApiError::InvalidApiKey if en => "Invalid or revoked API key".to_string(),
+            ApiError::InvalidApiKey => "API anahtarı geçersiz ya da iptal edilmiş".to_string(),
+            ApiError::InsufficientScope(scope) if en => format!("API key lacks the {} scope", scope),
+            ApiError::InsufficientScope(scope) => format!("API anahtarının {} yetkisi yok", scope),
+            ApiError::QuotaExceeded if en => "Daily API key quota exceeded".to_string(),
+            ApiError::QuotaExceeded => "API anahtarının günlük kotası doldu".to_string(),
+            ApiError::RateLimited(seconds) if en => format!("Too many requests, retry in {} seconds", seconds),
+            ApiError::RateLimited(seconds) => format!("Çok fazla istek, {} saniye sonra tekrar deneyin", seconds),
+            ApiError::Storage(resource, _) => resource.failure_message(lang).to_string(),
+            ApiError::Upstream(_) if en => "Failed to fetch announcement page".to_string(),
+            ApiError::Upstream(_) => "Duyuru sayfası çekilemedi".to_string(),
+            ApiError::PdfUnavailable(_) if en => "Failed to fetch PDF".to_string(),
+            ApiError::PdfUnavailable(_) => "PDF alınamadı".to_string(),
+            ApiError::InvalidPdf(_) if en => "Invalid or unreadable PDF".to_string(),
+            ApiError::InvalidPdf(_) => "PDF geçersiz ya da okunamadı".to_string(),
+            ApiError::PdfNoText if en => "PDF has no extractable text (scanned document?)".to_string(),
+            ApiError::PdfNoText => "PDF'te çıkarılabilir metin yok (taranmış belge olabilir)".to_string(),
+            ApiError::PdfTooLarge(max) if en => format!("PDF exceeds the {} MB limit", max / (1024 * 1024)),
+            ApiError::PdfTooLarge(max) => format!("PDF {} MB sınırını aşıyor", max / (1024 * 1024)),
+        }
+    }
+
+    // Loglanacak iç hata ayrıntısı (istemciye gönderilmez)
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            ApiError::Storage(_, e) => Some(e.to_string()),
+            ApiError::Upstream(detail) | ApiError::PdfUnavailable(detail) | ApiError::InvalidPdf(detail) => {
+                Some(detail.clone())
+            }
+            _ => None,
+        }
+    }
+
+    // Doğrulama hatasındaki alanlar (diğer hatalarda boş)
+    pub fn fields(&self) -> &[FieldError] {
+        match self {
+            ApiError::Validation(fields) => fields,
+            _ => &[],
+        }
+    }
+
+    pub fn render(&self, format: ErrorFormat, lang: Language, request_id: Option<&str>) -> HttpResponse {
+        let message = self.message(lang);
+        let request_id = request_id.map(|id| id.to_string());
+        let fields: Vec<FieldErrorDetail> = self
+            .fields()
+            .iter()
+            .map(|field| FieldErrorDetail {
+                field: field.field,
+                code: field.issue.code(),
+                message: field.issue.message(lang),
+            })
+            .collect();
+        let mut response = HttpResponse::build(self.status_code());
+
+        match format {
+            ErrorFormat::V2 => response.json(ErrorEnvelope {
+                success: false,
+                error: ErrorDetail {
+                    code: self.code(),
+                    message,
+                    request_id,
+                    fields,
+                },
+            }),
+            ErrorFormat::Legacy => {
+                let single = match self {
+                    ApiError::Storage(resource, _) => resource.is_single(),
+                    ApiError::DocumentNotFound
+                    | ApiError::InstitutionNotFound
+                    | ApiError::LinkNotFound
+                    | ApiError::WebhookNotFound
+                    | ApiError::DeliveryNotFound
+                    | ApiError::SavedSearchNotFound
+                    | ApiError::AlertNotFound
+                    | ApiError::InstitutionInUse(_) => true,
+                    _ => false,
+                };
+                response.json(LegacyErrorEnvelope {
+                    success: false,
+                    data: if single { Value::Null } else { Value::Array(vec![]) },
+                    count: 0,
+                    message: message.clone(),
+                    error: message,
+                    code: self.code(),
+                    request_id,
+                    fields,
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.detail() {
+            Some(detail) => write!(f, "{}: {}", self.code(), detail),
+            None => write!(f, "{}", self.code()),
+        }
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::EmptySearchQuery
+            | ApiError::InvalidSearchQuery
+            | ApiError::MissingParameter(_)
+            | ApiError::InvalidParameter(_)
+            | ApiError::InvalidQueryString(_)
+            | ApiError::InvalidBody(_) => StatusCode::BAD_REQUEST,
+            ApiError::Validation(_) | ApiError::InvalidPdf(_) | ApiError::PdfNoText => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::PdfTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::RouteNotFound
+            | ApiError::CorpusNotFound(_)
+            | ApiError::DocumentNotFound
+            | ApiError::InstitutionNotFound
+            | ApiError::AnnouncementSourceNotFound
+            | ApiError::AnnouncementLinkMissing
+            | ApiError::LinkNotFound
+            | ApiError::WebhookNotFound
+            | ApiError::DeliveryNotFound
+            | ApiError::SavedSearchNotFound
+            | ApiError::AlertNotFound => StatusCode::NOT_FOUND,
+            ApiError::InstitutionInUse(_) => StatusCode::CONFLICT,
+            ApiError::MissingApiKey | ApiError::InvalidApiKey => StatusCode::UNAUTHORIZED,
+            ApiError::InsufficientScope(_) => StatusCode::FORBIDDEN,
+            ApiError::QuotaExceeded | ApiError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::Storage(..) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Upstream(_) | ApiError::PdfUnavailable(_) => StatusCode::BAD_GATEWAY,
+        }
+    }
+
+    // Middleware olmadan (örn. testlerde) v2 formatında, request id'siz döner;
+    // request_context middleware'i bu cevabı istek bilgileriyle yeniden üretir
+    fn error_response(&self) -> HttpResponse {
+        self.render(ErrorFormat::V2, Language::Tr, None)
+    }
+}
@@ -0,0 +1,164 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+
+// RSS 2.0 ve Atom beslemeleri. Handler'lar kayıtları Feed/FeedEntry'ye çevirir; XML burada
+// üretilir. Girdi kimlikleri (RSS guid, Atom id) kayıt id'sinden üretilen tag URI'leridir:
+// slug ya da site adresi değişse de aynı kayıt okuyucularda yeniden "yeni" görünmez.
+
+// Kimliklerin sabit kalması için site adresinden bağımsızdır; değiştirilmemelidir
+const TAG_PREFIX: &str = "tag:mevzuatgpt.org,2024:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+impl FeedFormat {
+    // URL uzantısından: "xml" RSS, "atom" Atom
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "xml" | "rss" => Some(FeedFormat::Rss),
+            "atom" => Some(FeedFormat::Atom),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FeedEntry {
+    // Kaydın türü ve id'si, örn. "mevzuat/66f1..."; tag URI'ye çevrilir
+    pub id: String,
+    pub title: String,
+    pub link: String,
+    pub summary: Option<String>,
+    // Okunamayan tarih için None; tarih uydurulmaz
+    pub published: Option<DateTime<Utc>>,
+    pub categories: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Feed {
+    // Beslemenin kimliği, örn. "feeds/regulations"
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    // Beslemenin karşılığı olan sayfa
+    pub link: String,
+    // Beslemenin kendi adresi (uzantı hariç; biçime göre .xml ya da .atom eklenir)
+    pub self_link: String,
+    pub entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    // En yeni girdinin tarihi; Last-Modified ve Atom updated için kullanılır
+    pub fn updated(&self) -> Option<DateTime<Utc>> {
+        self.entries.iter().filter_map(|entry| entry.published).max()
+    }
+
+    pub fn render(&self, format: FeedFormat) -> String {
+        match format {
+            FeedFormat::Rss => self.to_rss(),
+            FeedFormat::Atom => self.to_atom(),
+        }
+    }
+
+    fn to_rss(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n<channel>\n");
+        xml.push_str(&format!("  <title>{}</title>\n", escape(&self.title)));
+        xml.push_str(&format!("  <link>{}</link>\n", escape(&self.link)));
+        xml.push_str(&format!("  <description>{}</description>\n", escape(&self.description)));
+        xml.push_str("  <language>tr</language>\n");
+        xml.push_str(&format!(
+            "  <atom:link href=\"{}.xml\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+            escape(&self.self_link)
+        ));
+        if let Some(updated) = self.updated() {
+            xml.push_str(&format!("  <lastBuildDate>{}</lastBuildDate>\n", updated.to_rfc2822()));
+        }
+        for entry in &self.entries {
+            xml.push_str("  <item>\n");
+            xml.push_str(&format!("    <title>{}</title>\n", escape(&entry.title)));
+            xml.push_str(&format!("    <link>{}</link>\n", escape(&entry.link)));
+            xml.push_str(&format!("    <guid isPermaLink=\"false\">{}{}</guid>\n", TAG_PREFIX, escape(&entry.id)));
+            if let Some(published) = entry.published {
+                xml.push_str(&format!("    <pubDate>{}</pubDate>\n", published.to_rfc2822()));
+            }
+            if let Some(summary) = &entry.summary {
+                xml.push_str(&format!("    <description>{}</description>\n", escape(summary)));
+            }
+            for category in &entry.categories {
+                xml.push_str(&format!("    <category>{}</category>\n", escape(category)));
+            }
+            xml.push_str("  </item>\n");
+        }
+        xml.push_str("</channel>\n</rss>\n");
+        xml
+    }
+
+    // Atom'da updated zorunludur: tarihi olmayan girdiye beslemenin tarihi, hiç tarih yoksa
+    // Unix başlangıcı yazılır (aynı içerik her istekte aynı çıktıyı versin diye şimdiki zaman değil)
+    fn to_atom(&self) -> String {
+        let updated = self.updated().unwrap_or(DateTime::UNIX_EPOCH);
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\" xml:lang=\"tr\">\n");
+        xml.push_str(&format!("  <id>{}{}</id>\n", TAG_PREFIX, escape(&self.id)));
+        xml.push_str(&format!("  <title>{}</title>\n", escape(&self.title)));
+        xml.push_str(&format!("  <subtitle>{}</subtitle>\n", escape(&self.description)));
+        xml.push_str(&format!("  <link href=\"{}\" rel=\"alternate\" type=\"text/html\"/>\n", escape(&self.link)));
+        xml.push_str(&format!(
+            "  <link href=\"{}.atom\" rel=\"self\" type=\"application/atom+xml\"/>\n",
+            escape(&self.self_link)
+        ));
+        xml.push_str(&format!("  <updated>{}</updated>\n", atom_date(updated)));
+        xml.push_str("  <author><name>MevzuatGPT</name></author>\n");
+        for entry in &self.entries {
+            xml.push_str("  <entry>\n");
+            xml.push_str(&format!("    <id>{}{}</id>\n", TAG_PREFIX, escape(&entry.id)));
+            xml.push_str(&format!("    <title>{}</title>\n", escape(&entry.title)));
+            xml.push_str(&format!("    <link href=\"{}\" rel=\"alternate\"/>\n", escape(&entry.link)));
+            xml.push_str(&format!("    <updated>{}</updated>\n", atom_date(entry.published.unwrap_or(updated))));
+            if let Some(published) = entry.published {
+                xml.push_str(&format!("    <published>{}</published>\n", atom_date(published)));
+            }
+            if let Some(summary) = &entry.summary {
+                xml.push_str(&format!("    <summary>{}</summary>\n", escape(summary)));
+            }
+            for category in &entry.categories {
+                xml.push_str(&format!("    <category term=\"{}\"/>\n", escape(category)));
+            }
+            xml.push_str("  </entry>\n");
+        }
+        xml.push_str("</feed>\n");
+        xml
+    }
+}
+
+fn atom_date(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+// XML metin ve attribute kaçışı; XML 1.0'da geçersiz kontrol karakterleri atılır
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,102 @@
+use actix_web::{web, HttpResponse};
+use utoipa::IntoParams;
+use crate::config::AppState;
+use crate::error::{ApiError, Resource};
+use crate::handlers::admin_documents::non_empty;
+use crate::models::error::ErrorEnvelope;
+use crate::models::kurum_duyuru_scraped::{
+    AnnouncementAttachmentListResponse, AnnouncementAttachmentsResponse, AttachmentCandidateResponse,
+};
+use crate::repository::{Page, ScrapedAnnouncementFilter};
+
+// Yönetim API'si: detay sayfasında PDF/DOCX eki bulunan duyurular. Her ek için pdf_url'si
+// aynı olan mevzuat belgesi aranır; belge_id boş olan ekler POST /api/admin/documents/ingest
+// ile belge olarak alınabilir.
+
+const DEFAULT_LIMIT: u64 = 50;
+const MAX_LIMIT: u64 = 200;
+
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AnnouncementAttachmentsQuery {
+    pub kurum_id: Option<String>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/announcement-attachments",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(AnnouncementAttachmentsQuery),
+    responses(
+        (status = 200, description = "Eki bulunan duyurular (en yeni önce)", body = AnnouncementAttachmentListResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn list_attachments(
+    state: web::Data<AppState>,
+    query: web::Query<AnnouncementAttachmentsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let filter = ScrapedAnnouncementFilter {
+        kurum_id: non_empty(&query.kurum_id).map(str::to_string),
+        with_attachments: true,
+        ..ScrapedAnnouncementFilter::default()
+    };
+    let page = Page {
+        offset: query.offset.unwrap_or(0),
+        limit: query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+    };
+
+    let stored = state.repos.scraped_announcements.as_ref();
+    let records = stored
+        .list(&filter, page)
+        .await
+        .map_err(ApiError::storage(Resource::ScrapedAnnouncements))?;
+    let count = stored
+        .count(&filter)
+        .await
+        .map_err(ApiError::storage(Resource::ScrapedAnnouncements))?;
+
+    let corpus = state.corpora.mevzuat();
+    let mut data = Vec::with_capacity(records.len());
+    for record in records {
+        let mut ekler = Vec::with_capacity(record.ekler.len());
+        for ek in record.ekler {
+            let belge = state
+                .repos
+                .documents
+                .find_by_pdf_url(corpus, &ek.url)
+                .await
+                .map_err(ApiError::storage(Resource::Document))?;
+            ekler.push(AttachmentCandidateResponse {
+                url: ek.url,
+                baslik: ek.baslik,
+                tur: ek.tur,
+                belge_id: belge.map(|belge| belge.id),
+            });
+        }
+        data.push(AnnouncementAttachmentsResponse {
+            duyuru_id: record.id,
+            kurum_id: record.kurum_id,
+            baslik: record.baslik,
+            link: record.link,
+            tarih: record.tarih,
+            ekler,
+        });
+    }
+
+    Ok(HttpResponse::Ok().json(AnnouncementAttachmentListResponse {
+        success: true,
+        message: format!("Eki bulunan {} duyuru bulundu", data.len()),
+        data,
+        count,
+    }))
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(list_attachments));
+}
@@ -0,0 +1,270 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use crate::audit::Actor;
+use crate::config::AppState;
+use crate::error::{ApiError, FieldError, FieldIssue, Resource};
+use crate::handlers::admin_documents::{actor, is_http_url, non_empty};
+use crate::handlers::admin_institutions::validate_kurum_id;
+use crate::models::admin_institution::{
+    AnnouncementSourceData, AnnouncementSourceDetailResponse, AnnouncementSourceInput, DeleteRecordResponse,
+    DeletedRecord,
+};
+use crate::models::error::ErrorEnvelope;
+use crate::repository::{AnnouncementSourceRecord, AnnouncementSourceWrite};
+
+// Yönetim API'si: kurum duyuru sayfaları (kurum_duyuru). Her kurumun tek duyuru
+// linki olabilir; duyuru hasadı (harvester) bu linki, varsa `scraper` kurallarıyla tarar.
+
+const BASLIK_MAX_LEN: usize = 200;
+
+async fn prepare(
+    state: &AppState,
+    input: AnnouncementSourceInput,
+    existing: Option<&AnnouncementSourceRecord>,
+    partial: bool,
+) -> Result<AnnouncementSourceWrite, ApiError> {
+    let id = existing.map(|record| record.id.as_str());
+    let mut errors = Vec::new();
+
+    validate_kurum_id(
+        state,
+        &input.kurum_id,
+        input.kurum_id.is_some() || !partial,
+        Resource::AnnouncementSourceWrite,
+        &mut errors,
+    )
+    .await?;
+    match non_empty(&input.duyuru_linki) {
+        None if input.duyuru_linki.is_some() || !partial => {
+            errors.push(FieldError::new("duyuru_linki", FieldIssue::Required))
+        }
+        None => {}
+        Some(link) if !is_http_url(link) => errors.push(FieldError::new("duyuru_linki", FieldIssue::InvalidFormat)),
+        Some(_) => {}
+    }
+    if non_empty(&input.baslik).is_some_and(|baslik| baslik.chars().count() > BASLIK_MAX_LEN) {
+        errors.push(FieldError::new("baslik", FieldIssue::TooLong(BASLIK_MAX_LEN)));
+    }
+    if let Some(rules) = &input.scraper {
+        errors.extend(rules.validate());
+    }
+
+    let kurum_id = match non_empty(&input.kurum_id) {
+        Some(kurum_id) => kurum_id.to_string(),
+        None => existing.map(|record| record.kurum_id.clone()).unwrap_or_default(),
+    };
+    if !errors.iter().any(|e| e.field == "kurum_id") {
+        let current = state
+            .repos
+            .announcement_sources
+            .find_by_kurum(&kurum_id)
+            .await
+            .map_err(ApiError::storage(Resource::AnnouncementSourceWrite))?;
+        if current.is_some_and(|record| Some(record.id.as_str()) != id) {
+            errors.push(FieldError::new("kurum_id", FieldIssue::AlreadyExists));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    Ok(AnnouncementSourceWrite {
+        kurum_id,
+        duyuru_linki: match non_empty(&input.duyuru_linki) {
+            Some(link) => link.to_string(),
+            None => existing.map(|record| record.duyuru_linki.clone()).unwrap_or_default(),
+        },
+        baslik: match input.baslik {
+            Some(baslik) => Some(baslik.trim().to_string()).filter(|baslik| !baslik.is_empty()),
+            None if partial => existing.and_then(|record| record.baslik.clone()),
+            None => None,
+        },
+        scraper: match input.scraper {
+            Some(rules) => Some(rules),
+            None if partial => existing.and_then(|record| record.scraper.clone()),
+            None => None,
+        },
+    })
+}
+
+async fn find_existing(state: &AppState, id: &str) -> Result<AnnouncementSourceRecord, ApiError> {
+    state
+        .repos
+        .announcement_sources
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::storage(Resource::AnnouncementSource))?
+        .ok_or(ApiError::AnnouncementSourceNotFound)
+}
+
+async fn written(state: &AppState, id: &str) -> Result<AnnouncementSourceData, ApiError> {
+    let record = find_existing(state, id).await?;
+    Ok(AnnouncementSourceData {
+        id: record.id,
+        kurum_id: record.kurum_id,
+        duyuru_linki: record.duyuru_linki,
+        baslik: record.baslik,
+        scraper: record.scraper,
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/announcement-sources",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    request_body = AnnouncementSourceInput,
+    responses(
+        (status = 201, description = "Duyuru linki oluşturuldu", body = AnnouncementSourceDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn create_announcement_source(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    body: web::Json<AnnouncementSourceInput>,
+) -> Result<HttpResponse, ApiError> {
+    let source = prepare(&state, body.into_inner(), None, false).await?;
+    let id = state
+        .repos
+        .announcement_source_writes
+        .insert(&Actor::from_request(&req), &source)
+        .await
+        .map_err(ApiError::storage(Resource::AnnouncementSourceWrite))?;
+    log::info!("Duyuru linki oluşturuldu: kurum {} ({}) - anahtar: {}", source.kurum_id, id, actor(&req));
+
+    Ok(HttpResponse::Created().json(AnnouncementSourceDetailResponse {
+        success: true,
+        data: written(&state, &id).await?,
+        message: "Duyuru linki oluşturuldu".to_string(),
+    }))
+}
+
+async fn save_existing(
+    req: &HttpRequest,
+    state: &AppState,
+    id: &str,
+    input: AnnouncementSourceInput,
+    partial: bool,
+) -> Result<HttpResponse, ApiError> {
+    let existing = find_existing(state, id).await?;
+    let source = prepare(state, input, Some(&existing), partial).await?;
+
+    let updated = state
+        .repos
+        .announcement_source_writes
+        .update(&Actor::from_request(req), &existing.id, &source)
+        .await
+        .map_err(ApiError::storage(Resource::AnnouncementSourceWrite))?;
+    if !updated {
+        return Err(ApiError::AnnouncementSourceNotFound);
+    }
+    log::info!("Duyuru linki güncellendi: kurum {} ({}) - anahtar: {}", source.kurum_id, existing.id, actor(req));
+
+    Ok(HttpResponse::Ok().json(AnnouncementSourceDetailResponse {
+        success: true,
+        data: written(state, &existing.id).await?,
+        message: "Duyuru linki güncellendi".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/announcement-sources/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Duyuru linki kaydının _id değeri")),
+    request_body = AnnouncementSourceInput,
+    responses(
+        (status = 200, description = "Duyuru linki güncellendi", body = AnnouncementSourceDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn replace_announcement_source(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<AnnouncementSourceInput>,
+) -> Result<HttpResponse, ApiError> {
+    save_existing(&req, &state, &path, body.into_inner(), false).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/admin/announcement-sources/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Duyuru linki kaydının _id değeri")),
+    request_body = AnnouncementSourceInput,
+    responses(
+        (status = 200, description = "Duyuru linki güncellendi", body = AnnouncementSourceDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn update_announcement_source(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<AnnouncementSourceInput>,
+) -> Result<HttpResponse, ApiError> {
+    save_existing(&req, &state, &path, body.into_inner(), true).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/announcement-sources/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Duyuru linki kaydının _id değeri")),
+    responses(
+        (status = 200, description = "Duyuru linki silindi", body = DeleteRecordResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn delete_announcement_source(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let existing = find_existing(&state, &path).await?;
+    let deleted = state
+        .repos
+        .announcement_source_writes
+        .delete(&Actor::from_request(&req), &existing.id)
+        .await
+        .map_err(ApiError::storage(Resource::AnnouncementSourceWrite))?;
+    if !deleted {
+        return Err(ApiError::AnnouncementSourceNotFound);
+    }
+    log::info!("Duyuru linki silindi: kurum {} ({}) - anahtar: {}", existing.kurum_id, existing.id, actor(&req));
+
+    Ok(HttpResponse::Ok().json(DeleteRecordResponse {
+        success: true,
+        data: DeletedRecord { id: existing.id },
+        message: "Duyuru linki silindi".to_string(),
+    }))
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::post().to(create_announcement_source))
+        .route("/{id}", web::put().to(replace_announcement_source))
+        .route("/{id}", web::patch().to(update_announcement_source))
+        .route("/{id}", web::delete().to(delete_announcement_source));
+}
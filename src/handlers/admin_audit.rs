@@ -0,0 +1,185 @@
+use std::sync::Arc;
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use futures::stream;
+use utoipa::IntoParams;
+use crate::audit::{format_timestamp, AuditAction, AuditSource, AUDITED_COLLECTIONS};
+use crate::config::AppState;
+use crate::error::{ApiError, Resource};
+use crate::handlers::admin_documents::non_empty;
+use crate::models::audit::{AuditEntryResponse, AuditListResponse};
+use crate::models::error::ErrorEnvelope;
+use crate::repository::{AuditFilter, AuditRepository, Page};
+use crate::utils::parse_time_bound;
+
+// Yönetim API'si: denetim kaydının sorgulanması ve NDJSON olarak dışa aktarılması.
+// Kayıtlar yalnızca eklenir; bu modülde yazma endpoint'i yoktur.
+
+const DEFAULT_LIMIT: u64 = 100;
+const MAX_LIMIT: u64 = 1000;
+// Dışa aktarmada repository'den tek seferde okunan kayıt sayısı
+const EXPORT_BATCH: u64 = 500;
+
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditQuery {
+    // API anahtarı prefix'i ya da CLI kullanıcısı
+    pub actor: Option<String>,
+    // create, update, delete
+    pub action: Option<String>,
+    // api, cli, change_stream
+    pub source: Option<String>,
+    // metadata, content, kurumlar, kurum_duyuru, links
+    pub collection: Option<String>,
+    pub target_id: Option<String>,
+    // YYYY-MM-DD ya da RFC 3339; dahil
+    pub from: Option<String>,
+    // YYYY-MM-DD (o gün dahil) ya da RFC 3339 (hariç)
+    pub to: Option<String>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+fn filter_from(query: &AuditQuery) -> Result<AuditFilter, ApiError> {
+    let action = non_empty(&query.action);
+    if action.is_some_and(|action| AuditAction::parse(action).is_none()) {
+        return Err(ApiError::InvalidParameter("action"));
+    }
+    let source = non_empty(&query.source);
+    if source.is_some_and(|source| AuditSource::parse(source).is_none()) {
+        return Err(ApiError::InvalidParameter("source"));
+    }
+    let collection = non_empty(&query.collection);
+    if collection.is_some_and(|collection| !AUDITED_COLLECTIONS.contains(&collection)) {
+        return Err(ApiError::InvalidParameter("collection"));
+    }
+    let from = match non_empty(&query.from) {
+        Some(from) => Some(parse_time_bound(from, false).ok_or(ApiError::InvalidParameter("from"))?),
+        None => None,
+    };
+    let to = match non_empty(&query.to) {
+        Some(to) => Some(parse_time_bound(to, true).ok_or(ApiError::InvalidParameter("to"))?),
+        None => None,
+    };
+
+    Ok(AuditFilter {
+        actor: non_empty(&query.actor).map(str::to_string),
+        action: action.map(str::to_string),
+        source: source.map(str::to_string),
+        collection: collection.map(str::to_string),
+        target_id: non_empty(&query.target_id).map(str::to_string),
+        from,
+        to,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/audit",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(AuditQuery),
+    responses(
+        (status = 200, description = "Denetim kayıtları (en yeni önce)", body = AuditListResponse),
+        (status = 400, description = "Geçersiz filtre", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn list_audit(
+    state: web::Data<AppState>,
+    query: web::Query<AuditQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let filter = filter_from(&query)?;
+    let page = Page {
+        offset: query.offset.unwrap_or(0),
+        limit: query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+    };
+
+    let records = state
+        .repos
+        .audit
+        .list(&filter, page)
+        .await
+        .map_err(ApiError::storage(Resource::Audit))?;
+    let count = state
+        .repos
+        .audit
+        .count(&filter)
+        .await
+        .map_err(ApiError::storage(Resource::Audit))?;
+    let data: Vec<AuditEntryResponse> = records.into_iter().map(AuditEntryResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(AuditListResponse {
+        success: true,
+        message: format!("{} denetim kaydı bulundu", data.len()),
+        data,
+        count: Some(count),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/audit/export",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(AuditQuery),
+    responses(
+        (status = 200, description = "Filtreye uyan tüm kayıtlar, satır başına bir JSON nesnesi (NDJSON, en yeni önce)", content_type = "application/x-ndjson", body = AuditEntryResponse),
+        (status = 400, description = "Geçersiz filtre", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+    )
+)]
+pub async fn export_audit(
+    state: web::Data<AppState>,
+    query: web::Query<AuditQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let mut filter = filter_from(&query)?;
+    // Dışa aktarma sürerken eklenen kayıtlar sayfaları kaydırmasın
+    let started = format_timestamp(Utc::now());
+    if filter.to.as_ref().is_none_or(|to| *to > started) {
+        filter.to = Some(started);
+    }
+
+    let audit: Arc<dyn AuditRepository> = state.repos.audit.clone();
+    let body = stream::unfold(Some(0u64), move |offset| {
+        let audit = audit.clone();
+        let filter = filter.clone();
+        async move {
+            let offset = offset?;
+            let records = match audit.list(&filter, Page { offset, limit: EXPORT_BATCH }).await {
+                Ok(records) => records,
+                Err(e) => {
+                    // Yarım kalan dosya tam bir dışa aktarma gibi görünmesin diye akış hatayla kesilir
+                    log::error!("Denetim kaydı dışa aktarılamadı: {}", e);
+                    let error = ApiError::storage(Resource::Audit)(e);
+                    return Some((Err(error.into()), None));
+                }
+            };
+            if records.is_empty() {
+                return None;
+            }
+
+            let next = (records.len() as u64 == EXPORT_BATCH).then_some(offset + EXPORT_BATCH);
+            let mut chunk = Vec::new();
+            for record in records {
+                if serde_json::to_writer(&mut chunk, &AuditEntryResponse::from(record)).is_ok() {
+                    chunk.push(b'\n');
+                }
+            }
+            Some((Ok::<_, actix_web::Error>(web::Bytes::from(chunk)), next))
+        }
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/x-ndjson")
+        .insert_header(("Content-Disposition", "attachment; filename=\"audit.ndjson\""))
+        .streaming(body))
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(list_audit))
+        .route("/export", web::get().to(export_audit));
+}
@@ -0,0 +1,40 @@
+use actix_web::{web, HttpResponse};
+use crate::config::AppState;
+use crate::error::ApiError;
+use crate::models::crawler::{CrawlerHostListResponse, CrawlerHostResponse};
+use crate::models::error::ErrorEnvelope;
+
+// Yönetim API'si: kurum sitelerini tarayan Crawler'ın host bazlı durumu (devre kesici,
+// süren istekler, son hata). Durum süreç içinde tutulur; yeniden başlatmada sıfırlanır.
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/crawler/hosts",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    responses(
+        (status = 200, description = "Taranan host'lar ve devre kesici durumları", body = CrawlerHostListResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+    )
+)]
+pub async fn list_hosts(state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let data: Vec<CrawlerHostResponse> = state
+        .fetcher
+        .crawler()
+        .hosts()
+        .into_iter()
+        .map(CrawlerHostResponse::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(CrawlerHostListResponse {
+        success: true,
+        message: format!("{} host bulundu", data.len()),
+        count: data.len(),
+        data,
+    }))
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/hosts", web::get().to(list_hosts));
+}
@@ -0,0 +1,488 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use chrono::{NaiveDate, Utc};
+use mongodb::bson::oid::ObjectId;
+use crate::auth::ApiKeyIdentity;
+use crate::audit::Actor;
+use crate::config::AppState;
+use crate::corpus::Corpus;
+use crate::error::{ApiError, FieldError, FieldIssue, Resource};
+use crate::handlers::document::fetch_document_detail;
+use crate::ingest::{self, PdfSource};
+use crate::models::admin_document::{DeleteDocumentResponse, DeletedDocument, DocumentInput, IngestDocumentInput};
+use crate::models::document::{DocumentDetailData, DocumentDetailResponse};
+use crate::models::error::ErrorEnvelope;
+use crate::repository::{DocumentRecord, DocumentWrite, RepositoryError};
+use crate::utils::{format_timestamp, slugify};
+
+// Yönetim API'si: mevzuat belgelerini (metadata + content) yazar. Route'lar admin
+// scope'lu API anahtarı ister (auth::authenticate).
+
+const PDF_ADI_MAX_LEN: usize = 500;
+const SLUG_MAX_LEN: usize = 120;
+const STATUSES: [&str; 2] = ["aktif", "pasif"];
+// Aynı başlıklı belgeler için denenecek en fazla sayı eki (-2, -3, ...)
+const SLUG_SUFFIX_ATTEMPTS: usize = 100;
+// Slug kontrolüyle yazma arasında aynı slug'ı eşzamanlı bir istek almışsa yazmanın tekrar sayısı
+const SLUG_WRITE_ATTEMPTS: usize = 3;
+
+pub(crate) fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|value| !value.is_empty())
+}
+
+// Veritabanına bakmadan yapılabilen alan kontrolleri. partial (PATCH) ise yalnızca
+// gövdede gönderilen alanlar kontrol edilir; mevcut eski kayıt değerleri yeniden doğrulanmaz.
+fn validate(input: &DocumentInput, partial: bool) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let required = |value: &Option<String>| value.is_some() || !partial;
+
+    match non_empty(&input.pdf_adi) {
+        None if required(&input.pdf_adi) => errors.push(FieldError::new("pdf_adi", FieldIssue::Required)),
+        None => {}
+        Some(pdf_adi) if pdf_adi.chars().count() > PDF_ADI_MAX_LEN => {
+            errors.push(FieldError::new("pdf_adi", FieldIssue::TooLong(PDF_ADI_MAX_LEN)))
+        }
+        Some(_) => {}
+    }
+
+    match non_empty(&input.kurum_id) {
+        None if required(&input.kurum_id) => errors.push(FieldError::new("kurum_id", FieldIssue::Required)),
+        None => {}
+        Some(kurum_id) if ObjectId::parse_str(kurum_id).is_err() => {
+            errors.push(FieldError::new("kurum_id", FieldIssue::InvalidFormat))
+        }
+        Some(_) => {}
+    }
+
+    for (field, value) in [
+        ("belge_yayin_tarihi", &input.belge_yayin_tarihi),
+        ("yururluluk_tarihi", &input.yururluluk_tarihi),
+    ] {
+        if non_empty(value).is_some_and(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err()) {
+            errors.push(FieldError::new(field, FieldIssue::InvalidFormat));
+        }
+    }
+
+    if let Some(slug) = non_empty(&input.url_slug) {
+        if slug.len() > SLUG_MAX_LEN {
+            errors.push(FieldError::new("url_slug", FieldIssue::TooLong(SLUG_MAX_LEN)));
+        } else if slugify(slug) != slug {
+            errors.push(FieldError::new("url_slug", FieldIssue::InvalidFormat));
+        }
+    }
+
+    if input.status.as_deref().is_some_and(|status| !STATUSES.contains(&status)) {
+        errors.push(FieldError::new("status", FieldIssue::NotAllowed));
+    }
+
+    if input.sayfa_sayisi.is_some_and(|sayfa| sayfa < 0) {
+        errors.push(FieldError::new("sayfa_sayisi", FieldIssue::Negative));
+    }
+    match input.dosya_boyutu_mb {
+        Some(boyut) if !boyut.is_finite() => errors.push(FieldError::new("dosya_boyutu_mb", FieldIssue::InvalidFormat)),
+        Some(boyut) if boyut < 0.0 => errors.push(FieldError::new("dosya_boyutu_mb", FieldIssue::Negative)),
+        _ => {}
+    }
+
+    if non_empty(&input.pdf_url).is_some_and(|url| !is_http_url(url)) {
+        errors.push(FieldError::new("pdf_url", FieldIssue::InvalidFormat));
+    }
+
+    errors
+}
+
+// Host'u olan mutlak http(s) adresi
+pub(crate) fn is_http_url(value: &str) -> bool {
+    url::Url::parse(value)
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some_and(|host| !host.is_empty()))
+}
+
+// Slug başka bir belgede kullanılıyor mu (güncellenen belgenin kendisi hariç)
+async fn slug_taken(state: &AppState, corpus: &Corpus, slug: &str, id: Option<&str>) -> Result<bool, ApiError> {
+    let found = state
+        .repos
+        .documents
+        .find_by_slug(corpus, slug)
+        .await
+        .map_err(ApiError::storage(Resource::DocumentWrite))?;
+    Ok(found.is_some_and(|record| Some(record.id.as_str()) != id))
+}
+
+// Başlıktan benzersiz slug üret; çakışmada -2, -3, ... eki denenir
+async fn unique_slug(state: &AppState, corpus: &Corpus, pdf_adi: &str, id: Option<&str>) -> Result<String, ApiError> {
+    let mut base = slugify(pdf_adi);
+    base.truncate(SLUG_MAX_LEN);
+    let base = match base.trim_end_matches('-') {
+        "" => "belge".to_string(),
+        base => base.to_string(),
+    };
+
+    for attempt in 1..=SLUG_SUFFIX_ATTEMPTS {
+        let candidate = if attempt == 1 { base.clone() } else { format!("{}-{}", base, attempt) };
+        if !slug_taken(state, corpus, &candidate, id).await? {
+            return Ok(candidate);
+        }
+    }
+    Ok(format!("{}-{}", base, ObjectId::new().to_hex()))
+}
+
+// Gövdeyi doğrulayıp yazılacak belgeye çevirir. existing verilirse (PUT/PATCH)
+// oluşturulma tarihi ve slug'ı korunur; partial ise eksik alanlar existing'ten alınır.
+async fn prepare(
+    state: &AppState,
+    corpus: &Corpus,
+    input: DocumentInput,
+    existing: Option<&DocumentRecord>,
+    partial: bool,
+) -> Result<DocumentWrite, ApiError> {
+    let id = existing.map(|record| record.id.as_str());
+    let mut errors = validate(&input, partial);
+
+    if let Some(kurum_id) = non_empty(&input.kurum_id).filter(|id| ObjectId::parse_str(id).is_ok()) {
+        let kurum = state
+            .repos
+            .institutions
+            .find_by_id(kurum_id)
+            .await
+            .map_err(ApiError::storage(Resource::DocumentWrite))?;
+        if kurum.is_none() {
+            errors.push(FieldError::new("kurum_id", FieldIssue::NotFound));
+        }
+    }
+
+    let requested_slug = non_empty(&input.url_slug).map(str::to_string);
+    if let Some(slug) = &requested_slug {
+        if !errors.iter().any(|e| e.field == "url_slug") && slug_taken(state, corpus, slug, id).await? {
+            errors.push(FieldError::new("url_slug", FieldIssue::AlreadyExists));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    let input = match existing {
+        Some(record) if partial => merge_existing(input, record),
+        _ => input,
+    };
+    let kurum_id = non_empty(&input.kurum_id).unwrap_or_default().to_string();
+    let pdf_adi = non_empty(&input.pdf_adi).unwrap_or_default().to_string();
+    let url_slug = match (requested_slug, existing.filter(|record| !record.url_slug.is_empty())) {
+        (Some(slug), _) => slug,
+        (None, Some(record)) => record.url_slug.clone(),
+        (None, None) => unique_slug(state, corpus, &pdf_adi, id).await?,
+    };
+
+    let now = format_timestamp(Utc::now());
+    let text = |value: Option<String>| value.map(|v| v.trim().to_string()).unwrap_or_default();
+
+    Ok(DocumentWrite {
+        kurum_id,
+        pdf_adi,
+        aciklama: text(input.aciklama),
+        belge_turu: text(input.belge_turu),
+        belge_durumu: text(input.belge_durumu),
+        belge_yayin_tarihi: text(input.belge_yayin_tarihi),
+        yururluluk_tarihi: non_empty(&input.yururluluk_tarihi).map(str::to_string),
+        etiketler: text(input.etiketler),
+        anahtar_kelimeler: text(input.anahtar_kelimeler),
+        url_slug,
+        status: input.status.unwrap_or_else(|| corpus.fields.active_status.clone()),
+        pdf_url: text(input.pdf_url),
+        sayfa_sayisi: input.sayfa_sayisi.unwrap_or(0),
+        dosya_boyutu_mb: input.dosya_boyutu_mb.unwrap_or(0.0),
+        olusturulma_tarihi: existing
+            .map(|record| record.olusturulma_tarihi.clone())
+            .filter(|tarih| !tarih.is_empty())
+            .unwrap_or_else(|| now.clone()),
+        guncellenme_tarihi: now,
+        icerik: input.icerik,
+    })
+}
+
+// PATCH: gövdede olmayan alanlar mevcut kayıttan alınır (içerik hariç; verilmezse dokunulmaz)
+fn merge_existing(input: DocumentInput, record: &DocumentRecord) -> DocumentInput {
+    let keep = |value: Option<String>, current: &str| value.or_else(|| Some(current.to_string()));
+    DocumentInput {
+        pdf_adi: keep(input.pdf_adi, &record.pdf_adi),
+        kurum_id: keep(input.kurum_id, &record.kurum_id),
+        belge_turu: keep(input.belge_turu, &record.belge_turu),
+        belge_durumu: keep(input.belge_durumu, &record.belge_durumu),
+        belge_yayin_tarihi: keep(input.belge_yayin_tarihi, &record.belge_yayin_tarihi),
+        yururluluk_tarihi: input.yururluluk_tarihi.or_else(|| record.yururluluk_tarihi.clone()),
+        etiketler: keep(input.etiketler, &record.etiketler),
+        anahtar_kelimeler: keep(input.anahtar_kelimeler, &record.anahtar_kelimeler),
+        aciklama: keep(input.aciklama, &record.aciklama),
+        url_slug: input.url_slug,
+        status: input.status.or_else(|| Some(record.status.clone()).filter(|s| !s.is_empty())),
+        sayfa_sayisi: input.sayfa_sayisi.or(record.sayfa_sayisi),
+        dosya_boyutu_mb: input.dosya_boyutu_mb.or(record.dosya_boyutu_mb),
+        pdf_url: keep(input.pdf_url, &record.pdf_url),
+        icerik: input.icerik,
+    }
+}
+
+async fn find_existing(state: &AppState, corpus: &Corpus, id: &str) -> Result<DocumentRecord, ApiError> {
+    state
+        .repos
+        .documents
+        .find_by_id(corpus, id)
+        .await
+        .map_err(ApiError::storage(Resource::Document))?
+        .ok_or(ApiError::DocumentNotFound)
+}
+
+// Yazılan belgeyi okuma API'sindeki detay formatında döndür
+async fn written(state: &AppState, corpus: &Corpus, slug: &str) -> Result<DocumentDetailData, ApiError> {
+    fetch_document_detail(state, corpus, slug)
+        .await
+        .map_err(ApiError::storage(Resource::Document))?
+        .ok_or(ApiError::DocumentNotFound)
+}
+
+// İşlemi yapan anahtarın prefix'i (log için)
+pub(crate) fn actor(req: &HttpRequest) -> String {
+    req.extensions()
+        .get::<ApiKeyIdentity>()
+        .map(|identity| identity.prefix.clone())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/documents",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    request_body = DocumentInput,
+    responses(
+        (status = 201, description = "Belge oluşturuldu", body = DocumentDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn create_document(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    body: web::Json<DocumentInput>,
+) -> Result<HttpResponse, ApiError> {
+    let (id, document) = create(&state, &Actor::from_request(&req), body.into_inner()).await?;
+    log::info!("Belge oluşturuldu: {} ({}) - anahtar: {}", document.url_slug, id, actor(&req));
+
+    Ok(HttpResponse::Created().json(DocumentDetailResponse {
+        success: true,
+        data: written(&state, state.corpora.mevzuat(), &document.url_slug).await?,
+        message: "Belge oluşturuldu".to_string(),
+    }))
+}
+
+// Yazma sırasında url_slug unique index'ine takılan belge. Slug üretilmişse (gövdede yoksa)
+// yeniden hazırlanır ve bir sonraki ek denenir; gövdede verilmişse alan hatası döner.
+fn slug_conflict(e: RepositoryError, input: &DocumentInput) -> Result<(), ApiError> {
+    match e {
+        RepositoryError::Duplicate(field) if field == "url_slug" && non_empty(&input.url_slug).is_none() => Ok(()),
+        RepositoryError::Duplicate(field) if field == "url_slug" => Err(slug_taken_error()),
+        e => Err(ApiError::storage(Resource::DocumentWrite)(e)),
+    }
+}
+
+fn slug_taken_error() -> ApiError {
+    ApiError::Validation(vec![FieldError::new("url_slug", FieldIssue::AlreadyExists)])
+}
+
+// Yeni belgeyi doğrulayıp kaydeder; PDF alım hattı (ingest) da bu yolu kullanır
+pub async fn create(state: &AppState, actor: &Actor, input: DocumentInput) -> Result<(String, DocumentWrite), ApiError> {
+    let corpus = state.corpora.mevzuat();
+    for _ in 0..SLUG_WRITE_ATTEMPTS {
+        let document = prepare(state, corpus, input.clone(), None, false).await?;
+        match state.repos.document_writes.insert(actor, corpus, &document).await {
+            Ok(id) => return Ok((id, document)),
+            Err(e) => slug_conflict(e, &input)?,
+        }
+    }
+    Err(slug_taken_error())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/documents/ingest",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    request_body = IngestDocumentInput,
+    responses(
+        (status = 201, description = "PDF işlendi, belge oluşturuldu", body = DocumentDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 413, description = "PDF boyut sınırını aşıyor", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası, geçersiz ya da metinsiz PDF", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+        (status = 502, description = "PDF indirilemedi", body = ErrorEnvelope),
+    )
+)]
+pub async fn ingest_document(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    body: web::Json<IngestDocumentInput>,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+    // Yerel dosyalar yalnızca CLI'dan (--ingest-pdf) alınır
+    let source = match PdfSource::parse(&body.url) {
+        PdfSource::Url(url) if is_http_url(&url) => PdfSource::Url(url),
+        _ => return Err(ApiError::Validation(vec![FieldError::new("url", FieldIssue::InvalidFormat)])),
+    };
+
+    let (id, document) = ingest::ingest(&state, &Actor::from_request(&req), &source, body.metadata).await?;
+    log::info!(
+        "PDF işlendi: {} ({}, {} sayfa) - anahtar: {}",
+        document.url_slug,
+        id,
+        document.sayfa_sayisi,
+        actor(&req)
+    );
+
+    Ok(HttpResponse::Created().json(DocumentDetailResponse {
+        success: true,
+        data: written(&state, state.corpora.mevzuat(), &document.url_slug).await?,
+        message: "PDF işlendi, belge oluşturuldu".to_string(),
+    }))
+}
+
+async fn save_existing(
+    req: &HttpRequest,
+    state: &AppState,
+    id: &str,
+    input: DocumentInput,
+    partial: bool,
+) -> Result<HttpResponse, ApiError> {
+    let corpus = state.corpora.mevzuat();
+    let existing = find_existing(state, corpus, id).await?;
+    let mut saved = None;
+    for _ in 0..SLUG_WRITE_ATTEMPTS {
+        let document = prepare(state, corpus, input.clone(), Some(&existing), partial).await?;
+        match state.repos.document_writes.update(&Actor::from_request(req), corpus, id, &document).await {
+            Ok(updated) => {
+                saved = Some((updated, document));
+                break;
+            }
+            Err(e) => slug_conflict(e, &input)?,
+        }
+    }
+    let Some((updated, document)) = saved else {
+        return Err(slug_taken_error());
+    };
+    if !updated {
+        return Err(ApiError::DocumentNotFound);
+    }
+    log::info!("Belge güncellendi: {} ({}) - anahtar: {}", document.url_slug, id, actor(req));
+
+    Ok(HttpResponse::Ok().json(DocumentDetailResponse {
+        success: true,
+        data: written(state, corpus, &document.url_slug).await?,
+        message: "Belge güncellendi".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/documents/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Belgenin _id değeri")),
+    request_body = DocumentInput,
+    responses(
+        (status = 200, description = "Belge güncellendi", body = DocumentDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn replace_document(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<DocumentInput>,
+) -> Result<HttpResponse, ApiError> {
+    save_existing(&req, &state, &path, body.into_inner(), false).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/admin/documents/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Belgenin _id değeri")),
+    request_body = DocumentInput,
+    responses(
+        (status = 200, description = "Belge güncellendi", body = DocumentDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn update_document(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<DocumentInput>,
+) -> Result<HttpResponse, ApiError> {
+    save_existing(&req, &state, &path, body.into_inner(), true).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/documents/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Belgenin _id değeri")),
+    responses(
+        (status = 200, description = "Belge silindi", body = DeleteDocumentResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn delete_document(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let corpus = state.corpora.mevzuat();
+    let existing = find_existing(&state, corpus, &path).await?;
+
+    let deleted = state
+        .repos
+        .document_writes
+        .delete(&Actor::from_request(&req), corpus, &existing.id)
+        .await
+        .map_err(ApiError::storage(Resource::DocumentWrite))?;
+    if !deleted {
+        return Err(ApiError::DocumentNotFound);
+    }
+    log::info!("Belge silindi: {} ({}) - anahtar: {}", existing.url_slug, existing.id, actor(&req));
+
+    Ok(HttpResponse::Ok().json(DeleteDocumentResponse {
+        success: true,
+        data: DeletedDocument {
+            id: existing.id,
+            url_slug: existing.url_slug,
+        },
+        message: "Belge silindi".to_string(),
+    }))
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::post().to(create_document))
+        .route("/ingest", web::post().to(ingest_document))
+        .route("/{id}", web::put().to(replace_document))
+        .route("/{id}", web::patch().to(update_document))
+        .route("/{id}", web::delete().to(delete_document));
+}
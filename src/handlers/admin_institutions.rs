@@ -0,0 +1,335 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::Utc;
+use mongodb::bson::oid::ObjectId;
+use crate::audit::Actor;
+use crate::config::AppState;
+use crate::error::{ApiError, FieldError, FieldIssue, Resource};
+use crate::handlers::admin_documents::{actor, is_http_url, non_empty};
+use crate::handlers::kurum::create_kurum_slug;
+use crate::models::admin_institution::{DeleteRecordResponse, DeletedRecord, InstitutionDetailResponse, InstitutionInput};
+use crate::models::error::ErrorEnvelope;
+use crate::models::kurum::KurumResponse;
+use crate::repository::{DocumentFilter, InstitutionRecord, InstitutionWrite};
+use crate::utils::format_timestamp;
+
+// Yönetim API'si: kurumlar. Silme, kuruma ait duyuru linkini ve faydalı linkleri de
+// siler; herhangi bir corpus'ta belgesi olan kurum silinemez.
+
+const KURUM_ADI_MAX_LEN: usize = 200;
+const DETSIS_LEN: usize = 8;
+
+// kurum_id alanını doğrular: zorunluluk, ObjectId formatı ve kurumun kayıtlı olması.
+// Duyuru linki ve faydalı link gövdeleri de kullanır.
+pub(crate) async fn validate_kurum_id(
+    state: &AppState,
+    value: &Option<String>,
+    required: bool,
+    resource: Resource,
+    errors: &mut Vec<FieldError>,
+) -> Result<(), ApiError> {
+    match non_empty(value) {
+        None if required => errors.push(FieldError::new("kurum_id", FieldIssue::Required)),
+        None => {}
+        Some(kurum_id) if ObjectId::parse_str(kurum_id).is_err() => {
+            errors.push(FieldError::new("kurum_id", FieldIssue::InvalidFormat))
+        }
+        Some(kurum_id) => {
+            let kurum = state
+                .repos
+                .institutions
+                .find_by_id(kurum_id)
+                .await
+                .map_err(ApiError::storage(resource))?;
+            if kurum.is_none() {
+                errors.push(FieldError::new("kurum_id", FieldIssue::NotFound));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn valid_detsis(detsis: &str) -> bool {
+    detsis.len() == DETSIS_LEN && detsis.bytes().all(|b| b.is_ascii_digit())
+}
+
+// Gövdeyi doğrulayıp yazılacak kuruma çevirir. partial (PATCH) ise yalnızca gönderilen
+// alanlar doğrulanır ve eksikler existing'ten alınır.
+async fn prepare(
+    state: &AppState,
+    input: InstitutionInput,
+    existing: Option<&InstitutionRecord>,
+    partial: bool,
+) -> Result<InstitutionWrite, ApiError> {
+    let id = existing.map(|record| record.id.as_str());
+    let mut errors = Vec::new();
+
+    match non_empty(&input.kurum_adi) {
+        None if input.kurum_adi.is_some() || !partial => {
+            errors.push(FieldError::new("kurum_adi", FieldIssue::Required))
+        }
+        None => {}
+        Some(kurum_adi) if kurum_adi.chars().count() > KURUM_ADI_MAX_LEN => {
+            errors.push(FieldError::new("kurum_adi", FieldIssue::TooLong(KURUM_ADI_MAX_LEN)))
+        }
+        Some(_) => {}
+    }
+    if non_empty(&input.kurum_logo).is_some_and(|logo| !is_http_url(logo)) {
+        errors.push(FieldError::new("kurum_logo", FieldIssue::InvalidFormat));
+    }
+    if non_empty(&input.detsis).is_some_and(|detsis| !valid_detsis(detsis)) {
+        errors.push(FieldError::new("detsis", FieldIssue::InvalidFormat));
+    }
+
+    // Kurum slug'ı (GET /institutions/slug/{slug}) ve DETSIS numarası benzersiz olmalı
+    let kurum_adi = non_empty(&input.kurum_adi).filter(|_| !errors.iter().any(|e| e.field == "kurum_adi"));
+    let detsis = non_empty(&input.detsis).filter(|detsis| valid_detsis(detsis));
+    if kurum_adi.is_some() || detsis.is_some() {
+        let others: Vec<InstitutionRecord> = state
+            .repos
+            .institutions
+            .list()
+            .await
+            .map_err(ApiError::storage(Resource::InstitutionWrite))?
+            .into_iter()
+            .filter(|record| Some(record.id.as_str()) != id)
+            .collect();
+        if let Some(kurum_adi) = kurum_adi {
+            let slug = create_kurum_slug(kurum_adi);
+            if others.iter().any(|record| create_kurum_slug(&record.kurum_adi) == slug) {
+                errors.push(FieldError::new("kurum_adi", FieldIssue::AlreadyExists));
+            }
+        }
+        if let Some(detsis) = detsis {
+            if others.iter().any(|record| record.detsis.as_deref() == Some(detsis)) {
+                errors.push(FieldError::new("detsis", FieldIssue::AlreadyExists));
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    let text = |value: Option<String>, current: Option<&str>| match (value, current) {
+        (Some(value), _) => value.trim().to_string(),
+        (None, Some(current)) if partial => current.to_string(),
+        (None, _) => String::new(),
+    };
+    let now = format_timestamp(Utc::now());
+    Ok(InstitutionWrite {
+        kurum_adi: text(input.kurum_adi, existing.map(|record| record.kurum_adi.as_str())),
+        kurum_logo: text(input.kurum_logo, existing.map(|record| record.kurum_logo.as_str())),
+        aciklama: text(input.aciklama, existing.map(|record| record.aciklama.as_str())),
+        detsis: match input.detsis {
+            Some(detsis) => Some(detsis.trim().to_string()).filter(|detsis| !detsis.is_empty()),
+            None if partial => existing.and_then(|record| record.detsis.clone()),
+            None => None,
+        },
+        olusturulma_tarihi: existing.is_none().then(|| now.clone()),
+        guncellenme_tarihi: now,
+    })
+}
+
+async fn find_existing(state: &AppState, id: &str) -> Result<InstitutionRecord, ApiError> {
+    state
+        .repos
+        .institutions
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::storage(Resource::Institution))?
+        .ok_or(ApiError::InstitutionNotFound)
+}
+
+async fn written(state: &AppState, id: &str) -> Result<KurumResponse, ApiError> {
+    let kurum = find_existing(state, id).await?;
+    Ok(KurumResponse {
+        kurum_id: kurum.id,
+        kurum_adi: kurum.kurum_adi,
+        kurum_logo: kurum.kurum_logo,
+        kurum_aciklama: kurum.aciklama,
+        detsis: kurum.detsis,
+    })
+}
+
+// Kurumun tüm corpus'lardaki belge sayısı. Tek kuruma sabitlenmiş corpus'larda
+// (örn. Yargıtay) koleksiyondaki tüm belgeler o kuruma aittir.
+async fn referencing_documents(state: &AppState, id: &str) -> Result<u64, ApiError> {
+    let mut total = 0;
+    for corpus in state.corpora.all() {
+        let count = if corpus.kurum_id.as_deref() == Some(id) {
+            state.repos.documents.count(corpus, &DocumentFilter::default()).await
+        } else {
+            state.repos.documents.count_for_kurum(corpus, id).await
+        };
+        total += count.map_err(ApiError::storage(Resource::InstitutionWrite))?;
+    }
+    Ok(total)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/institutions",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    request_body = InstitutionInput,
+    responses(
+        (status = 201, description = "Kurum oluşturuldu", body = InstitutionDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn create_institution(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    body: web::Json<InstitutionInput>,
+) -> Result<HttpResponse, ApiError> {
+    let institution = prepare(&state, body.into_inner(), None, false).await?;
+    let id = state
+        .repos
+        .institution_writes
+        .insert(&Actor::from_request(&req), &institution)
+        .await
+        .map_err(ApiError::storage(Resource::InstitutionWrite))?;
+    log::info!("Kurum oluşturuldu: {} ({}) - anahtar: {}", institution.kurum_adi, id, actor(&req));
+
+    Ok(HttpResponse::Created().json(InstitutionDetailResponse {
+        success: true,
+        data: written(&state, &id).await?,
+        message: "Kurum oluşturuldu".to_string(),
+    }))
+}
+
+async fn save_existing(
+    req: &HttpRequest,
+    state: &AppState,
+    id: &str,
+    input: InstitutionInput,
+    partial: bool,
+) -> Result<HttpResponse, ApiError> {
+    let existing = find_existing(state, id).await?;
+    let institution = prepare(state, input, Some(&existing), partial).await?;
+
+    let updated = state
+        .repos
+        .institution_writes
+        .update(&Actor::from_request(req), &existing.id, &institution)
+        .await
+        .map_err(ApiError::storage(Resource::InstitutionWrite))?;
+    if !updated {
+        return Err(ApiError::InstitutionNotFound);
+    }
+    log::info!("Kurum güncellendi: {} ({}) - anahtar: {}", institution.kurum_adi, existing.id, actor(req));
+
+    Ok(HttpResponse::Ok().json(InstitutionDetailResponse {
+        success: true,
+        data: written(state, &existing.id).await?,
+        message: "Kurum güncellendi".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/institutions/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Kurumun _id değeri")),
+    request_body = InstitutionInput,
+    responses(
+        (status = 200, description = "Kurum güncellendi", body = InstitutionDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn replace_institution(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<InstitutionInput>,
+) -> Result<HttpResponse, ApiError> {
+    save_existing(&req, &state, &path, body.into_inner(), false).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/admin/institutions/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Kurumun _id değeri")),
+    request_body = InstitutionInput,
+    responses(
+        (status = 200, description = "Kurum güncellendi", body = InstitutionDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn update_institution(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<InstitutionInput>,
+) -> Result<HttpResponse, ApiError> {
+    save_existing(&req, &state, &path, body.into_inner(), true).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/institutions/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Kurumun _id değeri")),
+    responses(
+        (status = 200, description = "Kurum, duyuru linki ve faydalı linkleri silindi", body = DeleteRecordResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 409, description = "Kuruma bağlı belgeler var", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn delete_institution(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let existing = find_existing(&state, &path).await?;
+
+    let documents = referencing_documents(&state, &existing.id).await?;
+    if documents > 0 {
+        return Err(ApiError::InstitutionInUse(documents));
+    }
+
+    let deleted = state
+        .repos
+        .institution_writes
+        .delete(&Actor::from_request(&req), &existing.id)
+        .await
+        .map_err(ApiError::storage(Resource::InstitutionWrite))?;
+    if !deleted {
+        return Err(ApiError::InstitutionNotFound);
+    }
+    log::info!("Kurum silindi: {} ({}) - anahtar: {}", existing.kurum_adi, existing.id, actor(&req));
+
+    Ok(HttpResponse::Ok().json(DeleteRecordResponse {
+        success: true,
+        data: DeletedRecord { id: existing.id },
+        message: "Kurum silindi".to_string(),
+    }))
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::post().to(create_institution))
+        .route("/{id}", web::put().to(replace_institution))
+        .route("/{id}", web::patch().to(update_institution))
+        .route("/{id}", web::delete().to(delete_institution));
+}
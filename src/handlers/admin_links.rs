@@ -0,0 +1,249 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use crate::audit::Actor;
+use crate::config::AppState;
+use crate::error::{ApiError, FieldError, FieldIssue, Resource};
+use crate::handlers::admin_documents::{actor, is_http_url, non_empty};
+use crate::handlers::admin_institutions::validate_kurum_id;
+use crate::models::admin_institution::{DeleteRecordResponse, DeletedRecord, LinkDetailResponse, LinkInput};
+use crate::models::error::ErrorEnvelope;
+use crate::models::link::LinkResponse;
+use crate::repository::{LinkRecord, LinkWrite};
+
+// Yönetim API'si: kurumların faydalı linkleri (links koleksiyonu)
+
+const BASLIK_MAX_LEN: usize = 200;
+
+async fn prepare(
+    state: &AppState,
+    input: LinkInput,
+    existing: Option<&LinkRecord>,
+    partial: bool,
+) -> Result<LinkWrite, ApiError> {
+    let mut errors = Vec::new();
+
+    validate_kurum_id(
+        state,
+        &input.kurum_id,
+        input.kurum_id.is_some() || !partial,
+        Resource::LinkWrite,
+        &mut errors,
+    )
+    .await?;
+    match non_empty(&input.baslik) {
+        None if input.baslik.is_some() || !partial => errors.push(FieldError::new("baslik", FieldIssue::Required)),
+        Some(baslik) if baslik.chars().count() > BASLIK_MAX_LEN => {
+            errors.push(FieldError::new("baslik", FieldIssue::TooLong(BASLIK_MAX_LEN)))
+        }
+        _ => {}
+    }
+    match non_empty(&input.url) {
+        None if input.url.is_some() || !partial => errors.push(FieldError::new("url", FieldIssue::Required)),
+        Some(url) if !is_http_url(url) => errors.push(FieldError::new("url", FieldIssue::InvalidFormat)),
+        _ => {}
+    }
+
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    let keep = |value: Option<&str>, current: fn(&LinkRecord) -> String| match value {
+        Some(value) => value.to_string(),
+        None => existing.map(current).unwrap_or_default(),
+    };
+    Ok(LinkWrite {
+        kurum_id: keep(non_empty(&input.kurum_id), |record| record.kurum_id.clone().unwrap_or_default()),
+        baslik: keep(non_empty(&input.baslik), |record| record.baslik.clone()),
+        aciklama: match input.aciklama {
+            Some(aciklama) => aciklama.trim().to_string(),
+            None if partial => existing.map(|record| record.aciklama.clone()).unwrap_or_default(),
+            None => String::new(),
+        },
+        url: keep(non_empty(&input.url), |record| record.url.clone()),
+        created_at: existing
+            .is_none()
+            .then(|| chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+    })
+}
+
+async fn find_existing(state: &AppState, id: &str) -> Result<LinkRecord, ApiError> {
+    state
+        .repos
+        .links
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::storage(Resource::Links))?
+        .ok_or(ApiError::LinkNotFound)
+}
+
+async fn written(state: &AppState, id: &str) -> Result<LinkResponse, ApiError> {
+    let record = find_existing(state, id).await?;
+    Ok(LinkResponse {
+        id: record.id,
+        baslik: record.baslik,
+        aciklama: record.aciklama,
+        url: record.url,
+        kurum_id: record.kurum_id.unwrap_or_default(),
+        created_at: record.created_at,
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/links",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    request_body = LinkInput,
+    responses(
+        (status = 201, description = "Link oluşturuldu", body = LinkDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn create_link(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    body: web::Json<LinkInput>,
+) -> Result<HttpResponse, ApiError> {
+    let link = prepare(&state, body.into_inner(), None, false).await?;
+    let id = state
+        .repos
+        .link_writes
+        .insert(&Actor::from_request(&req), &link)
+        .await
+        .map_err(ApiError::storage(Resource::LinkWrite))?;
+    log::info!("Link oluşturuldu: {} ({}) - anahtar: {}", link.baslik, id, actor(&req));
+
+    Ok(HttpResponse::Created().json(LinkDetailResponse {
+        success: true,
+        data: written(&state, &id).await?,
+        message: "Link oluşturuldu".to_string(),
+    }))
+}
+
+async fn save_existing(
+    req: &HttpRequest,
+    state: &AppState,
+    id: &str,
+    input: LinkInput,
+    partial: bool,
+) -> Result<HttpResponse, ApiError> {
+    let existing = find_existing(state, id).await?;
+    let link = prepare(state, input, Some(&existing), partial).await?;
+
+    let updated = state
+        .repos
+        .link_writes
+        .update(&Actor::from_request(req), &existing.id, &link)
+        .await
+        .map_err(ApiError::storage(Resource::LinkWrite))?;
+    if !updated {
+        return Err(ApiError::LinkNotFound);
+    }
+    log::info!("Link güncellendi: {} ({}) - anahtar: {}", link.baslik, existing.id, actor(req));
+
+    Ok(HttpResponse::Ok().json(LinkDetailResponse {
+        success: true,
+        data: written(state, &existing.id).await?,
+        message: "Link güncellendi".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/links/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Linkin _id değeri")),
+    request_body = LinkInput,
+    responses(
+        (status = 200, description = "Link güncellendi", body = LinkDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Link bulunamadı", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn replace_link(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<LinkInput>,
+) -> Result<HttpResponse, ApiError> {
+    save_existing(&req, &state, &path, body.into_inner(), false).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/admin/links/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Linkin _id değeri")),
+    request_body = LinkInput,
+    responses(
+        (status = 200, description = "Link güncellendi", body = LinkDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Link bulunamadı", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn update_link(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<LinkInput>,
+) -> Result<HttpResponse, ApiError> {
+    save_existing(&req, &state, &path, body.into_inner(), true).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/links/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Linkin _id değeri")),
+    responses(
+        (status = 200, description = "Link silindi", body = DeleteRecordResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Link bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn delete_link(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let existing = find_existing(&state, &path).await?;
+    let deleted = state
+        .repos
+        .link_writes
+        .delete(&Actor::from_request(&req), &existing.id)
+        .await
+        .map_err(ApiError::storage(Resource::LinkWrite))?;
+    if !deleted {
+        return Err(ApiError::LinkNotFound);
+    }
+    log::info!("Link silindi: {} ({}) - anahtar: {}", existing.baslik, existing.id, actor(&req));
+
+    Ok(HttpResponse::Ok().json(DeleteRecordResponse {
+        success: true,
+        data: DeletedRecord { id: existing.id },
+        message: "Link silindi".to_string(),
+    }))
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::post().to(create_link))
+        .route("/{id}", web::put().to(replace_link))
+        .route("/{id}", web::patch().to(update_link))
+        .route("/{id}", web::delete().to(delete_link));
+}
@@ -0,0 +1,433 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::Utc;
+use utoipa::IntoParams;
+use crate::config::AppState;
+use crate::error::{ApiError, FieldError, FieldIssue, Resource};
+use crate::handlers::admin_documents::{actor, is_http_url, non_empty};
+use crate::handlers::admin_institutions::validate_kurum_id;
+use crate::models::admin_institution::{DeleteRecordResponse, DeletedRecord};
+use crate::models::error::ErrorEnvelope;
+use crate::models::webhook::{
+    DeliveryDetailResponse, DeliveryListResponse, DeliveryResponse, WebhookDetailResponse, WebhookInput,
+    WebhookListResponse, WebhookResponse,
+};
+use crate::repository::{DeliveryFilter, DeliveryWrite, Page, WebhookFilters, WebhookRecord, WebhookWrite};
+use crate::utils::format_timestamp;
+use crate::webhooks::{self, EVENTS, STATUS_DELIVERED, STATUS_FAILED, STATUS_PENDING};
+
+// Yönetim API'si: webhook abonelikleri, teslimat kaydı ve tekrar gönderim. Teslimatları
+// webhook işçisi (webhooks::spawn) gönderir; bu modül yalnızca kayıtları yönetir.
+
+const URL_MAX_LEN: usize = 2000;
+const ACIKLAMA_MAX_LEN: usize = 200;
+const MAX_KEYWORDS: usize = 20;
+const KEYWORD_MAX_LEN: usize = 100;
+const DEFAULT_LIMIT: u64 = 50;
+const MAX_LIMIT: u64 = 500;
+
+async fn prepare(
+    state: &AppState,
+    input: WebhookInput,
+    existing: Option<&WebhookRecord>,
+) -> Result<WebhookWrite, ApiError> {
+    let mut errors = Vec::new();
+
+    match non_empty(&input.url) {
+        None if input.url.is_some() || existing.is_none() => errors.push(FieldError::new("url", FieldIssue::Required)),
+        None => {}
+        Some(url) if url.len() > URL_MAX_LEN => errors.push(FieldError::new("url", FieldIssue::TooLong(URL_MAX_LEN))),
+        Some(url) if !is_http_url(url) => errors.push(FieldError::new("url", FieldIssue::InvalidFormat)),
+        Some(_) => {}
+    }
+    if let Some(events) = &input.events {
+        if events.is_empty() {
+            errors.push(FieldError::new("events", FieldIssue::Required));
+        } else if events.iter().any(|event| !EVENTS.contains(&event.as_str())) {
+            errors.push(FieldError::new("events", FieldIssue::NotAllowed));
+        }
+    }
+    if input.aciklama.as_deref().is_some_and(|aciklama| aciklama.trim().chars().count() > ACIKLAMA_MAX_LEN) {
+        errors.push(FieldError::new("aciklama", FieldIssue::TooLong(ACIKLAMA_MAX_LEN)));
+    }
+
+    let filters = match input.filters {
+        Some(filters) => {
+            let mut kurum_errors = Vec::new();
+            validate_kurum_id(state, &filters.kurum_id, false, Resource::WebhookWrite, &mut kurum_errors).await?;
+            errors.extend(kurum_errors.into_iter().map(|e| FieldError::new("filters.kurum_id", e.issue)));
+            let corpus = non_empty(&filters.corpus);
+            if corpus.is_some_and(|corpus| state.corpora.get(corpus).is_none()) {
+                errors.push(FieldError::new("filters.corpus", FieldIssue::NotFound));
+            }
+            let keywords: Vec<String> = filters
+                .keywords
+                .unwrap_or_default()
+                .iter()
+                .map(|keyword| keyword.trim().to_string())
+                .filter(|keyword| !keyword.is_empty())
+                .collect();
+            if keywords.len() > MAX_KEYWORDS {
+                errors.push(FieldError::new("filters.keywords", FieldIssue::TooLong(MAX_KEYWORDS)));
+            }
+            if keywords.iter().any(|keyword| keyword.chars().count() > KEYWORD_MAX_LEN) {
+                errors.push(FieldError::new("filters.keywords", FieldIssue::TooLong(KEYWORD_MAX_LEN)));
+            }
+            WebhookFilters {
+                kurum_id: non_empty(&filters.kurum_id).map(str::to_string),
+                belge_turu: non_empty(&filters.belge_turu).map(str::to_string),
+                corpus: corpus.map(str::to_string),
+                keywords,
+            }
+        }
+        None => existing.map(|record| record.filters.clone()).unwrap_or_default(),
+    };
+
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    let now = format_timestamp(Utc::now());
+    Ok(WebhookWrite {
+        url: match non_empty(&input.url) {
+            Some(url) => url.to_string(),
+            None => existing.map(|record| record.url.clone()).unwrap_or_default(),
+        },
+        secret: match existing {
+            Some(record) => record.secret.clone(),
+            None => webhooks::generate_secret(),
+        },
+        events: match input.events {
+            Some(mut events) => {
+                events.sort_by_key(|event| EVENTS.iter().position(|known| known == event));
+                events.dedup();
+                events
+            }
+            None => match existing {
+                Some(record) => record.events.clone(),
+                None => EVENTS.iter().map(|event| event.to_string()).collect(),
+            },
+        },
+        filters,
+        aktif: input.aktif.or(existing.map(|record| record.aktif)).unwrap_or(true),
+        aciklama: match input.aciklama {
+            Some(aciklama) => aciklama.trim().to_string(),
+            None => existing.map(|record| record.aciklama.clone()).unwrap_or_default(),
+        },
+        created_at: existing.is_none().then(|| now.clone()),
+        updated_at: now,
+    })
+}
+
+async fn find_existing(state: &AppState, id: &str) -> Result<WebhookRecord, ApiError> {
+    state
+        .repos
+        .webhooks
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::storage(Resource::Webhooks))?
+        .ok_or(ApiError::WebhookNotFound)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/webhooks",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    request_body = WebhookInput,
+    responses(
+        (status = 201, description = "Abonelik oluşturuldu; imzalama anahtarı (secret) yalnızca bu yanıtta döner", body = WebhookDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn create_webhook(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    body: web::Json<WebhookInput>,
+) -> Result<HttpResponse, ApiError> {
+    let webhook = prepare(&state, body.into_inner(), None).await?;
+    let id = state
+        .repos
+        .webhooks
+        .insert(&webhook)
+        .await
+        .map_err(ApiError::storage(Resource::WebhookWrite))?;
+    log::info!("Webhook aboneliği oluşturuldu: {} ({}) - anahtar: {}", webhook.url, id, actor(&req));
+
+    let record = find_existing(&state, &id).await?;
+    let secret = record.secret.clone();
+    Ok(HttpResponse::Created().json(WebhookDetailResponse {
+        success: true,
+        data: WebhookResponse {
+            secret: Some(secret),
+            ..record.into()
+        },
+        message: "Webhook aboneliği oluşturuldu".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/webhooks",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    responses(
+        (status = 200, description = "Webhook abonelikleri (oluşturulma sırasıyla)", body = WebhookListResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn list_webhooks(state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let records = state
+        .repos
+        .webhooks
+        .list()
+        .await
+        .map_err(ApiError::storage(Resource::Webhooks))?;
+    let data: Vec<WebhookResponse> = records.into_iter().map(WebhookResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(WebhookListResponse {
+        success: true,
+        count: data.len(),
+        message: format!("{} webhook aboneliği bulundu", data.len()),
+        data,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/webhooks/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Aboneliğin _id değeri")),
+    responses(
+        (status = 200, description = "Webhook aboneliği", body = WebhookDetailResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Abonelik bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_webhook(state: web::Data<AppState>, path: web::Path<String>) -> Result<HttpResponse, ApiError> {
+    let record = find_existing(&state, &path).await?;
+    Ok(HttpResponse::Ok().json(WebhookDetailResponse {
+        success: true,
+        data: record.into(),
+        message: "Webhook aboneliği bulundu".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/admin/webhooks/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Aboneliğin _id değeri")),
+    request_body = WebhookInput,
+    responses(
+        (status = 200, description = "Abonelik güncellendi", body = WebhookDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Abonelik bulunamadı", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn update_webhook(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<WebhookInput>,
+) -> Result<HttpResponse, ApiError> {
+    let existing = find_existing(&state, &path).await?;
+    let webhook = prepare(&state, body.into_inner(), Some(&existing)).await?;
+    let updated = state
+        .repos
+        .webhooks
+        .update(&existing.id, &webhook)
+        .await
+        .map_err(ApiError::storage(Resource::WebhookWrite))?;
+    if !updated {
+        return Err(ApiError::WebhookNotFound);
+    }
+    log::info!("Webhook aboneliği güncellendi: {} ({}) - anahtar: {}", webhook.url, existing.id, actor(&req));
+
+    Ok(HttpResponse::Ok().json(WebhookDetailResponse {
+        success: true,
+        data: find_existing(&state, &existing.id).await?.into(),
+        message: "Webhook aboneliği güncellendi".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/webhooks/{id}",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Aboneliğin _id değeri")),
+    responses(
+        (status = 200, description = "Abonelik ve teslimat kaydı silindi", body = DeleteRecordResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Abonelik bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn delete_webhook(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let existing = find_existing(&state, &path).await?;
+    let deleted = state
+        .repos
+        .webhooks
+        .delete(&existing.id)
+        .await
+        .map_err(ApiError::storage(Resource::WebhookWrite))?;
+    if !deleted {
+        return Err(ApiError::WebhookNotFound);
+    }
+    log::info!("Webhook aboneliği silindi: {} ({}) - anahtar: {}", existing.url, existing.id, actor(&req));
+
+    Ok(HttpResponse::Ok().json(DeleteRecordResponse {
+        success: true,
+        data: DeletedRecord { id: existing.id },
+        message: "Webhook aboneliği silindi".to_string(),
+    }))
+}
+
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeliveryQuery {
+    // pending, delivered, failed
+    pub status: Option<String>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/webhooks/{id}/deliveries",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Aboneliğin _id değeri"), DeliveryQuery),
+    responses(
+        (status = 200, description = "Teslimat kaydı (en yeni önce)", body = DeliveryListResponse),
+        (status = 400, description = "Geçersiz filtre", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Abonelik bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn list_deliveries(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<DeliveryQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let status = non_empty(&query.status);
+    if status.is_some_and(|status| ![STATUS_PENDING, STATUS_DELIVERED, STATUS_FAILED].contains(&status)) {
+        return Err(ApiError::InvalidParameter("status"));
+    }
+    let webhook = find_existing(&state, &path).await?;
+    let filter = DeliveryFilter {
+        webhook_id: Some(webhook.id),
+        status: status.map(str::to_string),
+    };
+    let page = Page {
+        offset: query.offset.unwrap_or(0),
+        limit: query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+    };
+
+    let records = state
+        .repos
+        .webhooks
+        .deliveries(&filter, page)
+        .await
+        .map_err(ApiError::storage(Resource::WebhookDeliveries))?;
+    let count = state.repos.webhooks.count_deliveries(&filter).await.ok();
+    let data: Vec<DeliveryResponse> = records.into_iter().map(DeliveryResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(DeliveryListResponse {
+        success: true,
+        message: format!("{} teslimat bulundu", data.len()),
+        data,
+        count,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/webhooks/deliveries/{id}/replay",
+    tag = "yonetim",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Tekrar gönderilecek teslimatın _id değeri")),
+    responses(
+        (status = 202, description = "Aynı olay aynı gövdeyle yeniden kuyruğa alındı", body = DeliveryDetailResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "admin yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Teslimat ya da abonelik bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn replay_delivery(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let original = state
+        .repos
+        .webhooks
+        .find_delivery(&path)
+        .await
+        .map_err(ApiError::storage(Resource::WebhookDeliveries))?
+        .ok_or(ApiError::DeliveryNotFound)?;
+    // Silinmiş aboneliğin teslimatı da silinir; yine de kontrol edilir
+    find_existing(&state, &original.webhook_id).await?;
+
+    let replay = DeliveryWrite {
+        webhook_id: original.webhook_id.clone(),
+        event_id: original.event_id.clone(),
+        event: original.event.clone(),
+        payload: original.payload.clone(),
+        created_at: format_timestamp(Utc::now()),
+        replay_of: Some(original.id.clone()),
+    };
+    let ids = state
+        .repos
+        .webhooks
+        .enqueue(&[replay])
+        .await
+        .map_err(ApiError::storage(Resource::WebhookDeliveries))?;
+    let id = ids.into_iter().next().unwrap_or_default();
+    log::info!("Webhook teslimatı yeniden kuyruğa alındı: {} -> {} - anahtar: {}", original.id, id, actor(&req));
+
+    let record = state
+        .repos
+        .webhooks
+        .find_delivery(&id)
+        .await
+        .map_err(ApiError::storage(Resource::WebhookDeliveries))?
+        .ok_or(ApiError::DeliveryNotFound)?;
+    Ok(HttpResponse::Accepted().json(DeliveryDetailResponse {
+        success: true,
+        data: record.into(),
+        message: "Teslimat yeniden kuyruğa alındı".to_string(),
+    }))
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::post().to(create_webhook))
+        .route("", web::get().to(list_webhooks))
+        .route("/deliveries/{id}/replay", web::post().to(replay_delivery))
+        .route("/{id}", web::get().to(get_webhook))
+        .route("/{id}", web::patch().to(update_webhook))
+        .route("/{id}", web::delete().to(delete_webhook))
+        .route("/{id}/deliveries", web::get().to(list_deliveries));
+}
@@ -0,0 +1,212 @@
+use actix_web::{web, HttpResponse};
+use crate::config::AppState;
+use crate::models::error::ErrorEnvelope;
+use crate::corpus::Corpus;
+use crate::error::{ApiError, Resource};
+use crate::handlers::document::{fetch_document_detail, fetch_documents, DocumentQuery};
+use crate::handlers::regulation::{fetch_recent_regulations, recent_regulations_response, RecentRegulationsQuery};
+use crate::handlers::search::{search_corpus, with_total_count, SearchQuery};
+use crate::handlers::sitemap::{fetch_sitemap_documents, sitemap_documents_response};
+use crate::models::corpus::{CorpusInfo, CorporaListResponse};
+use crate::models::document::{DocumentsListResponse, DocumentDetailResponse};
+use crate::models::regulation::RecentRegulationsListResponse;
+use crate::models::search::SearchResponse;
+use crate::models::sitemap::SitemapDocumentsResponse;
+
+// Corpus registry'sinden üretilen genel route'lar:
+// /api/v1/corpora/{corpus}/documents, /documents/{slug}, /search, /recent, /sitemap
+
+fn resolve<'a>(state: &'a AppState, key: &str) -> Result<&'a Corpus, ApiError> {
+    state
+        .corpora
+        .get(key)
+        .ok_or_else(|| ApiError::CorpusNotFound(key.to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/corpora",
+    tag = "corpora",
+    responses(
+        (status = 200, description = "İşlem başarılı", body = CorporaListResponse),
+    )
+)]
+pub async fn list_corpora(state: web::Data<AppState>) -> HttpResponse {
+    let data: Vec<CorpusInfo> = state
+        .corpora
+        .all()
+        .iter()
+        .map(|corpus| CorpusInfo {
+            key: corpus.key.clone(),
+            title: corpus.title.clone(),
+            url_prefix: corpus.url_prefix.clone(),
+        })
+        .collect();
+
+    let count = data.len();
+    HttpResponse::Ok().json(CorporaListResponse {
+        success: true,
+        data,
+        count,
+        message: "İşlem başarılı".to_string(),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/corpora/{corpus}/documents",
+    tag = "corpora",
+    params(("corpus" = String, Path, description = "Corpus anahtarı (örn. mevzuat, yargitay)"), DocumentQuery),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = DocumentsListResponse),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_corpus_documents(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<DocumentQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let corpus = resolve(&state, &path)?;
+
+    let (documents, count) = fetch_documents(&state, corpus, &query)
+        .await
+        .map_err(ApiError::storage(Resource::Documents))?;
+
+    Ok(HttpResponse::Ok().json(DocumentsListResponse {
+        success: true,
+        data: documents,
+        count,
+        message: "İşlem başarılı".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/corpora/{corpus}/documents/{slug}",
+    tag = "corpora",
+    params(("corpus" = String, Path, description = "Corpus anahtarı (örn. mevzuat, yargitay)"), ("slug" = String, Path, description = "Belgenin url_slug değeri")),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = DocumentDetailResponse),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_corpus_document(
+    state: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, ApiError> {
+    let (key, slug) = path.into_inner();
+    let corpus = resolve(&state, &key)?;
+
+    let data = fetch_document_detail(&state, corpus, &slug)
+        .await
+        .map_err(ApiError::storage(Resource::Document))?
+        .ok_or(ApiError::DocumentNotFound)?;
+
+    Ok(HttpResponse::Ok().json(DocumentDetailResponse {
+        success: true,
+        data,
+        message: "İşlem başarılı".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/corpora/{corpus}/search",
+    tag = "corpora",
+    params(("corpus" = String, Path, description = "Corpus anahtarı (örn. mevzuat, yargitay)"), SearchQuery),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = SearchResponse),
+        (status = 400, description = "Geçersiz parametre", body = ErrorEnvelope),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn search_in_corpus(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<SearchQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let corpus = resolve(&state, &path)?;
+
+    // q parametresi boş olamaz
+    if query.q.trim().is_empty() {
+        return Err(ApiError::EmptySearchQuery);
+    }
+
+    let limit = query.limit.unwrap_or(10000);
+    let offset = query.offset.unwrap_or(0);
+
+    let (results, total_count) = search_corpus(
+        &state,
+        corpus,
+        query.q.trim(),
+        query.kurum_id.as_deref(),
+        limit,
+        offset,
+    )
+    .await?;
+
+    Ok(with_total_count(
+        HttpResponse::Ok().json(SearchResponse {
+            success: true,
+            data: results,
+            count: total_count,
+            message: "İşlem başarılı".to_string(),
+        }),
+        total_count,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/corpora/{corpus}/recent",
+    tag = "corpora",
+    params(("corpus" = String, Path, description = "Corpus anahtarı (örn. mevzuat, yargitay)"), RecentRegulationsQuery),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = RecentRegulationsListResponse),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_corpus_recent(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<RecentRegulationsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let corpus = resolve(&state, &path)?;
+
+    let limit = query.limit.unwrap_or(50).min(1000);
+    recent_regulations_response(fetch_recent_regulations(&state, corpus, limit).await)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/corpora/{corpus}/sitemap",
+    tag = "corpora",
+    params(("corpus" = String, Path, description = "Corpus anahtarı (örn. mevzuat, yargitay)")),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = SitemapDocumentsResponse),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_corpus_sitemap(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let corpus = resolve(&state, &path)?;
+
+    sitemap_documents_response(fetch_sitemap_documents(&state, corpus, 5000).await, corpus)
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(list_corpora))
+        .route("/{corpus}/documents", web::get().to(get_corpus_documents))
+        .route("/{corpus}/documents/{slug}", web::get().to(get_corpus_document))
+        .route("/{corpus}/search", web::get().to(search_in_corpus))
+        .route("/{corpus}/recent", web::get().to(get_corpus_recent))
+        .route("/{corpus}/sitemap", web::get().to(get_corpus_sitemap));
+}
@@ -1,17 +1,20 @@
-use actix_web::{web, HttpResponse, http::StatusCode};
-use mongodb::{Collection, bson::{doc, oid::ObjectId, Document as MongoDocument}};
-use crate::config::AppState;
+use actix_web::{web, HttpResponse};
+use crate::config::{AppState, PublicUrls};
+use crate::models::error::ErrorEnvelope;
+use crate::error::{ApiError, Resource};
+use crate::corpus::Corpus;
+use crate::dates;
+use crate::repository::{ContentRecord, DocumentFilter, DocumentRecord, DocumentSort, Page, RepositoryError};
 use crate::models::document::{
     DocumentResponse, DocumentsListResponse,
     DocumentDetailResponse, DocumentDetailData, DocumentMetadata, DocumentContent
 };
 use crate::models::document_filters::{DocumentFiltersResponse, DocumentFiltersData};
-use std::collections::HashSet;
-use regex;
-use chrono::Utc;
-use futures::{future, FutureExt};
+use futures::future;
+use utoipa::IntoParams;
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DocumentQuery {
     pub kurum_id: Option<String>,
     pub limit: Option<u64>,
@@ -22,331 +25,131 @@ pub struct DocumentQuery {
     pub etiketler: Option<String>,
 }
 
-pub async fn get_documents(
-    state: web::Data<AppState>,
-    query: web::Query<DocumentQuery>,
-) -> HttpResponse {
-    let metadata_collection: Collection<MongoDocument> =
-        state.db.collection("metadata");
-
+// Corpus'tan bağımsız belge listesi; v1 ve v2 listeleri ile /corpora route'ları bunu kullanır
+pub async fn fetch_documents(
+    state: &AppState,
+    corpus: &Corpus,
+    query: &DocumentQuery,
+) -> Result<(Vec<DocumentResponse>, Option<u64>), RepositoryError> {
     // Query parametrelerini al
     let limit = query.limit.unwrap_or(10000).min(10000); // Maksimum 10000
     let offset = query.offset.unwrap_or(0);
-    let sort_by = query.sort_by.as_deref().unwrap_or("olusturulma_tarihi");
-    let sort_order = query.sort_order.as_deref().unwrap_or("desc");
-    let sort_value = if sort_order == "asc" { 1 } else { -1 };
-
-    // Match stage için filter oluştur
-    let mut match_filter = doc! {};
-    
-    if let Some(kurum_id) = &query.kurum_id {
-        match_filter.insert("kurum_id", kurum_id);
-    }
-    
-    // belge_turu filtresi
-    if let Some(belge_turu) = &query.belge_turu {
-        if !belge_turu.is_empty() {
-            match_filter.insert("belge_turu", belge_turu);
-        }
-    }
-    
-    // etiketler filtresi (virgülle ayrılmış string içinde arama)
-    if let Some(etiketler) = &query.etiketler {
-        if !etiketler.is_empty() {
-            // Etiketler virgülle ayrılmış string içinde belirli bir etiketin geçip geçmediğini kontrol et
-            // Regex ile etiketin virgülle ayrılmış listede geçip geçmediğini kontrol et
-            let regex_pattern = format!(r"(^|,\s*){}(,|$)", regex::escape(etiketler));
-            match_filter.insert("etiketler", doc! {
-                "$regex": regex_pattern,
-                "$options": "i" // case insensitive
-            });
-        }
-    }
-
-    // Count için filter'ı klonla (pipeline'da move edilecek)
-    let count_filter = match_filter.clone();
-
-    // Aggregation pipeline oluştur
-    let mut pipeline = vec![
-        doc! { "$match": match_filter },
-        doc! {
-            "$addFields": {
-                "kurum_id_object": {
-                    "$toObjectId": "$kurum_id"
-                }
-            }
-        },
-        doc! { "$sort": { sort_by: sort_value } },
-    ];
-    
-    // Offset varsa $skip ekle
-    if offset > 0 {
-        pipeline.push(doc! { "$skip": offset as i64 });
-    }
-    
-    // Limit ekle
-    pipeline.push(doc! { "$limit": limit as i64 });
-    
-    // Lookup ve unwind ekle
-    pipeline.extend(vec![
-        doc! {
-            "$lookup": {
-                "from": "kurumlar",
-                "localField": "kurum_id_object",
-                "foreignField": "_id",
-                "as": "kurum_bilgisi"
-            }
-        },
-        doc! {
-            "$unwind": {
-                "path": "$kurum_bilgisi",
-                "preserveNullAndEmptyArrays": true
-            }
-        },
-    ]);
-
-    // Aggregation çalıştır
-    let mut cursor = match metadata_collection.aggregate(pipeline, None).await {
-        Ok(cursor) => cursor,
-        Err(e) => {
-            log::error!("MongoDB aggregation hatası: {}", e);
-            return HttpResponse::InternalServerError().json(DocumentsListResponse {
-                success: false,
-                data: vec![],
-                count: None,
-                message: "Belgeler alınamadı".to_string(),
-            });
-        }
+    let sort = DocumentSort {
+        field: query
+            .sort_by
+            .clone()
+            .unwrap_or_else(|| corpus.fields.created_at.clone()),
+        descending: query.sort_order.as_deref() != Some("asc"),
     };
 
-    let mut documents: Vec<DocumentResponse> = Vec::new();
-
-    // Sonuçları işle
-    while let Ok(true) = cursor.advance().await {
-        if let Ok(doc_map) = cursor.deserialize_current() {
-            // Sadece istenen alanları al
-            let url_slug = doc_map
-                .get_str("url_slug")
-                .unwrap_or("")
-                .to_string();
-
-            let pdf_adi = doc_map
-                .get_str("pdf_adi")
-                .unwrap_or("")
-                .to_string();
-
-            let aciklama = doc_map
-                .get_str("aciklama")
-                .unwrap_or("")
-                .to_string();
-
-            let belge_yayin_tarihi = doc_map
-                .get_str("belge_yayin_tarihi")
-                .unwrap_or("")
-                .to_string();
-
-            let belge_turu = doc_map
-                .get_str("belge_turu")
-                .unwrap_or("")
-                .to_string();
-
-            let belge_durumu = doc_map
-                .get_str("belge_durumu")
-                .unwrap_or("")
-                .to_string();
-
-            let etiketler = doc_map
-                .get_str("etiketler")
-                .unwrap_or("")
-                .to_string();
-
-            let anahtar_kelimeler = doc_map
-                .get_str("anahtar_kelimeler")
-                .unwrap_or("")
-                .to_string();
-
-            let pdf_url = doc_map
-                .get_str("pdf_url")
-                .unwrap_or("")
-                .to_string();
+    let filter = DocumentFilter {
+        kurum_id: query.kurum_id.clone(),
+        belge_turu: query.belge_turu.clone(),
+        etiket: query.etiketler.clone(),
+    };
 
-            let icerik = doc_map
-                .get_str("icerik")
-                .ok()
-                .filter(|value| !value.is_empty())
-                .map(|value| value.to_string());
+    let records = state
+        .repos
+        .documents
+        .list(corpus, &filter, &sort, Page { offset, limit })
+        .await?;
 
-            let karar_tarihi = doc_map
-                .get_str("kararTarihi")
-                .or_else(|_| doc_map.get_str("karar_tarihi"))
-                .ok()
-                .filter(|value| !value.is_empty())
-                .map(|value| value.to_string());
+    // Toplam sayıyı al (pagination için)
+    let count = state.repos.documents.count(corpus, &filter).await.ok();
 
-            let dosya_boyutu_mb = doc_map
-                .get_f64("dosya_boyutu_mb")
-                .ok()
-                .or_else(|| {
-                    doc_map
-                        .get_i32("dosya_boyutu_mb")
-                        .ok()
-                        .map(|value| value as f64)
-                })
-                .or_else(|| {
-                    doc_map
-                        .get_i64("dosya_boyutu_mb")
-                        .ok()
-                        .map(|value| value as f64)
-                });
+    Ok((
+        records
+            .into_iter()
+            .map(|record| document_response_from(&state.urls, corpus, record))
+            .collect(),
+        count,
+    ))
+}
 
-            let sayfa_sayisi = doc_map
-                .get_i32("sayfa_sayisi")
-                .ok()
-                .or_else(|| doc_map.get_i64("sayfa_sayisi").ok().map(|value| value as i32));
+fn document_response_from(urls: &PublicUrls, corpus: &Corpus, record: DocumentRecord) -> DocumentResponse {
+    // Yayın tarihi yoksa corpus'a özgü tarih alanına (örn. kararTarihi) düş
+    let belge_yayin_tarihi = if record.belge_yayin_tarihi.is_empty() {
+        record.yayin_tarihi
+    } else {
+        record.belge_yayin_tarihi
+    };
 
-            documents.push(DocumentResponse {
-                url_slug,
-                pdf_adi,
-                aciklama,
-                belge_yayin_tarihi,
-                belge_turu,
-                belge_durumu,
-                etiketler,
-                anahtar_kelimeler,
-                pdf_url,
-                icerik,
-                karar_tarihi,
-                dosya_boyutu_mb,
-                sayfa_sayisi,
-            });
-        }
+    DocumentResponse {
+        url: urls.document(corpus, &record.url_slug, &record.id),
+        url_slug: record.url_slug,
+        pdf_adi: record.pdf_adi,
+        aciklama: record.aciklama,
+        belge_yayin_tarihi,
+        belge_turu: record.belge_turu,
+        belge_durumu: record.belge_durumu,
+        etiketler: record.etiketler,
+        anahtar_kelimeler: record.anahtar_kelimeler,
+        pdf_url: record.pdf_url,
+        icerik: record.icerik,
+        karar_tarihi: record.karar_tarihi,
+        dosya_boyutu_mb: record.dosya_boyutu_mb,
+        sayfa_sayisi: record.sayfa_sayisi,
     }
+}
 
-    // Toplam sayıyı al (pagination için)
-    let count = match metadata_collection.count_documents(count_filter, None).await {
-        Ok(count) => Some(count),
-        Err(_) => None,
-    };
+#[utoipa::path(
+    get,
+    path = "/api/v1/documents",
+    tag = "belgeler",
+    params(DocumentQuery),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = DocumentsListResponse),
+        (status = 400, description = "Geçersiz parametre", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_documents(
+    state: web::Data<AppState>,
+    query: web::Query<DocumentQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let (documents, count) = fetch_documents(&state, state.corpora.mevzuat(), &query)
+        .await
+        .map_err(ApiError::storage(Resource::Documents))?;
 
-    HttpResponse::Ok().json(DocumentsListResponse {
+    Ok(HttpResponse::Ok().json(DocumentsListResponse {
         success: true,
         data: documents,
         count,
         message: "İşlem başarılı".to_string(),
-    })
+    }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/documents/filters",
+    tag = "belgeler",
+    responses(
+        (status = 200, description = "İşlem başarılı", body = DocumentFiltersResponse),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
 pub async fn get_document_filters(
     state: web::Data<AppState>,
     query: web::Query<DocumentQuery>,
-) -> HttpResponse {
-    let metadata_collection: Collection<MongoDocument> = state.db.collection("metadata");
-    
-    // Match stage için filter oluştur (sadece kurum_id filtresi)
-    let mut match_filter = doc! {};
-    
-    if let Some(kurum_id) = &query.kurum_id {
-        match_filter.insert("kurum_id", kurum_id);
-    }
-    
-    // belge_turu unique değerlerini al
-    let belge_turu_pipeline = vec![
-        doc! { "$match": match_filter.clone() },
-        doc! {
-            "$group": {
-                "_id": {
-                    "$cond": {
-                        "if": { "$or": [
-                            { "$eq": ["$belge_turu", null] },
-                            { "$eq": ["$belge_turu", ""] }
-                        ]},
-                        "then": "Belirtilmemiş",
-                        "else": "$belge_turu"
-                    }
-                }
-            }
-        },
-        doc! {
-            "$project": {
-                "belge_turu": "$_id",
-                "_id": 0
-            }
-        },
-        doc! {
-            "$sort": { "belge_turu": 1 }
-        },
-    ];
-    
-    let mut belge_turu_list: Vec<String> = Vec::new();
-    match metadata_collection.aggregate(belge_turu_pipeline, None).await {
-        Ok(mut cursor) => {
-            while let Ok(true) = cursor.advance().await {
-                if let Ok(doc_map) = cursor.deserialize_current() {
-                    if let Ok(belge_turu) = doc_map.get_str("belge_turu") {
-                        belge_turu_list.push(belge_turu.to_string());
-                    }
-                }
-            }
-        }
-        Err(e) => {
-            log::error!("Belge türü listesi alınamadı: {}", e);
-            return HttpResponse::InternalServerError().json(DocumentFiltersResponse {
-                success: false,
-                data: DocumentFiltersData {
-                    belge_turu: vec![],
-                    etiketler: vec![],
-                },
-                message: None,
-                error: Some("Belge türü listesi alınamadı".to_string()),
-            });
-        }
-    }
-    
-    // etiketler unique değerlerini al
-    let etiketler_pipeline = vec![
-        doc! { "$match": match_filter },
-        doc! {
-            "$project": {
-                "etiketler": 1
-            }
-        },
-    ];
-    
-    let mut etiketler_set: HashSet<String> = HashSet::new();
-    match metadata_collection.aggregate(etiketler_pipeline, None).await {
-        Ok(mut cursor) => {
-            while let Ok(true) = cursor.advance().await {
-                if let Ok(doc_map) = cursor.deserialize_current() {
-                    if let Ok(etiketler_str) = doc_map.get_str("etiketler") {
-                        // Virgülle ayrılmış etiketleri parse et
-                        for etiket in etiketler_str.split(',') {
-                            let trimmed = etiket.trim();
-                            if !trimmed.is_empty() {
-                                etiketler_set.insert(trimmed.to_string());
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        Err(e) => {
-            log::error!("Etiket listesi alınamadı: {}", e);
-            return HttpResponse::InternalServerError().json(DocumentFiltersResponse {
-                success: false,
-                data: DocumentFiltersData {
-                    belge_turu: belge_turu_list,
-                    etiketler: vec![],
-                },
-                message: None,
-                error: Some("Etiket listesi alınamadı".to_string()),
-            });
-        }
-    }
-    
-    let mut etiketler_list: Vec<String> = etiketler_set.into_iter().collect();
-    etiketler_list.sort();
-    
-    HttpResponse::Ok().json(DocumentFiltersResponse {
+) -> Result<HttpResponse, ApiError> {
+    let corpus = state.corpora.mevzuat();
+    let kurum_id = query.kurum_id.as_deref();
+
+    // belge_turu ve etiketler unique değerlerini al
+    let belge_turu_list = state
+        .repos
+        .documents
+        .belge_turu_values(corpus, kurum_id)
+        .await
+        .map_err(ApiError::storage(Resource::DocumentFilters))?;
+    let etiketler_list = state
+        .repos
+        .documents
+        .etiket_values(corpus, kurum_id)
+        .await
+        .map_err(ApiError::storage(Resource::DocumentFilters))?;
+
+    Ok(HttpResponse::Ok().json(DocumentFiltersResponse {
         success: true,
         data: DocumentFiltersData {
             belge_turu: belge_turu_list,
@@ -354,350 +157,116 @@ pub async fn get_document_filters(
         },
         message: Some("Filtre listeleri başarıyla alındı".to_string()),
         error: None,
-    })
+    }))
 }
 
-pub async fn get_document_by_slug(
-    state: web::Data<AppState>,
-    slug: web::Path<String>,
-) -> HttpResponse {
-    let metadata_collection: Collection<MongoDocument> = state.db.collection("metadata");
-    let content_collection: Collection<MongoDocument> = state.db.collection("content");
-    let kurum_collection: Collection<MongoDocument> = state.db.collection("kurumlar");
-
+// Corpus'tan bağımsız belge detayı. İçerik, corpus tanımına göre ayrı koleksiyondan
+// (Join) ya da belgenin kendisinden (Embedded) okunur.
+pub async fn fetch_document_detail(
+    state: &AppState,
+    corpus: &Corpus,
+    slug: &str,
+) -> Result<Option<DocumentDetailData>, RepositoryError> {
     // Metadata'yı url_slug ile bul
-    let metadata_doc = match metadata_collection
-        .find_one(doc! { "url_slug": slug.as_str() }, None)
-        .await
-    {
-        Ok(Some(doc)) => doc,
-        Ok(None) => {
-            return HttpResponse::build(StatusCode::NOT_FOUND).json(DocumentDetailResponse {
-                success: false,
-                data: DocumentDetailData {
-                    metadata: DocumentMetadata {
-                        id: String::new(),
-                        kurum_id: String::new(),
-                        kurum_adi: String::new(),
-                        kurum_logo: String::new(),
-                        kurum_aciklama: String::new(),
-                        pdf_adi: String::new(),
-                        etiketler: String::new(),
-                        belge_yayin_tarihi: String::new(),
-                        belge_durumu: String::new(),
-                        aciklama: String::new(),
-                        url_slug: String::new(),
-                        belge_turu: String::new(),
-                        anahtar_kelimeler: String::new(),
-                        status: String::new(),
-                        sayfa_sayisi: 0,
-                        dosya_boyutu_mb: 0.0,
-                        pdf_url: String::new(),
-                        karar_tarihi: None,
-                    },
-                    content: DocumentContent {
-                        id: String::new(),
-                        metadata_id: String::new(),
-                        icerik: String::new(),
-                        olusturulma_tarihi: String::new(),
-                    },
-                    kurum_adi: String::new(),
-                    kurum_logo: String::new(),
-                    kurum_aciklama: String::new(),
-                },
-                message: "Belge bulunamadı".to_string(),
-            });
-        }
-        Err(e) => {
-            log::error!("MongoDB metadata sorgu hatası: {}", e);
-            return HttpResponse::InternalServerError().json(DocumentDetailResponse {
-                success: false,
-                data: DocumentDetailData {
-                    metadata: DocumentMetadata {
-                        id: String::new(),
-                        kurum_id: String::new(),
-                        kurum_adi: String::new(),
-                        kurum_logo: String::new(),
-                        kurum_aciklama: String::new(),
-                        pdf_adi: String::new(),
-                        etiketler: String::new(),
-                        belge_yayin_tarihi: String::new(),
-                        belge_durumu: String::new(),
-                        aciklama: String::new(),
-                        url_slug: String::new(),
-                        belge_turu: String::new(),
-                        anahtar_kelimeler: String::new(),
-                        status: String::new(),
-                        sayfa_sayisi: 0,
-                        dosya_boyutu_mb: 0.0,
-                        pdf_url: String::new(),
-                        karar_tarihi: None,
-                    },
-                    content: DocumentContent {
-                        id: String::new(),
-                        metadata_id: String::new(),
-                        icerik: String::new(),
-                        olusturulma_tarihi: String::new(),
-                    },
-                    kurum_adi: String::new(),
-                    kurum_logo: String::new(),
-                    kurum_aciklama: String::new(),
-                },
-                message: "Belge alınamadı".to_string(),
-            });
-        }
+    let Some(record) = state.repos.documents.find_by_slug(corpus, slug).await? else {
+        return Ok(None);
     };
 
-    // Metadata'dan bilgileri çıkar
-    let metadata_id = metadata_doc
-        .get_object_id("_id")
-        .map(|oid| oid.to_hex())
-        .unwrap_or_default();
-
-    let kurum_id = metadata_doc
-        .get_str("kurum_id")
-        .unwrap_or("")
-        .to_string();
-
-    let pdf_adi = metadata_doc
-        .get_str("pdf_adi")
-        .unwrap_or("")
-        .to_string();
-
-    let belge_yayin_tarihi = metadata_doc
-        .get_str("belge_yayin_tarihi")
-        .unwrap_or("")
-        .to_string();
-
-    let etiketler = metadata_doc
-        .get_str("etiketler")
-        .unwrap_or("")
-        .to_string();
-
-    let aciklama = metadata_doc
-        .get_str("aciklama")
-        .unwrap_or("")
-        .to_string();
-
-    let belge_turu = metadata_doc
-        .get_str("belge_turu")
-        .unwrap_or("")
-        .to_string();
-
-    let belge_durumu = metadata_doc
-        .get_str("belge_durumu")
-        .unwrap_or("")
-        .to_string();
-
-    let url_slug = metadata_doc
-        .get_str("url_slug")
-        .unwrap_or("")
-        .to_string();
-
-    let anahtar_kelimeler = metadata_doc
-        .get_str("anahtar_kelimeler")
-        .unwrap_or("")
-        .to_string();
-
-    let status = metadata_doc
-        .get_str("status")
-        .unwrap_or("")
-        .to_string();
-
-    let sayfa_sayisi = metadata_doc
-        .get_i32("sayfa_sayisi")
-        .unwrap_or(0);
-
-    let dosya_boyutu_mb = metadata_doc
-        .get_f64("dosya_boyutu_mb")
-        .unwrap_or(0.0);
-
-    let pdf_url = metadata_doc
-        .get_str("pdf_url")
-        .unwrap_or("")
-        .to_string();
-
-    let karar_tarihi = metadata_doc
-        .get_str("kararTarihi")
-        .or_else(|_| metadata_doc.get_str("karar_tarihi"))
-        .ok()
-        .filter(|value| !value.is_empty())
-        .map(|value| value.to_string());
-
     // Kurum ve Content sorgularını paralel çalıştır (performans iyileştirmesi)
-    let kurum_oid_result = ObjectId::parse_str(&kurum_id);
-    let metadata_oid = metadata_doc.get_object_id("_id").ok();
-    
-    // Paralel sorguları hazırla
-    let kurum_future = if let Ok(kurum_oid) = kurum_oid_result {
-        kurum_collection.find_one(doc! { "_id": kurum_oid }, None).boxed()
-    } else {
-        future::ready(Ok(None::<MongoDocument>)).boxed()
-    };
-    
-    let content_future = if let Some(oid) = metadata_oid {
-        // Önce ObjectId ile dene
-        content_collection.find_one(doc! { "metadata_id": oid }, None).boxed()
-    } else {
-        // String ile dene
-        content_collection.find_one(doc! { "metadata_id": &metadata_id }, None).boxed()
-    };
-    
-    // Paralel çalıştır
-    let (kurum_result, content_result) = future::join(kurum_future, content_future).await;
-    
-    // Kurum bilgilerini işle
-    let (kurum_adi, kurum_logo, kurum_aciklama) = match kurum_result {
-        Ok(Some(kurum_doc)) => {
-            let kurum_adi = kurum_doc
-                .get_str("kurum_adi")
-                .or_else(|_| kurum_doc.get_str("kurumAdi"))
-                .unwrap_or("")
-                .to_string();
-            let kurum_logo = kurum_doc
-                .get_str("kurum_logo")
-                .or_else(|_| kurum_doc.get_str("kurumLogo"))
-                .unwrap_or("")
-                .to_string();
-            let kurum_aciklama = kurum_doc
-                .get_str("aciklama")
-                .or_else(|_| kurum_doc.get_str("kurumAciklama"))
-                .unwrap_or("")
-                .to_string();
-            (kurum_adi, kurum_logo, kurum_aciklama)
+    let kurum_future = async {
+        if corpus.lookup_kurum {
+            state.repos.institutions.find_by_id(&record.kurum_id).await
+        } else {
+            Ok(None)
         }
-        _ => (String::new(), String::new(), String::new()),
     };
-    
-    // Content'i işle
-    let (content_id, icerik, content_olusturulma_tarihi) = match content_result {
-        Ok(Some(content_doc)) => {
-            let content_id = content_doc
-                .get_object_id("_id")
-                .map(|oid| oid.to_hex())
-                .unwrap_or_default();
-
-            // icerik alanını farklı isimlerle deneyelim
-            let icerik = content_doc
-                .get_str("icerik")
-                .or_else(|_| content_doc.get_str("content"))
-                .or_else(|_| content_doc.get_str("text"))
-                .unwrap_or("")
-                .to_string();
+    let content_future = state.repos.contents.find_for_document(corpus, &record);
+    let (kurum_result, content_result) = future::join(kurum_future, content_future).await;
 
-            // olusturulma_tarihi'ni ISO formatına çevir
-            let olusturulma_tarihi = match content_doc
-                .get_str("olusturulma_tarihi")
-                .or_else(|_| content_doc.get_str("created_at"))
-            {
-                Ok(s) => {
-                    if s.contains('T') || s.contains('Z') {
-                        s.to_string()
-                    } else {
-                        format!("{}T00:00:00Z", s)
-                    }
-                }
-                Err(_) => match metadata_doc.get_str("olusturulma_tarihi") {
-                    Ok(s) => {
-                        if s.contains('T') || s.contains('Z') {
-                            s.to_string()
-                        } else {
-                            format!("{}T00:00:00Z", s)
-                        }
-                    }
-                    Err(_) => String::from(Utc::now().to_rfc3339()),
-                },
-            };
+    // Kurum bilgilerini işle
+    let kurum = kurum_result.ok().flatten().unwrap_or_default();
 
-            (content_id, icerik, olusturulma_tarihi)
-        }
+    // Content'i işle
+    let content = match content_result {
+        Ok(Some(content)) => content,
         _ => {
-            // Content bulunamadıysa string ile de dene (fallback)
-            match content_collection
-                .find_one(doc! { "metadata_id": &metadata_id }, None)
-                .await
-            {
-                Ok(Some(content_doc)) => {
-                    let content_id = content_doc
-                        .get_object_id("_id")
-                        .map(|oid| oid.to_hex())
-                        .unwrap_or_default();
-
-                    let icerik = content_doc
-                        .get_str("icerik")
-                        .or_else(|_| content_doc.get_str("content"))
-                        .or_else(|_| content_doc.get_str("text"))
-                        .unwrap_or("")
-                        .to_string();
-
-                    let olusturulma_tarihi = match content_doc
-                        .get_str("olusturulma_tarihi")
-                        .or_else(|_| content_doc.get_str("created_at"))
-                    {
-                        Ok(s) => {
-                            if s.contains('T') || s.contains('Z') {
-                                s.to_string()
-                            } else {
-                                format!("{}T00:00:00Z", s)
-                            }
-                        }
-                        Err(_) => match metadata_doc.get_str("olusturulma_tarihi") {
-                            Ok(s) => {
-                                if s.contains('T') || s.contains('Z') {
-                                    s.to_string()
-                                } else {
-                                    format!("{}T00:00:00Z", s)
-                                }
-                            }
-                            Err(_) => String::from(Utc::now().to_rfc3339()),
-                        },
-                    };
-
-                    (content_id, icerik, olusturulma_tarihi)
-                }
-                _ => {
-                    log::warn!("Content bulunamadı - metadata_id: {}", metadata_id);
-                    (String::new(), String::new(), String::new())
-                }
-            }
+            log::warn!("Content bulunamadı - metadata_id: {}", record.id);
+            ContentRecord::default()
         }
     };
 
-    HttpResponse::Ok().json(DocumentDetailResponse {
-        success: true,
-        data: DocumentDetailData {
-            metadata: DocumentMetadata {
-                id: metadata_id.clone(),
-                kurum_id: kurum_id.clone(),
-                kurum_adi: kurum_adi.clone(),
-                kurum_logo: kurum_logo.clone(),
-                kurum_aciklama: kurum_aciklama.clone(),
-                pdf_adi,
-                etiketler,
-                belge_yayin_tarihi,
-                belge_durumu,
-                aciklama,
-                url_slug,
-                belge_turu,
-                anahtar_kelimeler,
-                status,
-                sayfa_sayisi,
-                dosya_boyutu_mb,
-                pdf_url,
-                karar_tarihi,
-            },
-            content: DocumentContent {
-                id: content_id,
-                metadata_id,
-                icerik,
-                olusturulma_tarihi: content_olusturulma_tarihi,
-            },
-            kurum_adi,
-            kurum_logo,
-            kurum_aciklama,
+    // olusturulma_tarihi RFC 3339'a çevrilir; okunamıyorsa null döner
+    let olusturulma_tarihi = content
+        .olusturulma_tarihi
+        .or_else(|| Some(record.olusturulma_tarihi.clone()).filter(|s| !s.is_empty()))
+        .and_then(|value| match dates::to_rfc3339(&value) {
+            Ok(timestamp) => Some(timestamp),
+            Err(e) => {
+                log::warn!("Belge oluşturulma tarihi okunamadı - metadata_id: {}: {}", record.id, e);
+                None
+            }
+        });
+
+    let url = state.urls.document(corpus, &record.url_slug, &record.id);
+    Ok(Some(DocumentDetailData {
+        metadata: DocumentMetadata {
+            id: record.id.clone(),
+            kurum_id: record.kurum_id,
+            kurum_adi: kurum.kurum_adi.clone(),
+            kurum_logo: kurum.kurum_logo.clone(),
+            kurum_aciklama: kurum.aciklama.clone(),
+            pdf_adi: record.pdf_adi,
+            etiketler: record.etiketler,
+            belge_yayin_tarihi: record.belge_yayin_tarihi,
+            belge_durumu: record.belge_durumu,
+            aciklama: record.aciklama,
+            url_slug: record.url_slug,
+            url,
+            belge_turu: record.belge_turu,
+            anahtar_kelimeler: record.anahtar_kelimeler,
+            status: record.status,
+            sayfa_sayisi: record.sayfa_sayisi.unwrap_or(0),
+            dosya_boyutu_mb: record.dosya_boyutu_mb.unwrap_or(0.0),
+            pdf_url: record.pdf_url,
+            karar_tarihi: record.karar_tarihi,
+        },
+        content: DocumentContent {
+            id: content.id,
+            metadata_id: record.id,
+            icerik: content.icerik,
+            olusturulma_tarihi,
         },
+        kurum_adi: kurum.kurum_adi,
+        kurum_logo: kurum.kurum_logo,
+        kurum_aciklama: kurum.aciklama,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/documents/{slug}",
+    tag = "belgeler",
+    params(("slug" = String, Path, description = "Belgenin url_slug değeri")),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = DocumentDetailResponse),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_document_by_slug(
+    state: web::Data<AppState>,
+    slug: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let data = fetch_document_detail(&state, state.corpora.mevzuat(), slug.as_str())
+        .await
+        .map_err(ApiError::storage(Resource::Document))?
+        .ok_or(ApiError::DocumentNotFound)?;
+
+    Ok(HttpResponse::Ok().json(DocumentDetailResponse {
+        success: true,
+        data,
         message: "İşlem başarılı".to_string(),
-    })
+    }))
 }
 
 pub fn routes(cfg: &mut web::ServiceConfig) {
@@ -1,15 +1,13 @@
-use actix_web::{web, HttpResponse, http::StatusCode};
-use mongodb::{Collection, bson::{doc, oid::ObjectId, Document as MongoDocument}};
+use actix_web::{web, HttpResponse};
 use crate::config::AppState;
-use crate::models::document::{
-    DocumentResponse, DocumentsListResponse,
-    DocumentDetailResponse, DocumentDetailData, DocumentMetadata, DocumentContent
-};
-use regex;
-use chrono::Utc;
-use futures::{future, FutureExt};
-
-#[derive(serde::Deserialize)]
+use crate::models::error::ErrorEnvelope;
+use crate::error::{ApiError, Resource};
+use crate::handlers::document::{fetch_document_detail, fetch_documents, DocumentQuery};
+use crate::models::document::{DocumentsListResponse, DocumentDetailResponse};
+use utoipa::IntoParams;
+
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DocumentQueryV2 {
     pub limit: Option<u64>,
     pub offset: Option<u64>,
@@ -19,441 +17,74 @@ pub struct DocumentQueryV2 {
     pub etiketler: Option<String>,
 }
 
-pub async fn get_documents_yargitay(
-    state: web::Data<AppState>,
-    query: web::Query<DocumentQueryV2>,
-) -> HttpResponse {
-    let metadata_collection: Collection<MongoDocument> = state.db.collection("yargitay");
-
-    // Query parametrelerini al
-    let limit = query.limit.unwrap_or(10000).min(10000); // Maksimum 10000
-    let offset = query.offset.unwrap_or(0);
-    let sort_by = query.sort_by.as_deref().unwrap_or("olusturulma_tarihi");
-    let sort_order = query.sort_order.as_deref().unwrap_or("desc");
-    let sort_value = if sort_order == "asc" { 1 } else { -1 };
-
-    // Match stage için filter oluştur
-    let mut match_filter = doc! {};
-
-    // belge_turu filtresi
-    if let Some(belge_turu) = &query.belge_turu {
-        if !belge_turu.is_empty() {
-            match_filter.insert("belge_turu", belge_turu);
-        }
-    }
-
-    // etiketler filtresi (virgülle ayrılmış string içinde arama)
-    if let Some(etiketler) = &query.etiketler {
-        if !etiketler.is_empty() {
-            let regex_pattern = format!(r"(^|,\s*){}(,|$)", regex::escape(etiketler));
-            match_filter.insert("etiketler", doc! {
-                "$regex": regex_pattern,
-                "$options": "i"
-            });
+impl From<DocumentQueryV2> for DocumentQuery {
+    fn from(query: DocumentQueryV2) -> Self {
+        DocumentQuery {
+            kurum_id: None,
+            limit: query.limit,
+            offset: query.offset,
+            sort_by: query.sort_by,
+            sort_order: query.sort_order,
+            belge_turu: query.belge_turu,
+            etiketler: query.etiketler,
         }
     }
+}
 
-    // Count için filter'ı klonla (pipeline'da move edilecek)
-    let count_filter = match_filter.clone();
-
-    // Aggregation pipeline oluştur
-    let mut pipeline = vec![
-        doc! { "$match": match_filter },
-        doc! { "$sort": { sort_by: sort_value } },
-    ];
-
-    // Offset varsa $skip ekle
-    if offset > 0 {
-        pipeline.push(doc! { "$skip": offset as i64 });
-    }
-
-    // Limit ekle
-    pipeline.push(doc! { "$limit": limit as i64 });
-
-    // Aggregation çalıştır
-    let mut cursor = match metadata_collection.aggregate(pipeline, None).await {
-        Ok(cursor) => cursor,
-        Err(e) => {
-            log::error!("MongoDB aggregation hatası: {}", e);
-            return HttpResponse::InternalServerError().json(DocumentsListResponse {
-                success: false,
-                data: vec![],
-                count: None,
-                message: "Belgeler alınamadı".to_string(),
-            });
-        }
-    };
-
-    let mut documents: Vec<DocumentResponse> = Vec::new();
-
-    // Sonuçları işle
-    while let Ok(true) = cursor.advance().await {
-        if let Ok(doc_map) = cursor.deserialize_current() {
-            let url_slug = doc_map
-                .get_str("url_slug")
-                .unwrap_or("")
-                .to_string();
-
-            let pdf_adi = doc_map
-                .get_str("pdf_adi")
-                .unwrap_or("")
-                .to_string();
-
-            let aciklama = doc_map
-                .get_str("aciklama")
-                .unwrap_or("")
-                .to_string();
-
-            let belge_yayin_tarihi = doc_map
-                .get_str("belge_yayin_tarihi")
-                .unwrap_or("")
-                .to_string();
-
-            let belge_turu = doc_map
-                .get_str("belge_turu")
-                .unwrap_or("")
-                .to_string();
-
-            let belge_durumu = doc_map
-                .get_str("belge_durumu")
-                .unwrap_or("")
-                .to_string();
-
-            let etiketler = doc_map
-                .get_str("etiketler")
-                .unwrap_or("")
-                .to_string();
-
-            let anahtar_kelimeler = doc_map
-                .get_str("anahtar_kelimeler")
-                .unwrap_or("")
-                .to_string();
-
-            let pdf_url = doc_map
-                .get_str("pdf_url")
-                .unwrap_or("")
-                .to_string();
-
-            let icerik = doc_map
-                .get_str("icerik")
-                .ok()
-                .filter(|value| !value.is_empty())
-                .map(|value| value.to_string());
-
-            let karar_tarihi = doc_map
-                .get_str("kararTarihi")
-                .or_else(|_| doc_map.get_str("karar_tarihi"))
-                .ok()
-                .filter(|value| !value.is_empty())
-                .map(|value| value.to_string());
-
-            let dosya_boyutu_mb = doc_map
-                .get_f64("dosya_boyutu_mb")
-                .ok()
-                .or_else(|| {
-                    doc_map
-                        .get_i32("dosya_boyutu_mb")
-                        .ok()
-                        .map(|value| value as f64)
-                })
-                .or_else(|| {
-                    doc_map
-                        .get_i64("dosya_boyutu_mb")
-                        .ok()
-                        .map(|value| value as f64)
-                });
-
-            let sayfa_sayisi = doc_map
-                .get_i32("sayfa_sayisi")
-                .ok()
-                .or_else(|| doc_map.get_i64("sayfa_sayisi").ok().map(|value| value as i32));
-
-            documents.push(DocumentResponse {
-                url_slug,
-                pdf_adi,
-                aciklama,
-                belge_yayin_tarihi,
-                belge_turu,
-                belge_durumu,
-                etiketler,
-                anahtar_kelimeler,
-                pdf_url,
-                icerik,
-                karar_tarihi,
-                dosya_boyutu_mb,
-                sayfa_sayisi,
-            });
-        }
-    }
+#[utoipa::path(
+    get,
+    path = "/api/v2/documents",
+    tag = "yargitay",
+    params(DocumentQueryV2),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = DocumentsListResponse),
+        (status = 400, description = "Geçersiz parametre", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_documents_yargitay(
+    state: web::Data<AppState>,
+    query: web::Query<DocumentQueryV2>,
+) -> Result<HttpResponse, ApiError> {
+    let query: DocumentQuery = query.into_inner().into();
 
-    // Toplam sayıyı al (pagination için)
-    let count = match metadata_collection.count_documents(count_filter, None).await {
-        Ok(count) => Some(count),
-        Err(_) => None,
-    };
+    let (documents, count) = fetch_documents(&state, state.corpora.yargitay(), &query)
+        .await
+        .map_err(ApiError::storage(Resource::Documents))?;
 
-    HttpResponse::Ok().json(DocumentsListResponse {
+    Ok(HttpResponse::Ok().json(DocumentsListResponse {
         success: true,
         data: documents,
         count,
         message: "İşlem başarılı".to_string(),
-    })
+    }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v2/documents/{slug}",
+    tag = "yargitay",
+    params(("slug" = String, Path, description = "Belgenin url_slug değeri")),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = DocumentDetailResponse),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
 pub async fn get_document_by_slug_yargitay(
     state: web::Data<AppState>,
     slug: web::Path<String>,
-) -> HttpResponse {
-    let metadata_collection: Collection<MongoDocument> = state.db.collection("yargitay");
-    let kurum_collection: Collection<MongoDocument> = state.db.collection("kurumlar");
-
-    // Metadata'yı url_slug ile bul
-    let metadata_doc = match metadata_collection
-        .find_one(doc! { "url_slug": slug.as_str() }, None)
+) -> Result<HttpResponse, ApiError> {
+    let data = fetch_document_detail(&state, state.corpora.yargitay(), slug.as_str())
         .await
-    {
-        Ok(Some(doc)) => doc,
-        Ok(None) => {
-            return HttpResponse::build(StatusCode::NOT_FOUND).json(DocumentDetailResponse {
-                success: false,
-                data: DocumentDetailData {
-                    metadata: DocumentMetadata {
-                        id: String::new(),
-                        kurum_id: String::new(),
-                        kurum_adi: String::new(),
-                        kurum_logo: String::new(),
-                        kurum_aciklama: String::new(),
-                        pdf_adi: String::new(),
-                        etiketler: String::new(),
-                        belge_yayin_tarihi: String::new(),
-                        belge_durumu: String::new(),
-                        aciklama: String::new(),
-                        url_slug: String::new(),
-                        belge_turu: String::new(),
-                        anahtar_kelimeler: String::new(),
-                        status: String::new(),
-                        sayfa_sayisi: 0,
-                        dosya_boyutu_mb: 0.0,
-                        pdf_url: String::new(),
-                        karar_tarihi: None,
-                    },
-                    content: DocumentContent {
-                        id: String::new(),
-                        metadata_id: String::new(),
-                        icerik: String::new(),
-                        olusturulma_tarihi: String::new(),
-                    },
-                    kurum_adi: String::new(),
-                    kurum_logo: String::new(),
-                    kurum_aciklama: String::new(),
-                },
-                message: "Belge bulunamadı".to_string(),
-            });
-        }
-        Err(e) => {
-            log::error!("MongoDB metadata sorgu hatası: {}", e);
-            return HttpResponse::InternalServerError().json(DocumentDetailResponse {
-                success: false,
-                data: DocumentDetailData {
-                    metadata: DocumentMetadata {
-                        id: String::new(),
-                        kurum_id: String::new(),
-                        kurum_adi: String::new(),
-                        kurum_logo: String::new(),
-                        kurum_aciklama: String::new(),
-                        pdf_adi: String::new(),
-                        etiketler: String::new(),
-                        belge_yayin_tarihi: String::new(),
-                        belge_durumu: String::new(),
-                        aciklama: String::new(),
-                        url_slug: String::new(),
-                        belge_turu: String::new(),
-                        anahtar_kelimeler: String::new(),
-                        status: String::new(),
-                        sayfa_sayisi: 0,
-                        dosya_boyutu_mb: 0.0,
-                        pdf_url: String::new(),
-                        karar_tarihi: None,
-                    },
-                    content: DocumentContent {
-                        id: String::new(),
-                        metadata_id: String::new(),
-                        icerik: String::new(),
-                        olusturulma_tarihi: String::new(),
-                    },
-                    kurum_adi: String::new(),
-                    kurum_logo: String::new(),
-                    kurum_aciklama: String::new(),
-                },
-                message: "Belge alınamadı".to_string(),
-            });
-        }
-    };
-
-    // Metadata'dan bilgileri çıkar
-    let metadata_id = metadata_doc
-        .get_object_id("_id")
-        .map(|oid| oid.to_hex())
-        .unwrap_or_default();
-
-    let kurum_id = metadata_doc
-        .get_str("kurum_id")
-        .unwrap_or("")
-        .to_string();
-
-    let pdf_adi = metadata_doc
-        .get_str("pdf_adi")
-        .unwrap_or("")
-        .to_string();
-
-    let belge_yayin_tarihi = metadata_doc
-        .get_str("belge_yayin_tarihi")
-        .unwrap_or("")
-        .to_string();
-
-    let etiketler = metadata_doc
-        .get_str("etiketler")
-        .unwrap_or("")
-        .to_string();
-
-    let aciklama = metadata_doc
-        .get_str("aciklama")
-        .unwrap_or("")
-        .to_string();
-
-    let belge_turu = metadata_doc
-        .get_str("belge_turu")
-        .unwrap_or("")
-        .to_string();
-
-    let belge_durumu = metadata_doc
-        .get_str("belge_durumu")
-        .unwrap_or("")
-        .to_string();
-
-    let url_slug = metadata_doc
-        .get_str("url_slug")
-        .unwrap_or("")
-        .to_string();
-
-    let anahtar_kelimeler = metadata_doc
-        .get_str("anahtar_kelimeler")
-        .unwrap_or("")
-        .to_string();
-
-    let status = metadata_doc
-        .get_str("status")
-        .unwrap_or("")
-        .to_string();
-
-    let sayfa_sayisi = metadata_doc
-        .get_i32("sayfa_sayisi")
-        .unwrap_or(0);
-
-    let dosya_boyutu_mb = metadata_doc
-        .get_f64("dosya_boyutu_mb")
-        .unwrap_or(0.0);
-
-    let pdf_url = metadata_doc
-        .get_str("pdf_url")
-        .unwrap_or("")
-        .to_string();
-
-    let karar_tarihi = metadata_doc
-        .get_str("kararTarihi")
-        .or_else(|_| metadata_doc.get_str("karar_tarihi"))
-        .ok()
-        .filter(|value| !value.is_empty())
-        .map(|value| value.to_string());
-
-    // Kurum bilgilerini çek
-    let kurum_oid_result = ObjectId::parse_str(&kurum_id);
-    let kurum_future = if let Ok(kurum_oid) = kurum_oid_result {
-        kurum_collection.find_one(doc! { "_id": kurum_oid }, None).boxed()
-    } else {
-        future::ready(Ok(None::<MongoDocument>)).boxed()
-    };
-
-    let kurum_result = kurum_future.await;
-
-    let (kurum_adi, kurum_logo, kurum_aciklama) = match kurum_result {
-        Ok(Some(kurum_doc)) => {
-            let kurum_adi = kurum_doc
-                .get_str("kurum_adi")
-                .or_else(|_| kurum_doc.get_str("kurumAdi"))
-                .unwrap_or("")
-                .to_string();
-            let kurum_logo = kurum_doc
-                .get_str("kurum_logo")
-                .or_else(|_| kurum_doc.get_str("kurumLogo"))
-                .unwrap_or("")
-                .to_string();
-            let kurum_aciklama = kurum_doc
-                .get_str("aciklama")
-                .or_else(|_| kurum_doc.get_str("kurumAciklama"))
-                .unwrap_or("")
-                .to_string();
-            (kurum_adi, kurum_logo, kurum_aciklama)
-        }
-        _ => (String::new(), String::new(), String::new()),
-    };
-
-    let icerik = metadata_doc
-        .get_str("icerik")
-        .or_else(|_| metadata_doc.get_str("content"))
-        .or_else(|_| metadata_doc.get_str("text"))
-        .unwrap_or("")
-        .to_string();
-
-    let content_olusturulma_tarihi = match metadata_doc
-        .get_str("olusturulma_tarihi")
-        .or_else(|_| metadata_doc.get_str("created_at"))
-    {
-        Ok(s) => {
-            if s.contains('T') || s.contains('Z') {
-                s.to_string()
-            } else {
-                format!("{}T00:00:00Z", s)
-            }
-        }
-        Err(_) => String::from(Utc::now().to_rfc3339()),
-    };
+        .map_err(ApiError::storage(Resource::Document))?
+        .ok_or(ApiError::DocumentNotFound)?;
 
-    HttpResponse::Ok().json(DocumentDetailResponse {
+    Ok(HttpResponse::Ok().json(DocumentDetailResponse {
         success: true,
-        data: DocumentDetailData {
-            metadata: DocumentMetadata {
-                id: metadata_id.clone(),
-                kurum_id: kurum_id.clone(),
-                kurum_adi: kurum_adi.clone(),
-                kurum_logo: kurum_logo.clone(),
-                kurum_aciklama: kurum_aciklama.clone(),
-                pdf_adi,
-                etiketler,
-                belge_yayin_tarihi,
-                belge_durumu,
-                aciklama,
-                url_slug,
-                belge_turu,
-                anahtar_kelimeler,
-                status,
-                sayfa_sayisi,
-                dosya_boyutu_mb,
-                pdf_url,
-                karar_tarihi,
-            },
-            content: DocumentContent {
-                id: String::new(),
-                metadata_id,
-                icerik,
-                olusturulma_tarihi: content_olusturulma_tarihi,
-            },
-            kurum_adi,
-            kurum_logo,
-            kurum_aciklama,
-        },
+        data,
         message: "Başarılı".to_string(),
-    })
+    }))
 }
 
 pub fn routes_v2(cfg: &mut web::ServiceConfig) {
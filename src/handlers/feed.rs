@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+use std::time::SystemTime;
+use actix_web::http::header::{
+    CacheControl, CacheDirective, ETag, EntityTag, HttpDate, IfModifiedSince, IfNoneMatch, LastModified,
+};
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use sha2::{Digest, Sha256};
+use crate::config::{AppState, PublicUrls};
+use crate::corpus::Corpus;
+use crate::dates;
+use crate::error::{ApiError, Resource};
+use crate::feeds::{Feed, FeedEntry, FeedFormat};
+use crate::handlers::kurum::create_kurum_slug;
+use crate::models::error::ErrorEnvelope;
+use crate::models::kurum_duyuru_scraped::ozet;
+use crate::repository::{
+    DocumentFilter, DocumentRecord, DocumentSort, InstitutionRecord, Page, ScrapedAnnouncementFilter,
+    ScrapedAnnouncementRecord,
+};
+
+// RSS/Atom beslemeleri: son eklenen mevzuat ve Yargıtay kararları, kurum bazlı mevzuat ve
+// hasat edilen kurum duyuruları. Uzantı biçimi seçer (.xml RSS 2.0, .atom Atom). Cevaplar
+// ETag ve Last-Modified taşır; okuyucunun koşullu isteğine içerik değişmediyse 304 döner.
+
+// Beslemedeki girdi sayısı
+const FEED_SIZE: u64 = 50;
+// Okuyucuların ve ara önbelleklerin beslemeyi yeniden sormadan tutabileceği süre (saniye)
+const MAX_AGE_SECS: u32 = 300;
+
+fn feed_format(extension: &str) -> Result<FeedFormat, ApiError> {
+    FeedFormat::from_extension(extension).ok_or(ApiError::RouteNotFound)
+}
+
+// Gövdenin özetinden ETag; aynı içerik her zaman aynı etiketi alır
+fn entity_tag(body: &str) -> EntityTag {
+    EntityTag::new_strong(hex::encode(&Sha256::digest(body.as_bytes())[..16]))
+}
+
+// Koşullu istek: If-None-Match varsa yalnızca ona, yoksa If-Modified-Since'e bakılır
+fn not_modified(req: &HttpRequest, etag: &EntityTag, last_modified: Option<SystemTime>) -> bool {
+    if let Some(if_none_match) = req.get_header::<IfNoneMatch>() {
+        return match if_none_match {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::Items(tags) => tags.iter().any(|tag| tag.weak_eq(etag)),
+        };
+    }
+    match (req.get_header::<IfModifiedSince>(), last_modified) {
+        (Some(IfModifiedSince(since)), Some(last_modified)) => {
+            // HTTP tarihleri saniye hassasiyetindedir
+            SystemTime::from(HttpDate::from(last_modified)) <= SystemTime::from(since)
+        }
+        _ => false,
+    }
+}
+
+fn feed_response(req: &HttpRequest, feed: &Feed, format: FeedFormat) -> HttpResponse {
+    let body = feed.render(format);
+    let etag = entity_tag(&body);
+    let last_modified = feed.updated().map(SystemTime::from);
+
+    let unchanged = not_modified(req, &etag, last_modified);
+    let mut response = if unchanged {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    response
+        .insert_header(ETag(etag))
+        .insert_header(CacheControl(vec![CacheDirective::Public, CacheDirective::MaxAge(MAX_AGE_SECS)]));
+    if let Some(last_modified) = last_modified {
+        response.insert_header(LastModified(HttpDate::from(last_modified)));
+    }
+    if unchanged {
+        return response.finish();
+    }
+    response.content_type(format.content_type()).body(body)
+}
+
+fn document_entry(urls: &PublicUrls, corpus: &Corpus, record: DocumentRecord, kurum_adi: Option<&str>) -> FeedEntry {
+    let kurum_adi = kurum_adi.map(str::to_string).unwrap_or_else(|| record.kurum_adi());
+    FeedEntry {
+        id: format!("{}/{}", corpus.key, record.id),
+        link: urls.document(corpus, &record.url_slug, &record.id),
+        summary: Some(record.aciklama).filter(|aciklama| !aciklama.trim().is_empty()),
+        published: dates::parse_timestamp(&record.olusturulma_tarihi).ok(),
+        categories: [record.belge_turu, kurum_adi]
+            .into_iter()
+            .filter(|category| !category.trim().is_empty())
+            .collect(),
+        title: record.pdf_adi,
+    }
+}
+
+// Duyuruda tarih varsa o gün, yoksa ilk görüldüğü hasat zamanı kullanılır
+fn announcement_entry(record: ScrapedAnnouncementRecord, kurum_adi: Option<&str>) -> FeedEntry {
+    let published = record
+        .tarih
+        .as_deref()
+        .and_then(|tarih| dates::parse_timestamp(tarih).ok())
+        .or_else(|| dates::parse_timestamp(&record.first_seen_at).ok());
+    FeedEntry {
+        id: format!("duyuru/{}", record.id),
+        summary: record.icerik.as_deref().map(ozet).filter(|ozet| !ozet.is_empty()),
+        published,
+        categories: kurum_adi.into_iter().map(str::to_string).collect(),
+        title: record.baslik,
+        link: record.link,
+    }
+}
+
+async fn corpus_feed(
+    state: &AppState,
+    req: &HttpRequest,
+    corpus: &Corpus,
+    name: &str,
+    extension: &str,
+) -> Result<HttpResponse, ApiError> {
+    let format = feed_format(extension)?;
+    let records = state
+        .repos
+        .documents
+        .recent(corpus, FEED_SIZE)
+        .await
+        .map_err(ApiError::storage(Resource::Regulations))?;
+
+    let feed = Feed {
+        id: format!("feeds/{}", name),
+        title: format!("MevzuatGPT - Son Eklenen {}", corpus.title),
+        description: format!("MevzuatGPT'ye son eklenen {} belgeleri", corpus.title),
+        link: state.urls.base_url().to_string(),
+        self_link: state.urls.absolute(&format!("/feeds/{}", name)),
+        entries: records.into_iter().map(|record| document_entry(&state.urls, corpus, record, None)).collect(),
+    };
+    Ok(feed_response(req, &feed, format))
+}
+
+async fn find_kurum(state: &AppState, slug: &str) -> Result<InstitutionRecord, ApiError> {
+    state
+        .repos
+        .institutions
+        .list()
+        .await
+        .map_err(ApiError::storage(Resource::Institution))?
+        .into_iter()
+        .find(|kurum| !kurum.kurum_adi.is_empty() && create_kurum_slug(&kurum.kurum_adi) == slug)
+        .ok_or(ApiError::InstitutionNotFound)
+}
+
+#[utoipa::path(
+    get,
+    path = "/feeds/regulations.{format}",
+    tag = "beslemeler",
+    params(("format" = String, Path, description = "xml (RSS 2.0) ya da atom")),
+    responses(
+        (status = 200, description = "Son eklenen mevzuat beslemesi", content_type = "application/rss+xml", body = String),
+        (status = 304, description = "Besleme değişmedi (If-None-Match / If-Modified-Since)"),
+        (status = 404, description = "Bilinmeyen biçim", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_regulations_feed(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    corpus_feed(&state, &req, state.corpora.mevzuat(), "regulations", &path).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/feeds/yargitay.{format}",
+    tag = "beslemeler",
+    params(("format" = String, Path, description = "xml (RSS 2.0) ya da atom")),
+    responses(
+        (status = 200, description = "Son eklenen Yargıtay kararları beslemesi", content_type = "application/rss+xml", body = String),
+        (status = 304, description = "Besleme değişmedi (If-None-Match / If-Modified-Since)"),
+        (status = 404, description = "Bilinmeyen biçim", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_yargitay_feed(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    corpus_feed(&state, &req, state.corpora.yargitay(), "yargitay", &path).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/feeds/kurum/{slug}.{format}",
+    tag = "beslemeler",
+    params(
+        ("slug" = String, Path, description = "Kurum adından üretilen slug"),
+        ("format" = String, Path, description = "xml (RSS 2.0) ya da atom"),
+    ),
+    responses(
+        (status = 200, description = "Kurumun son eklenen mevzuatı", content_type = "application/rss+xml", body = String),
+        (status = 304, description = "Besleme değişmedi (If-None-Match / If-Modified-Since)"),
+        (status = 404, description = "Kurum bulunamadı ya da bilinmeyen biçim", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_kurum_feed(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, ApiError> {
+    let (slug, extension) = path.into_inner();
+    let format = feed_format(&extension)?;
+    let kurum = find_kurum(&state, &slug).await?;
+
+    let corpus = state.corpora.mevzuat();
+    let filter = DocumentFilter {
+        kurum_id: Some(kurum.id.clone()),
+        ..DocumentFilter::default()
+    };
+    let sort = DocumentSort {
+        field: corpus.fields.created_at.clone(),
+        descending: true,
+    };
+    let records = state
+        .repos
+        .documents
+        .list(corpus, &filter, &sort, Page { offset: 0, limit: FEED_SIZE })
+        .await
+        .map_err(ApiError::storage(Resource::Documents))?;
+
+    let feed = Feed {
+        id: format!("feeds/kurum/{}", kurum.id),
+        title: format!("MevzuatGPT - {} Mevzuatı", kurum.kurum_adi),
+        description: format!("{} tarafından yayımlanan ve MevzuatGPT'ye son eklenen belgeler", kurum.kurum_adi),
+        link: state.urls.institution(&slug),
+        self_link: state.urls.absolute(&format!("/feeds/kurum/{}", slug)),
+        entries: records
+            .into_iter()
+            .map(|record| document_entry(&state.urls, corpus, record, Some(&kurum.kurum_adi)))
+            .collect(),
+    };
+    Ok(feed_response(&req, &feed, format))
+}
+
+#[utoipa::path(
+    get,
+    path = "/feeds/kurum/{slug}/duyurular.{format}",
+    tag = "beslemeler",
+    params(
+        ("slug" = String, Path, description = "Kurum adından üretilen slug"),
+        ("format" = String, Path, description = "xml (RSS 2.0) ya da atom"),
+    ),
+    responses(
+        (status = 200, description = "Kurumun hasat edilen duyuruları", content_type = "application/rss+xml", body = String),
+        (status = 304, description = "Besleme değişmedi (If-None-Match / If-Modified-Since)"),
+        (status = 404, description = "Kurum bulunamadı ya da bilinmeyen biçim", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_kurum_announcements_feed(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, ApiError> {
+    let (slug, extension) = path.into_inner();
+    let format = feed_format(&extension)?;
+    let kurum = find_kurum(&state, &slug).await?;
+
+    let filter = ScrapedAnnouncementFilter {
+        kurum_id: Some(kurum.id.clone()),
+        ..ScrapedAnnouncementFilter::default()
+    };
+    let records = state
+        .repos
+        .scraped_announcements
+        .list(&filter, Page { offset: 0, limit: FEED_SIZE })
+        .await
+        .map_err(ApiError::storage(Resource::ScrapedAnnouncements))?;
+
+    let feed = Feed {
+        id: format!("feeds/kurum/{}/duyurular", kurum.id),
+        title: format!("{} Duyuruları", kurum.kurum_adi),
+        description: format!("{} sitesinde yayımlanan duyurular", kurum.kurum_adi),
+        link: state.urls.institution(&slug),
+        self_link: state.urls.absolute(&format!("/feeds/kurum/{}/duyurular", slug)),
+        entries: records
+            .into_iter()
+            .map(|record| announcement_entry(record, Some(&kurum.kurum_adi)))
+            .collect(),
+    };
+    Ok(feed_response(&req, &feed, format))
+}
+
+#[utoipa::path(
+    get,
+    path = "/feeds/duyurular.{format}",
+    tag = "beslemeler",
+    params(("format" = String, Path, description = "xml (RSS 2.0) ya da atom")),
+    responses(
+        (status = 200, description = "Tüm kurumların hasat edilen duyuruları", content_type = "application/rss+xml", body = String),
+        (status = 304, description = "Besleme değişmedi (If-None-Match / If-Modified-Since)"),
+        (status = 404, description = "Bilinmeyen biçim", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_announcements_feed(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let format = feed_format(&path)?;
+    let records = state
+        .repos
+        .scraped_announcements
+        .list(&ScrapedAnnouncementFilter::default(), Page { offset: 0, limit: FEED_SIZE })
+        .await
+        .map_err(ApiError::storage(Resource::ScrapedAnnouncements))?;
+    // Girdilerin kategorisi kurum adıdır
+    let kurumlar: HashMap<String, String> = state
+        .repos
+        .institutions
+        .list()
+        .await
+        .map_err(ApiError::storage(Resource::Institutions))?
+        .into_iter()
+        .map(|kurum| (kurum.id, kurum.kurum_adi))
+        .collect();
+
+    let feed = Feed {
+        id: "feeds/duyurular".to_string(),
+        title: "MevzuatGPT - Kurum Duyuruları".to_string(),
+        description: "Kamu kurumlarının sitelerinde yayımlanan son duyurular".to_string(),
+        link: state.urls.base_url().to_string(),
+        self_link: state.urls.absolute("/feeds/duyurular"),
+        entries: records
+            .into_iter()
+            .map(|record| {
+                let kurum_adi = kurumlar.get(&record.kurum_id).cloned();
+                announcement_entry(record, kurum_adi.as_deref())
+            })
+            .collect(),
+    };
+    Ok(feed_response(&req, &feed, format))
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/regulations.{format}", web::get().to(get_regulations_feed))
+        .route("/yargitay.{format}", web::get().to(get_yargitay_feed))
+        .route("/duyurular.{format}", web::get().to(get_announcements_feed))
+        .route("/kurum/{slug}/duyurular.{format}", web::get().to(get_kurum_announcements_feed))
+        .route("/kurum/{slug}.{format}", web::get().to(get_kurum_feed));
+}
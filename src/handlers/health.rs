@@ -2,9 +2,18 @@ use actix_web::{web, HttpResponse};
 use serde_json::json;
 use crate::config::AppState;
 
+#[utoipa::path(
+    get,
+    path = "/api/health",
+    tag = "sistem",
+    responses(
+        (status = 200, description = "Sunucu ve MongoDB bağlantısı başarılı", body = Value),
+        (status = 503, description = "MongoDB bağlantısı başarısız", body = Value),
+    )
+)]
 pub async fn health_check(state: web::Data<AppState>) -> HttpResponse {
     // MongoDB bağlantısını kontrol et
-    let mongodb_status = match state.db.list_collection_names(None).await {
+    let mongodb_status = match state.repos.health.ping().await {
         Ok(_) => {
             json!({
                 "status": "connected",
@@ -27,7 +36,14 @@ pub async fn health_check(state: web::Data<AppState>) -> HttpResponse {
 
     let success = mongodb_status["status"] == "connected";
 
-    HttpResponse::Ok().json(json!({
+    // Depolama erişilemiyorsa load balancer'ların fark etmesi için 503 döner
+    let mut response = if success {
+        HttpResponse::Ok()
+    } else {
+        HttpResponse::ServiceUnavailable()
+    };
+
+    response.json(json!({
         "success": success,
         "server": server_status,
         "mongodb": mongodb_status,
@@ -1,11 +1,11 @@
 use actix_web::{web, HttpResponse};
-use mongodb::{Collection, bson::Document as MongoDocument};
-use futures::stream::TryStreamExt;
 use crate::config::AppState;
+use crate::models::error::ErrorEnvelope;
+use crate::error::{ApiError, Resource};
 use crate::models::kurum::{KurumResponse, InstitutionsListResponse, KurumBySlugResponse, KurumBySlugData};
 
 // Helper function to create slug from institution name
-fn create_kurum_slug(name: &str) -> String {
+pub(crate) fn create_kurum_slug(name: &str) -> String {
     name.to_lowercase()
         .replace(' ', "-")
         .replace('ç', "c")
@@ -16,155 +16,83 @@ fn create_kurum_slug(name: &str) -> String {
         .replace('ü', "u")
 }
 
-pub async fn get_kurumlar(state: web::Data<AppState>) -> HttpResponse {
-    let kurum_collection: Collection<MongoDocument> = state.db.collection("kurumlar");
-
+#[utoipa::path(
+    get,
+    path = "/api/v1/institutions",
+    tag = "kurumlar",
+    responses(
+        (status = 200, description = "İşlem başarılı", body = InstitutionsListResponse),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_kurumlar(state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
     // Tüm kurumları al
-    let kurum_docs = match kurum_collection.find(None, None).await {
-        Ok(cursor) => {
-            match cursor.try_collect::<Vec<_>>().await {
-                Ok(docs) => docs,
-                Err(e) => {
-                    log::error!("Kurum deserialize hatası: {}", e);
-                    return HttpResponse::InternalServerError().json(InstitutionsListResponse {
-                        success: false,
-                        data: vec![],
-                        count: None,
-                        message: "Kurumlar listesi alınamadı".to_string(),
-                    });
-                }
-            }
-        }
-        Err(e) => {
-            log::error!("MongoDB sorgu hatası: {}", e);
-            return HttpResponse::InternalServerError().json(InstitutionsListResponse {
-                success: false,
-                data: vec![],
-                count: None,
-                message: "Kurumlar listesi alınamadı".to_string(),
-            });
-        }
-    };
+    let kurum_records = state
+        .repos
+        .institutions
+        .list()
+        .await
+        .map_err(ApiError::storage(Resource::Institutions))?;
 
     // Kurumları response formatına dönüştür
-    let mut kurumlar: Vec<KurumResponse> = Vec::new();
-
-    for doc_map in kurum_docs {
-        let kurum_id = doc_map
-            .get_object_id("_id")
-            .map(|oid| oid.to_hex())
-            .unwrap_or_default();
-
-        let kurum_adi = doc_map
-            .get_str("kurum_adi")
-            .or_else(|_| doc_map.get_str("kurumAdi"))
-            .unwrap_or("")
-            .to_string();
-
-        let kurum_logo = doc_map
-            .get_str("kurum_logo")
-            .or_else(|_| doc_map.get_str("kurumLogo"))
-            .unwrap_or("")
-            .to_string();
-
-        let kurum_aciklama = doc_map
-            .get_str("aciklama")
-            .or_else(|_| doc_map.get_str("kurumAciklama"))
-            .unwrap_or("")
-            .to_string();
-
-        let detsis = doc_map
-            .get_str("detsis")
-            .ok()
-            .filter(|s| !s.is_empty())
-            .map(|s| s.to_string());
-
-        kurumlar.push(KurumResponse {
-            kurum_id,
-            kurum_adi,
-            kurum_logo,
-            kurum_aciklama,
-            detsis,
-        });
-    }
+    let kurumlar: Vec<KurumResponse> = kurum_records
+        .into_iter()
+        .map(|kurum| KurumResponse {
+            kurum_id: kurum.id,
+            kurum_adi: kurum.kurum_adi,
+            kurum_logo: kurum.kurum_logo,
+            kurum_aciklama: kurum.aciklama,
+            detsis: kurum.detsis,
+        })
+        .collect();
 
     let total_count = kurumlar.len() as u64;
 
-    HttpResponse::Ok().json(InstitutionsListResponse {
+    Ok(HttpResponse::Ok().json(InstitutionsListResponse {
         success: true,
         data: kurumlar,
         count: Some(total_count),
         message: "İşlem başarılı".to_string(),
-    })
+    }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/institutions/slug/{slug}",
+    tag = "kurumlar",
+    params(("slug" = String, Path, description = "Kurum adından üretilen slug")),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = KurumBySlugResponse),
+        (status = 404, description = "Kayıt bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
 pub async fn get_kurum_by_slug(
     state: web::Data<AppState>,
     path: web::Path<String>,
-) -> HttpResponse {
+) -> Result<HttpResponse, ApiError> {
     let slug = path.into_inner();
-    let kurum_collection: Collection<MongoDocument> = state.db.collection("kurumlar");
 
     // Tüm kurumları al ve slug ile eşleştir
-    let kurum_docs = match kurum_collection.find(None, None).await {
-        Ok(cursor) => {
-            match cursor.try_collect::<Vec<_>>().await {
-                Ok(docs) => docs,
-                Err(e) => {
-                    log::error!("Kurum deserialize hatası: {}", e);
-                    return HttpResponse::InternalServerError().json(KurumBySlugResponse {
-                        success: false,
-                        data: None,
-                        message: "Kurum sorgulanamadı".to_string(),
-                    });
-                }
-            }
-        }
-        Err(e) => {
-            log::error!("MongoDB sorgu hatası: {}", e);
-            return HttpResponse::InternalServerError().json(KurumBySlugResponse {
-                success: false,
-                data: None,
-                message: "Kurum sorgulanamadı".to_string(),
-            });
-        }
-    };
+    let kurum_records = state
+        .repos
+        .institutions
+        .list()
+        .await
+        .map_err(ApiError::storage(Resource::Institution))?;
 
     // Slug ile eşleşen kurumu bul
-    for doc_map in kurum_docs {
-        let kurum_adi = doc_map
-            .get_str("kurum_adi")
-            .or_else(|_| doc_map.get_str("kurumAdi"))
-            .unwrap_or("")
-            .to_string();
-
-        if kurum_adi.is_empty() {
-            continue;
-        }
-
-        let kurum_slug = create_kurum_slug(&kurum_adi);
+    let kurum = kurum_records
+        .into_iter()
+        .find(|kurum| !kurum.kurum_adi.is_empty() && create_kurum_slug(&kurum.kurum_adi) == slug)
+        .ok_or(ApiError::InstitutionNotFound)?;
 
-        if kurum_slug == slug {
-            // Eşleşme bulundu, ID'yi döndür
-            let kurum_id = doc_map
-                .get_object_id("_id")
-                .map(|oid| oid.to_hex())
-                .unwrap_or_default();
-
-            return HttpResponse::Ok().json(KurumBySlugResponse {
-                success: true,
-                data: Some(KurumBySlugData { kurum_id }),
-                message: "Başarılı".to_string(),
-            });
-        }
-    }
-
-    // Kurum bulunamadı
-    HttpResponse::NotFound().json(KurumBySlugResponse {
-        success: false,
-        data: None,
-        message: "Kurum bulunamadı".to_string(),
-    })
+    // Eşleşme bulundu, ID'yi döndür
+    Ok(HttpResponse::Ok().json(KurumBySlugResponse {
+        success: true,
+        data: Some(KurumBySlugData { kurum_id: kurum.id }),
+        message: "Başarılı".to_string(),
+    }))
 }
 
 pub fn routes(cfg: &mut web::ServiceConfig) {
@@ -14,6 +14,18 @@ pub mod search;
 pub mod search_yargitay;
 pub mod documents_yargitay;
 pub mod sitemap;
+pub mod corpus;
+pub mod admin_documents;
+pub mod admin_institutions;
+pub mod admin_announcement_sources;
+pub mod admin_links;
+pub mod admin_audit;
+pub mod admin_crawler;
+pub mod admin_announcement_attachments;
+pub mod admin_webhooks;
+pub mod saved_search;
+pub mod feed;
 
 // Yeni handler'lar eklendikçe buraya ekleyin
 
+pub mod stream;
@@ -1,126 +1,73 @@
 use actix_web::{web, HttpResponse};
-use mongodb::{Collection, bson::{doc, Document as MongoDocument}};
 use crate::config::AppState;
+use crate::models::error::ErrorEnvelope;
+use crate::error::{ApiError, Resource};
+use crate::corpus::Corpus;
+use crate::repository::RepositoryError;
 use crate::models::regulation::{RecentRegulationResponse, RecentRegulationsListResponse};
+use utoipa::IntoParams;
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RecentRegulationsQuery {
     pub limit: Option<u64>,
 }
 
+// Corpus'tan bağımsız son eklenen belgeler (her zaman oluşturulma tarihine göre desc)
+pub async fn fetch_recent_regulations(
+    state: &AppState,
+    corpus: &Corpus,
+    limit: u64,
+) -> Result<Vec<RecentRegulationResponse>, RepositoryError> {
+    let records = state.repos.documents.recent(corpus, limit).await?;
+
+    Ok(records
+        .into_iter()
+        .map(|record| RecentRegulationResponse {
+            kurum_adi: record.kurum_adi(),
+            pdf_adi: record.pdf_adi,
+            aciklama: record.aciklama,
+            olusturulma_tarihi: record.olusturulma_tarihi,
+            belge_turu: record.belge_turu,
+            url_slug: record.url_slug,
+        })
+        .collect())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/regulations/recent",
+    tag = "belgeler",
+    params(RecentRegulationsQuery),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = RecentRegulationsListResponse),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
 pub async fn get_recent_regulations(
     state: web::Data<AppState>,
     query: web::Query<RecentRegulationsQuery>,
-) -> HttpResponse {
-    let metadata_collection: Collection<MongoDocument> = state.db.collection("metadata");
-
-    // Query parametrelerini al
-        let limit = query.limit.unwrap_or(50).min(1000); // Maksimum 1000 // Maksimum 1000
-    // Her zaman olusturulma_tarihi'ne göre desc sıralama yap
-    let sort_by = "olusturulma_tarihi";
-    let sort_value = -1; // desc (en yeni önce)
-
-    // Aggregation pipeline oluştur
-    let pipeline = vec![
-        doc! {
-            "$addFields": {
-                "kurum_id_object": {
-                    "$toObjectId": "$kurum_id"
-                }
-            }
-        },
-        doc! { "$sort": { sort_by: sort_value } },
-        doc! { "$limit": limit as i64 },
-        doc! {
-            "$lookup": {
-                "from": "kurumlar",
-                "localField": "kurum_id_object",
-                "foreignField": "_id",
-                "as": "kurum_bilgisi"
-            }
-        },
-        doc! {
-            "$unwind": {
-                "path": "$kurum_bilgisi",
-                "preserveNullAndEmptyArrays": true
-            }
-        },
-    ];
-
-    // Aggregation çalıştır
-    let mut cursor = match metadata_collection.aggregate(pipeline, None).await {
-        Ok(cursor) => cursor,
-        Err(e) => {
-            log::error!("MongoDB aggregation hatası: {}", e);
-            return HttpResponse::InternalServerError().json(RecentRegulationsListResponse {
-                success: false,
-                data: vec![],
-                count: 0,
-                message: None,
-                error: Some("Mevzuatlar alınamadı".to_string()),
-            });
-        }
-    };
-
-    let mut regulations: Vec<RecentRegulationResponse> = Vec::new();
-
-    // Sonuçları işle
-    while let Ok(true) = cursor.advance().await {
-        if let Ok(doc_map) = cursor.deserialize_current() {
-            // Kurum adını al
-            let kurum_adi = doc_map
-                .get_document("kurum_bilgisi")
-                .ok()
-                .and_then(|k| k.get_str("kurum_adi").ok())
-                .unwrap_or("")
-                .to_string();
+) -> Result<HttpResponse, ApiError> {
+    let limit = query.limit.unwrap_or(50).min(1000); // Maksimum 1000
 
-            // Diğer alanları al
-            let pdf_adi = doc_map
-                .get_str("pdf_adi")
-                .unwrap_or("")
-                .to_string();
-
-            let aciklama = doc_map
-                .get_str("aciklama")
-                .unwrap_or("")
-                .to_string();
-
-            let olusturulma_tarihi = doc_map
-                .get_str("olusturulma_tarihi")
-                .unwrap_or("")
-                .to_string();
-
-            let belge_turu = doc_map
-                .get_str("belge_turu")
-                .unwrap_or("")
-                .to_string();
-
-            let url_slug = doc_map
-                .get_str("url_slug")
-                .unwrap_or("")
-                .to_string();
-
-            regulations.push(RecentRegulationResponse {
-                pdf_adi,
-                kurum_adi,
-                aciklama,
-                olusturulma_tarihi,
-                belge_turu,
-                url_slug,
-            });
-        }
-    }
+    recent_regulations_response(
+        fetch_recent_regulations(&state, state.corpora.mevzuat(), limit).await,
+    )
+}
 
+pub(crate) fn recent_regulations_response(
+    result: Result<Vec<RecentRegulationResponse>, RepositoryError>,
+) -> Result<HttpResponse, ApiError> {
+    let regulations = result.map_err(ApiError::storage(Resource::Regulations))?;
     let count = regulations.len() as u64;
 
-    HttpResponse::Ok().json(RecentRegulationsListResponse {
+    Ok(HttpResponse::Ok().json(RecentRegulationsListResponse {
         success: true,
         data: regulations,
         count,
         message: Some("İşlem başarılı".to_string()),
         error: None,
-    })
+    }))
 }
 
 pub fn routes(cfg: &mut web::ServiceConfig) {
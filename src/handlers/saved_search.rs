@@ -0,0 +1,484 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use chrono::Utc;
+use utoipa::IntoParams;
+use crate::alerts::smtp::is_email;
+use crate::alerts::{target, CHANNELS, CHANNEL_EMAIL, CHANNEL_INBOX, CHANNEL_WEBHOOK};
+use crate::auth::ApiKeyIdentity;
+use crate::config::AppState;
+use crate::error::{ApiError, FieldError, FieldIssue, Resource};
+use crate::handlers::admin_documents::{is_http_url, non_empty};
+use crate::handlers::admin_institutions::validate_kurum_id;
+use crate::models::admin_institution::{DeleteRecordResponse, DeletedRecord};
+use crate::models::error::ErrorEnvelope;
+use crate::models::saved_search::{
+    AlertDetailResponse, AlertListResponse, AlertResponse, SavedSearchDetailResponse, SavedSearchInput,
+    SavedSearchListResponse, SavedSearchResponse,
+};
+use crate::repository::{AlertFilter, Page, SavedSearchRecord, SavedSearchWrite};
+use crate::utils::format_timestamp;
+use crate::webhooks;
+
+// Kayıtlı aramalar ve gelen kutusu. Her kayıt onu oluşturan API anahtarına aittir; başka
+// anahtarların kayıtları bulunamadı olarak döner. Aramaları kayıtlı arama işçisi
+// (alerts::spawn) çalıştırır; bu modül yalnızca kayıtları yönetir.
+
+const AD_MAX_LEN: usize = 100;
+const Q_MAX_LEN: usize = 200;
+const HEDEF_MAX_LEN: usize = 2000;
+// Anahtar başına en fazla kayıtlı arama
+const MAX_SAVED_SEARCHES: usize = 50;
+const DEFAULT_LIMIT: u64 = 20;
+const MAX_LIMIT: u64 = 100;
+
+// İsteği yapan API anahtarının id'si; anahtarsız istekler kayıtlı arama kullanamaz
+fn owner(req: &HttpRequest) -> Result<String, ApiError> {
+    req.extensions()
+        .get::<ApiKeyIdentity>()
+        .map(|identity| identity.id.clone())
+        .ok_or(ApiError::MissingApiKey)
+}
+
+// Boş metin filtreyi kaldırır, verilmeyen alan mevcut değeri korur
+fn optional_filter(value: &Option<String>, existing: Option<&Option<String>>) -> Option<String> {
+    match value {
+        Some(_) => non_empty(value).map(str::to_string),
+        None => existing.cloned().flatten(),
+    }
+}
+
+async fn prepare(
+    state: &AppState,
+    owner: &str,
+    input: SavedSearchInput,
+    existing: Option<&SavedSearchRecord>,
+) -> Result<SavedSearchWrite, ApiError> {
+    let mut errors = Vec::new();
+
+    let q = match non_empty(&input.q) {
+        None if input.q.is_some() || existing.is_none() => {
+            errors.push(FieldError::new("q", FieldIssue::Required));
+            String::new()
+        }
+        None => existing.map(|record| record.q.clone()).unwrap_or_default(),
+        Some(q) => {
+            if q.chars().count() > Q_MAX_LEN {
+                errors.push(FieldError::new("q", FieldIssue::TooLong(Q_MAX_LEN)));
+            }
+            q.to_string()
+        }
+    };
+    let ad = match non_empty(&input.ad) {
+        Some(ad) => {
+            if ad.chars().count() > AD_MAX_LEN {
+                errors.push(FieldError::new("ad", FieldIssue::TooLong(AD_MAX_LEN)));
+            }
+            ad.to_string()
+        }
+        // Ad verilmezse sorgunun kendisi
+        None => existing.map(|record| record.ad.clone()).unwrap_or_else(|| q.chars().take(AD_MAX_LEN).collect()),
+    };
+    let corpus = match non_empty(&input.corpus) {
+        Some(corpus) => {
+            if state.corpora.get(corpus).is_none() {
+                errors.push(FieldError::new("corpus", FieldIssue::NotFound));
+            }
+            corpus.to_string()
+        }
+        None => existing
+            .map(|record| record.corpus.clone())
+            .unwrap_or_else(|| state.corpora.mevzuat().key.clone()),
+    };
+    validate_kurum_id(state, &input.kurum_id, false, Resource::SavedSearchWrite, &mut errors).await?;
+    let kurum_id = optional_filter(&input.kurum_id, existing.map(|record| &record.kurum_id));
+    let belge_turu = optional_filter(&input.belge_turu, existing.map(|record| &record.belge_turu));
+
+    let kanal = match non_empty(&input.kanal) {
+        Some(kanal) if CHANNELS.contains(&kanal) => kanal.to_string(),
+        Some(_) => {
+            errors.push(FieldError::new("kanal", FieldIssue::NotAllowed));
+            String::new()
+        }
+        None => existing
+            .map(|record| record.kanal.clone())
+            .unwrap_or_else(|| CHANNEL_INBOX.to_string()),
+    };
+    // Kanal değiştiyse eski hedef geçersizdir
+    let kept_hedef = existing.filter(|record| record.kanal == kanal).and_then(|record| record.hedef.clone());
+    let hedef = non_empty(&input.hedef).map(str::to_string).or(kept_hedef);
+    match (kanal.as_str(), hedef.as_deref()) {
+        (CHANNEL_WEBHOOK | CHANNEL_EMAIL, None) => errors.push(FieldError::new("hedef", FieldIssue::Required)),
+        (CHANNEL_WEBHOOK | CHANNEL_EMAIL, Some(hedef)) if hedef.len() > HEDEF_MAX_LEN => {
+            errors.push(FieldError::new("hedef", FieldIssue::TooLong(HEDEF_MAX_LEN)))
+        }
+        (CHANNEL_WEBHOOK, Some(hedef)) if !is_http_url(hedef) => {
+            errors.push(FieldError::new("hedef", FieldIssue::InvalidFormat))
+        }
+        (CHANNEL_WEBHOOK, Some(hedef)) if target::check_url(hedef, &state.saved_searches.allowed_hosts).is_err() => {
+            errors.push(FieldError::new("hedef", FieldIssue::NotAllowed))
+        }
+        (CHANNEL_EMAIL, Some(hedef)) if !is_email(hedef) => {
+            errors.push(FieldError::new("hedef", FieldIssue::InvalidFormat))
+        }
+        (CHANNEL_INBOX, Some(_)) if input.hedef.is_some() => {
+            errors.push(FieldError::new("hedef", FieldIssue::NotAllowed))
+        }
+        _ => {}
+    }
+
+    if existing.is_none() {
+        let count = state
+            .repos
+            .saved_searches
+            .list(Some(owner))
+            .await
+            .map_err(ApiError::storage(Resource::SavedSearches))?
+            .len();
+        // Sınır doluysa yeni arama kaydedilemez
+        if count >= MAX_SAVED_SEARCHES {
+            errors.push(FieldError::new("q", FieldIssue::NotAllowed));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    let now = format_timestamp(Utc::now());
+    Ok(SavedSearchWrite {
+        owner: owner.to_string(),
+        // Sorgu değiştiyse imleç sıfırlanır; yeni sorgu için mevcut belgeler bildirilmez
+        reset_cursor: existing.is_none_or(|record| {
+            record.q != q || record.corpus != corpus || record.kurum_id != kurum_id
+        }),
+        ad,
+        q,
+        corpus,
+        kurum_id,
+        belge_turu,
+        secret: match existing.and_then(|record| record.secret.clone()) {
+            Some(secret) => Some(secret),
+            None => (kanal == CHANNEL_WEBHOOK).then(webhooks::generate_secret),
+        },
+        hedef: (kanal != CHANNEL_INBOX).then_some(hedef).flatten(),
+        kanal,
+        aktif: input.aktif.or(existing.map(|record| record.aktif)).unwrap_or(true),
+        created_at: existing.is_none().then(|| now.clone()),
+        updated_at: now,
+    })
+}
+
+// Sahibinin kayıtlı araması; başkasınınsa bulunamadı
+async fn find_owned(state: &AppState, owner: &str, id: &str) -> Result<SavedSearchRecord, ApiError> {
+    state
+        .repos
+        .saved_searches
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::storage(Resource::SavedSearches))?
+        .filter(|record| record.owner == owner)
+        .ok_or(ApiError::SavedSearchNotFound)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/search/saved",
+    tag = "arama",
+    security(("bearer" = []), ("api_key" = [])),
+    request_body = SavedSearchInput,
+    responses(
+        (status = 201, description = "Kayıtlı arama oluşturuldu; webhook kanalında imzalama anahtarı (secret) yalnızca bu yanıtta döner", body = SavedSearchDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "search yetkisi yok", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn create_saved_search(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    body: web::Json<SavedSearchInput>,
+) -> Result<HttpResponse, ApiError> {
+    let owner = owner(&req)?;
+    let search = prepare(&state, &owner, body.into_inner(), None).await?;
+    let id = state
+        .repos
+        .saved_searches
+        .insert(&search)
+        .await
+        .map_err(ApiError::storage(Resource::SavedSearchWrite))?;
+    log::info!("Kayıtlı arama oluşturuldu: {} ({}) - anahtar: {}", search.q, id, owner);
+
+    let record = find_owned(&state, &owner, &id).await?;
+    let secret = record.secret.clone();
+    Ok(HttpResponse::Created().json(SavedSearchDetailResponse {
+        success: true,
+        data: SavedSearchResponse {
+            secret,
+            ..record.into()
+        },
+        message: "Kayıtlı arama oluşturuldu".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/search/saved",
+    tag = "arama",
+    security(("bearer" = []), ("api_key" = [])),
+    responses(
+        (status = 200, description = "Anahtarın kayıtlı aramaları (oluşturulma sırasıyla)", body = SavedSearchListResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "search yetkisi yok", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn list_saved_searches(req: HttpRequest, state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let owner = owner(&req)?;
+    let records = state
+        .repos
+        .saved_searches
+        .list(Some(&owner))
+        .await
+        .map_err(ApiError::storage(Resource::SavedSearches))?;
+    let data: Vec<SavedSearchResponse> = records.into_iter().map(SavedSearchResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(SavedSearchListResponse {
+        success: true,
+        count: data.len(),
+        message: format!("{} kayıtlı arama bulundu", data.len()),
+        data,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/search/saved/{id}",
+    tag = "arama",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Kayıtlı aramanın _id değeri")),
+    responses(
+        (status = 200, description = "Kayıtlı arama", body = SavedSearchDetailResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "search yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıtlı arama bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_saved_search(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let owner = owner(&req)?;
+    let record = find_owned(&state, &owner, &path).await?;
+    Ok(HttpResponse::Ok().json(SavedSearchDetailResponse {
+        success: true,
+        data: record.into(),
+        message: "Kayıtlı arama bulundu".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/search/saved/{id}",
+    tag = "arama",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Kayıtlı aramanın _id değeri")),
+    request_body = SavedSearchInput,
+    responses(
+        (status = 200, description = "Kayıtlı arama güncellendi", body = SavedSearchDetailResponse),
+        (status = 400, description = "Geçersiz istek gövdesi", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "search yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıtlı arama bulunamadı", body = ErrorEnvelope),
+        (status = 422, description = "Doğrulama hatası", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn update_saved_search(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<SavedSearchInput>,
+) -> Result<HttpResponse, ApiError> {
+    let owner = owner(&req)?;
+    let existing = find_owned(&state, &owner, &path).await?;
+    let search = prepare(&state, &owner, body.into_inner(), Some(&existing)).await?;
+    let updated = state
+        .repos
+        .saved_searches
+        .update(&existing.id, &search)
+        .await
+        .map_err(ApiError::storage(Resource::SavedSearchWrite))?;
+    if !updated {
+        return Err(ApiError::SavedSearchNotFound);
+    }
+    log::info!("Kayıtlı arama güncellendi: {} ({}) - anahtar: {}", search.q, existing.id, owner);
+
+    let record = find_owned(&state, &owner, &existing.id).await?;
+    // Kanal webhook'a çevrildiyse yeni üretilen anahtar bir kez döner
+    let secret = record.secret.clone().filter(|_| existing.secret.is_none());
+    Ok(HttpResponse::Ok().json(SavedSearchDetailResponse {
+        success: true,
+        data: SavedSearchResponse {
+            secret,
+            ..record.into()
+        },
+        message: "Kayıtlı arama güncellendi".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/search/saved/{id}",
+    tag = "arama",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Kayıtlı aramanın _id değeri")),
+    responses(
+        (status = 200, description = "Kayıtlı arama ve bildirimleri silindi", body = DeleteRecordResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "search yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Kayıtlı arama bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn delete_saved_search(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let owner = owner(&req)?;
+    let existing = find_owned(&state, &owner, &path).await?;
+    let deleted = state
+        .repos
+        .saved_searches
+        .delete(&existing.id)
+        .await
+        .map_err(ApiError::storage(Resource::SavedSearchWrite))?;
+    if !deleted {
+        return Err(ApiError::SavedSearchNotFound);
+    }
+    log::info!("Kayıtlı arama silindi: {} ({}) - anahtar: {}", existing.q, existing.id, owner);
+
+    Ok(HttpResponse::Ok().json(DeleteRecordResponse {
+        success: true,
+        data: DeletedRecord { id: existing.id },
+        message: "Kayıtlı arama silindi".to_string(),
+    }))
+}
+
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AlertQuery {
+    // true ise yalnızca okunmamış bildirimler
+    pub okunmamis: Option<bool>,
+    // Yalnızca bu kayıtlı aramanın bildirimleri
+    pub saved_search_id: Option<String>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/search/alerts",
+    tag = "arama",
+    security(("bearer" = []), ("api_key" = [])),
+    params(AlertQuery),
+    responses(
+        (status = 200, description = "Gelen kutusu: kayıtlı aramaların yeni eşleşmeleri (en yeni önce)", body = AlertListResponse),
+        (status = 400, description = "Geçersiz parametre", body = ErrorEnvelope),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "search yetkisi yok", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn list_alerts(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<AlertQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let owner = owner(&req)?;
+    let filter = AlertFilter {
+        owner: Some(owner),
+        saved_search_id: non_empty(&query.saved_search_id).map(str::to_string),
+        unread: query.okunmamis.unwrap_or(false),
+    };
+    let page = Page {
+        offset: query.offset.unwrap_or(0),
+        limit: query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+    };
+
+    let records = state
+        .repos
+        .saved_searches
+        .alerts(&filter, page)
+        .await
+        .map_err(ApiError::storage(Resource::SearchAlerts))?;
+    let count = state.repos.saved_searches.count_alerts(&filter).await.ok();
+    let data: Vec<AlertResponse> = records.into_iter().map(AlertResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(AlertListResponse {
+        success: true,
+        message: format!("{} bildirim bulundu", data.len()),
+        data,
+        count,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/search/alerts/{id}/read",
+    tag = "arama",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = String, Path, description = "Bildirimin _id değeri")),
+    responses(
+        (status = 200, description = "Bildirim okundu olarak işaretlendi", body = AlertDetailResponse),
+        (status = 401, description = "API anahtarı gerekli", body = ErrorEnvelope),
+        (status = 403, description = "search yetkisi yok", body = ErrorEnvelope),
+        (status = 404, description = "Bildirim bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn mark_alert_read(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let owner = owner(&req)?;
+    let marked = state
+        .repos
+        .saved_searches
+        .mark_alert_read(&path, &owner, &format_timestamp(Utc::now()))
+        .await
+        .map_err(ApiError::storage(Resource::SearchAlerts))?;
+    if !marked {
+        return Err(ApiError::AlertNotFound);
+    }
+    let record = state
+        .repos
+        .saved_searches
+        .find_alert(&path)
+        .await
+        .map_err(ApiError::storage(Resource::SearchAlerts))?
+        .ok_or(ApiError::AlertNotFound)?;
+
+    Ok(HttpResponse::Ok().json(AlertDetailResponse {
+        success: true,
+        data: record.into(),
+        message: "Bildirim okundu olarak işaretlendi".to_string(),
+    }))
+}
+
+pub fn saved_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::post().to(create_saved_search))
+        .route("", web::get().to(list_saved_searches))
+        .route("/{id}", web::get().to(get_saved_search))
+        .route("/{id}", web::patch().to(update_saved_search))
+        .route("/{id}", web::delete().to(delete_saved_search));
+}
+
+pub fn alert_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(list_alerts))
+        .route("/{id}/read", web::post().to(mark_alert_read));
+}
@@ -1,10 +1,15 @@
-use actix_web::{web, HttpResponse, http::{StatusCode, header::HeaderValue}};
-use mongodb::{Collection, bson::{doc, Document as MongoDocument}};
+use actix_web::{web, HttpResponse, http::header::HeaderValue};
 use crate::config::AppState;
-use crate::models::search::{SearchResponse, SearchResult, };
+use crate::models::error::ErrorEnvelope;
+use crate::error::{ApiError, Resource};
+use crate::corpus::Corpus;
+use crate::repository::{DocumentRecord, Page, Repositories, RepositoryError, SearchFilter};
+use crate::models::search::{SearchResponse, SearchResult};
 use regex::Regex;
+use utoipa::IntoParams;
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SearchQuery {
     pub q: String,
     pub limit: Option<u64>,
@@ -13,386 +18,302 @@ pub struct SearchQuery {
 }
 
 
-pub async fn search(
-    state: web::Data<AppState>,
-    query: web::Query<SearchQuery>,
-) -> HttpResponse {
-    // q parametresi boş olamaz
-    if query.q.trim().is_empty() {
-        return HttpResponse::build(StatusCode::BAD_REQUEST).json(SearchResponse {
-            success: false,
-            data: vec![],
-            count: 0,
-            message: "Arama sorgusu boş olamaz".to_string(),
-        });
-    }
-
-    let limit = query.limit.unwrap_or(10000);
-    let offset = query.offset.unwrap_or(0);
-    let search_query = query.q.trim();
-
-    let metadata_collection: Collection<MongoDocument> = state.db.collection("metadata");
+// Sorgudan üretilen repository filtresi ve puanlama için regex'ler
+struct PreparedSearch {
+    filter: SearchFilter,
+    regex_obj: Regex,
+    mongo_patterns: Vec<String>,
+}
 
+fn prepare_search(corpus: &Corpus, search_query: &str, kurum_id: Option<&str>) -> Result<PreparedSearch, ApiError> {
     // Gelişmiş regex pattern oluştur (yakın eşleşmeler için)
     let regex_pattern = build_advanced_regex_pattern(search_query);
-    let regex_obj = match Regex::new(&regex_pattern) {
-        Ok(re) => re,
-        Err(_) => {
-            return HttpResponse::InternalServerError().json(SearchResponse {
-                success: false,
-                data: vec![],
-                count: 0,
-                message: "Geçersiz arama sorgusu".to_string(),
-            });
-        }
-    };
+    let regex_obj = Regex::new(&regex_pattern).map_err(|_| ApiError::InvalidSearchQuery)?;
 
-    // 1. Metadata'da arama yap (sadece pdf_adi ve icerik_text)
+    // 1. Metadata'da arama yap (sadece başlık ve düz metin alanı)
     // Gelişmiş regex: her kelime için ayrı pattern (yakın eşleşme)
     let mongo_patterns = build_mongodb_regex_patterns(search_query);
-    
-    let mut match_filter = if mongo_patterns.is_empty() {
-        // Tek kelime veya boş ise basit pattern
-        doc! {
-            "$or": [
-                { "pdf_adi": doc! { "$regex": &regex_pattern, "$options": "i" } },
-                { "icerik_text": doc! { "$regex": &regex_pattern, "$options": "i" } }
-            ]
-        }
-    } else if mongo_patterns.len() == 1 {
-        // Tek kelime için basit pattern (daha hızlı)
-        let pattern = &mongo_patterns[0];
-        doc! {
-            "$or": [
-                { "pdf_adi": doc! { "$regex": pattern, "$options": "i" } },
-                { "icerik_text": doc! { "$regex": pattern, "$options": "i" } }
-            ]
-        }
-    } else {
-        // Çoklu kelime için: her kelime için ayrı $and koşulu
-        let mut or_conditions = Vec::new();
-        
-        // pdf_adi için: tüm kelimeler geçmeli
-        let mut pdf_adi_conditions = Vec::new();
-        for pattern in &mongo_patterns {
-            pdf_adi_conditions.push(doc! {
-                "pdf_adi": doc! { "$regex": pattern, "$options": "i" }
-            });
-        }
-        if !pdf_adi_conditions.is_empty() {
-            or_conditions.push(doc! { "$and": pdf_adi_conditions });
-        }
-        
-        // icerik_text için: tüm kelimeler geçmeli
-        let mut icerik_text_conditions = Vec::new();
-        for pattern in &mongo_patterns {
-            icerik_text_conditions.push(doc! {
-                "icerik_text": doc! { "$regex": pattern, "$options": "i" }
-            });
-        }
-        if !icerik_text_conditions.is_empty() {
-            or_conditions.push(doc! { "$and": icerik_text_conditions });
-        }
-        
-        if or_conditions.is_empty() {
-            doc! {
-                "$or": [
-                    { "pdf_adi": doc! { "$regex": &regex_pattern, "$options": "i" } },
-                    { "icerik_text": doc! { "$regex": &regex_pattern, "$options": "i" } }
-                ]
-            }
+
+    let filter = SearchFilter {
+        // Tek kelime veya boş ise basit pattern, çoklu kelimede tüm kelimeler geçmeli
+        patterns: if mongo_patterns.is_empty() {
+            vec![regex_pattern]
         } else {
-            doc! { "$or": or_conditions }
-        }
+            mongo_patterns.clone()
+        },
+        // Tek kuruma ait corpus'larda kurum filtresi sabittir
+        kurum_id: corpus.kurum_id.as_deref().or(kurum_id).map(|s| s.to_string()),
     };
 
-    if let Some(kurum_id) = &query.kurum_id {
-        match_filter.insert("kurum_id", kurum_id);
-    }
+    Ok(PreparedSearch {
+        filter,
+        regex_obj,
+        mongo_patterns,
+    })
+}
+
+// Corpus'tan bağımsız arama; sonuçlar relevance'a göre sıralı, toplam eşleşme sayısıyla döner
+pub async fn search_corpus(
+    state: &AppState,
+    corpus: &Corpus,
+    search_query: &str,
+    kurum_id: Option<&str>,
+    limit: u64,
+    offset: u64,
+) -> Result<(Vec<SearchResult>, u64), ApiError> {
+    let prepared = prepare_search(corpus, search_query, kurum_id)?;
 
     // Toplam sayıyı hesapla
-    let total_count = match metadata_collection.count_documents(match_filter.clone(), None).await {
-        Ok(count) => count,
-        Err(e) => {
-            log::error!("MongoDB count hatası: {}", e);
-            return HttpResponse::InternalServerError().json(SearchResponse {
-                success: false,
-                data: vec![],
-                count: 0,
-                message: "Arama yapılamadı".to_string(),
-            });
+    let total_count = state
+        .repos
+        .documents
+        .count_search(corpus, &prepared.filter)
+        .await
+        .map_err(search_storage_error)?;
+
+    let records = state
+        .repos
+        .documents
+        .search(corpus, &prepared.filter, Page { offset, limit })
+        .await
+        .map_err(search_storage_error)?;
+
+    // Her metadata kaydı için detayları hesapla
+    let mut results: Vec<SearchResult> = records
+        .iter()
+        .map(|record| score_search_hit(record, corpus, &prepared.regex_obj, &prepared.mongo_patterns))
+        .collect();
+
+    // Relevance percentage'a göre sırala (yüksekten düşüğe)
+    results.sort_by(|a, b| {
+        b.relevance_percentage.cmp(&a.relevance_percentage)
+    });
+
+    Ok((results, total_count))
+}
+
+// Aynı arama, relevance yerine oluşturulma tarihine göre (en yeni önce) sıralı; kayıtlı
+// aramalar yeni eşleşmeleri bulmak için sayfa sayfa okur
+pub(crate) async fn search_newest(
+    repos: &Repositories,
+    corpus: &Corpus,
+    search_query: &str,
+    kurum_id: Option<&str>,
+    page: Page,
+) -> Result<Vec<(DocumentRecord, SearchResult)>, ApiError> {
+    let prepared = prepare_search(corpus, search_query, kurum_id)?;
+    let records = repos
+        .documents
+        .search(corpus, &prepared.filter, page)
+        .await
+        .map_err(search_storage_error)?;
+
+    Ok(records
+        .into_iter()
+        .map(|record| {
+            let result = score_search_hit(&record, corpus, &prepared.regex_obj, &prepared.mongo_patterns);
+            (record, result)
+        })
+        .collect())
+}
+
+fn search_storage_error(e: RepositoryError) -> ApiError {
+    match e {
+        RepositoryError::InvalidQuery(_) => ApiError::InvalidSearchQuery,
+        e => ApiError::Storage(Resource::Search, e),
+    }
+}
+
+// Tek bir arama sonucunun match type, match count ve relevance değerlerini hesapla
+fn score_search_hit(
+    record: &DocumentRecord,
+    corpus: &Corpus,
+    regex_obj: &Regex,
+    mongo_patterns: &[String],
+) -> SearchResult {
+    let pdf_adi = record.pdf_adi.clone();
+
+    // Kurum adı $lookup ile gelen kurum bilgisinden
+    let kurum_adi = record.kurum_adi();
+
+    let mut match_types: Vec<String> = Vec::new();
+
+    // Title match (pdf_adi) - tüm kelimeler geçmeli
+    let (title_count, title_matches) = count_matches(&pdf_adi.to_lowercase(), regex_obj, mongo_patterns);
+    if title_matches {
+        match_types.push("title".to_string());
+    }
+
+    // Content match (önizleme alanı) - tüm kelimeler geçmeli
+    let mut content_count = 0u64;
+    let mut content_preview = String::new();
+    if let Some(preview_source) = record.text_field(&corpus.search.preview_field) {
+        let preview_lower = preview_source.to_lowercase();
+        let (count, content_matches) = count_matches(&preview_lower, regex_obj, mongo_patterns);
+        content_count = count;
+        if content_matches {
+            match_types.push("content".to_string());
         }
+
+        content_preview = if corpus.search.full_preview {
+            preview_source.to_string()
+        } else {
+            // Eşleşme çevresini göster, eşleşme yoksa baştan kes
+            let match_pos = if !content_matches {
+                None
+            } else if mongo_patterns.is_empty() {
+                regex_obj.find(&preview_lower).map(|mat| (mat.start(), mat.end()))
+            } else {
+                let first_word = pattern_word(&mongo_patterns[0]).to_lowercase();
+                preview_lower.find(&first_word).map(|pos| (pos, pos + first_word.len()))
+            };
+            build_content_preview(preview_source, match_pos)
+        };
+    }
+
+    // Ağırlıklı puanlama ile relevance percentage hesapla
+    // Title ağırlığı: 0.7, Content ağırlığı: 0.3
+    const TITLE_WEIGHT: f64 = 0.7;
+    const CONTENT_WEIGHT: f64 = 0.3;
+    
+    // Her alanın kendi base score'unu hesapla
+    let title_base_score = if title_count > 0 {
+        title_count as f64 / (title_count as f64 + 1.0)
+    } else {
+        0.0
+    };
+    
+    let content_base_score = if content_count > 0 {
+        content_count as f64 / (content_count as f64 + 1.0)
+    } else {
+        0.0
     };
+    
+    // Ağırlıklı ortalama
+    let relevance_score = (title_base_score * TITLE_WEIGHT) + (content_base_score * CONTENT_WEIGHT);
+    let relevance_percentage = (relevance_score * 100.0) as u64;
+    
+    // Toplam match count (hem title hem content için)
+    let match_count = title_count + content_count;
 
-    // Metadata sonuçlarını al - $lookup ile kurum bilgilerini birleştir (N+1 query problemini çöz)
-    let pipeline = vec![
-        doc! { "$match": match_filter.clone() },
-        doc! {
-            "$addFields": {
-                "kurum_id_object": {
-                    "$cond": {
-                        "if": { "$eq": [{ "$type": "$kurum_id" }, "string"] },
-                        "then": { "$toObjectId": "$kurum_id" },
-                        "else": "$kurum_id"
-                    }
-                }
-            }
-        },
-        doc! {
-            "$lookup": {
-                "from": "kurumlar",
-                "localField": "kurum_id_object",
-                "foreignField": "_id",
-                "as": "kurum_bilgisi"
-            }
-        },
-        doc! {
-            "$unwind": {
-                "path": "$kurum_bilgisi",
-                "preserveNullAndEmptyArrays": true
-            }
-        },
-        doc! { "$sort": { "olusturulma_tarihi": -1 } },
-        doc! { "$skip": offset as i64 },
-        doc! { "$limit": limit as i64 },
-    ];
-
-    let mut cursor = match metadata_collection.aggregate(pipeline, None).await {
-        Ok(cursor) => cursor,
-        Err(e) => {
-            log::error!("MongoDB aggregation hatası: {}", e);
-            return HttpResponse::InternalServerError().json(SearchResponse {
-                success: false,
-                data: vec![],
-                count: 0,
-                message: "Arama yapılamadı".to_string(),
-            });
-        }
+    let optional_field = |value: &str| {
+        Some(value.to_string()).filter(|s| !s.is_empty())
     };
 
-    let mut results: Vec<SearchResult> = Vec::new();
+    SearchResult {
+        id: record.id.clone(),
+        pdf_adi,
+        kurum_adi,
+        match_type: match_types.join(","),
+        content_preview,
+        relevance_percentage,
+        match_count,
+        url_slug: record.url_slug.clone(),
+        belge_yayin_tarihi: optional_field(&record.belge_yayin_tarihi),
+        etiketler: optional_field(&record.etiketler),
+        aciklama: optional_field(&record.aciklama),
+        belge_turu: optional_field(&record.belge_turu),
+        belge_durumu: optional_field(&record.belge_durumu),
+    }
+}
 
-    // Her metadata kaydı için detayları hesapla
-    while let Ok(true) = cursor.advance().await {
-        if let Ok(metadata_doc) = cursor.deserialize_current() {
-            let id = metadata_doc
-                .get_object_id("_id")
-                .map(|oid| oid.to_hex())
-                .unwrap_or_default();
-
-            let pdf_adi = metadata_doc
-                .get_str("pdf_adi")
-                .unwrap_or("")
-                .to_string();
-
-            let url_slug = metadata_doc
-                .get_str("url_slug")
-                .unwrap_or("")
-                .to_string();
-
-            // Kurum adını $lookup ile gelen kurum_bilgisi'nden al
-            let kurum_adi = metadata_doc
-                .get_document("kurum_bilgisi")
-                .ok()
-                .and_then(|k| {
-                    k.get_str("kurum_adi")
-                        .or_else(|_| k.get_str("kurumAdi"))
-                        .ok()
-                })
-                .unwrap_or("")
-                .to_string();
-
-            // Match type ve match count hesapla
-            // MongoDB pattern'lerini kullanarak her kelime için ayrı kontrol
-            let mongo_patterns = build_mongodb_regex_patterns(search_query);
-            let mut match_types: Vec<String> = Vec::new();
-            
-            // Ağırlıklı puanlama için ayrı ayrı sayılar
-            let mut title_count = 0u64;
-            let mut content_count = 0u64;
-
-            // Title match (pdf_adi) - tüm kelimeler geçmeli
-            let pdf_adi_lower = pdf_adi.to_lowercase();
-            let mut title_matches = true;
-            
-            if mongo_patterns.is_empty() {
-                // Tek kelime veya basit pattern
-                if regex_obj.is_match(&pdf_adi_lower) {
-                    match_types.push("title".to_string());
-                    title_count = regex_obj.find_iter(&pdf_adi_lower).count() as u64;
-                }
-            } else {
-                // Çoklu kelime: her kelime için ayrı kontrol
-                // MongoDB pattern formatı: ".*kelime.*" -> sadece "kelime" kısmını al
-                for pattern in &mongo_patterns {
-                    // Pattern'den kelimeyi çıkar: ".*kısa.*" -> "kısa"
-                    let word = pattern.trim_start_matches(".*").trim_end_matches(".*");
-                    if pdf_adi_lower.contains(&word.to_lowercase()) {
-                        title_count += pdf_adi_lower.matches(&word.to_lowercase()).count() as u64;
-                    } else {
-                        title_matches = false;
-                        break;
-                    }
-                }
-                if title_matches && title_count > 0 {
-                    match_types.push("title".to_string());
-                }
-            }
+// Metindeki eşleşme sayısı ve tüm kelimelerin geçip geçmediği.
+// Çoklu kelimede ilk eksik kelimeye kadar bulunan eşleşmeler puanlamaya yine de katılır.
+fn count_matches(text_lower: &str, regex_obj: &Regex, mongo_patterns: &[String]) -> (u64, bool) {
+    if mongo_patterns.is_empty() {
+        // Tek kelime veya basit pattern
+        let count = regex_obj.find_iter(text_lower).count() as u64;
+        return (count, count > 0);
+    }
 
-            // Content match (aciklama) - tüm kelimeler geçmeli
-            let mut content_preview = String::new();
-            if let Ok(aciklama) = metadata_doc.get_str("aciklama") {
-                let aciklama_lower = aciklama.to_lowercase();
-                let mut content_matches = true;
-
-                if mongo_patterns.is_empty() {
-                    // Tek kelime veya basit pattern
-                    if regex_obj.is_match(&aciklama_lower) {
-                        match_types.push("content".to_string());
-                        content_count = regex_obj.find_iter(&aciklama_lower).count() as u64;
-
-                        // Content preview oluştur
-                        if let Some(mat) = regex_obj.find(&aciklama_lower) {
-                            let start = mat.start().saturating_sub(100);
-                            let end = (mat.end() + 100).min(aciklama.len());
-                            content_preview = format!("...{}...", &aciklama[start..end]);
-                        } else {
-                            content_preview = aciklama.chars().take(200).collect::<String>();
-                            if aciklama.len() > 200 {
-                                content_preview.push_str("...");
-                            }
-                        }
-                    } else {
-                        // Eşleşme yoksa da preview göster
-                        content_preview = aciklama.chars().take(200).collect::<String>();
-                        if aciklama.len() > 200 {
-                            content_preview.push_str("...");
-                        }
-                    }
-                } else {
-                    // Çoklu kelime: her kelime için ayrı kontrol
-                    // MongoDB pattern formatı: ".*kelime.*" -> sadece "kelime" kısmını al
-                    for pattern in &mongo_patterns {
-                        // Pattern'den kelimeyi çıkar: ".*kısa.*" -> "kısa"
-                        let word = pattern.trim_start_matches(".*").trim_end_matches(".*");
-                        if aciklama_lower.contains(&word.to_lowercase()) {
-                            content_count += aciklama_lower.matches(&word.to_lowercase()).count() as u64;
-                        } else {
-                            content_matches = false;
-                            break;
-                        }
-                    }
-                    if content_matches && content_count > 0 {
-                        match_types.push("content".to_string());
-
-                        // Content preview oluştur (ilk kelimeyi bul)
-                        let first_word = mongo_patterns[0].trim_start_matches(".*").trim_end_matches(".*");
-                        if let Some(pos) = aciklama_lower.find(&first_word.to_lowercase()) {
-                            let start = pos.saturating_sub(100);
-                            let end = (pos + first_word.len() + 100).min(aciklama.len());
-                            content_preview = format!("...{}...", &aciklama[start..end]);
-                        } else {
-                            content_preview = aciklama.chars().take(200).collect::<String>();
-                            if aciklama.len() > 200 {
-                                content_preview.push_str("...");
-                            }
-                        }
-                    } else {
-                        // Eşleşme yoksa da preview göster
-                        content_preview = aciklama.chars().take(200).collect::<String>();
-                        if aciklama.len() > 200 {
-                            content_preview.push_str("...");
-                        }
-                    }
-                }
-            }
+    // Çoklu kelime: her kelime için ayrı kontrol
+    let mut count = 0u64;
+    for pattern in mongo_patterns {
+        let word = pattern_word(pattern).to_lowercase();
+        let occurrences = text_lower.matches(&word).count() as u64;
+        if occurrences == 0 {
+            return (count, false);
+        }
+        count += occurrences;
+    }
+    (count, count > 0)
+}
 
-            // Ağırlıklı puanlama ile relevance percentage hesapla
-            // Title ağırlığı: 0.7, Content ağırlığı: 0.3
-            const TITLE_WEIGHT: f64 = 0.7;
-            const CONTENT_WEIGHT: f64 = 0.3;
-            
-            // Her alanın kendi base score'unu hesapla
-            let title_base_score = if title_count > 0 {
-                title_count as f64 / (title_count as f64 + 1.0)
-            } else {
-                0.0
-            };
-            
-            let content_base_score = if content_count > 0 {
-                content_count as f64 / (content_count as f64 + 1.0)
-            } else {
-                0.0
-            };
-            
-            // Ağırlıklı ortalama
-            let relevance_score = (title_base_score * TITLE_WEIGHT) + (content_base_score * CONTENT_WEIGHT);
-            let relevance_percentage = (relevance_score * 100.0) as u64;
-            
-            // Toplam match count (hem title hem content için)
-            let match_count = title_count + content_count;
-
-            let belge_yayin_tarihi = metadata_doc
-                .get_str("belge_yayin_tarihi")
-                .ok()
-                .map(|s| s.to_string());
-
-            let etiketler = metadata_doc
-                .get_str("etiketler")
-                .ok()
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string());
-
-            let aciklama = metadata_doc
-                .get_str("aciklama")
-                .ok()
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string());
-
-            let belge_turu = metadata_doc
-                .get_str("belge_turu")
-                .ok()
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string());
-
-            let belge_durumu = metadata_doc
-                .get_str("belge_durumu")
-                .ok()
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string());
-
-            results.push(SearchResult {
-                id,
-                pdf_adi,
-                kurum_adi,
-                match_type: match_types.join(","),
-                content_preview,
-                relevance_percentage,
-                match_count,
-                url_slug,
-                belge_yayin_tarihi,
-                etiketler,
-                aciklama,
-                belge_turu,
-                belge_durumu,
-            });
+// MongoDB pattern formatı: ".*kelime.*" -> sadece "kelime" kısmını al
+fn pattern_word(pattern: &str) -> &str {
+    pattern.trim_start_matches(".*").trim_end_matches(".*")
+}
+
+// Eşleşmenin 100 karakter çevresini al; Türkçe karakterlerde byte sınırlarını koru
+fn build_content_preview(text: &str, match_pos: Option<(usize, usize)>) -> String {
+    match match_pos {
+        Some((match_start, match_end)) => {
+            let mut start = match_start.saturating_sub(100).min(text.len());
+            while !text.is_char_boundary(start) {
+                start -= 1;
+            }
+            let mut end = (match_end + 100).min(text.len());
+            while !text.is_char_boundary(end) {
+                end += 1;
+            }
+            format!("...{}...", &text[start..end])
+        }
+        None => {
+            let mut preview = text.chars().take(200).collect::<String>();
+            if text.len() > 200 {
+                preview.push_str("...");
+            }
+            preview
         }
     }
+}
 
-    // Relevance percentage'a göre sırala (yüksekten düşüğe)
-    results.sort_by(|a, b| {
-        b.relevance_percentage.cmp(&a.relevance_percentage)
-    });
+#[utoipa::path(
+    get,
+    path = "/api/v1/search",
+    tag = "arama",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = SearchResponse),
+        (status = 400, description = "Geçersiz parametre", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn search(
+    state: web::Data<AppState>,
+    query: web::Query<SearchQuery>,
+) -> Result<HttpResponse, ApiError> {
+    // q parametresi boş olamaz
+    if query.q.trim().is_empty() {
+        return Err(ApiError::EmptySearchQuery);
+    }
 
-    // Response oluştur
-    let mut response = HttpResponse::Ok().json(SearchResponse {
-        success: true,
-        data: results,
-        count: total_count,
-        message: "İşlem başarılı".to_string(),
-    });
+    let limit = query.limit.unwrap_or(10000);
+    let offset = query.offset.unwrap_or(0);
+
+    let (results, total_count) = search_corpus(
+        &state,
+        state.corpora.mevzuat(),
+        query.q.trim(),
+        query.kurum_id.as_deref(),
+        limit,
+        offset,
+    )
+    .await?;
+
+    Ok(with_total_count(
+        HttpResponse::Ok().json(SearchResponse {
+            success: true,
+            data: results,
+            count: total_count,
+            message: "İşlem başarılı".to_string(),
+        }),
+        total_count,
+    ))
+}
 
-    // X-Total-Count header'ını ekle
+// X-Total-Count header'ını ekle
+pub(crate) fn with_total_count(mut response: HttpResponse, total_count: u64) -> HttpResponse {
     if let Ok(header_value) = HeaderValue::from_str(&total_count.to_string()) {
         response.headers_mut().insert(
             actix_web::http::header::HeaderName::from_static("x-total-count"),
@@ -403,7 +324,7 @@ pub async fn search(
     response
 }
 
-fn build_advanced_regex_pattern(query: &str) -> String {
+pub(crate) fn build_advanced_regex_pattern(query: &str) -> String {
     // Türkçe karakterleri normalize et
     let normalized = normalize_turkish_chars(query);
     
@@ -461,7 +382,7 @@ fn build_turkish_char_pattern(word: &str) -> String {
 
 // MongoDB için gelişmiş regex pattern oluştur (her kelime için ayrı)
 // Sadece Türkçe karakterleri destekler (normalize edilmiş versiyon yok)
-fn build_mongodb_regex_patterns(query: &str) -> Vec<String> {
+pub(crate) fn build_mongodb_regex_patterns(query: &str) -> Vec<String> {
     let words: Vec<&str> = query.split_whitespace().collect();
     
     words
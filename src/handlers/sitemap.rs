@@ -1,13 +1,17 @@
+use std::collections::BTreeSet;
+use actix_web::web::Bytes;
 use actix_web::{web, HttpResponse};
-use mongodb::{Collection, bson::{doc, Bson, Document as MongoDocument}};
-use crate::config::AppState;
+use crate::config::{AppState, PublicUrls};
+use crate::models::error::ErrorEnvelope;
+use crate::error::{ApiError, Resource};
+use crate::corpus::Corpus;
+use crate::dates;
+use crate::repository::{DocumentRecord, Page, RepositoryError};
+use crate::sitemap::{self, ChildName, UrlEntry, UrlsetWriter, BATCH_SIZE, SECTION_INSTITUTIONS, SECTION_STATIC};
 use crate::models::sitemap::{
     SitemapInstitution, SitemapDocument,
     SitemapInstitutionsResponse, SitemapDocumentsResponse
 };
-use futures::stream::TryStreamExt;
-
-const DOMAIN: &str = "https://mevzuatgpt.org";
 
 // Helper function to create slug from institution name
 fn create_slug_from_name(name: &str) -> String {
@@ -21,312 +25,346 @@ fn create_slug_from_name(name: &str) -> String {
         .replace('ü', "u")
 }
 
-fn get_date_string(doc: &MongoDocument, field: &str) -> String {
-    match doc.get(field) {
-        Some(Bson::DateTime(dt)) => dt.try_to_rfc3339_string().unwrap_or_default(),
-        Some(Bson::String(value)) => value.to_string(),
-        Some(Bson::Int64(value)) => value.to_string(),
-        Some(Bson::Int32(value)) => value.to_string(),
-        _ => String::new(),
-    }
-}
-
 // GetSitemapInstitutions returns all institutions for sitemap
-pub async fn get_sitemap_institutions(state: web::Data<AppState>) -> HttpResponse {
-    let kurum_collection: Collection<MongoDocument> = state.db.collection("kurumlar");
-
+#[utoipa::path(
+    get,
+    path = "/api/v1/sitemap/institutions",
+    tag = "sitemap",
+    responses(
+        (status = 200, description = "İşlem başarılı", body = SitemapInstitutionsResponse),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_sitemap_institutions(state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
     // Tüm kurumları al
-    let kurum_docs = match kurum_collection.find(None, None).await {
-        Ok(cursor) => {
-            match cursor.try_collect::<Vec<_>>().await {
-                Ok(docs) => docs,
-                Err(e) => {
-                    log::error!("Kurum deserialize hatası: {}", e);
-                    return HttpResponse::InternalServerError().json(SitemapInstitutionsResponse {
-                        success: false,
-                        data: vec![],
-                        count: 0,
-                        message: "Kurumlar alınamadı".to_string(),
-                    });
-                }
-            }
-        }
-        Err(e) => {
-            log::error!("MongoDB sorgu hatası: {}", e);
-            return HttpResponse::InternalServerError().json(SitemapInstitutionsResponse {
-                success: false,
-                data: vec![],
-                count: 0,
-                message: "Kurumlar alınamadı".to_string(),
-            });
-        }
-    };
-
-    let mut institutions: Vec<SitemapInstitution> = Vec::new();
-
-    for doc_map in kurum_docs {
-        let kurum_adi = doc_map
-            .get_str("kurum_adi")
-            .or_else(|_| doc_map.get_str("kurumAdi"))
-            .unwrap_or("")
-            .to_string();
-
+    let kurumlar = state
+        .repos
+        .institutions
+        .list()
+        .await
+        .map_err(ApiError::storage(Resource::Institutions))?;
+
+    let institutions: Vec<SitemapInstitution> = kurumlar
+        .into_iter()
         // Boş veya "Bilinmeyen Kurum" olanları atla
-        if kurum_adi.is_empty() || kurum_adi == "Bilinmeyen Kurum" {
-            continue;
-        }
-
-        // Create slug from institution name
-        let slug = create_slug_from_name(&kurum_adi);
-
-        institutions.push(SitemapInstitution {
-            kurum_adi,
-            slug,
-        });
-    }
+        .filter(|kurum| !kurum.kurum_adi.is_empty() && kurum.kurum_adi != "Bilinmeyen Kurum")
+        .map(|kurum| SitemapInstitution {
+            // Create slug from institution name
+            slug: create_slug_from_name(&kurum.kurum_adi),
+            kurum_adi: kurum.kurum_adi,
+        })
+        .collect();
 
     let count = institutions.len();
-    HttpResponse::Ok().json(SitemapInstitutionsResponse {
+    Ok(HttpResponse::Ok().json(SitemapInstitutionsResponse {
         success: true,
         data: institutions,
         count,
         message: "Başarılı".to_string(),
-    })
+    }))
 }
 
-// GetSitemapAllDocuments returns all documents for sitemap
-pub async fn get_sitemap_all_documents(state: web::Data<AppState>) -> HttpResponse {
-    let metadata_collection: Collection<MongoDocument> = state.db.collection("metadata");
-
-    let filter = doc! {
-        "status": "aktif"
-    };
-
-    let find_options = mongodb::options::FindOptions::builder()
-        .sort(doc! { "olusturulma_tarihi": -1 })
-        .projection(doc! {
-            "url_slug": 1,
-            "olusturulma_tarihi": 1
-        })
-        .limit(5000)
-        .build();
-
-    let cursor = match metadata_collection.find(filter, find_options).await {
-        Ok(cursor) => cursor,
-        Err(e) => {
-            log::error!("Tüm belgeler sorgu hatası: {}", e);
-            return HttpResponse::InternalServerError().json(SitemapDocumentsResponse {
-                success: false,
-                data: vec![],
-                count: 0,
-                message: "Tüm belgeler alınamadı".to_string(),
-            });
-        }
-    };
-
-    let raw_documents: Vec<MongoDocument> = match cursor.try_collect().await {
-        Ok(docs) => docs,
-        Err(e) => {
-            log::error!("Belgeler deserialize hatası: {}", e);
-            return HttpResponse::InternalServerError().json(SitemapDocumentsResponse {
-                success: false,
-                data: vec![],
-                count: 0,
-                message: "Belgeler decode edilemedi".to_string(),
-            });
-        }
-    };
+// Corpus'taki aktif belgelerin sitemap kayıtları (en yeni önce, en fazla `limit` adet)
+pub async fn fetch_sitemap_documents(
+    state: &AppState,
+    corpus: &Corpus,
+    limit: i64,
+) -> Result<Vec<SitemapDocument>, RepositoryError> {
+    let records = state
+        .repos
+        .documents
+        .active(corpus, &corpus.fields.created_at, Some(limit))
+        .await?;
 
     // Convert to sitemap format
-    let mut documents: Vec<SitemapDocument> = Vec::new();
-    for doc_map in raw_documents {
-        let id = doc_map
-            .get_object_id("_id")
-            .map(|oid| oid.to_hex())
-            .unwrap_or_default();
-        let url_slug = doc_map.get_str("url_slug").unwrap_or("").to_string();
-        let olusturulma_tarihi = doc_map
-            .get_str("olusturulma_tarihi")
-            .unwrap_or("")
-            .to_string();
-
-        documents.push(SitemapDocument {
-            id,
-            url_slug,
-            olusturulma_tarihi,
-        });
-    }
+    Ok(records
+        .into_iter()
+        .filter(|record| !record.url_slug.is_empty())
+        .map(|record| SitemapDocument {
+            id: record.id,
+            url_slug: record.url_slug,
+            olusturulma_tarihi: record.olusturulma_tarihi,
+        })
+        .collect())
+}
 
+pub(crate) fn sitemap_documents_response(
+    result: Result<Vec<SitemapDocument>, RepositoryError>,
+    corpus: &Corpus,
+) -> Result<HttpResponse, ApiError> {
+    let documents = result.map_err(ApiError::storage(Resource::Sitemap))?;
     let count = documents.len();
-    HttpResponse::Ok().json(SitemapDocumentsResponse {
+
+    Ok(HttpResponse::Ok().json(SitemapDocumentsResponse {
         success: true,
         data: documents,
         count,
-        message: "Tüm sitemap belgeleri başarıyla alındı".to_string(),
-    })
+        message: format!("{} sitemap belgeleri başarıyla alındı", corpus.title),
+    }))
 }
 
-// GetSitemapXML returns XML sitemap for all documents
-pub async fn get_sitemap_xml(state: web::Data<AppState>) -> HttpResponse {
-    let metadata_collection: Collection<MongoDocument> = state.db.collection("metadata");
-
-    // Get all active documents
-    let filter = doc! { "status": "aktif" };
-    let find_options = mongodb::options::FindOptions::builder()
-        .sort(doc! { "belge_yayin_tarihi": -1 })
-        .projection(doc! {
-            "url_slug": 1,
-            "belge_yayin_tarihi": 1,
-            "olusturulma_tarihi": 1
-        })
-        .build();
-
-    let cursor = match metadata_collection.find(filter, find_options).await {
-        Ok(cursor) => cursor,
-        Err(e) => {
-            log::error!("XML sitemap sorgu hatası: {}", e);
-            return HttpResponse::InternalServerError()
-                .content_type("application/xml")
-                .body("<?xml version=\"1.0\" encoding=\"UTF-8\"?><urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\"></urlset>");
-        }
-    };
+// GetSitemapAllDocuments returns all documents for sitemap
+#[utoipa::path(
+    get,
+    path = "/api/v1/sitemap/all-documents",
+    tag = "sitemap",
+    responses(
+        (status = 200, description = "İşlem başarılı", body = SitemapDocumentsResponse),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_sitemap_all_documents(state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let corpus = state.corpora.mevzuat();
+    sitemap_documents_response(fetch_sitemap_documents(&state, corpus, 5000).await, corpus)
+}
 
-    let documents: Vec<MongoDocument> = match cursor.try_collect().await {
-        Ok(docs) => docs,
-        Err(e) => {
-            log::error!("XML sitemap deserialize hatası: {}", e);
-            return HttpResponse::InternalServerError()
-                .content_type("application/xml")
-                .body("<?xml version=\"1.0\" encoding=\"UTF-8\"?><urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\"></urlset>");
-        }
+// Statik sayfalar, kurum sayfaları ve corpus başına parçalı belge sitemap'lerini listeleyen index
+#[utoipa::path(
+    get,
+    path = "/sitemap.xml",
+    tag = "sitemap",
+    responses(
+        (status = 200, description = "Sitemap index", content_type = "application/xml", body = String),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_sitemap_xml(state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let config = &state.sitemap;
+    let child = |section: &str, page: u64| {
+        let name = ChildName {
+            section: section.to_string(),
+            page,
+            gzip: config.gzip,
+        };
+        state.urls.absolute(&format!("/sitemaps/{}", name.file_name()))
     };
 
-    // Generate XML sitemap
-    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
-    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
-
-    // Add static pages
-    xml.push_str(&format!(
-        "  <url><loc>{}/</loc><changefreq>daily</changefreq><priority>1.0</priority></url>\n",
-        DOMAIN
-    ));
-    xml.push_str(&format!(
-        "  <url><loc>{}/hakkinda</loc><changefreq>weekly</changefreq><priority>0.8</priority></url>\n",
-        DOMAIN
-    ));
-    xml.push_str(&format!(
-        "  <url><loc>{}/iletisim</loc><changefreq>weekly</changefreq><priority>0.8</priority></url>\n",
-        DOMAIN
-    ));
-
-    // Add document pages
-    for doc_map in documents {
-        if let Ok(url_slug) = doc_map.get_str("url_slug") {
-            if !url_slug.is_empty() {
-                let lastmod = doc_map
-                    .get_str("olusturulma_tarihi")
-                    .or_else(|_| doc_map.get_str("belge_yayin_tarihi"))
-                    .unwrap_or("");
+    let mut locations = vec![child(SECTION_STATIC, 1)];
+    let institutions = institution_entries(&state).await?;
+    locations.extend((1..=config.chunks(institutions.len() as u64)).map(|page| child(SECTION_INSTITUTIONS, page)));
+    for corpus in state.corpora.all() {
+        let count = state
+            .repos
+            .documents
+            .count_active(corpus)
+            .await
+            .map_err(ApiError::storage(Resource::Sitemap))?;
+        locations.extend((1..=config.chunks(count)).map(|page| child(&corpus.key, page)));
+    }
 
-                // Format date if needed (ensure ISO 8601 format)
-                let formatted_date = if lastmod.contains('T') || lastmod.contains('Z') {
-                    lastmod.to_string()
-                } else if !lastmod.is_empty() {
-                    format!("{}T00:00:00Z", lastmod)
-                } else {
-                    String::new()
-                };
+    Ok(HttpResponse::Ok()
+        .content_type("application/xml")
+        .body(sitemap::render_index(&locations)))
+}
 
-                if !formatted_date.is_empty() {
-                    xml.push_str(&format!(
-                        "  <url><loc>{}/belge/{}</loc><lastmod>{}</lastmod><changefreq>monthly</changefreq><priority>0.9</priority></url>\n",
-                        DOMAIN, url_slug, formatted_date
-                    ));
-                } else {
-                    xml.push_str(&format!(
-                        "  <url><loc>{}/belge/{}</loc><changefreq>monthly</changefreq><priority>0.9</priority></url>\n",
-                        DOMAIN, url_slug
-                    ));
-                }
+// Alt sitemap: static, institutions-{n} ya da {corpus}-{n}; .xml.gz uzantısı gzip'li gönderilir
+#[utoipa::path(
+    get,
+    path = "/sitemaps/{name}",
+    tag = "sitemap",
+    params(("name" = String, Path, description = "static.xml, institutions-1.xml, mevzuat-1.xml.gz, yargitay-2.xml ...")),
+    responses(
+        (status = 200, description = "Sitemap (urlset); .xml.gz uzantısında gzip'li", content_type = "application/xml", body = String),
+        (status = 404, description = "Bilinmeyen bölüm ya da sayfa", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_child_sitemap(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let name = ChildName::parse(&path).ok_or(ApiError::RouteNotFound)?;
+    let chunk_size = state.sitemap.chunk_size;
+    let offset = (name.page - 1) * chunk_size;
+
+    let entries = match name.section.as_str() {
+        SECTION_STATIC => static_entries(&state),
+        SECTION_INSTITUTIONS => {
+            let entries = institution_entries(&state).await?;
+            if offset >= entries.len() as u64 {
+                return Err(ApiError::RouteNotFound);
             }
+            entries.into_iter().skip(offset as usize).take(chunk_size as usize).collect()
         }
-    }
+        key => {
+            let corpus = state.corpora.get(key).cloned().ok_or(ApiError::RouteNotFound)?;
+            let count = state
+                .repos
+                .documents
+                .count_active(&corpus)
+                .await
+                .map_err(ApiError::storage(Resource::Sitemap))?;
+            if offset >= count {
+                return Err(ApiError::RouteNotFound);
+            }
+            let chunk = DocumentChunk {
+                state: state.clone(),
+                corpus,
+                offset,
+                end: count.min(offset + chunk_size),
+                writer: Some(UrlsetWriter::new(name.gzip)),
+                opened: false,
+            };
+            return Ok(HttpResponse::Ok()
+                .content_type(name.content_type())
+                .streaming(futures::stream::unfold(chunk, DocumentChunk::next)));
+        }
+    };
 
-    xml.push_str("</urlset>");
+    let mut writer = UrlsetWriter::new(name.gzip);
+    let mut body = writer.open().to_vec();
+    body.extend_from_slice(&writer.entries(&entries));
+    body.extend_from_slice(&writer.close());
+    Ok(HttpResponse::Ok().content_type(name.content_type()).body(body))
+}
 
+// Tarayıcılar için kurallar: API yolları taranmaz, sitemap index'i bildirilir
+#[utoipa::path(
+    get,
+    path = "/robots.txt",
+    tag = "sitemap",
+    responses(
+        (status = 200, description = "robots.txt", content_type = "text/plain", body = String),
+    )
+)]
+pub async fn get_robots_txt(state: web::Data<AppState>) -> HttpResponse {
     HttpResponse::Ok()
-        .content_type("application/xml")
-        .body(xml)
+        .content_type("text/plain; charset=utf-8")
+        .body(sitemap::render_robots(&state.urls.absolute("/sitemap.xml")))
 }
 
-// GetSitemapAllDocumentsYargitay returns all Yargitay documents for sitemap
-pub async fn get_sitemap_all_documents_yargitay(state: web::Data<AppState>) -> HttpResponse {
-    let metadata_collection: Collection<MongoDocument> = state.db.collection("yargitay");
+// IndexNow anahtar dosyası; arama motorları bildirimin site sahibinden geldiğini bununla doğrular
+#[utoipa::path(
+    get,
+    path = "/indexnow-key.txt",
+    tag = "sitemap",
+    responses(
+        (status = 200, description = "INDEXNOW_KEY değeri", content_type = "text/plain", body = String),
+        (status = 404, description = "IndexNow yapılandırılmamış", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_indexnow_key(state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let key = state.indexnow.key.as_ref().ok_or(ApiError::RouteNotFound)?;
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .body(key.clone()))
+}
 
-    let filter = doc! {
-        "status": "aktif"
-    };
+fn static_entries(state: &AppState) -> Vec<UrlEntry> {
+    [("/", "daily", "1.0"), ("/hakkinda", "weekly", "0.8"), ("/iletisim", "weekly", "0.8")]
+        .into_iter()
+        .map(|(path, changefreq, priority)| UrlEntry {
+            loc: state.urls.absolute(path),
+            lastmod: None,
+            changefreq,
+            priority,
+        })
+        .collect()
+}
 
-    let find_options = mongodb::options::FindOptions::builder()
-        .sort(doc! { "olusturulma_tarihi": -1 })
-        .projection(doc! {
-            "url_slug": 1,
-            "olusturulma_tarihi": 1
+// Kurum sayfaları slug sırasıyla; parçalar istekten isteğe aynı kalır
+async fn institution_entries(state: &AppState) -> Result<Vec<UrlEntry>, ApiError> {
+    let kurumlar = state
+        .repos
+        .institutions
+        .list()
+        .await
+        .map_err(ApiError::storage(Resource::Sitemap))?;
+    let slugs: BTreeSet<String> = kurumlar
+        .into_iter()
+        .filter(|kurum| !kurum.kurum_adi.is_empty() && kurum.kurum_adi != "Bilinmeyen Kurum")
+        .map(|kurum| create_slug_from_name(&kurum.kurum_adi))
+        .collect();
+    Ok(slugs
+        .into_iter()
+        .map(|slug| UrlEntry {
+            loc: state.urls.institution(&slug),
+            lastmod: None,
+            changefreq: "weekly",
+            priority: "0.7",
         })
-        .limit(5000)
-        .build();
+        .collect())
+}
 
-    let cursor = match metadata_collection.find(filter, find_options).await {
-        Ok(cursor) => cursor,
-        Err(e) => {
-            log::error!("Yargıtay belgeleri sorgu hatası: {}", e);
-            return HttpResponse::InternalServerError().json(SitemapDocumentsResponse {
-                success: false,
-                data: vec![],
-                count: 0,
-                message: "Yargıtay belgeleri alınamadı".to_string(),
-            });
-        }
-    };
+fn document_entry(urls: &PublicUrls, corpus: &Corpus, record: &DocumentRecord) -> Option<UrlEntry> {
+    if record.url_slug.is_empty() {
+        return None;
+    }
+    // Okunamayan tarih lastmod'a yazılmaz; sıradaki alan denenir
+    let lastmod = [&record.olusturulma_tarihi, &record.belge_yayin_tarihi]
+        .into_iter()
+        .find_map(|value| dates::to_rfc3339(value).ok());
+    Some(UrlEntry {
+        loc: urls.document(corpus, &record.url_slug, &record.id),
+        lastmod,
+        changefreq: "monthly",
+        priority: "0.9",
+    })
+}
 
-    let raw_documents: Vec<MongoDocument> = match cursor.try_collect().await {
-        Ok(docs) => docs,
-        Err(e) => {
-            log::error!("Yargıtay belgeleri deserialize hatası: {}", e);
-            return HttpResponse::InternalServerError().json(SitemapDocumentsResponse {
-                success: false,
-                data: vec![],
-                count: 0,
-                message: "Yargıtay belgeleri alınamadı".to_string(),
-            });
-        }
-    };
+// Corpus alt sitemap'inin akışı: belgeler BATCH_SIZE'lık sayfalarla okunup yazılır
+struct DocumentChunk {
+    state: web::Data<AppState>,
+    corpus: Corpus,
+    offset: u64,
+    end: u64,
+    // Akış bitince None
+    writer: Option<UrlsetWriter>,
+    opened: bool,
+}
 
-    let documents: Vec<SitemapDocument> = raw_documents
-        .into_iter()
-        .filter_map(|doc| {
-            let id = doc
-                .get_object_id("_id")
-                .map(|oid| oid.to_hex())
-                .unwrap_or_default();
-            let url_slug = doc.get_str("url_slug").ok()?.to_string();
-            let olusturulma_tarihi = get_date_string(&doc, "olusturulma_tarihi");
+impl DocumentChunk {
+    async fn next(mut self) -> Option<(Result<Bytes, actix_web::Error>, Self)> {
+        let writer = self.writer.as_mut()?;
+        if !self.opened {
+            self.opened = true;
+            let bytes = writer.open();
+            return Some((Ok(bytes), self));
+        }
+        if self.offset >= self.end {
+            let bytes = self.writer.take()?.close();
+            return Some((Ok(bytes), self));
+        }
 
-            Some(SitemapDocument {
-                id,
-                url_slug,
-                olusturulma_tarihi,
-            })
-        })
-        .collect();
+        let page = Page {
+            offset: self.offset,
+            limit: BATCH_SIZE.min(self.end - self.offset),
+        };
+        match self.state.repos.documents.active_page(&self.corpus, page).await {
+            Ok(records) => {
+                // Sayım ile okuma arasında belge silindiyse parça erken biter
+                self.offset = if records.is_empty() { self.end } else { self.offset + page.limit };
+                let entries: Vec<UrlEntry> = records
+                    .iter()
+                    .filter_map(|record| document_entry(&self.state.urls, &self.corpus, record))
+                    .collect();
+                let bytes = writer.entries(&entries);
+                Some((Ok(bytes), self))
+            }
+            Err(e) => {
+                // Yarım kalan dosya geçerli bir sitemap gibi görünmesin diye akış hatayla kesilir
+                log::error!("Sitemap belge sorgusu başarısız ({}): {}", self.corpus.key, e);
+                self.writer = None;
+                let error = ApiError::storage(Resource::Sitemap)(e);
+                Some((Err(error.into()), self))
+            }
+        }
+    }
+}
 
-    let count = documents.len();
-    HttpResponse::Ok().json(SitemapDocumentsResponse {
-        success: true,
-        data: documents,
-        count,
-        message: "Yargıtay sitemap belgeleri başarıyla alındı".to_string(),
-    })
+// GetSitemapAllDocumentsYargitay returns all Yargitay documents for sitemap
+#[utoipa::path(
+    get,
+    path = "/api/v1/sitemap/all-documents-yargitay-1",
+    tag = "sitemap",
+    responses(
+        (status = 200, description = "İşlem başarılı", body = SitemapDocumentsResponse),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_sitemap_all_documents_yargitay(state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let corpus = state.corpora.yargitay();
+    sitemap_documents_response(fetch_sitemap_documents(&state, corpus, 5000).await, corpus)
 }
 
 pub fn routes(cfg: &mut web::ServiceConfig) {
@@ -1,124 +1,208 @@
 use actix_web::{web, HttpResponse};
-use mongodb::{Collection, bson::{doc, Document as MongoDocument}};
+use utoipa::IntoParams;
 use crate::config::AppState;
-use crate::models::statistics::{StatisticsResponse, StatisticsData, BelgeTuruCount};
+use crate::models::error::ErrorEnvelope;
+use crate::error::{ApiError, Resource};
+use crate::handlers::admin_documents::non_empty;
+use crate::models::statistics::{
+    StatisticsResponse, StatisticsData, BelgeTuruCount, CorpusCount, MonthCount,
+    TimeseriesData, TimeseriesResponse, InstitutionStatistics, InstitutionStatisticsResponse,
+};
+use crate::statistics::{self, CellFilter, MonthlySeries, Snapshot};
 
-pub async fn get_statistics(state: web::Data<AppState>) -> HttpResponse {
-    let kurum_collection: Collection<MongoDocument> = state.db.collection("kurumlar");
-    let metadata_collection: Collection<MongoDocument> = state.db.collection("metadata");
+// İstatistikler önceden hesaplanmış özetten okunur (bkz. crate::statistics)
 
-    // Toplam kurum sayısı
-    let total_kurumlar = match kurum_collection.count_documents(doc! {}, None).await {
-        Ok(count) => count,
-        Err(e) => {
-            log::error!("Kurum sayısı alınamadı: {}", e);
-            return HttpResponse::InternalServerError().json(StatisticsResponse {
-                success: false,
-                data: None,
-                message: None,
-                error: Some("Failed to count institutions".to_string()),
-            });
-        }
-    };
-
-    // Toplam belge sayısı
-    let total_belgeler = match metadata_collection.count_documents(doc! {}, None).await {
-        Ok(count) => count,
-        Err(e) => {
-            log::error!("Belge sayısı alınamadı: {}", e);
-            return HttpResponse::InternalServerError().json(StatisticsResponse {
-                success: false,
-                data: None,
-                message: None,
-                error: Some("Failed to count documents".to_string()),
-            });
-        }
-    };
+async fn snapshot(state: &AppState) -> Result<std::sync::Arc<Snapshot>, ApiError> {
+    state
+        .statistics
+        .current(&state.repos, &state.corpora)
+        .await
+        .map_err(ApiError::storage(Resource::Statistics))
+}
 
-    // Belge türü istatistikleri - Aggregation pipeline
-    let belge_turu_pipeline = vec![
-        doc! {
-            "$group": {
-                "_id": {
-                    "$cond": {
-                        "if": { "$or": [
-                            { "$eq": ["$belge_turu", null] },
-                            { "$eq": ["$belge_turu", ""] }
-                        ]},
-                        "then": "Belirtilmemiş",
-                        "else": "$belge_turu"
-                    }
-                },
-                "count": { "$sum": 1 }
-            }
-        },
-        doc! {
-            "$project": {
-                "belge_turu": "$_id",
-                "count": 1,
-                "_id": 0
-            }
-        },
-        doc! {
-            "$sort": { "count": -1 }
-        },
-    ];
+fn belge_turu_counts(snapshot: &Snapshot, filter: &CellFilter) -> Vec<BelgeTuruCount> {
+    snapshot
+        .by_belge_turu(filter)
+        .into_iter()
+        .map(|(belge_turu, count)| BelgeTuruCount { belge_turu, count })
+        .collect()
+}
 
-    let mut belge_turu_istatistik: Vec<BelgeTuruCount> = Vec::new();
+fn corpus_counts(state: &AppState, snapshot: &Snapshot, filter: &CellFilter) -> Vec<CorpusCount> {
+    snapshot
+        .by_corpus(&state.corpora, filter)
+        .into_iter()
+        .map(|(corpus, count)| CorpusCount {
+            title: state.corpora.get(&corpus).map(|corpus| corpus.title.clone()).unwrap_or_default(),
+            corpus,
+            count,
+        })
+        .collect()
+}
 
-    match metadata_collection.aggregate(belge_turu_pipeline, None).await {
-        Ok(mut cursor) => {
-            while let Ok(true) = cursor.advance().await {
-                if let Ok(doc_map) = cursor.deserialize_current() {
-                    let belge_turu = doc_map
-                        .get_str("belge_turu")
-                        .unwrap_or("Belirtilmemiş")
-                        .to_string();
+fn month_counts(series: MonthlySeries) -> (Vec<MonthCount>, u64) {
+    let months = series
+        .months
+        .into_iter()
+        .map(|(ay, count)| MonthCount { ay, count })
+        .collect();
+    (months, series.undated)
+}
 
-                    // Count değerini farklı tiplerden oku
-                    let count = if let Ok(c) = doc_map.get_i64("count") {
-                        c as u64
-                    } else if let Ok(c) = doc_map.get_i32("count") {
-                        c as u64
-                    } else {
-                        // BSON value'dan direkt oku
-                        doc_map.get("count")
-                            .and_then(|v| v.as_i64())
-                            .or_else(|| doc_map.get("count").and_then(|v| v.as_i32().map(|i| i as i64)))
-                            .unwrap_or(0) as u64
-                    };
+#[utoipa::path(
+    get,
+    path = "/api/v1/statistics",
+    tag = "sistem",
+    responses(
+        (status = 200, description = "İşlem başarılı", body = StatisticsResponse),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_statistics(state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let snapshot = snapshot(&state).await?;
 
-                    belge_turu_istatistik.push(BelgeTuruCount {
-                        belge_turu,
-                        count,
-                    });
-                }
-            }
-        }
-        Err(e) => {
-            log::error!("Belge türü istatistikleri alınamadı: {}", e);
-            return HttpResponse::InternalServerError().json(StatisticsResponse {
-                success: false,
-                data: None,
-                message: None,
-                error: Some("Failed to aggregate document types".to_string()),
-            });
-        }
-    }
+    // Toplam belge sayısı ve belge türü istatistikleri mevzuat corpus'u için
+    let mevzuat = CellFilter {
+        corpus: Some(state.corpora.mevzuat().key.clone()),
+        ..CellFilter::default()
+    };
 
-    HttpResponse::Ok().json(StatisticsResponse {
+    Ok(HttpResponse::Ok().json(StatisticsResponse {
         success: true,
         data: Some(StatisticsData {
-            total_kurumlar,
-            total_belgeler,
-            belge_turu_istatistik,
+            total_kurumlar: snapshot.total_kurumlar,
+            total_belgeler: snapshot.total(&mevzuat),
+            belge_turu_istatistik: belge_turu_counts(&snapshot, &mevzuat),
+            corpus_istatistik: corpus_counts(&state, &snapshot, &CellFilter::default()),
+            hesaplanma_tarihi: snapshot.computed_at.clone(),
         }),
         message: Some("Statistics fetched successfully".to_string()),
         error: None,
-    })
+    }))
 }
 
-pub fn routes(cfg: &mut web::ServiceConfig) {
-    cfg.route("", web::get().to(get_statistics));
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TimeseriesQuery {
+    // Verilmezse tüm corpus'lar (mevzuat, yargitay, ...)
+    pub corpus: Option<String>,
+    pub kurum_id: Option<String>,
+    pub belge_turu: Option<String>,
+    // İlk ay (YYYY-MM, dahil); verilmezse ilk belgeli ay
+    pub from: Option<String>,
+    // Son ay (YYYY-MM, dahil); verilmezse son belgeli ay. from ile en fazla MAX_SPAN_MONTHS ay
+    pub to: Option<String>,
 }
 
+// Yayın ayı başına belge sayısı; boş aylar 0 ile döner
+#[utoipa::path(
+    get,
+    path = "/api/v1/statistics/timeseries",
+    tag = "sistem",
+    params(TimeseriesQuery),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = TimeseriesResponse),
+        (status = 400, description = "Geçersiz ay (YYYY-MM), from > to ya da 600 aydan uzun aralık", body = ErrorEnvelope),
+        (status = 404, description = "Corpus bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_statistics_timeseries(
+    state: web::Data<AppState>,
+    query: web::Query<TimeseriesQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let corpus = non_empty(&query.corpus).map(str::to_string);
+    if let Some(key) = &corpus {
+        if state.corpora.get(key).is_none() {
+            return Err(ApiError::CorpusNotFound(key.clone()));
+        }
+    }
+    let month = |value: &Option<String>, name: &'static str| match non_empty(value) {
+        Some(value) => statistics::parse_month(value)
+            .filter(|month| statistics::clamp_month(*month) == *month)
+            .map(Some)
+            .ok_or(ApiError::InvalidParameter(name)),
+        None => Ok(None),
+    };
+    let from = month(&query.from, "from")?;
+    let to = month(&query.to, "to")?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(ApiError::InvalidParameter("from"));
+        }
+        if statistics::month_span(from, to) > i64::from(statistics::MAX_SPAN_MONTHS) {
+            return Err(ApiError::InvalidParameter("to"));
+        }
+    }
+
+    let snapshot = snapshot(&state).await?;
+    let filter = CellFilter {
+        corpus,
+        kurum_id: non_empty(&query.kurum_id).map(str::to_string),
+        belge_turu: non_empty(&query.belge_turu).map(str::to_string),
+    };
+    let (aylar, tarihsiz_belgeler) = month_counts(snapshot.monthly(&filter, from, to));
+
+    Ok(HttpResponse::Ok().json(TimeseriesResponse {
+        success: true,
+        data: TimeseriesData {
+            total_belgeler: aylar.iter().map(|month| month.count).sum(),
+            aylar,
+            tarihsiz_belgeler,
+            hesaplanma_tarihi: snapshot.computed_at.clone(),
+        },
+        message: "İşlem başarılı".to_string(),
+    }))
+}
+
+// Kurumun corpus, belge türü ve yayın ayı dağılımı
+#[utoipa::path(
+    get,
+    path = "/api/v1/statistics/institutions/{id}",
+    tag = "sistem",
+    params(("id" = String, Path, description = "Kurumun ObjectId hex değeri")),
+    responses(
+        (status = 200, description = "İşlem başarılı", body = InstitutionStatisticsResponse),
+        (status = 404, description = "Kurum bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn get_institution_statistics(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let kurum_id = path.into_inner();
+    let snapshot = snapshot(&state).await?;
+    let filter = CellFilter {
+        kurum_id: Some(kurum_id.clone()),
+        ..CellFilter::default()
+    };
+    // Kurumlar koleksiyonunda olmayan ama belgelerde geçen kurum_id de sayılır
+    let kurum_adi = match snapshot.kurumlar.get(&kurum_id) {
+        Some(kurum_adi) => kurum_adi.clone(),
+        None if snapshot.cells(&filter).next().is_some() => String::new(),
+        None => return Err(ApiError::InstitutionNotFound),
+    };
+    let (aylar, tarihsiz_belgeler) = month_counts(snapshot.monthly(&filter, None, None));
+
+    Ok(HttpResponse::Ok().json(InstitutionStatisticsResponse {
+        success: true,
+        data: InstitutionStatistics {
+            total_belgeler: snapshot.total(&filter),
+            corpus_istatistik: corpus_counts(&state, &snapshot, &filter),
+            belge_turu_istatistik: belge_turu_counts(&snapshot, &filter),
+            aylar,
+            tarihsiz_belgeler,
+            hesaplanma_tarihi: snapshot.computed_at.clone(),
+            kurum_id,
+            kurum_adi,
+        },
+        message: "İşlem başarılı".to_string(),
+    }))
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get_statistics))
+        .route("/timeseries", web::get().to(get_statistics_timeseries))
+        .route("/institutions/{id}", web::get().to(get_institution_statistics));
+}
@@ -0,0 +1,167 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use actix_web::web::Bytes;
+use actix_web::{web, HttpRequest, HttpResponse};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{Instant, Interval};
+use utoipa::IntoParams;
+use crate::config::AppState;
+use crate::error::{ApiError, Resource};
+use crate::handlers::admin_documents::non_empty;
+use crate::models::error::ErrorEnvelope;
+use crate::models::stream::DocumentEvent;
+use crate::stream::{self, StreamEvent, StreamFilter};
+
+// Yeni belgelerin Server-Sent Events akışı. Bağlantı açık kaldıkça metadata/yargitay
+// koleksiyonlarına eklenen her belge için bir "document.created" olayı gönderilir.
+// Kopan istemci Last-Event-ID ile yeniden bağlandığında aradaki belgeler önce gönderilir;
+// aradaki belgeler bir bağlantıda gönderilemeyecek kadar çoksa gönderilenlerin ardından
+// "reset" olayı gelir ve bağlantı kapanır, istemci bu olayın id'siyle kaldığı yerden devam eder.
+
+// İstemcinin yeniden bağlanmadan önce beklemesi önerilen süre (ms)
+const RETRY_MILLIS: u64 = 5000;
+
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StreamQuery {
+    // Yalnızca bu corpus'un belgeleri (mevzuat, yargitay, ...)
+    pub corpus: Option<String>,
+    // Kurumun ObjectId hex değeri
+    pub kurum_id: Option<String>,
+    pub belge_turu: Option<String>,
+    // Last-Event-ID başlığı gönderemeyen istemciler için (başlık varsa o kullanılır)
+    pub last_event_id: Option<String>,
+}
+
+struct Session {
+    receiver: broadcast::Receiver<Arc<StreamEvent>>,
+    filter: StreamFilter,
+    // Önce gönderilecek çerçeveler (retry ve kaçırılan belgeler)
+    pending: VecDeque<String>,
+    // Kaçırılanların tamamı gönderilemedi: bekleyen çerçevelerden sonra bağlantı kapatılır,
+    // istemci reset olayının id'siyle yeniden bağlanır
+    close_after_pending: bool,
+    // Kaçırılan belgeler içinde gönderilenler; yayın kanalından ikinci kez gönderilmez
+    replayed: HashSet<String>,
+    keep_alive: Interval,
+}
+
+impl Session {
+    async fn next_frame(&mut self) -> Option<String> {
+        if let Some(frame) = self.pending.pop_front() {
+            return Some(frame);
+        }
+        if self.close_after_pending {
+            return None;
+        }
+        loop {
+            tokio::select! {
+                received = self.receiver.recv() => match received {
+                    Ok(event) => {
+                        if self.replayed.remove(&event.id) || !self.filter.matches(&event.data) {
+                            continue;
+                        }
+                        self.keep_alive.reset();
+                        return Some(event.frame());
+                    }
+                    // İstemci geride kaldı: bağlantıyı kapat, Last-Event-ID ile kaldığı yerden devam etsin
+                    Err(RecvError::Lagged(skipped)) => {
+                        log::warn!("Belge akışı istemcisi {} olay geride kaldı, bağlantı kapatılıyor", skipped);
+                        return None;
+                    }
+                    Err(RecvError::Closed) => return None,
+                },
+                _ = self.keep_alive.tick() => return Some(": keep-alive\n\n".to_string()),
+            }
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/stream/documents",
+    tag = "belgeler",
+    params(
+        StreamQuery,
+        ("Last-Event-ID" = Option<String>, Header, description = "Son alınan olayın id değeri; aradaki belgeler önce gönderilir"),
+    ),
+    responses(
+        (status = 200, description = "document.created olayları (Server-Sent Events); data alanı JSON. Kaçırılanlar bir bağlantıda gönderilemeyecek kadar çoksa reset olayı gönderilir ve bağlantı kapanır; istemci olayın id'siyle yeniden bağlanmalıdır", content_type = "text/event-stream", body = DocumentEvent),
+        (status = 400, description = "Geçersiz Last-Event-ID", body = ErrorEnvelope),
+        (status = 404, description = "Corpus bulunamadı", body = ErrorEnvelope),
+        (status = 500, description = "Depolama hatası", body = ErrorEnvelope),
+    )
+)]
+pub async fn stream_documents(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<StreamQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let corpus = non_empty(&query.corpus).map(str::to_string);
+    if let Some(key) = &corpus {
+        if state.corpora.get(key).is_none() {
+            return Err(ApiError::CorpusNotFound(key.clone()));
+        }
+    }
+    let filter = StreamFilter {
+        corpus,
+        kurum_id: non_empty(&query.kurum_id).map(str::to_string),
+        belge_turu: non_empty(&query.belge_turu).map(str::to_string),
+    };
+
+    let last_event_id = req
+        .headers()
+        .get("Last-Event-ID")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .or_else(|| query.last_event_id.clone())
+        .filter(|value| !value.trim().is_empty());
+    let since = match last_event_id {
+        Some(value) => Some(stream::parse_event_id(&value).ok_or(ApiError::InvalidParameter("last_event_id"))?),
+        None => None,
+    };
+
+    // Kaçırılanlar okunurken yayınlanan olaylar kaybolmasın diye önce abone olunur
+    let receiver = state.document_stream.subscribe();
+    let mut pending = VecDeque::from([format!("retry: {}\n\n", RETRY_MILLIS)]);
+    let mut replayed = HashSet::new();
+    let mut close_after_pending = false;
+    if let Some(since) = since {
+        let replay = stream::replay(&state.repos, &state.corpora, &state.urls, &filter, since)
+            .await
+            .map_err(ApiError::storage(Resource::Documents))?;
+        for event in replay.events {
+            pending.push_back(event.frame());
+            replayed.insert(event.id);
+        }
+        if let Some(resume_from) = replay.resume_from {
+            pending.push_back(stream::reset_frame(&resume_from));
+            close_after_pending = true;
+        }
+    }
+
+    let keep_alive = state.document_stream.keep_alive;
+    let session = Session {
+        receiver,
+        filter,
+        pending,
+        close_after_pending,
+        replayed,
+        keep_alive: tokio::time::interval_at(Instant::now() + keep_alive, keep_alive),
+    };
+    let body = futures::stream::unfold(session, |mut session| async move {
+        let frame = session.next_frame().await?;
+        Some((Ok::<_, actix_web::Error>(Bytes::from(frame)), session))
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        // nginx arkasında olayların tamponlanmaması için
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(body))
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/documents", web::get().to(stream_documents));
+}
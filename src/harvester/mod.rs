@@ -0,0 +1,250 @@
+use std::env;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+use chrono::Utc;
+use futures::future::BoxFuture;
+use crate::models::kurum_duyuru_scraped::DuyuruItem;
+use url::Url;
+use crate::dates;
+use crate::repository::{
+    AnnouncementDetailWrite, AnnouncementSourceRecord, AttachmentRecord, Repositories, RepositoryError,
+    ScrapedAnnouncementWrite,
+};
+use crate::scraping::{self, AnnouncementDetail, Fetcher};
+use crate::utils::format_timestamp;
+
+// Kurum duyuru hasadı: kurum_duyuru'daki her duyuru sayfası belirli aralıklarla taranır,
+// yeni duyurular kurum_duyuru_items koleksiyonuna link'e göre tekilleştirilerek yazılır.
+// /api/v1/kurum-duyuru istekleri dış siteye gitmeden bu koleksiyondan okunur.
+// Detay adımı açıksa her turdan sonra detayı çekilmemiş duyuruların sayfaları da okunur:
+// ana metin (aramada kullanılır), yayın tarihi ve PDF/DOCX ekleri (belge alımına aday).
+
+const DEFAULT_INTERVAL_SECS: u64 = 3600;
+// Bir turda detayı çekilen en fazla duyuru sayısı; kalanlar sonraki turlara kalır
+pub const DETAIL_BATCH: u64 = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HarvestConfig {
+    // None ise hasat yapılmaz (örn. birden fazla instance'tan yalnızca biri hasat etsin)
+    pub interval: Option<Duration>,
+    // Duyuru detay sayfaları da çekilsin mi
+    pub details: bool,
+}
+
+impl Default for HarvestConfig {
+    fn default() -> Self {
+        HarvestConfig {
+            interval: Some(Duration::from_secs(DEFAULT_INTERVAL_SECS)),
+            details: false,
+        }
+    }
+}
+
+impl HarvestConfig {
+    // ANNOUNCEMENT_HARVEST_INTERVAL: saniye cinsinden aralık; 0 hasadı kapatır.
+    // ANNOUNCEMENT_DETAILS: true/1 ise detay sayfaları da çekilir
+    pub fn from_env() -> Self {
+        let seconds = env::var("ANNOUNCEMENT_HARVEST_INTERVAL")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok());
+        let interval = match seconds {
+            Some(0) => None,
+            Some(seconds) => Some(Duration::from_secs(seconds)),
+            None => HarvestConfig::default().interval,
+        };
+        let details = env::var("ANNOUNCEMENT_DETAILS")
+            .map(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+        HarvestConfig { interval, details }
+    }
+}
+
+#[derive(Debug)]
+pub enum HarvestError {
+    // Duyuru sayfası çekilemedi ya da çözümlenemedi
+    Scrape(String),
+    Storage(RepositoryError),
+}
+
+impl fmt::Display for HarvestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HarvestError::Scrape(message) => write!(f, "{}", message),
+            HarvestError::Storage(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<RepositoryError> for HarvestError {
+    fn from(e: RepositoryError) -> Self {
+        HarvestError::Storage(e)
+    }
+}
+
+// Bir hasat turunun özeti
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HarvestReport {
+    pub sources: usize,
+    pub failed: usize,
+    // Yeni eklenen duyuru sayısı
+    pub stored: u64,
+}
+
+// Detay turunun özeti
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DetailReport {
+    pub fetched: usize,
+    pub failed: usize,
+}
+
+// Kaynağı kurallarıyla (yoksa domain'e göre hazır desenle) paylaşılan fetcher üzerinden tarayan fonksiyon
+pub fn scrape_with(fetcher: &Fetcher) -> impl Fn(AnnouncementSourceRecord) -> BoxFuture<'static, Result<Vec<DuyuruItem>, String>> {
+    let fetcher = fetcher.clone();
+    move |source| {
+        let fetcher = fetcher.clone();
+        Box::pin(async move { scraping::scrape_source(&fetcher, &source).await })
+    }
+}
+
+// Duyuru sayfasını önbelleğe almadan çekip okuyan fonksiyon
+pub fn detail_with(fetcher: &Fetcher) -> impl Fn(String) -> BoxFuture<'static, Result<AnnouncementDetail, String>> {
+    let fetcher = fetcher.clone();
+    move |link| {
+        let fetcher = fetcher.clone();
+        Box::pin(async move {
+            let url = Url::parse(&link).map_err(|_| "Geçersiz URL".to_string())?;
+            let html = fetcher.fetch_uncached(&url).await?;
+            Ok(scraping::extract_detail(&html, &url))
+        })
+    }
+}
+
+// Detayı çekilmemiş en fazla limit duyurunun sayfasını okur. Başarısız deneme de hatasıyla
+// kaydedilir; böylece erişilemeyen sayfa her turda yeniden denenmez.
+pub async fn fetch_details<F, Fut>(repos: &Repositories, fetch: &F, limit: u64) -> Result<DetailReport, RepositoryError>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<AnnouncementDetail, String>>,
+{
+    let pending = repos.scraped_announcements.pending_details(limit).await?;
+    let mut report = DetailReport::default();
+    for record in pending {
+        let fetched_at = format_timestamp(Utc::now());
+        let details = match fetch(record.link.clone()).await {
+            Ok(detail) => {
+                report.fetched += 1;
+                AnnouncementDetailWrite {
+                    icerik: Some(detail.icerik).filter(|icerik| !icerik.is_empty()),
+                    // Liste sayfasındaki tarih varsa korunur
+                    tarih: detail.tarih.filter(|_| record.tarih.is_none()).map(dates::day_month_year),
+                    ekler: detail
+                        .ekler
+                        .into_iter()
+                        .map(|ek| AttachmentRecord {
+                            url: ek.url,
+                            baslik: ek.baslik,
+                            tur: ek.tur,
+                        })
+                        .collect(),
+                    fetched_at,
+                    error: None,
+                }
+            }
+            Err(e) => {
+                report.failed += 1;
+                log::warn!("Duyuru detayı çekilemedi: {}: {}", record.link, e);
+                AnnouncementDetailWrite {
+                    fetched_at,
+                    error: Some(e),
+                    ..AnnouncementDetailWrite::default()
+                }
+            }
+        };
+        repos.scraped_announcements.store_details(&record.id, &details).await?;
+    }
+    Ok(report)
+}
+
+// Tek kaynağı tarar; yeni eklenen duyuru sayısını döner
+pub async fn harvest_source<F, Fut>(
+    repos: &Repositories,
+    source: &AnnouncementSourceRecord,
+    scrape: &F,
+) -> Result<u64, HarvestError>
+where
+    F: Fn(AnnouncementSourceRecord) -> Fut,
+    Fut: Future<Output = Result<Vec<DuyuruItem>, String>>,
+{
+    let items = scrape(source.clone()).await.map_err(HarvestError::Scrape)?;
+    let first_seen_at = format_timestamp(Utc::now());
+
+    // Sayfalar en yeni duyuruyu üstte gösterir; en eski önce eklenir ki aynı hasattaki
+    // duyurular listede sayfadaki sırayla dönsün
+    let writes: Vec<ScrapedAnnouncementWrite> = items
+        .into_iter()
+        .rev()
+        .filter(|item| !item.link.is_empty())
+        .map(|item| ScrapedAnnouncementWrite {
+            kurum_id: source.kurum_id.clone(),
+            baslik: item.baslik,
+            link: item.link,
+            tarih: item.tarih,
+            first_seen_at: first_seen_at.clone(),
+        })
+        .collect();
+    Ok(repos.scraped_announcements.store(&writes).await?)
+}
+
+// Tüm duyuru kaynaklarını sırayla tarar; bir kaynağın hatası diğerlerini durdurmaz
+pub async fn harvest_all<F, Fut>(repos: &Repositories, scrape: &F) -> Result<HarvestReport, RepositoryError>
+where
+    F: Fn(AnnouncementSourceRecord) -> Fut,
+    Fut: Future<Output = Result<Vec<DuyuruItem>, String>>,
+{
+    let sources = repos.announcement_sources.list(None).await?;
+    let mut report = HarvestReport::default();
+    for source in sources.iter().filter(|source| !source.duyuru_linki.is_empty()) {
+        report.sources += 1;
+        match harvest_source(repos, source, scrape).await {
+            Ok(stored) => report.stored += stored,
+            Err(e) => {
+                report.failed += 1;
+                log::warn!("Duyuru hasadı başarısız: kurum {} ({}): {}", source.kurum_id, source.duyuru_linki, e);
+            }
+        }
+    }
+    Ok(report)
+}
+
+pub fn spawn(repos: Repositories, fetcher: Fetcher, interval: Duration, details: bool) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let scrape = scrape_with(&fetcher);
+        let detail = detail_with(&fetcher);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match harvest_all(&repos, &scrape).await {
+                Ok(report) => log::info!(
+                    "Duyuru hasadı tamamlandı: {} kaynak, {} yeni duyuru, {} hata",
+                    report.sources,
+                    report.stored,
+                    report.failed
+                ),
+                Err(e) => log::error!("Duyuru kaynakları okunamadı: {}", e),
+            }
+            if !details {
+                continue;
+            }
+            match fetch_details(&repos, &detail, DETAIL_BATCH).await {
+                Ok(report) => log::info!(
+                    "Duyuru detayları çekildi: {} başarılı, {} hata",
+                    report.fetched,
+                    report.failed
+                ),
+                Err(e) => log::error!("Duyuru detayları kaydedilemedi: {}", e),
+            }
+        }
+    })
+}
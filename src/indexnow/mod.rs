@@ -0,0 +1,320 @@
+use std::env;
+use std::fmt;
+use std::time::Duration;
+use serde_json::json;
+use crate::config::PublicUrls;
+use crate::corpus::{Corpus, CorpusRegistry};
+use crate::repository::{AuditFilter, Page, Repositories, RepositoryError, ScanCursor};
+use crate::webhooks::start_cursor;
+
+// IndexNow: eklenen ve değişen belgelerin adresleri arama motorlarına bildirilir.
+// Yeni belgeler corpus başına olusturulma_tarihi imleciyle, güncellenen ve silinen belgeler
+// denetim kaydından (audit_log) bulunur. İmleçler webhook taramasıyla aynı koleksiyonda
+// (webhook_state) "indexnow:" önekiyle tutulur ve yalnızca bildirim kabul edilince ilerler;
+// başarısız tur sonraki aralıkta aynı adreslerle tekrarlanır.
+
+pub const DEFAULT_ENDPOINT: &str = "https://api.indexnow.org/indexnow";
+// Anahtar dosyası site kökünde olmalı; kökteki dosya tüm adresler için geçerlidir
+pub const KEY_FILE_PATH: &str = "/indexnow-key.txt";
+
+const DEFAULT_INTERVAL_SECS: u64 = 300;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+// Belge ve denetim kayıtlarının tek seferde okunan sayısı (sayfa boyutu)
+const PAGE_SIZE: u64 = 200;
+// IndexNow tek istekte en fazla 10.000 adres kabul eder
+pub const MAX_URLS_PER_REQUEST: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexNowConfig {
+    // None ise bildirim yapılmaz ve anahtar dosyası sunulmaz
+    pub key: Option<String>,
+    pub endpoint: String,
+    // None ise tarama yapılmaz (birden fazla instance'tan yalnızca biri çalıştırsın)
+    pub interval: Option<Duration>,
+    pub timeout: Duration,
+}
+
+impl Default for IndexNowConfig {
+    fn default() -> Self {
+        IndexNowConfig {
+            key: None,
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            interval: Some(Duration::from_secs(DEFAULT_INTERVAL_SECS)),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+        }
+    }
+}
+
+impl IndexNowConfig {
+    // INDEXNOW_KEY (8-128 karakter; harf, rakam ve "-"), INDEXNOW_ENDPOINT, INDEXNOW_INTERVAL
+    // (saniye, 0 kapatır) ve INDEXNOW_TIMEOUT (saniye). Geçersiz anahtar ya da adres başlangıçta hata verir.
+    pub fn from_env() -> Result<Self, String> {
+        let default = IndexNowConfig::default();
+        let number = |name: &str| env::var(name).ok().and_then(|value| value.trim().parse::<u64>().ok());
+        let key = match env::var("INDEXNOW_KEY") {
+            Ok(key) if !key.trim().is_empty() => Some(validate_key(key.trim())?),
+            _ => None,
+        };
+        let endpoint = match env::var("INDEXNOW_ENDPOINT") {
+            Ok(endpoint) if !endpoint.trim().is_empty() => validate_endpoint(endpoint.trim())?,
+            _ => default.endpoint,
+        };
+        let interval = match number("INDEXNOW_INTERVAL") {
+            Some(0) => None,
+            Some(seconds) => Some(Duration::from_secs(seconds)),
+            None => default.interval,
+        };
+        Ok(IndexNowConfig {
+            key,
+            endpoint,
+            interval,
+            timeout: number("INDEXNOW_TIMEOUT")
+                .filter(|seconds| *seconds > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(default.timeout),
+        })
+    }
+}
+
+pub fn validate_key(key: &str) -> Result<String, String> {
+    let valid = (8..=128).contains(&key.len()) && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !valid {
+        return Err(format!("INDEXNOW_KEY 8-128 karakter olmalı ve yalnızca harf, rakam ve \"-\" içermeli: {}", key));
+    }
+    Ok(key.to_string())
+}
+
+fn validate_endpoint(endpoint: &str) -> Result<String, String> {
+    match url::Url::parse(endpoint) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(endpoint.to_string()),
+        _ => Err(format!("INDEXNOW_ENDPOINT http(s) adresi olmalı: {}", endpoint)),
+    }
+}
+
+#[derive(Debug)]
+pub enum PingError {
+    Storage(RepositoryError),
+    // Bağlantı hatası ya da 200/202 dışındaki yanıt
+    Endpoint(String),
+}
+
+impl fmt::Display for PingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PingError::Storage(e) => write!(f, "{}", e),
+            PingError::Endpoint(message) => write!(f, "IndexNow bildirimi başarısız: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for PingError {}
+
+impl From<RepositoryError> for PingError {
+    fn from(e: RepositoryError) -> Self {
+        PingError::Storage(e)
+    }
+}
+
+// Turun özeti
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PingReport {
+    // Bildirilen tekil adres sayısı
+    pub urls: usize,
+    // Endpoint'e yapılan istek sayısı
+    pub requests: usize,
+}
+
+fn documents_cursor(corpus: &Corpus) -> String {
+    format!("indexnow:documents:{}", corpus.key)
+}
+
+fn changes_cursor(corpus: &Corpus) -> String {
+    format!("indexnow:changes:{}", corpus.key)
+}
+
+// Bildirilecek adresler (ilk görülme sırasıyla, tekil) ve kabul edilince yazılacak imleçler
+#[derive(Debug, Default)]
+pub struct Pending {
+    pub urls: Vec<String>,
+    cursors: Vec<(String, ScanCursor)>,
+}
+
+impl Pending {
+    // Bir isteğin taşıyabileceği kadar adres toplandı mı
+    fn full(&self) -> bool {
+        self.urls.len() >= MAX_URLS_PER_REQUEST
+    }
+
+    fn push(&mut self, url: String) {
+        if !self.urls.contains(&url) {
+            self.urls.push(url);
+        }
+    }
+}
+
+// Son turdan bu yana eklenen, güncellenen ya da silinen belgelerin adresleri. İlk turda
+// imleçler kurulur; mevcut arşiv bildirilmez. Kayıtlar imleçten itibaren en eski önce sayfa
+// sayfa okunur; bir turda en fazla MAX_URLS_PER_REQUEST adres toplanır, kalanlar sonraki
+// tura kalır (imleçler yalnızca toplanan kayıtlara kadar ilerler).
+pub async fn collect(repos: &Repositories, corpora: &CorpusRegistry, urls: &PublicUrls) -> Result<Pending, RepositoryError> {
+    let mut pending = Pending::default();
+    for corpus in corpora.all() {
+        let key = documents_cursor(corpus);
+        match repos.webhooks.cursor(&key).await? {
+            None => {
+                let newest = repos.documents.recent(corpus, PAGE_SIZE).await?;
+                let start = start_cursor(newest.iter().map(|record| (record.olusturulma_tarihi.as_str(), record.id.as_str())));
+                pending.cursors.push((key, start));
+            }
+            Some(cursor) => {
+                let mut next = cursor.clone();
+                while !pending.full() {
+                    let page = repos.documents.created_after(corpus, &next, PAGE_SIZE).await?;
+                    for record in &page {
+                        pending.push(urls.document(corpus, &record.url_slug, &record.id));
+                    }
+                    next = next.advance(page.iter().map(|record| (record.olusturulma_tarihi.as_str(), record.id.as_str())));
+                    if (page.len() as u64) < PAGE_SIZE {
+                        break;
+                    }
+                }
+                if next != cursor {
+                    pending.cursors.push((key, next));
+                }
+            }
+        }
+
+        // Güncellenen belgenin güncel adresi; slug değiştiyse ya da belge silindiyse eski adresi de
+        let key = changes_cursor(corpus);
+        let filter = AuditFilter {
+            collection: Some(corpus.collection.clone()),
+            ..AuditFilter::default()
+        };
+        match repos.webhooks.cursor(&key).await? {
+            None => {
+                let newest = repos.audit.list(&filter, Page { offset: 0, limit: PAGE_SIZE }).await?;
+                let start = start_cursor(newest.iter().map(|entry| (entry.timestamp.as_str(), entry.id.as_str())));
+                pending.cursors.push((key, start));
+            }
+            Some(cursor) => {
+                let mut next = cursor.clone();
+                while !pending.full() {
+                    let entries = repos.audit.after(&filter, &next, PAGE_SIZE).await?;
+                    for entry in &entries {
+                        for change in entry.changes.iter().filter(|change| change.field == "url_slug") {
+                            for slug in [&change.before, &change.after].into_iter().flatten().filter_map(|slug| slug.as_str()) {
+                                if !slug.is_empty() {
+                                    pending.push(urls.document(corpus, slug, &entry.target_id));
+                                }
+                            }
+                        }
+                        if let Some(record) = repos.documents.find_by_id(corpus, &entry.target_id).await? {
+                            pending.push(urls.document(corpus, &record.url_slug, &record.id));
+                        }
+                    }
+                    next = next.advance(entries.iter().map(|entry| (entry.timestamp.as_str(), entry.id.as_str())));
+                    if (entries.len() as u64) < PAGE_SIZE {
+                        break;
+                    }
+                }
+                if next != cursor {
+                    pending.cursors.push((key, next));
+                }
+            }
+        }
+    }
+    Ok(pending)
+}
+
+pub fn client(config: &IndexNowConfig) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(config.timeout)
+        .build()
+        .expect("HTTP client oluşturulamadı")
+}
+
+// Adresleri MAX_URLS_PER_REQUEST'lik parçalar halinde gönderir, istek sayısını döndürür.
+// Sitenin host'una ait olmayan adresler (örn. başka alan adındaki şablonlar) gönderilmez;
+// IndexNow bu durumda isteğin tamamını reddeder.
+pub async fn submit(
+    client: &reqwest::Client,
+    endpoint: &str,
+    key: &str,
+    urls: &PublicUrls,
+    list: &[String],
+) -> Result<usize, PingError> {
+    let prefix = format!("{}/", urls.base_url());
+    let list: Vec<&String> = list.iter().filter(|url| url.starts_with(&prefix)).collect();
+    let mut requests = 0;
+    for chunk in list.chunks(MAX_URLS_PER_REQUEST) {
+        let body = json!({
+            "host": urls.host(),
+            "key": key,
+            "keyLocation": urls.absolute(KEY_FILE_PATH),
+            "urlList": chunk,
+        });
+        let response = client
+            .post(endpoint)
+            .header(reqwest::header::USER_AGENT, "MevzuatGPT-IndexNow/1.0")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| PingError::Endpoint(e.to_string()))?;
+        requests += 1;
+        // 200: alındı, 202: alındı ancak anahtar doğrulaması bekliyor
+        if !matches!(response.status().as_u16(), 200 | 202) {
+            return Err(PingError::Endpoint(format!("HTTP {}", response.status().as_u16())));
+        }
+    }
+    Ok(requests)
+}
+
+// Yeni ve değişen belgeleri bulur, bildirir ve imleçleri ilerletir
+pub async fn run(
+    repos: &Repositories,
+    corpora: &CorpusRegistry,
+    urls: &PublicUrls,
+    client: &reqwest::Client,
+    endpoint: &str,
+    key: &str,
+) -> Result<PingReport, PingError> {
+    let pending = collect(repos, corpora, urls).await?;
+    let mut report = PingReport {
+        urls: pending.urls.len(),
+        requests: 0,
+    };
+    if !pending.urls.is_empty() {
+        report.requests = submit(client, endpoint, key, urls, &pending.urls).await?;
+    }
+    for (name, cursor) in &pending.cursors {
+        repos.webhooks.save_cursor(name, cursor).await?;
+    }
+    Ok(report)
+}
+
+pub fn spawn(
+    repos: Repositories,
+    corpora: CorpusRegistry,
+    urls: PublicUrls,
+    config: IndexNowConfig,
+    key: String,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let client = client(&config);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match run(&repos, &corpora, &urls, &client, &config.endpoint, &key).await {
+                Ok(report) if report.urls > 0 => log::info!(
+                    "IndexNow: {} adres {} istekle bildirildi",
+                    report.urls,
+                    report.requests
+                ),
+                Ok(_) => {}
+                Err(e) => log::error!("IndexNow turu başarısız: {}", e),
+            }
+        }
+    })
+}
@@ -0,0 +1,74 @@
+use crate::audit::Actor;
+use crate::config::AppState;
+use crate::error::Language;
+use crate::models::admin_document::DocumentInput;
+use super::{ingest, PdfSource};
+
+// PDF alım komutu:
+//   --ingest-pdf <dosya|url> --kurum-id <id> [--title <ad>] [--belge-turu <tür>]
+//     [--yayin-tarihi YYYY-MM-DD] [--slug <slug>] [--status aktif|pasif] [--pdf-url <url>]
+#[derive(Debug, PartialEq)]
+pub struct IngestCommand {
+    pub source: PdfSource,
+    pub metadata: DocumentInput,
+}
+
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .map(|value| value.as_str())
+        .filter(|value| !value.starts_with("--"))
+}
+
+impl IngestCommand {
+    // --ingest-pdf verilmemişse None döner
+    pub fn from_args(args: &[String]) -> Option<Result<Self, String>> {
+        if !args.iter().any(|arg| arg == "--ingest-pdf") {
+            return None;
+        }
+        Some(Self::parse(args))
+    }
+
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let source = arg_value(args, "--ingest-pdf").ok_or("--ingest-pdf için dosya yolu ya da URL gerekli")?;
+        let kurum_id = arg_value(args, "--kurum-id").ok_or("--kurum-id gerekli")?;
+        let value = |flag: &str| arg_value(args, flag).map(str::to_string);
+
+        Ok(IngestCommand {
+            source: PdfSource::parse(source),
+            metadata: DocumentInput {
+                kurum_id: Some(kurum_id.to_string()),
+                pdf_adi: value("--title"),
+                belge_turu: value("--belge-turu"),
+                belge_yayin_tarihi: value("--yayin-tarihi"),
+                url_slug: value("--slug"),
+                status: value("--status"),
+                pdf_url: value("--pdf-url"),
+                ..DocumentInput::default()
+            },
+        })
+    }
+}
+
+pub async fn run(command: IngestCommand, state: &AppState) -> Result<(), String> {
+    let (id, document) = ingest(state, &Actor::cli(), &command.source, command.metadata)
+        .await
+        .map_err(|e| {
+            let mut message = format!("PDF alınamadı: {}", e.message(Language::Tr));
+            if let Some(detail) = e.detail() {
+                message.push_str(&format!(" ({})", detail));
+            }
+            for field in e.fields() {
+                message.push_str(&format!("\n  {}: {}", field.field, field.issue.message(Language::Tr)));
+            }
+            message
+        })?;
+
+    println!("Belge oluşturuldu: {} ({})", document.pdf_adi, id);
+    println!("Slug: {}", document.url_slug);
+    println!("Sayfa sayısı: {}", document.sayfa_sayisi);
+    println!("Dosya boyutu: {:.2} MB", document.dosya_boyutu_mb);
+    println!("Metin uzunluğu: {} karakter", document.icerik.as_deref().unwrap_or_default().chars().count());
+    Ok(())
+}
@@ -0,0 +1,226 @@
+pub mod cli;
+pub mod text;
+
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+use pdf_extract::{Document, Object, PlainTextOutput};
+use reqwest::header::HeaderMap;
+use crate::audit::Actor;
+use crate::config::AppState;
+use crate::error::ApiError;
+use crate::handlers::admin_documents;
+use crate::models::admin_document::DocumentInput;
+use crate::repository::DocumentWrite;
+use crate::scraping::Crawler;
+pub use text::normalize_text;
+
+// PDF alım hattı: yerel dosya ya da URL'den PDF'i okur, metni çıkarıp normalize eder,
+// sayfa sayısı ve boyutu hesaplar ve belgeyi yönetim API'siyle aynı yoldan
+// (doğrulama, slug, metadata + content) kaydeder. URL'ler paylaşılan tarayıcıyla indirilir
+// (kendini tanıtan User-Agent, robots.txt, host başına sınır ve devre kesici).
+
+// İndirilecek / okunacak en büyük PDF
+pub const MAX_PDF_BYTES: usize = 50 * 1024 * 1024;
+const DOWNLOAD_TIMEOUT_SECS: u64 = 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PdfSource {
+    File(PathBuf),
+    Url(String),
+}
+
+#[derive(Debug)]
+pub enum IngestError {
+    Io(String),
+    Download(String),
+    // Okunan bayt sayısı (sınır aşıldığı anda)
+    TooLarge(usize),
+    NotPdf,
+    Encrypted,
+    Parse(String),
+    // Metin katmanı olmayan (taranmış) PDF
+    NoText,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedPdf {
+    pub text: String,
+    pub sayfa_sayisi: i32,
+    // MB, iki ondalık basamak
+    pub dosya_boyutu_mb: f64,
+    // PDF bilgi sözlüğündeki başlık (Info /Title)
+    pub title: Option<String>,
+}
+
+impl PdfSource {
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim();
+        if value.starts_with("https://") || value.starts_with("http://") {
+            PdfSource::Url(value.to_string())
+        } else {
+            PdfSource::File(PathBuf::from(value))
+        }
+    }
+
+    // Başlık bulunamazsa kullanılacak dosya adı (uzantısız)
+    pub fn file_stem(&self) -> Option<String> {
+        let name = match self {
+            PdfSource::File(path) => path.file_stem()?.to_string_lossy().to_string(),
+            PdfSource::Url(url) => {
+                let path = url::Url::parse(url).ok()?.path_segments()?.next_back()?.to_string();
+                let decoded = percent_decode(&path);
+                decoded.strip_suffix(".pdf").or_else(|| decoded.strip_suffix(".PDF")).unwrap_or(&decoded).to_string()
+            }
+        };
+        let name = name.replace(['_', '-'], " ").trim().to_string();
+        Some(name).filter(|name| !name.is_empty())
+    }
+
+    pub async fn read(&self, crawler: &Crawler) -> Result<Vec<u8>, IngestError> {
+        match self {
+            PdfSource::File(path) => {
+                let size = tokio::fs::metadata(path)
+                    .await
+                    .map_err(|e| IngestError::Io(format!("{}: {}", path.display(), e)))?
+                    .len() as usize;
+                if size > MAX_PDF_BYTES {
+                    return Err(IngestError::TooLarge(size));
+                }
+                tokio::fs::read(path)
+                    .await
+                    .map_err(|e| IngestError::Io(format!("{}: {}", path.display(), e)))
+            }
+            PdfSource::Url(url) => download(crawler, url).await,
+        }
+    }
+}
+
+// Yüzde kodlu dosya adlarını çöz (örn. "Y%C3%B6netmelik.pdf"); "+" boşluğa döner
+fn percent_decode(value: &str) -> String {
+    url::form_urlencoded::parse(value.replace(['&', '='], "_").as_bytes())
+        .map(|(name, _)| name.into_owned())
+        .next()
+        .unwrap_or_default()
+}
+
+async fn download(crawler: &Crawler, url: &str) -> Result<Vec<u8>, IngestError> {
+    let url = url::Url::parse(url).map_err(|e| IngestError::Download(format!("PDF çekilemedi: {}", e)))?;
+    let mut response = crawler
+        .get_with_timeout(&url, HeaderMap::new(), Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
+        .await
+        .map_err(IngestError::Download)?;
+    if let Some(length) = response.content_length().filter(|length| *length as usize > MAX_PDF_BYTES) {
+        return Err(IngestError::TooLarge(length as usize));
+    }
+
+    // Content-Length güvenilir olmayabilir; sınır okurken de kontrol edilir
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| IngestError::Download(format!("PDF okunamadı: {}", e)))?
+    {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > MAX_PDF_BYTES {
+            return Err(IngestError::TooLarge(bytes.len()));
+        }
+    }
+    Ok(bytes)
+}
+
+// PDF ayrıştırma CPU'ya bağlı ve bozuk dosyalarda panikleyebilir; blocking thread'de çalışır
+pub async fn extract(bytes: Vec<u8>) -> Result<ExtractedPdf, IngestError> {
+    tokio::task::spawn_blocking(move || extract_blocking(&bytes))
+        .await
+        .map_err(|e| IngestError::Parse(format!("PDF ayrıştırıcı beklenmedik şekilde durdu: {}", e)))?
+}
+
+fn extract_blocking(bytes: &[u8]) -> Result<ExtractedPdf, IngestError> {
+    // Baştaki boşluk / BOM sonrası "%PDF-" imzası (dosyanın ilk 1024 baytı içinde)
+    let header = &bytes[..bytes.len().min(1024)];
+    if !header.windows(5).any(|window| window == b"%PDF-") {
+        return Err(IngestError::NotPdf);
+    }
+
+    let mut doc = Document::load_mem(bytes).map_err(|e| IngestError::Parse(e.to_string()))?;
+    if doc.is_encrypted() && doc.decrypt("").is_err() {
+        return Err(IngestError::Encrypted);
+    }
+
+    let mut raw = String::new();
+    pdf_extract::output_doc(&doc, &mut PlainTextOutput::new(&mut raw))
+        .map_err(|e| IngestError::Parse(e.to_string()))?;
+    let text = normalize_text(&raw);
+    if text.is_empty() {
+        return Err(IngestError::NoText);
+    }
+
+    Ok(ExtractedPdf {
+        text,
+        sayfa_sayisi: doc.get_pages().len() as i32,
+        dosya_boyutu_mb: (bytes.len() as f64 / (1024.0 * 1024.0) * 100.0).round() / 100.0,
+        title: info_title(&doc),
+    })
+}
+
+fn info_title(doc: &Document) -> Option<String> {
+    let (_, info) = doc.dereference(doc.trailer.get(b"Info").ok()?).ok()?;
+    let title = info.as_dict().ok()?.get(b"Title").ok()?;
+    let title = match title {
+        Object::String(..) => pdf_extract::decode_text_string(title).ok()?,
+        _ => return None,
+    };
+    let title = normalize_text(&title).replace('\n', " ");
+    Some(title).filter(|title| !title.is_empty())
+}
+
+// PDF'i okuyup belgeyi kaydeder. Çıkarılan metin, sayfa sayısı ve boyut gövdedeki
+// değerleri ezer; pdf_adi verilmezse PDF başlığı, o da yoksa dosya adı kullanılır.
+pub async fn ingest(
+    state: &AppState,
+    actor: &Actor,
+    source: &PdfSource,
+    metadata: DocumentInput,
+) -> Result<(String, DocumentWrite), ApiError> {
+    let bytes = source.read(state.fetcher.crawler()).await?;
+    let extracted = extract(bytes).await?;
+
+    let mut input = metadata;
+    if input.pdf_adi.as_deref().is_none_or(|pdf_adi| pdf_adi.trim().is_empty()) {
+        input.pdf_adi = extracted.title.clone().or_else(|| source.file_stem());
+    }
+    if let (None, PdfSource::Url(url)) = (&input.pdf_url, source) {
+        input.pdf_url = Some(url.clone());
+    }
+    input.sayfa_sayisi = Some(extracted.sayfa_sayisi);
+    input.dosya_boyutu_mb = Some(extracted.dosya_boyutu_mb);
+    input.icerik = Some(extracted.text);
+
+    admin_documents::create(state, actor, input).await
+}
+
+impl fmt::Display for IngestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IngestError::Io(detail) => write!(f, "PDF okunamadı: {}", detail),
+            IngestError::Download(detail) => write!(f, "{}", detail),
+            IngestError::TooLarge(size) => write!(f, "PDF çok büyük: {} bayt (sınır {})", size, MAX_PDF_BYTES),
+            IngestError::NotPdf => write!(f, "Dosya PDF değil"),
+            IngestError::Encrypted => write!(f, "PDF parola korumalı"),
+            IngestError::Parse(detail) => write!(f, "PDF ayrıştırılamadı: {}", detail),
+            IngestError::NoText => write!(f, "PDF'te çıkarılabilir metin yok"),
+        }
+    }
+}
+
+impl From<IngestError> for ApiError {
+    fn from(e: IngestError) -> Self {
+        match e {
+            IngestError::Io(_) | IngestError::Download(_) => ApiError::PdfUnavailable(e.to_string()),
+            IngestError::TooLarge(_) => ApiError::PdfTooLarge(MAX_PDF_BYTES),
+            IngestError::NoText => ApiError::PdfNoText,
+            IngestError::NotPdf | IngestError::Encrypted | IngestError::Parse(_) => ApiError::InvalidPdf(e.to_string()),
+        }
+    }
+}
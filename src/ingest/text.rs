@@ -0,0 +1,65 @@
+use unicode_normalization::UnicodeNormalization;
+
+// PDF'ten çıkarılan ham metni aramaya hazır hale getirir:
+//   - Unicode NFC (ayrışık "i̇", "ş" gibi karakterler tek karaktere birleşir)
+//   - tipografik bağlar (ﬁ, ﬂ, ...) harflerine açılır
+//   - Latin-1 olarak çözülmüş Türkçe harfler düzeltilir (ý -> ı, þ -> ş, ð -> ğ)
+//   - yumuşak tire ve sıfır genişlikli karakterler silinir, boşluk türleri tek boşluğa iner
+//   - satır sonunda tireyle bölünmüş kelimeler birleştirilir
+//   - en fazla bir boş satır (paragraf arası) bırakılır
+pub fn normalize_text(raw: &str) -> String {
+    let mut cleaned = String::with_capacity(raw.len());
+    for c in raw.nfc() {
+        match c {
+            'ﬀ' => cleaned.push_str("ff"),
+            'ﬁ' => cleaned.push_str("fi"),
+            'ﬂ' => cleaned.push_str("fl"),
+            'ﬃ' => cleaned.push_str("ffi"),
+            'ﬄ' => cleaned.push_str("ffl"),
+            'ﬅ' | 'ﬆ' => cleaned.push_str("st"),
+            // Windows-1254 kodlu fontların Latin-1 olarak okunması; bu harfler Türkçede kullanılmaz
+            'ý' => cleaned.push('ı'),
+            'Ý' => cleaned.push('İ'),
+            'þ' => cleaned.push('ş'),
+            'Þ' => cleaned.push('Ş'),
+            'ð' => cleaned.push('ğ'),
+            'Ð' => cleaned.push('Ğ'),
+            '\u{AD}' | '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' => {}
+            '\r' | '\u{C}' | '\u{2028}' | '\u{2029}' => cleaned.push('\n'),
+            c if c != '\n' && c.is_whitespace() => cleaned.push(' '),
+            c if c != '\n' && c.is_control() => {}
+            c => cleaned.push(c),
+        }
+    }
+
+    let mut text = String::with_capacity(cleaned.len());
+    let mut blank_line = false;
+    for line in cleaned.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            blank_line = !text.is_empty();
+            continue;
+        }
+
+        if !text.is_empty() {
+            if blank_line {
+                text.push_str("\n\n");
+            } else if joins_hyphenated(&text, &line) {
+                text.pop();
+            } else {
+                text.push('\n');
+            }
+        }
+        blank_line = false;
+        text.push_str(&line);
+    }
+    text
+}
+
+// "yönet-" + "melik" gibi satır sonu bölünmeleri: tireden önce harf, sonraki satır küçük harfle başlar
+fn joins_hyphenated(text: &str, next_line: &str) -> bool {
+    let mut tail = text.chars().rev();
+    tail.next() == Some('-')
+        && tail.next().is_some_and(char::is_alphabetic)
+        && next_line.chars().next().is_some_and(char::is_lowercase)
+}
@@ -1,6 +1,25 @@
+pub mod alerts;
+pub mod audit;
+pub mod auth;
 pub mod config;
+pub mod corpus;
+pub mod dates;
+pub mod error;
+pub mod feeds;
 pub mod handlers;
+pub mod harvester;
+pub mod indexnow;
+pub mod ingest;
+pub mod middleware;
 pub mod models;
+pub mod openapi;
+pub mod ratelimit;
+pub mod repository;
 pub mod routes;
+pub mod scraping;
+pub mod sitemap;
+pub mod statistics;
+pub mod stream;
 pub mod utils;
+pub mod webhooks;
 
@@ -1,12 +1,17 @@
-mod config;
-mod handlers;
-mod models;
-mod routes;
-mod utils;
-
 use actix_cors::Cors;
-use actix_web::{middleware::Logger, web, App, HttpServer};
-use config::{AppConfig, AppState};
+use actix_web::{middleware::{from_fn, Logger}, web, App, HttpServer};
+use api_mevzuatgpt::alerts::{self, Notifiers};
+use api_mevzuatgpt::audit;
+use api_mevzuatgpt::auth::cli::{self, KeyCommand};
+use api_mevzuatgpt::config::{AppConfig, AppState};
+use api_mevzuatgpt::harvester;
+use api_mevzuatgpt::indexnow;
+use api_mevzuatgpt::ingest::cli::{self as ingest_cli, IngestCommand};
+use api_mevzuatgpt::repository::Repositories;
+use api_mevzuatgpt::statistics;
+use api_mevzuatgpt::stream;
+use api_mevzuatgpt::webhooks;
+use api_mevzuatgpt::{middleware, routes};
 use std::io;
 
 #[actix_web::main]
@@ -22,19 +27,134 @@ async fn main() -> io::Result<()> {
     // Config yükle
     let config = AppConfig::from_env().expect("Config yüklenemedi");
     
-    // MongoDB bağlantısı
-    let app_state = AppState::new(&config)
-        .await
-        .expect("MongoDB bağlantısı kurulamadı");
-
     // Eğer --create-indexes parametresi verildiyse index'leri oluştur ve çık
     if create_indexes {
         log::info!("Index oluşturma modu aktif - MongoDB index'leri oluşturuluyor...");
-        AppState::ensure_indexes(&app_state.db).await;
+        let db = AppState::connect(&config)
+            .await
+            .expect("MongoDB bağlantısı kurulamadı");
+        AppState::ensure_indexes(&db).await;
         log::info!("Index oluşturma işlemi tamamlandı. Uygulama kapatılıyor...");
         return Ok(());
     }
 
+    // API anahtarı yönetimi (--create-api-key, --revoke-api-key, --list-api-keys)
+    if let Some(command) = KeyCommand::from_args(&args) {
+        let command = command.unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        });
+        let client = AppState::connect_client(&config)
+            .await
+            .expect("MongoDB bağlantısı kurulamadı");
+        let repos = Repositories::mongo(client, &config.mongodb_db_name);
+        if let Err(e) = cli::run(command, repos.api_keys.as_ref()).await {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // PDF alımı (--ingest-pdf <dosya|url> --kurum-id <id> ...)
+    if let Some(command) = IngestCommand::from_args(&args) {
+        let command = command.unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        });
+        let state = AppState::new(&config)
+            .await
+            .expect("MongoDB bağlantısı kurulamadı");
+        if let Err(e) = ingest_cli::run(command, &state).await {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // MongoDB bağlantısı
+    let app_state = AppState::new(&config)
+        .await
+        .expect("MongoDB bağlantısı kurulamadı");
+
+    // API dışındaki değişiklikler için denetim kaydı (change stream)
+    if config.audit_change_stream {
+        let db = AppState::connect(&config)
+            .await
+            .expect("MongoDB bağlantısı kurulamadı");
+        audit::stream::spawn(db, app_state.repos.audit.clone());
+    }
+
+    // Kurum duyuru sayfalarının periyodik hasadı
+    if let Some(interval) = config.announcement_harvest.interval {
+        harvester::spawn(
+            app_state.repos.clone(),
+            app_state.fetcher.clone(),
+            interval,
+            config.announcement_harvest.details,
+        );
+    }
+
+    // Yeni belge/duyuru taraması ve webhook teslimatları
+    if let Some(interval) = config.webhooks.interval {
+        webhooks::spawn(
+            app_state.repos.clone(),
+            app_state.corpora.clone(),
+            app_state.urls.clone(),
+            config.webhooks.clone(),
+            interval,
+        );
+    }
+
+    // Kayıtlı aramaların yeni eşleşmeleri ve bildirimleri
+    if let Some(interval) = config.saved_searches.interval {
+        alerts::spawn(
+            app_state.repos.clone(),
+            app_state.corpora.clone(),
+            app_state.urls.clone(),
+            Notifiers::new(&config.saved_searches),
+            interval,
+        );
+    }
+
+    // Yeni belge akışı (SSE): replica set'te change stream, değilse yoklama
+    if config.stream.change_stream || config.stream.poll_interval.is_some() {
+        let db = if config.stream.change_stream {
+            Some(AppState::connect(&config)
+                .await
+                .expect("MongoDB bağlantısı kurulamadı"))
+        } else {
+            None
+        };
+        stream::spawn(
+            app_state.document_stream.clone(),
+            db,
+            app_state.repos.clone(),
+            app_state.corpora.clone(),
+            app_state.urls.clone(),
+            config.stream.clone(),
+        );
+    }
+
+    // İstatistik özetinin periyodik yenilenmesi
+    statistics::spawn(
+        app_state.statistics.clone(),
+        app_state.repos.clone(),
+        app_state.corpora.clone(),
+        config.statistics.refresh_interval,
+    );
+
+    // Eklenen ve değişen belgelerin IndexNow ile arama motorlarına bildirilmesi
+    if let (Some(key), Some(interval)) = (config.indexnow.key.clone(), config.indexnow.interval) {
+        indexnow::spawn(
+            app_state.repos.clone(),
+            app_state.corpora.clone(),
+            app_state.urls.clone(),
+            config.indexnow.clone(),
+            key,
+            interval,
+        );
+    }
+
     log::info!("Server başlatılıyor: {}:{}", config.host, config.port);
 
     let app_state = web::Data::new(app_state);
@@ -44,16 +164,34 @@ async fn main() -> io::Result<()> {
         let cors = Cors::default()
             .allowed_origin("https://mevzuatgpt.org")
             .allowed_origin("https://www.mevzuatgpt.org")
-            .allowed_methods(vec!["GET", "POST", "OPTIONS"])
+            .allowed_methods(vec!["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"])
             .allowed_headers(vec![
                 actix_web::http::header::CONTENT_TYPE,
                 actix_web::http::header::ACCEPT,
                 actix_web::http::header::AUTHORIZATION,
+                actix_web::http::header::ACCEPT_LANGUAGE,
+                actix_web::http::header::HeaderName::from_static(middleware::REQUEST_ID_HEADER),
+                actix_web::http::header::HeaderName::from_static(middleware::ERROR_FORMAT_HEADER),
+                actix_web::http::header::HeaderName::from_static(middleware::API_KEY_HEADER),
+                actix_web::http::header::HeaderName::from_static("last-event-id"),
+            ])
+            .expose_headers(vec![
+                actix_web::http::header::HeaderName::from_static(middleware::REQUEST_ID_HEADER),
+                actix_web::http::header::HeaderName::from_static("x-total-count"),
+                actix_web::http::header::HeaderName::from_static(middleware::QUOTA_LIMIT_HEADER),
+                actix_web::http::header::HeaderName::from_static(middleware::QUOTA_REMAINING_HEADER),
+                actix_web::http::header::HeaderName::from_static(middleware::RATE_LIMIT_LIMIT_HEADER),
+                actix_web::http::header::HeaderName::from_static(middleware::RATE_LIMIT_REMAINING_HEADER),
+                actix_web::http::header::HeaderName::from_static(middleware::RATE_LIMIT_RESET_HEADER),
+                actix_web::http::header::RETRY_AFTER,
             ])
             .max_age(3600);
 
         App::new()
             .app_data(app_state.clone())
+            .wrap(from_fn(middleware::api_key_auth))
+            .wrap(from_fn(middleware::rate_limit))
+            .wrap(from_fn(middleware::request_context))
             .wrap(cors)
             .wrap(Logger::default())
             .configure(routes::configure_routes)
@@ -0,0 +1,259 @@
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_LANGUAGE, AUTHORIZATION, RETRY_AFTER},
+    middleware::Next,
+    web, Error, HttpMessage, ResponseError,
+};
+use crate::auth::{self, ApiKeyIdentity, Scope};
+use crate::config::AppState;
+use crate::ratelimit::{Decision, Subject};
+use crate::error::{ApiError, ErrorFormat, Language};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+pub const ERROR_FORMAT_HEADER: &str = "x-error-format";
+pub const API_KEY_HEADER: &str = "x-api-key";
+pub const QUOTA_LIMIT_HEADER: &str = "x-quota-limit";
+pub const QUOTA_REMAINING_HEADER: &str = "x-quota-remaining";
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "x-ratelimit-limit";
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+pub const RATE_LIMIT_RESET_HEADER: &str = "x-ratelimit-reset";
+
+// Handler'ların erişebilmesi için request extension'larına eklenen istek kimliği
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+// İstemcinin gönderdiği X-Request-Id makul görünüyorsa korunur, yoksa yenisi üretilir
+fn request_id_from(req: &ServiceRequest) -> String {
+    req.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| {
+            !value.is_empty()
+                && value.len() <= 128
+                && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+// Her isteğe request id atar ve ApiError cevaplarını istemcinin diline ve
+// hata formatına (X-Error-Format başlığı ya da API_ERROR_FORMAT) göre yeniden üretir
+pub async fn request_context(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let request_id = request_id_from(&req);
+    let lang = Language::from_accept_language(
+        req.headers().get(ACCEPT_LANGUAGE).and_then(|value| value.to_str().ok()),
+    );
+    let default_format = req
+        .app_data::<web::Data<AppState>>()
+        .map(|state| state.error_format)
+        .unwrap_or(ErrorFormat::Legacy);
+    let format = req
+        .headers()
+        .get(ERROR_FORMAT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(ErrorFormat::parse)
+        .unwrap_or(default_format);
+    req.extensions_mut().insert(RequestId(request_id.clone()));
+
+    let res = next.call(req).await?;
+
+    let rendered = res
+        .response()
+        .error()
+        .and_then(|e| e.as_error::<ApiError>())
+        .map(|api_error| {
+            if api_error.status_code().is_server_error() {
+                log::error!("[{}] {}", request_id, api_error);
+            }
+            api_error.render(format, lang, Some(&request_id))
+        });
+
+    // Yeniden üretilen cevap, iç middleware'lerin eklediği başlıkları (örn. Retry-After) korur
+    let mut res = match rendered {
+        Some(mut response) => {
+            for (name, value) in res.headers() {
+                if !response.headers().contains_key(name) {
+                    response.headers_mut().append(name.clone(), value.clone());
+                }
+            }
+            res.into_response(response.map_into_boxed_body())
+        }
+        None => res.map_into_boxed_body(),
+    };
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        res.headers_mut()
+            .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
+
+    Ok(res)
+}
+
+// Anahtar önce "Authorization: Bearer <anahtar>", yoksa X-API-Key başlığından okunur
+fn api_key_from(req: &ServiceRequest) -> Option<String> {
+    let bearer = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim());
+
+    bearer
+        .or_else(|| {
+            req.headers()
+                .get(API_KEY_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.trim())
+        })
+        .filter(|key| !key.is_empty())
+        .map(|key| key.to_string())
+}
+
+// Router'ın eşleştirdiği yol: yüzde kodları çözülmüştür ("/api/%61dmin" -> "/api/admin").
+// Scope ve rate limit grubu ham yoldan değil bundan seçilir
+pub fn route_path(req: &ServiceRequest) -> String {
+    req.match_info().as_str().to_string()
+}
+
+// API anahtarı doğrulaması, scope kontrolü ve günlük kota sayacı.
+// rate_limit'in içinde çalışır ki sınırı aşan istekler kotadan düşmesin.
+pub async fn api_key_auth(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let Some(state) = req.app_data::<web::Data<AppState>>().cloned() else {
+        return Ok(next.call(req).await?.map_into_boxed_body());
+    };
+
+    let key = api_key_from(&req);
+    let authenticated = match auth::authenticate(&state, key.as_deref(), &route_path(&req)).await {
+        Ok(authenticated) => authenticated,
+        Err(e) => return Ok(req.error_response(e)),
+    };
+
+    let Some(authenticated) = authenticated else {
+        return Ok(next.call(req).await?.map_into_boxed_body());
+    };
+
+    let quota = authenticated.identity.daily_quota;
+    req.extensions_mut().insert(authenticated.identity);
+    let mut res = next.call(req).await?.map_into_boxed_body();
+
+    if let Some(quota) = quota {
+        let remaining = quota.saturating_sub(authenticated.used_today);
+        let headers = res.headers_mut();
+        headers.insert(HeaderName::from_static(QUOTA_LIMIT_HEADER), HeaderValue::from(quota));
+        headers.insert(HeaderName::from_static(QUOTA_REMAINING_HEADER), HeaderValue::from(remaining));
+    }
+
+    Ok(res)
+}
+
+// /api/admin kapsamına sarılır: yol önekinden bağımsız olarak yönetim route'larına yalnızca
+// admin scope'lu, doğrulanmış anahtarlar ulaşır. api_key_auth'un içinde çalışır
+pub async fn require_admin(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let error = match req.extensions().get::<ApiKeyIdentity>() {
+        Some(identity) if identity.grants(Scope::Admin) => None,
+        Some(_) => Some(ApiError::InsufficientScope(Scope::Admin.as_str())),
+        None => Some(ApiError::MissingApiKey),
+    };
+    match error {
+        Some(error) => Ok(req.error_response(error)),
+        None => Ok(next.call(req).await?.map_into_boxed_body()),
+    }
+}
+
+fn rate_limit_headers(headers: &mut HeaderMap, decision: &Decision) {
+    headers.insert(HeaderName::from_static(RATE_LIMIT_LIMIT_HEADER), HeaderValue::from(decision.limit));
+    headers.insert(HeaderName::from_static(RATE_LIMIT_REMAINING_HEADER), HeaderValue::from(decision.remaining));
+    headers.insert(HeaderName::from_static(RATE_LIMIT_RESET_HEADER), HeaderValue::from(decision.reset));
+}
+
+fn client_ip(req: &ServiceRequest, trust_proxy: bool) -> String {
+    let info = req.connection_info();
+    let ip = if trust_proxy {
+        info.realip_remote_addr()
+    } else {
+        info.peer_addr()
+    };
+    ip.unwrap_or("unknown").to_string()
+}
+
+fn rate_limited(req: ServiceRequest, decision: &Decision) -> ServiceResponse<BoxBody> {
+    let mut res = req.error_response(ApiError::RateLimited(decision.retry_after));
+    res.headers_mut().insert(RETRY_AFTER, HeaderValue::from(decision.retry_after));
+    res
+}
+
+// Route grubu başına token bucket; anahtarlı istekler anahtar, diğerleri IP başına
+// sınırlanır. api_key_auth'un dışında çalışır: reddedilen istek anahtar aramasına ve günlük
+// kotaya ulaşmaz. Geçersiz anahtar denemeleri ayrıca IP başına sınırlanır.
+pub async fn rate_limit(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let Some(state) = req.app_data::<web::Data<AppState>>().cloned() else {
+        return Ok(next.call(req).await?.map_into_boxed_body());
+    };
+    let limiter = &state.rate_limiter;
+    if !limiter.config.enabled {
+        return Ok(next.call(req).await?.map_into_boxed_body());
+    }
+
+    let ip = client_ip(&req, limiter.config.trust_proxy);
+    let key = api_key_from(&req);
+
+    // Store'a ulaşılamazsa istek engellenmez
+    if key.is_some() {
+        match limiter.check_auth_failures(&ip).await {
+            Ok(Some(decision)) if !decision.allowed => {
+                let mut res = rate_limited(req, &decision);
+                rate_limit_headers(res.headers_mut(), &decision);
+                return Ok(res);
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Rate limit store hatası, istek sınırlanmadan geçiriliyor: {}", e),
+        }
+    }
+
+    let subject = match &key {
+        Some(key) => Subject::ApiKey(auth::hash_key(key)),
+        None => Subject::Ip(ip.clone()),
+    };
+    let decision = match limiter.check(&subject, &route_path(&req)).await {
+        Ok(decision) => decision,
+        Err(e) => {
+            log::warn!("Rate limit store hatası, istek sınırlanmadan geçiriliyor: {}", e);
+            None
+        }
+    };
+
+    let mut res = match &decision {
+        Some(decision) if !decision.allowed => rate_limited(req, decision),
+        _ => next.call(req).await?.map_into_boxed_body(),
+    };
+
+    let invalid_key = matches!(
+        res.response().error().and_then(|e| e.as_error::<ApiError>()),
+        Some(ApiError::InvalidApiKey)
+    );
+    if invalid_key {
+        if let Err(e) = limiter.record_auth_failure(&ip).await {
+            log::warn!("Geçersiz anahtar denemesi kaydedilemedi: {}", e);
+        }
+    }
+
+    if let Some(decision) = &decision {
+        rate_limit_headers(res.headers_mut(), decision);
+    }
+
+    Ok(res)
+}
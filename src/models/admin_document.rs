@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// Yönetim API'sinin belge gövdesi. POST ve PUT'ta verilmeyen alanlar varsayılan
+// değerlerini alır, PATCH'te mevcut değerler korunur.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DocumentInput {
+    pub pdf_adi: Option<String>,
+    // Kurumun ObjectId hex değeri; kurum kayıtlı olmalıdır
+    pub kurum_id: Option<String>,
+    pub belge_turu: Option<String>,
+    pub belge_durumu: Option<String>,
+    // YYYY-MM-DD
+    pub belge_yayin_tarihi: Option<String>,
+    pub yururluluk_tarihi: Option<String>,
+    // Virgülle ayrılmış etiketler
+    pub etiketler: Option<String>,
+    pub anahtar_kelimeler: Option<String>,
+    pub aciklama: Option<String>,
+    // Verilmezse pdf_adi'ndan üretilir
+    pub url_slug: Option<String>,
+    // aktif | pasif
+    pub status: Option<String>,
+    pub sayfa_sayisi: Option<i32>,
+    pub dosya_boyutu_mb: Option<f64>,
+    pub pdf_url: Option<String>,
+    // Belgenin tam metni; verilmezse mevcut içerik değişmez
+    pub icerik: Option<String>,
+}
+
+// PDF alım isteği: PDF URL'den indirilir; metin, sayfa sayısı ve boyut PDF'ten
+// hesaplanır. pdf_adi verilmezse PDF başlığı ya da dosya adı kullanılır.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IngestDocumentInput {
+    // http(s) PDF adresi
+    pub url: String,
+    #[serde(default)]
+    pub metadata: DocumentInput,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DeletedDocument {
+    pub id: String,
+    pub url_slug: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DeleteDocumentResponse {
+    pub success: bool,
+    pub data: DeletedDocument,
+    pub message: String,
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::models::kurum::KurumResponse;
+use crate::models::link::LinkResponse;
+use crate::scraping::ScrapeRules;
+
+// Yönetim API'sinin kurum, duyuru linki ve faydalı link gövdeleri. POST ve PUT'ta
+// verilmeyen alanlar boş kalır, PATCH'te mevcut değerler korunur.
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InstitutionInput {
+    pub kurum_adi: Option<String>,
+    // http(s) logo adresi
+    pub kurum_logo: Option<String>,
+    pub aciklama: Option<String>,
+    // DETSIS (Devlet Teşkilatı Merkezi Kayıt Sistemi) numarası, 8 hane
+    pub detsis: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AnnouncementSourceInput {
+    // Kurumun ObjectId hex değeri; her kurumun tek duyuru linki olabilir
+    pub kurum_id: Option<String>,
+    pub duyuru_linki: Option<String>,
+    pub baslik: Option<String>,
+    // Duyuru sayfasının CSS seçicileriyle okunma kuralları; verilmezse domain'e göre hazır desen
+    pub scraper: Option<ScrapeRules>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LinkInput {
+    pub kurum_id: Option<String>,
+    pub baslik: Option<String>,
+    pub aciklama: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct InstitutionDetailResponse {
+    pub success: bool,
+    pub data: KurumResponse,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AnnouncementSourceData {
+    pub id: String,
+    pub kurum_id: String,
+    pub duyuru_linki: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baslik: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scraper: Option<ScrapeRules>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AnnouncementSourceDetailResponse {
+    pub success: bool,
+    pub data: AnnouncementSourceData,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct LinkDetailResponse {
+    pub success: bool,
+    pub data: LinkResponse,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DeletedRecord {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DeleteRecordResponse {
+    pub success: bool,
+    pub data: DeletedRecord,
+    pub message: String,
+}
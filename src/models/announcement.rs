@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use bson::oid::ObjectId;
 
 #[allow(dead_code)]
@@ -13,7 +14,7 @@ pub struct KurumDuyuru {
     pub baslik: Option<String>, // Opsiyonel: Eğer koleksiyonda varsa kullanılır
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AnnouncementResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -22,7 +23,7 @@ pub struct AnnouncementResponse {
     pub tarih: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AnnouncementsListResponse {
     pub success: bool,
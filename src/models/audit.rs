@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use crate::repository::{AuditChange, AuditRecord};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AuditChangeResponse {
+    pub field: String,
+    // Alan yoksa (oluşturma/silme ya da pre-image'sız change stream olayı) null
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AuditEntryResponse {
+    pub id: String,
+    // RFC 3339, UTC
+    pub timestamp: String,
+    // API anahtarı prefix'i ya da CLI kullanıcısı; change stream kayıtlarında yok
+    pub actor: Option<String>,
+    // api, cli, change_stream
+    pub source: String,
+    // create, update, delete
+    pub action: String,
+    pub collection: String,
+    pub target_id: String,
+    pub changes: Vec<AuditChangeResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AuditListResponse {
+    pub success: bool,
+    pub data: Vec<AuditEntryResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    pub message: String,
+}
+
+impl From<AuditChange> for AuditChangeResponse {
+    fn from(change: AuditChange) -> Self {
+        AuditChangeResponse {
+            field: change.field,
+            before: change.before.map(|value| value.into_relaxed_extjson()),
+            after: change.after.map(|value| value.into_relaxed_extjson()),
+        }
+    }
+}
+
+impl From<AuditRecord> for AuditEntryResponse {
+    fn from(record: AuditRecord) -> Self {
+        AuditEntryResponse {
+            id: record.id,
+            timestamp: record.timestamp,
+            actor: record.actor,
+            source: record.source,
+            action: record.action,
+            collection: record.collection,
+            target_id: record.target_id,
+            changes: record.changes.into_iter().map(AuditChangeResponse::from).collect(),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CorpusInfo {
+    pub key: String,
+    pub title: String,
+    pub url_prefix: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CorporaListResponse {
+    pub success: bool,
+    pub data: Vec<CorpusInfo>,
+    pub count: usize,
+    pub message: String,
+}
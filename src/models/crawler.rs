@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::scraping::HostStatus;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CrawlerHostResponse {
+    // host ya da host:port
+    pub host: String,
+    // closed, open (istek gönderilmiyor), half_open (sıradaki istek host'u yoklar)
+    pub state: String,
+    pub consecutive_failures: u32,
+    // RFC 3339; devre hiç açılmadıysa ya da kapandıysa null
+    pub open_until: Option<String>,
+    // Şu anda süren istek sayısı
+    pub in_flight: usize,
+    // Yeniden denemeler dahil gönderilen istekler ve başarısız sonuçlanan çekimler
+    pub requests: u64,
+    pub failures: u64,
+    pub last_error: Option<String>,
+    pub last_success_at: Option<String>,
+    pub last_failure_at: Option<String>,
+    pub robots_fetched_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CrawlerHostListResponse {
+    pub success: bool,
+    pub data: Vec<CrawlerHostResponse>,
+    pub count: usize,
+    pub message: String,
+}
+
+impl From<HostStatus> for CrawlerHostResponse {
+    fn from(status: HostStatus) -> Self {
+        CrawlerHostResponse {
+            host: status.host,
+            state: status.state.as_str().to_string(),
+            consecutive_failures: status.consecutive_failures,
+            open_until: status.open_until.map(|at| at.to_rfc3339()),
+            in_flight: status.in_flight,
+            requests: status.requests,
+            failures: status.failures,
+            last_error: status.last_error,
+            last_success_at: status.last_success_at.map(|at| at.to_rfc3339()),
+            last_failure_at: status.last_failure_at.map(|at| at.to_rfc3339()),
+            robots_fetched_at: status.robots_fetched_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
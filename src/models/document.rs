@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use bson::oid::ObjectId;
 
 #[allow(dead_code)]
@@ -25,10 +26,12 @@ pub struct Document {
     pub pdf_url: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct DocumentResponse {
     pub url_slug: String,
+    // Belge sayfasının kanonik adresi (PUBLIC_BASE_URL ve corpus şablonundan)
+    pub url: String,
     pub pdf_adi: String,
     pub aciklama: String,
     pub belge_yayin_tarihi: String,
@@ -46,7 +49,7 @@ pub struct DocumentResponse {
     pub sayfa_sayisi: Option<i32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct DocumentsListResponse {
     pub success: bool,
@@ -56,7 +59,7 @@ pub struct DocumentsListResponse {
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct DocumentMetadata {
     pub id: String,
@@ -70,6 +73,8 @@ pub struct DocumentMetadata {
     pub belge_durumu: String,
     pub aciklama: String,
     pub url_slug: String,
+    // Belge sayfasının kanonik adresi
+    pub url: String,
     pub belge_turu: String,
     pub anahtar_kelimeler: String,
     pub status: String,
@@ -80,16 +85,17 @@ pub struct DocumentMetadata {
     pub karar_tarihi: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct DocumentContent {
     pub id: String,
     pub metadata_id: String,
     pub icerik: String,
-    pub olusturulma_tarihi: String,
+    // RFC 3339; kayıttaki değer okunamıyorsa null
+    pub olusturulma_tarihi: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct DocumentDetailData {
     pub metadata: DocumentMetadata,
@@ -99,11 +105,10 @@ pub struct DocumentDetailData {
     pub kurum_aciklama: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct DocumentDetailResponse {
     pub success: bool,
     pub data: DocumentDetailData,
     pub message: String,
 }
-
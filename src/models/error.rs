@@ -0,0 +1,43 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use serde_json::Value;
+
+// v2 hata zarfı: { "success": false, "error": { "code", "message", "request_id" } }
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorEnvelope {
+    pub success: bool,
+    pub error: ErrorDetail,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorDetail {
+    pub code: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    // Yalnızca VALIDATION_FAILED hatalarında doldurulur
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldErrorDetail>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FieldErrorDetail {
+    pub field: &'static str,
+    pub code: &'static str,
+    pub message: String,
+}
+
+// Eski istemciler için düz format; message ve error alanlarının ikisi de doldurulur
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LegacyErrorEnvelope {
+    pub success: bool,
+    pub data: Value,
+    pub count: u64,
+    pub message: String,
+    pub error: String,
+    pub code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldErrorDetail>,
+}
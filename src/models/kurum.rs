@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use bson::oid::ObjectId;
 
 #[allow(dead_code)]
@@ -13,7 +14,7 @@ pub struct Kurum {
     pub detsis: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct KurumResponse {
     pub kurum_id: String,
@@ -24,7 +25,7 @@ pub struct KurumResponse {
     pub detsis: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct InstitutionsListResponse {
     pub success: bool,
@@ -34,13 +35,13 @@ pub struct InstitutionsListResponse {
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct KurumBySlugData {
     pub kurum_id: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct KurumBySlugResponse {
     pub success: bool,
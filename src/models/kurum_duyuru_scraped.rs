@@ -1,18 +1,79 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::repository::{AttachmentRecord, ScrapedAnnouncementRecord};
 
-#[derive(Debug, Serialize, Deserialize)]
+// Listede gösterilen özetin uzunluğu (karakter)
+const OZET_CHARS: usize = 280;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct DuyuruItem {
     pub baslik: String,
     pub link: String,
-    pub tarih: String,
+    // DD.MM.YYYY; sayfada okunabilir bir tarih yoksa null
+    pub tarih: Option<String>,
+}
+
+// Hasatla kaydedilmiş duyuru
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct KurumDuyuruItem {
+    pub baslik: String,
+    pub link: String,
+    pub tarih: Option<String>,
+    // Duyurunun ilk görüldüğü hasat zamanı (RFC 3339, UTC); since filtresi buna uygulanır
+    pub first_seen_at: String,
+    // Detay sayfası metninin başı; detay çekilmediyse null
+    pub ozet: Option<String>,
+    // Duyuru sayfasındaki PDF/DOCX ekleri
+    pub ekler: Vec<DuyuruEki>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DuyuruEki {
+    pub url: String,
+    pub baslik: String,
+    // pdf, docx, doc
+    pub tur: String,
+}
+
+// Yönetim API'si: eki bulunan duyuru ve eklerin belge alımına aday olup olmadığı
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AttachmentCandidateResponse {
+    pub url: String,
+    pub baslik: String,
+    pub tur: String,
+    // pdf_url'si bu ek olan belge; null ise ek henüz alınmamıştır
+    pub belge_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AnnouncementAttachmentsResponse {
+    pub duyuru_id: String,
+    pub kurum_id: String,
+    pub baslik: String,
+    pub link: String,
+    pub tarih: Option<String>,
+    pub ekler: Vec<AttachmentCandidateResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AnnouncementAttachmentListResponse {
+    pub success: bool,
+    pub data: Vec<AnnouncementAttachmentsResponse>,
+    pub count: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct KurumDuyuruScrapedResponse {
     pub success: bool,
-    pub data: Vec<DuyuruItem>,
+    pub data: Vec<KurumDuyuruItem>,
     pub count: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
@@ -20,3 +81,34 @@ pub struct KurumDuyuruScrapedResponse {
     pub error: Option<String>,
 }
 
+// Metnin başı; uzun metin kelime sınırına bakılmadan kesilip "…" ile biter
+pub(crate) fn ozet(icerik: &str) -> String {
+    let icerik = icerik.split_whitespace().collect::<Vec<_>>().join(" ");
+    match icerik.char_indices().nth(OZET_CHARS) {
+        Some((index, _)) => format!("{}…", icerik[..index].trim_end()),
+        None => icerik,
+    }
+}
+
+impl From<AttachmentRecord> for DuyuruEki {
+    fn from(ek: AttachmentRecord) -> Self {
+        DuyuruEki {
+            url: ek.url,
+            baslik: ek.baslik,
+            tur: ek.tur,
+        }
+    }
+}
+
+impl From<ScrapedAnnouncementRecord> for KurumDuyuruItem {
+    fn from(record: ScrapedAnnouncementRecord) -> Self {
+        KurumDuyuruItem {
+            ozet: record.icerik.as_deref().map(ozet),
+            ekler: record.ekler.into_iter().map(DuyuruEki::from).collect(),
+            baslik: record.baslik,
+            link: record.link,
+            tarih: record.tarih,
+            first_seen_at: record.first_seen_at,
+        }
+    }
+}
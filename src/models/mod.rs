@@ -13,6 +13,14 @@ pub mod statistics;
 pub mod kurum_duyuru_scraped;
 pub mod search;
 pub mod sitemap;
+pub mod corpus;
+pub mod error;
+pub mod admin_document;
+pub mod admin_institution;
+pub mod audit;
+pub mod crawler;
+pub mod webhook;
+pub mod saved_search;
 
 #[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize)]
@@ -41,3 +49,4 @@ impl<T> ApiResponse<T> {
     }
 }
 
+pub mod stream;
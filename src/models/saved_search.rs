@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::repository::{AlertHit, AlertRecord, SavedSearchRecord};
+
+// Kayıtlı aramalar ve gelen kutusu bildirimleri. POST'ta verilmeyen alanlar varsayılan değeri
+// alır (mevzuat corpus'u, inbox kanalı, aktif); PATCH'te mevcut değerler korunur.
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SavedSearchInput {
+    pub ad: Option<String>,
+    // /api/v1/search ile aynı sorgu söz dizimi
+    pub q: Option<String>,
+    // Corpus anahtarı (mevzuat, yargitay, ...)
+    pub corpus: Option<String>,
+    // Kurumun ObjectId hex değeri; boş metin filtreyi kaldırır
+    pub kurum_id: Option<String>,
+    // Boş metin filtreyi kaldırır
+    pub belge_turu: Option<String>,
+    // inbox, webhook, email
+    pub kanal: Option<String>,
+    // webhook için http(s) adresi, email için e-posta adresi
+    pub hedef: Option<String>,
+    pub aktif: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SavedSearchResponse {
+    pub id: String,
+    pub ad: String,
+    pub q: String,
+    pub corpus: String,
+    pub kurum_id: Option<String>,
+    pub belge_turu: Option<String>,
+    pub kanal: String,
+    pub hedef: Option<String>,
+    pub aktif: bool,
+    // Webhook kanalında imzalama anahtarı; yalnızca oluşturmada bir kez döner
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub last_run_at: Option<String>,
+    // Son çalışmada sorgu çalıştırılamadıysa nedeni
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SavedSearchDetailResponse {
+    pub success: bool,
+    pub data: SavedSearchResponse,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SavedSearchListResponse {
+    pub success: bool,
+    pub data: Vec<SavedSearchResponse>,
+    pub count: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AlertHitResponse {
+    pub id: String,
+    pub pdf_adi: String,
+    pub url_slug: String,
+    pub url: String,
+    pub kurum_adi: String,
+    pub belge_turu: String,
+    pub content_preview: String,
+    pub olusturulma_tarihi: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AlertResponse {
+    pub id: String,
+    pub saved_search_id: String,
+    pub ad: String,
+    pub q: String,
+    // Yeni eşleşmeler, en yeni önce
+    pub hits: Vec<AlertHitResponse>,
+    pub kanal: String,
+    // delivered, failed; inbox kanalında null
+    pub notification_status: Option<String>,
+    pub notification_error: Option<String>,
+    pub created_at: String,
+    pub read_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AlertListResponse {
+    pub success: bool,
+    pub data: Vec<AlertResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AlertDetailResponse {
+    pub success: bool,
+    pub data: AlertResponse,
+    pub message: String,
+}
+
+impl From<SavedSearchRecord> for SavedSearchResponse {
+    fn from(record: SavedSearchRecord) -> Self {
+        SavedSearchResponse {
+            id: record.id,
+            ad: record.ad,
+            q: record.q,
+            corpus: record.corpus,
+            kurum_id: record.kurum_id,
+            belge_turu: record.belge_turu,
+            kanal: record.kanal,
+            hedef: record.hedef,
+            aktif: record.aktif,
+            secret: None,
+            last_run_at: record.last_run_at,
+            last_error: record.last_error,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+impl From<AlertHit> for AlertHitResponse {
+    fn from(hit: AlertHit) -> Self {
+        AlertHitResponse {
+            id: hit.id,
+            pdf_adi: hit.pdf_adi,
+            url_slug: hit.url_slug,
+            url: hit.url,
+            kurum_adi: hit.kurum_adi,
+            belge_turu: hit.belge_turu,
+            content_preview: hit.content_preview,
+            olusturulma_tarihi: hit.olusturulma_tarihi,
+        }
+    }
+}
+
+impl From<AlertRecord> for AlertResponse {
+    fn from(record: AlertRecord) -> Self {
+        AlertResponse {
+            id: record.id,
+            saved_search_id: record.saved_search_id,
+            ad: record.ad,
+            q: record.q,
+            hits: record.hits.into_iter().map(AlertHitResponse::from).collect(),
+            kanal: record.kanal,
+            notification_status: record.notification_status,
+            notification_error: record.notification_error,
+            created_at: record.created_at,
+            read_at: record.read_at,
+        }
+    }
+}
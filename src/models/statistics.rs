@@ -1,21 +1,25 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct BelgeTuruCount {
     pub belge_turu: String,
     pub count: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct StatisticsData {
     pub total_kurumlar: u64,
     pub total_belgeler: u64,
     pub belge_turu_istatistik: Vec<BelgeTuruCount>,
+    // Tüm corpus'ların (yargitay dahil) belge sayıları
+    pub corpus_istatistik: Vec<CorpusCount>,
+    pub hesaplanma_tarihi: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct StatisticsResponse {
     pub success: bool,
@@ -27,3 +31,59 @@ pub struct StatisticsResponse {
     pub error: Option<String>,
 }
 
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CorpusCount {
+    pub corpus: String,
+    pub title: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MonthCount {
+    // YYYY-MM
+    pub ay: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TimeseriesData {
+    pub aylar: Vec<MonthCount>,
+    // Aralıktaki belge sayısı (aylar toplamı)
+    pub total_belgeler: u64,
+    // Yayın tarihi olmayan ya da okunamayan belgeler; aralıktan bağımsız
+    pub tarihsiz_belgeler: u64,
+    pub hesaplanma_tarihi: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TimeseriesResponse {
+    pub success: bool,
+    pub data: TimeseriesData,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct InstitutionStatistics {
+    pub kurum_id: String,
+    pub kurum_adi: String,
+    pub total_belgeler: u64,
+    pub corpus_istatistik: Vec<CorpusCount>,
+    pub belge_turu_istatistik: Vec<BelgeTuruCount>,
+    pub aylar: Vec<MonthCount>,
+    pub tarihsiz_belgeler: u64,
+    pub hesaplanma_tarihi: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct InstitutionStatisticsResponse {
+    pub success: bool,
+    pub data: InstitutionStatistics,
+    pub message: String,
+}
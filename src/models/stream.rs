@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// /api/v1/stream/documents olaylarının data alanı (JSON)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DocumentEvent {
+    // Corpus anahtarı (mevzuat, yargitay, ...)
+    pub corpus: String,
+    pub id: String,
+    pub url_slug: String,
+    pub url: String,
+    pub pdf_adi: String,
+    pub kurum_id: String,
+    pub kurum_adi: String,
+    pub belge_turu: String,
+    pub olusturulma_tarihi: String,
+}
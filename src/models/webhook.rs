@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use crate::repository::{DeliveryRecord, WebhookFilters, WebhookRecord};
+
+// Webhook abonelikleri ve teslimat kayıtları. POST'ta verilmeyen alanlar varsayılan değeri
+// alır (tüm olaylar, filtre yok, aktif); PATCH'te mevcut değerler korunur.
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookFiltersInput {
+    // Kurumun ObjectId hex değeri
+    pub kurum_id: Option<String>,
+    // Yalnızca belgeler için; verilirse duyurular gönderilmez
+    pub belge_turu: Option<String>,
+    // Corpus anahtarı (mevzuat, yargitay, ...); verilirse duyurular gönderilmez
+    pub corpus: Option<String>,
+    // Herhangi biri başlıkta, açıklamada, etiketlerde ya da duyuru metninde geçmelidir
+    pub keywords: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookInput {
+    // Teslimatların POST edileceği http(s) adresi
+    pub url: Option<String>,
+    // document.created, announcement.created; verilmezse ikisi de
+    pub events: Option<Vec<String>>,
+    // PATCH'te verilirse filtrelerin tamamı değişir
+    pub filters: Option<WebhookFiltersInput>,
+    pub aktif: Option<bool>,
+    pub aciklama: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WebhookFiltersResponse {
+    pub kurum_id: Option<String>,
+    pub belge_turu: Option<String>,
+    pub corpus: Option<String>,
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WebhookResponse {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub filters: WebhookFiltersResponse,
+    pub aktif: bool,
+    pub aciklama: String,
+    // İmzalama anahtarı yalnızca oluşturmada bir kez döner
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WebhookDetailResponse {
+    pub success: bool,
+    pub data: WebhookResponse,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WebhookListResponse {
+    pub success: bool,
+    pub data: Vec<WebhookResponse>,
+    pub count: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DeliveryResponse {
+    pub id: String,
+    pub webhook_id: String,
+    // Aynı olayın tüm teslimatlarında (tekrar gönderimler dahil) aynıdır
+    pub event_id: String,
+    pub event: String,
+    // pending, delivered, failed
+    pub status: String,
+    pub attempts: u32,
+    pub next_attempt_at: Option<String>,
+    pub last_attempt_at: Option<String>,
+    // Son denemede alınan HTTP kodu; bağlantı kurulamadıysa null
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub delivered_at: Option<String>,
+    // Tekrar gönderimse asıl teslimatın id'si
+    pub replay_of: Option<String>,
+    // Gönderilen (imzalanan) JSON gövdesi
+    pub payload: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DeliveryListResponse {
+    pub success: bool,
+    pub data: Vec<DeliveryResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DeliveryDetailResponse {
+    pub success: bool,
+    pub data: DeliveryResponse,
+    pub message: String,
+}
+
+impl From<WebhookFilters> for WebhookFiltersResponse {
+    fn from(filters: WebhookFilters) -> Self {
+        WebhookFiltersResponse {
+            kurum_id: filters.kurum_id,
+            belge_turu: filters.belge_turu,
+            corpus: filters.corpus,
+            keywords: filters.keywords,
+        }
+    }
+}
+
+impl From<WebhookRecord> for WebhookResponse {
+    fn from(record: WebhookRecord) -> Self {
+        WebhookResponse {
+            id: record.id,
+            url: record.url,
+            events: record.events,
+            filters: record.filters.into(),
+            aktif: record.aktif,
+            aciklama: record.aciklama,
+            secret: None,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+impl From<DeliveryRecord> for DeliveryResponse {
+    fn from(record: DeliveryRecord) -> Self {
+        DeliveryResponse {
+            payload: serde_json::from_str(&record.payload).unwrap_or(Value::String(record.payload)),
+            id: record.id,
+            webhook_id: record.webhook_id,
+            event_id: record.event_id,
+            event: record.event,
+            status: record.status,
+            attempts: record.attempts,
+            next_attempt_at: record.next_attempt_at,
+            last_attempt_at: record.last_attempt_at,
+            response_status: record.response_status,
+            error: record.error,
+            created_at: record.created_at,
+            delivered_at: record.delivered_at,
+            replay_of: record.replay_of,
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+use crate::handlers;
+use crate::models;
+
+// OpenAPI 3 dokümanı handler'lardaki #[utoipa::path] tanımlarından üretilir.
+// Yeni bir route eklendiğinde handler'ı paths(...) listesine eklemeyi unutmayın;
+// tests/openapi.rs eksik route'ları yakalar.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "MevzuatGPT API",
+        description = "Mevzuat, kurum ve Yargıtay kararları için okuma ve yönetim API'si"
+    ),
+    paths(
+        handlers::health::health_check,
+        handlers::kurum::get_kurumlar,
+        handlers::kurum::get_kurum_by_slug,
+        handlers::document::get_documents,
+        handlers::document::get_document_filters,
+        handlers::document::get_document_by_slug,
+        handlers::announcement::get_announcements,
+        handlers::link::get_links,
+        handlers::regulation::get_recent_regulations,
+        handlers::regulation_yargitay::get_recent_regulations_yargitay,
+        handlers::statistics::get_statistics,
+        handlers::statistics::get_statistics_timeseries,
+        handlers::statistics::get_institution_statistics,
+        handlers::kurum_duyuru::get_kurum_duyuru,
+        handlers::search::search,
+        handlers::saved_search::create_saved_search,
+        handlers::saved_search::list_saved_searches,
+        handlers::saved_search::get_saved_search,
+        handlers::saved_search::update_saved_search,
+        handlers::saved_search::delete_saved_search,
+        handlers::saved_search::list_alerts,
+        handlers::saved_search::mark_alert_read,
+        handlers::search_yargitay::search_v2_yargitay,
+        handlers::documents_yargitay::get_documents_yargitay,
+        handlers::documents_yargitay::get_document_by_slug_yargitay,
+        handlers::sitemap::get_sitemap_institutions,
+        handlers::sitemap::get_sitemap_all_documents,
+        handlers::sitemap::get_sitemap_all_documents_yargitay,
+        handlers::sitemap::get_sitemap_xml,
+        handlers::sitemap::get_child_sitemap,
+        handlers::sitemap::get_robots_txt,
+        handlers::sitemap::get_indexnow_key,
+        handlers::corpus::list_corpora,
+        handlers::corpus::get_corpus_documents,
+        handlers::corpus::get_corpus_document,
+        handlers::corpus::search_in_corpus,
+        handlers::corpus::get_corpus_recent,
+        handlers::corpus::get_corpus_sitemap,
+        handlers::admin_documents::create_document,
+        handlers::admin_documents::ingest_document,
+        handlers::admin_documents::replace_document,
+        handlers::admin_documents::update_document,
+        handlers::admin_documents::delete_document,
+        handlers::admin_institutions::create_institution,
+        handlers::admin_institutions::replace_institution,
+        handlers::admin_institutions::update_institution,
+        handlers::admin_institutions::delete_institution,
+        handlers::admin_announcement_sources::create_announcement_source,
+        handlers::admin_announcement_sources::replace_announcement_source,
+        handlers::admin_announcement_sources::update_announcement_source,
+        handlers::admin_announcement_sources::delete_announcement_source,
+        handlers::admin_links::create_link,
+        handlers::admin_links::replace_link,
+        handlers::admin_links::update_link,
+        handlers::admin_links::delete_link,
+        handlers::admin_audit::list_audit,
+        handlers::admin_audit::export_audit,
+        handlers::admin_crawler::list_hosts,
+        handlers::admin_announcement_attachments::list_attachments,
+        handlers::admin_webhooks::create_webhook,
+        handlers::admin_webhooks::list_webhooks,
+        handlers::admin_webhooks::get_webhook,
+        handlers::admin_webhooks::update_webhook,
+        handlers::admin_webhooks::delete_webhook,
+        handlers::admin_webhooks::list_deliveries,
+        handlers::admin_webhooks::replay_delivery,
+        handlers::feed::get_regulations_feed,
+        handlers::feed::get_yargitay_feed,
+        handlers::feed::get_announcements_feed,
+        handlers::feed::get_kurum_feed,
+        handlers::feed::get_kurum_announcements_feed,
+        handlers::stream::stream_documents,
+    ),
+    components(schemas(
+        models::error::ErrorEnvelope,
+        models::error::ErrorDetail,
+        models::error::LegacyErrorEnvelope,
+        models::error::FieldErrorDetail,
+    )),
+    modifiers(&ApiKeySecurity),
+    tags(
+        (name = "sistem", description = "Sağlık kontrolü ve istatistikler"),
+        (name = "kurumlar", description = "Kurumlar ve kurum linkleri"),
+        (name = "belgeler", description = "Mevzuat belgeleri"),
+        (name = "yargitay", description = "Yargıtay kararları (v2)"),
+        (name = "arama", description = "Tam metin arama"),
+        (name = "duyurular", description = "Kurum duyuruları"),
+        (name = "sitemap", description = "Sitemap verileri"),
+        (name = "beslemeler", description = "RSS 2.0 ve Atom beslemeleri"),
+        (name = "corpora", description = "Corpus registry'sinden üretilen genel route'lar"),
+        (name = "yonetim", description = "Yönetim API'si (admin scope'lu API anahtarı gerekir)"),
+    )
+)]
+pub struct ApiDoc;
+
+// API anahtarı "Authorization: Bearer" ya da X-API-Key ile gönderilebilir. Anahtar
+// zorunluluğu sunucu ayarına (API_AUTH_REQUIRED) bağlı olduğundan anonim erişim de listelenir.
+struct ApiKeySecurity;
+
+impl Modify for ApiKeySecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
+        );
+
+        openapi.security = Some(vec![
+            SecurityRequirement::default(),
+            SecurityRequirement::new("bearer", Vec::<String>::new()),
+            SecurityRequirement::new("api_key", Vec::<String>::new()),
+        ]);
+    }
+}
+
+pub const SPEC_PATH: &str = "/api/openapi.json";
+pub const DOCS_PATH: &str = "/api/docs";
+
+// /api/openapi.json ve gömülü Swagger UI (/api/docs/)
+pub fn service() -> SwaggerUi {
+    SwaggerUi::new(format!("{}/{{_:.*}}", DOCS_PATH)).url(SPEC_PATH, ApiDoc::openapi())
+}
@@ -0,0 +1,243 @@
+pub mod store;
+
+use std::env;
+use std::sync::Arc;
+use crate::auth::Scope;
+use crate::repository::RepositoryResult;
+
+pub use store::{BucketState, LocalStore, MongoStore, RateLimitStore};
+
+// Token bucket kuralı: dakikada dolan token sayısı ve bucket kapasitesi (burst)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rule {
+    pub per_minute: u32,
+    pub burst: u32,
+}
+
+impl Rule {
+    pub const fn new(per_minute: u32, burst: u32) -> Self {
+        Rule { per_minute, burst }
+    }
+
+    // "120/30" -> dakikada 120, burst 30
+    pub fn parse(value: &str) -> Option<Self> {
+        let (per_minute, burst) = value.trim().split_once('/')?;
+        let rule = Rule::new(per_minute.trim().parse().ok()?, burst.trim().parse().ok()?);
+        (rule.per_minute > 0 && rule.burst > 0).then_some(rule)
+    }
+
+    pub fn refill_per_ms(&self) -> f64 {
+        self.per_minute as f64 / 60_000.0
+    }
+}
+
+// Route grubu başına kurallar; gruplar router'ın çözdüğü yoldan auth::Scope::for_path ile belirlenir
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupRules {
+    pub read: Rule,
+    pub search: Rule,
+    pub scrape: Rule,
+}
+
+impl GroupRules {
+    pub fn get(&self, group: Scope) -> Rule {
+        match group {
+            Scope::Search => self.search,
+            Scope::Scrape => self.scrape,
+            Scope::Read | Scope::Admin => self.read,
+        }
+    }
+
+    // "read=300/60,search=30/10,scrape=6/3"; verilmeyen gruplar varsayılanı korur
+    fn apply(&mut self, value: &str) {
+        for part in value.split(',').filter(|part| !part.trim().is_empty()) {
+            let parsed = part
+                .split_once('=')
+                .and_then(|(group, rule)| Some((Scope::parse(group)?, Rule::parse(rule)?)));
+            match parsed {
+                Some((Scope::Search, rule)) => self.search = rule,
+                Some((Scope::Scrape, rule)) => self.scrape = rule,
+                Some((_, rule)) => self.read = rule,
+                None => log::warn!("Geçersiz rate limit kuralı atlandı: {}", part.trim()),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreKind {
+    // Süreç içi bucket'lar (tek instance)
+    Memory,
+    // Birden fazla instance için MongoDB'de paylaşılan bucket'lar
+    MongoDb,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    // Nginx gibi bir proxy arkasında istemci IP'si X-Forwarded-For'dan okunur
+    pub trust_proxy: bool,
+    pub store: StoreKind,
+    // Anahtarsız istekler IP başına, anahtarlı istekler anahtar başına sınırlanır
+    pub ip_rules: GroupRules,
+    pub key_rules: GroupRules,
+    // Geçersiz anahtar denemeleri IP başına bu kuralla sınırlanır
+    pub auth_failures: Rule,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        RateLimitConfig {
+            enabled: true,
+            trust_proxy: false,
+            store: StoreKind::Memory,
+            ip_rules: GroupRules {
+                read: Rule::new(300, 60),
+                search: Rule::new(30, 10),
+                scrape: Rule::new(6, 3),
+            },
+            key_rules: GroupRules {
+                read: Rule::new(1200, 200),
+                search: Rule::new(120, 30),
+                scrape: Rule::new(30, 10),
+            },
+            auth_failures: Rule::new(10, 10),
+        }
+    }
+}
+
+fn env_flag(name: &str) -> Option<bool> {
+    env::var(name)
+        .ok()
+        .map(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+}
+
+impl RateLimitConfig {
+    pub fn from_env() -> Self {
+        let mut config = RateLimitConfig::default();
+        if let Some(enabled) = env_flag("RATE_LIMIT_ENABLED") {
+            config.enabled = enabled;
+        }
+        if let Some(trust_proxy) = env_flag("RATE_LIMIT_TRUST_PROXY") {
+            config.trust_proxy = trust_proxy;
+        }
+        if let Ok(store) = env::var("RATE_LIMIT_STORE") {
+            config.store = match store.trim().to_ascii_lowercase().as_str() {
+                "mongodb" | "mongo" => StoreKind::MongoDb,
+                _ => StoreKind::Memory,
+            };
+        }
+        if let Ok(rules) = env::var("RATE_LIMIT_IP") {
+            config.ip_rules.apply(&rules);
+        }
+        if let Ok(rules) = env::var("RATE_LIMIT_KEY") {
+            config.key_rules.apply(&rules);
+        }
+        match env::var("RATE_LIMIT_AUTH_FAILURES").ok().map(|value| Rule::parse(&value)) {
+            Some(Some(rule)) => config.auth_failures = rule,
+            Some(None) => log::warn!("Geçersiz RATE_LIMIT_AUTH_FAILURES değeri atlandı"),
+            None => {}
+        }
+        config
+    }
+
+    pub fn disabled() -> Self {
+        RateLimitConfig {
+            enabled: false,
+            ..RateLimitConfig::default()
+        }
+    }
+}
+
+// Sınırlanan istemci: API anahtarı varsa anahtarın özeti (doğrulamadan önce bilinir), yoksa IP adresi
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subject {
+    ApiKey(String),
+    Ip(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    // Reddedildiyse bir sonraki token'a kadar saniye
+    pub retry_after: u64,
+    // Bucket'ın tamamen dolmasına kadar saniye
+    pub reset: u64,
+}
+
+pub struct RateLimiter {
+    pub config: RateLimitConfig,
+    store: Arc<dyn RateLimitStore>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig, store: Arc<dyn RateLimitStore>) -> Self {
+        RateLimiter { config, store }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(RateLimitConfig::disabled(), Arc::new(LocalStore::new()))
+    }
+
+    // Limiter kapalıysa None döner
+    pub async fn check(&self, subject: &Subject, path: &str) -> RepositoryResult<Option<Decision>> {
+        if !self.config.enabled {
+            return Ok(None);
+        }
+
+        let group = Scope::for_path(path);
+        let (rule, bucket) = match subject {
+            Subject::ApiKey(id) => (self.config.key_rules.get(group), format!("key:{}:{}", id, group.as_str())),
+            Subject::Ip(ip) => (self.config.ip_rules.get(group), format!("ip:{}:{}", ip, group.as_str())),
+        };
+
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let state = self.store.take(&bucket, rule, now_ms).await?;
+        Ok(Some(decide(rule, state)))
+    }
+
+    // IP'nin geçersiz anahtar bucket'ı token harcamadan okunur; boşsa anahtar aranmadan reddedilir
+    pub async fn check_auth_failures(&self, ip: &str) -> RepositoryResult<Option<Decision>> {
+        if !self.config.enabled {
+            return Ok(None);
+        }
+        let rule = self.config.auth_failures;
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let state = self.store.peek(&auth_failure_bucket(ip), rule, now_ms).await?;
+        Ok(Some(decide(rule, state)))
+    }
+
+    // Her geçersiz anahtar denemesi IP'nin bucket'ından bir token düşer
+    pub async fn record_auth_failure(&self, ip: &str) -> RepositoryResult<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        self.store.take(&auth_failure_bucket(ip), self.config.auth_failures, now_ms).await?;
+        Ok(())
+    }
+}
+
+fn auth_failure_bucket(ip: &str) -> String {
+    format!("auth:{}", ip)
+}
+
+fn decide(rule: Rule, state: BucketState) -> Decision {
+    let per_second = rule.refill_per_ms() * 1000.0;
+    let tokens = state.tokens.max(0.0);
+    let retry_after = if state.allowed {
+        0
+    } else {
+        ((1.0 - tokens) / per_second).ceil().max(1.0) as u64
+    };
+
+    Decision {
+        allowed: state.allowed,
+        limit: rule.burst,
+        remaining: tokens.floor() as u32,
+        retry_after,
+        reset: ((rule.burst as f64 - tokens) / per_second).ceil().max(0.0) as u64,
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use async_trait::async_trait;
+use mongodb::bson::{doc, DateTime as BsonDateTime, Document as MongoDocument};
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument, UpdateModifications};
+use mongodb::{Collection, Database};
+use crate::repository::RepositoryResult;
+use super::Rule;
+
+// Bucket'lar bu süre boyunca kullanılmazsa silinir (tüm kurallar bu sürede dolar)
+const IDLE_BUCKET_MS: i64 = 60 * 60 * 1000;
+// Süreç içi store bu boyutu aşınca boşta kalan bucket'ları temizler
+const LOCAL_PRUNE_THRESHOLD: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketState {
+    pub allowed: bool,
+    // Token alındıktan sonra kalan token (kesirli olabilir)
+    pub tokens: f64,
+}
+
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    // Bucket'ı now_ms'e kadar doldurup bir token almayı dener
+    async fn take(&self, bucket: &str, rule: Rule, now_ms: i64) -> RepositoryResult<BucketState>;
+    // Token almadan bucket'ın now_ms'teki durumunu döner; tokens mevcut token sayısıdır
+    async fn peek(&self, bucket: &str, rule: Rule, now_ms: i64) -> RepositoryResult<BucketState>;
+}
+
+// Yeni bucket dolu başlar; geçen süre kadar token eklenir, kapasite burst ile sınırlıdır
+fn refill(tokens: f64, updated_ms: i64, rule: Rule, now_ms: i64) -> f64 {
+    let elapsed = (now_ms - updated_ms).max(0) as f64;
+    (tokens + elapsed * rule.refill_per_ms()).min(rule.burst as f64)
+}
+
+#[derive(Default)]
+pub struct LocalStore {
+    // bucket -> (token, son güncelleme ms)
+    buckets: Mutex<HashMap<String, (f64, i64)>>,
+}
+
+impl LocalStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for LocalStore {
+    async fn take(&self, bucket: &str, rule: Rule, now_ms: i64) -> RepositoryResult<BucketState> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() > LOCAL_PRUNE_THRESHOLD {
+            buckets.retain(|_, (_, updated_ms)| now_ms - *updated_ms < IDLE_BUCKET_MS);
+        }
+
+        let (tokens, updated_ms) = buckets
+            .entry(bucket.to_string())
+            .or_insert((rule.burst as f64, now_ms));
+        let available = refill(*tokens, *updated_ms, rule, now_ms);
+        let allowed = available >= 1.0;
+
+        *tokens = if allowed { available - 1.0 } else { available };
+        *updated_ms = now_ms;
+
+        Ok(BucketState { allowed, tokens: *tokens })
+    }
+
+    async fn peek(&self, bucket: &str, rule: Rule, now_ms: i64) -> RepositoryResult<BucketState> {
+        let buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let available = match buckets.get(bucket) {
+            Some((tokens, updated_ms)) => refill(*tokens, *updated_ms, rule, now_ms),
+            None => rule.burst as f64,
+        };
+        Ok(BucketState { allowed: available >= 1.0, tokens: available })
+    }
+}
+
+// Birden fazla API instance'ı için MongoDB'de paylaşılan bucket'lar. Dolum ve token
+// alma tek bir pipeline update ile atomik yapılır; expires_at üzerinde TTL index vardır.
+pub struct MongoStore {
+    collection: Collection<MongoDocument>,
+}
+
+impl MongoStore {
+    pub fn new(db: &Database) -> Self {
+        MongoStore {
+            collection: db.collection("rate_limits"),
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for MongoStore {
+    async fn take(&self, bucket: &str, rule: Rule, now_ms: i64) -> RepositoryResult<BucketState> {
+        let burst = rule.burst as f64;
+        let pipeline = vec![
+            doc! {
+                "$set": {
+                    "tokens": {
+                        "$min": [
+                            burst,
+                            {
+                                "$add": [
+                                    { "$ifNull": ["$tokens", burst] },
+                                    {
+                                        "$multiply": [
+                                            { "$max": [0, { "$subtract": [now_ms, { "$ifNull": ["$updated_ms", now_ms] }] }] },
+                                            rule.refill_per_ms(),
+                                        ]
+                                    },
+                                ]
+                            },
+                        ]
+                    },
+                    "updated_ms": now_ms,
+                    "expires_at": BsonDateTime::from_millis(now_ms + IDLE_BUCKET_MS),
+                }
+            },
+            doc! {
+                "$set": {
+                    "allowed": { "$gte": ["$tokens", 1] },
+                    "tokens": {
+                        "$cond": [{ "$gte": ["$tokens", 1] }, { "$subtract": ["$tokens", 1] }, "$tokens"]
+                    },
+                }
+            },
+        ];
+
+        let options = FindOneAndUpdateOptions::builder()
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .build();
+        let doc = self
+            .collection
+            .find_one_and_update(doc! { "_id": bucket }, UpdateModifications::Pipeline(pipeline), options)
+            .await?;
+
+        Ok(match doc {
+            Some(doc) => BucketState {
+                allowed: doc.get_bool("allowed").unwrap_or(true),
+                tokens: doc.get_f64("tokens").unwrap_or(0.0),
+            },
+            // upsert + ReturnDocument::After her zaman doküman döndürür
+            None => BucketState { allowed: true, tokens: burst - 1.0 },
+        })
+    }
+
+    async fn peek(&self, bucket: &str, rule: Rule, now_ms: i64) -> RepositoryResult<BucketState> {
+        let doc = self.collection.find_one(doc! { "_id": bucket }, None).await?;
+        let available = match doc {
+            Some(doc) => refill(
+                doc.get_f64("tokens").unwrap_or(rule.burst as f64),
+                doc.get_i64("updated_ms").unwrap_or(now_ms),
+                rule,
+                now_ms,
+            ),
+            None => rule.burst as f64,
+        };
+        Ok(BucketState { allowed: available >= 1.0, tokens: available })
+    }
+}
@@ -0,0 +1,1194 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use async_trait::async_trait;
+use mongodb::bson::{doc, oid::ObjectId, Bson, Document as MongoDocument};
+use regex::Regex;
+use crate::audit::{self, Actor, AUDIT_CLAIMS_COLLECTION, AUDIT_COLLECTION};
+use crate::corpus::{ContentSource, Corpus};
+use crate::utils::bson_date_string;
+use super::{
+    AlertFilter, AnnouncementSourceRepository, AnnouncementSourceWriteRepository, ApiKeyRepository, AuditFilter,
+    AuditRepository, ContentRepository, DeliveryFilter, DocumentFilter, DocumentRepository, DocumentSort,
+    DocumentWriteRepository, InstitutionRepository, InstitutionWriteRepository, LinkRepository, LinkWriteRepository,
+    Page, RepositoryError, RepositoryResult, SavedSearchRepository, ScrapedAnnouncementFilter,
+    ScrapedAnnouncementRepository, SearchFilter, StorageHealth, WebhookRepository,
+};
+use super::mongo::etiket_pattern;
+use super::records::{
+    canonical_id, collect_etiketler, id_string, AlertRecord, AlertWrite, AnnouncementDetailWrite,
+    AnnouncementSourceRecord, AnnouncementSourceWrite, ApiKeyRecord, AuditRecord, ContentRecord, DeliveryAttempt,
+    DeliveryRecord, DeliveryWrite, DocumentCountGroup, DocumentRecord, DocumentWrite, InstitutionRecord,
+    InstitutionWrite, LinkRecord, LinkWrite, SavedSearchRecord, SavedSearchRun, SavedSearchWrite, ScanCursor,
+    ScrapedAnnouncementRecord, ScrapedAnnouncementWrite, WebhookRecord, WebhookWrite,
+};
+
+// Bellek içi repository (testler ve yerel geliştirme için).
+// Kayıtlar MongoDB'deki gibi ham doküman olarak koleksiyon adına göre tutulur,
+// böylece alan adı toleransı Mongo implementasyonuyla aynı kalır.
+type Collections = HashMap<String, Vec<MongoDocument>>;
+
+#[derive(Default)]
+pub struct MemoryRepository {
+    collections: RwLock<Collections>,
+}
+
+impl MemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Dokümanı koleksiyona ekle; _id yoksa yeni ObjectId atanır. Eklenen id'yi döndürür.
+    pub fn insert(&self, collection: &str, mut doc: MongoDocument) -> String {
+        if !doc.contains_key("_id") {
+            doc.insert("_id", ObjectId::new());
+        }
+        let id = id_string(&doc, "_id");
+        self.collections
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(collection.to_string())
+            .or_default()
+            .push(doc);
+        id
+    }
+
+    // Koleksiyondaki ham dokümanlar (testlerde saklanan tipleri incelemek için de kullanılır)
+    pub fn documents(&self, collection: &str) -> Vec<MongoDocument> {
+        self.collections
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(collection)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // Koleksiyon üzerinde yerinde değişiklik (update/upsert karşılığı)
+    fn update<R>(&self, collection: &str, f: impl FnOnce(&mut Vec<MongoDocument>) -> R) -> R {
+        let mut collections = self.collections.write().unwrap_or_else(|e| e.into_inner());
+        f(collections.entry(collection.to_string()).or_default())
+    }
+
+    // Yazmayı tek kilit altında uygular; dokunulan koleksiyonların önceki ve sonraki
+    // hallerinden üretilen denetim kayıtları audit_log'a eklenir
+    fn audited<R>(&self, actor: &Actor, touched: &[&str], f: impl FnOnce(&mut Collections) -> R) -> R {
+        let snapshot = |collections: &Collections| -> Vec<(String, MongoDocument)> {
+            touched
+                .iter()
+                .flat_map(|name| {
+                    collections
+                        .get(*name)
+                        .into_iter()
+                        .flatten()
+                        .map(|doc| (name.to_string(), doc.clone()))
+                })
+                .collect()
+        };
+
+        let mut collections = self.collections.write().unwrap_or_else(|e| e.into_inner());
+        let before = snapshot(&collections);
+        let result = f(&mut collections);
+        let after = snapshot(&collections);
+        collections
+            .entry(AUDIT_COLLECTION.to_string())
+            .or_default()
+            .extend(audit::entries(actor, &before, &after).iter().map(AuditRecord::to_document));
+        result
+    }
+
+    fn insert_audited(&self, actor: &Actor, collection: &str, mut doc: MongoDocument) -> String {
+        let oid = ObjectId::new();
+        doc.insert("_id", oid);
+        self.audited(actor, &[collection], |collections| {
+            collections.entry(collection.to_string()).or_default().push(doc);
+        });
+        oid.to_hex()
+    }
+
+    // _id'si eşleşen dokümanın alanlarını günceller ($set karşılığı); bulunamazsa false
+    fn set_fields(&self, actor: &Actor, collection: &str, id: &str, fields: MongoDocument) -> bool {
+        self.audited(actor, &[collection], |collections| {
+            let docs = collections.entry(collection.to_string()).or_default();
+            match docs.iter_mut().find(|doc| id_string(doc, "_id") == id) {
+                Some(doc) => {
+                    doc.extend(fields);
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+
+    fn remove(&self, actor: &Actor, collection: &str, id: &str) -> bool {
+        self.audited(actor, &[collection], |collections| {
+            let docs = collections.entry(collection.to_string()).or_default();
+            let before = docs.len();
+            docs.retain(|doc| id_string(doc, "_id") != id);
+            docs.len() != before
+        })
+    }
+
+    // $lookup karşılığı: kurum dokümanını kurum_bilgisi alanına ekle
+    fn with_kurum(&self, corpus: &Corpus, mut doc: MongoDocument) -> MongoDocument {
+        if corpus.lookup_kurum {
+            let kurum_id = id_string(&doc, "kurum_id");
+            if let Some(kurum) = self
+                .documents("kurumlar")
+                .into_iter()
+                .find(|kurum| !kurum_id.is_empty() && id_string(kurum, "_id") == kurum_id)
+            {
+                doc.insert("kurum_bilgisi", kurum);
+            }
+        }
+        doc
+    }
+
+    fn search_matches(
+        &self,
+        corpus: &Corpus,
+        filter: &SearchFilter,
+    ) -> RepositoryResult<Vec<MongoDocument>> {
+        if filter.patterns.is_empty() {
+            return Err(RepositoryError::InvalidQuery("boş arama".to_string()));
+        }
+        let regexes = filter
+            .patterns
+            .iter()
+            .map(|pattern| Regex::new(&format!("(?i){}", pattern)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| RepositoryError::InvalidQuery(e.to_string()))?;
+
+        let all_match = |doc: &MongoDocument, field: &str| {
+            let value = doc.get_str(field).unwrap_or("");
+            doc.contains_key(field) && regexes.iter().all(|regex| regex.is_match(value))
+        };
+
+        let mut docs: Vec<MongoDocument> = self
+            .documents(&corpus.collection)
+            .into_iter()
+            .filter(|doc| kurum_matches(doc, filter.kurum_id.as_deref()))
+            .filter(|doc| {
+                all_match(doc, &corpus.fields.title) || all_match(doc, &corpus.fields.search_text)
+            })
+            .collect();
+        sort_documents(&mut docs, &corpus.fields.created_at, true);
+        Ok(docs)
+    }
+
+    fn filtered(&self, corpus: &Corpus, filter: &DocumentFilter) -> Vec<MongoDocument> {
+        let etiket_regex = filter
+            .etiket
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .and_then(|etiket| Regex::new(&format!("(?i){}", etiket_pattern(etiket))).ok());
+
+        self.documents(&corpus.collection)
+            .into_iter()
+            .filter(|doc| kurum_matches(doc, filter.kurum_id.as_deref()))
+            .filter(|doc| match filter.belge_turu.as_deref().filter(|s| !s.is_empty()) {
+                Some(belge_turu) => doc.get_str("belge_turu").ok() == Some(belge_turu),
+                None => true,
+            })
+            .filter(|doc| match &etiket_regex {
+                Some(regex) => regex.is_match(doc.get_str("etiketler").unwrap_or("")),
+                None => true,
+            })
+            .collect()
+    }
+}
+
+// kurum_id hem string hem ObjectId olarak saklanabilir
+fn kurum_matches(doc: &MongoDocument, kurum_id: Option<&str>) -> bool {
+    match kurum_id {
+        Some(kurum_id) => id_string(doc, "kurum_id") == kurum_id,
+        None => true,
+    }
+}
+
+fn belge_turu_key(doc: &MongoDocument) -> String {
+    match doc.get_str("belge_turu") {
+        Ok(belge_turu) if !belge_turu.is_empty() => belge_turu.to_string(),
+        _ => "Belirtilmemiş".to_string(),
+    }
+}
+
+// MongoDB sıralama düzenine yakın karşılaştırma: eksik < sayı < string < ObjectId < tarih
+fn compare_values(a: Option<&Bson>, b: Option<&Bson>) -> Ordering {
+    fn rank(value: Option<&Bson>) -> u8 {
+        match value {
+            None | Some(Bson::Null) => 0,
+            Some(Bson::Int32(_)) | Some(Bson::Int64(_)) | Some(Bson::Double(_)) => 1,
+            Some(Bson::String(_)) => 2,
+            Some(Bson::ObjectId(_)) => 3,
+            Some(Bson::DateTime(_)) => 4,
+            Some(_) => 5,
+        }
+    }
+    fn number(value: &Bson) -> f64 {
+        match value {
+            Bson::Int32(n) => *n as f64,
+            Bson::Int64(n) => *n as f64,
+            Bson::Double(n) => *n,
+            _ => 0.0,
+        }
+    }
+
+    match (a, b) {
+        (Some(Bson::String(a)), Some(Bson::String(b))) => a.cmp(b),
+        (Some(Bson::ObjectId(a)), Some(Bson::ObjectId(b))) => a.bytes().cmp(&b.bytes()),
+        (Some(Bson::DateTime(a)), Some(Bson::DateTime(b))) => a.cmp(b),
+        (Some(a), Some(b)) if rank(Some(a)) == 1 && rank(Some(b)) == 1 => {
+            number(a).partial_cmp(&number(b)).unwrap_or(Ordering::Equal)
+        }
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+fn sort_documents(docs: &mut [MongoDocument], field: &str, descending: bool) {
+    docs.sort_by(|a, b| {
+        let ordering = compare_values(a.get(field), b.get(field));
+        if descending { ordering.reverse() } else { ordering }
+    });
+}
+
+fn paginate<T>(items: Vec<T>, page: Page) -> Vec<T> {
+    items
+        .into_iter()
+        .skip(page.offset as usize)
+        .take(page.limit as usize)
+        .collect()
+}
+
+#[async_trait]
+impl DocumentRepository for MemoryRepository {
+    async fn list(
+        &self,
+        corpus: &Corpus,
+        filter: &DocumentFilter,
+        sort: &DocumentSort,
+        page: Page,
+    ) -> RepositoryResult<Vec<DocumentRecord>> {
+        let mut docs = self.filtered(corpus, filter);
+        sort_documents(&mut docs, &sort.field, sort.descending);
+        Ok(paginate(docs, page)
+            .iter()
+            .map(|doc| DocumentRecord::from_document(doc, corpus))
+            .collect())
+    }
+
+    async fn count(&self, corpus: &Corpus, filter: &DocumentFilter) -> RepositoryResult<u64> {
+        Ok(self.filtered(corpus, filter).len() as u64)
+    }
+
+    async fn count_for_kurum(&self, corpus: &Corpus, kurum_id: &str) -> RepositoryResult<u64> {
+        Ok(self
+            .documents(&corpus.collection)
+            .iter()
+            .filter(|doc| kurum_matches(doc, Some(kurum_id)))
+            .count() as u64)
+    }
+
+    async fn find_by_slug(&self, corpus: &Corpus, slug: &str) -> RepositoryResult<Option<DocumentRecord>> {
+        Ok(self
+            .documents(&corpus.collection)
+            .iter()
+            .find(|doc| doc.get_str("url_slug").ok() == Some(slug))
+            .map(|doc| DocumentRecord::from_document(doc, corpus)))
+    }
+
+    async fn find_by_id(&self, corpus: &Corpus, id: &str) -> RepositoryResult<Option<DocumentRecord>> {
+        Ok(self
+            .documents(&corpus.collection)
+            .iter()
+            .find(|doc| id_string(doc, "_id") == id)
+            .map(|doc| DocumentRecord::from_document(doc, corpus)))
+    }
+
+    async fn recent(&self, corpus: &Corpus, limit: u64) -> RepositoryResult<Vec<DocumentRecord>> {
+        let mut docs = self.documents(&corpus.collection);
+        sort_documents(&mut docs, &corpus.fields.created_at, true);
+        Ok(paginate(docs, Page { offset: 0, limit })
+            .into_iter()
+            .map(|doc| DocumentRecord::from_document(&self.with_kurum(corpus, doc), corpus))
+            .collect())
+    }
+
+    async fn created_after(&self, corpus: &Corpus, cursor: &ScanCursor, limit: u64) -> RepositoryResult<Vec<DocumentRecord>> {
+        let mut docs: Vec<(String, String, MongoDocument)> = self
+            .documents(&corpus.collection)
+            .into_iter()
+            .map(|doc| (bson_date_string(&doc, &corpus.fields.created_at), id_string(&doc, "_id"), doc))
+            .filter(|(time, id, _)| !cursor.covers(time, id))
+            .collect();
+        docs.sort_by(|(a_time, a_id, _), (b_time, b_id, _)| a_time.cmp(b_time).then_with(|| a_id.cmp(b_id)));
+        Ok(docs
+            .into_iter()
+            .take(limit as usize)
+            .map(|(_, _, doc)| DocumentRecord::from_document(&self.with_kurum(corpus, doc), corpus))
+            .collect())
+    }
+
+    async fn search(
+        &self,
+        corpus: &Corpus,
+        filter: &SearchFilter,
+        page: Page,
+    ) -> RepositoryResult<Vec<DocumentRecord>> {
+        let docs = self.search_matches(corpus, filter)?;
+        Ok(paginate(docs, page)
+            .into_iter()
+            .map(|doc| DocumentRecord::from_document(&self.with_kurum(corpus, doc), corpus))
+            .collect())
+    }
+
+    async fn count_search(&self, corpus: &Corpus, filter: &SearchFilter) -> RepositoryResult<u64> {
+        Ok(self.search_matches(corpus, filter)?.len() as u64)
+    }
+
+    async fn active(
+        &self,
+        corpus: &Corpus,
+        sort_field: &str,
+        limit: Option<i64>,
+    ) -> RepositoryResult<Vec<DocumentRecord>> {
+        let mut docs: Vec<MongoDocument> = self
+            .documents(&corpus.collection)
+            .into_iter()
+            .filter(|doc| doc.get_str("status").ok() == Some(corpus.fields.active_status.as_str()))
+            .collect();
+        sort_documents(&mut docs, sort_field, true);
+        if let Some(limit) = limit {
+            docs.truncate(limit.max(0) as usize);
+        }
+        Ok(docs
+            .iter()
+            .map(|doc| DocumentRecord::from_document(doc, corpus))
+            .collect())
+    }
+
+    async fn count_active(&self, corpus: &Corpus) -> RepositoryResult<u64> {
+        Ok(self
+            .documents(&corpus.collection)
+            .iter()
+            .filter(|doc| doc.get_str("status").ok() == Some(corpus.fields.active_status.as_str()))
+            .count() as u64)
+    }
+
+    async fn active_page(&self, corpus: &Corpus, page: Page) -> RepositoryResult<Vec<DocumentRecord>> {
+        let mut docs: Vec<MongoDocument> = self
+            .documents(&corpus.collection)
+            .into_iter()
+            .filter(|doc| doc.get_str("status").ok() == Some(corpus.fields.active_status.as_str()))
+            .collect();
+        sort_documents(&mut docs, "_id", false);
+        Ok(paginate(docs, page)
+            .iter()
+            .map(|doc| DocumentRecord::from_document(doc, corpus))
+            .collect())
+    }
+
+    async fn count_groups(&self, corpus: &Corpus) -> RepositoryResult<Vec<DocumentCountGroup>> {
+        let mut counts: HashMap<(String, String, Option<String>), u64> = HashMap::new();
+        for doc in self.documents(&corpus.collection) {
+            let yayin_tarihi = doc.get_str(&corpus.fields.publication_date).ok().map(str::to_string);
+            *counts.entry((id_string(&doc, "kurum_id"), belge_turu_key(&doc), yayin_tarihi)).or_default() += 1;
+        }
+        Ok(counts
+            .into_iter()
+            .map(|((kurum_id, belge_turu, yayin_tarihi), count)| DocumentCountGroup {
+                kurum_id,
+                belge_turu,
+                yayin_tarihi,
+                count,
+            })
+            .collect())
+    }
+
+    async fn belge_turu_values(&self, corpus: &Corpus, kurum_id: Option<&str>) -> RepositoryResult<Vec<String>> {
+        let mut values: Vec<String> = self
+            .documents(&corpus.collection)
+            .iter()
+            .filter(|doc| kurum_matches(doc, kurum_id))
+            .map(belge_turu_key)
+            .collect();
+        values.sort();
+        values.dedup();
+        Ok(values)
+    }
+
+    async fn etiket_values(&self, corpus: &Corpus, kurum_id: Option<&str>) -> RepositoryResult<Vec<String>> {
+        let docs: Vec<MongoDocument> = self
+            .documents(&corpus.collection)
+            .into_iter()
+            .filter(|doc| kurum_matches(doc, kurum_id))
+            .collect();
+        Ok(collect_etiketler(docs.iter().filter_map(|doc| doc.get_str("etiketler").ok())))
+    }
+
+    async fn find_by_pdf_url(&self, corpus: &Corpus, pdf_url: &str) -> RepositoryResult<Option<DocumentRecord>> {
+        Ok(self
+            .documents(&corpus.collection)
+            .iter()
+            .find(|doc| doc.get_str("pdf_url").ok() == Some(pdf_url))
+            .map(|doc| DocumentRecord::from_document(doc, corpus)))
+    }
+}
+
+// Belgenin metadata ve içerik koleksiyonları (denetim kaydı için)
+fn document_collections(corpus: &Corpus) -> Vec<&str> {
+    let mut collections = vec![corpus.collection.as_str()];
+    if let ContentSource::Join { collection, .. } = &corpus.content {
+        collections.push(collection);
+    }
+    collections
+}
+
+// MongoDB'deki idx_url_slug_unique index'inin karşılığı: slug başka bir belgede varsa yazma reddedilir
+fn slug_unique(metadata_docs: &[MongoDocument], slug: &str, id: Option<&str>) -> RepositoryResult<()> {
+    let taken = metadata_docs
+        .iter()
+        .any(|doc| doc.get_str("url_slug") == Ok(slug) && Some(id_string(doc, "_id").as_str()) != id);
+    if taken && !slug.is_empty() {
+        return Err(RepositoryError::Duplicate("url_slug".to_string()));
+    }
+    Ok(())
+}
+
+// Metadata ve içerik aynı kilit altında yazılır
+#[async_trait]
+impl DocumentWriteRepository for MemoryRepository {
+    async fn insert(&self, actor: &Actor, corpus: &Corpus, document: &DocumentWrite) -> RepositoryResult<String> {
+        let oid = ObjectId::new();
+        let mut metadata = document.metadata_fields(corpus);
+        metadata.insert("_id", oid);
+        let content = document.content_document(corpus, Bson::ObjectId(oid));
+
+        self.audited(actor, &document_collections(corpus), |collections| {
+            let metadata_docs = collections.entry(corpus.collection.clone()).or_default();
+            slug_unique(metadata_docs, &document.url_slug, None)?;
+            metadata_docs.push(metadata);
+            if let (ContentSource::Join { collection, .. }, Some(mut content)) = (&corpus.content, content) {
+                content.insert("_id", ObjectId::new());
+                collections.entry(collection.clone()).or_default().push(content);
+            }
+            RepositoryResult::Ok(())
+        })?;
+        Ok(oid.to_hex())
+    }
+
+    async fn update(&self, actor: &Actor, corpus: &Corpus, id: &str, document: &DocumentWrite) -> RepositoryResult<bool> {
+        self.audited(actor, &document_collections(corpus), |collections| {
+            let metadata_docs = collections.entry(corpus.collection.clone()).or_default();
+            slug_unique(metadata_docs, &document.url_slug, Some(id))?;
+            let Some(metadata) = metadata_docs.iter_mut().find(|doc| id_string(doc, "_id") == id) else {
+                return Ok(false);
+            };
+            metadata.extend(document.metadata_fields(corpus));
+            let metadata_id = canonical_id(metadata.get("_id").unwrap_or(&Bson::Null));
+
+            if let ContentSource::Join { collection, foreign_key } = &corpus.content {
+                let contents = collections.entry(collection.clone()).or_default();
+                match document.content_document(corpus, metadata_id.clone()) {
+                    Some(mut content) => {
+                        // Mevcut içerik kaydının _id'si korunur
+                        let content_id = contents
+                            .iter()
+                            .find(|doc| id_string(doc, foreign_key) == id)
+                            .and_then(|doc| doc.get("_id").cloned())
+                            .unwrap_or_else(|| Bson::ObjectId(ObjectId::new()));
+                        contents.retain(|doc| id_string(doc, foreign_key) != id);
+                        content.insert("_id", content_id);
+                        contents.push(content);
+                    }
+                    None => {
+                        for content in contents.iter_mut().filter(|doc| id_string(doc, foreign_key) == id) {
+                            content.insert(foreign_key.as_str(), metadata_id.clone());
+                        }
+                    }
+                }
+            }
+            Ok(true)
+        })
+    }
+
+    async fn delete(&self, actor: &Actor, corpus: &Corpus, id: &str) -> RepositoryResult<bool> {
+        Ok(self.audited(actor, &document_collections(corpus), |collections| {
+            let metadata_docs = collections.entry(corpus.collection.clone()).or_default();
+            let before = metadata_docs.len();
+            metadata_docs.retain(|doc| id_string(doc, "_id") != id);
+            if metadata_docs.len() == before {
+                return false;
+            }
+
+            if let ContentSource::Join { collection, foreign_key } = &corpus.content {
+                if let Some(contents) = collections.get_mut(collection) {
+                    contents.retain(|doc| id_string(doc, foreign_key) != id);
+                }
+            }
+            true
+        }))
+    }
+}
+
+#[async_trait]
+impl InstitutionRepository for MemoryRepository {
+    async fn list(&self) -> RepositoryResult<Vec<InstitutionRecord>> {
+        Ok(self
+            .documents("kurumlar")
+            .iter()
+            .map(InstitutionRecord::from_document)
+            .collect())
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<InstitutionRecord>> {
+        Ok(self
+            .documents("kurumlar")
+            .iter()
+            .find(|doc| id_string(doc, "_id") == id)
+            .map(InstitutionRecord::from_document))
+    }
+
+    async fn count(&self) -> RepositoryResult<u64> {
+        Ok(self.documents("kurumlar").len() as u64)
+    }
+}
+
+// Kurum, duyuru linki ve faydalı linkleri aynı kilit altında silinir
+#[async_trait]
+impl InstitutionWriteRepository for MemoryRepository {
+    async fn insert(&self, actor: &Actor, institution: &InstitutionWrite) -> RepositoryResult<String> {
+        Ok(self.insert_audited(actor, "kurumlar", institution.to_document()))
+    }
+
+    async fn update(&self, actor: &Actor, id: &str, institution: &InstitutionWrite) -> RepositoryResult<bool> {
+        Ok(self.set_fields(actor, "kurumlar", id, institution.to_document()))
+    }
+
+    async fn delete(&self, actor: &Actor, id: &str) -> RepositoryResult<bool> {
+        Ok(self.audited(actor, &["kurumlar", "kurum_duyuru", "links"], |collections| {
+            let kurumlar = collections.entry("kurumlar".to_string()).or_default();
+            let before = kurumlar.len();
+            kurumlar.retain(|doc| id_string(doc, "_id") != id);
+            if kurumlar.len() == before {
+                return false;
+            }
+
+            for collection in ["kurum_duyuru", "links"] {
+                if let Some(docs) = collections.get_mut(collection) {
+                    docs.retain(|doc| !kurum_matches(doc, Some(id)));
+                }
+            }
+            true
+        }))
+    }
+}
+
+#[async_trait]
+impl ContentRepository for MemoryRepository {
+    async fn find_for_document(
+        &self,
+        corpus: &Corpus,
+        document: &DocumentRecord,
+    ) -> RepositoryResult<Option<ContentRecord>> {
+        match &corpus.content {
+            ContentSource::Embedded => Ok(Some(ContentRecord {
+                id: String::new(),
+                icerik: document.icerik.clone().unwrap_or_default(),
+                olusturulma_tarihi: Some(document.olusturulma_tarihi.clone())
+                    .filter(|s| !s.is_empty()),
+            })),
+            ContentSource::Join { collection, foreign_key } => Ok(self
+                .documents(collection)
+                .iter()
+                .find(|doc| id_string(doc, foreign_key) == document.id)
+                .map(|doc| ContentRecord::from_document(doc, corpus))),
+        }
+    }
+}
+
+#[async_trait]
+impl LinkRepository for MemoryRepository {
+    async fn list_by_kurum(&self, kurum_id: &str) -> RepositoryResult<Vec<LinkRecord>> {
+        Ok(self
+            .documents("links")
+            .iter()
+            .filter(|doc| kurum_matches(doc, Some(kurum_id)))
+            .map(LinkRecord::from_document)
+            .collect())
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<LinkRecord>> {
+        Ok(self
+            .documents("links")
+            .iter()
+            .find(|doc| id_string(doc, "_id") == id)
+            .map(LinkRecord::from_document))
+    }
+}
+
+#[async_trait]
+impl LinkWriteRepository for MemoryRepository {
+    async fn insert(&self, actor: &Actor, link: &LinkWrite) -> RepositoryResult<String> {
+        Ok(self.insert_audited(actor, "links", link.to_document()))
+    }
+
+    async fn update(&self, actor: &Actor, id: &str, link: &LinkWrite) -> RepositoryResult<bool> {
+        Ok(self.set_fields(actor, "links", id, link.to_document()))
+    }
+
+    async fn delete(&self, actor: &Actor, id: &str) -> RepositoryResult<bool> {
+        Ok(self.remove(actor, "links", id))
+    }
+}
+
+#[async_trait]
+impl AnnouncementSourceRepository for MemoryRepository {
+    async fn list(&self, kurum_id: Option<&str>) -> RepositoryResult<Vec<AnnouncementSourceRecord>> {
+        let mut docs: Vec<MongoDocument> = self
+            .documents("kurum_duyuru")
+            .into_iter()
+            .filter(|doc| kurum_matches(doc, kurum_id))
+            .collect();
+        sort_documents(&mut docs, "_id", true);
+        Ok(docs.iter().map(AnnouncementSourceRecord::from_document).collect())
+    }
+
+    async fn find_by_kurum(&self, kurum_id: &str) -> RepositoryResult<Option<AnnouncementSourceRecord>> {
+        Ok(self
+            .documents("kurum_duyuru")
+            .iter()
+            .find(|doc| kurum_matches(doc, Some(kurum_id)))
+            .map(AnnouncementSourceRecord::from_document))
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<AnnouncementSourceRecord>> {
+        Ok(self
+            .documents("kurum_duyuru")
+            .iter()
+            .find(|doc| id_string(doc, "_id") == id)
+            .map(AnnouncementSourceRecord::from_document))
+    }
+}
+
+#[async_trait]
+impl AnnouncementSourceWriteRepository for MemoryRepository {
+    async fn insert(&self, actor: &Actor, source: &AnnouncementSourceWrite) -> RepositoryResult<String> {
+        Ok(self.insert_audited(actor, "kurum_duyuru", source.to_document()))
+    }
+
+    async fn update(&self, actor: &Actor, id: &str, source: &AnnouncementSourceWrite) -> RepositoryResult<bool> {
+        Ok(self.set_fields(actor, "kurum_duyuru", id, source.to_document()))
+    }
+
+    async fn delete(&self, actor: &Actor, id: &str) -> RepositoryResult<bool> {
+        Ok(self.remove(actor, "kurum_duyuru", id))
+    }
+}
+
+fn scraped_announcement_matches(record: &ScrapedAnnouncementRecord, filter: &ScrapedAnnouncementFilter) -> bool {
+    let q = filter.q.as_deref().map(str::to_lowercase);
+    filter.kurum_id.as_deref().is_none_or(|kurum_id| record.kurum_id == kurum_id)
+        && filter.since.as_deref().is_none_or(|since| record.first_seen_at.as_str() >= since)
+        && (!filter.with_attachments || !record.ekler.is_empty())
+        && q.is_none_or(|q| {
+            record.baslik.to_lowercase().contains(&q)
+                || record.icerik.as_deref().is_some_and(|icerik| icerik.to_lowercase().contains(&q))
+        })
+}
+
+// En yeni önce (aynı hasattakiler eklenme sırasının tersiyle)
+fn scraped_announcements(docs: &[MongoDocument], filter: &ScrapedAnnouncementFilter) -> Vec<ScrapedAnnouncementRecord> {
+    let mut records: Vec<(usize, ScrapedAnnouncementRecord)> = docs
+        .iter()
+        .map(ScrapedAnnouncementRecord::from_document)
+        .enumerate()
+        .filter(|(_, record)| scraped_announcement_matches(record, filter))
+        .collect();
+    records.sort_by(|(a_pos, a), (b_pos, b)| b.first_seen_at.cmp(&a.first_seen_at).then(b_pos.cmp(a_pos)));
+    records.into_iter().map(|(_, record)| record).collect()
+}
+
+#[async_trait]
+impl ScrapedAnnouncementRepository for MemoryRepository {
+    async fn store(&self, items: &[ScrapedAnnouncementWrite]) -> RepositoryResult<u64> {
+        Ok(self.update("kurum_duyuru_items", |docs| {
+            let mut stored = 0;
+            for item in items {
+                if docs.iter().any(|doc| doc.get_str("link").ok() == Some(item.link.as_str())) {
+                    continue;
+                }
+                let mut doc = item.to_document();
+                doc.insert("_id", ObjectId::new());
+                docs.push(doc);
+                stored += 1;
+            }
+            stored
+        }))
+    }
+
+    async fn list(&self, filter: &ScrapedAnnouncementFilter, page: Page) -> RepositoryResult<Vec<ScrapedAnnouncementRecord>> {
+        Ok(scraped_announcements(&self.documents("kurum_duyuru_items"), filter)
+            .into_iter()
+            .skip(page.offset as usize)
+            .take(page.limit as usize)
+            .collect())
+    }
+
+    async fn count(&self, filter: &ScrapedAnnouncementFilter) -> RepositoryResult<u64> {
+        Ok(scraped_announcements(&self.documents("kurum_duyuru_items"), filter).len() as u64)
+    }
+
+    async fn seen_after(&self, cursor: &ScanCursor, limit: u64) -> RepositoryResult<Vec<ScrapedAnnouncementRecord>> {
+        let mut records: Vec<ScrapedAnnouncementRecord> = self
+            .documents("kurum_duyuru_items")
+            .iter()
+            .map(ScrapedAnnouncementRecord::from_document)
+            .filter(|record| !cursor.covers(&record.first_seen_at, &record.id))
+            .collect();
+        records.sort_by(|a, b| a.first_seen_at.cmp(&b.first_seen_at).then_with(|| a.id.cmp(&b.id)));
+        records.truncate(limit as usize);
+        Ok(records)
+    }
+
+    async fn pending_details(&self, limit: u64) -> RepositoryResult<Vec<ScrapedAnnouncementRecord>> {
+        Ok(scraped_announcements(&self.documents("kurum_duyuru_items"), &ScrapedAnnouncementFilter::default())
+            .into_iter()
+            .filter(|record| record.details_fetched_at.is_none())
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn store_details(&self, id: &str, details: &AnnouncementDetailWrite) -> RepositoryResult<bool> {
+        Ok(self.update("kurum_duyuru_items", |docs| {
+            match docs.iter_mut().find(|doc| id_string(doc, "_id") == id) {
+                Some(doc) => {
+                    doc.extend(details.to_document());
+                    true
+                }
+                None => false,
+            }
+        }))
+    }
+}
+
+fn usage_count(doc: &MongoDocument) -> u64 {
+    doc.get_i64("count").unwrap_or(0) as u64
+}
+
+#[async_trait]
+impl ApiKeyRepository for MemoryRepository {
+    async fn insert(&self, key: &ApiKeyRecord) -> RepositoryResult<String> {
+        Ok(MemoryRepository::insert(self, "api_keys", key.to_document()))
+    }
+
+    async fn find_by_hash(&self, key_hash: &str) -> RepositoryResult<Option<ApiKeyRecord>> {
+        Ok(self
+            .documents("api_keys")
+            .iter()
+            .find(|doc| doc.get_str("key_hash").ok() == Some(key_hash))
+            .map(ApiKeyRecord::from_document))
+    }
+
+    async fn list(&self) -> RepositoryResult<Vec<ApiKeyRecord>> {
+        Ok(self
+            .documents("api_keys")
+            .iter()
+            .map(ApiKeyRecord::from_document)
+            .collect())
+    }
+
+    async fn revoke(&self, id_or_prefix: &str, revoked_at: &str) -> RepositoryResult<bool> {
+        Ok(self.update("api_keys", |docs| {
+            let key = docs.iter_mut().find(|doc| {
+                let record = ApiKeyRecord::from_document(doc);
+                !record.is_revoked() && (record.id == id_or_prefix || record.prefix == id_or_prefix)
+            });
+            match key {
+                Some(doc) => {
+                    doc.insert("revoked_at", revoked_at);
+                    true
+                }
+                None => false,
+            }
+        }))
+    }
+
+    async fn record_usage(&self, key_id: &str, day: &str, scope: &str) -> RepositoryResult<u64> {
+        Ok(self.update("api_key_usage", |docs| {
+            let position = docs.iter().position(|doc| {
+                doc.get_str("key_id").ok() == Some(key_id) && doc.get_str("day").ok() == Some(day)
+            });
+            let doc = match position {
+                Some(position) => &mut docs[position],
+                None => {
+                    docs.push(doc! { "_id": ObjectId::new(), "key_id": key_id, "day": day, "scopes": {} });
+                    docs.last_mut().expect("yeni eklenen doküman")
+                }
+            };
+
+            let count = usage_count(doc) + 1;
+            doc.insert("count", count as i64);
+            if let Ok(scopes) = doc.get_document_mut("scopes") {
+                let scope_count = scopes.get_i64(scope).unwrap_or(0) + 1;
+                scopes.insert(scope, scope_count);
+            }
+            count
+        }))
+    }
+
+    async fn usage(&self, key_id: &str, day: &str) -> RepositoryResult<u64> {
+        Ok(self
+            .documents("api_key_usage")
+            .iter()
+            .find(|doc| {
+                doc.get_str("key_id").ok() == Some(key_id) && doc.get_str("day").ok() == Some(day)
+            })
+            .map(usage_count)
+            .unwrap_or(0))
+    }
+}
+
+fn audit_matches(doc: &MongoDocument, filter: &AuditFilter) -> bool {
+    let field_matches = |field: &str, value: &Option<String>| {
+        value.as_deref().is_none_or(|value| doc.get_str(field).ok() == Some(value))
+    };
+    let timestamp = doc.get_str("timestamp").unwrap_or("");
+    field_matches("actor", &filter.actor)
+        && field_matches("action", &filter.action)
+        && field_matches("source", &filter.source)
+        && field_matches("collection", &filter.collection)
+        && field_matches("target_id", &filter.target_id)
+        && filter.from.as_deref().is_none_or(|from| timestamp >= from)
+        && filter.to.as_deref().is_none_or(|to| timestamp < to)
+}
+
+#[async_trait]
+impl AuditRepository for MemoryRepository {
+    async fn record(&self, entry: &AuditRecord) -> RepositoryResult<bool> {
+        Ok(self.update(AUDIT_COLLECTION, |docs| {
+            if docs.iter().any(|doc| id_string(doc, "_id") == entry.id) {
+                return false;
+            }
+            docs.push(entry.to_document());
+            true
+        }))
+    }
+
+    async fn list(&self, filter: &AuditFilter, page: Page) -> RepositoryResult<Vec<AuditRecord>> {
+        let mut records: Vec<AuditRecord> = self
+            .documents(AUDIT_COLLECTION)
+            .iter()
+            .filter(|doc| audit_matches(doc, filter))
+            .map(AuditRecord::from_document)
+            .collect();
+        records.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| b.id.cmp(&a.id)));
+        Ok(records
+            .into_iter()
+            .skip(page.offset as usize)
+            .take(page.limit as usize)
+            .collect())
+    }
+
+    async fn after(&self, filter: &AuditFilter, cursor: &ScanCursor, limit: u64) -> RepositoryResult<Vec<AuditRecord>> {
+        let mut records: Vec<AuditRecord> = self
+            .documents(AUDIT_COLLECTION)
+            .iter()
+            .filter(|doc| audit_matches(doc, filter))
+            .map(AuditRecord::from_document)
+            .filter(|record| !cursor.covers(&record.timestamp, &record.id))
+            .collect();
+        records.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+        records.truncate(limit as usize);
+        Ok(records)
+    }
+
+    async fn count(&self, filter: &AuditFilter) -> RepositoryResult<u64> {
+        Ok(self
+            .documents(AUDIT_COLLECTION)
+            .iter()
+            .filter(|doc| audit_matches(doc, filter))
+            .count() as u64)
+    }
+
+    async fn claim(&self, record_id: &str, event_id: &str) -> RepositoryResult<bool> {
+        Ok(self.update(AUDIT_CLAIMS_COLLECTION, |docs| {
+            match docs.iter().find(|doc| id_string(doc, "_id") == record_id) {
+                Some(doc) => doc.get_str("event_id") == Ok(event_id),
+                None => {
+                    docs.push(doc! { "_id": record_id, "event_id": event_id });
+                    true
+                }
+            }
+        }))
+    }
+}
+
+fn delivery_matches(doc: &MongoDocument, filter: &DeliveryFilter) -> bool {
+    let field_matches = |field: &str, value: &Option<String>| {
+        value.as_deref().is_none_or(|value| doc.get_str(field).ok() == Some(value))
+    };
+    field_matches("webhook_id", &filter.webhook_id) && field_matches("status", &filter.status)
+}
+
+#[async_trait]
+impl WebhookRepository for MemoryRepository {
+    async fn insert(&self, webhook: &WebhookWrite) -> RepositoryResult<String> {
+        Ok(MemoryRepository::insert(self, "webhooks", webhook.to_document()))
+    }
+
+    async fn update(&self, id: &str, webhook: &WebhookWrite) -> RepositoryResult<bool> {
+        Ok(self.update("webhooks", |docs| match docs.iter_mut().find(|doc| id_string(doc, "_id") == id) {
+            Some(doc) => {
+                doc.extend(webhook.to_document());
+                true
+            }
+            None => false,
+        }))
+    }
+
+    async fn delete(&self, id: &str) -> RepositoryResult<bool> {
+        let deleted = self.update("webhooks", |docs| {
+            let before = docs.len();
+            docs.retain(|doc| id_string(doc, "_id") != id);
+            docs.len() < before
+        });
+        if deleted {
+            self.update("webhook_deliveries", |docs| docs.retain(|doc| doc.get_str("webhook_id").ok() != Some(id)));
+        }
+        Ok(deleted)
+    }
+
+    async fn list(&self) -> RepositoryResult<Vec<WebhookRecord>> {
+        Ok(self.documents("webhooks").iter().map(WebhookRecord::from_document).collect())
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<WebhookRecord>> {
+        Ok(self
+            .documents("webhooks")
+            .iter()
+            .find(|doc| id_string(doc, "_id") == id)
+            .map(WebhookRecord::from_document))
+    }
+
+    async fn enqueue(&self, deliveries: &[DeliveryWrite]) -> RepositoryResult<Vec<String>> {
+        Ok(deliveries
+            .iter()
+            .map(|delivery| MemoryRepository::insert(self, "webhook_deliveries", delivery.to_document()))
+            .collect())
+    }
+
+    async fn due(&self, now: &str, limit: u64) -> RepositoryResult<Vec<DeliveryRecord>> {
+        let mut records: Vec<DeliveryRecord> = self
+            .documents("webhook_deliveries")
+            .iter()
+            .map(DeliveryRecord::from_document)
+            .filter(|record| record.status == "pending")
+            .filter(|record| record.next_attempt_at.as_deref().is_some_and(|next| next <= now))
+            .collect();
+        records.sort_by(|a, b| a.next_attempt_at.cmp(&b.next_attempt_at));
+        records.truncate(limit as usize);
+        Ok(records)
+    }
+
+    async fn record_attempt(&self, id: &str, attempt: &DeliveryAttempt) -> RepositoryResult<bool> {
+        Ok(self.update("webhook_deliveries", |docs| {
+            match docs.iter_mut().find(|doc| id_string(doc, "_id") == id) {
+                Some(doc) => {
+                    let attempts = DeliveryRecord::from_document(doc).attempts + 1;
+                    doc.extend(attempt.to_document());
+                    doc.insert("attempts", attempts as i32);
+                    true
+                }
+                None => false,
+            }
+        }))
+    }
+
+    async fn deliveries(&self, filter: &DeliveryFilter, page: Page) -> RepositoryResult<Vec<DeliveryRecord>> {
+        let mut records: Vec<DeliveryRecord> = self
+            .documents("webhook_deliveries")
+            .iter()
+            .filter(|doc| delivery_matches(doc, filter))
+            .map(DeliveryRecord::from_document)
+            .collect();
+        records.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.id.cmp(&a.id)));
+        Ok(records
+            .into_iter()
+            .skip(page.offset as usize)
+            .take(page.limit as usize)
+            .collect())
+    }
+
+    async fn count_deliveries(&self, filter: &DeliveryFilter) -> RepositoryResult<u64> {
+        Ok(self
+            .documents("webhook_deliveries")
+            .iter()
+            .filter(|doc| delivery_matches(doc, filter))
+            .count() as u64)
+    }
+
+    async fn find_delivery(&self, id: &str) -> RepositoryResult<Option<DeliveryRecord>> {
+        Ok(self
+            .documents("webhook_deliveries")
+            .iter()
+            .find(|doc| id_string(doc, "_id") == id)
+            .map(DeliveryRecord::from_document))
+    }
+
+    async fn cursor(&self, key: &str) -> RepositoryResult<Option<ScanCursor>> {
+        Ok(self
+            .documents("webhook_state")
+            .iter()
+            .find(|doc| doc.get_str("_id").ok() == Some(key))
+            .map(ScanCursor::from_document))
+    }
+
+    async fn save_cursor(&self, key: &str, cursor: &ScanCursor) -> RepositoryResult<()> {
+        self.update("webhook_state", |docs| {
+            docs.retain(|doc| doc.get_str("_id").ok() != Some(key));
+            let mut doc = cursor.to_document();
+            doc.insert("_id", key);
+            docs.push(doc);
+        });
+        Ok(())
+    }
+}
+
+fn alert_matches(doc: &MongoDocument, filter: &AlertFilter) -> bool {
+    let field_matches = |field: &str, value: &Option<String>| {
+        value.as_deref().is_none_or(|value| doc.get_str(field).ok() == Some(value))
+    };
+    field_matches("owner", &filter.owner)
+        && field_matches("saved_search_id", &filter.saved_search_id)
+        && (!filter.unread || doc.get_str("read_at").is_err())
+}
+
+#[async_trait]
+impl SavedSearchRepository for MemoryRepository {
+    async fn insert(&self, search: &SavedSearchWrite) -> RepositoryResult<String> {
+        Ok(MemoryRepository::insert(self, "saved_searches", search.to_document()))
+    }
+
+    async fn update(&self, id: &str, search: &SavedSearchWrite) -> RepositoryResult<bool> {
+        Ok(self.update("saved_searches", |docs| match docs.iter_mut().find(|doc| id_string(doc, "_id") == id) {
+            Some(doc) => {
+                doc.extend(search.to_document());
+                true
+            }
+            None => false,
+        }))
+    }
+
+    async fn delete(&self, id: &str) -> RepositoryResult<bool> {
+        let deleted = self.update("saved_searches", |docs| {
+            let before = docs.len();
+            docs.retain(|doc| id_string(doc, "_id") != id);
+            docs.len() < before
+        });
+        if deleted {
+            self.update("saved_search_alerts", |docs| {
+                docs.retain(|doc| doc.get_str("saved_search_id").ok() != Some(id))
+            });
+        }
+        Ok(deleted)
+    }
+
+    async fn list(&self, owner: Option<&str>) -> RepositoryResult<Vec<SavedSearchRecord>> {
+        Ok(self
+            .documents("saved_searches")
+            .iter()
+            .filter(|doc| owner.is_none_or(|owner| doc.get_str("owner").ok() == Some(owner)))
+            .map(SavedSearchRecord::from_document)
+            .collect())
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<SavedSearchRecord>> {
+        Ok(self
+            .documents("saved_searches")
+            .iter()
+            .find(|doc| id_string(doc, "_id") == id)
+            .map(SavedSearchRecord::from_document))
+    }
+
+    async fn record_run(&self, id: &str, run: &SavedSearchRun) -> RepositoryResult<bool> {
+        Ok(self.update("saved_searches", |docs| match docs.iter_mut().find(|doc| id_string(doc, "_id") == id) {
+            Some(doc) => {
+                doc.extend(run.to_document());
+                true
+            }
+            None => false,
+        }))
+    }
+
+    async fn insert_alert(&self, alert: &AlertWrite) -> RepositoryResult<String> {
+        Ok(MemoryRepository::insert(self, "saved_search_alerts", alert.to_document()))
+    }
+
+    async fn alerts(&self, filter: &AlertFilter, page: Page) -> RepositoryResult<Vec<AlertRecord>> {
+        let mut records: Vec<AlertRecord> = self
+            .documents("saved_search_alerts")
+            .iter()
+            .filter(|doc| alert_matches(doc, filter))
+            .map(AlertRecord::from_document)
+            .collect();
+        records.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.id.cmp(&a.id)));
+        Ok(records
+            .into_iter()
+            .skip(page.offset as usize)
+            .take(page.limit as usize)
+            .collect())
+    }
+
+    async fn count_alerts(&self, filter: &AlertFilter) -> RepositoryResult<u64> {
+        Ok(self
+            .documents("saved_search_alerts")
+            .iter()
+            .filter(|doc| alert_matches(doc, filter))
+            .count() as u64)
+    }
+
+    async fn find_alert(&self, id: &str) -> RepositoryResult<Option<AlertRecord>> {
+        Ok(self
+            .documents("saved_search_alerts")
+            .iter()
+            .find(|doc| id_string(doc, "_id") == id)
+            .map(AlertRecord::from_document))
+    }
+
+    async fn mark_alert_read(&self, id: &str, owner: &str, read_at: &str) -> RepositoryResult<bool> {
+        Ok(self.update("saved_search_alerts", |docs| {
+            match docs
+                .iter_mut()
+                .find(|doc| id_string(doc, "_id") == id && doc.get_str("owner").ok() == Some(owner))
+            {
+                Some(doc) => {
+                    if doc.get_str("read_at").is_err() {
+                        doc.insert("read_at", read_at);
+                    }
+                    true
+                }
+                None => false,
+            }
+        }))
+    }
+
+    async fn record_notification(&self, id: &str, status: &str, error: Option<&str>) -> RepositoryResult<bool> {
+        Ok(self.update("saved_search_alerts", |docs| {
+            match docs.iter_mut().find(|doc| id_string(doc, "_id") == id) {
+                Some(doc) => {
+                    doc.insert("notification_status", status);
+                    doc.insert("notification_error", error);
+                    true
+                }
+                None => false,
+            }
+        }))
+    }
+}
+
+#[async_trait]
+impl StorageHealth for MemoryRepository {
+    async fn ping(&self) -> RepositoryResult<()> {
+        Ok(())
+    }
+}
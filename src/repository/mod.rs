@@ -0,0 +1,478 @@
+pub mod memory;
+pub mod mongo;
+pub mod records;
+
+use std::fmt;
+use std::sync::Arc;
+use async_trait::async_trait;
+use mongodb::Client;
+use crate::audit::Actor;
+use crate::corpus::Corpus;
+
+pub use memory::MemoryRepository;
+pub use mongo::MongoRepository;
+pub use records::{
+    AlertHit, AlertRecord, AlertWrite, AnnouncementDetailWrite, AnnouncementSourceRecord, AnnouncementSourceWrite,
+    ApiKeyRecord, AttachmentRecord, AuditChange, AuditRecord, ContentRecord, DeliveryAttempt, DeliveryRecord,
+    DeliveryWrite, DocumentCountGroup, DocumentRecord, DocumentWrite, InstitutionRecord, InstitutionWrite, LinkRecord,
+    LinkWrite, SavedSearchRecord, SavedSearchRun, SavedSearchWrite, ScanCursor, ScrapedAnnouncementRecord,
+    ScrapedAnnouncementWrite, WebhookFilters, WebhookRecord, WebhookWrite,
+};
+
+// Depolama katmanı hataları
+#[derive(Debug)]
+pub enum RepositoryError {
+    Database(mongodb::error::Error),
+    InvalidQuery(String),
+    // Unique index ihlali (örn. idx_url_slug_unique); alan adı
+    Duplicate(String),
+}
+
+impl fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepositoryError::Database(e) => write!(f, "{}", e),
+            RepositoryError::InvalidQuery(message) => write!(f, "Geçersiz sorgu: {}", message),
+            RepositoryError::Duplicate(field) => write!(f, "{} değeri başka bir kayıtta kullanılıyor", field),
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {}
+
+impl From<mongodb::error::Error> for RepositoryError {
+    fn from(e: mongodb::error::Error) -> Self {
+        match mongo::duplicate_key_field(&e) {
+            Some(field) => RepositoryError::Duplicate(field),
+            None => RepositoryError::Database(e),
+        }
+    }
+}
+
+pub type RepositoryResult<T> = Result<T, RepositoryError>;
+
+// Belge listesi filtreleri
+#[derive(Debug, Clone, Default)]
+pub struct DocumentFilter {
+    pub kurum_id: Option<String>,
+    pub belge_turu: Option<String>,
+    // Virgülle ayrılmış etiketler içinde aranacak tek etiket
+    pub etiket: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DocumentSort {
+    pub field: String,
+    pub descending: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Page {
+    pub offset: u64,
+    pub limit: u64,
+}
+
+// Arama filtresi: tüm pattern'ler başlık alanında ya da tümü metin alanında
+// eşleşmelidir (büyük/küçük harf duyarsız regex)
+#[derive(Debug, Clone)]
+pub struct SearchFilter {
+    pub patterns: Vec<String>,
+    pub kurum_id: Option<String>,
+}
+
+#[async_trait]
+pub trait DocumentRepository: Send + Sync {
+    async fn list(
+        &self,
+        corpus: &Corpus,
+        filter: &DocumentFilter,
+        sort: &DocumentSort,
+        page: Page,
+    ) -> RepositoryResult<Vec<DocumentRecord>>;
+
+    async fn count(&self, corpus: &Corpus, filter: &DocumentFilter) -> RepositoryResult<u64>;
+
+    // Kuruma bağlı belge sayısı; kurum_id ObjectId ya da string olarak saklanmış olabilir
+    async fn count_for_kurum(&self, corpus: &Corpus, kurum_id: &str) -> RepositoryResult<u64>;
+
+    async fn find_by_slug(&self, corpus: &Corpus, slug: &str) -> RepositoryResult<Option<DocumentRecord>>;
+
+    // _id ObjectId ya da string olarak saklanmış olabilir
+    async fn find_by_id(&self, corpus: &Corpus, id: &str) -> RepositoryResult<Option<DocumentRecord>>;
+
+    // Oluşturulma tarihine göre en yeni belgeler (kurum bilgisiyle)
+    async fn recent(&self, corpus: &Corpus, limit: u64) -> RepositoryResult<Vec<DocumentRecord>>;
+
+    // İmleçten sonra oluşturulan belgeler, (oluşturulma tarihi, _id) sırasıyla en eski önce
+    // (kurum bilgisiyle); periyodik taramalar bununla sayfa sayfa ilerler
+    async fn created_after(&self, corpus: &Corpus, cursor: &ScanCursor, limit: u64) -> RepositoryResult<Vec<DocumentRecord>>;
+
+    // Oluşturulma tarihine göre sıralı arama sonuçları (kurum bilgisiyle)
+    async fn search(
+        &self,
+        corpus: &Corpus,
+        filter: &SearchFilter,
+        page: Page,
+    ) -> RepositoryResult<Vec<DocumentRecord>>;
+
+    async fn count_search(&self, corpus: &Corpus, filter: &SearchFilter) -> RepositoryResult<u64>;
+
+    // Aktif belgeler (sitemap için); yalnızca slug ve tarih alanları doldurulur
+    async fn active(
+        &self,
+        corpus: &Corpus,
+        sort_field: &str,
+        limit: Option<i64>,
+    ) -> RepositoryResult<Vec<DocumentRecord>>;
+
+    // Aktif belge sayısı (sitemap parçalarının sayısı için)
+    async fn count_active(&self, corpus: &Corpus) -> RepositoryResult<u64>;
+
+    // Aktif belgeler _id sırasıyla sayfa sayfa (parçalı sitemap için); active ile aynı alanlar doldurulur
+    async fn active_page(&self, corpus: &Corpus, page: Page) -> RepositoryResult<Vec<DocumentRecord>>;
+
+    // Kurum, belge türü ve yayın tarihi (corpus.fields.publication_date) başına belge sayısı
+    async fn count_groups(&self, corpus: &Corpus) -> RepositoryResult<Vec<DocumentCountGroup>>;
+
+    // Tekil belge türleri (alfabetik, boşlar "Belirtilmemiş")
+    async fn belge_turu_values(&self, corpus: &Corpus, kurum_id: Option<&str>) -> RepositoryResult<Vec<String>>;
+
+    // Tekil etiketler (alfabetik)
+    async fn etiket_values(&self, corpus: &Corpus, kurum_id: Option<&str>) -> RepositoryResult<Vec<String>>;
+
+    // pdf_url'si verilen adres olan belge (duyuru eklerinin alınıp alınmadığını görmek için)
+    async fn find_by_pdf_url(&self, corpus: &Corpus, pdf_url: &str) -> RepositoryResult<Option<DocumentRecord>>;
+}
+
+// Yönetim API'sinin yazma işlemleri. Metadata ve içerik birlikte yazılır; biri
+// başarısız olursa diğeri de geri alınır. Tüm yazma işlemleri değişiklikleri actor
+// ile birlikte aynı işlemde denetim kaydına (audit_log) ekler.
+#[async_trait]
+pub trait DocumentWriteRepository: Send + Sync {
+    // Yeni belgeyi ekler ve atanan id'yi döndürür
+    async fn insert(&self, actor: &Actor, corpus: &Corpus, document: &DocumentWrite) -> RepositoryResult<String>;
+
+    // Belgenin alanlarını günceller; belge bulunamazsa false
+    async fn update(&self, actor: &Actor, corpus: &Corpus, id: &str, document: &DocumentWrite) -> RepositoryResult<bool>;
+
+    // Belgeyi içeriğiyle birlikte siler; belge bulunamazsa false
+    async fn delete(&self, actor: &Actor, corpus: &Corpus, id: &str) -> RepositoryResult<bool>;
+}
+
+#[async_trait]
+pub trait InstitutionRepository: Send + Sync {
+    async fn list(&self) -> RepositoryResult<Vec<InstitutionRecord>>;
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<InstitutionRecord>>;
+
+    async fn count(&self) -> RepositoryResult<u64>;
+}
+
+// Kurum yazma işlemleri. Silme, kuruma ait duyuru linki ve faydalı linkleri de
+// birlikte siler; belgelere bağlı kurumların silinmemesi handler'da kontrol edilir.
+#[async_trait]
+pub trait InstitutionWriteRepository: Send + Sync {
+    async fn insert(&self, actor: &Actor, institution: &InstitutionWrite) -> RepositoryResult<String>;
+
+    // Kurum bulunamazsa false
+    async fn update(&self, actor: &Actor, id: &str, institution: &InstitutionWrite) -> RepositoryResult<bool>;
+
+    async fn delete(&self, actor: &Actor, id: &str) -> RepositoryResult<bool>;
+}
+
+#[async_trait]
+pub trait ContentRepository: Send + Sync {
+    // Belgenin içeriği; corpus tanımına göre ayrı koleksiyondan (Join) ya da
+    // belgenin kendisinden (Embedded) okunur
+    async fn find_for_document(
+        &self,
+        corpus: &Corpus,
+        document: &DocumentRecord,
+    ) -> RepositoryResult<Option<ContentRecord>>;
+}
+
+#[async_trait]
+pub trait LinkRepository: Send + Sync {
+    // kurum_id ObjectId ya da string olarak saklanmış olabilir
+    async fn list_by_kurum(&self, kurum_id: &str) -> RepositoryResult<Vec<LinkRecord>>;
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<LinkRecord>>;
+}
+
+#[async_trait]
+pub trait LinkWriteRepository: Send + Sync {
+    async fn insert(&self, actor: &Actor, link: &LinkWrite) -> RepositoryResult<String>;
+
+    // Link bulunamazsa false
+    async fn update(&self, actor: &Actor, id: &str, link: &LinkWrite) -> RepositoryResult<bool>;
+
+    async fn delete(&self, actor: &Actor, id: &str) -> RepositoryResult<bool>;
+}
+
+#[async_trait]
+pub trait AnnouncementSourceRepository: Send + Sync {
+    // En yeni önce
+    async fn list(&self, kurum_id: Option<&str>) -> RepositoryResult<Vec<AnnouncementSourceRecord>>;
+
+    async fn find_by_kurum(&self, kurum_id: &str) -> RepositoryResult<Option<AnnouncementSourceRecord>>;
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<AnnouncementSourceRecord>>;
+}
+
+// Hasat edilmiş duyuru filtreleri
+#[derive(Debug, Clone, Default)]
+pub struct ScrapedAnnouncementFilter {
+    pub kurum_id: Option<String>,
+    // Bu andan (dahil) sonra ilk görülenler; first_seen_at formatında
+    pub since: Option<String>,
+    // Başlıkta ya da detay metninde geçen ifade (büyük/küçük harf duyarsız)
+    pub q: Option<String>,
+    // Yalnızca eki (PDF/DOCX) bulunan duyurular
+    pub with_attachments: bool,
+}
+
+// Harvester'ın topladığı kurum duyuruları; link'e göre tekilleştirilir
+#[async_trait]
+pub trait ScrapedAnnouncementRepository: Send + Sync {
+    // Kaydı olmayan linkleri verilen sırayla ekler, eklenen sayısını döner. Aynı hasattaki
+    // kayıtlar listede eklenme sırasının tersiyle döner.
+    async fn store(&self, items: &[ScrapedAnnouncementWrite]) -> RepositoryResult<u64>;
+
+    // En yeni önce
+    async fn list(&self, filter: &ScrapedAnnouncementFilter, page: Page) -> RepositoryResult<Vec<ScrapedAnnouncementRecord>>;
+
+    async fn count(&self, filter: &ScrapedAnnouncementFilter) -> RepositoryResult<u64>;
+
+    // İmleçten sonra ilk görülen duyurular, (first_seen_at, _id) sırasıyla en eski önce
+    async fn seen_after(&self, cursor: &ScanCursor, limit: u64) -> RepositoryResult<Vec<ScrapedAnnouncementRecord>>;
+
+    // Detay sayfası henüz çekilmemiş duyurular, en yeni önce
+    async fn pending_details(&self, limit: u64) -> RepositoryResult<Vec<ScrapedAnnouncementRecord>>;
+
+    // Detay sayfası sonucunu yazar; kayıt bulunamazsa false
+    async fn store_details(&self, id: &str, details: &AnnouncementDetailWrite) -> RepositoryResult<bool>;
+}
+
+#[async_trait]
+pub trait AnnouncementSourceWriteRepository: Send + Sync {
+    async fn insert(&self, actor: &Actor, source: &AnnouncementSourceWrite) -> RepositoryResult<String>;
+
+    // Kayıt bulunamazsa false
+    async fn update(&self, actor: &Actor, id: &str, source: &AnnouncementSourceWrite) -> RepositoryResult<bool>;
+
+    async fn delete(&self, actor: &Actor, id: &str) -> RepositoryResult<bool>;
+}
+
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    // Kaydı ekler ve atanan id'yi döndürür
+    async fn insert(&self, key: &ApiKeyRecord) -> RepositoryResult<String>;
+
+    async fn find_by_hash(&self, key_hash: &str) -> RepositoryResult<Option<ApiKeyRecord>>;
+
+    // Oluşturulma sırasına göre tüm anahtarlar (iptal edilenler dahil)
+    async fn list(&self) -> RepositoryResult<Vec<ApiKeyRecord>>;
+
+    // id ya da prefix ile eşleşen aktif anahtarı iptal eder; bulunamazsa false
+    async fn revoke(&self, id_or_prefix: &str, revoked_at: &str) -> RepositoryResult<bool>;
+
+    // Anahtarın gün (YYYY-MM-DD) sayacını ve scope bazlı sayacını artırır,
+    // günün toplam istek sayısını döndürür
+    async fn record_usage(&self, key_id: &str, day: &str, scope: &str) -> RepositoryResult<u64>;
+
+    async fn usage(&self, key_id: &str, day: &str) -> RepositoryResult<u64>;
+}
+
+// Denetim kaydı filtreleri; zaman sınırları AuditRecord.timestamp formatındadır
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    pub source: Option<String>,
+    pub collection: Option<String>,
+    pub target_id: Option<String>,
+    // from dahil, to hariç
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+// Yalnızca ekleme ve okuma; denetim kayıtları değiştirilemez
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    // Kaydı ekler; aynı _id ile kayıt zaten varsa eklemez ve false döner
+    async fn record(&self, entry: &AuditRecord) -> RepositoryResult<bool>;
+
+    // En yeni kayıt önce
+    async fn list(&self, filter: &AuditFilter, page: Page) -> RepositoryResult<Vec<AuditRecord>>;
+
+    // Filtreye uyan, imleçten sonraki kayıtlar; (timestamp, _id) sırasıyla en eski önce
+    async fn after(&self, filter: &AuditFilter, cursor: &ScanCursor, limit: u64) -> RepositoryResult<Vec<AuditRecord>>;
+
+    async fn count(&self, filter: &AuditFilter) -> RepositoryResult<u64>;
+
+    // API/CLI kaydını change stream olayına (event_id) ayırır. Kayıt başka bir olaya ayrılmışsa
+    // false döner; aynı olay yeniden işlenirse (yeniden başlatma, ikinci sunucu) true döner
+    async fn claim(&self, record_id: &str, event_id: &str) -> RepositoryResult<bool>;
+}
+
+// Webhook teslimat kaydı filtreleri
+#[derive(Debug, Clone, Default)]
+pub struct DeliveryFilter {
+    pub webhook_id: Option<String>,
+    pub status: Option<String>,
+}
+
+// Webhook abonelikleri, teslimat kuyruğu/kaydı ve olay taramasının imleçleri
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    async fn insert(&self, webhook: &WebhookWrite) -> RepositoryResult<String>;
+
+    // Abonelik bulunamazsa false
+    async fn update(&self, id: &str, webhook: &WebhookWrite) -> RepositoryResult<bool>;
+
+    // Aboneliği teslimat kayıtlarıyla birlikte siler; bulunamazsa false
+    async fn delete(&self, id: &str) -> RepositoryResult<bool>;
+
+    // Oluşturulma sırasıyla
+    async fn list(&self) -> RepositoryResult<Vec<WebhookRecord>>;
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<WebhookRecord>>;
+
+    // Teslimatları kuyruğa ekler, atanan id'leri verilen sırayla döndürür
+    async fn enqueue(&self, deliveries: &[DeliveryWrite]) -> RepositoryResult<Vec<String>>;
+
+    // Zamanı gelmiş (next_attempt_at <= now) pending teslimatlar, en eski önce
+    async fn due(&self, now: &str, limit: u64) -> RepositoryResult<Vec<DeliveryRecord>>;
+
+    // Deneme sonucunu yazar ve deneme sayısını artırır; teslimat bulunamazsa false
+    async fn record_attempt(&self, id: &str, attempt: &DeliveryAttempt) -> RepositoryResult<bool>;
+
+    // En yeni önce
+    async fn deliveries(&self, filter: &DeliveryFilter, page: Page) -> RepositoryResult<Vec<DeliveryRecord>>;
+
+    async fn count_deliveries(&self, filter: &DeliveryFilter) -> RepositoryResult<u64>;
+
+    async fn find_delivery(&self, id: &str) -> RepositoryResult<Option<DeliveryRecord>>;
+
+    // Olay taramasının kaynak (corpus ya da duyurular) başına kaldığı yer
+    async fn cursor(&self, key: &str) -> RepositoryResult<Option<ScanCursor>>;
+
+    async fn save_cursor(&self, key: &str, cursor: &ScanCursor) -> RepositoryResult<()>;
+}
+
+// Kayıtlı arama bildirimlerinin (gelen kutusu) filtreleri
+#[derive(Debug, Clone, Default)]
+pub struct AlertFilter {
+    pub owner: Option<String>,
+    pub saved_search_id: Option<String>,
+    // true ise yalnızca okunmamışlar
+    pub unread: bool,
+}
+
+// Kayıtlı aramalar ve ürettikleri bildirimler
+#[async_trait]
+pub trait SavedSearchRepository: Send + Sync {
+    async fn insert(&self, search: &SavedSearchWrite) -> RepositoryResult<String>;
+
+    // Kayıtlı arama bulunamazsa false
+    async fn update(&self, id: &str, search: &SavedSearchWrite) -> RepositoryResult<bool>;
+
+    // Aramayı bildirimleriyle birlikte siler; bulunamazsa false
+    async fn delete(&self, id: &str) -> RepositoryResult<bool>;
+
+    // Oluşturulma sırasıyla; owner verilmezse tüm sahiplerinki
+    async fn list(&self, owner: Option<&str>) -> RepositoryResult<Vec<SavedSearchRecord>>;
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<SavedSearchRecord>>;
+
+    // Periyodik çalışmanın imlecini ve sonucunu yazar; bulunamazsa false
+    async fn record_run(&self, id: &str, run: &SavedSearchRun) -> RepositoryResult<bool>;
+
+    async fn insert_alert(&self, alert: &AlertWrite) -> RepositoryResult<String>;
+
+    // En yeni önce
+    async fn alerts(&self, filter: &AlertFilter, page: Page) -> RepositoryResult<Vec<AlertRecord>>;
+
+    async fn count_alerts(&self, filter: &AlertFilter) -> RepositoryResult<u64>;
+
+    async fn find_alert(&self, id: &str) -> RepositoryResult<Option<AlertRecord>>;
+
+    // Sahibinin bildirimini okundu işaretler; bulunamazsa ya da başkasınınsa false
+    async fn mark_alert_read(&self, id: &str, owner: &str, read_at: &str) -> RepositoryResult<bool>;
+
+    // Webhook/e-posta gönderiminin sonucu
+    async fn record_notification(&self, id: &str, status: &str, error: Option<&str>) -> RepositoryResult<bool>;
+}
+
+#[async_trait]
+pub trait StorageHealth: Send + Sync {
+    async fn ping(&self) -> RepositoryResult<()>;
+}
+
+// AppState üzerinden handler'lara verilen repository seti
+#[derive(Clone)]
+pub struct Repositories {
+    pub documents: Arc<dyn DocumentRepository>,
+    pub document_writes: Arc<dyn DocumentWriteRepository>,
+    pub institutions: Arc<dyn InstitutionRepository>,
+    pub institution_writes: Arc<dyn InstitutionWriteRepository>,
+    pub contents: Arc<dyn ContentRepository>,
+    pub links: Arc<dyn LinkRepository>,
+    pub link_writes: Arc<dyn LinkWriteRepository>,
+    pub announcement_sources: Arc<dyn AnnouncementSourceRepository>,
+    pub announcement_source_writes: Arc<dyn AnnouncementSourceWriteRepository>,
+    pub scraped_announcements: Arc<dyn ScrapedAnnouncementRepository>,
+    pub api_keys: Arc<dyn ApiKeyRepository>,
+    pub audit: Arc<dyn AuditRepository>,
+    pub webhooks: Arc<dyn WebhookRepository>,
+    pub saved_searches: Arc<dyn SavedSearchRepository>,
+    pub health: Arc<dyn StorageHealth>,
+}
+
+impl Repositories {
+    pub fn mongo(client: Client, db_name: &str) -> Self {
+        Self::from_shared(Arc::new(MongoRepository::new(client, db_name)))
+    }
+
+    pub fn memory(store: Arc<MemoryRepository>) -> Self {
+        Self::from_shared(store)
+    }
+
+    fn from_shared<R>(repository: Arc<R>) -> Self
+    where
+        R: DocumentRepository
+            + DocumentWriteRepository
+            + InstitutionRepository
+            + InstitutionWriteRepository
+            + ContentRepository
+            + LinkRepository
+            + LinkWriteRepository
+            + AnnouncementSourceRepository
+            + AnnouncementSourceWriteRepository
+            + ScrapedAnnouncementRepository
+            + ApiKeyRepository
+            + AuditRepository
+            + WebhookRepository
+            + SavedSearchRepository
+            + StorageHealth
+            + 'static,
+    {
+        Repositories {
+            documents: repository.clone(),
+            document_writes: repository.clone(),
+            institutions: repository.clone(),
+            institution_writes: repository.clone(),
+            contents: repository.clone(),
+            links: repository.clone(),
+            link_writes: repository.clone(),
+            announcement_sources: repository.clone(),
+            announcement_source_writes: repository.clone(),
+            scraped_announcements: repository.clone(),
+            api_keys: repository.clone(),
+            audit: repository.clone(),
+            webhooks: repository.clone(),
+            saved_searches: repository.clone(),
+            health: repository,
+        }
+    }
+}
@@ -0,0 +1,1459 @@
+use async_trait::async_trait;
+use futures::stream::TryStreamExt;
+use mongodb::{Client, ClientSession, Collection, Database, bson::{doc, oid::ObjectId, Bson, Document as MongoDocument}};
+use mongodb::error::{ErrorKind, TRANSIENT_TRANSACTION_ERROR};
+use mongodb::options::{FindOneAndUpdateOptions, FindOptions, ReplaceOptions, ReturnDocument, UpdateOptions};
+use crate::audit::{self, Actor, AUDIT_CLAIMS_COLLECTION, AUDIT_COLLECTION};
+use crate::corpus::{ContentSource, Corpus};
+use crate::dates;
+use super::{
+    AlertFilter, AnnouncementSourceRepository, AnnouncementSourceWriteRepository, ApiKeyRepository, AuditFilter,
+    AuditRepository, ContentRepository, DeliveryFilter, DocumentFilter, DocumentRepository, DocumentSort,
+    DocumentWriteRepository, InstitutionRepository, InstitutionWriteRepository, LinkRepository, LinkWriteRepository,
+    Page, RepositoryError, RepositoryResult, SavedSearchRepository, ScrapedAnnouncementFilter,
+    ScrapedAnnouncementRepository, SearchFilter, StorageHealth, WebhookRepository,
+};
+use super::records::{
+    canonical_id, collect_etiketler, id_string, AlertRecord, AlertWrite, AnnouncementDetailWrite,
+    AnnouncementSourceRecord, AnnouncementSourceWrite, ApiKeyRecord, AuditRecord, ContentRecord, DeliveryAttempt,
+    DeliveryRecord, DeliveryWrite, DocumentCountGroup, DocumentRecord, DocumentWrite, InstitutionRecord,
+    InstitutionWrite, LinkRecord, LinkWrite, SavedSearchRecord, SavedSearchRun, SavedSearchWrite, ScanCursor,
+    ScrapedAnnouncementRecord, ScrapedAnnouncementWrite, WebhookRecord, WebhookWrite,
+};
+
+// Geçici hata etiketli (TransientTransactionError) transaction'ın en fazla deneme sayısı
+const TRANSACTION_ATTEMPTS: u32 = 3;
+
+// MongoDB tabanlı repository; tüm koleksiyonlar tek Database üzerinden okunur,
+// client yalnızca transaction session'ı açmak için tutulur
+pub struct MongoRepository {
+    client: Client,
+    db: Database,
+}
+
+impl MongoRepository {
+    pub fn new(client: Client, db_name: &str) -> Self {
+        MongoRepository {
+            db: client.database(db_name),
+            client,
+        }
+    }
+
+    fn collection(&self, name: &str) -> Collection<MongoDocument> {
+        self.db.collection(name)
+    }
+
+    async fn aggregate_records(
+        &self,
+        corpus: &Corpus,
+        pipeline: Vec<MongoDocument>,
+    ) -> RepositoryResult<Vec<DocumentRecord>> {
+        let cursor = self.collection(&corpus.collection).aggregate(pipeline, None).await?;
+        let docs: Vec<MongoDocument> = cursor.try_collect().await?;
+        Ok(docs
+            .iter()
+            .map(|doc| DocumentRecord::from_document(doc, corpus))
+            .collect())
+    }
+
+    async fn find_raw(&self, collection: &str, filter: MongoDocument) -> RepositoryResult<Vec<MongoDocument>> {
+        let cursor = self.collection(collection).find(filter, None).await?;
+        Ok(cursor.try_collect().await?)
+    }
+
+    // Yeni dokümanı denetim kaydıyla birlikte ekler ve atanan id'yi döndürür
+    async fn insert_audited(&self, actor: &Actor, collection: &str, mut doc: MongoDocument) -> RepositoryResult<String> {
+        let oid = ObjectId::new();
+        doc.insert("_id", oid);
+        let ops = vec![WriteOp::Insert { collection: collection.to_string(), doc: doc.clone() }];
+        let undo = vec![WriteOp::Delete { collection: collection.to_string(), filter: doc! { "_id": oid } }];
+        self.write_audited(actor, ops, undo, Vec::new(), vec![(collection.to_string(), doc)]).await?;
+        Ok(oid.to_hex())
+    }
+
+    // _id'si eşleşen dokümanın alanlarını $set ile günceller; bulunamazsa false
+    async fn set_fields(&self, actor: &Actor, collection: &str, id: &str, fields: MongoDocument) -> RepositoryResult<bool> {
+        let Some(old) = self.find_raw(collection, id_filter("_id", id)).await?.into_iter().next() else {
+            return Ok(false);
+        };
+        let mut new = old.clone();
+        new.extend(fields.clone());
+
+        let ops = vec![WriteOp::Update {
+            collection: collection.to_string(),
+            filter: doc! { "_id": old.get("_id").cloned().unwrap_or(Bson::Null) },
+            update: doc! { "$set": fields },
+        }];
+        let undo = vec![WriteOp::Restore { collection: collection.to_string(), doc: old.clone() }];
+        self.write_audited(actor, ops, undo, vec![(collection.to_string(), old)], vec![(collection.to_string(), new)])
+            .await?;
+        Ok(true)
+    }
+
+    async fn remove(&self, actor: &Actor, collection: &str, id: &str) -> RepositoryResult<bool> {
+        let Some(old) = self.find_raw(collection, id_filter("_id", id)).await?.into_iter().next() else {
+            return Ok(false);
+        };
+        let ops = vec![WriteOp::Delete {
+            collection: collection.to_string(),
+            filter: doc! { "_id": old.get("_id").cloned().unwrap_or(Bson::Null) },
+        }];
+        let undo = vec![WriteOp::Restore { collection: collection.to_string(), doc: old.clone() }];
+        self.write_audited(actor, ops, undo, vec![(collection.to_string(), old)], Vec::new()).await?;
+        Ok(true)
+    }
+
+    async fn apply(&self, op: &WriteOp, session: Option<&mut ClientSession>) -> mongodb::error::Result<()> {
+        match (op, session) {
+            (WriteOp::Insert { collection, doc }, Some(session)) => {
+                self.collection(collection).insert_one_with_session(doc, None, session).await?;
+            }
+            (WriteOp::Insert { collection, doc }, None) => {
+                self.collection(collection).insert_one(doc, None).await?;
+            }
+            (WriteOp::Update { collection, filter, update }, Some(session)) => {
+                self.collection(collection)
+                    .update_many_with_session(filter.clone(), update.clone(), None, session)
+                    .await?;
+            }
+            (WriteOp::Update { collection, filter, update }, None) => {
+                self.collection(collection).update_many(filter.clone(), update.clone(), None).await?;
+            }
+            (WriteOp::Restore { collection, doc }, session) => {
+                let filter = doc! { "_id": doc.get("_id").cloned().unwrap_or(Bson::Null) };
+                let options = ReplaceOptions::builder().upsert(true).build();
+                let coll = self.collection(collection);
+                match session {
+                    Some(session) => coll.replace_one_with_session(filter, doc, options, session).await?,
+                    None => coll.replace_one(filter, doc, options).await?,
+                };
+            }
+            (WriteOp::Delete { collection, filter }, Some(session)) => {
+                self.collection(collection)
+                    .delete_many_with_session(filter.clone(), None, session)
+                    .await?;
+            }
+            (WriteOp::Delete { collection, filter }, None) => {
+                self.collection(collection).delete_many(filter.clone(), None).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_transaction(&self, session: &mut ClientSession, ops: &[WriteOp]) -> mongodb::error::Result<()> {
+        session.start_transaction(None).await?;
+        for op in ops {
+            if let Err(e) = self.apply(op, Some(&mut *session)).await {
+                let _ = session.abort_transaction().await;
+                return Err(e);
+            }
+        }
+        session.commit_transaction().await
+    }
+
+    // Adımları tek transaction içinde uygular; geçici transaction hataları (yazma çakışması,
+    // primary değişimi) birkaç kez yeniden denenir. Replica set olmayan tekil sunucularda
+    // transaction desteklenmez; bu durumda adımlar sırayla uygulanır ve biri başarısız
+    // olursa `undo` adımlarıyla önceki duruma dönülür.
+    async fn write_atomically(&self, ops: Vec<WriteOp>, undo: Vec<WriteOp>) -> RepositoryResult<()> {
+        let mut session = self.client.start_session(None).await?;
+        let mut attempt = 1;
+        loop {
+            match self.run_transaction(&mut session, &ops).await {
+                Ok(()) => return Ok(()),
+                Err(e) if e.contains_label(TRANSIENT_TRANSACTION_ERROR) && attempt < TRANSACTION_ATTEMPTS => {
+                    log::warn!(
+                        "Geçici transaction hatası, yeniden deneniyor ({}/{}): {}",
+                        attempt,
+                        TRANSACTION_ATTEMPTS,
+                        e
+                    );
+                    attempt += 1;
+                }
+                Err(e) if transactions_unsupported(&e) => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        log::warn!("MongoDB transaction desteklemiyor, yazma adımları sırayla uygulanıyor");
+
+        for op in &ops {
+            if let Err(e) = self.apply(op, None).await {
+                for undo_op in &undo {
+                    if let Err(undo_error) = self.apply(undo_op, None).await {
+                        log::error!("Yazma işlemi geri alınamadı: {}", undo_error);
+                    }
+                }
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+
+    // before/after dokümanlarından üretilen denetim kayıtlarını yazma adımlarına ekler;
+    // kayıtlar veriyle aynı transaction'da yazılır
+    async fn write_audited(
+        &self,
+        actor: &Actor,
+        mut ops: Vec<WriteOp>,
+        mut undo: Vec<WriteOp>,
+        before: Vec<(String, MongoDocument)>,
+        after: Vec<(String, MongoDocument)>,
+    ) -> RepositoryResult<()> {
+        for entry in audit::entries(actor, &before, &after) {
+            let doc = entry.to_document();
+            undo.push(WriteOp::Delete {
+                collection: AUDIT_COLLECTION.to_string(),
+                filter: doc! { "_id": doc.get("_id").cloned().unwrap_or(Bson::Null) },
+            });
+            ops.push(WriteOp::Insert { collection: AUDIT_COLLECTION.to_string(), doc });
+        }
+        self.write_atomically(ops, undo).await
+    }
+}
+
+// Çok adımlı yazma işlemlerinin adımları; transaction içinde ya da sırayla uygulanır
+enum WriteOp {
+    Insert { collection: String, doc: MongoDocument },
+    Update { collection: String, filter: MongoDocument, update: MongoDocument },
+    // Dokümanı _id'si ile verilen haline getirir (yoksa ekler); geri alma ve içerik yenileme
+    Restore { collection: String, doc: MongoDocument },
+    Delete { collection: String, filter: MongoDocument },
+}
+
+// Tekil sunucu: "Transaction numbers are only allowed on a replica set member or mongos" (kod 20).
+// Diğer hatalar (yetki, doğrulama, ağ) sıralı yazmaya düşmeden yukarı iletilir
+fn transactions_unsupported(e: &mongodb::error::Error) -> bool {
+    let command = matches!(e.kind.as_ref(), ErrorKind::Command(command) if command.code == 20);
+    command || e.to_string().contains("Transaction numbers are only allowed")
+}
+
+// _id ObjectId ya da string olarak saklanmış olabilir
+fn id_filter(field: &str, id: &str) -> MongoDocument {
+    match ObjectId::parse_str(id) {
+        Ok(oid) => doc! { "$or": [{ field: oid }, { field: id }] },
+        Err(_) => doc! { field: id },
+    }
+}
+
+// ScanCursor'dan sonraki kayıtlar: alanı watermark'tan büyük ya da ona eşit olup _id'si ids içinde
+// olmayanlar. Tarih Date olarak saklanmışsa watermark'ın Date karşılığıyla da karşılaştırılır.
+fn cursor_filter(field: &str, cursor: &ScanCursor) -> MongoDocument {
+    let seen: Vec<Bson> = cursor
+        .ids
+        .iter()
+        .flat_map(|id| ObjectId::parse_str(id).ok().map(Bson::ObjectId).into_iter().chain([Bson::String(id.clone())]))
+        .collect();
+    let mut watermarks = vec![Bson::String(cursor.watermark.clone())];
+    if let Ok(time) = dates::parse_timestamp(&cursor.watermark) {
+        watermarks.push(Bson::DateTime(mongodb::bson::DateTime::from_millis(time.timestamp_millis())));
+    }
+    let clauses: Vec<MongoDocument> = watermarks
+        .into_iter()
+        .flat_map(|watermark| {
+            [
+                doc! { field: { "$gt": watermark.clone() } },
+                doc! { field: watermark, "_id": { "$nin": seen.clone() } },
+            ]
+        })
+        .collect();
+    doc! { "$or": clauses }
+}
+
+fn kurum_filter(kurum_id: Option<&str>) -> MongoDocument {
+    match kurum_id {
+        Some(kurum_id) => doc! { "kurum_id": kurum_id },
+        None => doc! {},
+    }
+}
+
+fn document_filter(filter: &DocumentFilter) -> MongoDocument {
+    let mut match_filter = kurum_filter(filter.kurum_id.as_deref());
+
+    // belge_turu filtresi
+    if let Some(belge_turu) = filter.belge_turu.as_deref().filter(|s| !s.is_empty()) {
+        match_filter.insert("belge_turu", belge_turu);
+    }
+
+    // etiketler filtresi (virgülle ayrılmış string içinde arama)
+    if let Some(etiket) = filter.etiket.as_deref().filter(|s| !s.is_empty()) {
+        match_filter.insert("etiketler", doc! {
+            "$regex": etiket_pattern(etiket),
+            "$options": "i" // case insensitive
+        });
+    }
+
+    match_filter
+}
+
+// Etiketin virgülle ayrılmış listede geçip geçmediğini kontrol eden regex
+pub(crate) fn etiket_pattern(etiket: &str) -> String {
+    format!(r"(^|,\s*){}(,|$)", regex::escape(etiket))
+}
+
+fn search_filter(corpus: &Corpus, filter: &SearchFilter) -> RepositoryResult<MongoDocument> {
+    let title_field = corpus.fields.title.as_str();
+    let text_field = corpus.fields.search_text.as_str();
+
+    let mut match_filter = match filter.patterns.as_slice() {
+        [] => return Err(RepositoryError::InvalidQuery("boş arama".to_string())),
+        // Tek pattern için basit $or (daha hızlı)
+        [pattern] => doc! {
+            "$or": [
+                { title_field: doc! { "$regex": pattern, "$options": "i" } },
+                { text_field: doc! { "$regex": pattern, "$options": "i" } }
+            ]
+        },
+        // Çoklu kelime için: her alan için tüm kelimeler geçmeli
+        patterns => {
+            let conditions = |field: &str| -> Vec<MongoDocument> {
+                patterns
+                    .iter()
+                    .map(|pattern| doc! { field: doc! { "$regex": pattern, "$options": "i" } })
+                    .collect()
+            };
+            doc! {
+                "$or": [
+                    { "$and": conditions(title_field) },
+                    { "$and": conditions(text_field) }
+                ]
+            }
+        }
+    };
+
+    if let Some(kurum_id) = &filter.kurum_id {
+        match_filter.insert("kurum_id", kurum_id);
+    }
+
+    Ok(match_filter)
+}
+
+// kurum_id string ya da ObjectId olabilir; her iki durumda da kurumlar ile eşleştir
+fn kurum_lookup_stages() -> Vec<MongoDocument> {
+    vec![
+        doc! {
+            "$addFields": {
+                "kurum_id_object": {
+                    "$cond": {
+                        "if": { "$eq": [{ "$type": "$kurum_id" }, "string"] },
+                        "then": { "$toObjectId": "$kurum_id" },
+                        "else": "$kurum_id"
+                    }
+                }
+            }
+        },
+        doc! {
+            "$lookup": {
+                "from": "kurumlar",
+                "localField": "kurum_id_object",
+                "foreignField": "_id",
+                "as": "kurum_bilgisi"
+            }
+        },
+        doc! {
+            "$unwind": {
+                "path": "$kurum_bilgisi",
+                "preserveNullAndEmptyArrays": true
+            }
+        },
+    ]
+}
+
+// Boş veya eksik belge türlerini "Belirtilmemiş" altında grupla
+fn belge_turu_group_key() -> MongoDocument {
+    doc! {
+        "$cond": {
+            "if": { "$or": [
+                { "$eq": ["$belge_turu", null] },
+                { "$eq": ["$belge_turu", ""] }
+            ]},
+            "then": "Belirtilmemiş",
+            "else": "$belge_turu"
+        }
+    }
+}
+
+// $sum sonucu int32 ya da int64 olabilir
+fn count_value(doc: &MongoDocument) -> u64 {
+    match doc.get("count") {
+        Some(Bson::Int64(count)) => *count as u64,
+        Some(Bson::Int32(count)) => *count as u64,
+        _ => 0,
+    }
+}
+
+// Sitemap sorgularında okunan alanlar: slug ve lastmod için tarihler
+fn active_projection(corpus: &Corpus) -> MongoDocument {
+    let mut projection = doc! {
+        "url_slug": 1,
+        "belge_yayin_tarihi": 1,
+        "created_at": 1,
+    };
+    projection.insert(corpus.fields.created_at.as_str(), 1);
+    projection.insert(corpus.fields.publication_date.as_str(), 1);
+    projection
+}
+
+#[async_trait]
+impl DocumentRepository for MongoRepository {
+    async fn list(
+        &self,
+        corpus: &Corpus,
+        filter: &DocumentFilter,
+        sort: &DocumentSort,
+        page: Page,
+    ) -> RepositoryResult<Vec<DocumentRecord>> {
+        let sort_value = if sort.descending { -1 } else { 1 };
+        let mut pipeline = vec![
+            doc! { "$match": document_filter(filter) },
+            doc! { "$sort": { sort.field.as_str(): sort_value } },
+        ];
+
+        // Offset varsa $skip ekle
+        if page.offset > 0 {
+            pipeline.push(doc! { "$skip": page.offset as i64 });
+        }
+        pipeline.push(doc! { "$limit": page.limit as i64 });
+
+        self.aggregate_records(corpus, pipeline).await
+    }
+
+    async fn count_for_kurum(&self, corpus: &Corpus, kurum_id: &str) -> RepositoryResult<u64> {
+        Ok(self
+            .collection(&corpus.collection)
+            .count_documents(id_filter("kurum_id", kurum_id), None)
+            .await?)
+    }
+
+    async fn count(&self, corpus: &Corpus, filter: &DocumentFilter) -> RepositoryResult<u64> {
+        Ok(self
+            .collection(&corpus.collection)
+            .count_documents(document_filter(filter), None)
+            .await?)
+    }
+
+    async fn find_by_slug(&self, corpus: &Corpus, slug: &str) -> RepositoryResult<Option<DocumentRecord>> {
+        let doc = self
+            .collection(&corpus.collection)
+            .find_one(doc! { "url_slug": slug }, None)
+            .await?;
+        Ok(doc.map(|doc| DocumentRecord::from_document(&doc, corpus)))
+    }
+
+    async fn find_by_id(&self, corpus: &Corpus, id: &str) -> RepositoryResult<Option<DocumentRecord>> {
+        let doc = self
+            .collection(&corpus.collection)
+            .find_one(id_filter("_id", id), None)
+            .await?;
+        Ok(doc.map(|doc| DocumentRecord::from_document(&doc, corpus)))
+    }
+
+    async fn recent(&self, corpus: &Corpus, limit: u64) -> RepositoryResult<Vec<DocumentRecord>> {
+        let mut pipeline = vec![
+            doc! { "$sort": { corpus.fields.created_at.as_str(): -1 } }, // desc (en yeni önce)
+            doc! { "$limit": limit as i64 },
+        ];
+        if corpus.lookup_kurum {
+            pipeline.extend(kurum_lookup_stages());
+        }
+
+        self.aggregate_records(corpus, pipeline).await
+    }
+
+    async fn created_after(&self, corpus: &Corpus, cursor: &ScanCursor, limit: u64) -> RepositoryResult<Vec<DocumentRecord>> {
+        let field = corpus.fields.created_at.as_str();
+        let mut pipeline = vec![
+            doc! { "$match": cursor_filter(field, cursor) },
+            doc! { "$sort": { field: 1, "_id": 1 } }, // asc (en eski önce)
+            doc! { "$limit": limit as i64 },
+        ];
+        if corpus.lookup_kurum {
+            pipeline.extend(kurum_lookup_stages());
+        }
+
+        self.aggregate_records(corpus, pipeline).await
+    }
+
+    async fn search(
+        &self,
+        corpus: &Corpus,
+        filter: &SearchFilter,
+        page: Page,
+    ) -> RepositoryResult<Vec<DocumentRecord>> {
+        // $lookup ile kurum bilgilerini birleştir (N+1 query problemini çöz)
+        let mut pipeline = vec![doc! { "$match": search_filter(corpus, filter)? }];
+        if corpus.lookup_kurum {
+            pipeline.extend(kurum_lookup_stages());
+        }
+        pipeline.extend(vec![
+            doc! { "$sort": { corpus.fields.created_at.as_str(): -1 } },
+            doc! { "$skip": page.offset as i64 },
+            doc! { "$limit": page.limit as i64 },
+        ]);
+
+        self.aggregate_records(corpus, pipeline).await
+    }
+
+    async fn count_search(&self, corpus: &Corpus, filter: &SearchFilter) -> RepositoryResult<u64> {
+        Ok(self
+            .collection(&corpus.collection)
+            .count_documents(search_filter(corpus, filter)?, None)
+            .await?)
+    }
+
+    async fn active(
+        &self,
+        corpus: &Corpus,
+        sort_field: &str,
+        limit: Option<i64>,
+    ) -> RepositoryResult<Vec<DocumentRecord>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { sort_field: -1 })
+            .projection(active_projection(corpus))
+            .limit(limit)
+            .build();
+
+        let cursor = self
+            .collection(&corpus.collection)
+            .find(doc! { "status": &corpus.fields.active_status }, find_options)
+            .await?;
+        let docs: Vec<MongoDocument> = cursor.try_collect().await?;
+
+        Ok(docs
+            .iter()
+            .map(|doc| DocumentRecord::from_document(doc, corpus))
+            .collect())
+    }
+
+    async fn count_active(&self, corpus: &Corpus) -> RepositoryResult<u64> {
+        Ok(self
+            .collection(&corpus.collection)
+            .count_documents(doc! { "status": &corpus.fields.active_status }, None)
+            .await?)
+    }
+
+    async fn active_page(&self, corpus: &Corpus, page: Page) -> RepositoryResult<Vec<DocumentRecord>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "_id": 1 })
+            .projection(active_projection(corpus))
+            .skip(page.offset)
+            .limit(page.limit as i64)
+            .build();
+        let docs = self
+            .collection(&corpus.collection)
+            .find(doc! { "status": &corpus.fields.active_status }, find_options)
+            .await?
+            .try_collect::<Vec<MongoDocument>>()
+            .await?;
+        Ok(docs
+            .iter()
+            .map(|doc| DocumentRecord::from_document(doc, corpus))
+            .collect())
+    }
+
+    async fn count_groups(&self, corpus: &Corpus) -> RepositoryResult<Vec<DocumentCountGroup>> {
+        let pipeline = vec![doc! {
+            "$group": {
+                "_id": {
+                    "kurum_id": "$kurum_id",
+                    "belge_turu": belge_turu_group_key(),
+                    "tarih": format!("${}", corpus.fields.publication_date),
+                },
+                "count": { "$sum": 1 }
+            }
+        }];
+
+        let cursor = self.collection(&corpus.collection).aggregate(pipeline, None).await?;
+        let docs: Vec<MongoDocument> = cursor.try_collect().await?;
+
+        Ok(docs
+            .iter()
+            .filter_map(|doc| {
+                let key = doc.get_document("_id").ok()?;
+                Some(DocumentCountGroup {
+                    kurum_id: id_string(key, "kurum_id"),
+                    belge_turu: key.get_str("belge_turu").unwrap_or("Belirtilmemiş").to_string(),
+                    yayin_tarihi: key.get_str("tarih").ok().map(str::to_string),
+                    count: count_value(doc),
+                })
+            })
+            .collect())
+    }
+
+    async fn belge_turu_values(&self, corpus: &Corpus, kurum_id: Option<&str>) -> RepositoryResult<Vec<String>> {
+        let pipeline = vec![
+            doc! { "$match": kurum_filter(kurum_id) },
+            doc! { "$group": { "_id": belge_turu_group_key() } },
+            doc! { "$sort": { "_id": 1 } },
+        ];
+
+        let cursor = self.collection(&corpus.collection).aggregate(pipeline, None).await?;
+        let docs: Vec<MongoDocument> = cursor.try_collect().await?;
+
+        Ok(docs
+            .iter()
+            .filter_map(|doc| doc.get_str("_id").ok().map(|s| s.to_string()))
+            .collect())
+    }
+
+    async fn etiket_values(&self, corpus: &Corpus, kurum_id: Option<&str>) -> RepositoryResult<Vec<String>> {
+        let find_options = FindOptions::builder()
+            .projection(doc! { "etiketler": 1 })
+            .build();
+
+        let cursor = self
+            .collection(&corpus.collection)
+            .find(kurum_filter(kurum_id), find_options)
+            .await?;
+        let docs: Vec<MongoDocument> = cursor.try_collect().await?;
+
+        Ok(collect_etiketler(docs.iter().filter_map(|doc| doc.get_str("etiketler").ok())))
+    }
+
+    async fn find_by_pdf_url(&self, corpus: &Corpus, pdf_url: &str) -> RepositoryResult<Option<DocumentRecord>> {
+        let doc = self
+            .collection(&corpus.collection)
+            .find_one(doc! { "pdf_url": pdf_url }, None)
+            .await?;
+        Ok(doc.map(|doc| DocumentRecord::from_document(&doc, corpus)))
+    }
+}
+
+#[async_trait]
+impl DocumentWriteRepository for MongoRepository {
+    async fn insert(&self, actor: &Actor, corpus: &Corpus, document: &DocumentWrite) -> RepositoryResult<String> {
+        let oid = ObjectId::new();
+        let mut metadata = document.metadata_fields(corpus);
+        metadata.insert("_id", oid);
+
+        let mut ops = vec![WriteOp::Insert { collection: corpus.collection.clone(), doc: metadata.clone() }];
+        let mut undo = vec![WriteOp::Delete { collection: corpus.collection.clone(), filter: doc! { "_id": oid } }];
+        let mut after = vec![(corpus.collection.clone(), metadata)];
+        if let ContentSource::Join { collection, foreign_key } = &corpus.content {
+            if let Some(mut content) = document.content_document(corpus, Bson::ObjectId(oid)) {
+                content.insert("_id", ObjectId::new());
+                ops.push(WriteOp::Insert { collection: collection.clone(), doc: content.clone() });
+                undo.push(WriteOp::Delete { collection: collection.clone(), filter: doc! { foreign_key.as_str(): oid } });
+                after.push((collection.clone(), content));
+            }
+        }
+
+        self.write_audited(actor, ops, undo, Vec::new(), after).await?;
+        Ok(oid.to_hex())
+    }
+
+    async fn update(&self, actor: &Actor, corpus: &Corpus, id: &str, document: &DocumentWrite) -> RepositoryResult<bool> {
+        let Some(old) = self.find_raw(&corpus.collection, id_filter("_id", id)).await?.into_iter().next() else {
+            return Ok(false);
+        };
+        let raw_id = old.get("_id").cloned().unwrap_or(Bson::Null);
+        let metadata_fields = document.metadata_fields(corpus);
+        let mut new = old.clone();
+        new.extend(metadata_fields.clone());
+
+        let mut ops = vec![WriteOp::Update {
+            collection: corpus.collection.clone(),
+            filter: doc! { "_id": raw_id.clone() },
+            update: doc! { "$set": metadata_fields },
+        }];
+        let mut undo = vec![WriteOp::Restore { collection: corpus.collection.clone(), doc: old.clone() }];
+        let mut before = vec![(corpus.collection.clone(), old)];
+        let mut after = vec![(corpus.collection.clone(), new)];
+
+        if let ContentSource::Join { collection, foreign_key } = &corpus.content {
+            let content_filter = id_filter(foreign_key, id);
+            let old_contents = self.find_raw(collection, content_filter.clone()).await?;
+            let metadata_id = canonical_id(&raw_id);
+
+            match document.content_document(corpus, metadata_id.clone()) {
+                Some(mut content) => {
+                    // Mevcut içerik kaydının _id'si korunur
+                    let content_id = old_contents
+                        .first()
+                        .and_then(|doc| doc.get("_id").cloned())
+                        .unwrap_or_else(|| Bson::ObjectId(ObjectId::new()));
+                    content.insert("_id", content_id.clone());
+                    // Fazladan içerik kayıtları silinir, kalan kayıt yerinde değiştirilir
+                    if old_contents.len() > 1 {
+                        ops.push(WriteOp::Delete {
+                            collection: collection.clone(),
+                            filter: doc! { "$and": [content_filter.clone(), { "_id": { "$ne": content_id } }] },
+                        });
+                    }
+                    ops.push(WriteOp::Restore { collection: collection.clone(), doc: content.clone() });
+                    after.push((collection.clone(), content));
+                }
+                // İçerik değişmese de foreign key tipi normalize edilir
+                None => {
+                    ops.push(WriteOp::Update {
+                        collection: collection.clone(),
+                        filter: content_filter.clone(),
+                        update: doc! { "$set": { foreign_key.as_str(): metadata_id.clone() } },
+                    });
+                    after.extend(old_contents.iter().map(|doc| {
+                        let mut doc = doc.clone();
+                        doc.insert(foreign_key.as_str(), metadata_id.clone());
+                        (collection.clone(), doc)
+                    }));
+                }
+            }
+
+            undo.push(WriteOp::Delete { collection: collection.clone(), filter: content_filter });
+            undo.extend(old_contents.iter().map(|doc| WriteOp::Restore { collection: collection.clone(), doc: doc.clone() }));
+            before.extend(old_contents.into_iter().map(|doc| (collection.clone(), doc)));
+        }
+
+        self.write_audited(actor, ops, undo, before, after).await?;
+        Ok(true)
+    }
+
+    async fn delete(&self, actor: &Actor, corpus: &Corpus, id: &str) -> RepositoryResult<bool> {
+        let Some(old) = self.find_raw(&corpus.collection, id_filter("_id", id)).await?.into_iter().next() else {
+            return Ok(false);
+        };
+        let raw_id = old.get("_id").cloned().unwrap_or(Bson::Null);
+
+        let mut ops = Vec::new();
+        let mut undo = vec![WriteOp::Restore { collection: corpus.collection.clone(), doc: old.clone() }];
+        let mut before = vec![(corpus.collection.clone(), old)];
+        if let ContentSource::Join { collection, foreign_key } = &corpus.content {
+            let content_filter = id_filter(foreign_key, id);
+            let old_contents = self.find_raw(collection, content_filter.clone()).await?;
+            ops.push(WriteOp::Delete { collection: collection.clone(), filter: content_filter });
+            undo.extend(old_contents.iter().map(|doc| WriteOp::Restore { collection: collection.clone(), doc: doc.clone() }));
+            before.extend(old_contents.into_iter().map(|doc| (collection.clone(), doc)));
+        }
+        ops.push(WriteOp::Delete { collection: corpus.collection.clone(), filter: doc! { "_id": raw_id } });
+
+        self.write_audited(actor, ops, undo, before, Vec::new()).await?;
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl InstitutionRepository for MongoRepository {
+    async fn list(&self) -> RepositoryResult<Vec<InstitutionRecord>> {
+        let cursor = self.collection("kurumlar").find(None, None).await?;
+        let docs: Vec<MongoDocument> = cursor.try_collect().await?;
+        Ok(docs.iter().map(InstitutionRecord::from_document).collect())
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<InstitutionRecord>> {
+        let Ok(oid) = ObjectId::parse_str(id) else {
+            return Ok(None);
+        };
+        let doc = self.collection("kurumlar").find_one(doc! { "_id": oid }, None).await?;
+        Ok(doc.as_ref().map(InstitutionRecord::from_document))
+    }
+
+    async fn count(&self) -> RepositoryResult<u64> {
+        Ok(self.collection("kurumlar").count_documents(doc! {}, None).await?)
+    }
+}
+
+#[async_trait]
+impl InstitutionWriteRepository for MongoRepository {
+    async fn insert(&self, actor: &Actor, institution: &InstitutionWrite) -> RepositoryResult<String> {
+        self.insert_audited(actor, "kurumlar", institution.to_document()).await
+    }
+
+    async fn update(&self, actor: &Actor, id: &str, institution: &InstitutionWrite) -> RepositoryResult<bool> {
+        self.set_fields(actor, "kurumlar", id, institution.to_document()).await
+    }
+
+    async fn delete(&self, actor: &Actor, id: &str) -> RepositoryResult<bool> {
+        let Some(old) = self.find_raw("kurumlar", id_filter("_id", id)).await?.into_iter().next() else {
+            return Ok(false);
+        };
+        let raw_id = old.get("_id").cloned().unwrap_or(Bson::Null);
+
+        let mut ops = Vec::new();
+        let mut undo = vec![WriteOp::Restore { collection: "kurumlar".to_string(), doc: old.clone() }];
+        let mut before = vec![("kurumlar".to_string(), old)];
+        for collection in ["kurum_duyuru", "links"] {
+            let filter = id_filter("kurum_id", id);
+            let old_docs = self.find_raw(collection, filter.clone()).await?;
+            ops.push(WriteOp::Delete { collection: collection.to_string(), filter });
+            undo.extend(old_docs.iter().map(|doc| WriteOp::Restore { collection: collection.to_string(), doc: doc.clone() }));
+            before.extend(old_docs.into_iter().map(|doc| (collection.to_string(), doc)));
+        }
+        ops.push(WriteOp::Delete { collection: "kurumlar".to_string(), filter: doc! { "_id": raw_id } });
+
+        self.write_audited(actor, ops, undo, before, Vec::new()).await?;
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl ContentRepository for MongoRepository {
+    async fn find_for_document(
+        &self,
+        corpus: &Corpus,
+        document: &DocumentRecord,
+    ) -> RepositoryResult<Option<ContentRecord>> {
+        let (collection, foreign_key) = match &corpus.content {
+            ContentSource::Embedded => {
+                return Ok(Some(ContentRecord {
+                    id: String::new(),
+                    icerik: document.icerik.clone().unwrap_or_default(),
+                    olusturulma_tarihi: Some(document.olusturulma_tarihi.clone())
+                        .filter(|s| !s.is_empty()),
+                }));
+            }
+            ContentSource::Join { collection, foreign_key } => (collection, foreign_key.as_str()),
+        };
+
+        let content_collection = self.collection(collection);
+
+        // Önce ObjectId ile dene
+        if let Ok(oid) = ObjectId::parse_str(&document.id) {
+            if let Some(doc) = content_collection.find_one(doc! { foreign_key: oid }, None).await? {
+                return Ok(Some(ContentRecord::from_document(&doc, corpus)));
+            }
+        }
+
+        // String ile dene (fallback)
+        let doc = content_collection
+            .find_one(doc! { foreign_key: &document.id }, None)
+            .await?;
+        Ok(doc.map(|doc| ContentRecord::from_document(&doc, corpus)))
+    }
+}
+
+#[async_trait]
+impl LinkRepository for MongoRepository {
+    async fn list_by_kurum(&self, kurum_id: &str) -> RepositoryResult<Vec<LinkRecord>> {
+        // Hem ObjectID hem string formatını dene
+        let filter = match ObjectId::parse_str(kurum_id) {
+            Ok(oid) => doc! { "$or": [{ "kurum_id": oid }, { "kurum_id": kurum_id }] },
+            Err(_) => doc! { "kurum_id": kurum_id },
+        };
+
+        let cursor = self.collection("links").find(filter, None).await?;
+        let docs: Vec<MongoDocument> = cursor.try_collect().await?;
+        Ok(docs.iter().map(LinkRecord::from_document).collect())
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<LinkRecord>> {
+        let doc = self.collection("links").find_one(id_filter("_id", id), None).await?;
+        Ok(doc.as_ref().map(LinkRecord::from_document))
+    }
+}
+
+#[async_trait]
+impl LinkWriteRepository for MongoRepository {
+    async fn insert(&self, actor: &Actor, link: &LinkWrite) -> RepositoryResult<String> {
+        self.insert_audited(actor, "links", link.to_document()).await
+    }
+
+    async fn update(&self, actor: &Actor, id: &str, link: &LinkWrite) -> RepositoryResult<bool> {
+        self.set_fields(actor, "links", id, link.to_document()).await
+    }
+
+    async fn delete(&self, actor: &Actor, id: &str) -> RepositoryResult<bool> {
+        self.remove(actor, "links", id).await
+    }
+}
+
+#[async_trait]
+impl AnnouncementSourceRepository for MongoRepository {
+    async fn list(&self, kurum_id: Option<&str>) -> RepositoryResult<Vec<AnnouncementSourceRecord>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "_id": -1 }) // En yeni önce
+            .build();
+
+        let cursor = self
+            .collection("kurum_duyuru")
+            .find(kurum_filter(kurum_id), find_options)
+            .await?;
+        let docs: Vec<MongoDocument> = cursor.try_collect().await?;
+        Ok(docs.iter().map(AnnouncementSourceRecord::from_document).collect())
+    }
+
+    async fn find_by_kurum(&self, kurum_id: &str) -> RepositoryResult<Option<AnnouncementSourceRecord>> {
+        let doc = self
+            .collection("kurum_duyuru")
+            .find_one(kurum_filter(Some(kurum_id)), None)
+            .await?;
+        Ok(doc.as_ref().map(AnnouncementSourceRecord::from_document))
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<AnnouncementSourceRecord>> {
+        let doc = self.collection("kurum_duyuru").find_one(id_filter("_id", id), None).await?;
+        Ok(doc.as_ref().map(AnnouncementSourceRecord::from_document))
+    }
+}
+
+#[async_trait]
+impl AnnouncementSourceWriteRepository for MongoRepository {
+    async fn insert(&self, actor: &Actor, source: &AnnouncementSourceWrite) -> RepositoryResult<String> {
+        self.insert_audited(actor, "kurum_duyuru", source.to_document()).await
+    }
+
+    async fn update(&self, actor: &Actor, id: &str, source: &AnnouncementSourceWrite) -> RepositoryResult<bool> {
+        self.set_fields(actor, "kurum_duyuru", id, source.to_document()).await
+    }
+
+    async fn delete(&self, actor: &Actor, id: &str) -> RepositoryResult<bool> {
+        self.remove(actor, "kurum_duyuru", id).await
+    }
+}
+
+// Aktif (iptal edilmemiş) ve id ya da prefix'i eşleşen anahtar
+fn active_key_filter(id_or_prefix: &str) -> MongoDocument {
+    let key = match ObjectId::parse_str(id_or_prefix) {
+        Ok(oid) => doc! { "$or": [{ "_id": oid }, { "prefix": id_or_prefix }] },
+        Err(_) => doc! { "prefix": id_or_prefix },
+    };
+    doc! { "$and": [key, { "revoked_at": null }] }
+}
+
+#[async_trait]
+impl ApiKeyRepository for MongoRepository {
+    async fn insert(&self, key: &ApiKeyRecord) -> RepositoryResult<String> {
+        let result = self.collection("api_keys").insert_one(key.to_document(), None).await?;
+        Ok(match result.inserted_id {
+            Bson::ObjectId(oid) => oid.to_hex(),
+            other => other.to_string(),
+        })
+    }
+
+    async fn find_by_hash(&self, key_hash: &str) -> RepositoryResult<Option<ApiKeyRecord>> {
+        let doc = self
+            .collection("api_keys")
+            .find_one(doc! { "key_hash": key_hash }, None)
+            .await?;
+        Ok(doc.as_ref().map(ApiKeyRecord::from_document))
+    }
+
+    async fn list(&self) -> RepositoryResult<Vec<ApiKeyRecord>> {
+        let find_options = FindOptions::builder().sort(doc! { "_id": 1 }).build();
+        let cursor = self.collection("api_keys").find(None, find_options).await?;
+        let docs: Vec<MongoDocument> = cursor.try_collect().await?;
+        Ok(docs.iter().map(ApiKeyRecord::from_document).collect())
+    }
+
+    async fn revoke(&self, id_or_prefix: &str, revoked_at: &str) -> RepositoryResult<bool> {
+        let result = self
+            .collection("api_keys")
+            .update_one(
+                active_key_filter(id_or_prefix),
+                doc! { "$set": { "revoked_at": revoked_at } },
+                None,
+            )
+            .await?;
+        Ok(result.modified_count > 0)
+    }
+
+    async fn record_usage(&self, key_id: &str, day: &str, scope: &str) -> RepositoryResult<u64> {
+        let options = FindOneAndUpdateOptions::builder()
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let doc = self
+            .collection("api_key_usage")
+            .find_one_and_update(
+                doc! { "key_id": key_id, "day": day },
+                doc! { "$inc": { "count": 1, format!("scopes.{}", scope): 1 } },
+                options,
+            )
+            .await?;
+        Ok(doc.as_ref().map(count_value).unwrap_or(0))
+    }
+
+    async fn usage(&self, key_id: &str, day: &str) -> RepositoryResult<u64> {
+        let doc = self
+            .collection("api_key_usage")
+            .find_one(doc! { "key_id": key_id, "day": day }, None)
+            .await?;
+        Ok(doc.as_ref().map(count_value).unwrap_or(0))
+    }
+}
+
+fn scraped_announcement_filter(filter: &ScrapedAnnouncementFilter) -> MongoDocument {
+    let mut match_filter = kurum_filter(filter.kurum_id.as_deref());
+    if let Some(since) = &filter.since {
+        match_filter.insert("first_seen_at", doc! { "$gte": since });
+    }
+    // İfade regex olarak değil, düz metin olarak aranır
+    if let Some(q) = filter.q.as_deref().filter(|q| !q.is_empty()) {
+        let pattern = regex::escape(q);
+        match_filter.insert("$or", vec![
+            doc! { "baslik": { "$regex": &pattern, "$options": "i" } },
+            doc! { "icerik": { "$regex": &pattern, "$options": "i" } },
+        ]);
+    }
+    if filter.with_attachments {
+        match_filter.insert("ekler.0", doc! { "$exists": true });
+    }
+    match_filter
+}
+
+#[async_trait]
+impl ScrapedAnnouncementRepository for MongoRepository {
+    async fn store(&self, items: &[ScrapedAnnouncementWrite]) -> RepositoryResult<u64> {
+        let collection = self.collection("kurum_duyuru_items");
+        let mut stored = 0;
+        for item in items {
+            let result = collection
+                .update_one(
+                    doc! { "link": &item.link },
+                    doc! { "$setOnInsert": item.to_document() },
+                    UpdateOptions::builder().upsert(true).build(),
+                )
+                .await;
+            match result {
+                Ok(result) if result.upserted_id.is_some() => stored += 1,
+                Ok(_) => {}
+                // Aynı anda çalışan başka bir hasat linki eklemiş (link unique index'i)
+                Err(e) if duplicate_key(&e) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(stored)
+    }
+
+    async fn list(&self, filter: &ScrapedAnnouncementFilter, page: Page) -> RepositoryResult<Vec<ScrapedAnnouncementRecord>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "first_seen_at": -1, "_id": -1 })
+            .skip(page.offset)
+            .limit(page.limit as i64)
+            .build();
+        let docs = self
+            .collection("kurum_duyuru_items")
+            .find(scraped_announcement_filter(filter), find_options)
+            .await?
+            .try_collect::<Vec<MongoDocument>>()
+            .await?;
+        Ok(docs.iter().map(ScrapedAnnouncementRecord::from_document).collect())
+    }
+
+    async fn count(&self, filter: &ScrapedAnnouncementFilter) -> RepositoryResult<u64> {
+        Ok(self
+            .collection("kurum_duyuru_items")
+            .count_documents(scraped_announcement_filter(filter), None)
+            .await?)
+    }
+
+    async fn seen_after(&self, cursor: &ScanCursor, limit: u64) -> RepositoryResult<Vec<ScrapedAnnouncementRecord>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "first_seen_at": 1, "_id": 1 })
+            .limit(limit as i64)
+            .build();
+        let docs = self
+            .collection("kurum_duyuru_items")
+            .find(cursor_filter("first_seen_at", cursor), find_options)
+            .await?
+            .try_collect::<Vec<MongoDocument>>()
+            .await?;
+        Ok(docs.iter().map(ScrapedAnnouncementRecord::from_document).collect())
+    }
+
+    async fn pending_details(&self, limit: u64) -> RepositoryResult<Vec<ScrapedAnnouncementRecord>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "first_seen_at": -1, "_id": -1 })
+            .limit(limit as i64)
+            .build();
+        let docs = self
+            .collection("kurum_duyuru_items")
+            .find(doc! { "details_fetched_at": null }, find_options)
+            .await?
+            .try_collect::<Vec<MongoDocument>>()
+            .await?;
+        Ok(docs.iter().map(ScrapedAnnouncementRecord::from_document).collect())
+    }
+
+    async fn store_details(&self, id: &str, details: &AnnouncementDetailWrite) -> RepositoryResult<bool> {
+        let result = self
+            .collection("kurum_duyuru_items")
+            .update_one(id_filter("_id", id), doc! { "$set": details.to_document() }, None)
+            .await?;
+        Ok(result.matched_count > 0)
+    }
+}
+
+fn audit_filter(filter: &AuditFilter) -> MongoDocument {
+    let mut match_filter = doc! {};
+    for (field, value) in [
+        ("actor", &filter.actor),
+        ("action", &filter.action),
+        ("source", &filter.source),
+        ("collection", &filter.collection),
+        ("target_id", &filter.target_id),
+    ] {
+        if let Some(value) = value {
+            match_filter.insert(field, value);
+        }
+    }
+
+    let mut timestamp = doc! {};
+    if let Some(from) = &filter.from {
+        timestamp.insert("$gte", from);
+    }
+    if let Some(to) = &filter.to {
+        timestamp.insert("$lt", to);
+    }
+    if !timestamp.is_empty() {
+        match_filter.insert("timestamp", timestamp);
+    }
+    match_filter
+}
+
+#[async_trait]
+impl AuditRepository for MongoRepository {
+    async fn record(&self, entry: &AuditRecord) -> RepositoryResult<bool> {
+        match self.collection(AUDIT_COLLECTION).insert_one(entry.to_document(), None).await {
+            Ok(_) => Ok(true),
+            Err(e) if duplicate_key(&e) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn list(&self, filter: &AuditFilter, page: Page) -> RepositoryResult<Vec<AuditRecord>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "timestamp": -1, "_id": -1 })
+            .skip(page.offset)
+            .limit(page.limit as i64)
+            .build();
+        let docs = self
+            .collection(AUDIT_COLLECTION)
+            .find(audit_filter(filter), find_options)
+            .await?
+            .try_collect::<Vec<MongoDocument>>()
+            .await?;
+        Ok(docs.iter().map(AuditRecord::from_document).collect())
+    }
+
+    async fn after(&self, filter: &AuditFilter, cursor: &ScanCursor, limit: u64) -> RepositoryResult<Vec<AuditRecord>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "timestamp": 1, "_id": 1 })
+            .limit(limit as i64)
+            .build();
+        let docs = self
+            .collection(AUDIT_COLLECTION)
+            .find(doc! { "$and": [audit_filter(filter), cursor_filter("timestamp", cursor)] }, find_options)
+            .await?
+            .try_collect::<Vec<MongoDocument>>()
+            .await?;
+        Ok(docs.iter().map(AuditRecord::from_document).collect())
+    }
+
+    async fn count(&self, filter: &AuditFilter) -> RepositoryResult<u64> {
+        Ok(self.collection(AUDIT_COLLECTION).count_documents(audit_filter(filter), None).await?)
+    }
+
+    async fn claim(&self, record_id: &str, event_id: &str) -> RepositoryResult<bool> {
+        let claims = self.collection(AUDIT_CLAIMS_COLLECTION);
+        let claim = doc! { "_id": record_id, "event_id": event_id, "claimed_at": mongodb::bson::DateTime::now() };
+        match claims.insert_one(claim, None).await {
+            Ok(_) => Ok(true),
+            // _id tekil olduğundan iki olay aynı kaydı alamaz
+            Err(e) if duplicate_key(&e) => {
+                let existing = claims.find_one(doc! { "_id": record_id }, None).await?;
+                Ok(existing.is_some_and(|doc| doc.get_str("event_id") == Ok(event_id)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+fn delivery_filter(filter: &DeliveryFilter) -> MongoDocument {
+    let mut match_filter = doc! {};
+    if let Some(webhook_id) = &filter.webhook_id {
+        match_filter.insert("webhook_id", webhook_id);
+    }
+    if let Some(status) = &filter.status {
+        match_filter.insert("status", status);
+    }
+    match_filter
+}
+
+#[async_trait]
+impl WebhookRepository for MongoRepository {
+    async fn insert(&self, webhook: &WebhookWrite) -> RepositoryResult<String> {
+        let result = self.collection("webhooks").insert_one(webhook.to_document(), None).await?;
+        Ok(match result.inserted_id {
+            Bson::ObjectId(oid) => oid.to_hex(),
+            other => other.to_string(),
+        })
+    }
+
+    async fn update(&self, id: &str, webhook: &WebhookWrite) -> RepositoryResult<bool> {
+        let result = self
+            .collection("webhooks")
+            .update_one(id_filter("_id", id), doc! { "$set": webhook.to_document() }, None)
+            .await?;
+        Ok(result.matched_count > 0)
+    }
+
+    async fn delete(&self, id: &str) -> RepositoryResult<bool> {
+        let result = self.collection("webhooks").delete_one(id_filter("_id", id), None).await?;
+        if result.deleted_count == 0 {
+            return Ok(false);
+        }
+        self.collection("webhook_deliveries")
+            .delete_many(doc! { "webhook_id": id }, None)
+            .await?;
+        Ok(true)
+    }
+
+    async fn list(&self) -> RepositoryResult<Vec<WebhookRecord>> {
+        let find_options = FindOptions::builder().sort(doc! { "_id": 1 }).build();
+        let docs = self
+            .collection("webhooks")
+            .find(None, find_options)
+            .await?
+            .try_collect::<Vec<MongoDocument>>()
+            .await?;
+        Ok(docs.iter().map(WebhookRecord::from_document).collect())
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<WebhookRecord>> {
+        let doc = self.collection("webhooks").find_one(id_filter("_id", id), None).await?;
+        Ok(doc.as_ref().map(WebhookRecord::from_document))
+    }
+
+    async fn enqueue(&self, deliveries: &[DeliveryWrite]) -> RepositoryResult<Vec<String>> {
+        if deliveries.is_empty() {
+            return Ok(Vec::new());
+        }
+        let docs: Vec<MongoDocument> = deliveries.iter().map(DeliveryWrite::to_document).collect();
+        let result = self.collection("webhook_deliveries").insert_many(docs, None).await?;
+        // inserted_ids verilen dokümanların sırasına göre indekslenir
+        let mut ids: Vec<(usize, String)> = result
+            .inserted_ids
+            .into_iter()
+            .map(|(index, id)| match id {
+                Bson::ObjectId(oid) => (index, oid.to_hex()),
+                other => (index, other.to_string()),
+            })
+            .collect();
+        ids.sort_by_key(|(index, _)| *index);
+        Ok(ids.into_iter().map(|(_, id)| id).collect())
+    }
+
+    async fn due(&self, now: &str, limit: u64) -> RepositoryResult<Vec<DeliveryRecord>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "next_attempt_at": 1, "_id": 1 })
+            .limit(limit as i64)
+            .build();
+        let docs = self
+            .collection("webhook_deliveries")
+            .find(doc! { "status": "pending", "next_attempt_at": { "$lte": now } }, find_options)
+            .await?
+            .try_collect::<Vec<MongoDocument>>()
+            .await?;
+        Ok(docs.iter().map(DeliveryRecord::from_document).collect())
+    }
+
+    async fn record_attempt(&self, id: &str, attempt: &DeliveryAttempt) -> RepositoryResult<bool> {
+        let result = self
+            .collection("webhook_deliveries")
+            .update_one(
+                id_filter("_id", id),
+                doc! { "$set": attempt.to_document(), "$inc": { "attempts": 1 } },
+                None,
+            )
+            .await?;
+        Ok(result.matched_count > 0)
+    }
+
+    async fn deliveries(&self, filter: &DeliveryFilter, page: Page) -> RepositoryResult<Vec<DeliveryRecord>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "created_at": -1, "_id": -1 })
+            .skip(page.offset)
+            .limit(page.limit as i64)
+            .build();
+        let docs = self
+            .collection("webhook_deliveries")
+            .find(delivery_filter(filter), find_options)
+            .await?
+            .try_collect::<Vec<MongoDocument>>()
+            .await?;
+        Ok(docs.iter().map(DeliveryRecord::from_document).collect())
+    }
+
+    async fn count_deliveries(&self, filter: &DeliveryFilter) -> RepositoryResult<u64> {
+        Ok(self
+            .collection("webhook_deliveries")
+            .count_documents(delivery_filter(filter), None)
+            .await?)
+    }
+
+    async fn find_delivery(&self, id: &str) -> RepositoryResult<Option<DeliveryRecord>> {
+        let doc = self.collection("webhook_deliveries").find_one(id_filter("_id", id), None).await?;
+        Ok(doc.as_ref().map(DeliveryRecord::from_document))
+    }
+
+    async fn cursor(&self, key: &str) -> RepositoryResult<Option<ScanCursor>> {
+        let doc = self.collection("webhook_state").find_one(doc! { "_id": key }, None).await?;
+        Ok(doc.as_ref().map(ScanCursor::from_document))
+    }
+
+    async fn save_cursor(&self, key: &str, cursor: &ScanCursor) -> RepositoryResult<()> {
+        self.collection("webhook_state")
+            .update_one(
+                doc! { "_id": key },
+                doc! { "$set": cursor.to_document() },
+                UpdateOptions::builder().upsert(true).build(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+fn alert_filter(filter: &AlertFilter) -> MongoDocument {
+    let mut match_filter = doc! {};
+    if let Some(owner) = &filter.owner {
+        match_filter.insert("owner", owner);
+    }
+    if let Some(saved_search_id) = &filter.saved_search_id {
+        match_filter.insert("saved_search_id", saved_search_id);
+    }
+    if filter.unread {
+        match_filter.insert("read_at", Bson::Null);
+    }
+    match_filter
+}
+
+#[async_trait]
+impl SavedSearchRepository for MongoRepository {
+    async fn insert(&self, search: &SavedSearchWrite) -> RepositoryResult<String> {
+        let result = self.collection("saved_searches").insert_one(search.to_document(), None).await?;
+        Ok(match result.inserted_id {
+            Bson::ObjectId(oid) => oid.to_hex(),
+            other => other.to_string(),
+        })
+    }
+
+    async fn update(&self, id: &str, search: &SavedSearchWrite) -> RepositoryResult<bool> {
+        let result = self
+            .collection("saved_searches")
+            .update_one(id_filter("_id", id), doc! { "$set": search.to_document() }, None)
+            .await?;
+        Ok(result.matched_count > 0)
+    }
+
+    async fn delete(&self, id: &str) -> RepositoryResult<bool> {
+        let result = self.collection("saved_searches").delete_one(id_filter("_id", id), None).await?;
+        if result.deleted_count == 0 {
+            return Ok(false);
+        }
+        self.collection("saved_search_alerts")
+            .delete_many(doc! { "saved_search_id": id }, None)
+            .await?;
+        Ok(true)
+    }
+
+    async fn list(&self, owner: Option<&str>) -> RepositoryResult<Vec<SavedSearchRecord>> {
+        let find_options = FindOptions::builder().sort(doc! { "_id": 1 }).build();
+        let filter = owner.map(|owner| doc! { "owner": owner });
+        let docs = self
+            .collection("saved_searches")
+            .find(filter, find_options)
+            .await?
+            .try_collect::<Vec<MongoDocument>>()
+            .await?;
+        Ok(docs.iter().map(SavedSearchRecord::from_document).collect())
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<SavedSearchRecord>> {
+        let doc = self.collection("saved_searches").find_one(id_filter("_id", id), None).await?;
+        Ok(doc.as_ref().map(SavedSearchRecord::from_document))
+    }
+
+    async fn record_run(&self, id: &str, run: &SavedSearchRun) -> RepositoryResult<bool> {
+        let result = self
+            .collection("saved_searches")
+            .update_one(id_filter("_id", id), doc! { "$set": run.to_document() }, None)
+            .await?;
+        Ok(result.matched_count > 0)
+    }
+
+    async fn insert_alert(&self, alert: &AlertWrite) -> RepositoryResult<String> {
+        let result = self.collection("saved_search_alerts").insert_one(alert.to_document(), None).await?;
+        Ok(match result.inserted_id {
+            Bson::ObjectId(oid) => oid.to_hex(),
+            other => other.to_string(),
+        })
+    }
+
+    async fn alerts(&self, filter: &AlertFilter, page: Page) -> RepositoryResult<Vec<AlertRecord>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "created_at": -1, "_id": -1 })
+            .skip(page.offset)
+            .limit(page.limit as i64)
+            .build();
+        let docs = self
+            .collection("saved_search_alerts")
+            .find(alert_filter(filter), find_options)
+            .await?
+            .try_collect::<Vec<MongoDocument>>()
+            .await?;
+        Ok(docs.iter().map(AlertRecord::from_document).collect())
+    }
+
+    async fn count_alerts(&self, filter: &AlertFilter) -> RepositoryResult<u64> {
+        Ok(self
+            .collection("saved_search_alerts")
+            .count_documents(alert_filter(filter), None)
+            .await?)
+    }
+
+    async fn find_alert(&self, id: &str) -> RepositoryResult<Option<AlertRecord>> {
+        let doc = self.collection("saved_search_alerts").find_one(id_filter("_id", id), None).await?;
+        Ok(doc.as_ref().map(AlertRecord::from_document))
+    }
+
+    async fn mark_alert_read(&self, id: &str, owner: &str, read_at: &str) -> RepositoryResult<bool> {
+        let mut filter = id_filter("_id", id);
+        filter.insert("owner", owner);
+        // Daha önce okunduysa ilk okunma zamanı korunur
+        let update = vec![doc! { "$set": { "read_at": { "$ifNull": ["$read_at", read_at] } } }];
+        let result = self.collection("saved_search_alerts").update_one(filter, update, None).await?;
+        Ok(result.matched_count > 0)
+    }
+
+    async fn record_notification(&self, id: &str, status: &str, error: Option<&str>) -> RepositoryResult<bool> {
+        let result = self
+            .collection("saved_search_alerts")
+            .update_one(
+                id_filter("_id", id),
+                doc! { "$set": { "notification_status": status, "notification_error": error } },
+                None,
+            )
+            .await?;
+        Ok(result.matched_count > 0)
+    }
+}
+
+// Aynı _id ile ikinci ekleme (E11000)
+fn duplicate_key(e: &mongodb::error::Error) -> bool {
+    match e.kind.as_ref() {
+        ErrorKind::Write(mongodb::error::WriteFailure::WriteError(write_error)) => write_error.code == 11000,
+        ErrorKind::Command(command) => command.code == 11000,
+        _ => false,
+    }
+}
+
+// E11000 hatasındaki alan adı: "... index: idx_url_slug_unique dup key: { url_slug: \"...\" }"
+pub(crate) fn duplicate_key_field(e: &mongodb::error::Error) -> Option<String> {
+    if !duplicate_key(e) {
+        return None;
+    }
+    let message = e.to_string();
+    let field = message
+        .split_once("dup key: {")
+        .and_then(|(_, rest)| rest.split(':').next())
+        .map(|field| field.trim().to_string())
+        .filter(|field| !field.is_empty());
+    Some(field.unwrap_or_else(|| "unique".to_string()))
+}
+
+#[async_trait]
+impl StorageHealth for MongoRepository {
+    async fn ping(&self) -> RepositoryResult<()> {
+        self.db.list_collection_names(None).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,1162 @@
+use mongodb::bson::{self, doc, oid::ObjectId, Bson, Document as MongoDocument};
+use crate::corpus::{ContentSource, Corpus};
+use crate::scraping::ScrapeRules;
+use crate::utils::bson_date_string;
+
+// Depolama katmanından dönen tipli kayıtlar. Alan adı toleransı
+// (kurum_adi/kurumAdi, icerik/content/text vb.) yalnızca burada uygulanır;
+// handler'lar ham MongoDocument okumaz.
+
+#[derive(Debug, Clone, Default)]
+pub struct InstitutionRecord {
+    pub id: String,
+    pub kurum_adi: String,
+    pub kurum_logo: String,
+    pub aciklama: String,
+    pub detsis: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DocumentRecord {
+    pub id: String,
+    pub kurum_id: String,
+    pub pdf_adi: String,
+    pub aciklama: String,
+    pub belge_yayin_tarihi: String,
+    pub yururluluk_tarihi: Option<String>,
+    // Corpus'a özgü yayın tarihi alanı (örn. Yargıtay için kararTarihi)
+    pub yayin_tarihi: String,
+    pub belge_turu: String,
+    pub belge_durumu: String,
+    pub etiketler: String,
+    pub anahtar_kelimeler: String,
+    pub url_slug: String,
+    pub status: String,
+    pub pdf_url: String,
+    pub icerik: Option<String>,
+    pub icerik_text: Option<String>,
+    pub karar_tarihi: Option<String>,
+    pub daire: String,
+    pub esas_no: String,
+    pub karar_no: String,
+    pub sayfa_sayisi: Option<i32>,
+    pub dosya_boyutu_mb: Option<f64>,
+    pub olusturulma_tarihi: String,
+    // $lookup ile birleştirilen kurum (corpus.lookup_kurum açıksa)
+    pub kurum: Option<InstitutionRecord>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContentRecord {
+    pub id: String,
+    pub icerik: String,
+    pub olusturulma_tarihi: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LinkRecord {
+    pub id: String,
+    pub kurum_id: Option<String>,
+    pub baslik: String,
+    pub aciklama: String,
+    pub url: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AnnouncementSourceRecord {
+    pub id: String,
+    pub kurum_id: String,
+    pub duyuru_linki: String,
+    pub baslik: Option<String>,
+    // _id içindeki zaman damgasından türetilen tarih (YYYY-MM-DD)
+    pub tarih: String,
+    // Sayfanın okunma kuralları; yoksa domain'e göre hazır desen kullanılır
+    pub scraper: Option<ScrapeRules>,
+}
+
+// Harvester'ın kurum duyuru sayfalarından topladığı duyuru (kurum_duyuru_items)
+#[derive(Debug, Clone, Default)]
+pub struct ScrapedAnnouncementRecord {
+    pub id: String,
+    pub kurum_id: String,
+    pub baslik: String,
+    pub link: String,
+    // DD.MM.YYYY; sayfada okunabilir tarih yoksa None
+    pub tarih: Option<String>,
+    // Duyurunun ilk görüldüğü hasat zamanı (RFC 3339, UTC)
+    pub first_seen_at: String,
+    // Detay sayfasından okunan ana metin; sayfa henüz çekilmediyse None
+    pub icerik: Option<String>,
+    pub ekler: Vec<AttachmentRecord>,
+    // Detay sayfasının çekilme zamanı (RFC 3339); başarısız denemeler de kaydedilir
+    pub details_fetched_at: Option<String>,
+    pub details_error: Option<String>,
+}
+
+// Duyuru sayfasında bulunan PDF/DOCX eki
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttachmentRecord {
+    pub url: String,
+    pub baslik: String,
+    // pdf, docx, doc
+    pub tur: String,
+}
+
+// Detay sayfası sonucunun duyuruya yazılması. tarih yalnızca Some ise yazılır; hata
+// durumunda içerik ve ekler boş kalır, error doldurulur.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnnouncementDetailWrite {
+    pub icerik: Option<String>,
+    pub tarih: Option<String>,
+    pub ekler: Vec<AttachmentRecord>,
+    pub fetched_at: String,
+    pub error: Option<String>,
+}
+
+// Hasatta kaydedilecek duyuru; link daha önce kaydedildiyse yazılmaz
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScrapedAnnouncementWrite {
+    pub kurum_id: String,
+    pub baslik: String,
+    pub link: String,
+    pub tarih: Option<String>,
+    pub first_seen_at: String,
+}
+
+// Yönetim API'sinden yazılan belge. Kimlikler tek tipe normalize edilir: kurum_id
+// hex string olarak, içerik koleksiyonundaki foreign key ObjectId olarak saklanır.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentWrite {
+    pub kurum_id: String,
+    pub pdf_adi: String,
+    pub aciklama: String,
+    pub belge_turu: String,
+    pub belge_durumu: String,
+    pub belge_yayin_tarihi: String,
+    pub yururluluk_tarihi: Option<String>,
+    pub etiketler: String,
+    pub anahtar_kelimeler: String,
+    pub url_slug: String,
+    pub status: String,
+    pub pdf_url: String,
+    pub sayfa_sayisi: i32,
+    pub dosya_boyutu_mb: f64,
+    pub olusturulma_tarihi: String,
+    pub guncellenme_tarihi: String,
+    // None ise mevcut içerik olduğu gibi kalır
+    pub icerik: Option<String>,
+}
+
+// Yönetim API'sinden yazılan kurum. Alanlar kanonik adlarıyla ($set) yazılır;
+// eski kayıtlardaki kurumAdi/kurumLogo gibi alanlar okuma toleransı için kalır.
+#[derive(Debug, Clone, Default)]
+pub struct InstitutionWrite {
+    pub kurum_adi: String,
+    pub kurum_logo: String,
+    pub aciklama: String,
+    pub detsis: Option<String>,
+    // Yalnızca eklemede yazılır; güncellemede mevcut değer korunur
+    pub olusturulma_tarihi: Option<String>,
+    pub guncellenme_tarihi: String,
+}
+
+// Kurumun duyuru sayfası (kurum_duyuru); kurum_id hex string olarak saklanır
+#[derive(Debug, Clone, Default)]
+pub struct AnnouncementSourceWrite {
+    pub kurum_id: String,
+    pub duyuru_linki: String,
+    pub baslik: Option<String>,
+    pub scraper: Option<ScrapeRules>,
+}
+
+// Kurumun faydalı linki (links); kurum_id hex string olarak saklanır
+#[derive(Debug, Clone, Default)]
+pub struct LinkWrite {
+    pub kurum_id: String,
+    pub baslik: String,
+    pub aciklama: String,
+    pub url: String,
+    // Yalnızca eklemede yazılır; güncellemede mevcut değer korunur
+    pub created_at: Option<String>,
+}
+
+// API anahtarı; anahtarın kendisi saklanmaz, yalnızca SHA-256 özeti tutulur
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeyRecord {
+    pub id: String,
+    pub name: String,
+    // Listeleme ve iptal için anahtarın ilk karakterleri (örn. mgpt_3f9a1c2b)
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    // Günlük istek kotası; None ise sınırsız
+    pub daily_quota: Option<u64>,
+    pub created_at: String,
+    pub revoked_at: Option<String>,
+}
+
+// Denetim kaydı (audit_log). Kayıtlar yalnızca eklenir; güncelleme ve silme yoktur.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    pub id: String,
+    // UTC, milisaniye hassasiyetli RFC3339 (string olarak sıralanabilir)
+    pub timestamp: String,
+    // İşlemi yapan API anahtarının prefix'i ya da CLI kullanıcısı; bilinmiyorsa None
+    pub actor: Option<String>,
+    pub source: String,
+    pub action: String,
+    pub collection: String,
+    pub target_id: String,
+    pub changes: Vec<AuditChange>,
+}
+
+// Üst düzey alan farkı; None alanın dokümanda olmadığını gösterir
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditChange {
+    pub field: String,
+    pub before: Option<Bson>,
+    pub after: Option<Bson>,
+}
+
+// Webhook aboneliğinin olay filtreleri; verilen tüm filtreler eşleşmelidir
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebhookFilters {
+    pub kurum_id: Option<String>,
+    pub belge_turu: Option<String>,
+    // Corpus anahtarı (mevzuat, yargitay, ...); verilirse duyurular eşleşmez
+    pub corpus: Option<String>,
+    // Herhangi biri başlıkta, açıklamada ya da etiketlerde geçmelidir
+    pub keywords: Vec<String>,
+}
+
+// Webhook aboneliği (webhooks). secret imzalama için düz saklanır.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebhookRecord {
+    pub id: String,
+    pub url: String,
+    pub secret: String,
+    // document.created, announcement.created
+    pub events: Vec<String>,
+    pub filters: WebhookFilters,
+    pub aktif: bool,
+    pub aciklama: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebhookWrite {
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+    pub filters: WebhookFilters,
+    pub aktif: bool,
+    pub aciklama: String,
+    // Yalnızca eklemede yazılır
+    pub created_at: Option<String>,
+    pub updated_at: String,
+}
+
+// Bir olayın bir aboneliğe teslimatı (webhook_deliveries). Kuyruk ve teslimat kaydı
+// aynı kayıttır: pending kayıtlar next_attempt_at geldiğinde gönderilir.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeliveryRecord {
+    pub id: String,
+    pub webhook_id: String,
+    // Olayın kimliği; aynı olayın tüm teslimatlarında (tekrar gönderimler dahil) aynıdır
+    pub event_id: String,
+    pub event: String,
+    // Gönderilen JSON gövdesi; imza bu metin üzerinden hesaplanır
+    pub payload: String,
+    // pending, delivered, failed
+    pub status: String,
+    pub attempts: u32,
+    pub next_attempt_at: Option<String>,
+    pub last_attempt_at: Option<String>,
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub delivered_at: Option<String>,
+    // Tekrar gönderimde kopyalanan teslimat
+    pub replay_of: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeliveryWrite {
+    pub webhook_id: String,
+    pub event_id: String,
+    pub event: String,
+    pub payload: String,
+    pub created_at: String,
+    pub replay_of: Option<String>,
+}
+
+// Gönderim denemesinin sonucu; attempts bir artırılır
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeliveryAttempt {
+    pub status: String,
+    pub attempted_at: String,
+    // Yeniden denenecekse zamanı
+    pub next_attempt_at: Option<String>,
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+}
+
+// Kurum, belge türü ve yayın tarihi başına belge sayısı (istatistik özeti için). Yayın tarihi
+// ham değeriyle döner; ay bazında toplama istatistik katmanında yapılır.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentCountGroup {
+    // Boşsa belgede kurum_id yok
+    pub kurum_id: String,
+    // Boş belge türü "Belirtilmemiş"
+    pub belge_turu: String,
+    pub yayin_tarihi: Option<String>,
+    pub count: u64,
+}
+
+// Periyodik taramanın (webhook olayları, kayıtlı aramalar) kaldığı yer: en yeni kaydın
+// zamanı ve o zamandaki kayıtların id'leri
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanCursor {
+    pub watermark: String,
+    pub ids: Vec<String>,
+}
+
+// Kayıtlı arama (saved_searches). Sahibi aramayı oluşturan API anahtarıdır.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SavedSearchRecord {
+    pub id: String,
+    // API anahtarının id'si
+    pub owner: String,
+    pub ad: String,
+    pub q: String,
+    // Corpus anahtarı (mevzuat, yargitay, ...)
+    pub corpus: String,
+    pub kurum_id: Option<String>,
+    pub belge_turu: Option<String>,
+    // inbox, webhook, email
+    pub kanal: String,
+    // Webhook adresi ya da e-posta adresi; inbox için None
+    pub hedef: Option<String>,
+    // Webhook gövdesinin imzalama anahtarı
+    pub secret: Option<String>,
+    pub aktif: bool,
+    // Henüz çalışmadıysa (ya da sorgu değiştiyse) None
+    pub cursor: Option<ScanCursor>,
+    pub last_run_at: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SavedSearchWrite {
+    pub owner: String,
+    pub ad: String,
+    pub q: String,
+    pub corpus: String,
+    pub kurum_id: Option<String>,
+    pub belge_turu: Option<String>,
+    pub kanal: String,
+    pub hedef: Option<String>,
+    pub secret: Option<String>,
+    pub aktif: bool,
+    // Sorgu ya da filtreler değiştiyse imleç silinir; sonraki çalışma baştan kurar
+    pub reset_cursor: bool,
+    // Yalnızca eklemede yazılır
+    pub created_at: Option<String>,
+    pub updated_at: String,
+}
+
+// Kayıtlı aramanın bir çalışmasının sonucu
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SavedSearchRun {
+    pub cursor: Option<ScanCursor>,
+    pub run_at: String,
+    pub error: Option<String>,
+}
+
+// Bildirimdeki tek eşleşme
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlertHit {
+    pub id: String,
+    pub pdf_adi: String,
+    pub url_slug: String,
+    pub url: String,
+    pub kurum_adi: String,
+    pub belge_turu: String,
+    pub content_preview: String,
+    pub olusturulma_tarihi: String,
+}
+
+// Kayıtlı aramanın yeni eşleşmeleri (saved_search_alerts); kanaldan bağımsız olarak gelen
+// kutusunda saklanır, webhook/e-posta gönderiminin sonucu da burada tutulur
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlertRecord {
+    pub id: String,
+    pub saved_search_id: String,
+    pub owner: String,
+    pub ad: String,
+    pub q: String,
+    pub hits: Vec<AlertHit>,
+    pub kanal: String,
+    // delivered, failed; inbox kanalında None
+    pub notification_status: Option<String>,
+    pub notification_error: Option<String>,
+    pub created_at: String,
+    pub read_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlertWrite {
+    pub saved_search_id: String,
+    pub owner: String,
+    pub ad: String,
+    pub q: String,
+    pub hits: Vec<AlertHit>,
+    pub kanal: String,
+    pub created_at: String,
+}
+
+impl InstitutionRecord {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        InstitutionRecord {
+            id: id_string(doc, "_id"),
+            kurum_adi: string_field(doc, &["kurum_adi", "kurumAdi"]),
+            kurum_logo: string_field(doc, &["kurum_logo", "kurumLogo"]),
+            aciklama: string_field(doc, &["aciklama", "kurumAciklama"]),
+            detsis: optional_string(doc, &["detsis"]),
+        }
+    }
+}
+
+impl DocumentRecord {
+    pub fn from_document(doc: &MongoDocument, corpus: &Corpus) -> Self {
+        let olusturulma_tarihi = match bson_date_string(doc, &corpus.fields.created_at) {
+            value if value.is_empty() => bson_date_string(doc, "created_at"),
+            value => value,
+        };
+
+        DocumentRecord {
+            id: id_string(doc, "_id"),
+            kurum_id: id_string(doc, "kurum_id"),
+            pdf_adi: string_field(doc, &[corpus.fields.title.as_str()]),
+            aciklama: string_field(doc, &["aciklama"]),
+            belge_yayin_tarihi: string_field(doc, &["belge_yayin_tarihi"]),
+            yururluluk_tarihi: optional_string(doc, &["yururluluk_tarihi"]),
+            yayin_tarihi: string_field(doc, &[corpus.fields.publication_date.as_str()]),
+            belge_turu: string_field(doc, &["belge_turu"]),
+            belge_durumu: string_field(doc, &["belge_durumu"]),
+            etiketler: string_field(doc, &["etiketler"]),
+            anahtar_kelimeler: string_field(doc, &["anahtar_kelimeler"]),
+            url_slug: string_field(doc, &["url_slug"]),
+            status: string_field(doc, &["status"]),
+            pdf_url: string_field(doc, &["pdf_url"]),
+            icerik: optional_string(doc, &["icerik", "content", "text"]),
+            icerik_text: optional_string(doc, &["icerik_text"]),
+            karar_tarihi: optional_string(doc, &["kararTarihi", "karar_tarihi"]),
+            daire: string_field(doc, &["daire"]),
+            esas_no: string_field(doc, &["esasNo", "esas_no"]),
+            karar_no: string_field(doc, &["kararNo", "karar_no"]),
+            sayfa_sayisi: int_field(doc, "sayfa_sayisi"),
+            dosya_boyutu_mb: float_field(doc, "dosya_boyutu_mb"),
+            olusturulma_tarihi,
+            kurum: doc
+                .get_document("kurum_bilgisi")
+                .ok()
+                .map(InstitutionRecord::from_document),
+        }
+    }
+
+    // Arama önizlemesi gibi corpus ayarından gelen alan adlarını kayıttaki değere çevir
+    pub fn text_field(&self, field: &str) -> Option<&str> {
+        match field {
+            "aciklama" => Some(self.aciklama.as_str()),
+            "icerik" => self.icerik.as_deref(),
+            "icerik_text" => self.icerik_text.as_deref(),
+            "etiketler" => Some(self.etiketler.as_str()),
+            "anahtar_kelimeler" => Some(self.anahtar_kelimeler.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn kurum_adi(&self) -> String {
+        self.kurum
+            .as_ref()
+            .map(|kurum| kurum.kurum_adi.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl DocumentWrite {
+    // Metadata alanları ($set ile yazılır; kayıttaki diğer alanlar korunur)
+    pub fn metadata_fields(&self, corpus: &Corpus) -> MongoDocument {
+        let mut fields = doc! {
+            "kurum_id": &self.kurum_id,
+            corpus.fields.title.as_str(): &self.pdf_adi,
+            "aciklama": &self.aciklama,
+            "belge_turu": &self.belge_turu,
+            "belge_durumu": &self.belge_durumu,
+            "belge_yayin_tarihi": &self.belge_yayin_tarihi,
+            "yururluluk_tarihi": self.yururluluk_tarihi.as_deref(),
+            "etiketler": &self.etiketler,
+            "anahtar_kelimeler": &self.anahtar_kelimeler,
+            "url_slug": &self.url_slug,
+            "status": &self.status,
+            "pdf_url": &self.pdf_url,
+            "sayfa_sayisi": self.sayfa_sayisi,
+            "dosya_boyutu_mb": self.dosya_boyutu_mb,
+            corpus.fields.created_at.as_str(): &self.olusturulma_tarihi,
+            "guncellenme_tarihi": &self.guncellenme_tarihi,
+        };
+        if let Some(icerik) = &self.icerik {
+            // Arama metadata üzerindeki düz metin alanında yapılır
+            fields.insert(corpus.fields.search_text.as_str(), icerik);
+            if matches!(corpus.content, ContentSource::Embedded) {
+                fields.insert("icerik", icerik);
+            }
+        }
+        fields
+    }
+
+    // Join corpus'larında içerik koleksiyonuna yazılacak doküman
+    pub fn content_document(&self, corpus: &Corpus, metadata_id: Bson) -> Option<MongoDocument> {
+        let (ContentSource::Join { foreign_key, .. }, Some(icerik)) = (&corpus.content, &self.icerik) else {
+            return None;
+        };
+        Some(doc! {
+            foreign_key.as_str(): metadata_id,
+            "content": icerik,
+            "created_at": &self.guncellenme_tarihi,
+        })
+    }
+}
+
+impl InstitutionWrite {
+    pub fn to_document(&self) -> MongoDocument {
+        let mut document = doc! {
+            "kurum_adi": &self.kurum_adi,
+            "kurum_logo": &self.kurum_logo,
+            "aciklama": &self.aciklama,
+            "detsis": self.detsis.as_deref(),
+            "guncellenme_tarihi": &self.guncellenme_tarihi,
+        };
+        if let Some(olusturulma_tarihi) = &self.olusturulma_tarihi {
+            document.insert("olusturulma_tarihi", olusturulma_tarihi);
+        }
+        document
+    }
+}
+
+impl AnnouncementSourceWrite {
+    pub fn to_document(&self) -> MongoDocument {
+        doc! {
+            "kurum_id": &self.kurum_id,
+            "duyuru_linki": &self.duyuru_linki,
+            "baslik": self.baslik.as_deref(),
+            "scraper": self.scraper.as_ref().and_then(|rules| bson::to_document(rules).ok()),
+        }
+    }
+}
+
+impl LinkWrite {
+    pub fn to_document(&self) -> MongoDocument {
+        let mut document = doc! {
+            "kurum_id": &self.kurum_id,
+            "baslik": &self.baslik,
+            "aciklama": &self.aciklama,
+            "url": &self.url,
+        };
+        if let Some(created_at) = &self.created_at {
+            document.insert("created_at", created_at);
+        }
+        document
+    }
+}
+
+impl ContentRecord {
+    pub fn from_document(doc: &MongoDocument, corpus: &Corpus) -> Self {
+        ContentRecord {
+            id: id_string(doc, "_id"),
+            icerik: string_field(doc, &["icerik", "content", "text"]),
+            olusturulma_tarihi: optional_string(doc, &[corpus.fields.created_at.as_str(), "created_at"]),
+        }
+    }
+}
+
+impl LinkRecord {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        // Created at: Önce koleksiyondan kontrol et, yoksa _id'den türet
+        let created_at = match doc.get_str("created_at") {
+            Ok(created_at) => created_at.to_string(),
+            Err(_) => object_id_time(doc)
+                .unwrap_or_else(chrono::Utc::now)
+                .format("%Y-%m-%dT%H:%M:%SZ")
+                .to_string(),
+        };
+
+        LinkRecord {
+            id: id_string(doc, "_id"),
+            kurum_id: Some(id_string(doc, "kurum_id")).filter(|id| !id.is_empty()),
+            baslik: string_field(doc, &["baslik"]),
+            aciklama: string_field(doc, &["aciklama"]),
+            url: string_field(doc, &["url"]),
+            created_at,
+        }
+    }
+}
+
+impl AnnouncementSourceRecord {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        AnnouncementSourceRecord {
+            id: id_string(doc, "_id"),
+            kurum_id: id_string(doc, "kurum_id"),
+            duyuru_linki: string_field(doc, &["duyuru_linki"]),
+            baslik: doc.get_str("baslik").ok().map(|s| s.to_string()),
+            tarih: object_id_time(doc)
+                .unwrap_or_else(chrono::Utc::now)
+                .format("%Y-%m-%d")
+                .to_string(),
+            scraper: doc
+                .get_document("scraper")
+                .ok()
+                .and_then(|rules| bson::from_document(rules.clone()).ok()),
+        }
+    }
+}
+
+impl ScrapedAnnouncementRecord {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        ScrapedAnnouncementRecord {
+            id: id_string(doc, "_id"),
+            kurum_id: id_string(doc, "kurum_id"),
+            baslik: string_field(doc, &["baslik"]),
+            link: string_field(doc, &["link"]),
+            tarih: optional_string(doc, &["tarih"]),
+            first_seen_at: string_field(doc, &["first_seen_at"]),
+            icerik: optional_string(doc, &["icerik"]),
+            ekler: doc
+                .get_array("ekler")
+                .map(|ekler| {
+                    ekler
+                        .iter()
+                        .filter_map(Bson::as_document)
+                        .map(AttachmentRecord::from_document)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            details_fetched_at: optional_string(doc, &["details_fetched_at"]),
+            details_error: optional_string(doc, &["details_error"]),
+        }
+    }
+}
+
+impl AttachmentRecord {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        AttachmentRecord {
+            url: string_field(doc, &["url"]),
+            baslik: string_field(doc, &["baslik"]),
+            tur: string_field(doc, &["tur"]),
+        }
+    }
+
+    pub fn to_document(&self) -> MongoDocument {
+        doc! {
+            "url": &self.url,
+            "baslik": &self.baslik,
+            "tur": &self.tur,
+        }
+    }
+}
+
+impl AnnouncementDetailWrite {
+    // $set ile yazılacak alanlar
+    pub fn to_document(&self) -> MongoDocument {
+        let mut document = doc! {
+            "icerik": self.icerik.as_deref(),
+            "ekler": self.ekler.iter().map(AttachmentRecord::to_document).collect::<Vec<_>>(),
+            "details_fetched_at": &self.fetched_at,
+            "details_error": self.error.as_deref(),
+        };
+        if let Some(tarih) = &self.tarih {
+            document.insert("tarih", tarih);
+        }
+        document
+    }
+}
+
+impl ScrapedAnnouncementWrite {
+    pub fn to_document(&self) -> MongoDocument {
+        doc! {
+            "kurum_id": &self.kurum_id,
+            "baslik": &self.baslik,
+            "link": &self.link,
+            "tarih": self.tarih.as_deref(),
+            "first_seen_at": &self.first_seen_at,
+        }
+    }
+}
+
+impl ApiKeyRecord {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        ApiKeyRecord {
+            id: id_string(doc, "_id"),
+            name: string_field(doc, &["name"]),
+            prefix: string_field(doc, &["prefix"]),
+            key_hash: string_field(doc, &["key_hash"]),
+            scopes: doc
+                .get_array("scopes")
+                .map(|scopes| {
+                    scopes
+                        .iter()
+                        .filter_map(|scope| scope.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            daily_quota: match doc.get("daily_quota") {
+                Some(Bson::Int32(value)) if *value >= 0 => Some(*value as u64),
+                Some(Bson::Int64(value)) if *value >= 0 => Some(*value as u64),
+                _ => None,
+            },
+            created_at: string_field(doc, &["created_at"]),
+            revoked_at: optional_string(doc, &["revoked_at"]),
+        }
+    }
+
+    pub fn to_document(&self) -> MongoDocument {
+        let mut document = doc! {
+            "name": &self.name,
+            "prefix": &self.prefix,
+            "key_hash": &self.key_hash,
+            "scopes": &self.scopes,
+            "daily_quota": self.daily_quota.map(|quota| quota as i64),
+            "created_at": &self.created_at,
+            "revoked_at": self.revoked_at.as_deref(),
+        };
+        if let Ok(oid) = ObjectId::parse_str(&self.id) {
+            document.insert("_id", oid);
+        }
+        document
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+impl AuditRecord {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        AuditRecord {
+            id: id_string(doc, "_id"),
+            timestamp: string_field(doc, &["timestamp"]),
+            actor: optional_string(doc, &["actor"]),
+            source: string_field(doc, &["source"]),
+            action: string_field(doc, &["action"]),
+            collection: string_field(doc, &["collection"]),
+            target_id: string_field(doc, &["target_id"]),
+            changes: doc
+                .get_array("changes")
+                .map(|changes| {
+                    changes
+                        .iter()
+                        .filter_map(|change| change.as_document())
+                        .map(|change| AuditChange {
+                            field: string_field(change, &["field"]),
+                            before: change.get("before").cloned(),
+                            after: change.get("after").cloned(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn to_document(&self) -> MongoDocument {
+        let changes: Vec<MongoDocument> = self
+            .changes
+            .iter()
+            .map(|change| {
+                let mut document = doc! { "field": &change.field };
+                if let Some(before) = &change.before {
+                    document.insert("before", before.clone());
+                }
+                if let Some(after) = &change.after {
+                    document.insert("after", after.clone());
+                }
+                document
+            })
+            .collect();
+
+        let mut document = doc! {
+            "_id": canonical_id(&Bson::String(self.id.clone())),
+            "timestamp": &self.timestamp,
+            "source": &self.source,
+            "action": &self.action,
+            "collection": &self.collection,
+            "target_id": &self.target_id,
+            "changes": changes,
+        };
+        if let Some(actor) = &self.actor {
+            document.insert("actor", actor);
+        }
+        document
+    }
+}
+
+impl WebhookFilters {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        WebhookFilters {
+            kurum_id: optional_string(doc, &["kurum_id"]),
+            belge_turu: optional_string(doc, &["belge_turu"]),
+            corpus: optional_string(doc, &["corpus"]),
+            keywords: string_array(doc, "keywords"),
+        }
+    }
+
+    pub fn to_document(&self) -> MongoDocument {
+        doc! {
+            "kurum_id": self.kurum_id.as_deref(),
+            "belge_turu": self.belge_turu.as_deref(),
+            "corpus": self.corpus.as_deref(),
+            "keywords": &self.keywords,
+        }
+    }
+}
+
+impl WebhookRecord {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        WebhookRecord {
+            id: id_string(doc, "_id"),
+            url: string_field(doc, &["url"]),
+            secret: string_field(doc, &["secret"]),
+            events: string_array(doc, "events"),
+            filters: doc.get_document("filters").map(WebhookFilters::from_document).unwrap_or_default(),
+            aktif: doc.get_bool("aktif").unwrap_or(true),
+            aciklama: string_field(doc, &["aciklama"]),
+            created_at: string_field(doc, &["created_at"]),
+            updated_at: string_field(doc, &["updated_at"]),
+        }
+    }
+}
+
+impl WebhookWrite {
+    pub fn to_document(&self) -> MongoDocument {
+        let mut document = doc! {
+            "url": &self.url,
+            "secret": &self.secret,
+            "events": &self.events,
+            "filters": self.filters.to_document(),
+            "aktif": self.aktif,
+            "aciklama": &self.aciklama,
+            "updated_at": &self.updated_at,
+        };
+        if let Some(created_at) = &self.created_at {
+            document.insert("created_at", created_at);
+        }
+        document
+    }
+}
+
+impl DeliveryRecord {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        DeliveryRecord {
+            id: id_string(doc, "_id"),
+            webhook_id: string_field(doc, &["webhook_id"]),
+            event_id: string_field(doc, &["event_id"]),
+            event: string_field(doc, &["event"]),
+            payload: string_field(doc, &["payload"]),
+            status: string_field(doc, &["status"]),
+            attempts: int_field(doc, "attempts").unwrap_or(0).max(0) as u32,
+            next_attempt_at: optional_string(doc, &["next_attempt_at"]),
+            last_attempt_at: optional_string(doc, &["last_attempt_at"]),
+            response_status: int_field(doc, "response_status").and_then(|status| u16::try_from(status).ok()),
+            error: optional_string(doc, &["error"]),
+            created_at: string_field(doc, &["created_at"]),
+            delivered_at: optional_string(doc, &["delivered_at"]),
+            replay_of: optional_string(doc, &["replay_of"]),
+        }
+    }
+}
+
+impl DeliveryWrite {
+    // Yeni teslimat hemen gönderilmek üzere kuyruğa girer
+    pub fn to_document(&self) -> MongoDocument {
+        doc! {
+            "webhook_id": &self.webhook_id,
+            "event_id": &self.event_id,
+            "event": &self.event,
+            "payload": &self.payload,
+            "status": "pending",
+            "attempts": 0,
+            "next_attempt_at": &self.created_at,
+            "created_at": &self.created_at,
+            "replay_of": self.replay_of.as_deref(),
+        }
+    }
+}
+
+impl DeliveryAttempt {
+    // $set ile yazılacak alanlar (attempts ayrıca artırılır)
+    pub fn to_document(&self) -> MongoDocument {
+        let mut document = doc! {
+            "status": &self.status,
+            "last_attempt_at": &self.attempted_at,
+            "next_attempt_at": self.next_attempt_at.as_deref(),
+            "response_status": self.response_status.map(i32::from),
+            "error": self.error.as_deref(),
+        };
+        if self.status == "delivered" {
+            document.insert("delivered_at", &self.attempted_at);
+        }
+        document
+    }
+}
+
+impl ScanCursor {
+    // Kayıt imlecin gerisinde mi (daha önce görüldü mü). Zaman saklandığı biçimiyle karşılaştırılır;
+    // uygulamanın yazdığı format_timestamp değerleri string olarak sıralanabilir.
+    pub fn covers(&self, time: &str, id: &str) -> bool {
+        time < self.watermark.as_str() || (time == self.watermark && self.ids.iter().any(|seen| seen == id))
+    }
+
+    // Verilen kayıtlar görüldükten sonraki imleç: en büyük zaman ve o zamandaki kayıtların id'leri
+    pub fn advance<'a>(&self, records: impl IntoIterator<Item = (&'a str, &'a str)>) -> ScanCursor {
+        let mut next = self.clone();
+        for (time, id) in records {
+            match time.cmp(next.watermark.as_str()) {
+                std::cmp::Ordering::Less => continue,
+                std::cmp::Ordering::Greater => {
+                    next.watermark = time.to_string();
+                    next.ids.clear();
+                }
+                std::cmp::Ordering::Equal => {}
+            }
+            if !next.ids.iter().any(|seen| seen == id) {
+                next.ids.push(id.to_string());
+            }
+        }
+        next.ids.sort();
+        next
+    }
+
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        ScanCursor {
+            watermark: string_field(doc, &["watermark"]),
+            ids: string_array(doc, "ids"),
+        }
+    }
+
+    pub fn to_document(&self) -> MongoDocument {
+        doc! {
+            "watermark": &self.watermark,
+            "ids": &self.ids,
+        }
+    }
+}
+
+impl SavedSearchRecord {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        SavedSearchRecord {
+            id: id_string(doc, "_id"),
+            owner: string_field(doc, &["owner"]),
+            ad: string_field(doc, &["ad"]),
+            q: string_field(doc, &["q"]),
+            corpus: string_field(doc, &["corpus"]),
+            kurum_id: optional_string(doc, &["kurum_id"]),
+            belge_turu: optional_string(doc, &["belge_turu"]),
+            kanal: string_field(doc, &["kanal"]),
+            hedef: optional_string(doc, &["hedef"]),
+            secret: optional_string(doc, &["secret"]),
+            aktif: doc.get_bool("aktif").unwrap_or(true),
+            cursor: doc.get_document("cursor").ok().map(ScanCursor::from_document),
+            last_run_at: optional_string(doc, &["last_run_at"]),
+            last_error: optional_string(doc, &["last_error"]),
+            created_at: string_field(doc, &["created_at"]),
+            updated_at: string_field(doc, &["updated_at"]),
+        }
+    }
+}
+
+impl SavedSearchWrite {
+    pub fn to_document(&self) -> MongoDocument {
+        let mut document = doc! {
+            "owner": &self.owner,
+            "ad": &self.ad,
+            "q": &self.q,
+            "corpus": &self.corpus,
+            "kurum_id": self.kurum_id.as_deref(),
+            "belge_turu": self.belge_turu.as_deref(),
+            "kanal": &self.kanal,
+            "hedef": self.hedef.as_deref(),
+            "secret": self.secret.as_deref(),
+            "aktif": self.aktif,
+            "updated_at": &self.updated_at,
+        };
+        if self.reset_cursor {
+            document.insert("cursor", Bson::Null);
+        }
+        if let Some(created_at) = &self.created_at {
+            document.insert("created_at", created_at);
+        }
+        document
+    }
+}
+
+impl SavedSearchRun {
+    // $set ile yazılacak alanlar
+    pub fn to_document(&self) -> MongoDocument {
+        doc! {
+            "cursor": self.cursor.as_ref().map(ScanCursor::to_document),
+            "last_run_at": &self.run_at,
+            "last_error": self.error.as_deref(),
+        }
+    }
+}
+
+impl AlertHit {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        AlertHit {
+            id: string_field(doc, &["id"]),
+            pdf_adi: string_field(doc, &["pdf_adi"]),
+            url_slug: string_field(doc, &["url_slug"]),
+            url: string_field(doc, &["url"]),
+            kurum_adi: string_field(doc, &["kurum_adi"]),
+            belge_turu: string_field(doc, &["belge_turu"]),
+            content_preview: string_field(doc, &["content_preview"]),
+            olusturulma_tarihi: string_field(doc, &["olusturulma_tarihi"]),
+        }
+    }
+
+    pub fn to_document(&self) -> MongoDocument {
+        doc! {
+            "id": &self.id,
+            "pdf_adi": &self.pdf_adi,
+            "url_slug": &self.url_slug,
+            "url": &self.url,
+            "kurum_adi": &self.kurum_adi,
+            "belge_turu": &self.belge_turu,
+            "content_preview": &self.content_preview,
+            "olusturulma_tarihi": &self.olusturulma_tarihi,
+        }
+    }
+}
+
+impl AlertRecord {
+    pub fn from_document(doc: &MongoDocument) -> Self {
+        AlertRecord {
+            id: id_string(doc, "_id"),
+            saved_search_id: string_field(doc, &["saved_search_id"]),
+            owner: string_field(doc, &["owner"]),
+            ad: string_field(doc, &["ad"]),
+            q: string_field(doc, &["q"]),
+            hits: doc
+                .get_array("hits")
+                .map(|hits| hits.iter().filter_map(Bson::as_document).map(AlertHit::from_document).collect())
+                .unwrap_or_default(),
+            kanal: string_field(doc, &["kanal"]),
+            notification_status: optional_string(doc, &["notification_status"]),
+            notification_error: optional_string(doc, &["notification_error"]),
+            created_at: string_field(doc, &["created_at"]),
+            read_at: optional_string(doc, &["read_at"]),
+        }
+    }
+}
+
+impl AlertWrite {
+    pub fn to_document(&self) -> MongoDocument {
+        doc! {
+            "saved_search_id": &self.saved_search_id,
+            "owner": &self.owner,
+            "ad": &self.ad,
+            "q": &self.q,
+            "hits": self.hits.iter().map(AlertHit::to_document).collect::<Vec<_>>(),
+            "kanal": &self.kanal,
+            "created_at": &self.created_at,
+            "read_at": Bson::Null,
+        }
+    }
+}
+
+fn string_array(doc: &MongoDocument, field: &str) -> Vec<String> {
+    doc.get_array(field)
+        .map(|values| values.iter().filter_map(|value| value.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+// İlk bulunan string alanı döndür, hiçbiri yoksa boş string
+fn string_field(doc: &MongoDocument, fields: &[&str]) -> String {
+    fields
+        .iter()
+        .find_map(|field| doc.get_str(field).ok())
+        .unwrap_or("")
+        .to_string()
+}
+
+// İlk bulunan boş olmayan string alanı döndür
+fn optional_string(doc: &MongoDocument, fields: &[&str]) -> Option<String> {
+    fields
+        .iter()
+        .filter_map(|field| doc.get_str(field).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| value.to_string())
+}
+
+// Foreign key olarak yazılacak id: ObjectId'ye çevrilebilen string'ler ObjectId olur
+pub(crate) fn canonical_id(id: &Bson) -> Bson {
+    match id {
+        Bson::String(value) => ObjectId::parse_str(value)
+            .map(Bson::ObjectId)
+            .unwrap_or_else(|_| id.clone()),
+        other => other.clone(),
+    }
+}
+
+// ObjectId ya da string olarak saklanan id alanlarını hex string'e çevir
+pub(crate) fn id_string(doc: &MongoDocument, field: &str) -> String {
+    match doc.get(field) {
+        Some(Bson::ObjectId(oid)) => oid.to_hex(),
+        Some(Bson::String(value)) => value.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn int_field(doc: &MongoDocument, field: &str) -> Option<i32> {
+    match doc.get(field) {
+        Some(Bson::Int32(value)) => Some(*value),
+        Some(Bson::Int64(value)) => Some(*value as i32),
+        Some(Bson::Double(value)) => Some(*value as i32),
+        Some(Bson::String(value)) => value.parse().ok(),
+        _ => None,
+    }
+}
+
+fn float_field(doc: &MongoDocument, field: &str) -> Option<f64> {
+    match doc.get(field) {
+        Some(Bson::Double(value)) => Some(*value),
+        Some(Bson::Int32(value)) => Some(*value as f64),
+        Some(Bson::Int64(value)) => Some(*value as f64),
+        Some(Bson::String(value)) => value.parse().ok(),
+        _ => None,
+    }
+}
+
+fn object_id_time(doc: &MongoDocument) -> Option<chrono::DateTime<chrono::Utc>> {
+    let oid = doc.get_object_id("_id").ok()?;
+    let timestamp = oid.timestamp().timestamp_millis() / 1000;
+    chrono::DateTime::from_timestamp(timestamp, 0)
+}
+
+// Virgülle ayrılmış etiket alanlarından tekil ve sıralı etiket listesi oluştur
+pub(crate) fn collect_etiketler<'a>(values: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut etiketler: Vec<String> = values
+        .flat_map(|value| value.split(','))
+        .map(|etiket| etiket.trim())
+        .filter(|etiket| !etiket.is_empty())
+        .map(|etiket| etiket.to_string())
+        .collect::<std::collections::HashSet<_>>()
+        .into_iter()
+        .collect();
+    etiketler.sort();
+    etiketler
+}
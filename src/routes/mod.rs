@@ -1,9 +1,38 @@
-use actix_web::web;
+use actix_web::middleware::from_fn;
+use actix_web::{web, HttpRequest};
+use crate::error::ApiError;
 use crate::handlers;
+use crate::indexnow;
+use crate::middleware;
+use crate::openapi;
+
+// Query string çözümlenemediğinde (örn. zorunlu parametre eksik) ortak hata zarfı döner
+fn query_config() -> web::QueryConfig {
+    web::QueryConfig::default()
+        .error_handler(|err, _req| ApiError::InvalidQueryString(err.to_string()).into())
+}
+
+// JSON gövdesi çözümlenemediğinde ortak hata zarfı döner. Belge içerikleri büyük
+// olabildiğinden gövde sınırı varsayılan 2 MB'tan yüksektir.
+fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default()
+        .limit(16 * 1024 * 1024)
+        .error_handler(|err, _req| ApiError::InvalidBody(err.to_string()).into())
+}
+
+async fn route_not_found(_req: HttpRequest) -> Result<&'static str, ApiError> {
+    Err(ApiError::RouteNotFound)
+}
 
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    // OpenAPI dokümanı ve Swagger UI; /api scope'undan önce kaydedilmeli
+    cfg.service(openapi::service());
+
     cfg.service(
         web::scope("/api")
+            .app_data(query_config())
+            .app_data(json_config())
+            .default_service(web::to(route_not_found))
             .route("/health", web::get().to(handlers::health::health_check))
             .service(web::scope("/v1/institutions").configure(handlers::kurum::routes))
             .service(web::scope("/v1/documents").configure(handlers::document::routes))
@@ -13,12 +42,34 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
             .service(web::scope("/v2/regulations").configure(handlers::regulation_yargitay::routes))
             .service(web::scope("/v1/statistics").configure(handlers::statistics::routes))
             .service(web::scope("/v1/kurum-duyuru").configure(handlers::kurum_duyuru::routes))
+            // /v1/search kapsamından önce; aksi halde alt yolları o kapsam yakalar
+            .service(web::scope("/v1/search/saved").configure(handlers::saved_search::saved_routes))
+            .service(web::scope("/v1/search/alerts").configure(handlers::saved_search::alert_routes))
             .service(web::scope("/v1/search").configure(handlers::search::routes))
             .service(web::scope("/v2/search").configure(handlers::search_yargitay::routes_v2))
             .service(web::scope("/v2/documents").configure(handlers::documents_yargitay::routes_v2))
             .service(web::scope("/v1/sitemap").configure(handlers::sitemap::routes))
+            .service(web::scope("/v1/stream").configure(handlers::stream::routes))
+            .service(web::scope("/v1/corpora").configure(handlers::corpus::routes))
+            // Yönetim route'ları; anahtar doğrulamasından sonra ayrıca admin scope'u aranır
+            .service(
+                web::scope("/admin")
+                    .wrap(from_fn(middleware::require_admin))
+                    .service(web::scope("/documents").configure(handlers::admin_documents::routes))
+                    .service(web::scope("/institutions").configure(handlers::admin_institutions::routes))
+                    .service(web::scope("/announcement-sources").configure(handlers::admin_announcement_sources::routes))
+                    .service(web::scope("/links").configure(handlers::admin_links::routes))
+                    .service(web::scope("/audit").configure(handlers::admin_audit::routes))
+                    .service(web::scope("/crawler").configure(handlers::admin_crawler::routes))
+                    .service(web::scope("/announcement-attachments").configure(handlers::admin_announcement_attachments::routes))
+                    .service(web::scope("/webhooks").configure(handlers::admin_webhooks::routes)),
+            )
             // Yeni route'lar buraya eklenecek
     )
-    .route("/sitemap.xml", web::get().to(handlers::sitemap::get_sitemap_xml));
+    .route("/sitemap.xml", web::get().to(handlers::sitemap::get_sitemap_xml))
+    .route("/sitemaps/{name}", web::get().to(handlers::sitemap::get_child_sitemap))
+    .route("/robots.txt", web::get().to(handlers::sitemap::get_robots_txt))
+    .route(indexnow::KEY_FILE_PATH, web::get().to(handlers::sitemap::get_indexnow_key))
+    .service(web::scope("/feeds").configure(handlers::feed::routes));
 }
 
@@ -0,0 +1,475 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, LOCATION, RETRY_AFTER, USER_AGENT};
+use reqwest::{redirect, Response, StatusCode};
+use tokio::sync::Semaphore;
+use url::Url;
+use super::robots::Robots;
+
+// Kurum sitelerine nazik tarama: istekler kendini tanıtan bir User-Agent ile gönderilir,
+// robots.txt'ye uyulur, aynı host'a eşzamanlı istek sayısı sınırlanır, geçici hatalar
+// üstel beklemeyle yeniden denenir ve art arda hata veren host için devre açılır
+// (cooldown boyunca istek gönderilmez, sonra tek denemeyle yeniden yoklanır). Yönlendirmeler
+// istemciye bırakılmaz; her adım aynı denetimlerden geçen ayrı bir istek olarak izlenir.
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+const ROBOTS_TTL: Duration = Duration::from_secs(24 * 3600);
+// Yeniden denemeler arasındaki en uzun bekleme (Retry-After dahil)
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+// Bir isteğin izlediği en fazla yönlendirme
+const MAX_REDIRECTS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrawlConfig {
+    // Ürün adı ve sürümü; robots.txt grupları "/" öncesine göre eşleşir
+    pub user_agent: String,
+    // User-Agent'a "(+url)" olarak eklenir; site yöneticileri için iletişim adresi
+    pub contact_url: Option<String>,
+    pub host_concurrency: usize,
+    // İlk denemeden sonra en fazla yeniden deneme sayısı (5xx, 429 ve ağ hataları)
+    pub max_retries: u32,
+    // İlk yeniden denemeden önceki bekleme; her denemede iki katına çıkar (en fazla MAX_BACKOFF)
+    pub backoff: Duration,
+    // Art arda bu kadar başarısız istekten sonra host için devre açılır
+    pub breaker_threshold: u32,
+    pub breaker_cooldown: Duration,
+    pub respect_robots: bool,
+}
+
+impl Default for CrawlConfig {
+    fn default() -> Self {
+        CrawlConfig {
+            user_agent: "MevzuatGPTBot/1.0".to_string(),
+            contact_url: Some("https://mevzuatgpt.org".to_string()),
+            host_concurrency: 2,
+            max_retries: 2,
+            backoff: Duration::from_millis(500),
+            breaker_threshold: 5,
+            breaker_cooldown: Duration::from_secs(300),
+            respect_robots: true,
+        }
+    }
+}
+
+fn env_parse<T: std::str::FromStr>(name: &str) -> Option<T> {
+    env::var(name).ok().and_then(|value| value.trim().parse().ok())
+}
+
+impl CrawlConfig {
+    // CRAWLER_USER_AGENT, CRAWLER_CONTACT_URL (boş ise eklenmez), CRAWLER_HOST_CONCURRENCY,
+    // CRAWLER_MAX_RETRIES, CRAWLER_BACKOFF_MS, CRAWLER_BREAKER_THRESHOLD,
+    // CRAWLER_BREAKER_COOLDOWN (saniye), CRAWLER_RESPECT_ROBOTS
+    pub fn from_env() -> Self {
+        let default = CrawlConfig::default();
+        CrawlConfig {
+            user_agent: env::var("CRAWLER_USER_AGENT")
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .unwrap_or(default.user_agent),
+            contact_url: match env::var("CRAWLER_CONTACT_URL") {
+                Ok(value) => Some(value.trim().to_string()).filter(|value| !value.is_empty()),
+                Err(_) => default.contact_url,
+            },
+            host_concurrency: env_parse("CRAWLER_HOST_CONCURRENCY").unwrap_or(default.host_concurrency).max(1),
+            max_retries: env_parse("CRAWLER_MAX_RETRIES").unwrap_or(default.max_retries),
+            backoff: env_parse("CRAWLER_BACKOFF_MS").map(Duration::from_millis).unwrap_or(default.backoff),
+            breaker_threshold: env_parse("CRAWLER_BREAKER_THRESHOLD").unwrap_or(default.breaker_threshold).max(1),
+            breaker_cooldown: env_parse("CRAWLER_BREAKER_COOLDOWN")
+                .map(Duration::from_secs)
+                .unwrap_or(default.breaker_cooldown),
+            respect_robots: env::var("CRAWLER_RESPECT_ROBOTS")
+                .map(|value| !matches!(value.trim().to_ascii_lowercase().as_str(), "false" | "0" | "off"))
+                .unwrap_or(default.respect_robots),
+        }
+    }
+
+    pub fn user_agent_header(&self) -> String {
+        match &self.contact_url {
+            Some(contact) => format!("{} (+{})", self.user_agent, contact),
+            None => self.user_agent.clone(),
+        }
+    }
+
+    fn product(&self) -> &str {
+        self.user_agent.split('/').next().unwrap_or(&self.user_agent)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    Closed,
+    // Cooldown sürüyor; istek gönderilmez
+    Open,
+    // Cooldown bitti; sıradaki tek istek host'u yoklar, yoklama sürerken diğerleri beklemez,
+    // devre açık sayılır
+    HalfOpen,
+}
+
+impl BreakerState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BreakerState::Closed => "closed",
+            BreakerState::Open => "open",
+            BreakerState::HalfOpen => "half_open",
+        }
+    }
+}
+
+// Yönetim API'sinde gösterilen host durumu
+#[derive(Debug, Clone)]
+pub struct HostStatus {
+    pub host: String,
+    pub state: BreakerState,
+    pub consecutive_failures: u32,
+    pub open_until: Option<DateTime<Utc>>,
+    pub in_flight: usize,
+    pub requests: u64,
+    pub failures: u64,
+    pub last_error: Option<String>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    pub robots_fetched_at: Option<DateTime<Utc>>,
+}
+
+struct HostState {
+    permits: Arc<Semaphore>,
+    consecutive_failures: u32,
+    open_until: Option<DateTime<Utc>>,
+    // Yarı açık devrede yoklama isteği sürüyor
+    probing: bool,
+    requests: u64,
+    failures: u64,
+    last_error: Option<String>,
+    last_success_at: Option<DateTime<Utc>>,
+    last_failure_at: Option<DateTime<Utc>>,
+    robots: Option<(Robots, Instant)>,
+    robots_fetched_at: Option<DateTime<Utc>>,
+}
+
+impl HostState {
+    fn new(concurrency: usize) -> Self {
+        HostState {
+            permits: Arc::new(Semaphore::new(concurrency)),
+            consecutive_failures: 0,
+            open_until: None,
+            probing: false,
+            requests: 0,
+            failures: 0,
+            last_error: None,
+            last_success_at: None,
+            last_failure_at: None,
+            robots: None,
+            robots_fetched_at: None,
+        }
+    }
+
+    fn breaker_state(&self, now: DateTime<Utc>) -> BreakerState {
+        match self.open_until {
+            Some(until) if now < until => BreakerState::Open,
+            Some(_) => BreakerState::HalfOpen,
+            None => BreakerState::Closed,
+        }
+    }
+}
+
+// 3xx cevabın Location başlığı (göreli ise isteğin adresine göre); 304 yönlendirme değildir
+fn redirect_target(url: &Url, response: &Response) -> Option<Result<Url, String>> {
+    if !response.status().is_redirection() || response.status() == StatusCode::NOT_MODIFIED {
+        return None;
+    }
+    let location = response.headers().get(LOCATION).and_then(|value| value.to_str().ok());
+    Some(match location {
+        Some(location) => url.join(location).map_err(|e| format!("Geçersiz yönlendirme ({}): {}", location, e)),
+        None => Err(format!("Sayfa çekilemedi: HTTP {} (Location yok)", response.status())),
+    })
+}
+
+// 429/503 cevabındaki Retry-After: saniye ya da HTTP tarihi; geçmiş tarih beklemesiz demektir
+fn retry_after(response: &Response) -> Option<Duration> {
+    if !matches!(response.status(), StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE) {
+        return None;
+    }
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((date.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default())
+}
+
+// Host anahtarı: varsayılan dışı port varsa "host:port"
+fn host_key(url: &Url) -> Result<String, String> {
+    let host = url.host_str().ok_or_else(|| format!("Geçersiz URL: {}", url))?;
+    Ok(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    })
+}
+
+#[derive(Clone)]
+pub struct Crawler {
+    client: reqwest::Client,
+    config: Arc<CrawlConfig>,
+    hosts: Arc<Mutex<HashMap<String, HostState>>>,
+}
+
+impl Crawler {
+    pub fn new(config: CrawlConfig) -> Self {
+        Crawler {
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .redirect(redirect::Policy::none())
+                .build()
+                .expect("HTTP client oluşturulamadı"),
+            config: Arc::new(config),
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn hosts(&self) -> Vec<HostStatus> {
+        let now = Utc::now();
+        let concurrency = self.config.host_concurrency;
+        let mut hosts: Vec<HostStatus> = self
+            .hosts
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(host, state)| HostStatus {
+                host: host.clone(),
+                state: state.breaker_state(now),
+                consecutive_failures: state.consecutive_failures,
+                open_until: state.open_until,
+                in_flight: concurrency.saturating_sub(state.permits.available_permits()),
+                requests: state.requests,
+                failures: state.failures,
+                last_error: state.last_error.clone(),
+                last_success_at: state.last_success_at,
+                last_failure_at: state.last_failure_at,
+                robots_fetched_at: state.robots_fetched_at,
+            })
+            .collect();
+        hosts.sort_by(|a, b| a.host.cmp(&b.host));
+        hosts
+    }
+
+    fn with_host<T>(&self, host: &str, f: impl FnOnce(&mut HostState) -> T) -> T {
+        let mut hosts = self.hosts.lock().unwrap();
+        let state = hosts
+            .entry(host.to_string())
+            .or_insert_with(|| HostState::new(self.config.host_concurrency));
+        f(state)
+    }
+
+    // Devre açıksa hata, yarı açıksa bu istek tek yoklama olarak geçer: open_until cooldown kadar
+    // ileri alınır ki eşzamanlı istekler de yoklama yapmasın. Dönen değer isteğin yoklama olup olmadığı
+    fn admit(&self, host: &str) -> Result<bool, String> {
+        let cooldown = chrono::Duration::from_std(self.config.breaker_cooldown).unwrap_or_default();
+        self.with_host(host, |state| {
+            let now = Utc::now();
+            match state.breaker_state(now) {
+                BreakerState::Closed => Ok(false),
+                BreakerState::HalfOpen => {
+                    state.open_until = Some(now + cooldown);
+                    state.probing = true;
+                    Ok(true)
+                }
+                BreakerState::Open => Err(format!(
+                    "{} art arda hata verdiği için geçici olarak taranmıyor (devre {} tarihine kadar açık)",
+                    host,
+                    state.open_until.unwrap_or(now).to_rfc3339()
+                )),
+            }
+        })
+    }
+
+    // Başarı ya da hata kaydedilmeden biten yoklama (robots.txt yasağı, 4xx) devreyi yeniden yarı
+    // açık bırakır
+    fn release_probe(&self, host: &str) {
+        self.with_host(host, |state| {
+            if state.probing {
+                state.probing = false;
+                state.open_until = Some(Utc::now());
+            }
+        });
+    }
+
+    fn record_success(&self, host: &str) {
+        self.with_host(host, |state| {
+            state.consecutive_failures = 0;
+            state.open_until = None;
+            state.probing = false;
+            state.last_success_at = Some(Utc::now());
+        });
+    }
+
+    fn record_failure(&self, host: &str, error: &str) {
+        let threshold = self.config.breaker_threshold;
+        let cooldown = chrono::Duration::from_std(self.config.breaker_cooldown).unwrap_or_default();
+        let opened = self.with_host(host, |state| {
+            let now = Utc::now();
+            state.failures += 1;
+            state.consecutive_failures += 1;
+            state.last_error = Some(error.to_string());
+            state.last_failure_at = Some(now);
+            state.probing = false;
+            // Yarı açık devrede tek hata devreyi yeniden açar
+            if state.consecutive_failures >= threshold {
+                state.open_until = Some(now + cooldown);
+                return true;
+            }
+            false
+        });
+        if opened {
+            log::warn!("{} için devre açıldı: {}", host, error);
+        }
+    }
+
+    // Host için geçerli robots.txt; süresi geçmişse yeniden alınır
+    async fn robots(&self, url: &Url, host: &str) -> Result<Robots, String> {
+        let cached = self.with_host(host, |state| {
+            state
+                .robots
+                .as_ref()
+                .filter(|(_, fetched_at)| fetched_at.elapsed() < ROBOTS_TTL)
+                .map(|(robots, _)| robots.clone())
+        });
+        if let Some(robots) = cached {
+            return Ok(robots);
+        }
+
+        // robots.txt'nin kendi yönlendirmeleri (örn. http -> https) izlenir
+        let mut robots_url = url.join("/robots.txt").map_err(|e| e.to_string())?;
+        let mut redirects = 0;
+        let response = loop {
+            let response = self
+                .client
+                .get(robots_url.as_str())
+                .header(USER_AGENT, self.config.user_agent_header())
+                .send()
+                .await
+                .map_err(|e| format!("robots.txt alınamadı: {}", e))?;
+            match redirect_target(&robots_url, &response) {
+                Some(Ok(target)) if redirects < MAX_REDIRECTS => {
+                    robots_url = target;
+                    redirects += 1;
+                }
+                _ => break response,
+            }
+        };
+        let robots = match response.status() {
+            status if status.is_success() => {
+                Robots::parse(&response.text().await.unwrap_or_default(), self.config.product())
+            }
+            // robots.txt yoksa (4xx) tarama serbesttir
+            status if status.is_client_error() => Robots::allow_all(),
+            // Sunucu hatası: RFC 9309'a göre site tamamen yasak sayılır; sonuç saklanmaz
+            status => return Err(format!("robots.txt alınamadı: HTTP {}", status)),
+        };
+
+        self.with_host(host, |state| {
+            state.robots = Some((robots.clone(), Instant::now()));
+            state.robots_fetched_at = Some(Utc::now());
+        });
+        Ok(robots)
+    }
+
+    // Sayfa için GET isteği; 2xx ve 304 dışındaki cevaplar hata döner
+    pub async fn get(&self, url: &Url, headers: HeaderMap) -> Result<Response, String> {
+        self.get_with_timeout(url, headers, REQUEST_TIMEOUT).await
+    }
+
+    // get ile aynı; gövdenin okunması da dahil süre sınırı verilir (büyük dosyalar).
+    // Yönlendirmenin her adımı hedef host'un devresinden, robots.txt'sinden ve eşzamanlılık
+    // sınırından geçer
+    pub async fn get_with_timeout(&self, url: &Url, headers: HeaderMap, timeout: Duration) -> Result<Response, String> {
+        let mut url = url.clone();
+        for _ in 0..=MAX_REDIRECTS {
+            let host = host_key(&url)?;
+            let probe = self.admit(&host)?;
+            let result = self.send(&url, &host, headers.clone(), timeout).await;
+            if probe {
+                self.release_probe(&host);
+            }
+            let response = result?;
+            match redirect_target(&url, &response) {
+                Some(target) => url = target?,
+                None => return Ok(response),
+            }
+        }
+        Err(format!("Sayfa çekilemedi: çok fazla yönlendirme ({})", url))
+    }
+
+    // Devre denetiminden geçen istek: robots.txt, host başına eşzamanlılık ve yeniden denemeler
+    async fn send(&self, url: &Url, host: &str, headers: HeaderMap, timeout: Duration) -> Result<Response, String> {
+        if self.config.respect_robots {
+            let robots = match self.robots(url, host).await {
+                Ok(robots) => robots,
+                Err(e) => {
+                    self.record_failure(host, &e);
+                    return Err(e);
+                }
+            };
+            let path = match url.query() {
+                Some(query) => format!("{}?{}", url.path(), query),
+                None => url.path().to_string(),
+            };
+            if !robots.allows(&path) {
+                return Err(format!("robots.txt {} adresinin taranmasına izin vermiyor", url));
+            }
+        }
+
+        let permits = self.with_host(host, |state| state.permits.clone());
+        let _permit = permits.acquire_owned().await.map_err(|e| e.to_string())?;
+
+        let mut attempt = 0;
+        loop {
+            self.with_host(host, |state| state.requests += 1);
+            let result = self
+                .client
+                .get(url.as_str())
+                .timeout(timeout)
+                .headers(headers.clone())
+                .header(USER_AGENT, self.config.user_agent_header())
+                .send()
+                .await;
+            let retryable = match &result {
+                Ok(response) => response.status().is_server_error() || response.status() == StatusCode::TOO_MANY_REQUESTS,
+                Err(_) => true,
+            };
+            if retryable && attempt < self.config.max_retries {
+                let delay = result
+                    .as_ref()
+                    .ok()
+                    .and_then(retry_after)
+                    .unwrap_or_else(|| self.config.backoff.saturating_mul(2u32.saturating_pow(attempt)));
+                tokio::time::sleep(delay.min(MAX_BACKOFF)).await;
+                attempt += 1;
+                continue;
+            }
+
+            return match result {
+                // Yönlendirme de host'un ayakta olduğunu gösterir; hedefi get_with_timeout izler
+                Ok(response) if response.status().is_success() || response.status().is_redirection() => {
+                    self.record_success(host);
+                    Ok(response)
+                }
+                Ok(response) => {
+                    let error = format!("Sayfa çekilemedi: HTTP {}", response.status());
+                    // 4xx host'un ayakta olduğunu gösterir; devreyi etkilemez
+                    if retryable {
+                        self.record_failure(host, &error);
+                    }
+                    Err(error)
+                }
+                Err(e) => {
+                    let error = format!("Sayfa çekilemedi: {}", e);
+                    self.record_failure(host, &error);
+                    Err(error)
+                }
+            };
+        }
+    }
+}
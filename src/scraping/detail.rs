@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+use chrono::NaiveDate;
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+use crate::dates;
+use super::collapse_whitespace;
+
+// Duyuru detay sayfasının okunması: ana metin, yayın tarihi ve sayfadaki PDF/DOCX ekleri.
+// Ana metin bilinen içerik kapsayıcılarından (article, main, .icerik ...) en uzun metinli
+// olanıdır; hiçbiri yoksa body kullanılır. Menü, üst/alt bilgi ve script metinleri alınmaz.
+
+// Ana metin için denenen kapsayıcılar
+const CONTENT_SELECTORS: &str = "article, main, [role=main], [itemprop=articleBody], #content, .content, \
+    .icerik, .detay, .haber-detay, .duyuru-detay, .news-detail, .post-content, .entry-content";
+// Metni alınmayan elemanlar
+const EXCLUDED_TAGS: [&str; 9] = ["script", "style", "noscript", "nav", "header", "footer", "form", "aside", "template"];
+// Başladığı yerde yeni satır açan elemanlar
+const BLOCK_TAGS: [&str; 20] = [
+    "p", "div", "br", "li", "ul", "ol", "h1", "h2", "h3", "h4", "h5", "h6", "tr", "table", "section", "article",
+    "blockquote", "pre", "dd", "dt",
+];
+// Ek sayılan dosya uzantıları
+const ATTACHMENT_TYPES: [&str; 3] = ["pdf", "docx", "doc"];
+// Saklanan metnin üst sınırı (karakter)
+const MAX_TEXT_CHARS: usize = 20_000;
+const MAX_ATTACHMENTS: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    pub url: String,
+    pub baslik: String,
+    // Dosya uzantısı: pdf, docx, doc
+    pub tur: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnnouncementDetail {
+    // Paragraflar satır sonuyla ayrılır
+    pub icerik: String,
+    // Sayfada okunabilir bir yayın tarihi yoksa None
+    pub tarih: Option<NaiveDate>,
+    pub ekler: Vec<Attachment>,
+}
+
+pub fn extract_detail(html: &str, page_url: &Url) -> AnnouncementDetail {
+    let document = Html::parse_document(html);
+    let candidates = Selector::parse(CONTENT_SELECTORS).unwrap();
+    let body = Selector::parse("body").unwrap();
+
+    let icerik = document
+        .select(&candidates)
+        .map(readable_text)
+        .max_by_key(|text| text.chars().count())
+        .filter(|text| !text.is_empty())
+        .or_else(|| document.select(&body).next().map(readable_text))
+        .unwrap_or_default();
+    let icerik = truncate(&icerik, MAX_TEXT_CHARS);
+
+    let tarih = published_date(&document).or_else(|| dates::find(&icerik));
+    AnnouncementDetail {
+        tarih,
+        ekler: attachments(&document, page_url),
+        icerik,
+    }
+}
+
+fn is_excluded(element: ElementRef, root: ElementRef) -> bool {
+    element
+        .ancestors()
+        .take_while(|node| node.id() != root.id())
+        .filter_map(|node| node.value().as_element())
+        .chain(std::iter::once(element.value()))
+        .any(|element| EXCLUDED_TAGS.contains(&element.name()))
+}
+
+// Elemanın görünen metni; blok elemanlar ayrı satırlara yazılır
+fn readable_text(root: ElementRef) -> String {
+    let mut text = String::new();
+    for node in root.descendants() {
+        if let Some(element) = ElementRef::wrap(node) {
+            if BLOCK_TAGS.contains(&element.value().name()) {
+                text.push('\n');
+            }
+        } else if let Some(fragment) = node.value().as_text() {
+            let parent = node.parent().and_then(ElementRef::wrap);
+            if parent.is_none_or(|parent| !is_excluded(parent, root)) {
+                text.push_str(fragment);
+            }
+        }
+    }
+    text.lines()
+        .map(collapse_whitespace)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((index, _)) => text[..index].to_string(),
+        None => text.to_string(),
+    }
+}
+
+// Meta etiketleri ve <time> elemanından yayın tarihi
+fn published_date(document: &Html) -> Option<NaiveDate> {
+    let meta = Selector::parse(
+        "meta[property='article:published_time'], meta[itemprop=datePublished], meta[name=date], meta[name=pubdate]",
+    )
+    .unwrap();
+    let time = Selector::parse("time").unwrap();
+
+    let from_meta = document
+        .select(&meta)
+        .filter_map(|element| element.value().attr("content"))
+        .find_map(|value| dates::parse_timestamp(value).ok());
+    if let Some(timestamp) = from_meta {
+        return Some(timestamp.date_naive());
+    }
+    document.select(&time).find_map(|element| match element.value().attr("datetime") {
+        Some(value) => dates::parse_timestamp(value).ok().map(|timestamp| timestamp.date_naive()),
+        None => dates::parse(&collapse_whitespace(&element.text().collect::<String>())).ok(),
+    })
+}
+
+// Sayfadaki PDF/DOCX linkleri (sayfadaki sırayla, tekilleştirilmiş)
+fn attachments(document: &Html, page_url: &Url) -> Vec<Attachment> {
+    let links = Selector::parse("a[href]").unwrap();
+    let mut seen = HashSet::new();
+    let mut ekler = Vec::new();
+    for link in document.select(&links) {
+        let Some(url) = link.value().attr("href").and_then(|href| page_url.join(href.trim()).ok()) else {
+            continue;
+        };
+        let Some(tur) = attachment_type(&url) else {
+            continue;
+        };
+        if !seen.insert(url.to_string()) {
+            continue;
+        }
+        let baslik = match collapse_whitespace(&link.text().collect::<String>()) {
+            text if text.is_empty() => file_name(&url),
+            text => text,
+        };
+        ekler.push(Attachment { url: url.to_string(), baslik, tur });
+        if ekler.len() >= MAX_ATTACHMENTS {
+            break;
+        }
+    }
+    ekler
+}
+
+fn attachment_type(url: &Url) -> Option<String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let extension = url.path().rsplit_once('.')?.1.to_ascii_lowercase();
+    ATTACHMENT_TYPES.contains(&extension.as_str()).then_some(extension)
+}
+
+fn file_name(url: &Url) -> String {
+    url.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .map(|name| name.replace("%20", " "))
+        .unwrap_or_default()
+}
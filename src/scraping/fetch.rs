@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use reqwest::header::{HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use url::Url;
+use super::crawl::{CrawlConfig, Crawler};
+
+// Duyuru sayfalarının indirilmesi: tüm taramalar tek HTTP client'ı ve URL'ye göre tutulan
+// sayfa önbelleğini paylaşır. Taze sayfa siteye gitmeden döner; bayatlamış sayfa hemen
+// dönerken arka planda yenilenir (stale-while-revalidate); süresi tamamen geçmiş sayfa
+// ETag/Last-Modified ile koşullu istekle doğrulanır, 304 gelirse gövde yeniden indirilmez.
+// İstekler Crawler üzerinden gider (robots.txt, host başına eşzamanlılık, devre kesici).
+// Önbellek sayfa sayısıyla sınırlıdır; dolunca en uzun süredir kullanılmayan sayfa çıkarılır.
+
+const DEFAULT_TTL_SECS: u64 = 300;
+const DEFAULT_STALE_SECS: u64 = 3600;
+const DEFAULT_MAX_PAGES: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchConfig {
+    // Bu süre içinde sayfa önbellekten döner
+    pub ttl: Duration,
+    // ttl'den sonraki bu süre içinde önbellekteki sayfa döner ve arka planda yenilenir
+    pub stale_while_revalidate: Duration,
+    // Önbellekte tutulan en fazla sayfa
+    pub max_pages: usize,
+    pub crawl: CrawlConfig,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        FetchConfig {
+            ttl: Duration::from_secs(DEFAULT_TTL_SECS),
+            stale_while_revalidate: Duration::from_secs(DEFAULT_STALE_SECS),
+            max_pages: DEFAULT_MAX_PAGES,
+            crawl: CrawlConfig::default(),
+        }
+    }
+}
+
+fn env_secs(name: &str, default: u64) -> Duration {
+    Duration::from_secs(
+        env::var(name)
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(default),
+    )
+}
+
+impl FetchConfig {
+    // SCRAPE_CACHE_TTL ve SCRAPE_CACHE_STALE: saniye; TTL 0 her istekte koşullu doğrulama yapar.
+    // SCRAPE_CACHE_MAX_PAGES: önbellekteki en fazla sayfa
+    pub fn from_env() -> Self {
+        FetchConfig {
+            ttl: env_secs("SCRAPE_CACHE_TTL", DEFAULT_TTL_SECS),
+            stale_while_revalidate: env_secs("SCRAPE_CACHE_STALE", DEFAULT_STALE_SECS),
+            max_pages: env::var("SCRAPE_CACHE_MAX_PAGES")
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or(DEFAULT_MAX_PAGES)
+                .max(1),
+            crawl: CrawlConfig::from_env(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CachedPage {
+    body: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched_at: Instant,
+    // Son okunma zamanı; önbellek dolunca en eski olan çıkarılır
+    used_at: Instant,
+    // Arka planda yenileme sürüyor; aynı sayfa için ikinci yenileme başlatılmaz
+    revalidating: bool,
+}
+
+// Paylaşılan tarayıcı ve sayfa önbelleği; klonlar aynı önbelleği ve host durumlarını kullanır
+#[derive(Clone)]
+pub struct Fetcher {
+    crawler: Crawler,
+    config: FetchConfig,
+    // Anahtar sayfa URL'si. Süresi geçen kayıtlar hemen silinmez: doğrulayıcıları (ETag vb.)
+    // sonraki koşullu istekte kullanılır; kayıt sayısı max_pages ile sınırlıdır.
+    pages: Arc<Mutex<HashMap<String, CachedPage>>>,
+}
+
+impl Fetcher {
+    pub fn new(config: FetchConfig) -> Self {
+        Fetcher {
+            crawler: Crawler::new(config.crawl.clone()),
+            config,
+            pages: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn crawler(&self) -> &Crawler {
+        &self.crawler
+    }
+
+    pub async fn fetch(&self, url: &Url) -> Result<String, String> {
+        let cached = self.pages.lock().unwrap().get_mut(url.as_str()).map(|page| {
+            page.used_at = Instant::now();
+            page.clone()
+        });
+        let Some(page) = cached else {
+            return self.revalidate(url).await;
+        };
+
+        let age = page.fetched_at.elapsed();
+        if age < self.config.ttl {
+            return Ok(page.body);
+        }
+        if age < self.config.ttl + self.config.stale_while_revalidate {
+            if self.begin_revalidation(url) {
+                let fetcher = self.clone();
+                let url = url.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = fetcher.revalidate(&url).await {
+                        log::warn!("Önbellekteki sayfa yenilenemedi: {}: {}", url, e);
+                    }
+                });
+            }
+            return Ok(page.body);
+        }
+        self.revalidate(url).await
+    }
+
+    // Önbelleğe yazmadan çeker (duyuru detay sayfaları gibi bir kez okunan sayfalar için)
+    pub async fn fetch_uncached(&self, url: &Url) -> Result<String, String> {
+        let response = self.crawler.get(url, HeaderMap::new()).await?;
+        response.text().await.map_err(|e| format!("HTML okunamadı: {}", e))
+    }
+
+    // Yenileme bayrağını alır; başka bir yenileme sürüyorsa false
+    fn begin_revalidation(&self, url: &Url) -> bool {
+        let mut pages = self.pages.lock().unwrap();
+        match pages.get_mut(url.as_str()) {
+            Some(page) if !page.revalidating => {
+                page.revalidating = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    async fn revalidate(&self, url: &Url) -> Result<String, String> {
+        let result = self.request(url).await;
+        if result.is_err() {
+            if let Some(page) = self.pages.lock().unwrap().get_mut(url.as_str()) {
+                page.revalidating = false;
+            }
+        }
+        result
+    }
+
+    // Sayfayı siteden ister; önbellekte doğrulayıcı varsa istek koşullu gönderilir
+    async fn request(&self, url: &Url) -> Result<String, String> {
+        let validators = self
+            .pages
+            .lock()
+            .unwrap()
+            .get(url.as_str())
+            .map(|page| (page.etag.clone(), page.last_modified.clone()));
+
+        let mut headers = HeaderMap::new();
+        if let Some((etag, last_modified)) = validators {
+            if let Some(value) = etag.and_then(|etag| HeaderValue::from_str(&etag).ok()) {
+                headers.insert(IF_NONE_MATCH, value);
+            }
+            if let Some(value) = last_modified.and_then(|date| HeaderValue::from_str(&date).ok()) {
+                headers.insert(IF_MODIFIED_SINCE, value);
+            }
+        }
+
+        let response = self.crawler.get(url, headers).await?;
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            let mut pages = self.pages.lock().unwrap();
+            if let Some(page) = pages.get_mut(url.as_str()) {
+                page.fetched_at = Instant::now();
+                page.revalidating = false;
+                return Ok(page.body.clone());
+            }
+            return Err("Sayfa çekilemedi: önbellekte olmayan sayfa için 304 döndü".to_string());
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let body = response.text().await.map_err(|e| format!("HTML okunamadı: {}", e))?;
+
+        let now = Instant::now();
+        let mut pages = self.pages.lock().unwrap();
+        pages.insert(
+            url.to_string(),
+            CachedPage {
+                body: body.clone(),
+                etag,
+                last_modified,
+                fetched_at: now,
+                used_at: now,
+                revalidating: false,
+            },
+        );
+        while pages.len() > self.config.max_pages {
+            let Some(oldest) = pages.iter().min_by_key(|(_, page)| page.used_at).map(|(url, _)| url.clone()) else {
+                break;
+            };
+            pages.remove(&oldest);
+        }
+        Ok(body)
+    }
+}
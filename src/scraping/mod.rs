@@ -0,0 +1,144 @@
+pub mod crawl;
+pub mod detail;
+pub mod fetch;
+pub mod patterns;
+pub mod robots;
+pub mod rules;
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+use regex::{Captures, Regex};
+use url::Url;
+use crate::models::kurum_duyuru_scraped::DuyuruItem;
+use crate::repository::AnnouncementSourceRecord;
+
+pub use crawl::{BreakerState, CrawlConfig, Crawler, HostStatus};
+pub use detail::{extract_detail, AnnouncementDetail, Attachment};
+pub use fetch::{FetchConfig, Fetcher};
+pub use patterns::PatternScraper;
+pub use rules::{RuleScraper, ScrapeRules};
+
+// Kurum duyuru sayfalarının taranması. kurum_duyuru kaydında `scraper` kuralları (CSS
+// seçicileri) varsa sayfa RuleScraper ile okunur; yoksa domain'e göre hazır link desenleri
+// (PatternScraper) kullanılır. Yeni kurum eklemek için kod değil, kural girmek yeterlidir.
+
+pub trait Scraper: Send + Sync {
+    // Tek sayfadaki duyurular, sayfadaki sırayla
+    fn extract(&self, html: &str, page_url: &Url) -> Vec<DuyuruItem>;
+
+    // Sonraki sayfanın adresi; sayfalama yoksa None
+    fn next_page(&self, _html: &str, _page_url: &Url) -> Option<Url> {
+        None
+    }
+
+    // Taranacak en fazla sayfa sayısı
+    fn max_pages(&self) -> usize {
+        1
+    }
+}
+
+// Kaynağın kurallarına göre scraper seçer; kayıttaki kurallar geçersizse hata döner
+pub fn scraper_for(source: &AnnouncementSourceRecord) -> Result<Box<dyn Scraper>, String> {
+    match &source.scraper {
+        Some(rules) => Ok(Box::new(RuleScraper::new(rules)?)),
+        None => Ok(Box::new(PatternScraper::for_url(&source.duyuru_linki))),
+    }
+}
+
+// Kaynağın duyuru sayfasını (ve varsa sonraki sayfaları) tarar
+pub async fn scrape_source(fetcher: &Fetcher, source: &AnnouncementSourceRecord) -> Result<Vec<DuyuruItem>, String> {
+    let scraper = scraper_for(source)?;
+    scrape(fetcher, scraper.as_ref(), &source.duyuru_linki).await
+}
+
+pub async fn scrape(fetcher: &Fetcher, scraper: &dyn Scraper, url: &str) -> Result<Vec<DuyuruItem>, String> {
+    let mut page_url = Url::parse(url).map_err(|_| "Geçersiz URL".to_string())?;
+
+    let mut duyurular = Vec::new();
+    let mut seen_links = HashSet::new();
+    let mut visited = HashSet::new();
+    for page in 0..scraper.max_pages().max(1) {
+        visited.insert(page_url.clone());
+        let html = match fetcher.fetch(&page_url).await {
+            Ok(html) => html,
+            Err(e) if page == 0 => return Err(e),
+            // Sonraki sayfalardaki hata o ana kadar toplananları geçersiz kılmaz
+            Err(e) => {
+                log::warn!("Duyuru sayfası {} çekilemedi: {}", page_url, e);
+                break;
+            }
+        };
+
+        for item in scraper.extract(&html, &page_url) {
+            if seen_links.insert(item.link.clone()) {
+                duyurular.push(item);
+            }
+        }
+        match scraper.next_page(&html, &page_url) {
+            Some(next) if !visited.contains(&next) => page_url = next,
+            _ => break,
+        }
+    }
+    Ok(duyurular)
+}
+
+fn html_tag_regex() -> &'static Regex {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    TAG.get_or_init(|| Regex::new(r"<[^>]*>").unwrap())
+}
+
+fn html_entity_regex() -> &'static Regex {
+    static ENTITY: OnceLock<Regex> = OnceLock::new();
+    ENTITY.get_or_init(|| Regex::new(r"&#(x[0-9a-fA-F]+|\d+);").unwrap())
+}
+
+// HTML parçasından düz metin: tag'ler kaldırılır, entity'ler çözülür, boşluklar sadeleşir.
+// Her duyuru için çağrıldığından desenler bir kez derlenir
+pub(crate) fn clean_html_text(text: &str) -> String {
+    let cleaned = html_tag_regex()
+        .replace_all(text, "")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'");
+
+    // Sayısal entity'ler (&#x131; -> ı, &#231; -> ç)
+    let cleaned = html_entity_regex().replace_all(&cleaned, |caps: &Captures| {
+        let code = &caps[1];
+        let number = match code.strip_prefix('x') {
+            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+            None => code.parse::<u32>().ok(),
+        };
+        number
+            .and_then(std::char::from_u32)
+            .map(|ch| ch.to_string())
+            .unwrap_or_else(|| caps[0].to_string())
+    });
+
+    collapse_whitespace(&cleaned)
+}
+
+pub(crate) fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Menü, giriş, arama gibi navigasyon linkleri
+pub(crate) fn is_navigation_link(text: &str) -> bool {
+    let nav_keywords = [
+        "ana sayfa", "anasayfa", "home", "menü", "menu",
+        "hakkımızda", "iletişim", "contact", "about",
+        "giriş", "login", "kayıt", "register", "çıkış", "logout",
+        "ara", "search", "site haritası", "sitemap",
+    ];
+
+    let lower_text = text.to_lowercase();
+    if nav_keywords.iter().any(|keyword| lower_text.contains(keyword)) {
+        return true;
+    }
+
+    // Çok kısa metinler muhtemelen navigasyon linkidir
+    text.trim().len() < 15
+}
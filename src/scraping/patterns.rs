@@ -0,0 +1,207 @@
+use std::collections::HashSet;
+use std::ops::Range;
+use regex::{Regex, RegexBuilder};
+use url::Url;
+use crate::models::kurum_duyuru_scraped::DuyuruItem;
+use crate::dates;
+use super::{clean_html_text, is_navigation_link, Scraper};
+
+// Kuralı tanımlanmamış kaynaklar için link desenlerine dayalı scraper. Desenler sırayla
+// denenir; sonraki desen yalnızca yeterli duyuru bulunamadıysa çalışır. Tarih, linkin
+// HTML'deki yerinin çevresindeki tarihlerden linke en yakın olanıdır.
+
+const DEFAULT_LIMIT: usize = 5;
+
+// Genel link deseni: (href, iç HTML) yakalar
+const ANY_LINK: &str = r#"<a[^>]+href=["']([^"']*)["'][^>]*>([\s\S]{15,}?)</a>"#;
+const KEYWORD_LINK: &str =
+    r#"<a[^>]+href=["']([^"']*(?:duyuru|haber|news|announcement)[^"']*)["'][^>]*>([\s\S]*?)</a>"#;
+
+struct LinkPattern {
+    // İlk grup href, ikinci grup başlık (iç HTML ya da title attribute'u)
+    regex: Regex,
+    // Başlık bu uzunluktan (byte) kısa ise atlanır
+    min_title_len: usize,
+    skip_navigation: bool,
+    // "#", "javascript:" ve kök ("/") linklerini atla
+    skip_fragments: bool,
+}
+
+impl LinkPattern {
+    fn new(pattern: &str, min_title_len: usize, skip_navigation: bool) -> Self {
+        LinkPattern {
+            regex: RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .dot_matches_new_line(true)
+                .build()
+                .unwrap(),
+            min_title_len,
+            skip_navigation,
+            skip_fragments: false,
+        }
+    }
+
+    fn skipping_fragments(mut self) -> Self {
+        self.skip_fragments = true;
+        self
+    }
+}
+
+pub struct PatternScraper {
+    patterns: Vec<LinkPattern>,
+    // Tarih aranacak, başlığın önündeki ve arkasındaki karakter sayısı (byte)
+    date_window: usize,
+    limit: usize,
+}
+
+impl PatternScraper {
+    // Yargıtay: /item/<id>/ linkleri ve anahtar kelimeli linkler
+    pub fn yargitay() -> Self {
+        PatternScraper {
+            patterns: vec![
+                LinkPattern::new(
+                    r#"<a[^>]+href=["']([^"']*(?:/item/\d+/[^"']*|(?:duyuru|haber|news|announcement)[^"']*))["'][^>]*>([\s\S]*?)</a>"#,
+                    10,
+                    true,
+                ),
+                LinkPattern::new(ANY_LINK, 15, true).skipping_fragments(),
+            ],
+            date_window: 500,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    // SGK: /Duyuru/Detay/ linkleri; tarihler "23 Eylül 2025" biçiminde
+    pub fn sgk() -> Self {
+        PatternScraper {
+            patterns: vec![
+                LinkPattern::new(r#"<a[^>]+href=["']([^"']*/Duyuru/Detay/[^"']*)["'][^>]*>([\s\S]*?)</a>"#, 10, false),
+                LinkPattern::new(r#"<a[^>]+href=["']([^"']*duyuru[^"']*)["'][^>]*>([\s\S]*?)</a>"#, 15, true),
+            ],
+            date_window: 1000,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    // İŞKUR: /duyurular/ linkleri, başlık title attribute'unda; tarihler "11 Ağu 2025" biçiminde
+    pub fn iskur() -> Self {
+        PatternScraper {
+            patterns: vec![
+                LinkPattern::new(r#"<a[^>]+href=["']([^"']*/duyurular/[^"']+)["'][^>]*title=["']([^"']+)["']"#, 10, false),
+                LinkPattern::new(r#"<a[^>]+href=["']([^"']*/duyurular/[^"']*)["'][^>]*>([\s\S]*?)</a>"#, 15, true),
+            ],
+            date_window: 1500,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    // Bilinmeyen siteler: anahtar kelimeli linkler, yetmezse uzun metinli herhangi bir link
+    pub fn generic() -> Self {
+        PatternScraper {
+            patterns: vec![
+                LinkPattern::new(KEYWORD_LINK, 10, true),
+                LinkPattern::new(ANY_LINK, 15, true).skipping_fragments(),
+            ],
+            date_window: 500,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    // Domain'e göre hazır desen; tanınmayan domain'ler için genel desen (kural girilmesi önerilir)
+    pub fn for_url(url: &str) -> Self {
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.to_lowercase()))
+            .unwrap_or_default();
+        let matches = |domain: &str| host == domain || host.ends_with(&format!(".{}", domain));
+
+        if matches("yargitay.gov.tr") {
+            PatternScraper::yargitay()
+        } else if matches("sgk.gov.tr") {
+            PatternScraper::sgk()
+        } else if matches("iskur.gov.tr") {
+            PatternScraper::iskur()
+        } else {
+            log::debug!("{} için scraper kuralı yok, genel link deseni kullanılıyor", url);
+            PatternScraper::generic()
+        }
+    }
+
+    // Linkin (link: HTML'deki byte aralığı) çevresindeki en yakın tarih. Pencerede komşu
+    // duyuruların tarihleri de bulunur; ilk bulunanı almak onları önceki duyuruya yazar.
+    fn date_near(&self, html: &str, link: Range<usize>) -> Option<String> {
+        let mut start = link.start.saturating_sub(self.date_window);
+        while !html.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = (link.end + self.date_window).min(html.len());
+        while !html.is_char_boundary(end) {
+            end += 1;
+        }
+        dates::find_all(&html[start..end])
+            .into_iter()
+            .min_by_key(|(range, _)| {
+                let (date_start, date_end) = (start + range.start, start + range.end);
+                if date_end <= link.start {
+                    link.start - date_end
+                } else {
+                    date_start.saturating_sub(link.end)
+                }
+            })
+            .map(|(_, date)| dates::day_month_year(date))
+    }
+}
+
+// Göreli href'i sayfa adresine göre mutlak URL'ye çevirir
+fn absolute_url(href: &str, page_url: &Url) -> String {
+    if href.starts_with("http") {
+        return href.to_string();
+    }
+    page_url
+        .join(href)
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| href.to_string())
+}
+
+// Desen yalnızca açılış etiketini yakaladıysa (başlık title attribute'unda) aralık </a>'ya uzatılır
+fn link_element(html: &str, link: Range<usize>) -> Range<usize> {
+    if html[link.clone()].to_ascii_lowercase().ends_with("</a>") {
+        return link;
+    }
+    match html[link.end..].to_ascii_lowercase().find("</a>") {
+        Some(offset) => link.start..link.end + offset + "</a>".len(),
+        None => link,
+    }
+}
+
+impl Scraper for PatternScraper {
+    fn extract(&self, html: &str, page_url: &Url) -> Vec<DuyuruItem> {
+        let mut duyurular = Vec::new();
+        let mut seen_links = HashSet::new();
+
+        for pattern in &self.patterns {
+            for caps in pattern.regex.captures_iter(html) {
+                if duyurular.len() >= self.limit {
+                    return duyurular;
+                }
+                let href = caps.get(1).map(|m| m.as_str().trim()).unwrap_or("");
+                if href.is_empty()
+                    || (pattern.skip_fragments && (href.contains('#') || href.contains("javascript:") || href == "/"))
+                {
+                    continue;
+                }
+
+                let title = clean_html_text(caps.get(2).map(|m| m.as_str()).unwrap_or(""));
+                if title.len() <= pattern.min_title_len || (pattern.skip_navigation && is_navigation_link(&title)) {
+                    continue;
+                }
+                let link = absolute_url(href, page_url);
+                if seen_links.insert(link.clone()) {
+                    let tarih = caps.get(0).and_then(|link| self.date_near(html, link_element(html, link.range())));
+                    duyurular.push(DuyuruItem { baslik: title, link, tarih });
+                }
+            }
+        }
+        duyurular
+    }
+}
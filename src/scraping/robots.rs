@@ -0,0 +1,107 @@
+// robots.txt kuralları (RFC 9309). Tarayıcının ürün adını (User-Agent'ın "/" öncesi) içeren
+// gruplar, yoksa "*" grubu uygulanır. Yola en uzun eşleşen kural kazanır; eşit uzunlukta
+// Allow önceliklidir. "*" herhangi bir karakter dizisine, sondaki "$" yolun sonuna eşleşir.
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Robots {
+    // (allow, desen)
+    rules: Vec<(bool, String)>,
+}
+
+struct Group {
+    agents: Vec<String>,
+    rules: Vec<(bool, String)>,
+}
+
+impl Robots {
+    pub fn allow_all() -> Self {
+        Robots::default()
+    }
+
+    pub fn parse(text: &str, product: &str) -> Self {
+        let product = product.to_lowercase();
+        let mut groups: Vec<Group> = Vec::new();
+        // Art arda gelen User-agent satırları aynı grubu oluşturur
+        let mut reading_agents = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "user-agent" => {
+                    if !reading_agents {
+                        groups.push(Group { agents: Vec::new(), rules: Vec::new() });
+                        reading_agents = true;
+                    }
+                    if let Some(group) = groups.last_mut() {
+                        group.agents.push(value.to_lowercase());
+                    }
+                }
+                key @ ("allow" | "disallow") => {
+                    reading_agents = false;
+                    // Boş Disallow her şeye izin verir
+                    if let (Some(group), false) = (groups.last_mut(), value.is_empty()) {
+                        group.rules.push((key == "allow", value.to_string()));
+                    }
+                }
+                _ => reading_agents = false,
+            }
+        }
+
+        let own: Vec<&Group> = groups
+            .iter()
+            .filter(|group| {
+                group
+                    .agents
+                    .iter()
+                    .any(|agent| agent != "*" && agent.split('/').next() == Some(product.as_str()))
+            })
+            .collect();
+        let selected = if own.is_empty() {
+            groups.iter().filter(|group| group.agents.iter().any(|agent| agent == "*")).collect()
+        } else {
+            own
+        };
+        Robots {
+            rules: selected.into_iter().flat_map(|group| group.rules.iter().cloned()).collect(),
+        }
+    }
+
+    // path: yol ve varsa sorgu ("/duyurular?sayfa=2")
+    pub fn allows(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| matches(pattern, path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .is_none_or(|(allow, _)| *allow)
+    }
+}
+
+fn matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = path.strip_prefix(parts.next().unwrap_or("")) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return !anchored || rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    if anchored {
+        rest.ends_with(last)
+    } else {
+        rest.contains(last)
+    }
+}
@@ -0,0 +1,181 @@
+use chrono::format::{Item, StrftimeItems};
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use url::Url;
+use utoipa::ToSchema;
+use crate::error::{FieldError, FieldIssue};
+use crate::models::kurum_duyuru_scraped::DuyuruItem;
+use crate::dates;
+use super::{collapse_whitespace, Scraper};
+
+// kurum_duyuru kaydındaki `scraper` alt belgesi. Seçiciler CSS seçicisidir; title, link ve
+// date seçicileri her duyuru elemanının (item) içinde aranır.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ScrapeRules {
+    // Her duyuruyu kapsayan eleman (örn. "ul.duyurular > li")
+    pub item: String,
+    // Başlık elemanı; verilmezse link elemanı
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    // Başlığın okunacağı attribute (örn. "title"); verilmezse elemanın metni
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_attribute: Option<String>,
+    // href'i okunacak eleman; verilmezse item'ın kendisi ya da içindeki ilk <a>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    // Tarih elemanı; verilmezse tarih item'ın metninde aranır
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    // chrono biçimi (örn. "%d.%m.%Y", "%d %B %Y"); Türkçe ay adları tanınır
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    // Sonraki sayfa linki (örn. "a[rel=next]"); verilmezse yalnızca ilk sayfa taranır
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page: Option<String>,
+    // Taranacak en fazla sayfa (varsayılan 1, en fazla 20)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_pages: Option<u32>,
+}
+
+pub const MAX_PAGES: u32 = 20;
+
+fn valid_selector(selector: &str) -> bool {
+    !selector.trim().is_empty() && Selector::parse(selector).is_ok()
+}
+
+impl ScrapeRules {
+    // Yönetim API'sinde kural doğrulaması; alan adları "scraper." önekiyle döner
+    pub fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if self.item.trim().is_empty() {
+            errors.push(FieldError::new("scraper.item", FieldIssue::Required));
+        } else if !valid_selector(&self.item) {
+            errors.push(FieldError::new("scraper.item", FieldIssue::InvalidFormat));
+        }
+        let selectors = [
+            ("scraper.title", &self.title),
+            ("scraper.link", &self.link),
+            ("scraper.date", &self.date),
+            ("scraper.next_page", &self.next_page),
+        ];
+        for (field, selector) in selectors {
+            if selector.as_deref().is_some_and(|selector| !valid_selector(selector)) {
+                errors.push(FieldError::new(field, FieldIssue::InvalidFormat));
+            }
+        }
+        if self.title_attribute.as_deref().is_some_and(|attribute| attribute.trim().is_empty()) {
+            errors.push(FieldError::new("scraper.title_attribute", FieldIssue::InvalidFormat));
+        }
+        if self.date_format.as_deref().is_some_and(|format| {
+            format.trim().is_empty() || StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+        }) {
+            errors.push(FieldError::new("scraper.date_format", FieldIssue::InvalidFormat));
+        }
+        if self.max_pages.is_some_and(|pages| pages == 0 || pages > MAX_PAGES) {
+            errors.push(FieldError::new("scraper.max_pages", FieldIssue::InvalidFormat));
+        }
+        errors
+    }
+}
+
+// Kurallarla çalışan scraper; seçiciler bir kez derlenir
+pub struct RuleScraper {
+    item: Selector,
+    title: Option<Selector>,
+    title_attribute: Option<String>,
+    link: Option<Selector>,
+    anchor: Selector,
+    date: Option<Selector>,
+    date_format: Option<String>,
+    next_page: Option<Selector>,
+    max_pages: usize,
+}
+
+fn compile(field: &str, selector: &str) -> Result<Selector, String> {
+    Selector::parse(selector).map_err(|_| format!("Geçersiz scraper kuralı ({}): {}", field, selector))
+}
+
+fn compile_optional(field: &str, selector: &Option<String>) -> Result<Option<Selector>, String> {
+    selector.as_deref().map(|selector| compile(field, selector)).transpose()
+}
+
+fn element_text(element: ElementRef) -> String {
+    collapse_whitespace(&element.text().collect::<String>())
+}
+
+impl RuleScraper {
+    pub fn new(rules: &ScrapeRules) -> Result<Self, String> {
+        Ok(RuleScraper {
+            item: compile("item", &rules.item)?,
+            title: compile_optional("title", &rules.title)?,
+            title_attribute: rules.title_attribute.clone(),
+            link: compile_optional("link", &rules.link)?,
+            anchor: Selector::parse("a[href]").unwrap(),
+            date: compile_optional("date", &rules.date)?,
+            date_format: rules.date_format.clone(),
+            next_page: compile_optional("next_page", &rules.next_page)?,
+            max_pages: rules.max_pages.unwrap_or(1).clamp(1, MAX_PAGES) as usize,
+        })
+    }
+
+    fn parse_item(&self, item: ElementRef, page_url: &Url) -> Option<DuyuruItem> {
+        let link_element = match &self.link {
+            Some(selector) => item.select(selector).next()?,
+            None if item.value().name() == "a" => item,
+            None => item.select(&self.anchor).next()?,
+        };
+        let href = link_element.value().attr("href")?.trim();
+        if href.is_empty() || href.starts_with('#') || href.starts_with("javascript:") {
+            return None;
+        }
+        let link = page_url.join(href).ok()?.to_string();
+
+        let title_element = match &self.title {
+            Some(selector) => item.select(selector).next()?,
+            None => link_element,
+        };
+        let baslik = match &self.title_attribute {
+            Some(attribute) => collapse_whitespace(title_element.value().attr(attribute)?),
+            None => element_text(title_element),
+        };
+        if baslik.is_empty() {
+            return None;
+        }
+
+        let date_text = match &self.date {
+            Some(selector) => item.select(selector).next().map(element_text),
+            None => None,
+        }
+        .unwrap_or_else(|| element_text(item));
+        let tarih = self
+            .date_format
+            .as_deref()
+            .and_then(|format| dates::parse_with_format(&date_text, format).ok())
+            .or_else(|| dates::find(&date_text))
+            .map(dates::day_month_year);
+
+        Some(DuyuruItem { baslik, link, tarih })
+    }
+}
+
+impl Scraper for RuleScraper {
+    fn extract(&self, html: &str, page_url: &Url) -> Vec<DuyuruItem> {
+        let document = Html::parse_document(html);
+        document
+            .select(&self.item)
+            .filter_map(|item| self.parse_item(item, page_url))
+            .collect()
+    }
+
+    fn next_page(&self, html: &str, page_url: &Url) -> Option<Url> {
+        let selector = self.next_page.as_ref()?;
+        let document = Html::parse_document(html);
+        let href = document.select(selector).next()?.value().attr("href")?.trim().to_string();
+        page_url.join(&href).ok()
+    }
+
+    fn max_pages(&self) -> usize {
+        self.max_pages
+    }
+}
@@ -0,0 +1,202 @@
+use std::env;
+use std::io::Write;
+use actix_web::web::Bytes;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use crate::feeds::escape;
+
+// Parçalı sitemap: /sitemap.xml bir sitemap index'idir ve corpus başına (mevzuat, yargitay, ...),
+// kurum sayfaları ve statik sayfalar için numaralı alt sitemap'lere işaret eder. Alt sitemap'ler
+// veritabanından sayfa sayfa okunarak akış halinde (istenirse gzip'lenerek) üretilir; tek
+// dosyanın 50.000 URL / 50 MB sınırı aşılmaz.
+
+// Protokolün tek sitemap için izin verdiği en fazla URL
+pub const MAX_URLS: u64 = 50_000;
+// Alt sitemap akışında veritabanından tek seferde okunan belge sayısı
+pub const BATCH_SIZE: u64 = 1_000;
+
+pub const SECTION_STATIC: &str = "static";
+pub const SECTION_INSTITUTIONS: &str = "institutions";
+
+const XML_HEADER: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+const URLSET_OPEN: &str = "<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n";
+const URLSET_CLOSE: &str = "</urlset>\n";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SitemapConfig {
+    // Alt sitemap başına URL sayısı (en fazla 50.000)
+    pub chunk_size: u64,
+    // Index'te alt sitemap'ler .xml.gz adresleriyle listelensin mi
+    pub gzip: bool,
+}
+
+impl Default for SitemapConfig {
+    fn default() -> Self {
+        SitemapConfig {
+            chunk_size: MAX_URLS,
+            gzip: true,
+        }
+    }
+}
+
+impl SitemapConfig {
+    // SITEMAP_CHUNK_SIZE: alt sitemap başına URL (1-50000); SITEMAP_GZIP=false index'te .xml kullanır
+    pub fn from_env() -> Self {
+        let default = SitemapConfig::default();
+        SitemapConfig {
+            chunk_size: env::var("SITEMAP_CHUNK_SIZE")
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .filter(|size| *size > 0)
+                .map(|size| size.min(MAX_URLS))
+                .unwrap_or(default.chunk_size),
+            gzip: env::var("SITEMAP_GZIP")
+                .map(|value| !matches!(value.trim().to_ascii_lowercase().as_str(), "false" | "0" | "off"))
+                .unwrap_or(default.gzip),
+        }
+    }
+
+    // count URL için gereken alt sitemap sayısı
+    pub fn chunks(&self, count: u64) -> u64 {
+        count.div_ceil(self.chunk_size)
+    }
+}
+
+// Alt sitemap dosya adı: "<bölüm>-<sayfa>.xml" ya da ".xml.gz"; statik sayfalar "static.xml"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChildName {
+    // static, institutions ya da corpus anahtarı
+    pub section: String,
+    // 1'den başlar
+    pub page: u64,
+    pub gzip: bool,
+}
+
+impl ChildName {
+    pub fn parse(name: &str) -> Option<Self> {
+        let (stem, gzip) = match name.strip_suffix(".xml.gz") {
+            Some(stem) => (stem, true),
+            None => (name.strip_suffix(".xml")?, false),
+        };
+        if stem == SECTION_STATIC {
+            return Some(ChildName { section: stem.to_string(), page: 1, gzip });
+        }
+        let (section, page) = stem.rsplit_once('-')?;
+        let page = page.parse::<u64>().ok().filter(|page| *page > 0)?;
+        (!section.is_empty() && section != SECTION_STATIC).then(|| ChildName {
+            section: section.to_string(),
+            page,
+            gzip,
+        })
+    }
+
+    pub fn file_name(&self) -> String {
+        let extension = if self.gzip { "xml.gz" } else { "xml" };
+        if self.section == SECTION_STATIC {
+            format!("{}.{}", SECTION_STATIC, extension)
+        } else {
+            format!("{}-{}.{}", self.section, self.page, extension)
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        if self.gzip {
+            "application/gzip"
+        } else {
+            "application/xml"
+        }
+    }
+}
+
+// Alt sitemap'teki tek URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlEntry {
+    pub loc: String,
+    // W3C tarih biçimi (RFC 3339); okunamayan tarih yazılmaz
+    pub lastmod: Option<String>,
+    pub changefreq: &'static str,
+    pub priority: &'static str,
+}
+
+impl UrlEntry {
+    pub fn render(&self) -> String {
+        let lastmod = self
+            .lastmod
+            .as_ref()
+            .map(|lastmod| format!("<lastmod>{}</lastmod>", escape(lastmod)))
+            .unwrap_or_default();
+        format!(
+            "  <url><loc>{}</loc>{}<changefreq>{}</changefreq><priority>{}</priority></url>\n",
+            escape(&self.loc),
+            lastmod,
+            self.changefreq,
+            self.priority
+        )
+    }
+}
+
+// Alt sitemap adreslerinden sitemap index'i
+pub fn render_index(locations: &[String]) -> String {
+    let mut xml = String::from(XML_HEADER);
+    xml.push_str("<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for loc in locations {
+        xml.push_str(&format!("  <sitemap><loc>{}</loc></sitemap>\n", escape(loc)));
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
+
+// robots.txt: JSON API ve dokümantasyonu taranmaz; sitemap ve beslemeler açıktır
+pub const ROBOTS_DISALLOW: [&str; 1] = ["/api/"];
+
+pub fn render_robots(sitemap_index: &str) -> String {
+    let mut text = String::from("User-agent: *\n");
+    for path in ROBOTS_DISALLOW {
+        text.push_str(&format!("Disallow: {}\n", path));
+    }
+    text.push_str(&format!("\nSitemap: {}\n", sitemap_index));
+    text
+}
+
+// Alt sitemap gövdesini parça parça üreten yazıcı. gzip'te sıkıştırılan veri her parçada
+// boşaltılır; böylece dosyanın tamamı bellekte tutulmaz.
+pub struct UrlsetWriter {
+    gzip: Option<GzEncoder<Vec<u8>>>,
+}
+
+impl UrlsetWriter {
+    pub fn new(gzip: bool) -> Self {
+        UrlsetWriter {
+            gzip: gzip.then(|| GzEncoder::new(Vec::new(), Compression::default())),
+        }
+    }
+
+    pub fn open(&mut self) -> Bytes {
+        self.write(&format!("{}{}", XML_HEADER, URLSET_OPEN))
+    }
+
+    pub fn entries(&mut self, entries: &[UrlEntry]) -> Bytes {
+        let xml: String = entries.iter().map(UrlEntry::render).collect();
+        self.write(&xml)
+    }
+
+    // Kapanış etiketi ve (gzip'te) sıkıştırma akışının sonu
+    pub fn close(mut self) -> Bytes {
+        let mut bytes = self.write(URLSET_CLOSE).to_vec();
+        if let Some(encoder) = self.gzip.take() {
+            // Vec'e yazma başarısız olmaz
+            bytes.extend(encoder.finish().unwrap_or_default());
+        }
+        Bytes::from(bytes)
+    }
+
+    fn write(&mut self, xml: &str) -> Bytes {
+        match &mut self.gzip {
+            Some(encoder) => {
+                let _ = encoder.write_all(xml.as_bytes());
+                Bytes::from(std::mem::take(encoder.get_mut()))
+            }
+            None => Bytes::from(xml.to_string()),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use crate::corpus::CorpusRegistry;
+use crate::dates;
+use crate::repository::{Repositories, RepositoryError};
+use crate::utils::format_timestamp;
+
+// Önceden hesaplanmış istatistikler: her corpus'taki belgeler kurum, belge türü ve yayın ayı
+// başına sayılır ve bellekte tutulur. İstatistik endpoint'leri koleksiyonları her istekte saymak
+// yerine bu özetten okur; özet STATISTICS_REFRESH_INTERVAL aralıkla yenilenir. Her instance
+// kendi özetini hesaplar; özet henüz yoksa ilk istek hesaplatır.
+
+const DEFAULT_REFRESH_SECS: u64 = 600;
+// Zaman serisinde istenebilecek en fazla ay (50 yıl)
+pub const MAX_SPAN_MONTHS: u32 = 600;
+// Aylar bu yıl aralığına sıkıştırılır; hatalı yayın tarihleri seriyi binlerce yıla yaymaz
+const MIN_YEAR: i32 = 1800;
+const MAX_YEAR: i32 = 2100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatisticsConfig {
+    pub refresh_interval: Duration,
+}
+
+impl Default for StatisticsConfig {
+    fn default() -> Self {
+        StatisticsConfig {
+            refresh_interval: Duration::from_secs(DEFAULT_REFRESH_SECS),
+        }
+    }
+}
+
+impl StatisticsConfig {
+    // STATISTICS_REFRESH_INTERVAL: saniye cinsinden yenileme aralığı
+    pub fn from_env() -> Self {
+        let default = StatisticsConfig::default();
+        StatisticsConfig {
+            refresh_interval: env::var("STATISTICS_REFRESH_INTERVAL")
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .filter(|seconds| *seconds > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(default.refresh_interval),
+        }
+    }
+}
+
+// Özetin tek hücresi: corpus × kurum × belge türü × yayın ayı
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub corpus: String,
+    pub kurum_id: String,
+    pub belge_turu: String,
+    // YYYY-MM; yayın tarihi yoksa ya da okunamazsa None
+    pub month: Option<String>,
+    pub count: u64,
+}
+
+// Hücre filtreleri; verilenlerin tümü eşleşmelidir
+#[derive(Debug, Clone, Default)]
+pub struct CellFilter {
+    pub corpus: Option<String>,
+    pub kurum_id: Option<String>,
+    pub belge_turu: Option<String>,
+}
+
+impl CellFilter {
+    fn matches(&self, cell: &Cell) -> bool {
+        let matches = |filter: &Option<String>, value: &str| filter.as_deref().is_none_or(|filter| filter == value);
+        matches(&self.corpus, &cell.corpus)
+            && matches(&self.kurum_id, &cell.kurum_id)
+            && matches(&self.belge_turu, &cell.belge_turu)
+    }
+}
+
+// Aylık seri; aradaki boş aylar 0 ile doldurulur
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MonthlySeries {
+    pub months: Vec<(String, u64)>,
+    // Yayın tarihi olmayan ya da okunamayan belgeler
+    pub undated: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub computed_at: String,
+    pub total_kurumlar: u64,
+    // kurum_id -> kurum_adi
+    pub kurumlar: HashMap<String, String>,
+    pub cells: Vec<Cell>,
+}
+
+// YYYY-MM
+pub fn month_key(date: NaiveDate) -> String {
+    format!("{:04}-{:02}", date.year(), date.month())
+}
+
+// Sorgu parametresindeki ay (YYYY-MM)
+pub fn parse_month(value: &str) -> Option<NaiveDate> {
+    if value.len() != 7 {
+        return None;
+    }
+    NaiveDate::parse_from_str(&format!("{}-01", value), "%Y-%m-%d").ok()
+}
+
+// Ayı MIN_YEAR..=MAX_YEAR aralığına sıkıştırır
+pub fn clamp_month(month: NaiveDate) -> NaiveDate {
+    let first = NaiveDate::from_ymd_opt(MIN_YEAR, 1, 1).expect("geçerli tarih");
+    let last = NaiveDate::from_ymd_opt(MAX_YEAR, 12, 1).expect("geçerli tarih");
+    month.clamp(first, last)
+}
+
+// from ile to arasındaki ay sayısı (ikisi dahil)
+pub fn month_span(from: NaiveDate, to: NaiveDate) -> i64 {
+    i64::from(to.year() - from.year()) * 12 + i64::from(to.month()) - i64::from(from.month()) + 1
+}
+
+impl Snapshot {
+    pub fn cells<'a>(&'a self, filter: &'a CellFilter) -> impl Iterator<Item = &'a Cell> + 'a {
+        self.cells.iter().filter(move |cell| filter.matches(cell))
+    }
+
+    pub fn total(&self, filter: &CellFilter) -> u64 {
+        self.cells(filter).map(|cell| cell.count).sum()
+    }
+
+    // Belge türü başına sayı (çoktan aza, eşitse alfabetik)
+    pub fn by_belge_turu(&self, filter: &CellFilter) -> Vec<(String, u64)> {
+        let mut counts: HashMap<&str, u64> = HashMap::new();
+        for cell in self.cells(filter) {
+            *counts.entry(cell.belge_turu.as_str()).or_default() += cell.count;
+        }
+        let mut counts: Vec<(String, u64)> = counts
+            .into_iter()
+            .map(|(belge_turu, count)| (belge_turu.to_string(), count))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    // Corpus başına sayı; registry sırasıyla, belgesi olmayan corpus'lar 0 ile
+    pub fn by_corpus(&self, corpora: &CorpusRegistry, filter: &CellFilter) -> Vec<(String, u64)> {
+        corpora
+            .all()
+            .iter()
+            .filter(|corpus| filter.corpus.as_ref().is_none_or(|key| *key == corpus.key))
+            .map(|corpus| {
+                let filter = CellFilter {
+                    corpus: Some(corpus.key.clone()),
+                    ..filter.clone()
+                };
+                (corpus.key.clone(), self.total(&filter))
+            })
+            .collect()
+    }
+
+    // Yayın ayı başına sayı. from/to (dahil) verilmezse ilk ve son belgeli ay kullanılır; bu aylar
+    // clamp_month ile sıkıştırılır.
+    pub fn monthly(&self, filter: &CellFilter, from: Option<NaiveDate>, to: Option<NaiveDate>) -> MonthlySeries {
+        let mut counts: BTreeMap<&str, u64> = BTreeMap::new();
+        let mut undated = 0;
+        for cell in self.cells(filter) {
+            match &cell.month {
+                Some(month) => *counts.entry(month.as_str()).or_default() += cell.count,
+                None => undated += cell.count,
+            }
+        }
+
+        let first = counts.keys().next().and_then(|month| parse_month(month)).map(clamp_month);
+        let last = counts.keys().next_back().and_then(|month| parse_month(month)).map(clamp_month);
+        let (Some(start), Some(end)) = (from.or(first), to.or(last)) else {
+            return MonthlySeries { months: Vec::new(), undated };
+        };
+        let mut months = Vec::new();
+        let mut month = start;
+        while month <= end {
+            let key = month_key(month);
+            let count = counts.get(key.as_str()).copied().unwrap_or(0);
+            months.push((key, count));
+            let Some(next) = month.checked_add_months(Months::new(1)) else {
+                break;
+            };
+            month = next;
+        }
+        MonthlySeries { months, undated }
+    }
+}
+
+// Tüm corpus'ları sayarak yeni bir özet hesaplar
+pub async fn compute(repos: &Repositories, corpora: &CorpusRegistry) -> Result<Snapshot, RepositoryError> {
+    let total_kurumlar = repos.institutions.count().await?;
+    let kurumlar = repos
+        .institutions
+        .list()
+        .await?
+        .into_iter()
+        .map(|kurum| (kurum.id, kurum.kurum_adi))
+        .collect();
+
+    let mut cells = Vec::new();
+    for corpus in corpora.all() {
+        // Aynı aya düşen farklı tarih yazımları tek hücrede toplanır
+        let mut merged: HashMap<(String, String, Option<String>), u64> = HashMap::new();
+        for group in repos.documents.count_groups(corpus).await? {
+            // Tek kuruma ait corpus'larda (yargitay) belgede kurum_id olmayabilir
+            let kurum_id = match (group.kurum_id.is_empty(), &corpus.kurum_id) {
+                (true, Some(kurum_id)) => kurum_id.clone(),
+                _ => group.kurum_id,
+            };
+            let month = group
+                .yayin_tarihi
+                .as_deref()
+                .and_then(|text| dates::parse(text).ok())
+                .map(month_key);
+            *merged.entry((kurum_id, group.belge_turu, month)).or_default() += group.count;
+        }
+        cells.extend(merged.into_iter().map(|((kurum_id, belge_turu, month), count)| Cell {
+            corpus: corpus.key.clone(),
+            kurum_id,
+            belge_turu,
+            month,
+            count,
+        }));
+    }
+
+    Ok(Snapshot {
+        computed_at: format_timestamp(Utc::now()),
+        total_kurumlar,
+        kurumlar,
+        cells,
+    })
+}
+
+// Son hesaplanan özet (AppState'te paylaşılır)
+#[derive(Clone, Default)]
+pub struct StatisticsCache {
+    snapshot: Arc<RwLock<Option<Arc<Snapshot>>>>,
+}
+
+impl StatisticsCache {
+    pub fn get(&self) -> Option<Arc<Snapshot>> {
+        self.snapshot.read().unwrap().clone()
+    }
+
+    pub fn set(&self, snapshot: Snapshot) -> Arc<Snapshot> {
+        let snapshot = Arc::new(snapshot);
+        *self.snapshot.write().unwrap() = Some(snapshot.clone());
+        snapshot
+    }
+
+    // Özeti yeniden hesaplayıp saklar
+    pub async fn refresh(&self, repos: &Repositories, corpora: &CorpusRegistry) -> Result<Arc<Snapshot>, RepositoryError> {
+        Ok(self.set(compute(repos, corpora).await?))
+    }
+
+    // Saklanan özet; yoksa (ilk yenileme henüz bitmediyse) hesaplanır
+    pub async fn current(&self, repos: &Repositories, corpora: &CorpusRegistry) -> Result<Arc<Snapshot>, RepositoryError> {
+        match self.get() {
+            Some(snapshot) => Ok(snapshot),
+            None => self.refresh(repos, corpora).await,
+        }
+    }
+}
+
+pub fn spawn(
+    cache: StatisticsCache,
+    repos: Repositories,
+    corpora: CorpusRegistry,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match cache.refresh(&repos, &corpora).await {
+                Ok(snapshot) => log::debug!("İstatistik özeti yenilendi: {} hücre", snapshot.cells.len()),
+                Err(e) => log::error!("İstatistik özeti yenilenemedi: {}", e),
+            }
+        }
+    })
+}
@@ -0,0 +1,418 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use mongodb::bson::{doc, Document as MongoDocument};
+use mongodb::change_stream::event::{ChangeStreamEvent, ResumeToken};
+use mongodb::options::ChangeStreamOptions;
+use mongodb::Database;
+use tokio::sync::broadcast;
+use crate::audit::stream::change_streams_unsupported;
+use crate::config::PublicUrls;
+use crate::corpus::{Corpus, CorpusRegistry};
+use crate::dates;
+use crate::models::stream::DocumentEvent;
+use crate::repository::records::id_string;
+use crate::repository::{DocumentRecord, Repositories, RepositoryError, ScanCursor};
+use crate::utils::format_timestamp;
+use crate::webhooks::start_cursor;
+
+// Yeni belgelerin canlı akışı (/api/v1/stream/documents). Corpus koleksiyonlarına eklenen
+// belgeler MongoDB change stream'i ile izlenir; tekil sunucularda (change stream yok) corpus'lar
+// olusturulma_tarihi üzerinden belirli aralıklarla yoklanır. Olaylar süreç içi bir yayın kanalıyla
+// bağlı istemcilere dağıtılır; her instance kendi kaynağını çalıştırır.
+
+pub const EVENT_DOCUMENT_CREATED: &str = "document.created";
+// Kaçırılanların tamamı gönderilemedi; istemci id'si verilen yerden yeniden bağlanmalı
+pub const EVENT_RESET: &str = "reset";
+
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 10;
+const DEFAULT_KEEP_ALIVE_SECS: u64 = 15;
+// Yavaş istemcinin geride kalabileceği en fazla olay; aşılırsa bağlantısı kapatılır
+const CHANNEL_CAPACITY: usize = 256;
+// Yoklamada ve kaçırılanların okunmasında tek seferde okunan belge sayısı (sayfa boyutu)
+const PAGE_SIZE: u64 = 200;
+// Last-Event-ID ile devam edilirken corpus başına okunan en fazla belge; aşılırsa istemciye
+// reset olayı gönderilir ve kalınan yerden yeniden bağlanması beklenir
+pub const REPLAY_LIMIT: u64 = 500;
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamConfig {
+    // None ise yoklama yapılmaz (change stream yoksa canlı olay da gelmez)
+    pub poll_interval: Option<Duration>,
+    // Replica set'te change stream kullanılsın mı
+    pub change_stream: bool,
+    // Boşta bağlantıların kapanmaması için yorum satırı aralığı
+    pub keep_alive: Duration,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        StreamConfig {
+            poll_interval: Some(Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS)),
+            change_stream: true,
+            keep_alive: Duration::from_secs(DEFAULT_KEEP_ALIVE_SECS),
+        }
+    }
+}
+
+impl StreamConfig {
+    // STREAM_POLL_INTERVAL: saniye cinsinden yoklama aralığı; 0 yoklamayı kapatır.
+    // STREAM_CHANGE_STREAM=false change stream'i kapatır; STREAM_KEEP_ALIVE (saniye)
+    pub fn from_env() -> Self {
+        let default = StreamConfig::default();
+        let number = |name: &str| env::var(name).ok().and_then(|value| value.trim().parse::<u64>().ok());
+        StreamConfig {
+            poll_interval: match number("STREAM_POLL_INTERVAL") {
+                Some(0) => None,
+                Some(seconds) => Some(Duration::from_secs(seconds)),
+                None => default.poll_interval,
+            },
+            change_stream: env::var("STREAM_CHANGE_STREAM")
+                .map(|value| !matches!(value.trim().to_ascii_lowercase().as_str(), "false" | "0" | "off"))
+                .unwrap_or(default.change_stream),
+            keep_alive: number("STREAM_KEEP_ALIVE")
+                .filter(|seconds| *seconds > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(default.keep_alive),
+        }
+    }
+}
+
+// Akıştaki tek olay. id: "<olusturulma_tarihi unix ms>-<belge _id>"; Last-Event-ID olarak
+// geri geldiğinde kalınan yer veritabanından bulunabilir.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamEvent {
+    pub id: String,
+    pub time: DateTime<Utc>,
+    pub data: DocumentEvent,
+}
+
+impl StreamEvent {
+    pub fn document(urls: &PublicUrls, corpus: &Corpus, record: &DocumentRecord, time: DateTime<Utc>) -> Self {
+        StreamEvent {
+            id: event_id(time, &record.id),
+            time,
+            data: DocumentEvent {
+                corpus: corpus.key.clone(),
+                id: record.id.clone(),
+                url_slug: record.url_slug.clone(),
+                url: urls.document(corpus, &record.url_slug, &record.id),
+                pdf_adi: record.pdf_adi.clone(),
+                kurum_id: record.kurum_id.clone(),
+                kurum_adi: record.kurum_adi(),
+                belge_turu: record.belge_turu.clone(),
+                olusturulma_tarihi: record.olusturulma_tarihi.clone(),
+            },
+        }
+    }
+
+    // SSE çerçevesi
+    pub fn frame(&self) -> String {
+        let data = serde_json::to_string(&self.data).unwrap_or_default();
+        format!("id: {}\nevent: {}\ndata: {}\n\n", self.id, EVENT_DOCUMENT_CREATED, data)
+    }
+}
+
+pub fn event_id(time: DateTime<Utc>, id: &str) -> String {
+    format!("{}-{}", time.timestamp_millis(), id)
+}
+
+// Last-Event-ID değerinden (zaman, belge _id)
+pub fn parse_event_id(value: &str) -> Option<(DateTime<Utc>, String)> {
+    let (millis, id) = value.trim().split_once('-')?;
+    let time = DateTime::from_timestamp_millis(millis.parse().ok()?)?;
+    (!id.is_empty()).then(|| (time, id.to_string()))
+}
+
+// İstemcinin sorgu parametreleriyle verdiği filtreler
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamFilter {
+    pub corpus: Option<String>,
+    pub kurum_id: Option<String>,
+    pub belge_turu: Option<String>,
+}
+
+impl StreamFilter {
+    pub fn matches(&self, event: &DocumentEvent) -> bool {
+        self.corpus.as_ref().is_none_or(|corpus| *corpus == event.corpus)
+            && self.kurum_id.as_ref().is_none_or(|kurum_id| *kurum_id == event.kurum_id)
+            && self
+                .belge_turu
+                .as_ref()
+                .is_none_or(|belge_turu| belge_turu.eq_ignore_ascii_case(&event.belge_turu))
+    }
+}
+
+// Olayların bağlı istemcilere dağıtıldığı kanal (AppState'te paylaşılır)
+#[derive(Clone)]
+pub struct DocumentStream {
+    sender: broadcast::Sender<Arc<StreamEvent>>,
+    pub keep_alive: Duration,
+}
+
+impl Default for DocumentStream {
+    fn default() -> Self {
+        DocumentStream::new(&StreamConfig::default())
+    }
+}
+
+impl DocumentStream {
+    pub fn new(config: &StreamConfig) -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        DocumentStream {
+            sender,
+            keep_alive: config.keep_alive,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<StreamEvent>> {
+        self.sender.subscribe()
+    }
+
+    // Olayı alan istemci sayısı
+    pub fn publish(&self, event: StreamEvent) -> usize {
+        self.sender.send(Arc::new(event)).unwrap_or(0)
+    }
+}
+
+fn record_time(record: &DocumentRecord) -> Option<(DateTime<Utc>, String)> {
+    dates::parse_timestamp(&record.olusturulma_tarihi)
+        .ok()
+        .map(|time| (time, record.id.clone()))
+}
+
+fn record_key(record: &DocumentRecord) -> (&str, &str) {
+    (&record.olusturulma_tarihi, &record.id)
+}
+
+// Last-Event-ID'den sonra kaçırılan belgeler
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Replay {
+    // En eski önce
+    pub events: Vec<StreamEvent>,
+    // REPLAY_LIMIT aşıldıysa gönderilen olaylardan sonra kalınan yer (olay id'si biçiminde);
+    // aradaki belgelerin tamamı gönderilemediğinden istemci buradan yeniden bağlanmalıdır
+    pub resume_from: Option<String>,
+}
+
+// reset olayının çerçevesi; id alanı istemcinin Last-Event-ID'sini kalınan yere taşır
+pub fn reset_frame(resume_from: &str) -> String {
+    let data = serde_json::json!({ "reason": "replay_limit", "resume_from": resume_from });
+    format!("id: {}\nevent: {}\ndata: {}\n\n", resume_from, EVENT_RESET, data)
+}
+
+// Last-Event-ID'den sonraki belgeler, en eski önce. Okunamayan olusturulma_tarihi değerli
+// belgeler atlanır. Belgeler corpus başına imleçten itibaren sayfa sayfa okunur; bir corpus'ta
+// REPLAY_LIMIT'e ulaşılırsa yalnızca tüm corpus'larda eksiksiz okunan aralık gönderilir.
+pub async fn replay(
+    repos: &Repositories,
+    corpora: &CorpusRegistry,
+    urls: &PublicUrls,
+    filter: &StreamFilter,
+    since: (DateTime<Utc>, String),
+) -> Result<Replay, RepositoryError> {
+    let (since_time, last_id) = since;
+    let start = ScanCursor {
+        watermark: format_timestamp(since_time),
+        ids: vec![last_id.clone()],
+    };
+    let mut events = Vec::new();
+    // Okunmayan belge kalan corpus'ların son okunan olayları; en erkeni gönderimin sınırıdır
+    let mut bound: Option<(DateTime<Utc>, String)> = None;
+    for corpus in corpora.all() {
+        if filter.corpus.as_ref().is_some_and(|key| *key != corpus.key) {
+            continue;
+        }
+        let mut cursor = start.clone();
+        let mut read = Vec::new();
+        // Sınırın bir fazlası okunur; okunabildiyse kalan belge vardır
+        let truncated = loop {
+            let limit = PAGE_SIZE.min(REPLAY_LIMIT + 1 - read.len() as u64);
+            let page = repos.documents.created_after(corpus, &cursor, limit).await?;
+            cursor = cursor.advance(page.iter().map(record_key));
+            let exhausted = (page.len() as u64) < limit;
+            read.extend(page);
+            if read.len() as u64 > REPLAY_LIMIT {
+                read.truncate(REPLAY_LIMIT as usize);
+                break true;
+            }
+            if exhausted {
+                break false;
+            }
+        };
+        let corpus_events: Vec<StreamEvent> = read
+            .iter()
+            .filter_map(|record| record_time(record).map(|(time, _)| StreamEvent::document(urls, corpus, record, time)))
+            .filter(|event| event.time > since_time || (event.time == since_time && event.data.id != last_id))
+            .collect();
+        if truncated {
+            if let Some(last) = corpus_events.iter().max_by(|a, b| a.time.cmp(&b.time).then_with(|| a.id.cmp(&b.id))) {
+                let candidate = (last.time, last.id.clone());
+                if bound.as_ref().is_none_or(|bound| candidate < *bound) {
+                    bound = Some(candidate);
+                }
+            }
+        }
+        events.extend(corpus_events);
+    }
+    if let Some((time, id)) = &bound {
+        events.retain(|event| (event.time, &event.id) <= (*time, id));
+    }
+    events.retain(|event| filter.matches(&event.data));
+    events.sort_by(|a, b| a.time.cmp(&b.time).then_with(|| a.id.cmp(&b.id)));
+    Ok(Replay {
+        events,
+        resume_from: bound.map(|(_, id)| id),
+    })
+}
+
+// Change stream olmayan sunucular için: corpus başına imleçten sonraki belgeleri sayfa sayfa
+// okuyup yayınlar. İlk yoklama yalnızca imleçleri en yeni belgelere kurar.
+#[derive(Default)]
+pub struct Poller {
+    cursors: HashMap<String, ScanCursor>,
+}
+
+impl Poller {
+    // Yayınlanan olay sayısı
+    pub async fn poll(
+        &mut self,
+        repos: &Repositories,
+        corpora: &CorpusRegistry,
+        urls: &PublicUrls,
+        stream: &DocumentStream,
+    ) -> Result<usize, RepositoryError> {
+        let mut events = Vec::new();
+        for corpus in corpora.all() {
+            let Some(cursor) = self.cursors.get_mut(&corpus.key) else {
+                let newest = repos.documents.recent(corpus, PAGE_SIZE).await?;
+                self.cursors.insert(corpus.key.clone(), start_cursor(newest.iter().map(record_key)));
+                continue;
+            };
+            loop {
+                let page = repos.documents.created_after(corpus, cursor, PAGE_SIZE).await?;
+                *cursor = cursor.advance(page.iter().map(record_key));
+                events.extend(page.iter().filter_map(|record| {
+                    record_time(record).map(|(time, _)| StreamEvent::document(urls, corpus, record, time))
+                }));
+                if (page.len() as u64) < PAGE_SIZE {
+                    break;
+                }
+            }
+        }
+        events.sort_by(|a, b| a.time.cmp(&b.time).then_with(|| a.id.cmp(&b.id)));
+        let published = events.len();
+        for event in events {
+            stream.publish(event);
+        }
+        Ok(published)
+    }
+}
+
+// Change stream'deki ekleme olayından yayınlanacak olay; belge kurum bilgisiyle yeniden okunur
+async fn inserted(
+    repos: &Repositories,
+    corpora: &CorpusRegistry,
+    urls: &PublicUrls,
+    event: &ChangeStreamEvent<MongoDocument>,
+) -> Result<Option<StreamEvent>, RepositoryError> {
+    let Some(collection) = event.ns.as_ref().and_then(|ns| ns.coll.as_deref()) else {
+        return Ok(None);
+    };
+    let Some(corpus) = corpora.all().iter().find(|corpus| corpus.collection == collection) else {
+        return Ok(None);
+    };
+    let Some(key) = event.document_key.as_ref() else {
+        return Ok(None);
+    };
+    let id = id_string(key, "_id");
+    let record = match repos.documents.find_by_id(corpus, &id).await? {
+        Some(record) => record,
+        None => match &event.full_document {
+            Some(doc) => DocumentRecord::from_document(doc, corpus),
+            None => return Ok(None),
+        },
+    };
+    // Tarihi okunamayan belge olay zamanıyla yayınlanır
+    let time = dates::parse_timestamp(&record.olusturulma_tarihi)
+        .ok()
+        .or_else(|| event.wall_time.and_then(|time| DateTime::from_timestamp_millis(time.timestamp_millis())))
+        .unwrap_or_else(Utc::now);
+    Ok(Some(StreamEvent::document(urls, corpus, &record, time)))
+}
+
+async fn watch(
+    db: &Database,
+    repos: &Repositories,
+    corpora: &CorpusRegistry,
+    urls: &PublicUrls,
+    stream: &DocumentStream,
+    resume: &mut Option<ResumeToken>,
+) -> mongodb::error::Result<()> {
+    let collections: Vec<&str> = corpora.all().iter().map(|corpus| corpus.collection.as_str()).collect();
+    let options = ChangeStreamOptions::builder().resume_after(resume.clone()).build();
+    let pipeline = [doc! { "$match": {
+        "ns.coll": { "$in": &collections },
+        "operationType": "insert",
+    } }];
+
+    let mut changes = db.watch(pipeline, options).await?;
+    log::info!("Belge akışı change stream'i dinleniyor: {}", collections.join(", "));
+    while let Some(event) = changes.next().await {
+        let event = event?;
+        match inserted(repos, corpora, urls, &event).await {
+            Ok(Some(event)) => {
+                stream.publish(event);
+            }
+            Ok(None) => {}
+            Err(e) => log::error!("Belge akışı olayı okunamadı: {}", e),
+        }
+        *resume = Some(event.id);
+    }
+    Ok(())
+}
+
+// db verilirse ve change stream destekleniyorsa onu, aksi halde yoklamayı çalıştırır
+pub fn spawn(
+    stream: DocumentStream,
+    db: Option<Database>,
+    repos: Repositories,
+    corpora: CorpusRegistry,
+    urls: PublicUrls,
+    config: StreamConfig,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Some(db) = db.filter(|_| config.change_stream) {
+            let mut resume = None;
+            loop {
+                match watch(&db, &repos, &corpora, &urls, &stream, &mut resume).await {
+                    Ok(()) => log::warn!("Belge akışı change stream'i kapandı, yeniden açılıyor"),
+                    Err(e) if change_streams_unsupported(&e) => {
+                        log::warn!("MongoDB change stream desteklemiyor (replica set değil); belge akışı yoklama ile çalışacak");
+                        break;
+                    }
+                    Err(e) => log::error!("Belge akışı change stream hatası: {}", e),
+                }
+                tokio::time::sleep(RETRY_DELAY).await;
+            }
+        }
+
+        let Some(interval) = config.poll_interval else {
+            log::warn!("Belge akışı yoklaması kapalı (STREAM_POLL_INTERVAL=0); canlı olay yayınlanmayacak");
+            return;
+        };
+        let mut poller = Poller::default();
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = poller.poll(&repos, &corpora, &urls, &stream).await {
+                log::error!("Belge akışı yoklaması başarısız: {}", e);
+            }
+        }
+    })
+}
@@ -1,26 +1,54 @@
-// Utility fonksiyonları gelecekte kullanılmak üzere hazırlanmıştır
-// Şu an için kullanılmıyor, ancak ileride gerekebilir
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use mongodb::bson::{Bson, Document as MongoDocument};
 
-#[allow(dead_code)]
-pub fn json_response<T: serde::Serialize>(data: T) -> actix_web::HttpResponse {
-    actix_web::HttpResponse::Ok().json(data)
+// Tarih alanını string olarak oku; BSON DateTime ise RFC3339'a çevir
+pub fn bson_date_string(doc: &MongoDocument, field: &str) -> String {
+    match doc.get(field) {
+        Some(Bson::DateTime(dt)) => dt.try_to_rfc3339_string().unwrap_or_default(),
+        Some(Bson::String(value)) => value.to_string(),
+        Some(Bson::Int64(value)) => value.to_string(),
+        Some(Bson::Int32(value)) => value.to_string(),
+        _ => String::new(),
+    }
 }
 
-#[allow(dead_code)]
-pub fn error_response(message: &str, status_code: u16) -> actix_web::HttpResponse {
-    actix_web::HttpResponse::build(
-        actix_web::http::StatusCode::from_u16(status_code).unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
-    )
-    .json(serde_json::json!({
-        "error": message
-    }))
+// Uygulamanın yazdığı zaman damgaları: milisaniyeli RFC3339, UTC (string olarak sıralanabilir)
+pub fn format_timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
 }
 
-#[allow(dead_code)]
-pub fn success_response(message: &str) -> actix_web::HttpResponse {
-    actix_web::HttpResponse::Ok().json(serde_json::json!({
-        "success": true,
-        "message": message
-    }))
+// Sorgu parametresindeki tarih sınırı (YYYY-MM-DD ya da RFC3339) format_timestamp
+// formatına çevrilir. Gün verilirse günün başı, end_of_day ise ertesi günün başı döner.
+pub fn parse_time_bound(value: &str, end_of_day: bool) -> Option<String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let date = if end_of_day { date.succ_opt()? } else { date };
+        return Some(format_timestamp(date.and_hms_opt(0, 0, 0)?.and_utc()));
+    }
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| format_timestamp(time.with_timezone(&Utc)))
 }
 
+// URL slug'ı üret: Türkçe karakterler ASCII karşılıklarına çevrilir, harf ve rakam
+// dışındaki karakterler tek tireye indirilir (örn. "İş Kanunu Genelgesi" -> "is-kanunu-genelgesi")
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.chars() {
+        let c = match c {
+            'ç' | 'Ç' => 'c',
+            'ğ' | 'Ğ' => 'g',
+            'ı' | 'I' | 'İ' | 'î' | 'Î' => 'i',
+            'ö' | 'Ö' => 'o',
+            'ş' | 'Ş' => 's',
+            'ü' | 'Ü' | 'û' | 'Û' => 'u',
+            'â' | 'Â' => 'a',
+            c => c.to_ascii_lowercase(),
+        };
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
@@ -0,0 +1,475 @@
+use std::env;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use mongodb::bson::oid::ObjectId;
+use rand::RngCore;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use crate::config::PublicUrls;
+use crate::corpus::{Corpus, CorpusRegistry};
+use crate::repository::{
+    DeliveryAttempt, DeliveryRecord, DeliveryWrite, DocumentRecord, Page, Repositories, RepositoryError,
+    ScanCursor, ScrapedAnnouncementFilter, ScrapedAnnouncementRecord, WebhookFilters, WebhookRecord,
+};
+use crate::utils::{format_timestamp, slugify};
+
+// Webhook abonelikleri: yeni belge ve duyurular belirli aralıklarla taranır, filtreleri eşleşen
+// her aktif aboneliğe bir teslimat kuyruğa yazılır ve HMAC ile imzalanmış JSON olarak POST edilir.
+// Başarısız teslimatlar artan aralıklarla yeniden denenir; deneme hakkı bitince failed olur.
+// Taramanın kaldığı yer (corpus ve duyurular için ayrı) webhook_state koleksiyonunda tutulur.
+
+pub const EVENT_DOCUMENT_CREATED: &str = "document.created";
+pub const EVENT_ANNOUNCEMENT_CREATED: &str = "announcement.created";
+pub const EVENTS: [&str; 2] = [EVENT_DOCUMENT_CREATED, EVENT_ANNOUNCEMENT_CREATED];
+
+// İmza: t=<unix saniye>,v1=<hex(HMAC-SHA256(secret, "<t>.<gövde>"))>
+pub const SIGNATURE_HEADER: &str = "X-MevzuatGPT-Signature";
+pub const EVENT_HEADER: &str = "X-MevzuatGPT-Event";
+pub const DELIVERY_HEADER: &str = "X-MevzuatGPT-Delivery";
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_DELIVERED: &str = "delivered";
+pub const STATUS_FAILED: &str = "failed";
+
+const DEFAULT_INTERVAL_SECS: u64 = 60;
+const DEFAULT_MAX_ATTEMPTS: u32 = 8;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+// Taramada corpus/duyurular için tek seferde okunan kayıt sayısı (sayfa boyutu)
+const SCAN_LIMIT: u64 = 200;
+// Bir turda gönderilen en fazla teslimat; kalanlar sonraki turlara kalır
+pub const DELIVERY_BATCH: u64 = 100;
+// Yeniden deneme aralığı: 30 sn, 1 dk, 2 dk, ... en fazla 6 saat
+const BACKOFF_BASE_SECS: i64 = 30;
+const BACKOFF_MAX_SECS: i64 = 6 * 3600;
+// Teslimat kaydında saklanan yanıt hatasının üst sınırı (karakter)
+const MAX_ERROR_CHARS: usize = 500;
+
+const ANNOUNCEMENTS_CURSOR: &str = "announcements";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookConfig {
+    // None ise tarama ve gönderim yapılmaz (birden fazla instance'tan yalnızca biri çalıştırsın)
+    pub interval: Option<Duration>,
+    // Bu sayıda denemeden sonra teslimat failed olur
+    pub max_attempts: u32,
+    // Tek gönderimin zaman aşımı
+    pub timeout: Duration,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        WebhookConfig {
+            interval: Some(Duration::from_secs(DEFAULT_INTERVAL_SECS)),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+        }
+    }
+}
+
+impl WebhookConfig {
+    // WEBHOOK_INTERVAL: saniye cinsinden tur aralığı; 0 webhook işçisini kapatır.
+    // WEBHOOK_MAX_ATTEMPTS, WEBHOOK_TIMEOUT (saniye)
+    pub fn from_env() -> Self {
+        let default = WebhookConfig::default();
+        let number = |name: &str| env::var(name).ok().and_then(|value| value.trim().parse::<u64>().ok());
+        let interval = match number("WEBHOOK_INTERVAL") {
+            Some(0) => None,
+            Some(seconds) => Some(Duration::from_secs(seconds)),
+            None => default.interval,
+        };
+        WebhookConfig {
+            interval,
+            max_attempts: number("WEBHOOK_MAX_ATTEMPTS")
+                .filter(|attempts| *attempts > 0)
+                .map(|attempts| attempts.min(u32::MAX as u64) as u32)
+                .unwrap_or(default.max_attempts),
+            timeout: number("WEBHOOK_TIMEOUT")
+                .filter(|seconds| *seconds > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(default.timeout),
+        }
+    }
+}
+
+// Aboneliğe verilen imzalama anahtarı
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 24];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+// hex(HMAC-SHA256(secret, "<timestamp>.<body>"))
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC her uzunlukta anahtar kabul eder");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+// Signature başlığının değeri
+pub fn signature_header(secret: &str, timestamp: i64, body: &str) -> String {
+    format!("t={},v1={}", timestamp, sign(secret, timestamp, body))
+}
+
+// Eşleşme için kaydın olayla birlikte taşınan alanları
+struct Candidate {
+    event: &'static str,
+    kurum_id: String,
+    belge_turu: Option<String>,
+    corpus: Option<String>,
+    // Anahtar kelimelerin arandığı metin (slug biçiminde)
+    text: String,
+    data: Value,
+}
+
+fn keyword_text(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .map(|part| slugify(part))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl Candidate {
+    fn document(urls: &PublicUrls, corpus: &Corpus, record: &DocumentRecord) -> Self {
+        Candidate {
+            event: EVENT_DOCUMENT_CREATED,
+            kurum_id: record.kurum_id.clone(),
+            belge_turu: Some(record.belge_turu.clone()),
+            corpus: Some(corpus.key.clone()),
+            text: keyword_text(&[&record.pdf_adi, &record.aciklama, &record.etiketler, &record.anahtar_kelimeler]),
+            data: json!({
+                "corpus": corpus.key,
+                "id": record.id,
+                "url_slug": record.url_slug,
+                "url": urls.document(corpus, &record.url_slug, &record.id),
+                "pdf_adi": record.pdf_adi,
+                "kurum_id": record.kurum_id,
+                "kurum_adi": record.kurum_adi(),
+                "belge_turu": record.belge_turu,
+                "aciklama": record.aciklama,
+                "olusturulma_tarihi": record.olusturulma_tarihi,
+            }),
+        }
+    }
+
+    fn announcement(record: &ScrapedAnnouncementRecord) -> Self {
+        Candidate {
+            event: EVENT_ANNOUNCEMENT_CREATED,
+            kurum_id: record.kurum_id.clone(),
+            belge_turu: None,
+            corpus: None,
+            text: keyword_text(&[&record.baslik, record.icerik.as_deref().unwrap_or_default()]),
+            data: json!({
+                "id": record.id,
+                "kurum_id": record.kurum_id,
+                "baslik": record.baslik,
+                "link": record.link,
+                "tarih": record.tarih,
+                "first_seen_at": record.first_seen_at,
+            }),
+        }
+    }
+}
+
+// Filtrelerin tümü sağlanmalıdır; anahtar kelimelerden biri yeterlidir
+fn filters_match(filters: &WebhookFilters, candidate: &Candidate) -> bool {
+    if filters.kurum_id.as_ref().is_some_and(|kurum_id| *kurum_id != candidate.kurum_id) {
+        return false;
+    }
+    if filters.belge_turu.is_some() && filters.belge_turu != candidate.belge_turu {
+        return false;
+    }
+    if filters.corpus.is_some() && filters.corpus != candidate.corpus {
+        return false;
+    }
+    filters.keywords.is_empty()
+        || filters.keywords.iter().any(|keyword| {
+            let keyword = slugify(keyword);
+            !keyword.is_empty() && candidate.text.contains(&keyword)
+        })
+}
+
+pub fn matches(webhook: &WebhookRecord, event: &str) -> bool {
+    webhook.aktif && webhook.events.iter().any(|subscribed| subscribed == event)
+}
+
+// Taramanın özeti
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanReport {
+    // Yeni bulunan belge ve duyuru sayısı
+    pub events: usize,
+    // Kuyruğa yazılan teslimat sayısı
+    pub enqueued: usize,
+}
+
+// Gönderim turunun özeti
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeliveryReport {
+    pub delivered: usize,
+    // Yeniden denenecek olanlar
+    pub retried: usize,
+    pub failed: usize,
+}
+
+// İlk taramada imleç en yeni kayıtlara kurulur; mevcut arşiv yeni sayılmaz. Boş koleksiyonda
+// imleç en baştan kurulur, böylece ilk eklenen kayıtlar da yeni sayılır.
+pub(crate) fn start_cursor<'a>(newest: impl IntoIterator<Item = (&'a str, &'a str)>) -> ScanCursor {
+    ScanCursor {
+        watermark: format_timestamp(DateTime::UNIX_EPOCH),
+        ids: Vec::new(),
+    }
+    .advance(newest)
+}
+
+fn document_key(record: &DocumentRecord) -> (&str, &str) {
+    (&record.olusturulma_tarihi, &record.id)
+}
+
+fn announcement_key(record: &ScrapedAnnouncementRecord) -> (&str, &str) {
+    (&record.first_seen_at, &record.id)
+}
+
+fn corpus_cursor(corpus: &Corpus) -> String {
+    format!("corpus:{}", corpus.key)
+}
+
+// Yeni belge ve duyuruları bulur, eşleşen aboneliklere teslimat yazar. Tarama her durumda
+// yapılır (abonelik olmasa da imleç ilerler); yeni abonelik yalnızca sonraki kayıtları alır.
+// Kayıtlar imleçten itibaren en eski önce SCAN_LIMIT'lik sayfalarla okunur; her sayfanın
+// teslimatları yazıldıktan sonra imleç kaydedilir, böylece aralıkta ne kadar kayıt gelirse
+// gelsin atlanan olmaz.
+pub async fn scan(repos: &Repositories, corpora: &CorpusRegistry, urls: &PublicUrls) -> Result<ScanReport, RepositoryError> {
+    let webhooks = repos.webhooks.list().await?;
+    let mut report = ScanReport::default();
+
+    for corpus in corpora.all() {
+        let key = corpus_cursor(corpus);
+        let Some(mut cursor) = repos.webhooks.cursor(&key).await? else {
+            let newest = repos.documents.recent(corpus, SCAN_LIMIT).await?;
+            repos.webhooks.save_cursor(&key, &start_cursor(newest.iter().map(document_key))).await?;
+            continue;
+        };
+        loop {
+            let page = repos.documents.created_after(corpus, &cursor, SCAN_LIMIT).await?;
+            if page.is_empty() {
+                break;
+            }
+            let candidates = page.iter().map(|record| Candidate::document(urls, corpus, record)).collect();
+            enqueue_events(repos, &webhooks, candidates, &mut report).await?;
+            cursor = cursor.advance(page.iter().map(document_key));
+            repos.webhooks.save_cursor(&key, &cursor).await?;
+            if (page.len() as u64) < SCAN_LIMIT {
+                break;
+            }
+        }
+    }
+
+    match repos.webhooks.cursor(ANNOUNCEMENTS_CURSOR).await? {
+        None => {
+            let newest = repos
+                .scraped_announcements
+                .list(&ScrapedAnnouncementFilter::default(), Page { offset: 0, limit: SCAN_LIMIT })
+                .await?;
+            let start = start_cursor(newest.iter().map(announcement_key));
+            repos.webhooks.save_cursor(ANNOUNCEMENTS_CURSOR, &start).await?;
+        }
+        Some(mut cursor) => loop {
+            let page = repos.scraped_announcements.seen_after(&cursor, SCAN_LIMIT).await?;
+            if page.is_empty() {
+                break;
+            }
+            enqueue_events(repos, &webhooks, page.iter().map(Candidate::announcement).collect(), &mut report).await?;
+            cursor = cursor.advance(page.iter().map(announcement_key));
+            repos.webhooks.save_cursor(ANNOUNCEMENTS_CURSOR, &cursor).await?;
+            if (page.len() as u64) < SCAN_LIMIT {
+                break;
+            }
+        },
+    }
+    Ok(report)
+}
+
+// Kayıtları (en eski önce) filtreleri eşleşen aboneliklere teslimat olarak kuyruğa yazar
+async fn enqueue_events(
+    repos: &Repositories,
+    webhooks: &[WebhookRecord],
+    candidates: Vec<Candidate>,
+    report: &mut ScanReport,
+) -> Result<(), RepositoryError> {
+    report.events += candidates.len();
+    let created_at = format_timestamp(Utc::now());
+    let mut deliveries = Vec::new();
+    for candidate in candidates {
+        let subscribers: Vec<&WebhookRecord> = webhooks
+            .iter()
+            .filter(|webhook| matches(webhook, candidate.event) && filters_match(&webhook.filters, &candidate))
+            .collect();
+        if subscribers.is_empty() {
+            continue;
+        }
+        let event_id = format!("evt_{}", ObjectId::new().to_hex());
+        let payload = json!({
+            "id": event_id,
+            "type": candidate.event,
+            "created_at": created_at,
+            "data": candidate.data,
+        })
+        .to_string();
+        deliveries.extend(subscribers.into_iter().map(|webhook| DeliveryWrite {
+            webhook_id: webhook.id.clone(),
+            event_id: event_id.clone(),
+            event: candidate.event.to_string(),
+            payload: payload.clone(),
+            created_at: created_at.clone(),
+            replay_of: None,
+        }));
+    }
+    if !deliveries.is_empty() {
+        report.enqueued += repos.webhooks.enqueue(&deliveries).await?.len();
+    }
+    Ok(())
+}
+
+// attempts'inci başarısız denemeden sonraki bekleme
+pub fn backoff(attempts: u32) -> chrono::Duration {
+    let exponent = attempts.saturating_sub(1).min(20);
+    chrono::Duration::seconds((BACKOFF_BASE_SECS << exponent).min(BACKOFF_MAX_SECS))
+}
+
+pub fn client(config: &WebhookConfig) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(config.timeout)
+        // Yönlendirme izlenmez; abonelik adresi doğrudan yanıt vermelidir
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("HTTP client oluşturulamadı")
+}
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_ERROR_CHARS) {
+        Some((index, _)) => text[..index].to_string(),
+        None => text.to_string(),
+    }
+}
+
+// Teslimatı bir kez gönderir; (yanıt kodu, hata) döner, hata yoksa teslim edilmiştir
+async fn send(client: &reqwest::Client, webhook: &WebhookRecord, delivery: &DeliveryRecord, now: DateTime<Utc>) -> (Option<u16>, Option<String>) {
+    let response = client
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(reqwest::header::USER_AGENT, "MevzuatGPT-Webhooks/1.0")
+        .header(SIGNATURE_HEADER, signature_header(&webhook.secret, now.timestamp(), &delivery.payload))
+        .header(EVENT_HEADER, &delivery.event)
+        .header(DELIVERY_HEADER, &delivery.id)
+        .body(delivery.payload.clone())
+        .send()
+        .await;
+    match response {
+        Ok(response) if response.status().is_success() => (Some(response.status().as_u16()), None),
+        Ok(response) => {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let error = match body.trim() {
+                "" => format!("HTTP {}", status.as_u16()),
+                body => format!("HTTP {}: {}", status.as_u16(), truncate(body)),
+            };
+            (Some(status.as_u16()), Some(error))
+        }
+        Err(e) => (None, Some(truncate(&e.to_string()))),
+    }
+}
+
+// Zamanı gelmiş teslimatları gönderir
+pub async fn deliver_due(
+    repos: &Repositories,
+    client: &reqwest::Client,
+    config: &WebhookConfig,
+    now: DateTime<Utc>,
+) -> Result<DeliveryReport, RepositoryError> {
+    let due = repos.webhooks.due(&format_timestamp(now), DELIVERY_BATCH).await?;
+    let mut report = DeliveryReport::default();
+    for delivery in due {
+        let webhook = repos.webhooks.find_by_id(&delivery.webhook_id).await?;
+        let attempted_at = format_timestamp(now);
+        let attempt = match webhook.filter(|webhook| webhook.aktif) {
+            // Silinmiş ya da durdurulmuş abonelik için teslimat beklemede tutulmaz
+            None => DeliveryAttempt {
+                status: STATUS_FAILED.to_string(),
+                attempted_at,
+                error: Some("Abonelik bulunamadı ya da aktif değil".to_string()),
+                ..DeliveryAttempt::default()
+            },
+            Some(webhook) => {
+                let (response_status, error) = send(client, &webhook, &delivery, now).await;
+                let attempts = delivery.attempts + 1;
+                let status = match &error {
+                    None => STATUS_DELIVERED,
+                    Some(_) if attempts >= config.max_attempts => STATUS_FAILED,
+                    Some(_) => STATUS_PENDING,
+                };
+                DeliveryAttempt {
+                    status: status.to_string(),
+                    attempted_at,
+                    next_attempt_at: (status == STATUS_PENDING).then(|| format_timestamp(now + backoff(attempts))),
+                    response_status,
+                    error,
+                }
+            }
+        };
+        match attempt.status.as_str() {
+            STATUS_DELIVERED => report.delivered += 1,
+            STATUS_PENDING => report.retried += 1,
+            _ => {
+                report.failed += 1;
+                log::warn!(
+                    "Webhook teslimatı başarısız: {} ({}): {}",
+                    delivery.id,
+                    delivery.webhook_id,
+                    attempt.error.as_deref().unwrap_or_default()
+                );
+            }
+        }
+        repos.webhooks.record_attempt(&delivery.id, &attempt).await?;
+    }
+    Ok(report)
+}
+
+pub fn spawn(
+    repos: Repositories,
+    corpora: CorpusRegistry,
+    urls: PublicUrls,
+    config: WebhookConfig,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let client = client(&config);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match scan(&repos, &corpora, &urls).await {
+                Ok(report) if report.events > 0 => log::info!(
+                    "Webhook taraması: {} yeni kayıt, {} teslimat kuyruğa yazıldı",
+                    report.events,
+                    report.enqueued
+                ),
+                Ok(_) => {}
+                Err(e) => log::error!("Webhook taraması başarısız: {}", e),
+            }
+            match deliver_due(&repos, &client, &config, Utc::now()).await {
+                Ok(report) if report != DeliveryReport::default() => log::info!(
+                    "Webhook teslimatları: {} başarılı, {} yeniden denenecek, {} başarısız",
+                    report.delivered,
+                    report.retried,
+                    report.failed
+                ),
+                Ok(_) => {}
+                Err(e) => log::error!("Webhook teslimatları okunamadı: {}", e),
+            }
+        }
+    })
+}
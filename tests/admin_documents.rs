@@ -0,0 +1,291 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use actix_web::test;
+use api_mevzuatgpt::audit::Actor;
+use api_mevzuatgpt::auth::Scope;
+use api_mevzuatgpt::corpus::Corpus;
+use api_mevzuatgpt::repository::{
+    DocumentWrite, DocumentWriteRepository, MemoryRepository, RepositoryResult,
+};
+use api_mevzuatgpt::utils::slugify;
+use async_trait::async_trait;
+use mongodb::bson::{doc, oid::ObjectId, Bson};
+use serde_json::json;
+
+#[macro_use]
+mod common;
+use common::{fixture, key, memory_state, state, status_and_body, with_key};
+
+// Belge yönetim API'si: yetki, doğrulama, slug üretimi ve kimlik normalizasyonu
+
+#[actix_web::test]
+async fn admin_routes_require_admin_scope() {
+    let f = fixture().await;
+    let reader_key = key(&f.store, "okuyucu", &[Scope::Read]).await;
+    let app = app!(memory_state(f.store.clone()));
+    let body = json!({ "pdf_adi": "Genelge", "kurum_id": f.kurum_id.to_hex() });
+
+    // API_AUTH_REQUIRED kapalı olsa da yönetim route'ları anahtar ister
+    let req = test::TestRequest::post().uri("/api/admin/documents").set_json(&body).to_request();
+    let (status, body_json) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 401);
+    assert_eq!(body_json["error"]["code"], "API_KEY_REQUIRED");
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/documents"), &reader_key)
+        .set_json(&body)
+        .to_request();
+    let (status, body_json) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 403);
+    assert_eq!(body_json["error"]["code"], "INSUFFICIENT_SCOPE");
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/documents"), &f.admin_key)
+        .set_json(&body)
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 201);
+}
+
+#[actix_web::test]
+async fn invalid_bodies_are_rejected_with_field_errors() {
+    let f = fixture().await;
+    let app = app!(memory_state(f.store.clone()));
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/documents"), &f.admin_key)
+        .set_json(json!({
+            "kurum_id": ObjectId::new().to_hex(),
+            "belge_yayin_tarihi": "01.02.2024",
+            "status": "silindi",
+            "sayfa_sayisi": -1,
+            "url_slug": "Büyük Harf",
+        }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    assert_eq!(body["error"]["code"], "VALIDATION_FAILED");
+
+    let fields: Vec<(String, String)> = body["error"]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| (f["field"].as_str().unwrap().to_string(), f["code"].as_str().unwrap().to_string()))
+        .collect();
+    for expected in [
+        ("pdf_adi", "REQUIRED"),
+        ("kurum_id", "NOT_FOUND"),
+        ("belge_yayin_tarihi", "INVALID_FORMAT"),
+        ("status", "NOT_ALLOWED"),
+        ("sayfa_sayisi", "NEGATIVE"),
+        ("url_slug", "INVALID_FORMAT"),
+    ] {
+        assert!(
+            fields.contains(&(expected.0.to_string(), expected.1.to_string())),
+            "{:?} bekleniyordu: {:?}",
+            expected,
+            fields
+        );
+    }
+
+    // Bilinmeyen alanlar ve bozuk JSON ortak zarfla döner
+    let req = with_key(test::TestRequest::post().uri("/api/admin/documents"), &f.admin_key)
+        .set_json(json!({ "pdf_adi": "Genelge", "kurum": "sgk" }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 400);
+    assert_eq!(body["error"]["code"], "INVALID_BODY");
+
+    assert!(f.store.documents("metadata").is_empty());
+}
+
+#[actix_web::test]
+async fn create_generates_unique_slugs_and_writes_content() {
+    let f = fixture().await;
+    let app = app!(memory_state(f.store.clone()));
+    let body = json!({
+        "pdf_adi": "İş Sağlığı ve Güvenliği Genelgesi",
+        "kurum_id": f.kurum_id.to_hex(),
+        "belge_turu": "Genelge",
+        "belge_yayin_tarihi": "2024-06-01",
+        "icerik": "İşverenler risk değerlendirmesi yapmakla yükümlüdür",
+    });
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/documents"), &f.admin_key)
+        .set_json(&body)
+        .to_request();
+    let (status, created) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    assert_eq!(created["data"]["metadata"]["url_slug"], "is-sagligi-ve-guvenligi-genelgesi");
+    assert_eq!(created["data"]["metadata"]["status"], "aktif");
+    assert_eq!(created["data"]["kurum_adi"], "Çalışma Bakanlığı");
+    assert_eq!(
+        created["data"]["content"]["icerik"],
+        "İşverenler risk değerlendirmesi yapmakla yükümlüdür"
+    );
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/documents"), &f.admin_key)
+        .set_json(&body)
+        .to_request();
+    let (_, second) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(second["data"]["metadata"]["url_slug"], "is-sagligi-ve-guvenligi-genelgesi-2");
+
+    // kurum_id string, içerik foreign key'i ObjectId olarak saklanır
+    let metadata = f.store.documents("metadata");
+    assert!(matches!(metadata[0].get("kurum_id"), Some(Bson::String(_))));
+    let content = f.store.documents("content");
+    assert_eq!(content.len(), 2);
+    assert_eq!(content[0].get("metadata_id"), metadata[0].get("_id"));
+
+    // Okuma API'si ve arama yeni belgeyi görür
+    let req = test::TestRequest::get()
+        .uri("/api/v1/documents/is-sagligi-ve-guvenligi-genelgesi")
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+    let req = test::TestRequest::get().uri("/api/v1/search?q=risk").to_request();
+    let (_, found) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(found["count"], 2);
+}
+
+// Slug kontrolünden sonra, yazmadan önce aynı slug'ı başka bir istek alır
+struct RacingWrites {
+    store: Arc<MemoryRepository>,
+    inner: Arc<dyn DocumentWriteRepository>,
+    raced: AtomicBool,
+}
+
+#[async_trait]
+impl DocumentWriteRepository for RacingWrites {
+    async fn insert(&self, actor: &Actor, corpus: &Corpus, document: &DocumentWrite) -> RepositoryResult<String> {
+        if !self.raced.swap(true, Ordering::SeqCst) {
+            self.store.insert("metadata", doc! { "pdf_adi": "Eşzamanlı", "url_slug": &document.url_slug, "status": "aktif" });
+        }
+        self.inner.insert(actor, corpus, document).await
+    }
+
+    async fn update(&self, actor: &Actor, corpus: &Corpus, id: &str, document: &DocumentWrite) -> RepositoryResult<bool> {
+        self.inner.update(actor, corpus, id, document).await
+    }
+
+    async fn delete(&self, actor: &Actor, corpus: &Corpus, id: &str) -> RepositoryResult<bool> {
+        self.inner.delete(actor, corpus, id).await
+    }
+}
+
+#[actix_web::test]
+async fn concurrent_slug_conflicts_are_retried_or_rejected() {
+    let f = fixture().await;
+    let mut repos = f.repos.clone();
+    let racing = Arc::new(RacingWrites { store: f.store.clone(), inner: f.repos.document_writes.clone(), raced: AtomicBool::new(false) });
+    repos.document_writes = racing.clone();
+    let app = app!(state(repos));
+    let body = json!({ "pdf_adi": "Kısa Çalışma Genelgesi", "kurum_id": f.kurum_id.to_hex() });
+
+    // Üretilen slug bir sonraki ekle yeniden denenir
+    let req = with_key(test::TestRequest::post().uri("/api/admin/documents"), &f.admin_key)
+        .set_json(&body)
+        .to_request();
+    let (status, created) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    assert_eq!(created["data"]["metadata"]["url_slug"], "kisa-calisma-genelgesi-2");
+
+    // Gövdede verilen slug yeniden üretilmez; alan hatası döner
+    racing.raced.store(false, Ordering::SeqCst);
+    let mut explicit = body.clone();
+    explicit["url_slug"] = json!("ozel-slug");
+    let req = with_key(test::TestRequest::post().uri("/api/admin/documents"), &f.admin_key)
+        .set_json(&explicit)
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    assert_eq!(body["error"]["fields"][0]["field"], "url_slug");
+    assert_eq!(body["error"]["fields"][0]["code"], "ALREADY_EXISTS");
+    assert_eq!(f.store.documents("metadata").len(), 3);
+}
+
+#[actix_web::test]
+async fn updates_normalize_legacy_records() {
+    let f = fixture().await;
+    let legacy_id = ObjectId::new();
+    // Eski script'lerle yazılmış kayıt: kurum_id ObjectId, içerik foreign key'i string
+    f.store.insert("metadata", doc! {
+        "_id": legacy_id,
+        "kurum_id": f.kurum_id,
+        "pdf_adi": "Eski Yönetmelik",
+        "url_slug": "eski-yonetmelik",
+        "belge_yayin_tarihi": "01.01.2010",
+        "status": "aktif",
+        "olusturulma_tarihi": "2010-01-01",
+    });
+    f.store.insert("content", doc! { "metadata_id": legacy_id.to_hex(), "content": "Eski metin" });
+    f.store.insert("metadata", doc! { "pdf_adi": "Başka Belge", "url_slug": "baska-belge" });
+    let app = app!(memory_state(f.store.clone()));
+    let uri = format!("/api/admin/documents/{}", legacy_id.to_hex());
+
+    // PATCH yalnızca gönderilen alanları doğrular ve değiştirir
+    let req = with_key(test::TestRequest::patch().uri(&uri), &f.admin_key)
+        .set_json(json!({ "aciklama": "Güncel açıklama" }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["data"]["metadata"]["aciklama"], "Güncel açıklama");
+    assert_eq!(body["data"]["metadata"]["url_slug"], "eski-yonetmelik");
+    assert_eq!(body["data"]["content"]["icerik"], "Eski metin");
+
+    let metadata = f.store.documents("metadata");
+    assert_eq!(metadata[0].get("kurum_id"), Some(&Bson::String(f.kurum_id.to_hex())));
+    assert_eq!(metadata[0].get_str("olusturulma_tarihi").unwrap(), "2010-01-01");
+    assert_eq!(f.store.documents("content")[0].get("metadata_id"), Some(&Bson::ObjectId(legacy_id)));
+
+    // PUT başka belgenin slug'ını alamaz
+    let req = with_key(test::TestRequest::put().uri(&uri), &f.admin_key)
+        .set_json(json!({ "pdf_adi": "Yeni Yönetmelik", "kurum_id": f.kurum_id.to_hex(), "url_slug": "baska-belge" }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    assert_eq!(body["error"]["fields"][0]["code"], "ALREADY_EXISTS");
+
+    // PUT içerik verilirse eski içeriği değiştirir
+    let req = with_key(test::TestRequest::put().uri(&uri), &f.admin_key)
+        .set_json(json!({ "pdf_adi": "Yeni Yönetmelik", "kurum_id": f.kurum_id.to_hex(), "icerik": "Yeni metin" }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["data"]["metadata"]["pdf_adi"], "Yeni Yönetmelik");
+    assert_eq!(body["data"]["metadata"]["belge_yayin_tarihi"], "");
+    assert_eq!(body["data"]["content"]["icerik"], "Yeni metin");
+    assert_eq!(f.store.documents("content").len(), 1);
+
+    let req = with_key(test::TestRequest::patch().uri("/api/admin/documents/yok"), &f.admin_key)
+        .set_json(json!({}))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+}
+
+#[actix_web::test]
+async fn delete_removes_metadata_and_content() {
+    let f = fixture().await;
+    let app = app!(memory_state(f.store.clone()));
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/documents"), &f.admin_key)
+        .set_json(json!({ "pdf_adi": "Silinecek", "kurum_id": f.kurum_id.to_hex(), "icerik": "metin" }))
+        .to_request();
+    let (_, created) = status_and_body(test::call_service(&app, req).await).await;
+    let id = created["data"]["metadata"]["id"].as_str().unwrap().to_string();
+
+    let req = with_key(test::TestRequest::delete().uri(&format!("/api/admin/documents/{}", id)), &f.admin_key)
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["data"]["url_slug"], "silinecek");
+    assert!(f.store.documents("metadata").is_empty());
+    assert!(f.store.documents("content").is_empty());
+
+    let req = with_key(test::TestRequest::delete().uri(&format!("/api/admin/documents/{}", id)), &f.admin_key)
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+}
+
+#[actix_web::test]
+async fn slugify_transliterates_turkish() {
+    assert_eq!(slugify("İş Kanunu (4857) Genelgesi"), "is-kanunu-4857-genelgesi");
+    assert_eq!(slugify("  ÇAĞRI   Merkezi  "), "cagri-merkezi");
+    assert_eq!(slugify("Kâr Payı—Dağıtımı"), "kar-payi-dagitimi");
+    assert_eq!(slugify("!!!"), "");
+}
@@ -0,0 +1,267 @@
+use actix_web::test;
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::{json, Value};
+
+#[macro_use]
+mod common;
+use common::{fixture, memory_state, status_and_body, with_key};
+
+// Kurum, duyuru linki ve faydalı link yönetimi: doğrulama ve kurum_id bütünlüğü
+
+fn field_errors(body: &Value) -> Vec<(String, String)> {
+    body["error"]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| (f["field"].as_str().unwrap().to_string(), f["code"].as_str().unwrap().to_string()))
+        .collect()
+}
+
+fn field(name: &str, code: &str) -> (String, String) {
+    (name.to_string(), code.to_string())
+}
+
+#[actix_web::test]
+async fn institutions_are_validated_and_created() {
+    let f = fixture().await;
+    f.store.insert("kurumlar", doc! { "_id": ObjectId::new(), "kurum_adi": "Adalet Bakanlığı", "detsis": "12345678" });
+    let app = app!(memory_state(f.store.clone()));
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/institutions"), &f.admin_key)
+        .set_json(json!({
+            "kurum_adi": "çalışma bakanlığı",
+            "kurum_logo": "ftp://logo.example/logo.png",
+            "detsis": "12AB",
+        }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    let fields = field_errors(&body);
+    assert!(fields.contains(&field("kurum_adi", "ALREADY_EXISTS")), "{:?}", fields);
+    assert!(fields.contains(&field("kurum_logo", "INVALID_FORMAT")), "{:?}", fields);
+    assert!(fields.contains(&field("detsis", "INVALID_FORMAT")), "{:?}", fields);
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/institutions"), &f.admin_key)
+        .set_json(json!({ "kurum_adi": "Sağlık Bakanlığı", "detsis": "12345678" }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    assert_eq!(field_errors(&body), vec![field("detsis", "ALREADY_EXISTS")]);
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/institutions"), &f.admin_key)
+        .set_json(json!({
+            "kurum_adi": " Sağlık Bakanlığı ",
+            "kurum_logo": "https://cdn.example.gov.tr/saglik.png",
+            "detsis": "87654321",
+        }))
+        .to_request();
+    let (status, created) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    assert_eq!(created["data"]["kurum_adi"], "Sağlık Bakanlığı");
+    assert_eq!(created["data"]["detsis"], "87654321");
+    let id = created["data"]["kurum_id"].as_str().unwrap().to_string();
+
+    // PATCH yalnızca gönderilen alanı değiştirir
+    let req = with_key(test::TestRequest::patch().uri(&format!("/api/admin/institutions/{}", id)), &f.admin_key)
+        .set_json(json!({ "aciklama": "Sağlık politikaları" }))
+        .to_request();
+    let (status, updated) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(updated["data"]["kurum_adi"], "Sağlık Bakanlığı");
+    assert_eq!(updated["data"]["kurum_aciklama"], "Sağlık politikaları");
+    assert_eq!(updated["data"]["kurum_logo"], "https://cdn.example.gov.tr/saglik.png");
+
+    let req = with_key(test::TestRequest::put().uri(&format!("/api/admin/institutions/{}", ObjectId::new())), &f.admin_key)
+        .set_json(json!({ "kurum_adi": "Yok" }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 404);
+    assert_eq!(body["error"]["code"], "INSTITUTION_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn institution_with_documents_cannot_be_deleted() {
+    let f = fixture().await;
+    f.store.insert("metadata", doc! { "pdf_adi": "Genelge", "kurum_id": f.kurum_id.to_hex() });
+    let app = app!(memory_state(f.store.clone()));
+
+    let req = with_key(test::TestRequest::delete().uri(&format!("/api/admin/institutions/{}", f.kurum_id)), &f.admin_key)
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 409);
+    assert_eq!(body["error"]["code"], "INSTITUTION_IN_USE");
+    assert_eq!(f.store.documents("kurumlar").len(), 1);
+}
+
+#[actix_web::test]
+async fn deleting_institution_removes_its_sources_and_links() {
+    let f = fixture().await;
+    let other = ObjectId::new();
+    f.store.insert("kurumlar", doc! { "_id": other, "kurum_adi": "Adalet Bakanlığı" });
+    f.store.insert("links", doc! { "kurum_id": other.to_hex(), "baslik": "Başka", "aciklama": "", "url": "https://adalet.gov.tr" });
+    let app = app!(memory_state(f.store.clone()));
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/announcement-sources"), &f.admin_key)
+        .set_json(json!({ "kurum_id": f.kurum_id.to_hex(), "duyuru_linki": "https://csgb.gov.tr/duyurular" }))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 201);
+    let req = with_key(test::TestRequest::post().uri("/api/admin/links"), &f.admin_key)
+        .set_json(json!({ "kurum_id": f.kurum_id.to_hex(), "baslik": "e-Devlet", "url": "https://turkiye.gov.tr" }))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 201);
+
+    let req = with_key(test::TestRequest::delete().uri(&format!("/api/admin/institutions/{}", f.kurum_id)), &f.admin_key)
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["data"]["id"], f.kurum_id.to_hex());
+
+    assert_eq!(f.store.documents("kurumlar").len(), 1);
+    assert!(f.store.documents("kurum_duyuru").is_empty());
+    let links = f.store.documents("links");
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].get_str("kurum_id").unwrap(), other.to_hex());
+}
+
+#[actix_web::test]
+async fn announcement_sources_require_existing_institution() {
+    let f = fixture().await;
+    let app = app!(memory_state(f.store.clone()));
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/announcement-sources"), &f.admin_key)
+        .set_json(json!({ "kurum_id": ObjectId::new().to_hex(), "duyuru_linki": "csgb.gov.tr" }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    let fields = field_errors(&body);
+    assert!(fields.contains(&field("kurum_id", "NOT_FOUND")), "{:?}", fields);
+    assert!(fields.contains(&field("duyuru_linki", "INVALID_FORMAT")), "{:?}", fields);
+
+    let body = json!({ "kurum_id": f.kurum_id.to_hex(), "duyuru_linki": "https://csgb.gov.tr/duyurular" });
+    let req = with_key(test::TestRequest::post().uri("/api/admin/announcement-sources"), &f.admin_key)
+        .set_json(&body)
+        .to_request();
+    let (status, created) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    let id = created["data"]["id"].as_str().unwrap().to_string();
+
+    // Her kurumun tek duyuru linki olabilir
+    let req = with_key(test::TestRequest::post().uri("/api/admin/announcement-sources"), &f.admin_key)
+        .set_json(&body)
+        .to_request();
+    let (status, duplicate) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    assert_eq!(field_errors(&duplicate), vec![field("kurum_id", "ALREADY_EXISTS")]);
+
+    let req = with_key(test::TestRequest::patch().uri(&format!("/api/admin/announcement-sources/{}", id)), &f.admin_key)
+        .set_json(json!({ "baslik": "Duyurular" }))
+        .to_request();
+    let (status, updated) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(updated["data"]["baslik"], "Duyurular");
+    assert_eq!(updated["data"]["duyuru_linki"], "https://csgb.gov.tr/duyurular");
+
+    let req = with_key(test::TestRequest::delete().uri(&format!("/api/admin/announcement-sources/{}", id)), &f.admin_key)
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+    assert!(f.store.documents("kurum_duyuru").is_empty());
+}
+
+#[actix_web::test]
+async fn announcement_source_scraper_rules_are_validated_and_kept() {
+    let f = fixture().await;
+    let app = app!(memory_state(f.store.clone()));
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/announcement-sources"), &f.admin_key)
+        .set_json(json!({
+            "kurum_id": f.kurum_id.to_hex(),
+            "duyuru_linki": "https://csgb.gov.tr/duyurular",
+            "scraper": { "item": "", "link": "a[", "date_format": "%d.%Q.%Y", "max_pages": 50 },
+        }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    let mut fields = field_errors(&body);
+    fields.sort();
+    assert_eq!(
+        fields,
+        vec![
+            field("scraper.date_format", "INVALID_FORMAT"),
+            field("scraper.item", "REQUIRED"),
+            field("scraper.link", "INVALID_FORMAT"),
+            field("scraper.max_pages", "INVALID_FORMAT"),
+        ]
+    );
+
+    let rules = json!({ "item": "ul.duyurular > li", "date": "span.tarih", "date_format": "%d %B %Y", "max_pages": 3 });
+    let req = with_key(test::TestRequest::post().uri("/api/admin/announcement-sources"), &f.admin_key)
+        .set_json(json!({ "kurum_id": f.kurum_id.to_hex(), "duyuru_linki": "https://csgb.gov.tr/duyurular", "scraper": rules }))
+        .to_request();
+    let (status, created) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    assert_eq!(created["data"]["scraper"], rules);
+    let id = created["data"]["id"].as_str().unwrap().to_string();
+
+    // PATCH'te verilmeyen kurallar korunur, PUT'ta kaldırılır
+    let req = with_key(test::TestRequest::patch().uri(&format!("/api/admin/announcement-sources/{}", id)), &f.admin_key)
+        .set_json(json!({ "baslik": "Duyurular" }))
+        .to_request();
+    let (_, updated) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(updated["data"]["scraper"], rules);
+
+    let req = with_key(test::TestRequest::put().uri(&format!("/api/admin/announcement-sources/{}", id)), &f.admin_key)
+        .set_json(json!({ "kurum_id": f.kurum_id.to_hex(), "duyuru_linki": "https://csgb.gov.tr/duyurular" }))
+        .to_request();
+    let (status, replaced) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert!(replaced["data"].get("scraper").is_none());
+}
+
+#[actix_web::test]
+async fn links_are_validated_and_listed_for_institution() {
+    let f = fixture().await;
+    let app = app!(memory_state(f.store.clone()));
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/links"), &f.admin_key)
+        .set_json(json!({ "kurum_id": "gecersiz", "url": "javascript:alert(1)" }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    let fields = field_errors(&body);
+    for expected in [field("kurum_id", "INVALID_FORMAT"), field("baslik", "REQUIRED"), field("url", "INVALID_FORMAT")] {
+        assert!(fields.contains(&expected), "{:?} bekleniyordu: {:?}", expected, fields);
+    }
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/links"), &f.admin_key)
+        .set_json(json!({
+            "kurum_id": f.kurum_id.to_hex(),
+            "baslik": "İSG Katip",
+            "aciklama": "İş güvenliği kayıt sistemi",
+            "url": "https://isgkatip.csgb.gov.tr",
+        }))
+        .to_request();
+    let (status, created) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    let id = created["data"]["id"].as_str().unwrap().to_string();
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/v1/links?kurum_id={}", f.kurum_id))
+        .to_request();
+    let (status, listed) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(listed["data"][0]["baslik"], "İSG Katip");
+
+    let req = with_key(test::TestRequest::put().uri(&format!("/api/admin/links/{}", id)), &f.admin_key)
+        .set_json(json!({ "kurum_id": f.kurum_id.to_hex(), "baslik": "İSG Katip", "url": "https://isgkatip.gov.tr" }))
+        .to_request();
+    let (status, replaced) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(replaced["data"]["aciklama"], "");
+    assert_eq!(replaced["data"]["created_at"], created["data"]["created_at"]);
+
+    let req = with_key(test::TestRequest::delete().uri(&format!("/api/admin/links/{}", ObjectId::new())), &f.admin_key)
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 404);
+    assert_eq!(body["error"]["code"], "LINK_NOT_FOUND");
+}
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use api_mevzuatgpt::auth::Scope;
+use api_mevzuatgpt::harvester::{self, DetailReport};
+use api_mevzuatgpt::repository::{MemoryRepository, Repositories, ScrapedAnnouncementWrite};
+use api_mevzuatgpt::scraping::{extract_detail, Attachment, FetchConfig, Fetcher};
+use chrono::NaiveDate;
+use mongodb::bson::{doc, oid::ObjectId};
+use url::Url;
+
+#[macro_use]
+mod common;
+use common::{key, state, status_and_body, with_key};
+
+// Duyuru detay sayfaları: ana metin, yayın tarihi ve eklerin okunması, saklanması ve aranması
+
+const DETAIL_PAGE: &str = r#"<html>
+<head>
+  <meta property="article:published_time" content="2024-03-12T09:30:00+03:00">
+  <script>var menu = "Ana Sayfa";</script>
+</head>
+<body>
+  <header><nav><a href="/">Ana Sayfa</a> <a href="/iletisim">İletişim</a></nav></header>
+  <div class="sidebar"><a href="/duyurular">Diğer duyurular</a></div>
+  <div class="haber-detay">
+    <h1>Asgari ücret desteği başvuruları</h1>
+    <p>Asgari ücret desteği   başvuruları <b>1 Nisan 2024</b> tarihine kadar uzatılmıştır.</p>
+    <p>Başvuru rehberi için <a href="/dosyalar/Rehber%20Belgesi.pdf">tıklayınız</a>.</p>
+    <ul>
+      <li><a href="https://www.sgk.gov.tr/dosyalar/genelge.docx">2024/3 sayılı genelge</a></li>
+      <li><a href="/dosyalar/Rehber%20Belgesi.pdf">Rehber (tekrar)</a></li>
+      <li><a href="/dosyalar/tablo.xlsx">Tablo</a></li>
+      <li><a href="/dosyalar/ek-2.PDF"></a></li>
+    </ul>
+    <script>console.log("izleme");</script>
+  </div>
+  <footer>Tüm hakları saklıdır</footer>
+</body>
+</html>"#;
+
+#[actix_web::test]
+async fn main_text_date_and_attachments_are_extracted() {
+    let page = Url::parse("https://www.sgk.gov.tr/Duyuru/Detay/42").unwrap();
+    let detail = extract_detail(DETAIL_PAGE, &page);
+
+    assert_eq!(
+        detail.icerik,
+        "Asgari ücret desteği başvuruları\n\
+         Asgari ücret desteği başvuruları 1 Nisan 2024 tarihine kadar uzatılmıştır.\n\
+         Başvuru rehberi için tıklayınız.\n\
+         2024/3 sayılı genelge\n\
+         Rehber (tekrar)\n\
+         Tablo"
+    );
+    // Meta etiketi metindeki tarihten önce gelir
+    assert_eq!(detail.tarih, NaiveDate::from_ymd_opt(2024, 3, 12));
+    assert_eq!(
+        detail.ekler,
+        [
+            Attachment {
+                url: "https://www.sgk.gov.tr/dosyalar/Rehber%20Belgesi.pdf".to_string(),
+                baslik: "tıklayınız".to_string(),
+                tur: "pdf".to_string(),
+            },
+            Attachment {
+                url: "https://www.sgk.gov.tr/dosyalar/genelge.docx".to_string(),
+                baslik: "2024/3 sayılı genelge".to_string(),
+                tur: "docx".to_string(),
+            },
+            // Metni olmayan linkin başlığı dosya adıdır
+            Attachment {
+                url: "https://www.sgk.gov.tr/dosyalar/ek-2.PDF".to_string(),
+                baslik: "ek-2.PDF".to_string(),
+                tur: "pdf".to_string(),
+            },
+        ]
+    );
+
+    // İçerik kapsayıcısı ve tarih etiketi olmayan sayfa: body metni ve metindeki ilk tarih
+    let plain = extract_detail("<body><nav>Menü</nav><p>Sınav 05.06.2024 günü yapılacaktır.</p></body>", &page);
+    assert_eq!(plain.icerik, "Sınav 05.06.2024 günü yapılacaktır.");
+    assert_eq!(plain.tarih, NaiveDate::from_ymd_opt(2024, 6, 5));
+    assert!(plain.ekler.is_empty());
+}
+
+async fn detail_page() -> HttpResponse {
+    HttpResponse::Ok().content_type("text/html; charset=utf-8").body(DETAIL_PAGE)
+}
+
+#[actix_web::test]
+async fn details_are_stored_searchable_and_listed_as_ingestion_candidates() {
+    let server = HttpServer::new(|| {
+        App::new()
+            .route("/Duyuru/Detay/42", web::get().to(detail_page))
+            .default_service(web::get().to(HttpResponse::NotFound))
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let base = Url::parse(&format!("http://{}/", server.addrs()[0])).unwrap();
+    let server = server.run();
+    let handle = server.handle();
+    actix_web::rt::spawn(server);
+
+    let store = Arc::new(MemoryRepository::new());
+    let repos = Repositories::memory(store.clone());
+    let kurum_id = ObjectId::new().to_hex();
+    store.insert("kurum_duyuru", doc! { "kurum_id": &kurum_id, "duyuru_linki": base.join("/Duyuru").unwrap().as_str() });
+    let write = |path: &str, baslik: &str, tarih: Option<&str>| ScrapedAnnouncementWrite {
+        kurum_id: kurum_id.clone(),
+        baslik: baslik.to_string(),
+        link: base.join(path).unwrap().to_string(),
+        tarih: tarih.map(str::to_string),
+        first_seen_at: "2024-03-12T08:00:00.000Z".to_string(),
+    };
+    repos
+        .scraped_announcements
+        .store(&[
+            write("/Duyuru/Detay/41", "Kaldırılmış duyuru", Some("01.03.2024")),
+            write("/Duyuru/Detay/42", "Teşvik başvuruları", None),
+        ])
+        .await
+        .unwrap();
+
+    let fetcher = Fetcher::new(FetchConfig::default());
+    let detail = harvester::detail_with(&fetcher);
+    let report = harvester::fetch_details(&repos, &detail, harvester::DETAIL_BATCH).await.unwrap();
+    assert_eq!(report, DetailReport { fetched: 1, failed: 1 });
+    // Başarısız sayfa da işaretlenir; sonraki turda yeniden denenmez
+    assert!(repos.scraped_announcements.pending_details(10).await.unwrap().is_empty());
+    let report = harvester::fetch_details(&repos, &detail, harvester::DETAIL_BATCH).await.unwrap();
+    assert_eq!(report, DetailReport::default());
+    handle.stop(false).await;
+
+    let items = store.documents("kurum_duyuru_items");
+    let failed = items.iter().find(|item| item.get_str("baslik") == Ok("Kaldırılmış duyuru")).unwrap();
+    assert!(failed.get_str("details_error").unwrap().contains("404"));
+    assert_eq!(failed.get_str("tarih"), Ok("01.03.2024"));
+
+    let pdf_url = base.join("/dosyalar/Rehber%20Belgesi.pdf").unwrap().to_string();
+    store.insert("metadata", doc! { "pdf_adi": "Asgari Ücret Desteği Rehberi", "url_slug": "asgari-ucret-rehberi", "pdf_url": &pdf_url });
+    let admin_key = key(&store, "yonetici", &[Scope::Admin]).await;
+    let app = app!(state(repos));
+
+    // Başlıkta geçmeyen ifade detay metninde aranır
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/v1/kurum-duyuru?kurum_id={}&q=ASGARI%20%C3%BCcret", kurum_id))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["count"], 1);
+    let item = &body["data"][0];
+    assert_eq!(item["baslik"], "Teşvik başvuruları");
+    // Liste sayfasında tarihi olmayan duyuruya detay sayfasındaki tarih yazılır
+    assert_eq!(item["tarih"], "12.03.2024");
+    assert!(item["ozet"].as_str().unwrap().starts_with("Asgari ücret desteği başvuruları Asgari"));
+    assert_eq!(item["ekler"].as_array().unwrap().len(), 3);
+    assert_eq!(item["ekler"][1]["tur"], "docx");
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/v1/kurum-duyuru?kurum_id={}&q=yok.*", kurum_id))
+        .to_request();
+    let (_, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(body["count"], 0);
+
+    let req = test::TestRequest::get().uri("/api/admin/announcement-attachments").to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 401);
+
+    let req = with_key(
+        test::TestRequest::get().uri(&format!("/api/admin/announcement-attachments?kurum_id={}", kurum_id)),
+        &admin_key,
+    )
+    .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    // Eki olmayan (detayı çekilemeyen) duyuru listelenmez
+    assert_eq!(body["count"], 1);
+    let candidate = &body["data"][0];
+    assert_eq!(candidate["baslik"], "Teşvik başvuruları");
+    assert!(candidate["duyuru_id"].is_string());
+    assert_eq!(candidate["ekler"][0]["url"], pdf_url.as_str());
+    // Alınmış ek belgeye bağlanır, alınmamışlar aday olarak kalır
+    assert!(candidate["ekler"][0]["belge_id"].is_string());
+    assert!(candidate["ekler"][1]["belge_id"].is_null());
+}
@@ -0,0 +1,314 @@
+use std::sync::Arc;
+use actix_web::test;
+use api_mevzuatgpt::audit::{self, Actor, AuditSource};
+use api_mevzuatgpt::auth::Scope;
+use api_mevzuatgpt::repository::{
+    AuditFilter, AuditRecord, AuditRepository, Page, Repositories, RepositoryError, RepositoryResult,
+    ScanCursor,
+};
+use async_trait::async_trait;
+use mongodb::bson::{doc, oid::ObjectId, Bson};
+use serde_json::{json, Value};
+
+#[macro_use]
+mod common;
+use common::{fixture, key, state, status_and_body, with_key};
+
+// Denetim kaydı: yönetim API'si yazmalarının kaydı, filtreler ve NDJSON dışa aktarma
+
+fn change<'a>(entry: &'a Value, field: &str) -> &'a Value {
+    entry["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|change| change["field"] == field)
+        .unwrap_or_else(|| panic!("{} değişikliği yok: {}", field, entry))
+}
+
+fn record(timestamp: &str, source: AuditSource, collection: &str) -> AuditRecord {
+    AuditRecord {
+        id: ObjectId::new().to_hex(),
+        timestamp: timestamp.to_string(),
+        actor: None,
+        source: source.as_str().to_string(),
+        action: "update".to_string(),
+        collection: collection.to_string(),
+        target_id: ObjectId::new().to_hex(),
+        changes: Vec::new(),
+    }
+}
+
+#[actix_web::test]
+async fn institution_writes_are_recorded_with_actor_and_diff() {
+    let f = fixture().await;
+    let app = app!(state(f.repos.clone()));
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/institutions"), &f.admin_key)
+        .set_json(json!({ "kurum_adi": "Sağlık Bakanlığı" }))
+        .to_request();
+    let (status, created) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    let id = created["data"]["kurum_id"].as_str().unwrap().to_string();
+
+    let req = with_key(test::TestRequest::patch().uri(&format!("/api/admin/institutions/{}", id)), &f.admin_key)
+        .set_json(json!({ "kurum_adi": "T.C. Sağlık Bakanlığı" }))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let req = with_key(test::TestRequest::delete().uri(&format!("/api/admin/institutions/{}", id)), &f.admin_key)
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let req = with_key(test::TestRequest::get().uri(&format!("/api/admin/audit?target_id={}", id)), &f.admin_key)
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["count"], 3);
+
+    // En yeni önce
+    let entries = body["data"].as_array().unwrap();
+    let actions: Vec<&str> = entries.iter().map(|entry| entry["action"].as_str().unwrap()).collect();
+    assert_eq!(actions, ["delete", "update", "create"]);
+    for entry in entries {
+        assert_eq!(entry["collection"], "kurumlar");
+        assert_eq!(entry["source"], "api");
+        assert!(f.admin_key.starts_with(entry["actor"].as_str().unwrap()), "{}", entry);
+    }
+
+    let renamed = change(&entries[1], "kurum_adi");
+    assert_eq!(renamed["before"], "Sağlık Bakanlığı");
+    assert_eq!(renamed["after"], "T.C. Sağlık Bakanlığı");
+    // Gönderilmeyen alanlar değişmediği için kayda girmez
+    assert!(entries[1]["changes"].as_array().unwrap().iter().all(|c| c["field"] != "kurum_logo"));
+
+    let deleted = change(&entries[0], "kurum_adi");
+    assert_eq!(deleted["before"], "T.C. Sağlık Bakanlığı");
+    assert_eq!(deleted["after"], Value::Null);
+    assert_eq!(change(&entries[2], "kurum_adi")["before"], Value::Null);
+}
+
+#[actix_web::test]
+async fn document_writes_record_metadata_and_content() {
+    let f = fixture().await;
+    let app = app!(state(f.repos.clone()));
+
+    let req = with_key(test::TestRequest::post().uri("/api/admin/documents"), &f.admin_key)
+        .set_json(json!({
+            "pdf_adi": "İş Güvenliği Genelgesi",
+            "kurum_id": f.kurum_id.to_hex(),
+            "belge_yayin_tarihi": "2024-06-01",
+            "icerik": "İlk metin",
+        }))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 201);
+
+    let metadata_id = f.store.documents("metadata")[0].get_object_id("_id").unwrap();
+    let req = with_key(test::TestRequest::patch().uri(&format!("/api/admin/documents/{}", metadata_id)), &f.admin_key)
+        .set_json(json!({ "icerik": "x".repeat(2000) }))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let req = with_key(test::TestRequest::get().uri("/api/admin/audit?collection=metadata"), &f.admin_key)
+        .to_request();
+    let (_, metadata) = status_and_body(test::call_service(&app, req).await).await;
+    let created = metadata["data"].as_array().unwrap().last().unwrap();
+    assert_eq!(created["action"], "create");
+    assert_eq!(created["target_id"], metadata_id.to_hex());
+    assert_eq!(change(created, "pdf_adi")["after"], "İş Güvenliği Genelgesi");
+
+    let req = with_key(test::TestRequest::get().uri("/api/admin/audit?collection=content"), &f.admin_key)
+        .to_request();
+    let (_, content) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(content["count"], 2);
+    assert_eq!(content["data"][0]["action"], "update");
+    assert_eq!(content["data"][0]["target_id"], content["data"][1]["target_id"]);
+
+    // Uzun metinler kısaltılarak saklanır
+    let text = change(&content["data"][0], "content");
+    assert_eq!(text["before"], "İlk metin");
+    let after = text["after"].as_str().unwrap();
+    assert!(after.ends_with('…'));
+    assert!(after.chars().count() < 1000);
+}
+
+#[actix_web::test]
+async fn filters_and_pagination_are_applied() {
+    let f = fixture().await;
+    for record in [
+        record("2024-03-01T09:00:00.000Z", AuditSource::ChangeStream, "links"),
+        record("2024-03-01T23:59:59.999Z", AuditSource::Api, "links"),
+        record("2024-03-02T00:00:00.000Z", AuditSource::Api, "kurumlar"),
+        record("2024-03-05T12:00:00.000Z", AuditSource::Cli, "metadata"),
+    ] {
+        assert!(f.repos.audit.record(&record).await.unwrap());
+    }
+    let app = app!(state(f.repos.clone()));
+
+    let list = |query: &str| {
+        with_key(test::TestRequest::get().uri(&format!("/api/admin/audit?{}", query)), &f.admin_key).to_request()
+    };
+
+    // Gün olarak verilen to, o günü kapsar
+    let (_, body) = status_and_body(test::call_service(&app, list("from=2024-03-01&to=2024-03-01")).await).await;
+    assert_eq!(body["count"], 2);
+    assert_eq!(body["data"][0]["timestamp"], "2024-03-01T23:59:59.999Z");
+
+    let (_, body) = status_and_body(test::call_service(&app, list("from=2024-03-01T12:30:00%2B03:00")).await).await;
+    assert_eq!(body["count"], 3);
+
+    let (_, body) = status_and_body(test::call_service(&app, list("source=change_stream")).await).await;
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["data"][0]["actor"], Value::Null);
+
+    let (_, body) = status_and_body(test::call_service(&app, list("action=update&limit=2&offset=1")).await).await;
+    assert_eq!(body["count"], 4);
+    let timestamps: Vec<&str> = body["data"].as_array().unwrap().iter().map(|e| e["timestamp"].as_str().unwrap()).collect();
+    assert_eq!(timestamps, ["2024-03-02T00:00:00.000Z", "2024-03-01T23:59:59.999Z"]);
+
+    for query in ["action=rename", "source=web", "collection=api_keys", "from=2024-13-01", "to=dün"] {
+        let query = query.replace("ü", "%C3%BC");
+        let (status, body) = status_and_body(test::call_service(&app, list(&query)).await).await;
+        assert_eq!(status, 400, "{}", query);
+        assert_eq!(body["error"]["code"], "INVALID_PARAMETER");
+    }
+}
+
+#[actix_web::test]
+async fn export_streams_ndjson() {
+    let f = fixture().await;
+    for day in 1..=3 {
+        let record = record(&format!("2024-04-0{}T10:00:00.000Z", day), AuditSource::Api, "links");
+        f.repos.audit.record(&record).await.unwrap();
+    }
+    let app = app!(state(f.repos.clone()));
+
+    let req = with_key(test::TestRequest::get().uri("/api/admin/audit/export?from=2024-04-02"), &f.admin_key)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "application/x-ndjson");
+    assert!(resp.headers().get("content-disposition").unwrap().to_str().unwrap().starts_with("attachment"));
+
+    let body = test::read_body(resp).await;
+    let lines: Vec<Value> = std::str::from_utf8(&body)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["timestamp"], "2024-04-03T10:00:00.000Z");
+    assert_eq!(lines[1]["collection"], "links");
+}
+
+// İlk sayfadan sonraki okumalar ve sayım depolama hatası verir
+struct FailingAudit {
+    inner: Arc<dyn AuditRepository>,
+}
+
+#[async_trait]
+impl AuditRepository for FailingAudit {
+    async fn record(&self, entry: &AuditRecord) -> RepositoryResult<bool> {
+        self.inner.record(entry).await
+    }
+
+    async fn list(&self, filter: &AuditFilter, page: Page) -> RepositoryResult<Vec<AuditRecord>> {
+        if page.offset > 0 {
+            return Err(RepositoryError::InvalidQuery("bağlantı koptu".to_string()));
+        }
+        self.inner.list(filter, page).await
+    }
+
+    async fn after(&self, filter: &AuditFilter, cursor: &ScanCursor, limit: u64) -> RepositoryResult<Vec<AuditRecord>> {
+        self.inner.after(filter, cursor, limit).await
+    }
+
+    async fn count(&self, _filter: &AuditFilter) -> RepositoryResult<u64> {
+        Err(RepositoryError::InvalidQuery("bağlantı koptu".to_string()))
+    }
+
+    async fn claim(&self, record_id: &str, event_id: &str) -> RepositoryResult<bool> {
+        self.inner.claim(record_id, event_id).await
+    }
+}
+
+#[actix_web::test]
+async fn storage_errors_are_not_hidden() {
+    let f = fixture().await;
+    for n in 0..500 {
+        let record = record(&format!("2024-04-01T10:{:02}:{:02}.000Z", n / 60, n % 60), AuditSource::Api, "links");
+        f.repos.audit.record(&record).await.unwrap();
+    }
+    let mut repos = f.repos.clone();
+    repos.audit = Arc::new(FailingAudit { inner: f.repos.audit.clone() });
+    let app = app!(state(repos));
+
+    // Sayım hatası boş sayımla 200 dönmez
+    let req = with_key(test::TestRequest::get().uri("/api/admin/audit"), &f.admin_key).to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 500);
+    assert_eq!(body["error"]["code"], "STORAGE_ERROR");
+
+    // Dışa aktarma ilk sayfadan sonra hatayla kesilir; yarım gövde tamamlanmış gibi bitmez
+    let req = with_key(test::TestRequest::get().uri("/api/admin/audit/export"), &f.admin_key).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert!(test::try_read_body(resp).await.is_err());
+}
+
+#[actix_web::test]
+async fn change_stream_claims_are_stored_with_the_data() {
+    let f = fixture().await;
+    assert!(f.repos.audit.claim("api-kaydi", "olay-1").await.unwrap());
+    // Aynı olay yeniden işlenirse (yeniden başlatma, ikinci sunucu) eşleşme korunur
+    assert!(f.repos.audit.claim("api-kaydi", "olay-1").await.unwrap());
+
+    // Eşleşme süreç belleğinde değil depoda tutulur; başka bir örnek de kaydı başka olaya vermez
+    let other = Repositories::memory(f.store.clone());
+    assert!(!other.audit.claim("api-kaydi", "olay-2").await.unwrap());
+    assert!(other.audit.claim("baska-kayit", "olay-2").await.unwrap());
+}
+
+#[actix_web::test]
+async fn audit_log_requires_admin_scope() {
+    let f = fixture().await;
+    let read_key = key(&f.store, "okuyucu", &[Scope::Read]).await;
+    let app = app!(state(f.repos.clone()));
+
+    let req = test::TestRequest::get().uri("/api/admin/audit").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 401);
+    let req = with_key(test::TestRequest::get().uri("/api/admin/audit/export"), &read_key).to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+}
+
+#[actix_web::test]
+async fn entries_pair_documents_by_collection_and_id() {
+    let f = fixture().await;
+    let kept = ObjectId::new();
+    let removed = ObjectId::new();
+    let added = ObjectId::new();
+    let before = vec![
+        ("links".to_string(), doc! { "_id": kept, "baslik": "Eski", "url": "https://a.gov.tr" }),
+        ("links".to_string(), doc! { "_id": removed, "baslik": "Silinen" }),
+        ("kurumlar".to_string(), doc! { "_id": kept, "kurum_adi": "Aynı" }),
+    ];
+    let after = vec![
+        ("links".to_string(), doc! { "_id": kept, "baslik": "Yeni", "url": "https://a.gov.tr" }),
+        ("kurumlar".to_string(), doc! { "_id": kept, "kurum_adi": "Aynı" }),
+        ("links".to_string(), doc! { "_id": added, "baslik": "Eklenen" }),
+    ];
+
+    let actor = Actor { source: AuditSource::Cli, name: Some("deploy".to_string()) };
+    let entries = audit::entries(&actor, &before, &after);
+    let summary: Vec<(&str, String)> = entries.iter().map(|e| (e.action.as_str(), e.target_id.clone())).collect();
+    assert_eq!(
+        summary,
+        [("update", kept.to_hex()), ("delete", removed.to_hex()), ("create", added.to_hex())]
+    );
+    assert_eq!(entries[0].changes.len(), 1);
+    assert_eq!(entries[0].changes[0].before, Some(Bson::String("Eski".to_string())));
+    assert!(entries.iter().all(|e| e.source == "cli" && e.actor.as_deref() == Some("deploy")));
+
+    // Aynı _id ile ikinci kayıt eklenmez (birden çok change stream dinleyicisi)
+    assert!(f.repos.audit.record(&entries[0]).await.unwrap());
+    assert!(!f.repos.audit.record(&entries[0]).await.unwrap());
+}
@@ -0,0 +1,223 @@
+use std::sync::Arc;
+use actix_web::test;
+use api_mevzuatgpt::auth::cli::KeyCommand;
+use api_mevzuatgpt::auth::{create_key, AuthConfig, Scope};
+use api_mevzuatgpt::config::AppState;
+use api_mevzuatgpt::repository::{ApiKeyRepository, MemoryRepository};
+
+#[macro_use]
+mod common;
+use common::{error_code, memory_state};
+
+// API anahtarı doğrulaması, scope'lar ve günlük kota
+
+fn state(store: Arc<MemoryRepository>, required: bool) -> AppState {
+    let mut state = memory_state(store);
+    state.auth = AuthConfig {
+        required,
+        ..AuthConfig::default()
+    };
+    state
+}
+
+#[actix_web::test]
+async fn anonymous_access_follows_config() {
+    let store = Arc::new(MemoryRepository::new());
+
+    // Zorunlu değilken anahtarsız istekler geçer
+    let app = app!(state(store.clone(), false));
+    let req = test::TestRequest::get().uri("/api/v1/documents").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let app = app!(state(store, true));
+    let req = test::TestRequest::get().uri("/api/v1/documents").to_request();
+    let (status, code) = error_code(test::call_service(&app, req).await).await;
+    assert_eq!(status, 401);
+    assert_eq!(code, "API_KEY_REQUIRED");
+
+    // Herkese açık route'lar anahtarsız erişilebilir
+    for uri in ["/api/health", "/api/openapi.json", "/api/v1/sitemap/institutions"] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200, "{}", uri);
+    }
+}
+
+#[actix_web::test]
+async fn keys_are_accepted_from_both_headers() {
+    let store = Arc::new(MemoryRepository::new());
+    let (key, record) = create_key(store.as_ref(), "entegrasyon", &[Scope::Read], None)
+        .await
+        .unwrap();
+    assert!(key.starts_with("mgpt_"));
+    assert!(key.starts_with(&record.prefix));
+    assert_ne!(record.key_hash, key);
+
+    let app = app!(state(store.clone(), true));
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/documents")
+        .insert_header(("Authorization", format!("Bearer {}", key)))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/institutions")
+        .insert_header(("X-API-Key", key.as_str()))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/documents")
+        .insert_header(("X-API-Key", "mgpt_yanlis"))
+        .to_request();
+    let (status, code) = error_code(test::call_service(&app, req).await).await;
+    assert_eq!(status, 401);
+    assert_eq!(code, "INVALID_API_KEY");
+
+    // Kullanım günlük sayaçta scope bazında tutulur
+    let used = store.usage(&record.id, &api_mevzuatgpt::auth::today()).await.unwrap();
+    assert_eq!(used, 2);
+}
+
+#[actix_web::test]
+async fn scopes_restrict_route_groups() {
+    let store = Arc::new(MemoryRepository::new());
+    let (reader, _) = create_key(store.as_ref(), "okuyucu", &[Scope::Read], None).await.unwrap();
+    let (admin, _) = create_key(store.as_ref(), "yonetici", &[Scope::Admin], None).await.unwrap();
+    let app = app!(state(store, false));
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/search?q=emeklilik")
+        .insert_header(("X-API-Key", reader.as_str()))
+        .to_request();
+    let (status, code) = error_code(test::call_service(&app, req).await).await;
+    assert_eq!(status, 403);
+    assert_eq!(code, "INSUFFICIENT_SCOPE");
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/corpora/yargitay/search?q=tazminat")
+        .insert_header(("X-API-Key", reader.as_str()))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/search?q=emeklilik")
+        .insert_header(("X-API-Key", admin.as_str()))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    // Scope route önekinden belirlenir; slug'daki "admin" ya da "search" okuma scope'u ister
+    let req = test::TestRequest::get()
+        .uri("/api/v1/documents/admin")
+        .insert_header(("X-API-Key", reader.as_str()))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+    assert_eq!(Scope::for_path("/api/v1/documents/admin"), Scope::Read);
+    assert_eq!(Scope::for_path("/api/v1/documents/search"), Scope::Read);
+    assert_eq!(Scope::for_path("/api/v1/institutions/kurum-duyuru"), Scope::Read);
+    assert_eq!(Scope::for_path("/api/v1/searchable"), Scope::Read);
+    assert_eq!(Scope::for_path("/api/admin/documents"), Scope::Admin);
+    assert_eq!(Scope::for_path("/api/v1/search/saved"), Scope::Search);
+    assert_eq!(Scope::for_path("/api/v2/search"), Scope::Search);
+    assert_eq!(Scope::for_path("/api/v1/corpora/yargitay/search"), Scope::Search);
+    assert_eq!(Scope::for_path("/api/v1/kurum-duyuru/sgk"), Scope::Scrape);
+}
+
+#[actix_web::test]
+async fn percent_encoded_paths_require_the_routed_scope() {
+    let store = Arc::new(MemoryRepository::new());
+    let (reader, _) = create_key(store.as_ref(), "okuyucu", &[Scope::Read], None).await.unwrap();
+    let app = app!(state(store.clone(), false));
+
+    // Router yüzde kodlarını çözerek eşleştirir; scope da çözülmüş yoldan seçilmeli
+    for uri in ["/api/%61dmin/audit", "/api/%61dmin/crawler/hosts", "/api/admin/%61udit"] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        let (status, code) = error_code(test::call_service(&app, req).await).await;
+        assert_eq!(status, 401, "{}", uri);
+        assert_eq!(code, "API_KEY_REQUIRED");
+
+        let req = test::TestRequest::get().uri(uri).insert_header(("X-API-Key", reader.as_str())).to_request();
+        let (status, code) = error_code(test::call_service(&app, req).await).await;
+        assert_eq!(status, 403, "{}", uri);
+        assert_eq!(code, "INSUFFICIENT_SCOPE");
+    }
+
+    let req = test::TestRequest::post()
+        .uri("/api/%61dmin/documents")
+        .set_json(serde_json::json!({ "pdf_adi": "Yetkisiz Belge" }))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 401);
+    assert!(store.documents("metadata").is_empty());
+
+    for uri in ["/api/v1/%73earch?q=emeklilik", "/api/v2/%73earch?q=tazminat"] {
+        let req = test::TestRequest::get().uri(uri).insert_header(("X-API-Key", reader.as_str())).to_request();
+        let (status, code) = error_code(test::call_service(&app, req).await).await;
+        assert_eq!(status, 403, "{}", uri);
+        assert_eq!(code, "INSUFFICIENT_SCOPE");
+    }
+}
+
+#[actix_web::test]
+async fn daily_quota_and_revocation() {
+    let store = Arc::new(MemoryRepository::new());
+    let (key, record) = create_key(store.as_ref(), "kotali", &[Scope::Read], Some(2))
+        .await
+        .unwrap();
+    let app = app!(state(store.clone(), true));
+
+    let request = || {
+        test::TestRequest::get()
+            .uri("/api/v1/statistics")
+            .insert_header(("X-API-Key", key.as_str()))
+            .to_request()
+    };
+
+    let resp = test::call_service(&app, request()).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("x-quota-limit").unwrap(), "2");
+    assert_eq!(resp.headers().get("x-quota-remaining").unwrap(), "1");
+
+    let resp = test::call_service(&app, request()).await;
+    assert_eq!(resp.headers().get("x-quota-remaining").unwrap(), "0");
+
+    let (status, code) = error_code(test::call_service(&app, request()).await).await;
+    assert_eq!(status, 429);
+    assert_eq!(code, "QUOTA_EXCEEDED");
+
+    assert!(store.revoke(&record.prefix, "2026-01-01T00:00:00Z").await.unwrap());
+    assert!(!store.revoke(&record.prefix, "2026-01-01T00:00:00Z").await.unwrap());
+    let (status, code) = error_code(test::call_service(&app, request()).await).await;
+    assert_eq!(status, 401);
+    assert_eq!(code, "INVALID_API_KEY");
+}
+
+#[actix_web::test]
+async fn cli_arguments() {
+    let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(KeyCommand::from_args(&args(&["api"])), None);
+    assert_eq!(
+        KeyCommand::from_args(&args(&["api", "--create-api-key", "partner", "--scopes", "read,scrape", "--daily-quota", "500"])),
+        Some(Ok(KeyCommand::Create {
+            name: "partner".to_string(),
+            scopes: vec![Scope::Read, Scope::Scrape],
+            daily_quota: Some(500),
+        }))
+    );
+    assert_eq!(
+        KeyCommand::from_args(&args(&["api", "--create-api-key", "partner"])),
+        Some(Ok(KeyCommand::Create {
+            name: "partner".to_string(),
+            scopes: vec![Scope::Read, Scope::Search],
+            daily_quota: None,
+        }))
+    );
+    assert!(matches!(
+        KeyCommand::from_args(&args(&["api", "--create-api-key", "partner", "--scopes", "yaz"])),
+        Some(Err(_))
+    ));
+    assert_eq!(
+        KeyCommand::from_args(&args(&["api", "--revoke-api-key", "mgpt_1234abcd"])),
+        Some(Ok(KeyCommand::Revoke("mgpt_1234abcd".to_string())))
+    );
+}
@@ -0,0 +1,81 @@
+// Entegrasyon testlerinin ortak kurulumu: bellek deposu üzerinde AppState, örnek kurum,
+// API anahtarları ve yanıt okuma yardımcıları. Test dosyaları `#[macro_use] mod common;`
+// ile kullanır; her dosya yardımcıların yalnızca bir kısmını kullandığından kullanılmayan
+// öğe uyarıları kapalıdır.
+#![allow(dead_code)]
+
+use std::sync::Arc;
+use actix_web::{body::MessageBody, dev::ServiceResponse, test};
+use api_mevzuatgpt::auth::{create_key, Scope};
+use api_mevzuatgpt::config::AppState;
+use api_mevzuatgpt::corpus::CorpusRegistry;
+use api_mevzuatgpt::error::ErrorFormat;
+use api_mevzuatgpt::repository::{MemoryRepository, Repositories};
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::Value;
+
+pub const KURUM_ADI: &str = "Çalışma Bakanlığı";
+
+pub struct Fixture {
+    pub store: Arc<MemoryRepository>,
+    pub repos: Repositories,
+    pub kurum_id: ObjectId,
+    pub admin_key: String,
+}
+
+// Tek kurumlu boş depo ve admin scope'lu anahtar
+pub async fn fixture() -> Fixture {
+    let store = Arc::new(MemoryRepository::new());
+    let kurum_id = ObjectId::new();
+    store.insert("kurumlar", doc! { "_id": kurum_id, "kurum_adi": KURUM_ADI });
+    let admin_key = key(&store, "yonetici", &[Scope::Admin]).await;
+    let repos = Repositories::memory(store.clone());
+    Fixture { store, repos, kurum_id, admin_key }
+}
+
+// Kotasız yeni API anahtarı (düz metin)
+pub async fn key(store: &MemoryRepository, name: &str, scopes: &[Scope]) -> String {
+    create_key(store, name, scopes, None).await.unwrap().0
+}
+
+// Yerleşik corpus'lar ve V2 hata zarfıyla uygulama durumu
+pub fn state(repos: Repositories) -> AppState {
+    let mut state = AppState::with_repositories(CorpusRegistry::builtin(), repos);
+    state.error_format = ErrorFormat::V2;
+    state
+}
+
+pub fn memory_state(store: Arc<MemoryRepository>) -> AppState {
+    state(Repositories::memory(store))
+}
+
+pub fn with_key(request: test::TestRequest, key: &str) -> test::TestRequest {
+    request.insert_header(("X-API-Key", key))
+}
+
+pub async fn status_and_body<B: MessageBody>(resp: ServiceResponse<B>) -> (u16, Value) {
+    let status = resp.status().as_u16();
+    (status, test::read_body_json(resp).await)
+}
+
+// Durum kodu ve V2 zarfındaki hata kodu
+pub async fn error_code<B: MessageBody>(resp: ServiceResponse<B>) -> (u16, Value) {
+    let (status, body) = status_and_body(resp).await;
+    (status, body["error"]["code"].clone())
+}
+
+// Verilen AppState ile üretimdeki middleware sırasına (request_context en dışta) sahip test servisi
+#[allow(unused_macros)]
+macro_rules! app {
+    ($state:expr) => {
+        actix_web::test::init_service(
+            actix_web::App::new()
+                .app_data(actix_web::web::Data::new($state))
+                .wrap(actix_web::middleware::from_fn(api_mevzuatgpt::middleware::api_key_auth))
+                .wrap(actix_web::middleware::from_fn(api_mevzuatgpt::middleware::rate_limit))
+                .wrap(actix_web::middleware::from_fn(api_mevzuatgpt::middleware::request_context))
+                .configure(api_mevzuatgpt::routes::configure_routes),
+        )
+        .await
+    };
+}
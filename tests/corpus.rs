@@ -0,0 +1,156 @@
+use std::sync::Arc;
+use actix_web::test;
+use api_mevzuatgpt::config::AppState;
+use api_mevzuatgpt::corpus::{ContentSource, CorpusRegistry, YARGITAY_KURUM_ID};
+use api_mevzuatgpt::error::ErrorFormat;
+use api_mevzuatgpt::repository::{MemoryRepository, Repositories};
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::{json, Value};
+
+#[macro_use]
+mod common;
+use common::status_and_body;
+
+// Corpus registry: CORPORA_CONFIG tanımlarının okunması, yerleşik tanımların ezilmesi ve
+// tanımlardan üretilen /api/v1/corpora route'ları
+
+const DANISTAY: &str = r#"[
+  {
+    "key": "danistay",
+    "title": "Danıştay Kararları",
+    "collection": "danistay",
+    "url_prefix": "/danistay",
+    "fields": { "publication_date": "kararTarihi" },
+    "content": { "type": "embedded" },
+    "search": { "preview_field": "icerik_text", "full_preview": true }
+  }
+]"#;
+
+fn keys(registry: &CorpusRegistry) -> Vec<&str> {
+    registry.all().iter().map(|corpus| corpus.key.as_str()).collect()
+}
+
+#[actix_web::test]
+async fn config_definitions_are_parsed_with_defaults() {
+    let registry = CorpusRegistry::from_json(DANISTAY).unwrap();
+    assert_eq!(keys(&registry), ["mevzuat", "yargitay", "danistay"]);
+
+    let danistay = registry.get("danistay").unwrap();
+    assert_eq!(danistay.title, "Danıştay Kararları");
+    assert_eq!(danistay.fields.publication_date, "kararTarihi");
+    assert!(matches!(danistay.content, ContentSource::Embedded));
+    assert!(danistay.search.full_preview);
+    // Verilmeyen alanlar metadata koleksiyonunun adlarıyla gelir
+    assert_eq!(danistay.fields.title, "pdf_adi");
+    assert_eq!(danistay.fields.active_status, "aktif");
+    assert!(danistay.lookup_kurum);
+    assert_eq!(danistay.kurum_id, None);
+
+    let minimal = CorpusRegistry::from_json(r#"[{ "key": "aym", "title": "AYM", "collection": "aym" }]"#).unwrap();
+    let aym = minimal.get("aym").unwrap();
+    assert_eq!(aym.url_prefix, "/belge");
+    assert_eq!(aym.search.preview_field, "aciklama");
+
+    let join = CorpusRegistry::from_json(
+        r#"[{ "key": "tebligler", "title": "Tebliğler", "collection": "teblig", "content": { "type": "join", "collection": "teblig_content", "foreign_key": "teblig_id" } }]"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        &join.get("tebligler").unwrap().content,
+        ContentSource::Join { collection, foreign_key } if collection == "teblig_content" && foreign_key == "teblig_id"
+    ));
+
+    // Zorunlu alanı eksik ya da bilinmeyen içerik türü reddedilir
+    assert!(CorpusRegistry::from_json(r#"[{ "key": "aym", "title": "AYM" }]"#).is_err());
+    assert!(CorpusRegistry::from_json(r#"[{ "key": "aym", "title": "AYM", "collection": "aym", "content": { "type": "gridfs" } }]"#).is_err());
+    assert!(CorpusRegistry::from_json("{}").is_err());
+}
+
+#[actix_web::test]
+async fn same_key_overrides_builtin_definition() {
+    let registry = CorpusRegistry::from_json(
+        r#"[{ "key": "yargitay", "title": "Yargıtay", "collection": "yargitay_v2", "url_prefix": "/karar" }]"#,
+    )
+    .unwrap();
+    // Sıra korunur; ezilen tanım yerleşik değerleri devralmaz
+    assert_eq!(keys(&registry), ["mevzuat", "yargitay"]);
+    let yargitay = registry.yargitay();
+    assert_eq!(yargitay.collection, "yargitay_v2");
+    assert_eq!(yargitay.url_prefix, "/karar");
+    assert_eq!(yargitay.fields.publication_date, "belge_yayin_tarihi");
+    assert_eq!(yargitay.kurum_id, None);
+    assert_eq!(registry.mevzuat().collection, "metadata");
+
+    let builtin = CorpusRegistry::builtin();
+    assert_eq!(builtin.yargitay().kurum_id.as_deref(), Some(YARGITAY_KURUM_ID));
+    assert!(CorpusRegistry::from_json("[]").unwrap().get("danistay").is_none());
+}
+
+#[actix_web::test]
+async fn config_file_is_read_from_env() {
+    let path = std::env::temp_dir().join(format!("corpora-{}.json", ObjectId::new().to_hex()));
+    std::fs::write(&path, DANISTAY).unwrap();
+    std::env::set_var("CORPORA_CONFIG", &path);
+    let registry = CorpusRegistry::from_env().unwrap();
+    assert_eq!(keys(&registry), ["mevzuat", "yargitay", "danistay"]);
+
+    std::fs::write(&path, "[{").unwrap();
+    let error = CorpusRegistry::from_env().unwrap_err().to_string();
+    assert!(error.starts_with("CORPORA_CONFIG geçersiz"), "{}", error);
+
+    std::fs::remove_file(&path).unwrap();
+    let error = CorpusRegistry::from_env().unwrap_err().to_string();
+    assert!(error.starts_with("CORPORA_CONFIG okunamadı"), "{}", error);
+    std::env::remove_var("CORPORA_CONFIG");
+}
+
+#[actix_web::test]
+async fn configured_corpus_gets_list_and_detail_routes() {
+    let store = Arc::new(MemoryRepository::new());
+    let kurum_id = ObjectId::new();
+    store.insert("kurumlar", doc! { "_id": kurum_id, "kurum_adi": "Danıştay" });
+    for (slug, tarih, created) in [
+        ("ikinci-daire-karari", "12.01.2024", "2024-01-20"),
+        ("onuncu-daire-karari", "05.03.2024", "2024-03-10"),
+    ] {
+        store.insert("danistay", doc! {
+            "kurum_id": kurum_id.to_hex(),
+            "pdf_adi": format!("{} başlığı", slug),
+            "url_slug": slug,
+            "status": "aktif",
+            "kararTarihi": tarih,
+            "icerik": "İdari işlemin iptali",
+            "olusturulma_tarihi": created,
+        });
+    }
+    let mut state = AppState::with_repositories(CorpusRegistry::from_json(DANISTAY).unwrap(), Repositories::memory(store));
+    state.error_format = ErrorFormat::V2;
+    let app = app!(state);
+    let get = |uri: &str| test::TestRequest::get().uri(uri).to_request();
+
+    let (_, body) = status_and_body(test::call_service(&app, get("/api/v1/corpora")).await).await;
+    assert_eq!(body["count"], 3);
+    assert_eq!(body["data"][2], json!({ "key": "danistay", "title": "Danıştay Kararları", "url_prefix": "/danistay" }));
+
+    let (status, body) = status_and_body(test::call_service(&app, get("/api/v1/corpora/danistay/documents")).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["count"], 2);
+    let slugs: Vec<&str> = body["data"].as_array().unwrap().iter().map(|item| item["url_slug"].as_str().unwrap()).collect();
+    assert_eq!(slugs, ["onuncu-daire-karari", "ikinci-daire-karari"]);
+
+    let (status, body) = status_and_body(test::call_service(&app, get("/api/v1/corpora/danistay/documents/onuncu-daire-karari")).await).await;
+    assert_eq!(status, 200);
+    let data: &Value = &body["data"];
+    assert_eq!(data["metadata"]["url_slug"], "onuncu-daire-karari");
+    assert_eq!(data["kurum_adi"], "Danıştay");
+    assert_eq!(data["content"]["icerik"], "İdari işlemin iptali");
+
+    // Aynı slug başka corpus'ta aranmaz
+    let (status, body) = status_and_body(test::call_service(&app, get("/api/v1/corpora/mevzuat/documents/onuncu-daire-karari")).await).await;
+    assert_eq!(status, 404);
+    assert_eq!(body["error"]["code"], "DOCUMENT_NOT_FOUND");
+
+    let (status, body) = status_and_body(test::call_service(&app, get("/api/v1/corpora/aym/documents")).await).await;
+    assert_eq!(status, 404);
+    assert_eq!(body["error"]["code"], "CORPUS_NOT_FOUND");
+}
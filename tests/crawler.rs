@@ -0,0 +1,288 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use actix_web::http::header::USER_AGENT;
+use actix_web::{dev::ServerHandle, test, web, App, HttpRequest, HttpResponse, HttpServer};
+use api_mevzuatgpt::scraping::{BreakerState, CrawlConfig, Crawler, FetchConfig, Fetcher};
+use reqwest::header::HeaderMap;
+use url::Url;
+
+#[macro_use]
+mod common;
+use common::{fixture, state, status_and_body, with_key};
+
+// Nazik tarama: robots.txt, User-Agent, yeniden deneme, devre kesici ve host başına eşzamanlılık
+
+#[derive(Default)]
+struct Origin {
+    robots: Option<String>,
+    // Sıradaki cevapların durum kodları ve başlıkları; boşsa 200
+    responses: VecDeque<(u16, Vec<(&'static str, String)>)>,
+    // Her isteğin (yol, User-Agent) bilgisi
+    requests: Vec<(String, String)>,
+    in_flight: usize,
+    max_in_flight: usize,
+}
+
+async fn robots(origin: web::Data<Mutex<Origin>>) -> HttpResponse {
+    match origin.lock().unwrap().robots.clone() {
+        Some(text) => HttpResponse::Ok().content_type("text/plain").body(text),
+        None => HttpResponse::NotFound().finish(),
+    }
+}
+
+async fn page(req: HttpRequest, origin: web::Data<Mutex<Origin>>) -> HttpResponse {
+    let status = {
+        let mut origin = origin.lock().unwrap();
+        let agent = req
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        origin.requests.push((req.path().to_string(), agent));
+        origin.in_flight += 1;
+        origin.max_in_flight = origin.max_in_flight.max(origin.in_flight);
+        origin.responses.pop_front().unwrap_or((200, Vec::new()))
+    };
+    actix_web::rt::time::sleep(Duration::from_millis(30)).await;
+    origin.lock().unwrap().in_flight -= 1;
+    let (status, headers) = status;
+    let mut response = HttpResponse::build(actix_web::http::StatusCode::from_u16(status).unwrap());
+    for header in headers {
+        response.insert_header(header);
+    }
+    response.body("duyurular")
+}
+
+struct Site {
+    origin: Arc<Mutex<Origin>>,
+    base: Url,
+    handle: ServerHandle,
+}
+
+impl Site {
+    async fn start(robots_txt: Option<&str>) -> Self {
+        let origin = Arc::new(Mutex::new(Origin {
+            robots: robots_txt.map(str::to_string),
+            ..Origin::default()
+        }));
+        let data = web::Data::from(origin.clone());
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(data.clone())
+                .route("/robots.txt", web::get().to(robots))
+                .default_service(web::get().to(page))
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let base = Url::parse(&format!("http://{}/", server.addrs()[0])).unwrap();
+        let server = server.run();
+        let handle = server.handle();
+        actix_web::rt::spawn(server);
+        Site { origin, base, handle }
+    }
+
+    fn url(&self, path: &str) -> Url {
+        self.base.join(path).unwrap()
+    }
+
+    fn fail_next(&self, statuses: &[u16]) {
+        self.origin.lock().unwrap().responses.extend(statuses.iter().map(|status| (*status, Vec::new())));
+    }
+
+    fn respond_next(&self, status: u16, headers: Vec<(&'static str, String)>) {
+        self.origin.lock().unwrap().responses.push_back((status, headers));
+    }
+
+    fn requests(&self) -> Vec<(String, String)> {
+        self.origin.lock().unwrap().requests.clone()
+    }
+}
+
+fn config() -> CrawlConfig {
+    CrawlConfig {
+        backoff: Duration::from_millis(10),
+        ..CrawlConfig::default()
+    }
+}
+
+#[actix_web::test]
+async fn robots_rules_and_user_agent_are_honored() {
+    let site = Site::start(Some(
+        "User-agent: *\nDisallow: /\n\nUser-agent: MevzuatGPTBot\nAllow: /duyurular\nDisallow: /ozel\n",
+    ))
+    .await;
+    let crawler = Crawler::new(config());
+
+    let response = crawler.get(&site.url("/duyurular"), HeaderMap::new()).await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "duyurular");
+    let error = crawler.get(&site.url("/ozel/liste"), HeaderMap::new()).await.unwrap_err();
+    assert!(error.contains("robots.txt"), "{}", error);
+
+    // Yasaklı sayfaya istek gitmez; istek botun adı ve iletişim adresiyle gönderilir
+    let requests = site.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0], ("/duyurular".to_string(), "MevzuatGPTBot/1.0 (+https://mevzuatgpt.org)".to_string()));
+    site.handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn transient_errors_are_retried() {
+    let site = Site::start(None).await;
+    site.fail_next(&[503, 429]);
+    let crawler = Crawler::new(config());
+
+    let response = crawler.get(&site.url("/duyurular"), HeaderMap::new()).await.unwrap();
+    assert!(response.status().is_success());
+    assert_eq!(site.requests().len(), 3);
+
+    // 404 yeniden denenmez ve devreyi etkilemez
+    site.fail_next(&[404]);
+    let error = crawler.get(&site.url("/yok"), HeaderMap::new()).await.unwrap_err();
+    assert!(error.contains("404"), "{}", error);
+    assert_eq!(site.requests().len(), 4);
+    assert_eq!(crawler.hosts()[0].consecutive_failures, 0);
+    site.handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn retry_after_is_honored() {
+    let site = Site::start(None).await;
+    site.respond_next(429, vec![("Retry-After", "1".to_string())]);
+    let crawler = Crawler::new(config());
+
+    let started = Instant::now();
+    let response = crawler.get(&site.url("/duyurular"), HeaderMap::new()).await.unwrap();
+    assert!(response.status().is_success());
+    assert!(started.elapsed() >= Duration::from_secs(1), "{:?}", started.elapsed());
+
+    // Geçmiş HTTP tarihi beklemesiz yeniden denenir
+    site.respond_next(503, vec![("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT".to_string())]);
+    let started = Instant::now();
+    assert!(crawler.get(&site.url("/duyurular"), HeaderMap::new()).await.is_ok());
+    assert!(started.elapsed() < Duration::from_secs(1), "{:?}", started.elapsed());
+    assert_eq!(site.requests().len(), 4);
+    site.handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn redirects_are_followed_through_the_crawler() {
+    let site = Site::start(Some("User-agent: *\nDisallow: /ozel\n")).await;
+    let crawler = Crawler::new(config());
+
+    site.respond_next(301, vec![("Location", "/duyurular/yeni".to_string())]);
+    let response = crawler.get(&site.url("/duyurular"), HeaderMap::new()).await.unwrap();
+    assert_eq!(response.url().path(), "/duyurular/yeni");
+    assert_eq!(crawler.hosts()[0].requests, 2);
+
+    // Yönlendirme hedefi de robots.txt'ye tabidir; yasaklı adrese istek gitmez
+    site.respond_next(302, vec![("Location", "/ozel/liste".to_string())]);
+    let error = crawler.get(&site.url("/duyurular"), HeaderMap::new()).await.unwrap_err();
+    assert!(error.contains("robots.txt"), "{}", error);
+    let paths: Vec<String> = site.requests().into_iter().map(|(path, _)| path).collect();
+    assert_eq!(paths, ["/duyurular", "/duyurular/yeni", "/duyurular"]);
+
+    // Döngüye giren yönlendirmeler sınırlıdır
+    for _ in 0..=10 {
+        site.respond_next(302, vec![("Location", "/duyurular".to_string())]);
+    }
+    let error = crawler.get(&site.url("/duyurular"), HeaderMap::new()).await.unwrap_err();
+    assert!(error.contains("yönlendirme"), "{}", error);
+    site.handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn repeated_failures_open_the_circuit_breaker() {
+    let site = Site::start(None).await;
+    site.fail_next(&[500, 500, 500]);
+    let fetcher = Fetcher::new(FetchConfig {
+        crawl: CrawlConfig {
+            max_retries: 0,
+            breaker_threshold: 2,
+            ..config()
+        },
+        ..FetchConfig::default()
+    });
+
+    for _ in 0..2 {
+        assert!(fetcher.fetch(&site.url("/duyurular")).await.is_err());
+    }
+    // Devre açık: host'a istek gönderilmeden hata döner
+    let error = fetcher.fetch(&site.url("/duyurular")).await.unwrap_err();
+    assert!(error.contains("geçici olarak taranmıyor"), "{}", error);
+    assert_eq!(site.requests().len(), 2);
+    site.handle.stop(false).await;
+
+    let f = fixture().await;
+    let mut state = state(f.repos.clone());
+    state.fetcher = fetcher;
+    let app = app!(state);
+
+    let req = test::TestRequest::get().uri("/api/admin/crawler/hosts").to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 401);
+
+    let req = with_key(test::TestRequest::get().uri("/api/admin/crawler/hosts"), &f.admin_key).to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["count"], 1);
+    let host = &body["data"][0];
+    assert_eq!(host["host"], format!("127.0.0.1:{}", site.base.port().unwrap()));
+    assert_eq!(host["state"], "open");
+    assert_eq!(host["consecutive_failures"], 2);
+    assert_eq!(host["requests"], 2);
+    assert!(host["open_until"].is_string());
+    assert!(host["last_error"].as_str().unwrap().contains("500"));
+}
+
+#[actix_web::test]
+async fn half_open_breaker_lets_a_single_probe_through() {
+    let site = Site::start(None).await;
+    site.fail_next(&[500]);
+    let crawler = Crawler::new(CrawlConfig {
+        max_retries: 0,
+        breaker_threshold: 1,
+        breaker_cooldown: Duration::from_millis(100),
+        ..config()
+    });
+    let url = site.url("/duyurular");
+    assert!(crawler.get(&url, HeaderMap::new()).await.is_err());
+    assert_eq!(crawler.hosts()[0].state, BreakerState::Open);
+
+    // Cooldown sonrası eşzamanlı isteklerden yalnızca biri host'u yoklar
+    actix_web::rt::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(crawler.hosts()[0].state, BreakerState::HalfOpen);
+    let results = futures::future::join_all((0..3).map(|_| crawler.get(&url, HeaderMap::new()))).await;
+    assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+    for error in results.iter().filter_map(|result| result.as_ref().err()) {
+        assert!(error.contains("geçici olarak taranmıyor"), "{}", error);
+    }
+    assert_eq!(site.requests().len(), 2);
+
+    // Başarılı yoklama devreyi kapatır
+    assert_eq!(crawler.hosts()[0].state, BreakerState::Closed);
+    assert!(crawler.get(&url, HeaderMap::new()).await.is_ok());
+    site.handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn concurrent_requests_per_host_are_limited() {
+    let site = Site::start(None).await;
+    let crawler = Crawler::new(CrawlConfig {
+        host_concurrency: 1,
+        ..config()
+    });
+
+    let urls: Vec<Url> = (0..4).map(|page| site.url(&format!("/duyurular?sayfa={}", page))).collect();
+    let results = futures::future::join_all(urls.iter().map(|url| crawler.get(url, HeaderMap::new()))).await;
+    assert!(results.iter().all(Result::is_ok));
+
+    let (requests, max_in_flight) = {
+        let origin = site.origin.lock().unwrap();
+        (origin.requests.len(), origin.max_in_flight)
+    };
+    assert_eq!(requests, 4);
+    assert_eq!(max_in_flight, 1);
+    site.handle.stop(false).await;
+}
@@ -0,0 +1,68 @@
+use api_mevzuatgpt::dates::{self, DateError};
+use chrono::NaiveDate;
+
+// Türkçe tarih okuma: sayısal, ISO ve ay adlı biçimler; okunamayan değerler için açık hata
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn dates_in_free_text_are_found() {
+    let cases = [
+        ("Yayın tarihi: 01.09.2025", Some(date(2025, 9, 1))),
+        ("15/08/25", Some(date(2025, 8, 15))),
+        ("1.2.2024 tarihinde", Some(date(2024, 2, 1))),
+        ("2025-08-04T10:00:00", Some(date(2025, 8, 4))),
+        ("23 Eylül 2025", Some(date(2025, 9, 23))),
+        ("3 Mart 2025 Pazartesi", Some(date(2025, 3, 3))),
+        ("11 Ağu 2025", Some(date(2025, 8, 11))),
+        ("27 Haz 2025", Some(date(2025, 6, 27))),
+        ("12 Mar. 2024", Some(date(2024, 3, 12))),
+        ("5 MAYIS 2024", Some(date(2024, 5, 5))),
+        ("30 KASIM 2023", Some(date(2023, 11, 30))),
+        ("1 Subat 2024", Some(date(2024, 2, 1))),
+        ("20 EKİM 2024", Some(date(2024, 10, 20))),
+        // Ay adından önceki ilgisiz sayılar tarihe karışmaz
+        ("2. Duyuru: 12 Mart 2024", Some(date(2024, 3, 12))),
+        ("Sayı 45 - 7 Aralık 2023", Some(date(2023, 12, 7))),
+        // Metinde ilk geçen tarih döner
+        ("Güncelleme 2024-05-02, ilk yayın 01.01.2024", Some(date(2024, 5, 2))),
+        ("31.02.2025", None),
+        ("12 Martta 2024", None),
+        ("tarih yok", None),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(dates::find(text), expected, "{}", text);
+    }
+}
+
+#[test]
+fn whole_values_are_parsed_or_rejected_explicitly() {
+    assert_eq!(dates::parse("12 Mart 2024"), Ok(date(2024, 3, 12)));
+    assert_eq!(dates::parse(" 12 Mar 2024 "), Ok(date(2024, 3, 12)));
+    assert_eq!(dates::parse("2024-03-12T08:30:00+03:00"), Ok(date(2024, 3, 12)));
+    assert_eq!(dates::parse("12.03.2024 14:30"), Ok(date(2024, 3, 12)));
+    assert_eq!(dates::parse("12 Mart 2024 Salı"), Ok(date(2024, 3, 12)));
+
+    assert_eq!(dates::parse(""), Err(DateError::Empty));
+    assert!(matches!(dates::parse("Mart ayında"), Err(DateError::Unrecognized(_))));
+    assert!(matches!(dates::parse("Yayın: 12.03.2024"), Err(DateError::Unrecognized(_))));
+    assert!(matches!(dates::parse("12.03.2024 tarihli karar"), Err(DateError::Unrecognized(_))));
+    assert_eq!(dates::parse("30.02.2024"), Err(DateError::Invalid("30.02.2024".to_string())));
+}
+
+#[test]
+fn formats_and_timestamps() {
+    assert_eq!(dates::parse_with_format("3 Mart 2025", "%d %B %Y"), Ok(date(2025, 3, 3)));
+    assert_eq!(dates::parse_with_format("27 Şub 2025", "%d %b %Y"), Ok(date(2025, 2, 27)));
+    assert_eq!(dates::parse_with_format("AĞUSTOS 11, 2025", "%B %d, %Y"), Ok(date(2025, 8, 11)));
+    assert!(dates::parse_with_format("27 Şub 2025", "%d.%m.%Y").is_err());
+
+    assert_eq!(dates::to_rfc3339("2024-03-02T10:00:00Z").as_deref(), Ok("2024-03-02T10:00:00Z"));
+    assert_eq!(dates::to_rfc3339("2024-03-02T13:00:00+03:00").as_deref(), Ok("2024-03-02T10:00:00Z"));
+    assert_eq!(dates::to_rfc3339("01.01.2010").as_deref(), Ok("2010-01-01T00:00:00Z"));
+    assert_eq!(dates::to_rfc3339("1 Ocak 2010").as_deref(), Ok("2010-01-01T00:00:00Z"));
+    assert!(dates::to_rfc3339("bilinmiyor").is_err());
+    assert_eq!(dates::day_month_year(date(2024, 3, 5)), "05.03.2024");
+}
@@ -0,0 +1,221 @@
+use std::sync::Arc;
+use actix_web::{body::MessageBody, dev::ServiceResponse, test};
+use api_mevzuatgpt::corpus::YARGITAY_KURUM_ID;
+use api_mevzuatgpt::repository::{MemoryRepository, Repositories, ScrapedAnnouncementWrite};
+use mongodb::bson::{doc, oid::ObjectId};
+
+#[macro_use]
+mod common;
+use common::state;
+
+// RSS/Atom beslemeleri: içerik, kalıcı kimlikler, tarihler ve koşullu GET
+
+struct Fixture {
+    store: Arc<MemoryRepository>,
+    repos: Repositories,
+    genelge_id: ObjectId,
+}
+
+async fn seed() -> Fixture {
+    let store = Arc::new(MemoryRepository::new());
+    let repos = Repositories::memory(store.clone());
+
+    let sgk_id = ObjectId::new();
+    store.insert("kurumlar", doc! { "_id": sgk_id, "kurum_adi": "Sosyal Güvenlik Kurumu" });
+    let adalet_id = ObjectId::new();
+    store.insert("kurumlar", doc! { "_id": adalet_id, "kurum_adi": "Adalet Bakanlığı" });
+
+    let genelge_id = ObjectId::new();
+    store.insert("metadata", doc! {
+        "_id": genelge_id,
+        "kurum_id": sgk_id.to_hex(),
+        "pdf_adi": "Emeklilik & Prim <Genelgesi>",
+        "aciklama": "Emeklilik işlemleri hakkında genelge",
+        "belge_turu": "Genelge",
+        "url_slug": "emeklilik-genelgesi",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-03-01",
+    });
+    store.insert("metadata", doc! {
+        "kurum_id": sgk_id.to_hex(),
+        "pdf_adi": "Prim Yönetmeliği",
+        "aciklama": "",
+        "belge_turu": "Yönetmelik",
+        "url_slug": "prim-yonetmeligi",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-05-10T08:30:00Z",
+    });
+    store.insert("metadata", doc! {
+        "kurum_id": adalet_id.to_hex(),
+        "pdf_adi": "Arabuluculuk Yönetmeliği",
+        "url_slug": "arabuluculuk-yonetmeligi",
+        "status": "aktif",
+        "olusturulma_tarihi": "bilinmiyor",
+    });
+    store.insert("yargitay", doc! {
+        "kurum_id": YARGITAY_KURUM_ID,
+        "pdf_adi": "9. Hukuk Dairesi Kararı",
+        "url_slug": "9-hd-karar",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-01-15",
+    });
+
+    repos
+        .scraped_announcements
+        .store(&[
+            ScrapedAnnouncementWrite {
+                kurum_id: sgk_id.to_hex(),
+                baslik: "Prim affı başvuruları".to_string(),
+                link: "https://www.sgk.gov.tr/Duyuru/Detay/42".to_string(),
+                tarih: Some("12.03.2024".to_string()),
+                first_seen_at: "2024-03-13T08:00:00.000Z".to_string(),
+            },
+            ScrapedAnnouncementWrite {
+                kurum_id: adalet_id.to_hex(),
+                baslik: "Sınav duyurusu".to_string(),
+                link: "https://www.adalet.gov.tr/duyuru/7".to_string(),
+                tarih: None,
+                first_seen_at: "2024-04-02T06:15:00.000Z".to_string(),
+            },
+        ])
+        .await
+        .unwrap();
+
+    Fixture { store, repos, genelge_id }
+}
+
+fn header<B>(resp: &ServiceResponse<B>, name: &str) -> String {
+    resp.headers().get(name).unwrap().to_str().unwrap().to_string()
+}
+
+async fn body_text<B: MessageBody>(resp: ServiceResponse<B>) -> String {
+    String::from_utf8(test::read_body(resp).await.to_vec()).unwrap()
+}
+
+#[actix_web::test]
+async fn regulation_feeds_render_rss_and_atom() {
+    let f = seed().await;
+    let app = app!(state(f.repos));
+
+    let req = test::TestRequest::get().uri("/feeds/regulations.xml").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(header(&resp, "content-type"), "application/rss+xml; charset=utf-8");
+    assert_eq!(header(&resp, "last-modified"), "Fri, 10 May 2024 08:30:00 GMT");
+    let rss = body_text(resp).await;
+    assert!(rss.contains("<atom:link href=\"https://mevzuatgpt.org/feeds/regulations.xml\" rel=\"self\""));
+    assert!(rss.contains("<title>Emeklilik &amp; Prim &lt;Genelgesi&gt;</title>"));
+    assert!(rss.contains("<link>https://mevzuatgpt.org/belge/emeklilik-genelgesi</link>"));
+    // Kimlik slug'dan değil kayıt id'sinden üretilir
+    assert!(rss.contains(&format!(
+        "<guid isPermaLink=\"false\">tag:mevzuatgpt.org,2024:mevzuat/{}</guid>",
+        f.genelge_id.to_hex()
+    )));
+    assert!(rss.contains("<pubDate>Fri, 1 Mar 2024 00:00:00 +0000</pubDate>"));
+    assert!(rss.contains("<category>Sosyal Güvenlik Kurumu</category>"));
+    assert!(rss.contains("<lastBuildDate>Fri, 10 May 2024 08:30:00 +0000</lastBuildDate>"));
+    // Okunamayan tarih için pubDate yazılmaz; boş açıklama description olmaz
+    let arabuluculuk = &rss[rss.find("Arabuluculuk").unwrap()..];
+    let arabuluculuk = &arabuluculuk[..arabuluculuk.find("</item>").unwrap()];
+    assert!(!arabuluculuk.contains("<pubDate>"));
+    assert_eq!(rss.matches("<description>").count(), 2);
+
+    let req = test::TestRequest::get().uri("/feeds/yargitay.atom").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(header(&resp, "content-type"), "application/atom+xml; charset=utf-8");
+    let atom = body_text(resp).await;
+    assert!(atom.contains("<id>tag:mevzuatgpt.org,2024:feeds/yargitay</id>"));
+    assert!(atom.contains("<link href=\"https://mevzuatgpt.org/yargitay/9-hd-karar\" rel=\"alternate\"/>"));
+    assert!(atom.contains("<published>2024-01-15T00:00:00Z</published>"));
+    assert!(!atom.contains("Emeklilik"));
+
+    // Bilinmeyen biçim 404
+    let req = test::TestRequest::get().uri("/feeds/regulations.json").to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 404);
+}
+
+#[actix_web::test]
+async fn institution_and_announcement_feeds() {
+    let f = seed().await;
+    let app = app!(state(f.repos));
+
+    let req = test::TestRequest::get().uri("/feeds/kurum/sosyal-guvenlik-kurumu.xml").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status().as_u16(), 200);
+    let rss = body_text(resp).await;
+    assert_eq!(rss.matches("<item>").count(), 2);
+    assert!(!rss.contains("Arabuluculuk"));
+    // En yeni belge önce
+    assert!(rss.find("Prim Yönetmeliği").unwrap() < rss.find("Emeklilik").unwrap());
+
+    let req = test::TestRequest::get().uri("/feeds/kurum/olmayan-kurum.xml").to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 404);
+
+    let req = test::TestRequest::get().uri("/feeds/kurum/sosyal-guvenlik-kurumu/duyurular.atom").to_request();
+    let atom = body_text(test::call_service(&app, req).await).await;
+    assert!(atom.contains("<title>Prim affı başvuruları</title>"));
+    assert!(atom.contains("<link href=\"https://www.sgk.gov.tr/Duyuru/Detay/42\" rel=\"alternate\"/>"));
+    // Duyurunun kendi tarihi ilk görülme zamanından önce gelir
+    assert!(atom.contains("<published>2024-03-12T00:00:00Z</published>"));
+    assert!(!atom.contains("Sınav duyurusu"));
+
+    let req = test::TestRequest::get().uri("/feeds/duyurular.xml").to_request();
+    let rss = body_text(test::call_service(&app, req).await).await;
+    assert_eq!(rss.matches("<item>").count(), 2);
+    // Tarihi olmayan duyuruda ilk görülme zamanı kullanılır
+    assert!(rss.contains("<pubDate>Tue, 2 Apr 2024 06:15:00 +0000</pubDate>"));
+    assert!(rss.contains("<category>Adalet Bakanlığı</category>"));
+    assert_eq!(f.store.documents("kurum_duyuru_items").len(), 2);
+}
+
+#[actix_web::test]
+async fn conditional_requests_get_not_modified() {
+    let f = seed().await;
+    let app = app!(state(f.repos));
+
+    let req = test::TestRequest::get().uri("/feeds/regulations.xml").to_request();
+    let resp = test::call_service(&app, req).await;
+    let etag = header(&resp, "etag");
+    let last_modified = header(&resp, "last-modified");
+    assert_eq!(header(&resp, "cache-control"), "public, max-age=300");
+
+    let req = test::TestRequest::get()
+        .uri("/feeds/regulations.xml")
+        .insert_header(("If-None-Match", etag.as_str()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status().as_u16(), 304);
+    assert_eq!(header(&resp, "etag"), etag);
+    assert!(body_text(resp).await.is_empty());
+
+    let req = test::TestRequest::get()
+        .uri("/feeds/regulations.xml")
+        .insert_header(("If-Modified-Since", last_modified.as_str()))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 304);
+
+    // Atom çıktısının etiketi farklıdır
+    let req = test::TestRequest::get()
+        .uri("/feeds/regulations.atom")
+        .insert_header(("If-None-Match", etag.as_str()))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 200);
+
+    // Yeni belge eklenince besleme değişir
+    f.store.insert("metadata", doc! {
+        "pdf_adi": "Yeni Tebliğ",
+        "url_slug": "yeni-teblig",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-06-01",
+    });
+    let req = test::TestRequest::get()
+        .uri("/feeds/regulations.xml")
+        .insert_header(("If-None-Match", etag.as_str()))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 200);
+    let req = test::TestRequest::get()
+        .uri("/feeds/regulations.xml")
+        .insert_header(("If-Modified-Since", last_modified.as_str()))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 200);
+}
@@ -0,0 +1,270 @@
+use std::sync::Arc;
+use actix_web::test;
+use api_mevzuatgpt::config::AppState;
+use api_mevzuatgpt::corpus::YARGITAY_KURUM_ID;
+use api_mevzuatgpt::error::ErrorFormat;
+use api_mevzuatgpt::repository::MemoryRepository;
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::Value;
+
+#[macro_use]
+mod common;
+use common::memory_state;
+
+// Bellek içi repository ile uçtan uca handler testleri
+
+fn seed() -> Arc<MemoryRepository> {
+    let store = Arc::new(MemoryRepository::new());
+
+    let sgk_id = ObjectId::new();
+    store.insert("kurumlar", doc! {
+        "_id": sgk_id,
+        "kurum_adi": "Sosyal Güvenlik Kurumu",
+        "kurum_logo": "sgk.png",
+        "aciklama": "SGK",
+    });
+    // Eski kayıtlarda camelCase alan adları kullanılıyor
+    store.insert("kurumlar", doc! {
+        "kurumAdi": "Yargıtay",
+        "kurumLogo": "yargitay.png",
+    });
+
+    let genelge_id = ObjectId::new();
+    store.insert("metadata", doc! {
+        "_id": genelge_id,
+        "kurum_id": sgk_id.to_hex(),
+        "pdf_adi": "Emeklilik Genelgesi",
+        "aciklama": "Emeklilik işlemleri hakkında genelge",
+        "belge_turu": "Genelge",
+        "etiketler": "emeklilik, sigorta",
+        "url_slug": "emeklilik-genelgesi",
+        "status": "aktif",
+        "icerik_text": "Emeklilik yaşı ve prim günü şartları",
+        "sayfa_sayisi": 12,
+        "olusturulma_tarihi": "2024-03-01",
+    });
+    store.insert("metadata", doc! {
+        "kurum_id": sgk_id,
+        "pdf_adi": "Prim Yönetmeliği",
+        "aciklama": "Prim tahsilatı",
+        "belge_turu": "",
+        "etiketler": "prim",
+        "url_slug": "prim-yonetmeligi",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-05-10",
+    });
+    store.insert("content", doc! {
+        "metadata_id": genelge_id,
+        "content": "Genelgenin tam metni",
+        "created_at": "2024-03-02T10:00:00Z",
+    });
+
+    store.insert("yargitay", doc! {
+        "kurum_id": YARGITAY_KURUM_ID,
+        "pdf_adi": "9. Hukuk Dairesi Kararı",
+        "url_slug": "9-hd-karar",
+        "status": "aktif",
+        "kararTarihi": "2023-11-20",
+        "esasNo": "2023/100",
+        "text": "Kıdem tazminatı talebi",
+        "icerik_text": "Kıdem tazminatı talebi",
+        "olusturulma_tarihi": "2024-01-15",
+    });
+
+    store
+}
+
+fn state(store: Arc<MemoryRepository>, error_format: ErrorFormat) -> AppState {
+    let mut state = memory_state(store);
+    state.error_format = error_format;
+    state
+}
+
+#[actix_web::test]
+async fn documents_list_sorted_and_filtered() {
+    let app = app!(state(seed(), ErrorFormat::Legacy));
+
+    let req = test::TestRequest::get().uri("/api/v1/documents").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["success"], true);
+    assert_eq!(body["count"], 2);
+    assert_eq!(body["data"][0]["url_slug"], "prim-yonetmeligi");
+    assert_eq!(body["data"][1]["sayfa_sayisi"], 12);
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/documents?etiketler=sigorta&sort_order=asc")
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["data"][0]["pdf_adi"], "Emeklilik Genelgesi");
+}
+
+#[actix_web::test]
+async fn document_detail_joins_institution_and_content() {
+    let app = app!(state(seed(), ErrorFormat::Legacy));
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/documents/emeklilik-genelgesi")
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["success"], true);
+    assert_eq!(body["data"]["kurum_adi"], "Sosyal Güvenlik Kurumu");
+    assert_eq!(body["data"]["content"]["icerik"], "Genelgenin tam metni");
+    assert_eq!(body["data"]["content"]["olusturulma_tarihi"], "2024-03-02T10:00:00Z");
+
+    let req = test::TestRequest::get().uri("/api/v1/documents/yok").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[actix_web::test]
+async fn yargitay_detail_reads_embedded_content() {
+    let app = app!(state(seed(), ErrorFormat::Legacy));
+
+    let req = test::TestRequest::get().uri("/api/v2/documents/9-hd-karar").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["data"]["content"]["icerik"], "Kıdem tazminatı talebi");
+    assert_eq!(body["data"]["metadata"]["kararTarihi"], "2023-11-20");
+
+    let req = test::TestRequest::get().uri("/api/v2/regulations/recent").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["data"][0]["esasNo"], "2023/100");
+}
+
+#[actix_web::test]
+async fn search_scores_and_counts_matches() {
+    let app = app!(state(seed(), ErrorFormat::Legacy));
+
+    let req = test::TestRequest::get().uri("/api/v1/search?q=emeklilik").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("x-total-count").unwrap(), "1");
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["data"][0]["url_slug"], "emeklilik-genelgesi");
+    assert_eq!(body["data"][0]["kurum_adi"], "Sosyal Güvenlik Kurumu");
+    assert_eq!(body["data"][0]["match_type"], "title,content");
+
+    let req = test::TestRequest::get().uri("/api/v1/search?q=%20").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[actix_web::test]
+async fn institutions_tolerate_legacy_field_names() {
+    let app = app!(state(seed(), ErrorFormat::Legacy));
+
+    let req = test::TestRequest::get().uri("/api/v1/institutions").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["count"], 2);
+    let names: Vec<&str> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|kurum| kurum["kurum_adi"].as_str())
+        .collect();
+    assert!(names.contains(&"Yargıtay"));
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/institutions/slug/sosyal-guvenlik-kurumu")
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["success"], true);
+
+    let req = test::TestRequest::get().uri("/api/v1/institutions/slug/yok").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[actix_web::test]
+async fn statistics_and_filters() {
+    let app = app!(state(seed(), ErrorFormat::Legacy));
+
+    let req = test::TestRequest::get().uri("/api/v1/statistics").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["data"]["total_kurumlar"], 2);
+    assert_eq!(body["data"]["total_belgeler"], 2);
+
+    let req = test::TestRequest::get().uri("/api/v1/documents/filters").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["data"]["belge_turu"], serde_json::json!(["Belirtilmemiş", "Genelge"]));
+    assert_eq!(body["data"]["etiketler"], serde_json::json!(["emeklilik", "prim", "sigorta"]));
+}
+
+#[actix_web::test]
+async fn corpus_routes_and_health() {
+    let app = app!(state(seed(), ErrorFormat::Legacy));
+
+    let req = test::TestRequest::get().uri("/api/v1/corpora/yargitay/sitemap").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["count"], 1);
+
+    let req = test::TestRequest::get().uri("/api/v1/corpora/bilinmeyen/documents").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+
+    let req = test::TestRequest::get().uri("/api/health").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["success"], true);
+}
+
+#[actix_web::test]
+async fn errors_use_configured_envelope() {
+    let app = app!(state(seed(), ErrorFormat::V2));
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/documents/yok")
+        .insert_header(("X-Request-Id", "test-123"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    assert_eq!(resp.headers().get("x-request-id").unwrap(), "test-123");
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["success"], false);
+    assert_eq!(body["error"]["code"], "DOCUMENT_NOT_FOUND");
+    assert_eq!(body["error"]["message"], "Belge bulunamadı");
+    assert_eq!(body["error"]["request_id"], "test-123");
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/links")
+        .insert_header(("Accept-Language", "en-US,en;q=0.9"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"]["code"], "INVALID_QUERY_STRING");
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/links?kurum_id=abc")
+        .insert_header(("Accept-Language", "en"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["error"]["code"], "INVALID_PARAMETER");
+    assert_eq!(body["error"]["message"], "Invalid kurum_id format");
+
+    let req = test::TestRequest::get().uri("/api/v9/yok").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["error"]["code"], "ROUTE_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn legacy_error_format_keeps_flat_fields() {
+    let app = app!(state(seed(), ErrorFormat::Legacy));
+
+    let req = test::TestRequest::get().uri("/api/v1/search?q=%20").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+    assert!(resp.headers().contains_key("x-request-id"));
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["success"], false);
+    assert_eq!(body["message"], "Arama sorgusu boş olamaz");
+    assert_eq!(body["error"], "Arama sorgusu boş olamaz");
+    assert_eq!(body["code"], "EMPTY_SEARCH_QUERY");
+    assert_eq!(body["data"], serde_json::json!([]));
+
+    // İstek bazında v2 formatı seçilebilir
+    let req = test::TestRequest::get()
+        .uri("/api/v1/corpora/bilinmeyen/documents")
+        .insert_header(("X-Error-Format", "v2"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["error"]["code"], "CORPUS_NOT_FOUND");
+}
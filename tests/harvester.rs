@@ -0,0 +1,126 @@
+use std::sync::Arc;
+use actix_web::test;
+use api_mevzuatgpt::harvester::{self, HarvestReport};
+use api_mevzuatgpt::models::kurum_duyuru_scraped::DuyuruItem;
+use api_mevzuatgpt::repository::{
+    AnnouncementSourceRecord, MemoryRepository, Repositories, ScrapedAnnouncementFilter, ScrapedAnnouncementWrite,
+};
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::Value;
+
+#[macro_use]
+mod common;
+use common::{memory_state, status_and_body};
+
+// Duyuru hasadı: tekilleştirme, hata toleransı ve /api/v1/kurum-duyuru'nun depodan okuması
+
+fn item(baslik: &str, link: &str) -> DuyuruItem {
+    DuyuruItem {
+        baslik: baslik.to_string(),
+        link: link.to_string(),
+        tarih: Some("01.03.2024".to_string()),
+    }
+}
+
+// Kaynağın URL'sine göre sabit sayfa döndüren scraper
+async fn fake_scrape(source: AnnouncementSourceRecord) -> Result<Vec<DuyuruItem>, String> {
+    match source.duyuru_linki.as_str() {
+        "https://www.sgk.gov.tr/Duyuru" => Ok(vec![
+            item("Prim borcu yapılandırması", "https://www.sgk.gov.tr/Duyuru/3"),
+            item("E-bildirge bakım çalışması", "https://www.sgk.gov.tr/Duyuru/2"),
+            item("", ""),
+        ]),
+        "https://www.iskur.gov.tr/duyurular" => Ok(vec![
+            item("İşgücü uyum programı", "https://www.iskur.gov.tr/duyuru/7"),
+            // Başka kurumun sayfasında da yayımlanan duyuru
+            item("E-bildirge bakım çalışması", "https://www.sgk.gov.tr/Duyuru/2"),
+        ]),
+        _ => Err("bağlantı zaman aşımına uğradı".to_string()),
+    }
+}
+
+fn store_with_sources() -> (Arc<MemoryRepository>, String, String) {
+    let store = Arc::new(MemoryRepository::new());
+    let sgk = ObjectId::new().to_hex();
+    let iskur = ObjectId::new().to_hex();
+    store.insert("kurum_duyuru", doc! { "kurum_id": &sgk, "duyuru_linki": "https://www.sgk.gov.tr/Duyuru" });
+    store.insert("kurum_duyuru", doc! { "kurum_id": &iskur, "duyuru_linki": "https://www.iskur.gov.tr/duyurular" });
+    store.insert("kurum_duyuru", doc! { "kurum_id": ObjectId::new().to_hex(), "duyuru_linki": "https://erisilemez.gov.tr" });
+    store.insert("kurum_duyuru", doc! { "kurum_id": ObjectId::new().to_hex(), "duyuru_linki": "" });
+    (store, sgk, iskur)
+}
+
+fn links(body: &Value) -> Vec<&str> {
+    body["data"].as_array().unwrap().iter().map(|item| item["link"].as_str().unwrap()).collect()
+}
+
+#[actix_web::test]
+async fn harvest_deduplicates_by_link_and_tolerates_failures() {
+    let (store, sgk, iskur) = store_with_sources();
+    let repos = Repositories::memory(store.clone());
+
+    let report = harvester::harvest_all(&repos, &fake_scrape).await.unwrap();
+    assert_eq!(report, HarvestReport { sources: 3, failed: 1, stored: 3 });
+
+    // İkinci turda yeni duyuru yok
+    let report = harvester::harvest_all(&repos, &fake_scrape).await.unwrap();
+    assert_eq!(report, HarvestReport { sources: 3, failed: 1, stored: 0 });
+
+    let items = store.documents("kurum_duyuru_items");
+    assert_eq!(items.len(), 3);
+    // Aynı link ilk kaydeden kuruma ait kalır (kaynaklar en yeni önce taranır)
+    let count = |kurum_id: &str| ScrapedAnnouncementFilter {
+        kurum_id: Some(kurum_id.to_string()),
+        ..ScrapedAnnouncementFilter::default()
+    };
+    assert_eq!(repos.scraped_announcements.count(&count(&iskur)).await.unwrap(), 2);
+    assert_eq!(repos.scraped_announcements.count(&count(&sgk)).await.unwrap(), 1);
+}
+
+#[actix_web::test]
+async fn endpoint_reads_stored_announcements_with_pagination_and_since() {
+    let (store, sgk, _) = store_with_sources();
+    let repos = Repositories::memory(store.clone());
+    let write = |link: &str, first_seen_at: &str| ScrapedAnnouncementWrite {
+        kurum_id: sgk.clone(),
+        baslik: format!("Duyuru {}", link),
+        link: format!("https://www.sgk.gov.tr/Duyuru/{}", link),
+        tarih: Some("01.03.2024".to_string()),
+        first_seen_at: first_seen_at.to_string(),
+    };
+    // Aynı hasatta en eski önce eklenir
+    repos
+        .scraped_announcements
+        .store(&[
+            write("1", "2024-03-01T08:00:00.000Z"),
+            write("2", "2024-03-01T08:00:00.000Z"),
+            write("3", "2024-03-04T08:00:00.000Z"),
+        ])
+        .await
+        .unwrap();
+    let app = app!(memory_state(store.clone()));
+
+    let get = |query: &str| test::TestRequest::get().uri(&format!("/api/v1/kurum-duyuru?kurum_id={}&{}", sgk, query)).to_request();
+
+    let (status, body) = status_and_body(test::call_service(&app, get("limit=2")).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["count"], 3);
+    assert_eq!(links(&body), ["https://www.sgk.gov.tr/Duyuru/3", "https://www.sgk.gov.tr/Duyuru/2"]);
+    assert_eq!(body["data"][0]["first_seen_at"], "2024-03-04T08:00:00.000Z");
+
+    let (_, body) = status_and_body(test::call_service(&app, get("limit=2&offset=2")).await).await;
+    assert_eq!(links(&body), ["https://www.sgk.gov.tr/Duyuru/1"]);
+
+    let (_, body) = status_and_body(test::call_service(&app, get("since=2024-03-02")).await).await;
+    assert_eq!(body["count"], 1);
+    assert_eq!(links(&body), ["https://www.sgk.gov.tr/Duyuru/3"]);
+
+    let (status, body) = status_and_body(test::call_service(&app, get("since=ge%C3%A7en-hafta")).await).await;
+    assert_eq!(status, 400);
+    assert_eq!(body["error"]["code"], "INVALID_PARAMETER");
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/v1/kurum-duyuru?kurum_id={}", ObjectId::new()))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+}
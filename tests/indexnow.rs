@@ -0,0 +1,236 @@
+use std::sync::{Arc, Mutex};
+use actix_web::dev::ServerHandle;
+use actix_web::{test, web, App, HttpRequest, HttpResponse, HttpServer};
+use api_mevzuatgpt::audit::Actor;
+use api_mevzuatgpt::config::PublicUrls;
+use api_mevzuatgpt::corpus::CorpusRegistry;
+use api_mevzuatgpt::indexnow::{self, IndexNowConfig, PingError, PingReport};
+use api_mevzuatgpt::repository::{DocumentWrite, MemoryRepository, Repositories};
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::Value;
+
+#[macro_use]
+mod common;
+use common::memory_state;
+
+// robots.txt, IndexNow anahtar dosyası ve eklenen/değişen belgelerin yerel bir IndexNow
+// endpoint'ine bildirilmesi
+
+const KEY: &str = "3f6c1e0b9a8d4c27b5e2";
+
+type Received = Arc<Mutex<Vec<Value>>>;
+
+async fn receive(req: HttpRequest, body: web::Json<Value>, received: web::Data<Received>) -> HttpResponse {
+    received.lock().unwrap().push(body.into_inner());
+    if req.path() == "/indexnow" {
+        HttpResponse::Accepted().finish()
+    } else {
+        HttpResponse::TooManyRequests().finish()
+    }
+}
+
+// Yerel IndexNow endpoint'i: adresi ve durdurma tutamacı
+fn receiver(received: &Received) -> (String, ServerHandle) {
+    let receiver_state = web::Data::new(received.clone());
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(receiver_state.clone())
+            .default_service(web::post().to(receive))
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let base = format!("http://{}", server.addrs()[0]);
+    let server = server.run();
+    let handle = server.handle();
+    actix_web::rt::spawn(server);
+    (base, handle)
+}
+
+#[actix_web::test]
+async fn robots_txt_and_key_file_are_public() {
+    let mut state = memory_state(Arc::new(MemoryRepository::new()));
+    state.urls = PublicUrls::new("https://staging.mevzuatgpt.org").unwrap();
+    state.auth.required = true;
+    state.indexnow.key = Some(KEY.to_string());
+    let app = app!(state);
+
+    let resp = test::call_service(&app, test::TestRequest::get().uri("/robots.txt").to_request()).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "text/plain; charset=utf-8");
+    let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+    assert_eq!(body, "User-agent: *\nDisallow: /api/\n\nSitemap: https://staging.mevzuatgpt.org/sitemap.xml\n");
+
+    let resp = test::call_service(&app, test::TestRequest::get().uri("/indexnow-key.txt").to_request()).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, KEY);
+
+    // API yolları anahtarsız erişilemez kalır
+    let resp = test::call_service(&app, test::TestRequest::get().uri("/api/v1/documents").to_request()).await;
+    assert_eq!(resp.status(), 401);
+
+    let app = app!(memory_state(Arc::new(MemoryRepository::new())));
+    let resp = test::call_service(&app, test::TestRequest::get().uri("/indexnow-key.txt").to_request()).await;
+    assert_eq!(resp.status(), 404);
+
+    assert!(indexnow::validate_key(KEY).is_ok());
+    for key in ["kisa", "bosluk iceren anahtar", "ozel/karakter-1234"] {
+        assert!(indexnow::validate_key(key).is_err(), "{}", key);
+    }
+}
+
+#[actix_web::test]
+async fn added_changed_and_deleted_documents_are_submitted_once_accepted() {
+    let received: Received = Arc::new(Mutex::new(Vec::new()));
+    let (base, handle) = receiver(&received);
+
+    let store = Arc::new(MemoryRepository::new());
+    let repos = Repositories::memory(store.clone());
+    let corpora = CorpusRegistry::builtin();
+    let urls = PublicUrls::new("https://staging.mevzuatgpt.org").unwrap();
+    let renamed_id = store.insert("metadata", doc! {
+        "pdf_adi": "Prim Genelgesi",
+        "url_slug": "prim-genelgesi",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-03-01T10:00:00Z",
+    });
+    let deleted_id = store.insert("metadata", doc! {
+        "pdf_adi": "Mülga Yönetmelik",
+        "url_slug": "mulga-yonetmelik",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-03-02T10:00:00Z",
+    });
+    let config = IndexNowConfig {
+        endpoint: format!("{}/indexnow", base),
+        ..IndexNowConfig::default()
+    };
+    let client = indexnow::client(&config);
+    let run = |endpoint: String| {
+        let (repos, corpora, urls, client) = (repos.clone(), corpora.clone(), urls.clone(), client.clone());
+        async move { indexnow::run(&repos, &corpora, &urls, &client, &endpoint, KEY).await }
+    };
+
+    // İlk tur yalnızca imleçleri kurar; mevcut arşiv bildirilmez
+    assert_eq!(run(config.endpoint.clone()).await.unwrap(), PingReport::default());
+    assert!(received.lock().unwrap().is_empty());
+
+    let actor = Actor::cli();
+    let mevzuat = corpora.mevzuat();
+    repos
+        .document_writes
+        .insert(&actor, mevzuat, &DocumentWrite {
+            pdf_adi: "Yeni Tebliğ".to_string(),
+            url_slug: "yeni-teblig".to_string(),
+            status: "aktif".to_string(),
+            olusturulma_tarihi: "2024-06-03T10:00:00Z".to_string(),
+            ..DocumentWrite::default()
+        })
+        .await
+        .unwrap();
+    assert!(repos
+        .document_writes
+        .update(&actor, mevzuat, &renamed_id, &DocumentWrite {
+            pdf_adi: "Prim Genelgesi".to_string(),
+            url_slug: "prim-genelgesi-2024".to_string(),
+            status: "aktif".to_string(),
+            olusturulma_tarihi: "2024-03-01T10:00:00Z".to_string(),
+            ..DocumentWrite::default()
+        })
+        .await
+        .unwrap());
+    assert!(repos.document_writes.delete(&actor, mevzuat, &deleted_id).await.unwrap());
+
+    // Reddedilen bildirimde imleçler ilerlemez; sonraki tur aynı adresleri gönderir
+    let error = run(format!("{}/yogun", base)).await.unwrap_err();
+    assert!(matches!(error, PingError::Endpoint(ref message) if message == "HTTP 429"), "{}", error);
+
+    let report = run(config.endpoint.clone()).await.unwrap();
+    assert_eq!(report, PingReport { urls: 4, requests: 1 });
+    let bodies = received.lock().unwrap().clone();
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies[0], bodies[1]);
+    let body = &bodies[1];
+    assert_eq!(body["host"], "staging.mevzuatgpt.org");
+    assert_eq!(body["key"], KEY);
+    assert_eq!(body["keyLocation"], "https://staging.mevzuatgpt.org/indexnow-key.txt");
+    let mut submitted: Vec<&str> = body["urlList"].as_array().unwrap().iter().map(|url| url.as_str().unwrap()).collect();
+    submitted.sort();
+    assert_eq!(submitted, [
+        "https://staging.mevzuatgpt.org/belge/mulga-yonetmelik",
+        "https://staging.mevzuatgpt.org/belge/prim-genelgesi",
+        "https://staging.mevzuatgpt.org/belge/prim-genelgesi-2024",
+        "https://staging.mevzuatgpt.org/belge/yeni-teblig",
+    ]);
+
+    assert_eq!(run(config.endpoint.clone()).await.unwrap(), PingReport::default());
+    assert_eq!(received.lock().unwrap().len(), 2);
+
+    // Başka bir alan adındaki adresler gönderilmez
+    let foreign = vec![
+        "https://baska.example.org/belge/x".to_string(),
+        format!("https://staging.mevzuatgpt.org/belge/{}", ObjectId::new().to_hex()),
+    ];
+    assert_eq!(indexnow::submit(&client, &config.endpoint, KEY, &urls, &foreign).await.unwrap(), 1);
+    let last = received.lock().unwrap().last().cloned().unwrap();
+    assert_eq!(last["urlList"].as_array().unwrap().len(), 1);
+
+    handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn changes_beyond_one_page_are_all_submitted() {
+    let received: Received = Arc::new(Mutex::new(Vec::new()));
+    let (base, handle) = receiver(&received);
+    let store = Arc::new(MemoryRepository::new());
+    let repos = Repositories::memory(store.clone());
+    let corpora = CorpusRegistry::builtin();
+    let urls = PublicUrls::new("https://staging.mevzuatgpt.org").unwrap();
+    let endpoint = format!("{}/indexnow", base);
+    let client = indexnow::client(&IndexNowConfig::default());
+    let ids: Vec<String> = (0..250)
+        .map(|i| {
+            store.insert("metadata", doc! {
+                "pdf_adi": format!("Genelge {}", i),
+                "url_slug": format!("genelge-{}", i),
+                "status": "aktif",
+                "olusturulma_tarihi": "2024-03-01T10:00:00Z",
+            })
+        })
+        .collect();
+    assert_eq!(indexnow::run(&repos, &corpora, &urls, &client, &endpoint, KEY).await.unwrap(), PingReport::default());
+
+    // Bir sayfadan (200) fazla güncelleme ve yeni belge; denetim kayıtları aynı milisaniyeye düşebilir
+    let actor = Actor::cli();
+    let mevzuat = corpora.mevzuat();
+    for (i, id) in ids.iter().enumerate() {
+        let document = DocumentWrite {
+            pdf_adi: format!("Genelge {}", i),
+            url_slug: format!("genelge-{}-yeni", i),
+            status: "aktif".to_string(),
+            olusturulma_tarihi: "2024-03-01T10:00:00Z".to_string(),
+            ..DocumentWrite::default()
+        };
+        assert!(repos.document_writes.update(&actor, mevzuat, id, &document).await.unwrap());
+    }
+    for i in 0..230 {
+        let document = DocumentWrite {
+            pdf_adi: format!("Tebliğ {}", i),
+            url_slug: format!("teblig-{}", i),
+            status: "aktif".to_string(),
+            olusturulma_tarihi: format!("2024-06-01T10:00:{:02}.000Z", i % 60),
+            ..DocumentWrite::default()
+        };
+        repos.document_writes.insert(&actor, mevzuat, &document).await.unwrap();
+    }
+
+    let report = indexnow::run(&repos, &corpora, &urls, &client, &endpoint, KEY).await.unwrap();
+    assert_eq!(report, PingReport { urls: 250 * 2 + 230, requests: 1 });
+    let body = received.lock().unwrap().last().cloned().unwrap();
+    let submitted = body["urlList"].as_array().unwrap();
+    for url in ["genelge-0", "genelge-0-yeni", "genelge-249-yeni", "teblig-0", "teblig-229"] {
+        assert!(submitted.contains(&Value::from(format!("https://staging.mevzuatgpt.org/belge/{}", url))), "{}", url);
+    }
+    assert_eq!(indexnow::run(&repos, &corpora, &urls, &client, &endpoint, KEY).await.unwrap(), PingReport::default());
+
+    handle.stop(false).await;
+}
@@ -0,0 +1,282 @@
+use std::sync::{Arc, Mutex};
+use actix_web::http::header::USER_AGENT;
+use actix_web::{test, web, App, HttpRequest, HttpResponse, HttpServer};
+use api_mevzuatgpt::audit::Actor;
+use api_mevzuatgpt::ingest::cli::IngestCommand;
+use api_mevzuatgpt::ingest::{self, normalize_text, IngestError, PdfSource};
+use api_mevzuatgpt::models::admin_document::DocumentInput;
+use api_mevzuatgpt::scraping::CrawlConfig;
+use mongodb::bson::oid::ObjectId;
+use pdf_extract::content::{Content, Operation};
+use pdf_extract::{dictionary, Document, Object, Stream, StringFormat};
+use serde_json::{json, Value};
+
+#[macro_use]
+mod common;
+use common::{fixture, memory_state, status_and_body, with_key};
+
+// PDF alım hattı: metin normalizasyonu, PDF'ten metin/sayfa/boyut çıkarma, CLI ve endpoint
+
+// Helvetica + WinAnsi ile yazılmış test PDF'i. Her sayfa bir satır listesidir; Türkçe
+// harfler eski belgelerdeki gibi Latin-1 karşılıklarıyla yazılır (ý = ı, þ = ş, ð = ğ).
+fn sample_pdf(pages: &[&[&[u8]]], title: Option<&str>) -> Vec<u8> {
+    let mut doc = Document::with_version("1.5");
+    let pages_id = doc.new_object_id();
+    let font_id = doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Helvetica",
+        "Encoding" => "WinAnsiEncoding",
+    });
+    let resources_id = doc.add_object(dictionary! { "Font" => dictionary! { "F1" => font_id } });
+
+    let mut kids = Vec::new();
+    for lines in pages {
+        let mut operations = vec![
+            Operation::new("BT", vec![]),
+            Operation::new("Tf", vec!["F1".into(), 12.into()]),
+            Operation::new("Td", vec![72.into(), 700.into()]),
+        ];
+        for (index, line) in lines.iter().enumerate() {
+            if index > 0 {
+                operations.push(Operation::new("Td", vec![0.into(), (-16).into()]));
+            }
+            operations.push(Operation::new("Tj", vec![Object::String(line.to_vec(), StringFormat::Literal)]));
+        }
+        operations.push(Operation::new("ET", vec![]));
+
+        let content = Content { operations }.encode().unwrap();
+        let content_id = doc.add_object(Stream::new(dictionary! {}, content));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        });
+        kids.push(page_id.into());
+    }
+
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => kids,
+            "Count" => pages.len() as i64,
+            "Resources" => resources_id,
+            "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+        }),
+    );
+    let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+    doc.trailer.set("Root", catalog_id);
+
+    if let Some(title) = title {
+        // UTF-16BE (BOM'lu) metin dizgisi
+        let mut encoded = vec![0xFE, 0xFF];
+        encoded.extend(title.encode_utf16().flat_map(|unit| unit.to_be_bytes()));
+        let info_id = doc.add_object(dictionary! { "Title" => Object::String(encoded, StringFormat::Hexadecimal) });
+        doc.trailer.set("Info", info_id);
+    }
+
+    let mut bytes = Vec::new();
+    doc.save_to(&mut bytes).unwrap();
+    bytes
+}
+
+fn regulation_pdf() -> Vec<u8> {
+    sample_pdf(
+        &[
+            &[b"Birinci B\xf6l\xfcm", b"Bu y\xf6netmelik i\xfe g\xfcvenli-", b"\xf0i kurallar\xfdn\xfd d\xfczenler."],
+            &[b"Y\xfcr\xfcrl\xfck", b"Bu y\xf6netmelik yay\xfdm\xfd tarihinde y\xfcr\xfcrl\xfc\xf0e girer."],
+        ],
+        Some("İş Güvenliği Yönetmeliği"),
+    )
+}
+
+// PDF'i yerel bir HTTP sunucusundan servis eder; dönen adres /belgeler/<ad> altındadır.
+// İndirme isteklerinin User-Agent'ları da döner
+fn serve_pdf(bytes: Vec<u8>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let bytes = web::Bytes::from(bytes);
+    let agents = Arc::new(Mutex::new(Vec::new()));
+    let recorded = agents.clone();
+    let server = HttpServer::new(move || {
+        let (bytes, recorded) = (bytes.clone(), recorded.clone());
+        App::new()
+            .route(
+                "/belgeler/{name}",
+                web::get().to(move |req: HttpRequest| {
+                    let bytes = bytes.clone();
+                    let agent = req.headers().get(USER_AGENT).and_then(|value| value.to_str().ok());
+                    recorded.lock().unwrap().push(agent.unwrap_or_default().to_string());
+                    async move { HttpResponse::Ok().content_type("application/pdf").body(bytes) }
+                }),
+            )
+            .route("/eksik.pdf", web::get().to(HttpResponse::NotFound))
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let address = server.addrs()[0];
+    actix_web::rt::spawn(server.run());
+    (format!("http://{}", address), agents)
+}
+
+#[actix_web::test]
+async fn normalize_text_cleans_pdf_artifacts() {
+    // Latin-1 olarak çözülmüş Türkçe harfler ve tipografik bağlar
+    assert_eq!(normalize_text("Ýþ güvenliði ve ﬁnansal ﬂuktuasyon"), "İş güvenliği ve finansal fluktuasyon");
+    // Ayrışık (NFD) karakterler tek karaktere birleşir
+    assert_eq!(normalize_text("s\u{327}irket gu\u{308}venlig\u{306}i"), "şirket güvenliği");
+    // Satır sonu bölünmeleri birleşir; büyük harfle başlayan satır ve sayılar bölünmez
+    assert_eq!(
+        normalize_text("iş güvenli-\nği kuralları\n2024-\n2025 dönemi\nAnkara-\nİstanbul"),
+        "iş güvenliği kuralları\n2024-\n2025 dönemi\nAnkara-\nİstanbul"
+    );
+    // Yumuşak tire, sıfır genişlikli karakterler, bölünmez boşluk ve fazla boş satırlar
+    assert_eq!(
+        normalize_text("  yönet\u{AD}me\u{200B}lik\u{A0}\u{A0}madde 1  \r\n\r\n\r\n\u{C}\tMadde   2 \n"),
+        "yönetmelik madde 1\n\nMadde 2"
+    );
+    assert_eq!(normalize_text(" \n\u{FEFF}\n "), "");
+}
+
+#[actix_web::test]
+async fn extract_reads_text_pages_size_and_title() {
+    let bytes = regulation_pdf();
+    let size = bytes.len();
+    let extracted = ingest::extract(bytes).await.unwrap();
+
+    assert_eq!(extracted.sayfa_sayisi, 2);
+    assert_eq!(extracted.title.as_deref(), Some("İş Güvenliği Yönetmeliği"));
+    assert_eq!(extracted.dosya_boyutu_mb, (size as f64 / 1_048_576.0 * 100.0).round() / 100.0);
+    assert!(extracted.text.contains("Birinci Bölüm"), "{}", extracted.text);
+    assert!(extracted.text.contains("iş güvenliği kurallarını düzenler"), "{}", extracted.text);
+    assert!(extracted.text.contains("yayımı tarihinde yürürlüğe girer"), "{}", extracted.text);
+
+    assert!(matches!(ingest::extract(b"<html>PDF degil</html>".to_vec()).await, Err(IngestError::NotPdf)));
+    assert!(matches!(ingest::extract(b"%PDF-1.5\nbozuk".to_vec()).await, Err(IngestError::Parse(_))));
+    // Metin katmanı olmayan (taranmış) PDF
+    let scanned = sample_pdf(&[&[]], None);
+    assert!(matches!(ingest::extract(scanned).await, Err(IngestError::NoText)));
+}
+
+#[actix_web::test]
+async fn cli_arguments_are_parsed() {
+    let args = |line: &str| line.split(' ').map(str::to_string).collect::<Vec<_>>();
+
+    assert_eq!(IngestCommand::from_args(&args("api --create-indexes")), None);
+    assert!(IngestCommand::from_args(&args("api --ingest-pdf belge.pdf")).unwrap().is_err());
+    assert!(IngestCommand::from_args(&args("api --ingest-pdf --kurum-id abc")).unwrap().is_err());
+
+    let command = IngestCommand::from_args(&args(
+        "api --ingest-pdf https://example.gov.tr/yonetmelik.pdf --kurum-id 65a1 --belge-turu Yönetmelik --status pasif",
+    ))
+    .unwrap()
+    .unwrap();
+    assert_eq!(command.source, PdfSource::Url("https://example.gov.tr/yonetmelik.pdf".to_string()));
+    assert_eq!(command.metadata.kurum_id.as_deref(), Some("65a1"));
+    assert_eq!(command.metadata.belge_turu.as_deref(), Some("Yönetmelik"));
+    assert_eq!(command.metadata.status.as_deref(), Some("pasif"));
+    assert_eq!(command.metadata.pdf_adi, None);
+
+    let command = IngestCommand::from_args(&args("api --ingest-pdf ./arsiv/is_kanunu.pdf --kurum-id 65a1"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(command.source.file_stem().as_deref(), Some("is kanunu"));
+}
+
+#[actix_web::test]
+async fn local_file_is_ingested_into_metadata_and_content() {
+    let f = fixture().await;
+    let state = memory_state(f.store.clone());
+    let path = std::env::temp_dir().join(format!("ingest-{}.pdf", ObjectId::new().to_hex()));
+    std::fs::write(&path, sample_pdf(&[&[b"Genelge metni"]], None)).unwrap();
+
+    let metadata = DocumentInput {
+        kurum_id: Some(f.kurum_id.to_hex()),
+        belge_turu: Some("Genelge".to_string()),
+        // Hesaplanan değerler gövdedekileri ezer
+        sayfa_sayisi: Some(99),
+        ..DocumentInput::default()
+    };
+    let (id, document) = ingest::ingest(&state, &Actor::cli(), &PdfSource::File(path.clone()), metadata).await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Başlık yoksa dosya adı kullanılır; yerel dosyada pdf_url boş kalır
+    assert!(document.pdf_adi.starts_with("ingest "));
+    assert_eq!(document.sayfa_sayisi, 1);
+    assert_eq!(document.pdf_url, "");
+
+    let metadata = f.store.documents("metadata");
+    assert_eq!(metadata.len(), 1);
+    assert_eq!(metadata[0].get_object_id("_id").unwrap().to_hex(), id);
+    assert_eq!(metadata[0].get_i32("sayfa_sayisi").unwrap(), 1);
+    let content = f.store.documents("content");
+    assert_eq!(content[0].get_str("content").unwrap(), "Genelge metni");
+
+    // Var olmayan dosya ve doğrulama hataları API hatalarına dönüşür
+    let missing = PdfSource::parse("/olmayan/dizin/belge.pdf");
+    let err = ingest::ingest(&state, &Actor::cli(), &missing, DocumentInput::default()).await.unwrap_err();
+    assert_eq!(err.code(), "PDF_UNAVAILABLE");
+
+    let path = std::env::temp_dir().join(format!("ingest-{}.pdf", ObjectId::new().to_hex()));
+    std::fs::write(&path, sample_pdf(&[&[b"Genelge metni"]], None)).unwrap();
+    let err = ingest::ingest(&state, &Actor::cli(), &PdfSource::File(path.clone()), DocumentInput::default()).await.unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(err.code(), "VALIDATION_FAILED");
+    assert_eq!(err.fields()[0].field, "kurum_id");
+}
+
+#[actix_web::test]
+async fn ingest_endpoint_downloads_and_indexes_pdf() {
+    let f = fixture().await;
+    let (base, agents) = serve_pdf(regulation_pdf());
+    let app = app!(memory_state(f.store.clone()));
+    let ingest_request = |body: Value| {
+        with_key(test::TestRequest::post().uri("/api/admin/documents/ingest"), &f.admin_key)
+            .set_json(body)
+            .to_request()
+    };
+
+    let url = format!("{}/belgeler/is-guvenligi.pdf", base);
+    let req = ingest_request(json!({ "url": url, "metadata": { "kurum_id": f.kurum_id.to_hex() } }));
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201, "{}", body);
+    let metadata = &body["data"]["metadata"];
+    assert_eq!(metadata["pdf_adi"], "İş Güvenliği Yönetmeliği");
+    assert_eq!(metadata["url_slug"], "is-guvenligi-yonetmeligi");
+    assert_eq!(metadata["sayfa_sayisi"], 2);
+    assert_eq!(metadata["pdf_url"], url.as_str());
+    assert!(body["data"]["content"]["icerik"].as_str().unwrap().contains("iş güvenliği kurallarını"));
+
+    // Çıkarılan metin aramada bulunur
+    let req = test::TestRequest::get().uri("/api/v1/search?q=kurallar%C4%B1n%C4%B1").to_request();
+    let (_, found) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(found["count"], 1);
+
+    // Gövdedeki pdf_adi PDF başlığından önce gelir
+    let req = ingest_request(json!({
+        "url": url,
+        "metadata": { "kurum_id": f.kurum_id.to_hex(), "pdf_adi": "İSG Yönetmeliği" },
+    }));
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    assert_eq!(body["data"]["metadata"]["url_slug"], "isg-yonetmeligi");
+
+    // Yerel yol kabul edilmez; indirilemeyen PDF 502 döner
+    let req = ingest_request(json!({ "url": "/etc/belge.pdf", "metadata": { "kurum_id": f.kurum_id.to_hex() } }));
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    assert_eq!(body["error"]["fields"][0]["field"], "url");
+
+    let req = ingest_request(json!({ "url": format!("{}/eksik.pdf", base) }));
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 502);
+    assert_eq!(body["error"]["code"], "PDF_UNAVAILABLE");
+
+    assert_eq!(f.store.documents("metadata").len(), 2);
+
+    // PDF'ler paylaşılan tarayıcının kendini tanıtan User-Agent'ıyla indirilir
+    let agents = agents.lock().unwrap().clone();
+    assert_eq!(agents.len(), 2);
+    let expected = CrawlConfig::default().user_agent_header();
+    assert!(agents.iter().all(|agent| *agent == expected), "{:?}", agents);
+}
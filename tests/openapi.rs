@@ -0,0 +1,122 @@
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use actix_web::{dev::Service, http::Method, test, web, App};
+use api_mevzuatgpt::openapi::ApiDoc;
+use api_mevzuatgpt::repository::MemoryRepository;
+use api_mevzuatgpt::routes;
+use regex::Regex;
+use serde_json::Value;
+use utoipa::OpenApi;
+
+#[macro_use]
+mod common;
+use common::memory_state;
+
+// OpenAPI dokümanının routes::configure_routes ile uyumlu kaldığını doğrular
+
+fn spec() -> Value {
+    serde_json::to_value(ApiDoc::openapi()).unwrap()
+}
+
+// Handler modüllerinde ve routes/mod.rs'te .to(...) ile bağlanan tüm handler fonksiyonları
+fn registered_handlers() -> BTreeSet<String> {
+    let to_re = Regex::new(r"\.to\(\s*(?:handlers::\w+::)?(\w+)\s*\)").unwrap();
+    let mut files = vec![std::path::PathBuf::from("src/routes/mod.rs")];
+    for entry in std::fs::read_dir("src/handlers").unwrap() {
+        files.push(entry.unwrap().path());
+    }
+
+    let mut names = BTreeSet::new();
+    for file in files {
+        let source = std::fs::read_to_string(&file).unwrap();
+        for cap in to_re.captures_iter(&source) {
+            names.insert(cap[1].to_string());
+        }
+    }
+    names
+}
+
+#[actix_web::test]
+async fn every_registered_route_is_documented() {
+    let spec = spec();
+    let operation_ids: BTreeSet<String> = spec["paths"]
+        .as_object()
+        .unwrap()
+        .values()
+        .flat_map(|item| item.as_object().unwrap().values())
+        .filter_map(|op| op["operationId"].as_str().map(|id| id.to_string()))
+        .collect();
+
+    let handlers = registered_handlers();
+    assert!(handlers.len() > 20, "route taraması handler bulamadı: {:?}", handlers);
+
+    let missing: Vec<&String> = handlers.difference(&operation_ids).collect();
+    assert!(
+        missing.is_empty(),
+        "OpenAPI dokümanında eksik handler'lar (src/openapi/mod.rs paths listesine ekleyin): {:?}",
+        missing
+    );
+}
+
+#[actix_web::test]
+async fn documented_paths_match_registered_routes() {
+    let matched = Arc::new(Mutex::new(None::<String>));
+    let recorder = matched.clone();
+
+    let state = memory_state(Arc::new(MemoryRepository::new()));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state))
+            .wrap_fn(move |req, srv| {
+                let recorder = recorder.clone();
+                let fut = srv.call(req);
+                async move {
+                    let res = fut.await?;
+                    *recorder.lock().unwrap() = res.request().match_pattern();
+                    Ok(res)
+                }
+            })
+            .configure(routes::configure_routes),
+    )
+    .await;
+
+    let param_re = Regex::new(r"\{[^}]+\}").unwrap();
+    let spec = spec();
+    for (path, item) in spec["paths"].as_object().unwrap() {
+        for method in item.as_object().unwrap().keys() {
+            let method = Method::from_bytes(method.to_uppercase().as_bytes()).unwrap();
+            let uri = param_re.replace_all(path, "ornek").to_string();
+            *matched.lock().unwrap() = None;
+            let req = test::TestRequest::default().method(method.clone()).uri(&uri).to_request();
+            test::call_service(&app, req).await;
+
+            assert_eq!(
+                matched.lock().unwrap().as_deref(),
+                Some(path.as_str()),
+                "{} {} dokümanda var ama kayıtlı bir route ile eşleşmiyor",
+                method,
+                path
+            );
+        }
+    }
+}
+
+#[actix_web::test]
+async fn spec_and_docs_are_served() {
+    let state = memory_state(Arc::new(MemoryRepository::new()));
+    let app = app!(state);
+
+    let req = test::TestRequest::get().uri("/api/openapi.json").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert!(body["openapi"].as_str().unwrap().starts_with("3."));
+    assert!(body["paths"]["/api/v1/documents"]["get"]["parameters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|param| param["name"] == "belge_turu"));
+    assert!(body["components"]["schemas"]["DocumentsListResponse"].is_object());
+
+    let req = test::TestRequest::get().uri("/api/docs/").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+}
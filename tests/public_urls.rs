@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use actix_web::{test, web};
+use api_mevzuatgpt::config::PublicUrls;
+use api_mevzuatgpt::corpus::{CorpusRegistry, YARGITAY_KURUM_ID};
+use api_mevzuatgpt::repository::MemoryRepository;
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::Value;
+
+#[macro_use]
+mod common;
+use common::memory_state;
+
+// Herkese açık adresler: staging base URL'i ve corpus şablonları sitemap, besleme ve belge
+// cevaplarında aynı şekilde kullanılır
+
+#[actix_web::test]
+async fn public_urls_validate_base_url_and_templates() {
+    let urls = PublicUrls::new(" https://staging.mevzuatgpt.org/ ").unwrap();
+    assert_eq!(urls.base_url(), "https://staging.mevzuatgpt.org");
+    assert_eq!(urls.absolute("/feeds/duyurular"), "https://staging.mevzuatgpt.org/feeds/duyurular");
+    assert_eq!(urls.institution("adalet-bakanligi"), "https://staging.mevzuatgpt.org/kurum/adalet-bakanligi");
+
+    let corpora = CorpusRegistry::builtin();
+    let urls = urls
+        .with_document_template("yargitay", "/kararlar/{id}/{slug}")
+        .unwrap()
+        .with_institution_template("https://kurumlar.example.org/{slug}")
+        .unwrap();
+    assert_eq!(
+        urls.document(corpora.mevzuat(), "prim-yonetmeligi", "1"),
+        "https://staging.mevzuatgpt.org/belge/prim-yonetmeligi"
+    );
+    assert_eq!(
+        urls.document(corpora.yargitay(), "9-hd-karar", "abc"),
+        "https://staging.mevzuatgpt.org/kararlar/abc/9-hd-karar"
+    );
+    assert_eq!(urls.institution("sgk"), "https://kurumlar.example.org/sgk");
+
+    for base_url in ["staging.mevzuatgpt.org", "ftp://mevzuatgpt.org", ""] {
+        assert!(PublicUrls::new(base_url).is_err(), "{}", base_url);
+    }
+    for template in ["/belge", "belge/{slug}"] {
+        assert!(PublicUrls::default().with_document_template("mevzuat", template).is_err(), "{}", template);
+    }
+}
+
+#[actix_web::test]
+async fn configured_urls_are_used_in_sitemaps_feeds_and_document_responses() {
+    let store = Arc::new(MemoryRepository::new());
+    store.insert("kurumlar", doc! { "_id": ObjectId::new(), "kurum_adi": "Adalet Bakanlığı" });
+    store.insert("metadata", doc! {
+        "_id": ObjectId::new(),
+        "pdf_adi": "Prim Yönetmeliği",
+        "url_slug": "prim-yonetmeligi",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-05-10T08:30:00Z",
+    });
+    let karar_id = ObjectId::new();
+    store.insert("yargitay", doc! {
+        "_id": karar_id,
+        "kurum_id": YARGITAY_KURUM_ID,
+        "pdf_adi": "9. Hukuk Dairesi Kararı",
+        "url_slug": "9-hd-karar",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-01-15",
+    });
+
+    let mut state = memory_state(store);
+    state.urls = PublicUrls::new("https://staging.mevzuatgpt.org")
+        .unwrap()
+        .with_document_template("yargitay", "/karar/{slug}")
+        .unwrap();
+    state.sitemap.gzip = false;
+    let app = app!(state);
+    let text = |body: web::Bytes| String::from_utf8(body.to_vec()).unwrap();
+    let fetch = |uri: &str| test::TestRequest::get().uri(uri).to_request();
+
+    let index = text(test::call_and_read_body(&app, fetch("/sitemap.xml")).await);
+    assert!(index.contains("<loc>https://staging.mevzuatgpt.org/sitemaps/mevzuat-1.xml</loc>"));
+    assert!(!index.contains("https://mevzuatgpt.org"));
+    let sitemap = text(test::call_and_read_body(&app, fetch("/sitemaps/yargitay-1.xml")).await);
+    assert!(sitemap.contains("<loc>https://staging.mevzuatgpt.org/karar/9-hd-karar</loc>"));
+    let sitemap = text(test::call_and_read_body(&app, fetch("/sitemaps/institutions-1.xml")).await);
+    assert!(sitemap.contains("<loc>https://staging.mevzuatgpt.org/kurum/adalet-bakanligi</loc>"));
+
+    let feed = text(test::call_and_read_body(&app, fetch("/feeds/yargitay.xml")).await);
+    assert!(feed.contains("<link>https://staging.mevzuatgpt.org/karar/9-hd-karar</link>"));
+    assert!(feed.contains("href=\"https://staging.mevzuatgpt.org/feeds/yargitay.xml\""));
+    // Girdi kimlikleri adresten bağımsızdır; ortam değişse de okuyucuda tekrar görünmez
+    assert!(feed.contains(&format!("tag:mevzuatgpt.org,2024:yargitay/{}", karar_id.to_hex())));
+
+    let body: Value = test::call_and_read_body_json(&app, fetch("/api/v1/documents")).await;
+    assert_eq!(body["data"][0]["url"], "https://staging.mevzuatgpt.org/belge/prim-yonetmeligi");
+    let body: Value = test::call_and_read_body_json(&app, fetch("/api/v2/documents/9-hd-karar")).await;
+    assert_eq!(body["data"]["metadata"]["url"], "https://staging.mevzuatgpt.org/karar/9-hd-karar");
+}
@@ -0,0 +1,199 @@
+use std::sync::Arc;
+use actix_web::test;
+use api_mevzuatgpt::auth::{create_key, Scope};
+use api_mevzuatgpt::config::AppState;
+use api_mevzuatgpt::ratelimit::{
+    GroupRules, LocalStore, RateLimitConfig, RateLimitStore, RateLimiter, Rule,
+};
+use api_mevzuatgpt::repository::{ApiKeyRepository, MemoryRepository};
+use serde_json::Value;
+
+#[macro_use]
+mod common;
+use common::{error_code, memory_state};
+
+// Token bucket rate limit middleware'i
+
+fn config() -> RateLimitConfig {
+    RateLimitConfig {
+        ip_rules: GroupRules {
+            read: Rule::new(60, 2),
+            search: Rule::new(60, 1),
+            scrape: Rule::new(60, 1),
+        },
+        key_rules: GroupRules {
+            read: Rule::new(60, 3),
+            search: Rule::new(60, 3),
+            scrape: Rule::new(60, 1),
+        },
+        ..RateLimitConfig::default()
+    }
+}
+
+fn state(store: Arc<MemoryRepository>, config: RateLimitConfig) -> AppState {
+    let mut state = memory_state(store);
+    state.rate_limiter = RateLimiter::new(config, Arc::new(LocalStore::new()));
+    state
+}
+
+fn from_ip(uri: &str, ip: &str) -> test::TestRequest {
+    test::TestRequest::get()
+        .uri(uri)
+        .peer_addr(format!("{}:40000", ip).parse().unwrap())
+}
+
+#[actix_web::test]
+async fn ip_bucket_rejects_after_burst() {
+    let app = app!(state(Arc::new(MemoryRepository::new()), config()));
+
+    let resp = test::call_service(&app, from_ip("/api/v1/documents", "10.0.0.1").to_request()).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("x-ratelimit-limit").unwrap(), "2");
+    assert_eq!(resp.headers().get("x-ratelimit-remaining").unwrap(), "1");
+
+    let resp = test::call_service(&app, from_ip("/api/v1/statistics", "10.0.0.1").to_request()).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("x-ratelimit-remaining").unwrap(), "0");
+
+    let resp = test::call_service(&app, from_ip("/api/v1/documents", "10.0.0.1").to_request()).await;
+    assert_eq!(resp.status(), 429);
+    assert_eq!(resp.headers().get("retry-after").unwrap(), "1");
+    assert_eq!(resp.headers().get("x-ratelimit-remaining").unwrap(), "0");
+    assert!(resp.headers().contains_key("x-request-id"));
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"]["code"], "RATE_LIMITED");
+
+    // Başka IP'nin bucket'ı ayrıdır
+    let resp = test::call_service(&app, from_ip("/api/v1/documents", "10.0.0.2").to_request()).await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn route_groups_have_separate_buckets() {
+    let app = app!(state(Arc::new(MemoryRepository::new()), config()));
+
+    let resp = test::call_service(&app, from_ip("/api/v1/search?q=emeklilik", "10.0.0.3").to_request()).await;
+    assert_eq!(resp.status(), 200);
+    let resp = test::call_service(&app, from_ip("/api/v2/search?q=tazminat", "10.0.0.3").to_request()).await;
+    assert_eq!(resp.status(), 429);
+
+    // Okuma grubu arama limitinden etkilenmez
+    let resp = test::call_service(&app, from_ip("/api/v1/documents", "10.0.0.3").to_request()).await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn api_keys_use_their_own_bucket() {
+    let store = Arc::new(MemoryRepository::new());
+    let key = common::key(&store, "partner", &[Scope::Read]).await;
+    let app = app!(state(store, config()));
+
+    for _ in 0..3 {
+        let req = test::TestRequest::get()
+            .uri("/api/v1/documents")
+            .peer_addr("10.0.0.4:40000".parse().unwrap())
+            .insert_header(("X-API-Key", key.as_str()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.headers().get("x-ratelimit-limit").unwrap(), "3");
+    }
+
+    // Aynı IP'den anahtarsız istekler IP bucket'ını kullanır
+    let resp = test::call_service(&app, from_ip("/api/v1/documents", "10.0.0.4").to_request()).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("x-ratelimit-limit").unwrap(), "2");
+}
+
+#[actix_web::test]
+async fn local_store_refills_over_time() {
+    let store = LocalStore::new();
+    let rule = Rule::new(60, 2);
+
+    assert!(store.take("b", rule, 0).await.unwrap().allowed);
+    assert!(store.take("b", rule, 0).await.unwrap().allowed);
+    assert!(!store.take("b", rule, 500).await.unwrap().allowed);
+    // Dakikada 60 token: 1 saniyede bir token dolar
+    assert!(store.take("b", rule, 1000).await.unwrap().allowed);
+    // Uzun beklemede kapasite burst'ü aşmaz
+    let state = store.take("b", rule, 600_000).await.unwrap();
+    assert_eq!(state.tokens, 1.0);
+}
+
+#[actix_web::test]
+async fn rule_parsing() {
+    assert_eq!(Rule::parse("120/30"), Some(Rule::new(120, 30)));
+    assert_eq!(Rule::parse(" 5 / 1 "), Some(Rule::new(5, 1)));
+    assert_eq!(Rule::parse("0/1"), None);
+    assert_eq!(Rule::parse("abc"), None);
+}
+
+#[actix_web::test]
+async fn percent_encoded_paths_use_the_routed_bucket() {
+    let app = app!(state(Arc::new(MemoryRepository::new()), config()));
+
+    let resp = test::call_service(&app, from_ip("/api/v1/search?q=emeklilik", "10.0.0.5").to_request()).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("x-ratelimit-limit").unwrap(), "1");
+
+    // "/api/v1/%73earch" de arama bucket'ından düşer
+    let resp = test::call_service(&app, from_ip("/api/v1/%73earch?q=tazminat", "10.0.0.5").to_request()).await;
+    assert_eq!(resp.status(), 429);
+    assert_eq!(resp.headers().get("x-ratelimit-limit").unwrap(), "1");
+}
+
+#[actix_web::test]
+async fn invalid_key_guesses_are_limited_per_ip() {
+    let store = Arc::new(MemoryRepository::new());
+    let key = common::key(&store, "partner", &[Scope::Read]).await;
+    let config = RateLimitConfig {
+        auth_failures: Rule::new(60, 2),
+        ..config()
+    };
+    let app = app!(state(store, config));
+
+    let attempt = |key: &str, ip: &str| {
+        test::TestRequest::get()
+            .uri("/api/v1/documents")
+            .peer_addr(format!("{}:40000", ip).parse().unwrap())
+            .insert_header(("X-API-Key", key.to_string()))
+            .to_request()
+    };
+
+    // Her deneme farklı anahtar olsa da başarısız denemeler IP'nin bucket'ından düşer
+    for guess in ["mgpt_tahmin1", "mgpt_tahmin2"] {
+        let (status, code) = error_code(test::call_service(&app, attempt(guess, "10.0.0.6")).await).await;
+        assert_eq!(status, 401);
+        assert_eq!(code, "INVALID_API_KEY");
+    }
+    let resp = test::call_service(&app, attempt("mgpt_tahmin3", "10.0.0.6")).await;
+    assert!(resp.headers().contains_key("retry-after"));
+    let (status, code) = error_code(resp).await;
+    assert_eq!(status, 429);
+    assert_eq!(code, "RATE_LIMITED");
+
+    // Bucket dolana kadar aynı IP'den geçerli anahtar da bekler; başka IP etkilenmez
+    let resp = test::call_service(&app, attempt(&key, "10.0.0.6")).await;
+    assert_eq!(resp.status(), 429);
+    let resp = test::call_service(&app, attempt(&key, "10.0.0.7")).await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn rejected_requests_do_not_count_against_the_quota() {
+    let store = Arc::new(MemoryRepository::new());
+    let (key, record) = create_key(store.as_ref(), "partner", &[Scope::Read], Some(100)).await.unwrap();
+    let app = app!(state(store.clone(), config()));
+
+    for expected in [200, 200, 200, 429] {
+        let req = test::TestRequest::get()
+            .uri("/api/v1/documents")
+            .peer_addr("10.0.0.8:40000".parse().unwrap())
+            .insert_header(("X-API-Key", key.as_str()))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), expected);
+    }
+
+    let used = store.usage(&record.id, &api_mevzuatgpt::auth::today()).await.unwrap();
+    assert_eq!(used, 3);
+}
@@ -0,0 +1,416 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use actix_web::{test, web, App, HttpRequest, HttpResponse, HttpServer};
+use api_mevzuatgpt::alerts::smtp::SmtpConfig;
+use api_mevzuatgpt::alerts::target::check_url;
+use api_mevzuatgpt::alerts::{self, AlertConfig, AlertMessage, Notifier, Notifiers, RunReport, WebhookNotifier};
+use api_mevzuatgpt::auth::Scope;
+use api_mevzuatgpt::config::PublicUrls;
+use api_mevzuatgpt::corpus::CorpusRegistry;
+use api_mevzuatgpt::repository::{MemoryRepository, Repositories, SavedSearchRecord};
+use api_mevzuatgpt::webhooks;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::Utc;
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+#[macro_use]
+mod common;
+use common::{state, status_and_body, with_key};
+
+// Kayıtlı aramalar: sahiplik, ilk çalışmada imleç kurulması, yeni eşleşmelerin gelen kutusuna
+// yazılması ve webhook/SMTP ile gönderilmesi
+
+// Alıcının kaydettiği istek: (signature, event, gövde)
+type Received = Arc<Mutex<Vec<(String, String, String)>>>;
+
+async fn receive(req: HttpRequest, body: String, received: web::Data<Received>) -> HttpResponse {
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string()
+    };
+    received
+        .lock()
+        .unwrap()
+        .push((header(webhooks::SIGNATURE_HEADER), header(webhooks::EVENT_HEADER), body));
+    HttpResponse::NoContent().finish()
+}
+
+// SMTP alıcısının kaydettiği oturum: (komutlar, DATA içeriği)
+type Mails = Arc<Mutex<Vec<(Vec<String>, String)>>>;
+
+// Yerel SMTP sunucusu yerine geçen alıcı: komutları ve DATA içeriğini kaydeder
+async fn smtp_stand_in(listener: TcpListener, mails: Mails) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            return;
+        };
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+        writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+        let mut commands = Vec::new();
+        let mut data = String::new();
+        let mut in_data = false;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await.unwrap() == 0 {
+                break;
+            }
+            if in_data {
+                if line == ".\r\n" {
+                    in_data = false;
+                    writer.write_all(b"250 2.0.0 kuyrukta\r\n").await.unwrap();
+                } else {
+                    data.push_str(&line);
+                }
+                continue;
+            }
+            let command = line.trim_end().to_string();
+            let reply: &[u8] = match command.split(' ').next().unwrap_or_default() {
+                "EHLO" => b"250-localhost\r\n250 8BITMIME\r\n",
+                "DATA" => {
+                    in_data = true;
+                    b"354 devam\r\n"
+                }
+                "QUIT" => b"221 hoscakal\r\n",
+                _ => b"250 tamam\r\n",
+            };
+            commands.push(command.clone());
+            writer.write_all(reply).await.unwrap();
+            if command == "QUIT" {
+                break;
+            }
+        }
+        mails.lock().unwrap().push((commands, data));
+    }
+}
+
+#[actix_web::test]
+async fn saved_searches_alert_owners_through_inbox_webhook_and_email() {
+    let received: Received = Arc::new(Mutex::new(Vec::new()));
+    let receiver_state = web::Data::new(received.clone());
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(receiver_state.clone())
+            .default_service(web::post().to(receive))
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let base = format!("http://{}", server.addrs()[0]);
+    let server = server.run();
+    let handle = server.handle();
+    actix_web::rt::spawn(server);
+
+    let mails: Mails = Arc::new(Mutex::new(Vec::new()));
+    let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+    let smtp_port = listener.local_addr().unwrap().port();
+    actix_web::rt::spawn(smtp_stand_in(listener, mails.clone()));
+
+    let store = Arc::new(MemoryRepository::new());
+    let repos = Repositories::memory(store.clone());
+    let corpora = CorpusRegistry::builtin();
+    let sgk_id = ObjectId::new().to_hex();
+    store.insert("kurumlar", doc! { "_id": ObjectId::parse_str(&sgk_id).unwrap(), "kurum_adi": "Sosyal Güvenlik Kurumu" });
+    store.insert("metadata", doc! {
+        "kurum_id": &sgk_id,
+        "pdf_adi": "Prim Affı Genelgesi 2023",
+        "url_slug": "prim-affi-genelgesi-2023",
+        "belge_turu": "Genelge",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-03-01",
+    });
+
+    let key = common::key(&store, "uzman", &[Scope::Search]).await;
+    let other_key = common::key(&store, "baska", &[Scope::Search]).await;
+    // Webhook alıcısı yerelde çalıştığından iç ağ denetiminden muaf tutulur
+    let mut app_state = state(repos.clone());
+    app_state.saved_searches.allowed_hosts = vec!["127.0.0.1".to_string()];
+    let app = app!(app_state);
+    let post = |uri: &str, key: &str, body: Value| with_key(test::TestRequest::post().uri(uri), key).set_json(body).to_request();
+    let get = |uri: &str, key: &str| with_key(test::TestRequest::get().uri(uri), key).to_request();
+
+    // Kayıtlı aramalar anahtara bağlıdır
+    let req = test::TestRequest::post()
+        .uri("/api/v1/search/saved")
+        .set_json(json!({ "q": "prim affı" }))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 401);
+
+    let req = post("/api/v1/search/saved", &key, json!({ "q": " ", "kanal": "email", "hedef": "uzman" }));
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    let fields: Vec<&str> = body["error"]["fields"].as_array().unwrap().iter().map(|f| f["field"].as_str().unwrap()).collect();
+    assert_eq!(fields, ["q", "hedef"]);
+
+    let req = post(
+        "/api/v1/search/saved",
+        &key,
+        json!({ "ad": "SGK prim affı genelgeleri", "q": "prim affı", "kurum_id": sgk_id, "belge_turu": "Genelge" }),
+    );
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    assert_eq!(body["data"]["kanal"], "inbox");
+    assert_eq!(body["data"]["corpus"], "mevzuat");
+    assert!(body["data"]["secret"].is_null());
+    let inbox_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let req = post(
+        "/api/v1/search/saved",
+        &key,
+        json!({ "q": "prim affı", "kanal": "webhook", "hedef": format!("{}/hook", base) }),
+    );
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    assert_eq!(body["data"]["ad"], "prim affı");
+    let secret = body["data"]["secret"].as_str().unwrap().to_string();
+    assert!(secret.starts_with("whsec_"));
+
+    let req = post(
+        "/api/v1/search/saved",
+        &key,
+        json!({ "ad": "Prim affı", "q": "prim affı", "kanal": "email", "hedef": "uzman@example.com" }),
+    );
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    let email_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (_, body) = status_and_body(test::call_service(&app, get("/api/v1/search/saved", &key)).await).await;
+    assert_eq!(body["count"], 3);
+    let (_, body) = status_and_body(test::call_service(&app, get("/api/v1/search/saved", &other_key)).await).await;
+    assert_eq!(body["count"], 0);
+    let req = get(&format!("/api/v1/search/saved/{}", inbox_id), &other_key);
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 404);
+
+    let config = AlertConfig {
+        smtp: Some(SmtpConfig {
+            host: "127.0.0.1".to_string(),
+            port: smtp_port,
+            from: "bildirim@mevzuatgpt.org".to_string(),
+            timeout: Duration::from_secs(5),
+        }),
+        allowed_hosts: vec!["127.0.0.1".to_string()],
+        ..AlertConfig::default()
+    };
+    let notifiers = Notifiers::new(&config);
+    let urls = PublicUrls::default();
+
+    // İlk çalışma yalnızca imleci kurar; mevcut belgeler bildirilmez
+    let report = alerts::run(&repos, &corpora, &urls, &notifiers, Utc::now()).await.unwrap();
+    assert_eq!(report, RunReport { searches: 3, ..RunReport::default() });
+
+    store.insert("metadata", doc! {
+        "kurum_id": &sgk_id,
+        "pdf_adi": "Prim Affı Uygulama Genelgesi",
+        "url_slug": "prim-affi-uygulama-genelgesi",
+        "belge_turu": "Genelge",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-06-01T10:00:00Z",
+    });
+    store.insert("metadata", doc! {
+        "kurum_id": ObjectId::new().to_hex(),
+        "pdf_adi": "Prim Affı Yönetmeliği",
+        "url_slug": "prim-affi-yonetmeligi",
+        "belge_turu": "Yönetmelik",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-06-02T10:00:00Z",
+    });
+    store.insert("metadata", doc! {
+        "kurum_id": &sgk_id,
+        "pdf_adi": "Emeklilik Genelgesi",
+        "url_slug": "emeklilik-genelgesi",
+        "belge_turu": "Genelge",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-06-03T10:00:00Z",
+    });
+
+    let report = alerts::run(&repos, &corpora, &urls, &notifiers, Utc::now()).await.unwrap();
+    assert_eq!(report, RunReport { searches: 3, alerts: 3, failed: 0, errors: 0 });
+    assert_eq!(alerts::run(&repos, &corpora, &urls, &notifiers, Utc::now()).await.unwrap().alerts, 0);
+
+    // Gelen kutusu: kanaldan bağımsız tüm bildirimler, en yeni önce
+    let (status, body) = status_and_body(test::call_service(&app, get("/api/v1/search/alerts?okunmamis=true", &key)).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["count"], 3);
+    let inbox = body["data"].as_array().unwrap().iter().find(|alert| alert["saved_search_id"] == inbox_id.as_str()).unwrap();
+    assert_eq!(inbox["ad"], "SGK prim affı genelgeleri");
+    assert!(inbox["notification_status"].is_null());
+    let hits = inbox["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["pdf_adi"], "Prim Affı Uygulama Genelgesi");
+    assert_eq!(hits[0]["url"], "https://mevzuatgpt.org/belge/prim-affi-uygulama-genelgesi");
+    assert_eq!(hits[0]["kurum_adi"], "Sosyal Güvenlik Kurumu");
+    let inbox_alert_id = inbox["id"].as_str().unwrap().to_string();
+    let email_alert = body["data"].as_array().unwrap().iter().find(|alert| alert["saved_search_id"] == email_id.as_str()).unwrap();
+    assert_eq!(email_alert["notification_status"], "delivered");
+    assert_eq!(email_alert["hits"].as_array().unwrap().len(), 2);
+
+    let (_, body) = status_and_body(test::call_service(&app, get("/api/v1/search/alerts", &other_key)).await).await;
+    assert_eq!(body["count"], 0);
+    let req = post(&format!("/api/v1/search/alerts/{}/read", inbox_alert_id), &other_key, json!({}));
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 404);
+
+    let req = post(&format!("/api/v1/search/alerts/{}/read", inbox_alert_id), &key, json!({}));
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert!(body["data"]["read_at"].is_string());
+    let (_, body) = status_and_body(test::call_service(&app, get("/api/v1/search/alerts?okunmamis=true", &key)).await).await;
+    assert_eq!(body["count"], 2);
+
+    // Webhook: aramanın anahtarıyla imzalı, tüm yeni eşleşmeler
+    {
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let (signature, event, body) = &received[0];
+        assert_eq!(event, alerts::EVENT_SAVED_SEARCH_MATCHED);
+        let (t, v1) = signature.split_once(",v1=").unwrap();
+        let t: i64 = t.trim_start_matches("t=").parse().unwrap();
+        assert_eq!(v1, webhooks::sign(&secret, t, body));
+        let payload: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(payload["type"], "saved_search.matched");
+        let titles: Vec<&str> = payload["data"]["hits"].as_array().unwrap().iter().map(|hit| hit["pdf_adi"].as_str().unwrap()).collect();
+        assert_eq!(titles, ["Prim Affı Yönetmeliği", "Prim Affı Uygulama Genelgesi"]);
+    }
+
+    // E-posta: yerel SMTP alıcısına zarf ve base64 UTF-8 gövde
+    {
+        let mails = mails.lock().unwrap();
+        assert_eq!(mails.len(), 1);
+        let (commands, data) = &mails[0];
+        assert_eq!(
+            commands,
+            &[
+                "EHLO mevzuatgpt.org",
+                "MAIL FROM:<bildirim@mevzuatgpt.org>",
+                "RCPT TO:<uzman@example.com>",
+                "DATA",
+                "QUIT",
+            ]
+        );
+        let (headers, body) = data.split_once("\r\n\r\n").unwrap();
+        assert!(headers.contains("To: <uzman@example.com>"));
+        assert!(headers.contains("Subject: =?UTF-8?B?"));
+        assert!(headers.contains("Content-Type: text/plain; charset=utf-8"));
+        let text = String::from_utf8(STANDARD.decode(body.replace("\r\n", "")).unwrap()).unwrap();
+        assert!(text.contains("\"Prim affı\" kayıtlı aramanız için 2 yeni belge bulundu."));
+        assert!(text.contains("https://mevzuatgpt.org/belge/prim-affi-yonetmeligi"));
+    }
+
+    // Sorgu değişince imleç sıfırlanır; sonraki çalışma yeniden yalnızca imleci kurar
+    let req = with_key(test::TestRequest::patch().uri(&format!("/api/v1/search/saved/{}", email_id)), &key)
+        .set_json(json!({ "q": "emeklilik" }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["data"]["hedef"], "uzman@example.com");
+    assert!(body["data"]["last_run_at"].is_string());
+    assert_eq!(alerts::run(&repos, &corpora, &urls, &notifiers, Utc::now()).await.unwrap().alerts, 0);
+
+    // Silinen aramanın bildirimleri de silinir
+    let req = with_key(test::TestRequest::delete().uri(&format!("/api/v1/search/saved/{}", inbox_id)), &key).to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 200);
+    let (_, body) = status_and_body(test::call_service(&app, get("/api/v1/search/alerts", &key)).await).await;
+    assert_eq!(body["count"], 2);
+
+    handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn webhook_targets_on_internal_networks_are_rejected() {
+    let store = Arc::new(MemoryRepository::new());
+    let key = common::key(&store, "uzman", &[Scope::Search]).await;
+    let app = app!(state(Repositories::memory(store.clone())));
+
+    for hedef in [
+        "http://127.0.0.1:27017/",
+        "http://169.254.169.254/latest/meta-data",
+        "http://localhost/hook",
+        "http://10.0.0.5/hook",
+        "http://[::1]/hook",
+        "http://[fd00::1]/hook",
+        "http://[::ffff:192.168.1.1]/hook",
+        "http://mongo:27017/",
+        "http://metadata.google.internal/",
+    ] {
+        let req = with_key(test::TestRequest::post().uri("/api/v1/search/saved"), &key)
+            .set_json(json!({ "q": "prim affı", "kanal": "webhook", "hedef": hedef }))
+            .to_request();
+        let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+        assert_eq!(status, 422, "{}", hedef);
+        assert_eq!(body["error"]["fields"][0]["field"], "hedef");
+        assert_eq!(body["error"]["fields"][0]["code"], "NOT_ALLOWED", "{}", hedef);
+    }
+
+    for hedef in ["https://hooks.example.com/mevzuat", "http://93.184.216.34/hook"] {
+        assert!(check_url(hedef, &[]).is_ok(), "{}", hedef);
+    }
+    assert!(check_url("http://127.0.0.1:8080/hook", &["127.0.0.1".to_string()]).is_ok());
+
+    // Kayıttan sonra iç adrese yönelen hedeflere gönderimde de istek atılmaz
+    let notifier = WebhookNotifier::new(Duration::from_secs(5), Vec::new());
+    let message = AlertMessage {
+        alert_id: ObjectId::new().to_hex(),
+        search: SavedSearchRecord {
+            kanal: alerts::CHANNEL_WEBHOOK.to_string(),
+            hedef: Some("http://127.0.0.1:9/hook".to_string()),
+            ..SavedSearchRecord::default()
+        },
+        hits: Vec::new(),
+        created_at: "2024-06-01T10:00:00.000Z".to_string(),
+    };
+    let error = notifier.notify(&message).await.unwrap_err();
+    assert!(error.contains("İç ağ"), "{}", error);
+}
+
+#[actix_web::test]
+async fn matches_beyond_one_alert_are_delivered_in_later_runs() {
+    let store = Arc::new(MemoryRepository::new());
+    let repos = Repositories::memory(store.clone());
+    let corpora = CorpusRegistry::builtin();
+    let key = common::key(&store, "uzman", &[Scope::Search]).await;
+    let app = app!(state(repos.clone()));
+    let req = with_key(test::TestRequest::post().uri("/api/v1/search/saved"), &key)
+        .set_json(json!({ "q": "prim affı", "belge_turu": "Genelge" }))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 201);
+    let notifiers = Notifiers::new(&AlertConfig::default());
+    let urls = PublicUrls::default();
+    assert_eq!(alerts::run(&repos, &corpora, &urls, &notifiers, Utc::now()).await.unwrap().alerts, 0);
+
+    // Bir sayfadan (100) ve bir bildirimden (50) fazla eşleşme; aralarında filtreye uymayanlar da var
+    for i in 0..130 {
+        store.insert("metadata", doc! {
+            "pdf_adi": format!("Prim Affı Genelgesi {}", i),
+            "url_slug": format!("prim-affi-genelgesi-{}", i),
+            "belge_turu": if i % 10 == 9 { "Yönetmelik" } else { "Genelge" },
+            "status": "aktif",
+            "olusturulma_tarihi": format!("2024-06-01T{:02}:{:02}:00.000Z", i / 60, i % 60),
+        });
+    }
+
+    let mut delivered = Vec::new();
+    for expected in [50, 50, 17, 0] {
+        let report = alerts::run(&repos, &corpora, &urls, &notifiers, Utc::now()).await.unwrap();
+        assert_eq!(report.alerts, usize::from(expected > 0));
+        if expected == 0 {
+            continue;
+        }
+        let req = with_key(test::TestRequest::get().uri("/api/v1/search/alerts?okunmamis=true"), &key).to_request();
+        let (_, body) = status_and_body(test::call_service(&app, req).await).await;
+        let alert = &body["data"][0];
+        let hits = alert["hits"].as_array().unwrap();
+        assert_eq!(hits.len(), expected);
+        delivered.extend(hits.iter().rev().map(|hit| hit["url_slug"].as_str().unwrap().to_string()));
+        let uri = format!("/api/v1/search/alerts/{}/read", alert["id"].as_str().unwrap());
+        let req = with_key(test::TestRequest::post().uri(&uri), &key).set_json(json!({})).to_request();
+        assert_eq!(test::call_service(&app, req).await.status().as_u16(), 200);
+    }
+    let expected: Vec<String> = (0..130).filter(|i| i % 10 != 9).map(|i| format!("prim-affi-genelgesi-{}", i)).collect();
+    assert_eq!(delivered, expected);
+}
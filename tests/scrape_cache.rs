@@ -0,0 +1,171 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use actix_web::http::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use actix_web::{dev::ServerHandle, web, App, HttpRequest, HttpResponse, HttpServer};
+use api_mevzuatgpt::scraping::{FetchConfig, Fetcher};
+use url::Url;
+
+// Duyuru sayfası önbelleği: TTL, stale-while-revalidate ve ETag/Last-Modified doğrulaması
+
+const LAST_MODIFIED_AT: &str = "Wed, 01 Oct 2025 08:00:00 GMT";
+
+#[derive(Default)]
+struct Origin {
+    body: String,
+    etag: String,
+    // Gelen isteklerin (If-None-Match, If-Modified-Since) başlıkları
+    requests: Vec<(Option<String>, Option<String>)>,
+}
+
+fn header(req: &HttpRequest, name: actix_web::http::header::HeaderName) -> Option<String> {
+    req.headers().get(name).and_then(|value| value.to_str().ok()).map(str::to_string)
+}
+
+async fn page(req: HttpRequest, origin: web::Data<Mutex<Origin>>) -> HttpResponse {
+    let mut origin = origin.lock().unwrap();
+    let if_none_match = header(&req, IF_NONE_MATCH);
+    origin.requests.push((if_none_match.clone(), header(&req, IF_MODIFIED_SINCE)));
+    if if_none_match.as_deref() == Some(origin.etag.as_str()) {
+        return HttpResponse::NotModified().finish();
+    }
+    HttpResponse::Ok()
+        .insert_header((ETAG, origin.etag.clone()))
+        .insert_header((LAST_MODIFIED, LAST_MODIFIED_AT))
+        .body(origin.body.clone())
+}
+
+struct Site {
+    origin: Arc<Mutex<Origin>>,
+    url: Url,
+    handle: ServerHandle,
+}
+
+impl Site {
+    async fn start() -> Self {
+        let origin = Arc::new(Mutex::new(Origin::default()));
+        let data = web::Data::from(origin.clone());
+        let server = HttpServer::new(move || App::new().app_data(data.clone()).route("/duyurular", web::get().to(page)))
+            .workers(1)
+            .bind(("127.0.0.1", 0))
+            .unwrap();
+        let url = Url::parse(&format!("http://{}/duyurular", server.addrs()[0])).unwrap();
+        let server = server.run();
+        let handle = server.handle();
+        actix_web::rt::spawn(server);
+        let site = Site { origin, url, handle };
+        site.publish("v1", "\"v1\"");
+        site
+    }
+
+    fn publish(&self, body: &str, etag: &str) {
+        let mut origin = self.origin.lock().unwrap();
+        origin.body = body.to_string();
+        origin.etag = etag.to_string();
+    }
+
+    fn requests(&self) -> Vec<(Option<String>, Option<String>)> {
+        self.origin.lock().unwrap().requests.clone()
+    }
+
+    async fn wait_for_requests(&self, count: usize) {
+        for _ in 0..100 {
+            if self.requests().len() >= count {
+                return;
+            }
+            actix_web::rt::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("{} istek bekleniyordu, gelen: {:?}", count, self.requests());
+    }
+}
+
+fn fetcher(ttl: u64, stale: u64) -> Fetcher {
+    Fetcher::new(FetchConfig {
+        ttl: Duration::from_secs(ttl),
+        stale_while_revalidate: Duration::from_secs(stale),
+        ..FetchConfig::default()
+    })
+}
+
+fn conditional(etag: &str) -> (Option<String>, Option<String>) {
+    (Some(etag.to_string()), Some(LAST_MODIFIED_AT.to_string()))
+}
+
+#[actix_web::test]
+async fn fresh_pages_are_served_from_cache() {
+    let site = Site::start().await;
+    let fetcher = fetcher(3600, 0);
+
+    assert_eq!(fetcher.fetch(&site.url).await.unwrap(), "v1");
+    site.publish("v2", "\"v2\"");
+    // Klonlar aynı önbelleği paylaşır
+    assert_eq!(fetcher.clone().fetch(&site.url).await.unwrap(), "v1");
+    assert_eq!(site.requests(), vec![(None, None)]);
+
+    site.handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn expired_pages_are_revalidated_with_validators() {
+    let site = Site::start().await;
+    let fetcher = fetcher(0, 0);
+
+    assert_eq!(fetcher.fetch(&site.url).await.unwrap(), "v1");
+    // 304: gövde yeniden indirilmez, önbellekteki döner
+    assert_eq!(fetcher.fetch(&site.url).await.unwrap(), "v1");
+    site.publish("v2", "\"v2\"");
+    assert_eq!(fetcher.fetch(&site.url).await.unwrap(), "v2");
+
+    assert_eq!(site.requests(), vec![(None, None), conditional("\"v1\""), conditional("\"v1\"")]);
+    site.handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn stale_pages_are_served_while_revalidating() {
+    let site = Site::start().await;
+    let fetcher = fetcher(0, 3600);
+
+    assert_eq!(fetcher.fetch(&site.url).await.unwrap(), "v1");
+    site.publish("v2", "\"v2\"");
+
+    // Bayat sayfa beklemeden döner, yenileme arka planda yapılır
+    assert_eq!(fetcher.fetch(&site.url).await.unwrap(), "v1");
+    site.wait_for_requests(2).await;
+    assert_eq!(site.requests()[1], conditional("\"v1\""));
+
+    // Yenileme kaydı güncellemiş olmalı; olmadıysa kısa bir süre daha beklenir
+    let mut body = fetcher.fetch(&site.url).await.unwrap();
+    for _ in 0..50 {
+        if body == "v2" {
+            break;
+        }
+        actix_web::rt::time::sleep(Duration::from_millis(20)).await;
+        body = fetcher.fetch(&site.url).await.unwrap();
+    }
+    assert_eq!(body, "v2");
+    site.handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn least_recently_used_pages_are_evicted() {
+    let site = Site::start().await;
+    let fetcher = Fetcher::new(FetchConfig {
+        ttl: Duration::from_secs(3600),
+        stale_while_revalidate: Duration::from_secs(0),
+        max_pages: 2,
+        ..FetchConfig::default()
+    });
+    let page = |n: u32| site.url.join(&format!("?sayfa={}", n)).unwrap();
+
+    for n in [1, 2, 1, 3] {
+        fetcher.fetch(&page(n)).await.unwrap();
+    }
+    assert_eq!(site.requests().len(), 3);
+
+    // Sayfa 1 yeni kullanıldığı için önbellekte kalır; en eski kullanılan sayfa 2 çıkarılmıştır
+    fetcher.fetch(&page(1)).await.unwrap();
+    assert_eq!(site.requests().len(), 3);
+    fetcher.fetch(&page(2)).await.unwrap();
+    assert_eq!(site.requests().len(), 4);
+    assert_eq!(site.requests()[3], (None, None));
+    site.handle.stop(false).await;
+}
@@ -0,0 +1,134 @@
+use api_mevzuatgpt::models::kurum_duyuru_scraped::DuyuruItem;
+use api_mevzuatgpt::repository::AnnouncementSourceRecord;
+use api_mevzuatgpt::scraping::{scraper_for, PatternScraper, RuleScraper, ScrapeRules, Scraper};
+use url::Url;
+
+// Duyuru sayfalarının kurallarla ve hazır desenlerle okunması (sabit HTML, ağ yok)
+
+const RULE_PAGE: &str = r##"
+<html><body>
+  <nav><a href="/">Ana Sayfa</a></nav>
+  <ul class="duyurular">
+    <li>
+      <a href="/duyuru/42" title="Asgari ücret tespit komisyonu toplandı">Asgari ücret...</a>
+      <span class="tarih">3 Mart 2025</span>
+    </li>
+    <li>
+      <a href="https://www.csgb.gov.tr/duyuru/41">  İş sağlığı ve
+        güvenliği haftası  </a>
+      <span class="tarih">27 Şub 2025</span>
+    </li>
+    <li><span class="tarih">Bağlantısız öğe</span></li>
+    <li><a href="#">Boş bağlantı</a></li>
+  </ul>
+  <a rel="next" href="?sayfa=2">Sonraki</a>
+</body></html>
+"##;
+
+fn page_url() -> Url {
+    Url::parse("https://www.csgb.gov.tr/duyurular").unwrap()
+}
+
+fn rules() -> ScrapeRules {
+    ScrapeRules {
+        item: "ul.duyurular > li".to_string(),
+        date: Some("span.tarih".to_string()),
+        date_format: Some("%d %B %Y".to_string()),
+        next_page: Some("a[rel=next]".to_string()),
+        max_pages: Some(3),
+        ..ScrapeRules::default()
+    }
+}
+
+fn titles(items: &[DuyuruItem]) -> Vec<&str> {
+    items.iter().map(|item| item.baslik.as_str()).collect()
+}
+
+#[test]
+fn rule_scraper_reads_items_with_selectors() {
+    let scraper = RuleScraper::new(&rules()).unwrap();
+    let items = scraper.extract(RULE_PAGE, &page_url());
+
+    assert_eq!(titles(&items), ["Asgari ücret...", "İş sağlığı ve güvenliği haftası"]);
+    assert_eq!(items[0].link, "https://www.csgb.gov.tr/duyuru/42");
+    assert_eq!(items[0].tarih.as_deref(), Some("03.03.2025"));
+    assert_eq!(items[1].link, "https://www.csgb.gov.tr/duyuru/41");
+    assert_eq!(items[1].tarih.as_deref(), Some("27.02.2025"));
+
+    assert_eq!(
+        scraper.next_page(RULE_PAGE, &page_url()).unwrap().as_str(),
+        "https://www.csgb.gov.tr/duyurular?sayfa=2"
+    );
+    assert_eq!(scraper.max_pages(), 3);
+
+    // Başlık attribute'tan okunabilir
+    let scraper = RuleScraper::new(&ScrapeRules {
+        title_attribute: Some("title".to_string()),
+        ..rules()
+    })
+    .unwrap();
+    let items = scraper.extract(RULE_PAGE, &page_url());
+    assert_eq!(titles(&items), ["Asgari ücret tespit komisyonu toplandı"]);
+}
+
+#[test]
+fn rule_scraper_falls_back_to_dates_in_item_text() {
+    let html = r#"<div class="haber"><a href="/h/1">Yeni yönetmelik yayımlandı</a> Yayın: 05.01.2024</div>
+                  <div class="haber"><a href="/h/2">Tarihsiz duyuru</a></div>"#;
+    let scraper = RuleScraper::new(&ScrapeRules {
+        item: "div.haber".to_string(),
+        ..ScrapeRules::default()
+    })
+    .unwrap();
+    let items = scraper.extract(html, &page_url());
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].tarih.as_deref(), Some("05.01.2024"));
+    // Tarih bulunamazsa uydurulmaz
+    assert_eq!(items[1].tarih, None);
+    assert!(scraper.next_page(html, &page_url()).is_none());
+    assert_eq!(scraper.max_pages(), 1);
+}
+
+#[test]
+fn pattern_presets_read_known_sites() {
+    let sgk = r#"
+        <div class="item"><a href="/Duyuru/Detay/Prim-Borcu-2025">Prim borcu yapılandırması başvuruları</a>
+        <span>23 Eylül 2025</span></div>
+        <a href="/Home/Iletisim">İletişim bilgilerimiz ve adreslerimiz</a>"#;
+    let items = PatternScraper::sgk().extract(sgk, &Url::parse("https://www.sgk.gov.tr/Duyuru").unwrap());
+    assert_eq!(titles(&items), ["Prim borcu yapılandırması başvuruları"]);
+    assert_eq!(items[0].link, "https://www.sgk.gov.tr/Duyuru/Detay/Prim-Borcu-2025");
+    assert_eq!(items[0].tarih.as_deref(), Some("23.09.2025"));
+
+    let iskur = r#"<li><a href="/duyurular/uyum-programi/" title="İşgücü uyum programı başvuruları">Detay</a>
+                   <small>11 Ağu 2025</small></li>"#;
+    let items = PatternScraper::iskur().extract(iskur, &Url::parse("https://www.iskur.gov.tr/duyurular").unwrap());
+    assert_eq!(titles(&items), ["İşgücü uyum programı başvuruları"]);
+    assert_eq!(items[0].tarih.as_deref(), Some("11.08.2025"));
+}
+
+#[test]
+fn scraper_is_chosen_from_source_rules() {
+    let mut source = AnnouncementSourceRecord {
+        duyuru_linki: "https://www.csgb.gov.tr/duyurular".to_string(),
+        scraper: Some(rules()),
+        ..AnnouncementSourceRecord::default()
+    };
+    let scraper = scraper_for(&source).unwrap();
+    assert_eq!(scraper.extract(RULE_PAGE, &page_url()).len(), 2);
+
+    // Kayda doğrudan yazılmış geçersiz kural sessizce yok sayılmaz
+    source.scraper = Some(ScrapeRules {
+        item: "li[".to_string(),
+        ..ScrapeRules::default()
+    });
+    assert!(scraper_for(&source).is_err());
+
+    // Kuralsız ve tanınmayan domain: genel desen; Yargıtay'a özgü /item/ linkleri aranmaz
+    source.scraper = None;
+    let html = r#"<a href="/item/12/karar">Yargıtay kararı bildirimi yayımlandı</a>
+                  <a href="/duyuru/7">Kurum duyurusu yayımlandı</a>"#;
+    let items = scraper_for(&source).unwrap().extract(html, &page_url());
+    assert_eq!(items[0].baslik, "Kurum duyurusu yayımlandı");
+}
@@ -0,0 +1,135 @@
+use std::fs;
+use std::path::PathBuf;
+use actix_web::{dev::ServerHandle, web, App, HttpResponse, HttpServer};
+use api_mevzuatgpt::scraping::{self, FetchConfig, Fetcher, PatternScraper, RuleScraper, ScrapeRules, Scraper};
+use serde_json::Value;
+
+// Scraper'ların kayıtlı HTML sayfalarına karşı snapshot testleri. Sayfalar tests/fixtures/scrapers
+// altından yerel bir HTTP sunucusuyla servis edilir; çıkarılan duyurular <ad>.snap.json ile
+// karşılaştırılır. Site markup'ı değiştiğinde yeni HTML fixture'a kaydedilir ve farklar raporlanır.
+// Bilinçli değişikliklerden sonra snapshot'lar güncellenir:
+//
+//     UPDATE_SCRAPER_SNAPSHOTS=1 cargo test --test scraper_fixtures
+
+fn fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/scrapers")
+}
+
+async fn serve_fixture(name: web::Path<String>) -> HttpResponse {
+    match fs::read_to_string(fixture_dir().join(format!("{}.html", name))) {
+        Ok(html) => HttpResponse::Ok().content_type("text/html; charset=utf-8").body(html),
+        Err(_) => HttpResponse::NotFound().finish(),
+    }
+}
+
+// Fixture sayfalarını servis eden yerel sunucu
+struct StandIn {
+    origin: String,
+    handle: ServerHandle,
+}
+
+impl StandIn {
+    async fn start() -> Self {
+        let server = HttpServer::new(|| App::new().route("/{name}", web::get().to(serve_fixture)))
+            .workers(1)
+            .bind(("127.0.0.1", 0))
+            .unwrap();
+        let origin = format!("http://{}", server.addrs()[0]);
+        let server = server.run();
+        let handle = server.handle();
+        actix_web::rt::spawn(server);
+        StandIn { origin, handle }
+    }
+
+    fn url(&self, name: &str) -> String {
+        format!("{}/{}", self.origin, name)
+    }
+
+    async fn stop(self) {
+        self.handle.stop(false).await;
+    }
+}
+
+// Fixture adı ve sayfayı okuyacak scraper; <ad>.rules.json varsa kurallar oradan okunur
+fn cases() -> Vec<(&'static str, Box<dyn Scraper>)> {
+    let rules = |name: &str| -> ScrapeRules {
+        let path = fixture_dir().join(format!("{}.rules.json", name));
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    };
+    vec![
+        ("yargitay", Box::new(PatternScraper::yargitay())),
+        ("sgk", Box::new(PatternScraper::sgk())),
+        ("iskur", Box::new(PatternScraper::iskur())),
+        ("csgb", Box::new(RuleScraper::new(&rules("csgb")).unwrap())),
+    ]
+}
+
+// Linklerdeki rastgele port snapshot'a girmesin diye sunucu adresi {origin} ile değiştirilir
+fn snapshot(items: &[api_mevzuatgpt::models::kurum_duyuru_scraped::DuyuruItem], origin: &str) -> Value {
+    let json = serde_json::to_string(items).unwrap().replace(origin, "{origin}");
+    serde_json::from_str(&json).unwrap()
+}
+
+// Beklenen ve bulunan duyuruları satır satır karşılaştırır
+fn regressions(name: &str, expected: &Value, actual: &Value) -> Vec<String> {
+    let empty = Vec::new();
+    let expected = expected.as_array().unwrap_or(&empty);
+    let actual = actual.as_array().unwrap_or(&empty);
+    let mut report = Vec::new();
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(e), Some(a)) if e == a => {}
+            (Some(e), Some(a)) => report.push(format!("{} #{}: beklenen {}, bulunan {}", name, index, e, a)),
+            (Some(e), None) => report.push(format!("{} #{}: eksik {}", name, index, e)),
+            (None, Some(a)) => report.push(format!("{} #{}: fazladan {}", name, index, a)),
+            (None, None) => {}
+        }
+    }
+    report
+}
+
+#[actix_web::test]
+async fn scrapers_match_fixture_snapshots() {
+    let stand_in = StandIn::start().await;
+    let update = std::env::var("UPDATE_SCRAPER_SNAPSHOTS").is_ok_and(|value| value == "1");
+    let fetcher = Fetcher::new(FetchConfig::default());
+
+    let mut report = Vec::new();
+    for (name, scraper) in cases() {
+        let items = match scraping::scrape(&fetcher, scraper.as_ref(), &stand_in.url(name)).await {
+            Ok(items) => items,
+            Err(e) => {
+                report.push(format!("{}: tarama başarısız: {}", name, e));
+                continue;
+            }
+        };
+        let actual = snapshot(&items, &stand_in.origin);
+        let path = fixture_dir().join(format!("{}.snap.json", name));
+        if update {
+            fs::write(&path, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+            continue;
+        }
+        match fs::read_to_string(&path) {
+            Ok(saved) => report.extend(regressions(name, &serde_json::from_str(&saved).unwrap(), &actual)),
+            Err(_) => report.push(format!("{}: snapshot yok ({})", name, path.display())),
+        }
+    }
+    stand_in.stop().await;
+
+    assert!(
+        report.is_empty(),
+        "Scraper çıktısı snapshot'lardan farklı:\n{}\n\nDeğişiklik bilinçliyse: UPDATE_SCRAPER_SNAPSHOTS=1 cargo test --test scraper_fixtures",
+        report.join("\n")
+    );
+}
+
+#[actix_web::test]
+async fn missing_first_page_fails_the_scrape() {
+    let stand_in = StandIn::start().await;
+    let fetcher = Fetcher::new(FetchConfig::default());
+    let result = scraping::scrape(&fetcher, &PatternScraper::generic(), &stand_in.url("olmayan-sayfa")).await;
+    stand_in.stop().await;
+
+    let error = result.unwrap_err();
+    assert!(error.contains("404"), "{}", error);
+}
@@ -0,0 +1,121 @@
+use std::io::Read;
+use std::sync::Arc;
+use actix_web::test;
+use api_mevzuatgpt::corpus::YARGITAY_KURUM_ID;
+use api_mevzuatgpt::repository::MemoryRepository;
+use api_mevzuatgpt::sitemap::{ChildName, SitemapConfig};
+use flate2::read::GzDecoder;
+use mongodb::bson::{doc, oid::ObjectId};
+use regex::Regex;
+
+#[macro_use]
+mod common;
+use common::memory_state;
+
+// Sitemap index'i ve parçalı alt sitemap'ler: bölümler, sayfalama, gzip ve lastmod
+
+fn locs(xml: &str) -> Vec<String> {
+    let re = Regex::new(r"<loc>([^<]+)</loc>").unwrap();
+    re.captures_iter(xml).map(|cap| cap[1].to_string()).collect()
+}
+
+#[actix_web::test]
+async fn child_sitemap_names_round_trip() {
+    let parsed = ChildName::parse("mevzuat-2.xml.gz").unwrap();
+    assert_eq!(parsed, ChildName { section: "mevzuat".to_string(), page: 2, gzip: true });
+    assert_eq!(parsed.file_name(), "mevzuat-2.xml.gz");
+    assert_eq!(ChildName::parse("static.xml").unwrap().file_name(), "static.xml");
+    for name in ["mevzuat-0.xml", "mevzuat.xml", "-1.xml", "mevzuat-1.txt", "static-1.xml"] {
+        assert_eq!(ChildName::parse(name), None, "{}", name);
+    }
+    assert_eq!(SitemapConfig::default().chunks(50_001), 2);
+}
+
+#[actix_web::test]
+async fn sitemap_index_points_to_chunked_gzipped_sitemaps_for_every_section() {
+    let store = Arc::new(MemoryRepository::new());
+    store.insert("kurumlar", doc! { "_id": ObjectId::new(), "kurum_adi": "Sosyal Güvenlik Kurumu" });
+    store.insert("kurumlar", doc! { "_id": ObjectId::new(), "kurum_adi": "Adalet Bakanlığı" });
+    store.insert("kurumlar", doc! { "_id": ObjectId::new(), "kurum_adi": "Bilinmeyen Kurum" });
+    // _id sırası parçaların sırasıdır
+    for (slug, status, tarih) in [
+        ("emeklilik-genelgesi", "aktif", "2024-03-01"),
+        ("prim-yonetmeligi", "aktif", "bilinmiyor"),
+        ("mulga-yonetmelik", "pasif", "2020-01-01"),
+        ("arabuluculuk-yonetmeligi", "aktif", "2024-05-10T08:30:00Z"),
+    ] {
+        store.insert("metadata", doc! {
+            "_id": ObjectId::new(),
+            "url_slug": slug,
+            "status": status,
+            "olusturulma_tarihi": tarih,
+        });
+    }
+    store.insert("yargitay", doc! {
+        "kurum_id": YARGITAY_KURUM_ID,
+        "url_slug": "9-hd-karar",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-01-15",
+    });
+
+    let mut state = memory_state(store.clone());
+    state.sitemap = SitemapConfig { chunk_size: 2, gzip: true };
+    let app = app!(state);
+    let fetch = |uri: &str| test::TestRequest::get().uri(uri).to_request();
+
+    let resp = test::call_service(&app, fetch("/sitemap.xml")).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "application/xml");
+    let index = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+    assert!(index.contains("<sitemapindex"));
+    assert_eq!(
+        locs(&index),
+        [
+            "https://mevzuatgpt.org/sitemaps/static.xml.gz",
+            "https://mevzuatgpt.org/sitemaps/institutions-1.xml.gz",
+            "https://mevzuatgpt.org/sitemaps/mevzuat-1.xml.gz",
+            "https://mevzuatgpt.org/sitemaps/mevzuat-2.xml.gz",
+            "https://mevzuatgpt.org/sitemaps/yargitay-1.xml.gz",
+        ]
+    );
+
+    let resp = test::call_service(&app, fetch("/sitemaps/mevzuat-1.xml.gz")).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "application/gzip");
+    let compressed = test::read_body(resp).await;
+    let mut xml = String::new();
+    GzDecoder::new(&compressed[..]).read_to_string(&mut xml).unwrap();
+    assert!(xml.starts_with("<?xml") && xml.trim_end().ends_with("</urlset>"));
+    assert_eq!(
+        locs(&xml),
+        ["https://mevzuatgpt.org/belge/emeklilik-genelgesi", "https://mevzuatgpt.org/belge/prim-yonetmeligi"]
+    );
+    // Okunamayan tarih lastmod'a yazılmaz
+    assert_eq!(xml.matches("<lastmod>").count(), 1);
+    assert!(xml.contains("<lastmod>2024-03-01T00:00:00Z</lastmod>"));
+
+    // Aynı parça gzip'siz de alınabilir
+    let resp = test::call_service(&app, fetch("/sitemaps/mevzuat-2.xml")).await;
+    assert_eq!(resp.headers().get("content-type").unwrap(), "application/xml");
+    let xml = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+    assert_eq!(locs(&xml), ["https://mevzuatgpt.org/belge/arabuluculuk-yonetmeligi"]);
+
+    let body = test::call_and_read_body(&app, fetch("/sitemaps/yargitay-1.xml")).await;
+    assert_eq!(locs(std::str::from_utf8(&body).unwrap()), ["https://mevzuatgpt.org/yargitay/9-hd-karar"]);
+
+    let body = test::call_and_read_body(&app, fetch("/sitemaps/institutions-1.xml")).await;
+    assert_eq!(
+        locs(std::str::from_utf8(&body).unwrap()),
+        ["https://mevzuatgpt.org/kurum/adalet-bakanligi", "https://mevzuatgpt.org/kurum/sosyal-guvenlik-kurumu"]
+    );
+
+    let body = test::call_and_read_body(&app, fetch("/sitemaps/static.xml")).await;
+    assert_eq!(
+        locs(std::str::from_utf8(&body).unwrap()),
+        ["https://mevzuatgpt.org/", "https://mevzuatgpt.org/hakkinda", "https://mevzuatgpt.org/iletisim"]
+    );
+
+    for uri in ["/sitemaps/mevzuat-3.xml", "/sitemaps/institutions-2.xml", "/sitemaps/kanun-1.xml", "/sitemaps/mevzuat.txt"] {
+        assert_eq!(test::call_service(&app, fetch(uri)).await.status(), 404, "{}", uri);
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+use actix_web::test;
+use api_mevzuatgpt::corpus::YARGITAY_KURUM_ID;
+use api_mevzuatgpt::repository::MemoryRepository;
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::{json, Value};
+
+#[macro_use]
+mod common;
+use common::memory_state;
+
+// Önceden hesaplanmış istatistikler: corpus/kurum/belge türü/yayın ayı kırılımları, aylık seri
+// ve özetin yenilenene kadar sabit kalması
+
+#[actix_web::test]
+async fn statistics_are_served_from_refreshed_snapshot() {
+    let store = Arc::new(MemoryRepository::new());
+    let sgk_id = ObjectId::new();
+    store.insert("kurumlar", doc! { "_id": sgk_id, "kurum_adi": "Sosyal Güvenlik Kurumu" });
+    store.insert("kurumlar", doc! { "_id": ObjectId::parse_str(YARGITAY_KURUM_ID).unwrap(), "kurum_adi": "Yargıtay" });
+    let sgk = sgk_id.to_hex();
+    for (belge_turu, tarih) in [
+        ("Genelge", "2024-01-15"),
+        ("Genelge", "15 Ocak 2024"),
+        ("Genelge", "02.04.2024"),
+        ("Yönetmelik", "10 Mart 2024 Pazar"),
+        ("", "tarih yok"),
+    ] {
+        store.insert("metadata", doc! {
+            "kurum_id": &sgk,
+            "pdf_adi": format!("{} {}", belge_turu, tarih),
+            "belge_turu": belge_turu,
+            "belge_yayin_tarihi": tarih,
+        });
+    }
+    store.insert("metadata", doc! {
+        "kurum_id": ObjectId::new(),
+        "pdf_adi": "Başka Kurum Tebliği",
+        "belge_turu": "Tebliğ",
+        "belge_yayin_tarihi": "2023-12-01",
+    });
+    // Yargıtay kararlarında kurum_id yok; corpus'un kurumuna sayılır
+    store.insert("yargitay", doc! { "pdf_adi": "9. HD Kararı", "belge_turu": "Karar", "kararTarihi": "20.02.2024" });
+    store.insert("yargitay", doc! { "pdf_adi": "10. HD Kararı", "belge_turu": "Karar", "kararTarihi": "05.03.2024" });
+
+    let state = memory_state(store.clone());
+    let cache = state.statistics.clone();
+    let (repos, corpora) = (state.repos.clone(), state.corpora.clone());
+    let app = app!(state);
+    let get = |uri: &str| test::TestRequest::get().uri(uri).to_request();
+
+    let body: Value = test::call_and_read_body_json(&app, get("/api/v1/statistics")).await;
+    let data = &body["data"];
+    assert_eq!(data["total_kurumlar"], 2);
+    assert_eq!(data["total_belgeler"], 6);
+    assert_eq!(data["belge_turu_istatistik"][0], json!({ "belge_turu": "Genelge", "count": 3 }));
+    assert_eq!(data["corpus_istatistik"], json!([
+        { "corpus": "mevzuat", "title": "Mevzuat", "count": 6 },
+        { "corpus": "yargitay", "title": "Yargıtay Kararları", "count": 2 },
+    ]));
+    let computed_at = data["hesaplanma_tarihi"].as_str().unwrap().to_string();
+
+    // Farklı yazımlar aynı aya düşer; aradaki boş aylar 0 ile gelir
+    let body: Value = test::call_and_read_body_json(&app, get("/api/v1/statistics/timeseries")).await;
+    assert_eq!(body["data"]["aylar"], json!([
+        { "ay": "2023-12", "count": 1 },
+        { "ay": "2024-01", "count": 2 },
+        { "ay": "2024-02", "count": 1 },
+        { "ay": "2024-03", "count": 2 },
+        { "ay": "2024-04", "count": 1 },
+    ]));
+    assert_eq!(body["data"]["total_belgeler"], 7);
+    assert_eq!(body["data"]["tarihsiz_belgeler"], 1);
+
+    let uri = format!("/api/v1/statistics/timeseries?corpus=mevzuat&kurum_id={}&belge_turu=Genelge&from=2024-01&to=2024-05", sgk);
+    let body: Value = test::call_and_read_body_json(&app, get(&uri)).await;
+    let counts: Vec<u64> = body["data"]["aylar"].as_array().unwrap().iter().map(|month| month["count"].as_u64().unwrap()).collect();
+    assert_eq!(counts, [2, 0, 0, 1, 0]);
+    assert_eq!(body["data"]["aylar"][4]["ay"], "2024-05");
+    assert_eq!(body["data"]["total_belgeler"], 3);
+
+    let body: Value = test::call_and_read_body_json(&app, get(&format!("/api/v1/statistics/institutions/{}", sgk))).await;
+    let data = &body["data"];
+    assert_eq!(data["kurum_adi"], "Sosyal Güvenlik Kurumu");
+    assert_eq!(data["total_belgeler"], 5);
+    assert_eq!(data["corpus_istatistik"][0]["count"], 5);
+    assert_eq!(data["corpus_istatistik"][1]["count"], 0);
+    assert_eq!(data["belge_turu_istatistik"], json!([
+        { "belge_turu": "Genelge", "count": 3 },
+        { "belge_turu": "Belirtilmemiş", "count": 1 },
+        { "belge_turu": "Yönetmelik", "count": 1 },
+    ]));
+    assert_eq!(data["aylar"].as_array().unwrap().len(), 4);
+    assert_eq!(data["tarihsiz_belgeler"], 1);
+
+    let body: Value = test::call_and_read_body_json(&app, get(&format!("/api/v1/statistics/institutions/{}", YARGITAY_KURUM_ID))).await;
+    assert_eq!(body["data"]["kurum_adi"], "Yargıtay");
+    assert_eq!(body["data"]["corpus_istatistik"][1], json!({ "corpus": "yargitay", "title": "Yargıtay Kararları", "count": 2 }));
+
+    for (uri, status) in [
+        (format!("/api/v1/statistics/institutions/{}", ObjectId::new().to_hex()), 404),
+        ("/api/v1/statistics/timeseries?corpus=danistay".to_string(), 404),
+        ("/api/v1/statistics/timeseries?from=2024-13".to_string(), 400),
+        ("/api/v1/statistics/timeseries?from=2024-1".to_string(), 400),
+        ("/api/v1/statistics/timeseries?from=2024-05&to=2024-01".to_string(), 400),
+        ("/api/v1/statistics/timeseries?from=0001-01&to=9999-12".to_string(), 400),
+        ("/api/v1/statistics/timeseries?from=1950-01&to=2024-12".to_string(), 400),
+        ("/api/v1/statistics/timeseries?from=0001-01".to_string(), 400),
+    ] {
+        let resp = test::call_service(&app, get(&uri)).await;
+        assert_eq!(resp.status(), status, "{}", uri);
+    }
+
+    // Yeni belge bir sonraki yenilemeye kadar sayılmaz
+    store.insert("metadata", doc! { "kurum_id": &sgk, "belge_turu": "Genelge", "belge_yayin_tarihi": "2024-04-20" });
+    let body: Value = test::call_and_read_body_json(&app, get("/api/v1/statistics")).await;
+    assert_eq!(body["data"]["total_belgeler"], 6);
+    assert_eq!(body["data"]["hesaplanma_tarihi"], computed_at.as_str());
+
+    cache.refresh(&repos, &corpora).await.unwrap();
+    let body: Value = test::call_and_read_body_json(&app, get("/api/v1/statistics")).await;
+    assert_eq!(body["data"]["total_belgeler"], 7);
+    let body: Value = test::call_and_read_body_json(&app, get("/api/v1/statistics/timeseries?from=2024-04&to=2024-04")).await;
+    assert_eq!(body["data"]["aylar"], json!([{ "ay": "2024-04", "count": 2 }]));
+}
@@ -0,0 +1,250 @@
+use std::sync::Arc;
+use std::time::Duration;
+use actix_web::{middleware::from_fn, web, App, HttpServer};
+use api_mevzuatgpt::corpus::CorpusRegistry;
+use api_mevzuatgpt::repository::{MemoryRepository, Repositories};
+use api_mevzuatgpt::stream::{event_id, parse_event_id, Poller};
+use api_mevzuatgpt::{middleware, routes};
+use chrono::{DateTime, Utc};
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::Value;
+
+mod common;
+
+// Yeni belge akışı (SSE): filtreli canlı olaylar, Last-Event-ID ile kaçırılanların gönderilmesi
+// ve geçersiz istekler
+
+// Akıştan gelen olay çerçeveleri: (id, event, data)
+type Frames = Vec<(String, String, Value)>;
+
+// Yorum/retry çerçevelerini atlayarak count kadar olay okur
+async fn read_events(response: &mut reqwest::Response, buffer: &mut String, count: usize) -> Frames {
+    let mut frames = Frames::new();
+    while frames.len() < count {
+        while let Some(end) = buffer.find("\n\n") {
+            let frame: String = buffer.drain(..end + 2).collect();
+            let field = |name: &str| {
+                frame
+                    .lines()
+                    .find_map(|line| line.strip_prefix(&format!("{}: ", name)))
+                    .map(str::to_string)
+            };
+            if let (Some(id), Some(event), Some(data)) = (field("id"), field("event"), field("data")) {
+                frames.push((id, event, serde_json::from_str(&data).unwrap()));
+            }
+        }
+        if frames.len() >= count {
+            break;
+        }
+        let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk())
+            .await
+            .expect("akıştan olay gelmedi")
+            .unwrap()
+            .expect("akış kapandı");
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+    frames
+}
+
+#[actix_web::test]
+async fn document_stream_pushes_filtered_events_and_replays_after_last_event_id() {
+    let store = Arc::new(MemoryRepository::new());
+    let repos = Repositories::memory(store.clone());
+    let corpora = CorpusRegistry::builtin();
+    let sgk_id = ObjectId::new().to_hex();
+    store.insert("kurumlar", doc! { "_id": ObjectId::parse_str(&sgk_id).unwrap(), "kurum_adi": "Sosyal Güvenlik Kurumu" });
+    let existing_id = store.insert("metadata", doc! {
+        "kurum_id": &sgk_id,
+        "pdf_adi": "Prim Affı Genelgesi 2023",
+        "url_slug": "prim-affi-genelgesi-2023",
+        "belge_turu": "Genelge",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-03-01T10:00:00Z",
+    });
+
+    let state = common::state(repos.clone());
+    let document_stream = state.document_stream.clone();
+    let urls = state.urls.clone();
+    let state = web::Data::new(state);
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(state.clone())
+            .wrap(from_fn(middleware::api_key_auth))
+            .wrap(from_fn(middleware::request_context))
+            .configure(routes::configure_routes)
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let base = format!("http://{}/api/v1/stream/documents", server.addrs()[0]);
+    let server = server.run();
+    let handle = server.handle();
+    actix_web::rt::spawn(server);
+    let client = reqwest::Client::new();
+
+    // İlk yoklama yalnızca imleçleri kurar; mevcut belgeler yayınlanmaz
+    let mut poller = Poller::default();
+    assert_eq!(poller.poll(&repos, &corpora, &urls, &document_stream).await.unwrap(), 0);
+
+    let mut response = client
+        .get(format!("{}?corpus=mevzuat&kurum_id={}", base, sgk_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    assert_eq!(response.headers()["cache-control"], "no-cache");
+    let mut buffer = String::new();
+    let first = response.chunk().await.unwrap().unwrap();
+    buffer.push_str(std::str::from_utf8(&first).unwrap());
+    assert!(buffer.starts_with("retry: 5000\n\n"));
+
+    // Filtreye uymayanlar daha eski; ilk gelen olay eşleşen belge olmalı
+    store.insert("metadata", doc! {
+        "kurum_id": ObjectId::new().to_hex(),
+        "pdf_adi": "Başka Kurum Yönetmeliği",
+        "url_slug": "baska-kurum-yonetmeligi",
+        "belge_turu": "Yönetmelik",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-06-01T10:00:00Z",
+    });
+    store.insert("yargitay", doc! {
+        "kurum_id": &sgk_id,
+        "pdf_adi": "Yargıtay 10. HD 2024/1 E.",
+        "url_slug": "yargitay-10-hd-2024-1",
+        "belge_turu": "Karar",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-06-02T10:00:00Z",
+    });
+    let new_id = store.insert("metadata", doc! {
+        "kurum_id": &sgk_id,
+        "pdf_adi": "Prim Affı Uygulama Genelgesi",
+        "url_slug": "prim-affi-uygulama-genelgesi",
+        "belge_turu": "Genelge",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-06-03T10:00:00Z",
+    });
+    assert_eq!(poller.poll(&repos, &corpora, &urls, &document_stream).await.unwrap(), 3);
+    assert_eq!(poller.poll(&repos, &corpora, &urls, &document_stream).await.unwrap(), 0);
+
+    let frames = read_events(&mut response, &mut buffer, 1).await;
+    let (id, event, data) = &frames[0];
+    let created: DateTime<Utc> = "2024-06-03T10:00:00Z".parse().unwrap();
+    assert_eq!(*id, event_id(created, &new_id));
+    assert_eq!(parse_event_id(id), Some((created, new_id.clone())));
+    assert_eq!(event, "document.created");
+    assert_eq!(data["corpus"], "mevzuat");
+    assert_eq!(data["id"], new_id.as_str());
+    assert_eq!(data["pdf_adi"], "Prim Affı Uygulama Genelgesi");
+    assert_eq!(data["kurum_adi"], "Sosyal Güvenlik Kurumu");
+    assert_eq!(data["url"], "https://mevzuatgpt.org/belge/prim-affi-uygulama-genelgesi");
+    drop(response);
+
+    // Yeniden bağlanan istemciye kaçırdığı belgeler eskiden yeniye gönderilir
+    let last_event_id = event_id("2024-03-01T10:00:00Z".parse().unwrap(), &existing_id);
+    let mut response = client
+        .get(&base)
+        .header("Last-Event-ID", &last_event_id)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    let mut buffer = String::new();
+    let frames = read_events(&mut response, &mut buffer, 3).await;
+    let slugs: Vec<&str> = frames.iter().map(|(_, _, data)| data["url_slug"].as_str().unwrap()).collect();
+    assert_eq!(slugs, ["baska-kurum-yonetmeligi", "yargitay-10-hd-2024-1", "prim-affi-uygulama-genelgesi"]);
+    assert_eq!(frames[1].2["corpus"], "yargitay");
+    drop(response);
+
+    // Başlık gönderemeyen istemciler sorgu parametresini kullanır; filtreler kaçırılanlara da uygulanır
+    let mut response = client
+        .get(format!("{}?corpus=yargitay&last_event_id={}", base, last_event_id))
+        .send()
+        .await
+        .unwrap();
+    let mut buffer = String::new();
+    let frames = read_events(&mut response, &mut buffer, 1).await;
+    assert_eq!(frames[0].2["url_slug"], "yargitay-10-hd-2024-1");
+    drop(response);
+
+    let response = client.get(format!("{}?corpus=yok", base)).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 404);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "CORPUS_NOT_FOUND");
+
+    let response = client.get(&base).header("Last-Event-ID", "bozuk").send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 400);
+
+    handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn long_gaps_are_polled_in_pages_and_replayed_with_a_reset_event() {
+    let store = Arc::new(MemoryRepository::new());
+    let repos = Repositories::memory(store.clone());
+    let corpora = CorpusRegistry::builtin();
+    let first_id = store.insert("metadata", doc! {
+        "pdf_adi": "İlk Genelge",
+        "url_slug": "ilk-genelge",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-03-01T10:00:00.000Z",
+    });
+    let state = common::state(repos.clone());
+    let document_stream = state.document_stream.clone();
+    let urls = state.urls.clone();
+    let mut poller = Poller::default();
+    assert_eq!(poller.poll(&repos, &corpora, &urls, &document_stream).await.unwrap(), 0);
+
+    // Yoklama sayfasından (200) ve kaçırılanlar sınırından (500) fazla belge
+    for i in 0..620 {
+        store.insert("metadata", doc! {
+            "pdf_adi": format!("Genelge {}", i),
+            "url_slug": format!("genelge-{}", i),
+            "status": "aktif",
+            "olusturulma_tarihi": format!("2024-06-01T{:02}:{:02}:00.000Z", i / 60, i % 60),
+        });
+    }
+    assert_eq!(poller.poll(&repos, &corpora, &urls, &document_stream).await.unwrap(), 620);
+    assert_eq!(poller.poll(&repos, &corpora, &urls, &document_stream).await.unwrap(), 0);
+
+    let state = web::Data::new(state);
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(state.clone())
+            .wrap(from_fn(middleware::api_key_auth))
+            .wrap(from_fn(middleware::request_context))
+            .configure(routes::configure_routes)
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let base = format!("http://{}/api/v1/stream/documents", server.addrs()[0]);
+    let server = server.run();
+    let handle = server.handle();
+    actix_web::rt::spawn(server);
+    let client = reqwest::Client::new();
+
+    // İlk 500 belge gönderilir, ardından reset olayı gelir ve bağlantı kapanır
+    let since = event_id("2024-03-01T10:00:00Z".parse().unwrap(), &first_id);
+    let mut response = client.get(&base).header("Last-Event-ID", &since).send().await.unwrap();
+    let mut buffer = String::new();
+    let frames = read_events(&mut response, &mut buffer, 501).await;
+    assert_eq!(frames[0].2["url_slug"], "genelge-0");
+    assert_eq!(frames[499].2["url_slug"], "genelge-499");
+    let (reset_id, event, data) = &frames[500];
+    assert_eq!(event, "reset");
+    assert_eq!(reset_id, &frames[499].0);
+    assert_eq!(data["resume_from"], frames[499].0.as_str());
+    assert!(response.chunk().await.unwrap().is_none());
+
+    // reset olayının id'siyle yeniden bağlanan istemci kalanları alır
+    let mut response = client.get(&base).header("Last-Event-ID", reset_id).send().await.unwrap();
+    let mut buffer = String::new();
+    let frames = read_events(&mut response, &mut buffer, 120).await;
+    assert_eq!(frames[0].2["url_slug"], "genelge-500");
+    assert_eq!(frames[119].2["url_slug"], "genelge-619");
+    assert!(!buffer.contains("event: reset"));
+    drop(response);
+
+    handle.stop(false).await;
+}
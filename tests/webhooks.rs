@@ -0,0 +1,289 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use actix_web::{test, web, App, HttpRequest, HttpResponse, HttpServer};
+use api_mevzuatgpt::auth::Scope;
+use api_mevzuatgpt::config::PublicUrls;
+use api_mevzuatgpt::corpus::CorpusRegistry;
+use api_mevzuatgpt::repository::{MemoryRepository, Repositories, ScrapedAnnouncementWrite, WebhookWrite};
+use api_mevzuatgpt::utils::format_timestamp;
+use api_mevzuatgpt::webhooks::{self, DeliveryReport, ScanReport, WebhookConfig};
+use chrono::{Duration, Utc};
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::{json, Value};
+
+#[macro_use]
+mod common;
+use common::{key, state, status_and_body, with_key};
+
+// Webhook abonelikleri: olay taraması, filtreler, imzalı teslimat, yeniden deneme ve tekrar gönderim
+
+// Alıcının kaydettiği istek: (path, signature, event, delivery, gövde)
+type Received = Arc<Mutex<Vec<(String, String, String, String, String)>>>;
+
+async fn receive(req: HttpRequest, body: String, received: web::Data<Received>) -> HttpResponse {
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string()
+    };
+    received.lock().unwrap().push((
+        req.path().to_string(),
+        header(webhooks::SIGNATURE_HEADER),
+        header(webhooks::EVENT_HEADER),
+        header(webhooks::DELIVERY_HEADER),
+        body,
+    ));
+    if req.path() == "/hook" {
+        HttpResponse::NoContent().finish()
+    } else {
+        HttpResponse::InternalServerError().body("bakımda")
+    }
+}
+
+#[actix_web::test]
+async fn new_records_are_delivered_signed_retried_and_replayed() {
+    let received: Received = Arc::new(Mutex::new(Vec::new()));
+    let receiver_state = web::Data::new(received.clone());
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(receiver_state.clone())
+            .default_service(web::post().to(receive))
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let base = format!("http://{}", server.addrs()[0]);
+    let server = server.run();
+    let handle = server.handle();
+    actix_web::rt::spawn(server);
+
+    let store = Arc::new(MemoryRepository::new());
+    let repos = Repositories::memory(store.clone());
+    let corpora = CorpusRegistry::builtin();
+    let sgk_id = ObjectId::new().to_hex();
+    store.insert("kurumlar", doc! { "_id": ObjectId::parse_str(&sgk_id).unwrap(), "kurum_adi": "Sosyal Güvenlik Kurumu" });
+    store.insert("metadata", doc! {
+        "kurum_id": &sgk_id,
+        "pdf_adi": "Eski Emeklilik Genelgesi",
+        "url_slug": "eski-emeklilik-genelgesi",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-03-01",
+    });
+
+    let admin_key = key(&store, "yonetici", &[Scope::Admin]).await;
+    let app = app!(state(repos.clone()));
+    let post = |uri: &str, body: Value| with_key(test::TestRequest::post().uri(uri), &admin_key).set_json(body).to_request();
+
+    let req = post("/api/admin/webhooks", json!({ "url": "ftp://ornek", "events": ["document.deleted"] }));
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 422);
+    let fields: Vec<&str> = body["error"]["fields"].as_array().unwrap().iter().map(|f| f["field"].as_str().unwrap()).collect();
+    assert_eq!(fields, ["url", "events"]);
+
+    let req = post(
+        "/api/admin/webhooks",
+        json!({ "url": format!("{}/hook", base), "filters": { "kurum_id": sgk_id, "keywords": ["EMEKLİLİK"] } }),
+    );
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 201);
+    let hook_id = body["data"]["id"].as_str().unwrap().to_string();
+    let secret = body["data"]["secret"].as_str().unwrap().to_string();
+    assert!(secret.starts_with("whsec_"));
+    assert_eq!(body["data"]["events"], json!(["document.created", "announcement.created"]));
+
+    let req = post(
+        "/api/admin/webhooks",
+        json!({ "url": format!("{}/bakim", base), "events": ["announcement.created"] }),
+    );
+    let (_, body) = status_and_body(test::call_service(&app, req).await).await;
+    let failing_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    // Secret yalnızca oluşturmada döner
+    let req = with_key(test::TestRequest::get().uri(&format!("/api/admin/webhooks/{}", hook_id)), &admin_key)
+        .to_request();
+    let (_, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert!(body["data"]["secret"].is_null());
+
+    // İlk tarama yalnızca imleci kurar; mevcut arşiv gönderilmez
+    assert_eq!(webhooks::scan(&repos, &corpora, &PublicUrls::default()).await.unwrap(), ScanReport::default());
+
+    store.insert("metadata", doc! {
+        "kurum_id": &sgk_id,
+        "pdf_adi": "Emeklilik İşlemleri Genelgesi",
+        "url_slug": "emeklilik-islemleri-genelgesi",
+        "belge_turu": "Genelge",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-06-01T10:00:00Z",
+    });
+    store.insert("metadata", doc! {
+        "kurum_id": ObjectId::new().to_hex(),
+        "pdf_adi": "Emeklilik Yönetmeliği",
+        "url_slug": "emeklilik-yonetmeligi",
+        "status": "aktif",
+        "olusturulma_tarihi": "2024-06-01T10:00:00Z",
+    });
+    repos
+        .scraped_announcements
+        .store(&[ScrapedAnnouncementWrite {
+            kurum_id: sgk_id.clone(),
+            baslik: "Emeklilik başvuruları uzatıldı".to_string(),
+            link: "https://www.sgk.gov.tr/Duyuru/Detay/42".to_string(),
+            tarih: None,
+            first_seen_at: format_timestamp(Utc::now()),
+        }])
+        .await
+        .unwrap();
+
+    // Diğer kurumun belgesi filtreye takılır; duyuru iki aboneliğe gider
+    assert_eq!(webhooks::scan(&repos, &corpora, &PublicUrls::default()).await.unwrap(), ScanReport { events: 3, enqueued: 3 });
+    assert_eq!(webhooks::scan(&repos, &corpora, &PublicUrls::default()).await.unwrap(), ScanReport::default());
+
+    let config = WebhookConfig {
+        max_attempts: 3,
+        ..WebhookConfig::default()
+    };
+    let client = webhooks::client(&config);
+    let now = Utc::now();
+    let report = webhooks::deliver_due(&repos, &client, &config, now).await.unwrap();
+    assert_eq!(report, DeliveryReport { delivered: 2, retried: 1, failed: 0 });
+
+    {
+        let received = received.lock().unwrap();
+        let hook: Vec<_> = received.iter().filter(|request| request.0 == "/hook").collect();
+        assert_eq!(hook.len(), 2);
+        for (_, signature, event, delivery, body) in &hook {
+            let (t, v1) = signature.split_once(",v1=").unwrap();
+            let t: i64 = t.trim_start_matches("t=").parse().unwrap();
+            assert_eq!(v1, &webhooks::sign(&secret, t, body));
+            assert_ne!(v1, &webhooks::sign("whsec_baska", t, body));
+            let payload: Value = serde_json::from_str(body).unwrap();
+            assert_eq!(&payload["type"], event.as_str());
+            assert!(payload["id"].as_str().unwrap().starts_with("evt_"));
+            assert!(!delivery.is_empty());
+        }
+        let document: Value = serde_json::from_str(&hook[0].4).unwrap();
+        assert_eq!(document["data"]["url"], "https://mevzuatgpt.org/belge/emeklilik-islemleri-genelgesi");
+        assert_eq!(document["data"]["kurum_adi"], "Sosyal Güvenlik Kurumu");
+        assert_eq!(hook[1].2, "announcement.created");
+    }
+
+    // Başarısız teslimat bekleme süresi dolmadan denenmez, deneme hakkı bitince failed olur
+    assert_eq!(webhooks::deliver_due(&repos, &client, &config, now).await.unwrap(), DeliveryReport::default());
+    let report = webhooks::deliver_due(&repos, &client, &config, now + Duration::seconds(31)).await.unwrap();
+    assert_eq!(report, DeliveryReport { delivered: 0, retried: 1, failed: 0 });
+    let report = webhooks::deliver_due(&repos, &client, &config, now + Duration::seconds(92)).await.unwrap();
+    assert_eq!(report, DeliveryReport { delivered: 0, retried: 0, failed: 1 });
+
+    let req = with_key(test::TestRequest::get().uri(&format!("/api/admin/webhooks/{}/deliveries?status=failed", failing_id)), &admin_key)
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["count"], 1);
+    let failed = &body["data"][0];
+    assert_eq!(failed["attempts"], 3);
+    assert_eq!(failed["response_status"], 500);
+    assert_eq!(failed["error"], "HTTP 500: bakımda");
+    assert!(failed["next_attempt_at"].is_null());
+    assert_eq!(failed["payload"]["data"]["baslik"], "Emeklilik başvuruları uzatıldı");
+    let failed_id = failed["id"].as_str().unwrap().to_string();
+    let event_id = failed["event_id"].as_str().unwrap().to_string();
+
+    // Adres düzeltilip teslimat tekrar gönderilir: aynı olay, aynı gövde, yeni teslimat
+    let req = with_key(test::TestRequest::patch().uri(&format!("/api/admin/webhooks/{}", failing_id)), &admin_key)
+        .set_json(json!({ "url": format!("{}/hook", base) }))
+        .to_request();
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["data"]["events"], json!(["announcement.created"]));
+
+    let req = post(&format!("/api/admin/webhooks/deliveries/{}/replay", failed_id), json!({}));
+    let (status, body) = status_and_body(test::call_service(&app, req).await).await;
+    assert_eq!(status, 202);
+    assert_eq!(body["data"]["status"], "pending");
+    assert_eq!(body["data"]["replay_of"], failed_id.as_str());
+    assert_eq!(body["data"]["event_id"], event_id.as_str());
+
+    let report = webhooks::deliver_due(&repos, &client, &config, Utc::now()).await.unwrap();
+    assert_eq!(report, DeliveryReport { delivered: 1, retried: 0, failed: 0 });
+    let last = received.lock().unwrap().last().cloned().unwrap();
+    assert_eq!(last.0, "/hook");
+    assert!(last.4.contains(&event_id));
+
+    let req = post("/api/admin/webhooks/deliveries/000000000000000000000000/replay", json!({}));
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 404);
+
+    // Abonelik silinince teslimat kaydı da silinir
+    let req = with_key(test::TestRequest::delete().uri(&format!("/api/admin/webhooks/{}", failing_id)), &admin_key)
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 200);
+    assert_eq!(store.documents("webhook_deliveries").len(), 2);
+    let req = with_key(test::TestRequest::get().uri(&format!("/api/admin/webhooks/{}/deliveries", failing_id)), &admin_key)
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 404);
+
+    // Yönetim anahtarı olmadan erişilemez
+    let req = test::TestRequest::get().uri("/api/admin/webhooks").to_request();
+    assert_eq!(test::call_service(&app, req).await.status().as_u16(), 401);
+
+    handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn bursts_larger_than_a_scan_page_are_not_dropped() {
+    let store = Arc::new(MemoryRepository::new());
+    let repos = Repositories::memory(store.clone());
+    let corpora = CorpusRegistry::builtin();
+    let urls = PublicUrls::default();
+    repos
+        .webhooks
+        .insert(&WebhookWrite {
+            url: "http://127.0.0.1:9/hook".to_string(),
+            secret: webhooks::generate_secret(),
+            events: vec!["document.created".to_string(), "announcement.created".to_string()],
+            aktif: true,
+            updated_at: format_timestamp(Utc::now()),
+            ..WebhookWrite::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(webhooks::scan(&repos, &corpora, &urls).await.unwrap(), ScanReport::default());
+
+    // Bir sayfadan (200) fazla kayıt; 250'si aynı zamanda oluşturulmuş
+    for i in 0..450 {
+        let time = match i {
+            0..250 => "2024-06-01T10:00:00.000Z".to_string(),
+            _ => format!("2024-06-02T10:{:02}:{:02}.000Z", i / 60, i % 60),
+        };
+        store.insert("metadata", doc! {
+            "pdf_adi": format!("Genelge {}", i),
+            "url_slug": format!("genelge-{}", i),
+            "status": "aktif",
+            "olusturulma_tarihi": time,
+        });
+    }
+    let items: Vec<ScrapedAnnouncementWrite> = (0..230)
+        .map(|i| ScrapedAnnouncementWrite {
+            kurum_id: ObjectId::new().to_hex(),
+            baslik: format!("Duyuru {}", i),
+            link: format!("https://www.sgk.gov.tr/Duyuru/Detay/{}", i),
+            tarih: None,
+            first_seen_at: "2024-06-03T08:00:00.000Z".to_string(),
+        })
+        .collect();
+    repos.scraped_announcements.store(&items).await.unwrap();
+
+    assert_eq!(webhooks::scan(&repos, &corpora, &urls).await.unwrap(), ScanReport { events: 680, enqueued: 680 });
+    assert_eq!(webhooks::scan(&repos, &corpora, &urls).await.unwrap(), ScanReport::default());
+
+    let deliveries = store.documents("webhook_deliveries");
+    let slugs: HashSet<String> = deliveries
+        .iter()
+        .filter_map(|delivery| {
+            let payload: Value = serde_json::from_str(delivery.get_str("payload").ok()?).ok()?;
+            payload["data"]["url_slug"].as_str().map(str::to_string)
+        })
+        .collect();
+    assert_eq!(slugs.len(), 450);
+}